@@ -0,0 +1,240 @@
+//! Assignments
+//!
+//! `rust-learn assignment submit week-3` hands work in through git. A
+//! `RESULTS.toml` listing the lessons, sections and exercises the learner
+//! has completed is written into the exercises directory (`exercises`, or
+//! `--dir`); everything in that directory is committed, in the repository it
+//! is in (the rust-learn checkout, usually) or, outside any, in one started
+//! there; and the commit is tagged `week-3-<hash>`, the hash being of the
+//! results, so a teacher can tell two submissions with the same results
+//! apart from two different ones.
+//!
+//! The teacher's remote comes from config.toml; with it, `--push` (or `push =
+//! true`) sends the branch and the tag there:
+//!
+//! ```toml
+//! [assignment]
+//! remote = "https://git.school.example/alice/rust-learn.git"
+//! push = true
+//! ```
+//!
+//! git itself does the work (`toolchain::git`), so its credentials and
+//! settings apply as usual.
+
+use rust_learn_core::progress::{self, Progress};
+use rust_learn_core::random::fnv1a;
+use rust_learn_core::{output, settings, toolchain};
+use std::fs;
+use std::path::Path;
+use std::time::SystemTime;
+
+const RESULTS_FILE: &str = "RESULTS.toml";
+
+/// Commit and tag the exercises in `dir` for assignment `id`, pushing them
+/// too when asked to on the command line or in config.toml
+pub fn submit(id: &str, dir: &Path, push: bool) -> Result<(), String> {
+    if !is_valid_id(id) {
+        return Err(format!(
+            "'{}' cannot be an assignment id: use letters, digits, '.', '-' and '_'",
+            id
+        ));
+    }
+    if !dir.is_dir() {
+        return Err(format!(
+            "there are no exercises at {} (--dir picks another directory)",
+            dir.display()
+        ));
+    }
+    let config = settings::get().table("assignment");
+    let remote = config.and_then(|config| config.str("remote"));
+    let push = push || config.and_then(|config| config.bool("push")) == Some(true);
+    if push && remote.is_none() {
+        return Err("nothing to push to: set `remote` under [assignment] in config.toml".into());
+    }
+
+    let completed = completed_keys(&Progress::load());
+    let hash = format!("{:016x}", fnv1a(completed.join("\n").as_bytes()));
+    let tag = format!("{}-{}", id, &hash[..12]);
+    let results = results_text(id, &hash, &completed, SystemTime::now());
+    fs::write(dir.join(RESULTS_FILE), results)
+        .map_err(|error| format!("cannot write {}: {}", RESULTS_FILE, error))?;
+
+    // Inside a repository already (a rust-learn checkout, say), the commit
+    // goes there and takes in only `dir`; a new one would nest in it
+    let top = toolchain::git(dir, &["rev-parse", "--show-toplevel"])
+        .map_err(|error| format!("could not run git (is it installed?): {}", error))?;
+    if !top.success {
+        git(dir, &["init", "--quiet"])?;
+        println!(
+            "{}",
+            output::dim(&format!("Started a git repository in {}", dir.display()))
+        );
+    }
+    git(dir, &["add", "--all", "--", "."])?;
+    let message = format!("Submit {} (results {})", id, &hash[..12]);
+    git_as_learner(
+        dir,
+        &[
+            "commit",
+            "--quiet",
+            "--allow-empty",
+            "-m",
+            &message,
+            "--",
+            ".",
+        ],
+    )?;
+    let tag_ref = format!("refs/tags/{}", tag);
+    if git(dir, &["rev-parse", "--verify", "--quiet", &tag_ref]).is_ok() {
+        println!(
+            "{}",
+            output::dim(&format!(
+                "{} was already tagged; the new commit is not",
+                tag
+            ))
+        );
+    } else {
+        git_as_learner(dir, &["tag", "-a", &tag, "-m", &message])?;
+    }
+    println!(
+        "Submitted {} as {} ({} lessons, sections and exercises completed)",
+        id,
+        output::bold(&tag),
+        completed.len()
+    );
+
+    if let Some(remote) = remote.filter(|_| push) {
+        git(dir, &["push", "--quiet", remote, "HEAD", &tag])?;
+        println!("Pushed to {}", remote);
+    }
+    Ok(())
+}
+
+/// Safe in a tag name and a file name alike
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && !id.starts_with(['.', '-'])
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+}
+
+/// Every lesson, section and exercise completed, with when, as `key = time`
+/// lines in key order
+fn completed_keys(progress: &Progress) -> Vec<String> {
+    let mut keys = Vec::new();
+    for (key, time) in progress.completed_under("exercise::") {
+        keys.push(format!(
+            "\"{}\" = {}",
+            key,
+            progress::format_timestamp(time)
+        ));
+    }
+    for lesson in rust_learn_content::registry::LESSONS {
+        let lesson_keys = std::iter::once(lesson.name.to_string()).chain(
+            lesson
+                .sections
+                .iter()
+                .map(|section| format!("{}::{}", lesson.name, section.name)),
+        );
+        for key in lesson_keys {
+            if let Some(time) = progress.completed_at(&key) {
+                keys.push(format!(
+                    "\"{}\" = {}",
+                    key,
+                    progress::format_timestamp(time)
+                ));
+            }
+        }
+    }
+    keys.sort();
+    keys
+}
+
+fn results_text(id: &str, hash: &str, completed: &[String], now: SystemTime) -> String {
+    let mut text = format!(
+        "# Written by `rust-learn assignment submit`\n\
+         assignment = \"{}\"\n\
+         submitted = {}\n\
+         results = \"{}\"\n\n\
+         [completed]\n",
+        id,
+        progress::format_timestamp(now),
+        hash
+    );
+    for line in completed {
+        text.push_str(line);
+        text.push('\n');
+    }
+    text
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = toolchain::git(dir, args)
+        .map_err(|error| format!("could not run git (is it installed?): {}", error))?;
+    if !output.success {
+        let stderr = output.stderr.trim();
+        return Err(format!("git {} failed: {}", args[0], stderr));
+    }
+    Ok(output.stdout)
+}
+
+/// `git` with the learner as author, when git has no name configured
+fn git_as_learner(dir: &Path, args: &[&str]) -> Result<String, String> {
+    if git(dir, &["config", "user.name"]).is_ok() {
+        return git(dir, args);
+    }
+    let name = settings::learner();
+    let identity = [
+        format!("user.name={}", name),
+        format!(
+            "user.email={}@rust-learn.invalid",
+            name.to_lowercase().replace(' ', ".")
+        ),
+    ];
+    let mut with_identity: Vec<&str> = vec!["-c", &identity[0], "-c", &identity[1]];
+    with_identity.extend(args);
+    git(dir, &with_identity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_learn_core::store;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_results() {
+        assert!(is_valid_id("week-3_v2.1"));
+        assert!(!is_valid_id("../week"));
+        assert!(!is_valid_id("-f"));
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        let completed = vec!["\"loops\" = 2026-10-16T14:03:27Z".to_string()];
+        assert_eq!(
+            results_text("week-3", "00ff", &completed, UNIX_EPOCH),
+            "# Written by `rust-learn assignment submit`\n\
+             assignment = \"week-3\"\n\
+             submitted = 1970-01-01T00:00:00Z\n\
+             results = \"00ff\"\n\n\
+             [completed]\n\
+             \"loops\" = 2026-10-16T14:03:27Z\n"
+        );
+
+        let file =
+            std::env::temp_dir().join(format!("rust-learn-assignment-{}.json", std::process::id()));
+        let mut progress = Progress::with_store(Box::new(store::JsonFile::new(file)));
+        progress.mark_complete("ownership::slices");
+        progress.mark_complete("exercise::week-3/ownership1");
+        let completed = completed_keys(&progress);
+        let keys: Vec<&str> = completed
+            .iter()
+            .map(|line| line.split(" = ").next().unwrap())
+            .collect();
+        assert_eq!(
+            keys,
+            ["\"exercise::week-3/ownership1\"", "\"ownership::slices\""]
+        );
+    }
+}
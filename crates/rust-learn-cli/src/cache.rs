@@ -0,0 +1,63 @@
+//! `rust-learn cache`: how much space builds, crash reports and the run
+//! history take, and `cache gc` to remove what is old by the `[cache]` policy
+//! in config.toml (`rust_learn_core::gc`). The same collection runs on its own
+//! once a day at startup, unless `auto = false`. `cache gc --dry-run` lists
+//! what it would remove and leaves it all in place.
+
+use rust_learn_core::gc::{self, Policy};
+use rust_learn_core::memory::format_bytes;
+use rust_learn_core::output;
+use std::time::SystemTime;
+
+/// Each place with its size, and the policy that applies
+pub fn status() {
+    for usage in gc::usage() {
+        println!(
+            "{:<14} {:>10}  {}",
+            usage.name,
+            format_bytes(usage.bytes),
+            output::dim(&usage.path.display().to_string())
+        );
+    }
+    let policy = Policy::configured();
+    let note = format!(
+        "Policy: builds and crash reports older than {} days go, then the oldest builds over {}; runs older than {} days leave the history. Automatic: {}",
+        policy.max_age.as_secs() / 86_400,
+        format_bytes(policy.max_size),
+        policy.history_age.as_secs() / 86_400,
+        if policy.auto { "daily" } else { "off" },
+    );
+    println!("\n{}", output::dim(&note));
+}
+
+/// Remove what the policy calls old, and say what went; with `dry_run`,
+/// what would go
+pub fn gc(dry_run: bool) -> Result<(), String> {
+    let (report, transaction) = gc::prepare(&Policy::configured(), SystemTime::now())
+        .map_err(|error| format!("Cleaning up stopped: {}", error))?;
+    if transaction.is_empty() {
+        println!("Nothing is old enough to remove");
+        return Ok(());
+    }
+    crate::carry_out(transaction, dry_run).map_err(|error| format!("Cleaning up {}", error))?;
+    let verb = if dry_run { "Would free" } else { "Freed" };
+    let mut summary = format!("\n{} {}", verb, format_bytes(report.freed()));
+    if report.runs > 0 {
+        summary += &format!(", dropping {} old runs from the history", report.runs);
+    }
+    println!("{}", summary);
+    Ok(())
+}
+
+/// The daily check at startup; says something only when it removed files
+pub fn auto() {
+    if let Some(report) = gc::auto()
+        && !report.is_empty()
+    {
+        let note = format!(
+            "Removed {} of old builds and history (`rust-learn cache` shows what is kept)",
+            format_bytes(report.freed())
+        );
+        eprintln!("{}", output::dim(&note));
+    }
+}
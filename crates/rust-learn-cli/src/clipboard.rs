@@ -0,0 +1,80 @@
+//! Clipboard
+//!
+//! `show --copy` and `solutions gallery --copy` put code on the clipboard,
+//! so it can be pasted into the playground or an editor without selecting
+//! it across wrapped lines. Built
+//! with the `clipboard` feature, the system clipboard is set directly
+//! (arboard); on Linux it then lasts as long as a clipboard manager keeps it.
+//! Otherwise, or when that fails (over SSH, say), the terminal is asked to
+//! do it with an OSC 52 escape, which most terminals (and tmux, with
+//! `set-clipboard on`) accept.
+
+use std::io::{self, IsTerminal, Write};
+
+/// Copy `text`, saying how
+pub fn copy(text: &str) -> Result<&'static str, String> {
+    #[cfg(feature = "clipboard")]
+    {
+        let copied = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+        match copied {
+            Ok(()) => return Ok("the clipboard"),
+            Err(error) => tracing::debug!(%error, "no system clipboard, trying OSC 52"),
+        }
+    }
+    if !io::stdout().is_terminal() {
+        return Err("there is no clipboard to copy to, and stdout is not a terminal".to_string());
+    }
+    let mut stdout = io::stdout();
+    stdout
+        .write_all(osc52(text, std::env::var_os("TMUX").is_some()).as_bytes())
+        .and_then(|()| stdout.flush())
+        .map_err(|error| error.to_string())?;
+    Ok("the terminal's clipboard (OSC 52)")
+}
+
+/// The escape that sets the clipboard to `text`; inside tmux it is wrapped
+/// so tmux passes it on
+fn osc52(text: &str, tmux: bool) -> String {
+    let sequence = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", sequence.replace('\x1b', "\x1b\x1b"))
+    } else {
+        sequence
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let group = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |group, (index, &byte)| {
+                group | u32::from(byte) << (16 - 8 * index)
+            });
+        for index in 0..4 {
+            if index <= chunk.len() {
+                let digit = (group >> (18 - 6 * index)) & 0x3f;
+                encoded.push(char::from(ALPHABET[digit as usize]));
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_osc52() {
+        assert_eq!(base64(b"fn"), "Zm4=");
+        assert_eq!(base64(b"main"), "bWFpbg==");
+        assert_eq!(base64(b"let"), "bGV0");
+        assert_eq!(osc52("let", false), "\x1b]52;c;bGV0\x07");
+        assert_eq!(osc52("let", true), "\x1bPtmux;\x1b\x1b]52;c;bGV0\x07\x1b\\");
+    }
+}
@@ -0,0 +1,77 @@
+//! `rust-learn exercise reset <name>` puts an exercise back the way it was
+//! shipped, and `exercise restore <name>` brings the newest attempt back, or
+//! the one taken `--from` a time `exercise backups <name>` lists. Each first
+//! copies what is in the file to a backup (`rust_learn_core::backups`), so
+//! an accidental reset costs nothing; `--dry-run` shows both steps first.
+
+use crate::verify;
+use rust_learn_core::backups;
+use rust_learn_core::output;
+use std::path::Path;
+use std::time::SystemTime;
+
+fn root() -> Result<std::path::PathBuf, String> {
+    backups::root().ok_or_else(|| "no data directory (set RUST_LEARN_DATA_DIR or HOME)".into())
+}
+
+/// Back up exercise `name` in `dir`, then put the shipped version back
+pub fn reset(dir: &Path, name: &str, dry_run: bool) -> Result<(), String> {
+    let exercise = verify::find(dir, name)?;
+    let transaction = backups::reset(&root()?, &exercise, SystemTime::now())?;
+    if transaction.is_empty() {
+        println!("{} is already as shipped", exercise.id());
+        return Ok(());
+    }
+    crate::carry_out(transaction, dry_run)?;
+    if !dry_run {
+        let note = format!(
+            "`rust-learn exercise restore {}` brings your attempt back",
+            exercise.id()
+        );
+        println!(
+            "Reset {}. {}",
+            output::bold(&exercise.id()),
+            output::dim(&note)
+        );
+    }
+    Ok(())
+}
+
+/// Back up exercise `name` in `dir`, then put the backup `from` back, or
+/// the newest one
+pub fn restore(dir: &Path, name: &str, from: Option<&str>, dry_run: bool) -> Result<(), String> {
+    let exercise = verify::find(dir, name)?;
+    let transaction = backups::restore(&root()?, &exercise, from, SystemTime::now())?;
+    if transaction.is_empty() {
+        println!("{} already holds that attempt", exercise.id());
+        return Ok(());
+    }
+    crate::carry_out(transaction, dry_run)?;
+    if !dry_run {
+        println!("Restored {}", output::bold(&exercise.id()));
+    }
+    Ok(())
+}
+
+/// The backups of exercise `name` in `dir`, oldest first
+pub fn list(dir: &Path, name: &str) -> Result<(), String> {
+    let exercise = verify::find(dir, name)?;
+    let backups = backups::list_in(&root()?, &exercise);
+    if backups.is_empty() {
+        println!("{} has no backups yet", exercise.id());
+        return Ok(());
+    }
+    for backup in &backups {
+        println!(
+            "{:<20} {}",
+            backup.stamp,
+            output::dim(&backup.path.display().to_string())
+        );
+    }
+    let note = format!(
+        "`rust-learn exercise restore {} --from <time>` puts one back",
+        exercise.id()
+    );
+    println!("\n{}", output::dim(&note));
+    Ok(())
+}
@@ -0,0 +1,93 @@
+//! `rust-learn explain E0502`: rustc's explanation of an error code
+//! (`rust_learn_core::error_codes`), with its examples highlighted, followed
+//! by what this crate has on it: the lesson sections that teach it
+//! (`registry::ERROR_CODES`), the `show-errors` demos that fail with it and
+//! the `ref` topics that mention it.
+
+use crate::reference;
+use rust_learn_content::registry;
+use rust_learn_core::error_codes::{self, Block};
+use rust_learn_core::{highlight, output};
+
+pub fn run(code: &str) -> Result<(), String> {
+    let code = error_codes::normalize(code)
+        .ok_or_else(|| format!("'{}' is not an error code, like E0502", code))?;
+    let text = error_codes::explain(&code)
+        .map_err(|error| format!("Cannot run rustc: {}", error))?
+        .ok_or_else(|| format!("rustc does not know {}", code))?;
+
+    println!("{}\n", output::bold(&format!("error[{}]", code)));
+    let mut links = Vec::new();
+    for block in error_codes::blocks(&text) {
+        match block {
+            Block::Heading(heading) => println!("{}\n", output::bold(&heading)),
+            Block::Prose(prose) => println!("{}\n", prose),
+            Block::Code(example) => {
+                for line in highlight::ansi(&example).lines() {
+                    if line.is_empty() {
+                        println!();
+                    } else {
+                        println!("    {}", line);
+                    }
+                }
+                println!();
+            }
+            Block::Link(link) => links.push(link),
+        }
+    }
+    for link in &links {
+        println!("{}", output::dim(link));
+    }
+    if !links.is_empty() {
+        println!();
+    }
+
+    // Each as the command that shows it, and what it is
+    let mut linked: Vec<(String, String)> = Vec::new();
+    for (_, keys) in registry::ERROR_CODES
+        .iter()
+        .filter(|(linked, _)| *linked == code)
+    {
+        for key in *keys {
+            let found = key.split_once("::").and_then(|(lesson, section)| {
+                let lesson = registry::find(lesson)?;
+                Some((lesson.name, lesson.section(section)?))
+            });
+            if let Some((lesson, section)) = found {
+                let command = format!("rust-learn {}::{}", lesson, section.name);
+                linked.push((command, section.title.to_string()));
+            }
+        }
+    }
+    let error = format!("error[{}]", code);
+    for demo in registry::COMPILE_ERRORS {
+        if demo
+            .expected()
+            .is_some_and(|expected| expected.starts_with(&error))
+        {
+            let command = format!("rust-learn show-errors {} {}", demo.lesson, demo.name);
+            linked.push((command, demo.title()));
+        }
+    }
+    for topic in reference::topics() {
+        if topic.intro.contains(&code)
+            || topic.entries.iter().any(|entry| entry.text.contains(&code))
+        {
+            linked.push((format!("rust-learn ref {}", topic.name), topic.title));
+        }
+    }
+    if linked.is_empty() {
+        return Ok(());
+    }
+
+    println!("{}", output::bold("In rust-learn"));
+    let width = linked
+        .iter()
+        .map(|(command, _)| command.len())
+        .max()
+        .unwrap_or(0);
+    for (command, about) in linked {
+        println!("  {:width$}  {}", command, about, width = width);
+    }
+    Ok(())
+}
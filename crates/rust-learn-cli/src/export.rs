@@ -0,0 +1,285 @@
+//! Export
+//!
+//! `rust-learn export ownership --format md -o ownership.md` turns a lesson's
+//! output into study notes. The lesson runs in a child `rust-learn run`, like
+//! in the TUI (without input or color, at the current verbosity and
+//! language), so everything it prints is captured; `to_markdown` then
+//! rewrites its layout: numbered headings become `##` headers and CAPS
+//! subheadings `###`, the `===` underlines go, bullets stay bullets, and the
+//! program's own output is fenced as code. Lessons run with a scratch data directory, so an export does
+//! not count as progress.
+//!
+//! `--format html` makes a standalone page instead (`to_html`): each section's
+//! function, cut from the lesson's source and syntax highlighted
+//! (`highlight::html`), next to the output it printed.
+
+use rust_learn_content::registry::{Lesson, Section};
+use rust_learn_core::highlight::{self, escape_html as escape};
+use rust_learn_core::output::{self, LineKind};
+use rust_learn_core::toolchain;
+use rust_learn_core::{hooks, i18n, lesson};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
+
+/// Run `lesson` (or one `section` of it) and write its output as `format`
+/// to `path`, or to stdout without one
+pub fn export(
+    lesson: &Lesson,
+    section: Option<&Section>,
+    format: &str,
+    path: Option<&Path>,
+) -> Result<(), String> {
+    let text = capture(lesson, section)?;
+    let exported = match format {
+        "md" => to_markdown(lesson.title, &text),
+        "html" => to_html(lesson, &text),
+        _ => return Err(format!("unknown format '{}'", format)),
+    };
+
+    let Some(path) = path else {
+        print!("{}", exported);
+        return Ok(());
+    };
+    fs::write(path, exported)
+        .map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Everything the lesson prints, from a child `rust-learn run`
+fn capture(lesson: &Lesson, section: Option<&Section>) -> Result<String, String> {
+    let exe =
+        std::env::current_exe().map_err(|error| format!("no path to rust-learn: {}", error))?;
+    let dir = std::env::current_dir().map_err(|error| error.to_string())?;
+    let data_dir = std::env::temp_dir().join("rust-learn-export");
+
+    let mut args = vec!["--non-interactive", "--no-color"];
+    args.extend(output::settings().verbosity.flag());
+    args.extend(["--lang", i18n::lang()]);
+    args.extend(["run", lesson.name]);
+    args.extend(section.map(|section| section.name));
+    let env = [
+        ("RUST_LEARN_DATA_DIR", data_dir.as_os_str()),
+        (hooks::DISABLE_VAR, OsStr::new("1")),
+    ];
+
+    let run = toolchain::run_with_env(exe, &dir, &args, &env)
+        .map_err(|error| format!("could not start rust-learn: {}", error))?;
+    if !run.success {
+        let mut message = format!("{} did not finish:", lesson.name);
+        for line in run
+            .stderr
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .take(6)
+        {
+            message.push_str("\n  | ");
+            message.push_str(line);
+        }
+        return Err(message);
+    }
+    Ok(run.stdout)
+}
+
+/// A lesson's plain-text output as Markdown, under `title`
+pub fn to_markdown(title: &str, text: &str) -> String {
+    let mut markdown = Markdown::default();
+    markdown.block(&format!("# {}", title));
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let kind = output::line_kind(line);
+        if !trimmed.is_empty()
+            && matches!(kind, LineKind::Text | LineKind::Comment)
+            && !is_sentence(trimmed)
+        {
+            markdown.code(line.trim_end());
+            continue;
+        }
+
+        match kind {
+            LineKind::Rule => {}
+            // `=== Ownership Learning Examples ===`, a lesson's banner or footer
+            LineKind::Heading if trimmed.starts_with("===") => {
+                markdown.block(&format!("**{}**", trimmed.trim_matches('=').trim()));
+            }
+            LineKind::Heading => {
+                markdown.block(&format!("## {}", trimmed.trim_end_matches(':')));
+            }
+            LineKind::Subheading => {
+                markdown.block(&format!("### {}", trimmed.trim_end_matches(':')));
+            }
+            _ if trimmed.is_empty() => markdown.blank(),
+            _ => markdown.line(line.trim_end()),
+        }
+    }
+    markdown.finish()
+}
+
+/// `Each value has one owner.` - prose, where program output rarely ends in
+/// a full stop
+fn is_sentence(line: &str) -> bool {
+    line.starts_with(|c: char| c.is_ascii_uppercase()) && line.ends_with('.') && line.contains(' ')
+}
+
+/// Markdown text being written: keeps one blank line between blocks and
+/// opens and closes code fences around runs of code lines
+#[derive(Default)]
+struct Markdown {
+    text: String,
+    in_code: bool,
+    /// Whether the text ends with a blank line (or is empty)
+    blank: bool,
+}
+
+impl Markdown {
+    fn code(&mut self, line: &str) {
+        if !self.in_code {
+            self.blank();
+            self.text.push_str("```text\n");
+            self.in_code = true;
+        }
+        self.text.push_str(line);
+        self.text.push('\n');
+    }
+
+    fn line(&mut self, line: &str) {
+        self.close_code();
+        self.text.push_str(line);
+        self.text.push('\n');
+        self.blank = false;
+    }
+
+    /// A line with blank lines around it, like a header
+    fn block(&mut self, line: &str) {
+        self.blank();
+        self.line(line);
+        self.blank();
+    }
+
+    fn blank(&mut self) {
+        self.close_code();
+        if !self.blank && !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        self.blank = true;
+    }
+
+    fn close_code(&mut self) {
+        if self.in_code {
+            self.text.push_str("```\n");
+            self.in_code = false;
+            self.blank = false;
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.close_code();
+        let trimmed = self.text.trim_end().len();
+        self.text.truncate(trimmed);
+        self.text.push('\n');
+        self.text
+    }
+}
+
+const STYLE: &str =
+    "body { font-family: sans-serif; margin: 2em auto; max-width: 90em; color: #222; }
+h2 { border-bottom: 1px solid #ddd; padding-bottom: .2em; }
+.pair { display: grid; grid-template-columns: 1fr 1fr; gap: 1em; }
+pre { background: #f6f8fa; padding: .8em; overflow-x: auto; font-size: 13px; margin: 0; }
+pre.output { background: #1e1e1e; color: #ddd; }";
+
+/// A standalone page: each section's function, highlighted, beside the
+/// output it printed. Output before the first section (the banner) opens the
+/// page, and a section whose function is not found shows its output alone
+pub fn to_html(lesson: &Lesson, text: &str) -> String {
+    let mut intro = String::new();
+    let mut sections: Vec<(&Section, String)> = Vec::new();
+    for line in text.lines() {
+        if let Some(section) = lesson::section_by_heading(lesson.sections, line.trim()) {
+            sections.push((section, String::new()));
+            continue;
+        }
+        // The underline of the heading just seen
+        if output::line_kind(line) == LineKind::Rule
+            && sections.last().is_some_and(|(_, output)| output.is_empty())
+        {
+            continue;
+        }
+        let output = match sections.last_mut() {
+            Some((_, output)) => output,
+            None => &mut intro,
+        };
+        output.push_str(line);
+        output.push('\n');
+    }
+
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n<h1>{}</h1>\n",
+        escape(lesson.title),
+        STYLE,
+        escape(lesson.title)
+    );
+    if !intro.trim().is_empty() {
+        html.push_str(&format!(
+            "<pre class=\"output\">{}</pre>\n",
+            escape(intro.trim_matches('\n'))
+        ));
+    }
+    for (section, output) in &sections {
+        let number = lesson
+            .sections
+            .iter()
+            .position(|other| other.name == section.name)
+            .map_or(0, |index| index + 1);
+        html.push_str(&format!(
+            "<section id=\"{}\">\n<h2>{}. {}</h2>\n<div class=\"pair\">\n",
+            section.name,
+            number,
+            escape(section.title)
+        ));
+        if let Some(source) = lesson.section_source(section) {
+            html.push_str(&format!(
+                "<pre class=\"source\"><code>{}</code></pre>\n",
+                highlight::html(source)
+            ));
+        }
+        html.push_str(&format!(
+            "<pre class=\"output\">{}</pre>\n</div>\n</section>\n",
+            escape(output.trim_matches('\n'))
+        ));
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_markdown() {
+        let text = "=== Ownership Learning Examples ===\n\n\
+                    1. Basic Ownership Rules:\n\
+                    =========================\n\
+                    s1 = hello\n\
+                    // s1 is moved\n\
+                    \n\
+                    KEY POINTS:\n\
+                    ===========\n\
+                    - Each value has one `owner`\n\
+                    Values are dropped at the end of their scope.\n";
+        assert_eq!(
+            to_markdown("Ownership", text),
+            "# Ownership\n\n\
+             **Ownership Learning Examples**\n\n\
+             ## 1. Basic Ownership Rules\n\n\
+             ```text\ns1 = hello\n// s1 is moved\n```\n\n\
+             ### KEY POINTS\n\n\
+             - Each value has one `owner`\n\
+             Values are dropped at the end of their scope.\n"
+        );
+    }
+}
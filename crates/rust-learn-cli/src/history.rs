@@ -0,0 +1,149 @@
+//! `rust-learn history`: past runs of a lesson (`rust_learn_core::history`),
+//! and what changed between two of them. Each run's transcript is saved as
+//! it finishes; `history <lesson>` lists the runs with their ids, and
+//! `history diff <id> <id>` shows the lines one run printed or was given and
+//! the other was not, with a little context around each change.
+
+use rust_learn_content::registry;
+use rust_learn_core::diff::{self, Line};
+use rust_learn_core::history::{self, Run};
+use rust_learn_core::output;
+use rust_learn_core::progress::format_timestamp;
+
+/// Unchanged lines shown before and after each change
+const CONTEXT: usize = 2;
+
+/// Save the transcript of the run that just finished as a run of `key`
+pub fn save(key: &str) {
+    let Some(transcript) = history::finish() else {
+        return;
+    };
+    // -qq prints nothing of the lesson, so there is nothing to keep
+    if transcript.trim().is_empty() {
+        return;
+    }
+    if let Err(error) = history::save(key, &transcript) {
+        let note = format!("Could not save this run to the history: {}", error);
+        eprintln!("{}", output::dim(&note));
+    }
+}
+
+/// The runs of `lesson`, or of every lesson, oldest first
+pub fn list(lesson: Option<&str>) -> Result<(), String> {
+    if let Some(lesson) = lesson
+        && registry::find(lesson).is_none()
+    {
+        return Err(format!("No lesson named '{}'", lesson));
+    }
+    let runs: Vec<Run> = history::runs()
+        .map_err(|error| format!("Cannot read the history: {}", error))?
+        .into_iter()
+        .filter(|run| lesson.is_none_or(|lesson| run.lesson() == lesson))
+        .collect();
+    if runs.is_empty() {
+        println!("No runs saved yet: every lesson run from now on is saved here");
+        return Ok(());
+    }
+
+    println!("{:<10} {:<22} {:<36} {:>6}", "ID", "WHEN", "RUN", "LINES");
+    let mut previous: Vec<(&str, &str)> = Vec::new();
+    for run in &runs {
+        let lines = history::load(run).map_or(0, |text| text.lines().count());
+        let same = previous
+            .iter()
+            .rev()
+            .find(|(key, _)| *key == run.key)
+            .is_some_and(|(_, id)| *id == run.id);
+        let line = format!(
+            "{:<10} {:<22} {:<36} {:>6}  {}",
+            run.short_id(),
+            format_timestamp(run.time),
+            run.key,
+            lines,
+            if same {
+                output::dim("same as before")
+            } else {
+                String::new()
+            }
+        );
+        println!("{}", line.trim_end());
+        previous.push((&run.key, &run.id));
+    }
+    // The latest run, and the one before it of the same lesson or section
+    let newer = runs.last().expect("not empty");
+    let older = runs.iter().rev().skip(1).find(|run| run.key == newer.key);
+    if let Some(older) = older {
+        let hint = format!(
+            "Compare two runs: rust-learn history diff {} {}",
+            older.short_id(),
+            newer.short_id()
+        );
+        println!("\n{}", output::dim(&hint));
+    }
+    Ok(())
+}
+
+/// What changed from run `old` to run `new`, given as ids or their starts
+pub fn diff(old: &str, new: &str) -> Result<(), String> {
+    let (old, new) = (history::find(old)?, history::find(new)?);
+    let read = |run: &Run| {
+        history::load(run).map_err(|error| format!("Cannot read run {}: {}", run.short_id(), error))
+    };
+    let (old_text, new_text) = (read(&old)?, read(&new)?);
+
+    println!(
+        "{}",
+        output::red(&format!(
+            "- {} {} ({})",
+            old.key,
+            format_timestamp(old.time),
+            old.short_id()
+        ))
+    );
+    println!(
+        "{}",
+        output::green(&format!(
+            "+ {} {} ({})",
+            new.key,
+            format_timestamp(new.time),
+            new.short_id()
+        ))
+    );
+    let lines = diff::lines(&old_text, &new_text);
+    if lines.iter().all(Line::is_same) {
+        println!("\nNo changes: both runs printed and read the same lines");
+        return Ok(());
+    }
+
+    // Show a line when a change is within CONTEXT lines of it
+    let near_change = |index: usize| {
+        let from = index.saturating_sub(CONTEXT);
+        let to = (index + CONTEXT + 1).min(lines.len());
+        lines[from..to].iter().any(|line| !line.is_same())
+    };
+    let mut skipped = false;
+    let (mut removed, mut added) = (0, 0);
+    for (index, line) in lines.iter().enumerate() {
+        if !near_change(index) {
+            skipped = true;
+            continue;
+        }
+        if skipped {
+            println!("{}", output::dim("@@"));
+            skipped = false;
+        }
+        match line {
+            Line::Same(text) => println!("  {}", text),
+            Line::Removed(text) => {
+                removed += 1;
+                println!("{}", output::red(&format!("- {}", text)));
+            }
+            Line::Added(text) => {
+                added += 1;
+                println!("{}", output::green(&format!("+ {}", text)));
+            }
+        }
+    }
+    println!("\n{} lines removed, {} added", removed, added);
+    Ok(())
+}
@@ -0,0 +1,268 @@
+//! Idiom Score
+//!
+//! `rust-learn dev idioms solution.rs` reads working code and says how
+//! idiomatic it looks, as a score out of 100 with the lines to look at. It
+//! never fails the code: a low score is a hint, not an error.
+//!
+//! Two things are counted. `syn` parses the file and `Visitor` finds what
+//! beginners reach for first: `.clone()` to quiet the borrow checker,
+//! `.unwrap()` and `.expect()` instead of handling the error, and `for` loops
+//! that only push, add up or count, where an iterator chain says it directly.
+//! Then, when clippy is installed, `clippy-driver` checks the same file and
+//! each of its lints counts too (`--no-clippy` skips it).
+
+use proc_macro2::Span;
+use rust_learn_core::json::Value;
+use rust_learn_core::{output, toolchain};
+use std::fs;
+use std::path::Path;
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Expr, ExprForLoop, ExprMethodCall, RangeLimits, Stmt};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Clone,
+    Unwrap,
+    Loop,
+    Lint,
+}
+
+impl Kind {
+    /// Points off the score for each one found
+    fn cost(self) -> u32 {
+        match self {
+            Kind::Clone | Kind::Unwrap | Kind::Lint => 5,
+            Kind::Loop => 10,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Finding {
+    line: usize,
+    kind: Kind,
+    suggestion: String,
+}
+
+/// Score `path` and print the suggestions
+pub fn score_file(path: &Path, clippy: bool) -> Result<(), String> {
+    let source = fs::read_to_string(path)
+        .map_err(|error| format!("cannot read {}: {}", path.display(), error))?;
+    let mut findings = analyze(&source)
+        .map_err(|error| format!("{} is not valid Rust: {}", path.display(), error))?;
+    if clippy {
+        match clippy_lints(path) {
+            Some(lints) => {
+                for lint in lints {
+                    if !findings.iter().any(|finding| finding.line == lint.line) {
+                        findings.push(lint);
+                    }
+                }
+            }
+            None => println!(
+                "{}",
+                output::dim("clippy-driver is not installed; scoring without clippy")
+            ),
+        }
+    }
+    findings.sort_by_key(|finding| finding.line);
+
+    let score = score(&findings);
+    println!(
+        "Idiom score for {}: {}",
+        path.display(),
+        output::bold(&format!("{}/100", score))
+    );
+    if findings.is_empty() {
+        println!(
+            "{}",
+            output::green("Nothing to suggest: this reads like idiomatic Rust")
+        );
+        return Ok(());
+    }
+    for finding in &findings {
+        println!("  line {:<4} {}", finding.line, finding.suggestion);
+    }
+    Ok(())
+}
+
+fn score(findings: &[Finding]) -> u32 {
+    let cost: u32 = findings.iter().map(|finding| finding.kind.cost()).sum();
+    100u32.saturating_sub(cost)
+}
+
+/// Everything `Visitor` finds in `source`
+fn analyze(source: &str) -> syn::Result<Vec<Finding>> {
+    let file = syn::parse_file(source)?;
+    let mut visitor = Visitor::default();
+    visitor.visit_file(&file);
+    Ok(visitor.findings)
+}
+
+#[derive(Default)]
+struct Visitor {
+    findings: Vec<Finding>,
+}
+
+impl Visitor {
+    fn push(&mut self, span: Span, kind: Kind, suggestion: impl Into<String>) {
+        self.findings.push(Finding {
+            line: span.start().line,
+            kind,
+            suggestion: suggestion.into(),
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for Visitor {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        let span = call.method.span();
+        match call.method.to_string().as_str() {
+            "clone" if call.args.is_empty() => self.push(
+                span,
+                Kind::Clone,
+                "`.clone()`: would a borrow (`&`) do instead of a copy?",
+            ),
+            method @ ("unwrap" | "expect") => self.push(
+                span,
+                Kind::Unwrap,
+                format!(
+                    "`.{}()` panics on failure: `?`, `match` or `unwrap_or` handle it",
+                    method
+                ),
+            ),
+            _ => {}
+        }
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_for_loop(&mut self, for_loop: &'ast ExprForLoop) {
+        if let Some(suggestion) = loop_suggestion(for_loop) {
+            self.push(for_loop.for_token.span(), Kind::Loop, suggestion);
+        }
+        visit::visit_expr_for_loop(self, for_loop);
+    }
+}
+
+/// What a `for` loop could be instead, when it only indexes, pushes, adds up
+/// or counts
+fn loop_suggestion(for_loop: &ExprForLoop) -> Option<&'static str> {
+    // for i in 0..items.len()
+    if let Expr::Range(range) = &*for_loop.expr
+        && matches!(range.limits, RangeLimits::HalfOpen(_))
+        && let Some(end) = &range.end
+        && let Expr::MethodCall(call) = &**end
+        && call.method == "len"
+    {
+        return Some(
+            "loop over the items (`for item in &items`, or `.iter().enumerate()` for the index too)",
+        );
+    }
+
+    let [Stmt::Expr(statement, _)] = for_loop.body.stmts.as_slice() else {
+        return None;
+    };
+    let (statement, filtered) = match statement {
+        Expr::If(if_expr) if if_expr.else_branch.is_none() => {
+            match if_expr.then_branch.stmts.as_slice() {
+                [Stmt::Expr(statement, _)] => (statement, true),
+                _ => return None,
+            }
+        }
+        statement => (statement, false),
+    };
+    match (statement, filtered) {
+        (Expr::MethodCall(call), false) if call.method == "push" => {
+            Some("a loop that only pushes is `.map(..).collect()`")
+        }
+        (Expr::MethodCall(call), true) if call.method == "push" => {
+            Some("a loop that only pushes some items is `.filter(..).collect()`")
+        }
+        (Expr::Binary(binary), false) if matches!(binary.op, BinOp::AddAssign(_)) => {
+            Some("a loop that only adds up is `.sum()`")
+        }
+        (Expr::Binary(binary), true) if matches!(binary.op, BinOp::AddAssign(_)) => Some(
+            "a loop that only counts or adds up some items is `.filter(..).count()` or `.sum()`",
+        ),
+        _ => None,
+    }
+}
+
+/// clippy's lints on `path`, as findings, or `None` without clippy-driver
+fn clippy_lints(path: &Path) -> Option<Vec<Finding>> {
+    let out_dir = std::env::temp_dir().join("rust-learn-idioms");
+    let path_text = path.to_string_lossy();
+    let out_dir_text = out_dir.to_string_lossy();
+    let args = [
+        path_text.as_ref(),
+        "--edition=2024",
+        "--crate-type=lib",
+        "--emit=metadata",
+        "--error-format=json",
+        "--out-dir",
+        out_dir_text.as_ref(),
+    ];
+    let dir = std::env::current_dir().ok()?;
+    let run = toolchain::run("clippy-driver", &dir, &args).ok()?;
+    Some(run.stderr.lines().filter_map(lint_finding).collect())
+}
+
+/// One line of rustc's JSON diagnostics, when it is a clippy lint
+fn lint_finding(line: &str) -> Option<Finding> {
+    let diagnostic = Value::parse(line).ok()?;
+    let code = diagnostic.get("code")?.get("code")?.as_str()?;
+    let name = code.strip_prefix("clippy::")?;
+    let message = diagnostic.get("message")?.as_str()?;
+    let primary = diagnostic
+        .get("spans")?
+        .as_array()?
+        .iter()
+        .find(|span| matches!(span.get("is_primary"), Some(Value::Bool(true))))?;
+    Some(Finding {
+        line: primary.get("line_start")?.as_f64()? as usize,
+        kind: Kind::Lint,
+        suggestion: format!("{} (clippy::{})", message, name),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze() {
+        let source = "\
+fn total(names: &Vec<String>) -> usize {
+    let mut lengths = Vec::new();
+    for name in names {
+        lengths.push(name.clone().len());
+    }
+    let mut sum = 0;
+    for i in 0..lengths.len() {
+        sum += lengths[i];
+    }
+    sum + \"7\".parse::<usize>().unwrap()
+}
+";
+        let findings = analyze(source).unwrap();
+        let found: Vec<(usize, Kind)> = findings
+            .iter()
+            .map(|finding| (finding.line, finding.kind))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (3, Kind::Loop),
+                (4, Kind::Clone),
+                (7, Kind::Loop),
+                (10, Kind::Unwrap),
+            ]
+        );
+        assert_eq!(score(&findings), 70);
+
+        let lint = r#"{"message":"the loop variable `i` is only used to index `lengths`","code":{"code":"clippy::needless_range_loop","explanation":null},"level":"warning","spans":[{"line_start":7,"is_primary":true}]}"#;
+        assert_eq!(lint_finding(lint).unwrap().line, 7);
+        assert_eq!(lint_finding(r#"{"code":{"code":"dead_code"}}"#), None);
+    }
+}
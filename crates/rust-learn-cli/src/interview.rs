@@ -0,0 +1,360 @@
+//! `rust-learn interview`: timed algorithm problems
+//! (`rust_learn_core::interview`) served one after another. Each is written
+//! to a file in the interview directory with its hints held back; `check`
+//! builds it with optimizations and runs its tests, large inputs included,
+//! under the problem's time limit. Solving or skipping one goes to the
+//! activity log and serves the next, and `report` says how ready the learner
+//! is on each topic from every attempt so far.
+
+use rust_learn_content::registry;
+use rust_learn_core::activity::{self, Event};
+use rust_learn_core::dirs;
+use rust_learn_core::interview::{self, Outcome, Problem, Session, Verdict};
+use rust_learn_core::output::{self, Glyph};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Lines of a failing build or test run shown; the rest are cut
+const MAX_FAILURE_LINES: usize = 40;
+
+fn problem(key: &str) -> Result<&'static Problem, String> {
+    registry::INTERVIEW
+        .iter()
+        .find(|problem| problem.key() == key)
+        .ok_or_else(|| {
+            format!(
+                "'{}' is not a problem any more; `rust-learn interview stop` ends the session",
+                key
+            )
+        })
+}
+
+fn session() -> Result<Session, String> {
+    Session::load().ok_or_else(|| {
+        "No interview in progress: start one with `rust-learn interview`".to_string()
+    })
+}
+
+/// The current problem's file
+fn file(session: &Session, problem: &Problem) -> PathBuf {
+    session.dir.join(format!("{}.rs", problem.name))
+}
+
+/// `12:05`
+fn clock(time: Duration) -> String {
+    let seconds = time.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Start a session of `count` problems, or show the current problem of the
+/// one in progress
+pub fn start(topic: Option<&str>, count: usize, hints: bool, dir: &Path) -> Result<(), String> {
+    if let Some(session) = Session::load() {
+        let note = "An interview is in progress; `rust-learn interview stop` ends it.";
+        println!("{}\n", output::dim(note));
+        return serve(&session, false);
+    }
+    if let Some(topic) = topic
+        && !registry::INTERVIEW
+            .iter()
+            .any(|problem| problem.topic == topic)
+    {
+        let mut topics: Vec<&str> = registry::INTERVIEW
+            .iter()
+            .map(|problem| problem.topic)
+            .collect();
+        topics.dedup();
+        return Err(format!(
+            "There are no problems on '{}'. Topics: {}",
+            topic,
+            topics.join(", ")
+        ));
+    }
+    let picked = interview::pick(registry::INTERVIEW, topic, count.max(1), &activity::load());
+    let session = Session {
+        queue: picked.iter().map(|problem| problem.key()).collect(),
+        total: picked.len(),
+        started: SystemTime::now(),
+        hints: 0,
+        show_hints: hints,
+        dir: dir.to_path_buf(),
+    };
+    session
+        .save()
+        .map_err(|error| format!("Cannot save the interview: {}", error))?;
+    serve(&session, true)
+}
+
+/// Print the current problem, writing its file first when `fresh` or when
+/// it is missing
+fn serve(session: &Session, fresh: bool) -> Result<(), String> {
+    let problem = problem(session.current())?;
+    let path = file(session, problem);
+    if fresh || !path.exists() {
+        write(session, problem, &path)
+            .map_err(|error| format!("Cannot write {}: {}", path.display(), error))?;
+    }
+
+    let position = format!(
+        "Problem {} of {} ({})",
+        session.number(),
+        session.total,
+        problem.topic
+    );
+    println!("{}", output::dim(&position));
+    println!("{}\n", output::bold(problem.title()));
+    println!("{}\n", problem.statement());
+    let time = format!(
+        "You have {} minutes ({} used); the tests must finish within {}s.",
+        problem.minutes(),
+        clock(session.elapsed()),
+        problem.limit().as_secs()
+    );
+    println!("{}", time);
+    if session.show_hints {
+        println!("\n{}", output::bold("Hints"));
+        for hint in problem.hints() {
+            println!("  - {}", hint);
+        }
+    }
+    println!(
+        "\nWrite your answer in {}, then run `rust-learn interview check`.",
+        path.display()
+    );
+    let more = if session.show_hints {
+        "`rust-learn interview skip` moves on without it."
+    } else {
+        "`rust-learn interview hint` gives a hint; `rust-learn interview skip` moves on without it."
+    };
+    println!("{}", output::dim(more));
+    Ok(())
+}
+
+/// Write the problem's starter to `path`, keeping what was there before as
+/// `<name>.rs.old`
+fn write(session: &Session, problem: &Problem, path: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(&session.dir)?;
+    let text = if session.show_hints {
+        problem.source.to_string()
+    } else {
+        problem.starter()
+    };
+    if path.exists() && fs::read_to_string(path)? != text {
+        let old = path.with_extension("rs.old");
+        fs::rename(path, &old)?;
+        let note = format!("Your earlier {} is now {}", path.display(), old.display());
+        println!("{}\n", output::dim(&note));
+    }
+    fs::write(path, text)
+}
+
+/// Check the current problem; passing it serves the next
+pub fn check() -> Result<(), String> {
+    let session = session()?;
+    let problem = problem(session.current())?;
+    let path = file(&session, problem);
+    if !path.exists() {
+        return Err(format!(
+            "{} is missing; `rust-learn interview` writes it again",
+            path.display()
+        ));
+    }
+    let scratch = dirs::scratch_dir("interview");
+    let verdict = problem
+        .check(&path, &scratch)
+        .map_err(|error| format!("Cannot check {}: {}", problem.name, error))?;
+    let elapsed = session.elapsed();
+    let budget = Duration::from_secs(problem.minutes() * 60);
+    match verdict {
+        Verdict::Passed => {
+            let outcome = if elapsed <= budget {
+                Outcome::Solved
+            } else {
+                Outcome::Late
+            };
+            let passed = format!("Passed in {}", clock(elapsed));
+            let status = output::status(Glyph::Ok, &passed, output::green, 0);
+            match outcome {
+                Outcome::Late => println!(
+                    "{} {}",
+                    status,
+                    output::dim(&format!("(over the {} minutes)", problem.minutes()))
+                ),
+                _ => println!("{}", status),
+            }
+            finish(session, problem, outcome)
+        }
+        Verdict::TooSlow => {
+            let slow = format!(
+                "The tests did not finish within {}s",
+                problem.limit().as_secs()
+            );
+            println!("{}", output::status(Glyph::Fail, &slow, output::red, 0));
+            println!("The answer may be right, but the large input needs a faster algorithm.");
+            remaining(elapsed, budget);
+            Ok(())
+        }
+        Verdict::Failed(errors) => {
+            println!("{}", output::status(Glyph::Fail, "Not yet", output::red, 0));
+            let lines: Vec<&str> = errors.lines().collect();
+            for line in lines.iter().take(MAX_FAILURE_LINES) {
+                println!("  {}", line);
+            }
+            if lines.len() > MAX_FAILURE_LINES {
+                let cut = format!("  ... {} more lines", lines.len() - MAX_FAILURE_LINES);
+                println!("{}", output::dim(&cut));
+            }
+            remaining(elapsed, budget);
+            Ok(())
+        }
+    }
+}
+
+/// How much of the problem's time is left
+fn remaining(elapsed: Duration, budget: Duration) {
+    let left = match budget.checked_sub(elapsed) {
+        Some(left) => format!("{} left", clock(left)),
+        None => format!("{} over time", clock(elapsed - budget)),
+    };
+    println!("{}", output::dim(&left));
+}
+
+/// Record how the current problem went and serve the next, or end the
+/// session after the last
+fn finish(mut session: Session, problem: &Problem, outcome: Outcome) -> Result<(), String> {
+    let hints = if session.show_hints {
+        problem.hints().len()
+    } else {
+        session.hints
+    };
+    let event = Event::Interview {
+        key: problem.key(),
+        outcome,
+        seconds: session.elapsed().as_secs_f64(),
+        hints,
+    };
+    if let Err(error) = activity::append(vec![event]) {
+        let message = format!("Cannot record the attempt: {}", error);
+        eprintln!("{}", output::red(&message));
+    }
+    if session.advance() {
+        session
+            .save()
+            .map_err(|error| format!("Cannot save the interview: {}", error))?;
+        println!();
+        return serve(&session, true);
+    }
+    interview::clear().map_err(|error| format!("Cannot end the interview: {}", error))?;
+    println!("\nThat was the last problem.");
+    println!(
+        "{}",
+        output::dim("See how ready you are on each topic with `rust-learn interview report`.")
+    );
+    Ok(())
+}
+
+/// Show the next hint for the current problem
+pub fn hint() -> Result<(), String> {
+    let mut session = session()?;
+    let problem = problem(session.current())?;
+    let hints = problem.hints();
+    if session.show_hints {
+        println!(
+            "The hints are in {} already.",
+            file(&session, problem).display()
+        );
+        return Ok(());
+    }
+    if session.hints < hints.len() {
+        session.hints += 1;
+        session
+            .save()
+            .map_err(|error| format!("Cannot save the interview: {}", error))?;
+    }
+    for (index, hint) in hints.iter().enumerate().take(session.hints) {
+        let label = format!("Hint {} of {}:", index + 1, hints.len());
+        let label = if index + 1 == session.hints {
+            output::bold(&label)
+        } else {
+            output::dim(&label)
+        };
+        println!("{} {}", label, hint);
+    }
+    if session.hints == hints.len() {
+        println!("{}", output::dim("That is every hint for this problem."));
+    }
+    Ok(())
+}
+
+/// Give up on the current problem and serve the next
+pub fn skip() -> Result<(), String> {
+    let session = session()?;
+    let problem = problem(session.current())?;
+    println!("Skipped {}.", problem.title());
+    finish(session, problem, Outcome::Skipped)
+}
+
+/// End the session; problems already solved or skipped stay in the report
+pub fn stop() -> Result<(), String> {
+    if Session::load().is_none() {
+        return Err("No interview in progress".to_string());
+    }
+    interview::clear().map_err(|error| format!("Cannot end the interview: {}", error))?;
+    println!("Interview stopped.");
+    Ok(())
+}
+
+/// Readiness per topic, from the last attempt at each problem
+pub fn report() -> Result<(), String> {
+    let report = interview::readiness(registry::INTERVIEW, &activity::load());
+    if report.iter().all(|topic| topic.attempted() == 0) {
+        println!("No interview problems tried yet: start with `rust-learn interview`.");
+        return Ok(());
+    }
+    let width = report
+        .iter()
+        .map(|topic| topic.topic.len())
+        .max()
+        .unwrap_or(0)
+        .max("Topic".len());
+    let heading = format!(
+        "{:width$}  {:>5}  {:>7}  {:>4}  {:>7}  {:>5}  Readiness",
+        "Topic",
+        "Tried",
+        "In time",
+        "Late",
+        "Skipped",
+        "Hints",
+        width = width
+    );
+    println!("{}", output::bold(&heading));
+    for topic in &report {
+        let tried = format!("{}/{}", topic.attempted(), topic.problems);
+        let score = format!("{:>3.0}%", topic.score * 100.0);
+        let verdict = match topic.verdict() {
+            "ready" => output::green(topic.verdict()),
+            "needs practice" => output::red(topic.verdict()),
+            verdict => output::dim(verdict),
+        };
+        println!(
+            "{:width$}  {:>5}  {:>7}  {:>4}  {:>7}  {:>5}  {}  {}",
+            topic.topic,
+            tried,
+            topic.solved,
+            topic.late,
+            topic.skipped,
+            topic.hints,
+            score,
+            verdict,
+            width = width
+        );
+    }
+    println!(
+        "\n{}",
+        output::dim(
+            "A problem solved in time counts fully, a late one half, and each hint a little less."
+        )
+    );
+    Ok(())
+}
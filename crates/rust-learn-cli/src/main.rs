@@ -0,0 +1,2072 @@
+use clap::builder::{PossibleValuesParser, TypedValueParser};
+use clap::error::ErrorKind;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand};
+use rust_learn_content::registry::{Difficulty, Filter};
+use rust_learn_content::{locales, registry};
+use rust_learn_core::alloc_counter::{self, CountingAlloc};
+use rust_learn_core::calibration::Calibration;
+use rust_learn_core::hooks::{self, Event, EventKind};
+use rust_learn_core::input::{self, MenuChoice};
+use rust_learn_core::json::Value;
+use rust_learn_core::output::{self, Settings, Theme, Verbosity};
+use rust_learn_core::progress::{self, Progress};
+use rust_learn_core::recent::Recent;
+use rust_learn_core::transaction::Transaction;
+use rust_learn_core::{
+    activity, bench, checkpoint, classroom, crash, cross, dirs, events, highlight, i18n, packs,
+    question_bank, quiz, random, runner, settings, variants, watchdog,
+};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tracing::{debug, debug_span};
+use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
+
+mod assignment;
+mod cache;
+mod clipboard;
+mod exercise;
+mod explain;
+mod export;
+mod history;
+mod idioms;
+mod interview;
+mod metrics;
+mod open;
+mod pack;
+mod predict;
+mod recommend;
+mod reference;
+mod repl;
+mod self_test;
+mod share;
+mod solutions;
+mod stats;
+mod step;
+mod tui;
+mod verify;
+mod vscode;
+
+/// Count every allocation, so lessons and `--verbose` can report them
+#[cfg(not(feature = "mimalloc"))]
+#[global_allocator]
+static ALLOCATOR: CountingAlloc<std::alloc::System> =
+    CountingAlloc::new(std::alloc::System, "system");
+
+/// `cargo run --features mimalloc` swaps the allocator underneath the counter
+#[cfg(feature = "mimalloc")]
+#[global_allocator]
+static ALLOCATOR: CountingAlloc<mimalloc::MiMalloc> =
+    CountingAlloc::new(mimalloc::MiMalloc, "mimalloc");
+
+/// Rust lessons you can run, list, search and quiz yourself on
+#[derive(Parser)]
+#[command(name = "rust-learn", version)]
+struct Cli {
+    /// Explain more (-v adds beginner notes, -vv what happens underneath) and
+    /// report how long each lesson took
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    verbose: u8,
+
+    /// Print only the code's results, without the explanations; -qq prints
+    /// nothing of the lessons, only results and summaries (for scripts)
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "verbose")]
+    quiet: u8,
+
+    /// Never color the output (NO_COLOR is honored too)
+    #[arg(long, global = true)]
+    no_color: bool,
+
+    /// Mark results with `[ok]`, `[x]` and `[!]` instead of emoji (the
+    /// default where emoji may not show)
+    #[arg(long, global = true)]
+    ascii: bool,
+
+    /// Colors for lesson text, results and code (default: `theme` under
+    /// [output] in config.toml, or dark)
+    #[arg(
+        long,
+        global = true,
+        value_parser = PossibleValuesParser::new(Theme::NAMES).map(|name| name.parse::<Theme>().unwrap())
+    )]
+    theme: Option<Theme>,
+
+    /// Never wait for input: lessons use sample input instead. Implied when
+    /// stdin is not a terminal
+    #[arg(long, global = true)]
+    non_interactive: bool,
+
+    /// Log what happens underneath (commands run, progress saved, lessons
+    /// timed) to stderr. RUST_LEARN_LOG takes a filter instead, like
+    /// `rust_learn_core=trace`
+    #[arg(long, global = true)]
+    debug: bool,
+
+    /// Stop a lesson that runs longer than this, as stuck in a loop (0 never
+    /// stops one). Waiting for cargo or for input does not count. Without
+    /// it, non-interactive runs get 120 seconds and interactive ones no limit
+    #[arg(long, global = true, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Print each section's source code just before the output it produces
+    #[arg(long, global = true)]
+    show_source: bool,
+
+    /// Print the run as events for other tools instead: `ndjson` is one JSON
+    /// object per line (lesson and section starts and ends, lines, prompts,
+    /// panics, results)
+    #[arg(long, global = true, value_name = "FORMAT", value_parser = events::FORMATS)]
+    events: Option<String>,
+
+    /// Classroom mode: add every quiz score and exercise check to a results
+    /// file for the teacher, as this student (or `RUST_LEARN_STUDENT`)
+    #[arg(long, global = true, value_name = "ID")]
+    student: Option<String>,
+
+    /// After each section that has one, ask a quick yes/no question about
+    /// it (`checkpoints = true` under `[lessons]` in config.toml does too)
+    #[arg(long, global = true)]
+    checkpoints: bool,
+
+    /// Language of the lessons' headings and explanations (`bn` is Bengali);
+    /// the code's own output stays as it is
+    #[arg(
+        long,
+        global = true,
+        default_value = "en",
+        value_parser = locales::LANGS
+    )]
+    lang: String,
+
+    /// Seed the random choices (the quiz's questions and their order,
+    /// `run --random`), so the same seed repeats a session exactly, e.g. in
+    /// front of a class (default: `seed` under [random] in config.toml, or
+    /// the clock)
+    #[arg(long, global = true, value_name = "NUMBER")]
+    seed: Option<u64>,
+
+    /// Without a command, a numbered lesson menu opens
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a lesson, one of its sections, or every lesson in order
+    Run {
+        /// Lesson name, or `lesson::section`
+        #[arg(required_unless_present_any = ["all", "random"])]
+        lesson: Option<String>,
+        /// Section name or number (see `rust-learn list <lesson>`)
+        #[arg(conflicts_with = "random")]
+        section: Option<String>,
+        /// Run the whole curriculum and print a timing summary
+        #[arg(long, conflicts_with = "lesson")]
+        all: bool,
+        /// Practice a random lesson (or, given a lesson, a random section of
+        /// it), skipping the ones shown most recently
+        #[arg(long, conflicts_with = "all")]
+        random: bool,
+        /// Stop after each section: `n` next, `p` previous, `r` again, `s`
+        /// skip to a section, `q` quit, `?` help. Given a section, start there
+        #[arg(long, conflicts_with_all = ["all", "random"])]
+        step: bool,
+        /// With --all, how to print the summary: `text` (a table) or `json`
+        /// (one line, the last on stdout)
+        #[arg(long, value_name = "FORMAT", value_parser = ["text", "json"])]
+        report: Option<String>,
+        /// With --all or --random, only the lessons with this tag
+        /// (`memory`, `concurrency`, ...)
+        #[arg(long)]
+        tag: Option<String>,
+        /// With --all or --random, only the lessons at this difficulty
+        #[arg(long, value_parser = difficulty_parser())]
+        difficulty: Option<Difficulty>,
+    },
+    /// Run the next lesson in curriculum order that is not complete yet, and
+    /// mark it complete; a lesson marked for review comes first
+    Next,
+    /// Continue a run that was interrupted (Ctrl-C during `run --all` or a
+    /// lesson) where it stopped; without one, run the next lesson
+    Resume,
+    /// List the lessons by chapter, or the sections of one lesson
+    List {
+        lesson: Option<String>,
+        /// Print every lesson as a JSON array, for editors and other tools
+        #[arg(long, conflicts_with = "lesson")]
+        json: bool,
+        /// Only the lessons with this tag (`memory`, `concurrency`, ...)
+        #[arg(long, conflicts_with = "lesson")]
+        tag: Option<String>,
+        /// Only the lessons at this difficulty
+        #[arg(long, conflicts_with = "lesson", value_parser = difficulty_parser())]
+        difficulty: Option<Difficulty>,
+    },
+    /// Find lessons and sections whose name or title contains every word
+    Search {
+        #[arg(required = true)]
+        words: Vec<String>,
+    },
+    /// Multiple-choice questions from each lesson's bank, and which lesson
+    /// covers which section, with the answers explained
+    Quiz {
+        /// Ask only this lesson's questions
+        lesson: Option<String>,
+        /// Number of questions
+        #[arg(short = 'n', long, default_value_t = 5)]
+        questions: usize,
+        /// Ask as many questions as you can answer in this many seconds,
+        /// instead of a number of them
+        #[arg(
+            long,
+            value_name = "SECONDS",
+            value_parser = clap::value_parser!(u64).range(1..),
+            conflicts_with_all = ["questions", "predict"]
+        )]
+        timed: Option<u64>,
+        /// Add the questions in this bank: a .toml or .json file, or a
+        /// directory of them (repeatable; `banks` under [quiz] in
+        /// config.toml adds more)
+        #[arg(long, value_name = "PATH")]
+        bank: Vec<PathBuf>,
+        /// Show small programs from the lessons and ask what they print; the
+        /// answer is checked against what they really print
+        #[arg(long)]
+        predict: bool,
+    },
+    /// Run one of a lesson's snippets, for `quiz --predict` to capture
+    #[command(hide = true)]
+    Snippet { lesson: String, name: String },
+    /// Explore in one session: list, run, search and quiz without starting
+    /// rust-learn again each time
+    Repl,
+    /// Save a lesson's output (or one section's) as study notes
+    Export {
+        /// Lesson name, or `lesson::section`
+        lesson: String,
+        /// Section name or number
+        section: Option<String>,
+        /// `md` for Markdown notes, `html` for a page with each section's
+        /// source next to its output
+        #[arg(long, default_value = "md", value_parser = ["md", "html"])]
+        format: String,
+        /// File to write instead of stdout
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Hand in work for a teacher to collect
+    Assignment {
+        #[command(subcommand)]
+        command: AssignmentCommand,
+    },
+    /// Questions and exercises a teacher publishes for the class, kept in
+    /// the data directory; the quiz adds a pack's questions
+    Pack {
+        #[command(subcommand)]
+        command: PackCommand,
+    },
+    /// Lessons finished, quizzes passed, your daily streak and time spent
+    #[command(args_conflicts_with_subcommands = true)]
+    Stats {
+        /// Print the numbers as one line of JSON
+        #[arg(long)]
+        json: bool,
+        #[command(subcommand)]
+        command: Option<StatsCommand>,
+    },
+    /// Past runs of a lesson, each with what it printed and what you typed,
+    /// and what changed between two of them
+    #[command(args_conflicts_with_subcommands = true)]
+    History {
+        /// Only this lesson's runs
+        lesson: Option<String>,
+        #[command(subcommand)]
+        command: Option<HistoryCommand>,
+    },
+    /// Space taken by builds, crash reports and the run history; `cache gc`
+    /// removes what is old by the `[cache]` settings in config.toml
+    Cache {
+        #[command(subcommand)]
+        command: Option<CacheCommand>,
+    },
+    /// Tools for trying things out on your own machine
+    Dev {
+        #[command(subcommand)]
+        command: DevCommand,
+    },
+    /// Compile and run the exercises (small broken programs, one directory
+    /// per lesson) and say which pass; passing ones count as progress
+    Verify {
+        /// Check only this exercise, like `ownership1`, or one version of a
+        /// lesson's exercises, like `week-3/ownership` or `builtin/ownership`
+        exercise: Option<String>,
+        /// Where the exercises are
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+        /// Check again whenever an exercise file changes
+        #[arg(long, conflicts_with = "build_only")]
+        watch: bool,
+        /// Only compile the exercise, with debug info, into target/exercises/
+        #[arg(long, requires = "exercise")]
+        build_only: bool,
+    },
+    /// Put an exercise back as shipped, or bring back an attempt; what is
+    /// in the file is backed up first
+    Exercise {
+        #[command(subcommand)]
+        command: ExerciseCommand,
+    },
+    /// Compare curated ways to solve an exercise, once you have solved it
+    Solutions {
+        #[command(subcommand)]
+        command: SolutionsCommand,
+    },
+    /// Practice for coding interviews: algorithm problems one after
+    /// another against the clock, checked with large inputs under a time
+    /// limit, then a readiness report per topic
+    #[command(args_conflicts_with_subcommands = true)]
+    Interview {
+        /// Only problems on this topic, like `graphs`
+        #[arg(long)]
+        topic: Option<String>,
+        /// How many problems in the session
+        #[arg(short = 'n', long, default_value_t = 3)]
+        count: usize,
+        /// Show each problem's hints up front (they count as taken)
+        #[arg(long)]
+        hints: bool,
+        /// Where the problem files are written
+        #[arg(long, value_name = "DIR", default_value = "interview")]
+        dir: PathBuf,
+        #[command(subcommand)]
+        command: Option<InterviewCommand>,
+    },
+    /// Print a lesson's code, or one section's, without running it
+    Show {
+        /// Lesson name, or `lesson::section`
+        lesson: String,
+        /// Section name or number
+        section: Option<String>,
+        /// Also copy the code to the clipboard
+        #[arg(long)]
+        copy: bool,
+    },
+    /// Compile the lines a lesson says would not compile, and show rustc's
+    /// real errors
+    ShowErrors {
+        /// Lesson name
+        lesson: String,
+        /// Only this demo, like `use_after_move`
+        demo: Option<String>,
+    },
+    /// Print a play.rust-lang.org link that runs a section, to tinker with
+    /// it online
+    Share {
+        /// Lesson name, or `lesson::section`
+        lesson: String,
+        /// Section name or number
+        section: Option<String>,
+        /// Open the link in the browser too
+        #[arg(long)]
+        open: bool,
+        /// Print the program the link holds instead
+        #[arg(long, conflicts_with = "open")]
+        code: bool,
+    },
+    /// Which crates to use for a topic (HTTP, CLI parsing, serialization,
+    /// testing, ...), and the lessons that show them
+    Crates {
+        /// Topic, like `http` or `error handling`, or a crate's name; all
+        /// topics without one
+        topic: Vec<String>,
+    },
+    /// rustc's explanation of an error code, with the lessons that teach
+    /// what it is about
+    Explain {
+        /// Error code, like E0502 (or e502, or 502)
+        code: String,
+    },
+    /// Look up a bundled Rust reference excerpt, like the borrowing rules,
+    /// without a network
+    Ref {
+        /// Topic, or a lesson to list the topics it links to; all topics
+        /// without one
+        topic: Option<String>,
+    },
+    /// Open an exercise, or a lesson's source (at a section), in your editor
+    /// at the line to start from
+    Open {
+        /// An exercise like `ownership1`, a lesson, or `lesson::section`
+        target: String,
+        /// Section name or number, for a lesson
+        section: Option<String>,
+        /// Where the exercises are
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+        /// Print `file:line` instead of opening it
+        #[arg(long)]
+        print: bool,
+    },
+    /// Check all shipped content: run every lesson without input, check the
+    /// quiz questions and the compile-fail fixtures; exits with 1 on a failure
+    SelfTest,
+    /// Browse lessons in a two-pane interface: pick, run and read them, and
+    /// mark them complete
+    Tui {
+        /// Start with the code and its output side by side (`v` switches)
+        #[arg(long)]
+        split: bool,
+    },
+    /// Crash reports, written when rust-learn panics
+    Report {
+        #[command(subcommand)]
+        command: ReportCommand,
+    },
+    /// `rust-learn ownership slices` is short for `rust-learn run ownership slices`
+    #[command(external_subcommand)]
+    Lesson(Vec<String>),
+}
+
+#[derive(Subcommand)]
+enum AssignmentCommand {
+    /// Commit the exercises directory with a RESULTS.toml of what you have
+    /// completed, and tag it `<ID>-<results hash>`
+    Submit {
+        /// Assignment id from the teacher, like `week-3`
+        id: String,
+        /// Where the exercises are
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+        /// Also push the commit and tag to `remote` under [assignment] in
+        /// config.toml
+        #[arg(long)]
+        push: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExerciseCommand {
+    /// Back up your attempt, then put the exercise back as it was shipped
+    Reset {
+        /// The exercise, like `ownership1`, or `week-3/ownership1` for a
+        /// pack's
+        name: String,
+        /// Where the exercises are
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+        /// Print what would be written, and change nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Put a backed-up attempt back, backing up what is there now
+    Restore {
+        name: String,
+        /// The backup's time as `exercise backups` lists it, or its start;
+        /// the newest without it
+        #[arg(long, value_name = "TIME")]
+        from: Option<String>,
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+        /// Print what would be written, and change nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// List an exercise's backups, oldest first
+    Backups {
+        name: String,
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum SolutionsCommand {
+    /// Show the solutions that come with an exercise (beginner, idiomatic,
+    /// performance, ...), each with a note on what it does well
+    Gallery {
+        /// The exercise, like `vectors2`, or `week-3/vectors2` for a pack's
+        exercise: String,
+        #[arg(long, value_name = "DIR", default_value = "exercises")]
+        dir: PathBuf,
+        /// Also copy one solution's code, as shown, to the clipboard
+        #[arg(long, value_name = "APPROACH")]
+        copy: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackCommand {
+    /// Fetch a pack, check it and register it; run it again to update it
+    Add {
+        #[command(subcommand)]
+        source: PackSource,
+    },
+    /// The registered packs, and where their exercises are
+    List,
+    /// Delete a pack's files and take it off the list
+    Remove {
+        /// The pack's name, as `pack list` shows it
+        name: String,
+        /// Print what would be removed, and change nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackSource {
+    /// Clone a pack's git repository the first time, pull it after that
+    Git {
+        /// The repository, as `git clone` takes it
+        url: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum InterviewCommand {
+    /// Build the current problem and run its tests; passing serves the next
+    Check,
+    /// Show the next hint for the current problem
+    Hint,
+    /// Give up on the current problem and move on
+    Skip,
+    /// End the interview in progress
+    Stop,
+    /// How ready you are on each topic, from every problem tried so far
+    Report,
+}
+
+#[derive(Subcommand)]
+enum StatsCommand {
+    /// Suggest new minutes and difficulties for each lesson from your own
+    /// times and quiz scores; `list` shows them from then on
+    Recalibrate,
+    /// For content authors: how learners who saw each explanation variant
+    /// did on the quiz
+    Variants {
+        /// Activity logs to read, one per learner (default: your own)
+        #[arg(value_name = "LOG")]
+        logs: Vec<PathBuf>,
+    },
+}
+
+#[derive(Subcommand)]
+enum HistoryCommand {
+    /// Show the lines one run printed or read and the other did not
+    Diff {
+        /// The older run's id (4 or more of its digits)
+        old: String,
+        /// The newer run's id
+        new: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Remove old builds and crash reports, then the oldest builds over the
+    /// size limit, and old runs from the history
+    Gc {
+        /// Print what would be removed, and change nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum DevCommand {
+    /// Build hello world for another target, checking the standard library and
+    /// linker first and explaining any failure
+    Cross {
+        /// Target triple, e.g. x86_64-pc-windows-gnu (leave out to list the
+        /// targets the walkthrough knows)
+        target: Option<String>,
+    },
+    /// Time a fixed workload, to compare builds made with different profiles
+    Bench,
+    /// Score how idiomatic a Rust file looks (clones, unwraps, loops that
+    /// could be iterators, clippy's lints) and suggest what to change
+    Idioms {
+        /// The .rs file to score
+        file: PathBuf,
+        /// Leave clippy out, counting only what the analyzer finds
+        #[arg(long)]
+        no_clippy: bool,
+    },
+    /// Lines, nesting depth and complexity of every function in a file or
+    /// directory
+    Metrics {
+        /// A .rs file, or a directory to search for them
+        path: PathBuf,
+        /// Fail (exit 1) when a function is more complex than this
+        #[arg(long, value_name = "N")]
+        max_complexity: Option<usize>,
+    },
+    /// Write .vscode/tasks.json and launch.json entries to check, watch and
+    /// debug the exercise open in VS Code
+    Vscode {
+        /// Replace a file that is not plain JSON (one with comments, say)
+        #[arg(long)]
+        force: bool,
+        /// Print what would be written, and change nothing
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum ReportCommand {
+    /// Print the newest crash report
+    ShowLast,
+}
+
+fn difficulty_parser() -> impl TypedValueParser<Value = Difficulty> {
+    PossibleValuesParser::new(Difficulty::NAMES).map(|name| name.parse::<Difficulty>().unwrap())
+}
+
+/// How long a non-interactive lesson may run without `--timeout`
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Exit with a usage error when `run` has a flag that only works with
+/// another one. `requires = "all"` cannot say this: clap fills in `false` for
+/// a missing `--all`, which then counts as present
+fn check_run_flags(cli: &Cli) {
+    let Some(Command::Run {
+        lesson,
+        all,
+        random,
+        report,
+        tag,
+        difficulty,
+        ..
+    }) = &cli.command
+    else {
+        return;
+    };
+    let problem = if report.is_some() && !all {
+        "--report needs --all"
+    } else if (tag.is_some() || difficulty.is_some()) && !all && !(*random && lesson.is_none()) {
+        "--tag and --difficulty need --all, or --random without a lesson"
+    } else {
+        return;
+    };
+    let mut command = Cli::command();
+    command.build();
+    command
+        .find_subcommand_mut("run")
+        .expect("run is a command")
+        .error(ErrorKind::MissingRequiredArgument, problem)
+        .exit();
+}
+
+fn main() {
+    crash::install(env!("CARGO_PKG_VERSION"));
+    let cli = Cli::parse();
+    check_run_flags(&cli);
+    let detected = Settings::detect(Verbosity::from_flags(cli.verbose, cli.quiet), cli.no_color);
+    output::init(Settings {
+        theme: cli.theme.or_else(Theme::configured).unwrap_or_default(),
+        show_source: cli.show_source,
+        ascii: cli.ascii || detected.ascii,
+        ..detected
+    });
+    input::init(cli.non_interactive);
+    checkpoint::init(cli.checkpoints);
+    random::init(cli.seed);
+    events::init(cli.events.is_some());
+    if let Err(error) = classroom::init(cli.student.clone()) {
+        eprintln!("{}", output::red(&error));
+        std::process::exit(1);
+    }
+    if let Some(text) = locales::catalog(&cli.lang) {
+        match i18n::Catalog::parse(&cli.lang, text) {
+            Ok(catalog) => i18n::init(catalog),
+            Err(error) => eprintln!(
+                "The {} translation is broken ({}); using English",
+                cli.lang, error
+            ),
+        }
+    }
+    init_logging(cli.debug);
+    #[cfg(feature = "http")]
+    if let Some(url) = rust_learn_core::remote::install() {
+        debug!(%url, "building on the remote service");
+    }
+    watchdog::init(match cli.timeout {
+        Some(0) => None,
+        Some(seconds) => Some(Duration::from_secs(seconds)),
+        None if !input::interactive() => Some(DEFAULT_TIMEOUT),
+        None => None,
+    });
+
+    if !matches!(cli.command, Some(Command::Cache { .. })) {
+        cache::auto();
+    }
+
+    match cli.command {
+        // The menu and the quiz can still read piped answers, unless told not to
+        None if cli.non_interactive => list(None, &Filter::default()),
+        None => lesson_menu(),
+        Some(Command::Run {
+            all: true,
+            report,
+            tag,
+            difficulty,
+            ..
+        }) => run_all(
+            &Filter { tag, difficulty },
+            report.as_deref() == Some("json"),
+        ),
+        Some(Command::Run {
+            random: true,
+            lesson,
+            tag,
+            difficulty,
+            ..
+        }) => run_random(lesson.as_deref(), &Filter { tag, difficulty }),
+        Some(Command::Run {
+            lesson: Some(lesson),
+            section,
+            step: true,
+            ..
+        }) => run_stepped(&lesson, section.as_deref()),
+        Some(Command::Run {
+            lesson: Some(lesson),
+            section,
+            ..
+        }) => run_target(&lesson, section.as_deref()),
+        Some(Command::Run { lesson: None, .. }) => unreachable!("clap requires a lesson or --all"),
+        Some(Command::Next) => run_next(),
+        Some(Command::Resume) => resume(),
+        Some(Command::List {
+            lesson,
+            json,
+            tag,
+            difficulty,
+        }) => {
+            let filter = Filter { tag, difficulty };
+            match filter.lessons() {
+                Ok(lessons) if json => println!("{}", lessons_json(&lessons)),
+                Ok(_) => list(lesson.as_deref(), &filter),
+                Err(error) => {
+                    eprintln!("{}", output::red(&format!("Cannot list: {}", error)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Search { words }) => search(&words),
+        Some(Command::Quiz { .. }) if cli.non_interactive => {
+            eprintln!(
+                "{}",
+                output::red("The quiz needs answers; drop --non-interactive.")
+            );
+            std::process::exit(1);
+        }
+        Some(Command::Quiz {
+            lesson,
+            questions,
+            timed,
+            bank,
+            predict,
+        }) => quiz(
+            lesson.as_deref().map(find_lesson),
+            questions,
+            timed.map(Duration::from_secs),
+            &bank,
+            predict,
+        ),
+        Some(Command::Snippet { lesson, name }) => {
+            let lesson = find_lesson(&lesson);
+            match lesson.snippets.iter().find(|snippet| snippet.name == name) {
+                Some(snippet) => (snippet.run)(),
+                None => {
+                    eprintln!("{} has no snippet '{}'", lesson.name, name);
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Repl) => repl::repl(),
+        Some(Command::Export {
+            lesson,
+            section,
+            format,
+            output,
+        }) => export(&lesson, section.as_deref(), &format, output.as_deref()),
+        Some(Command::Assignment {
+            command: AssignmentCommand::Submit { id, dir, push },
+        }) => {
+            if let Err(error) = assignment::submit(&id, &dir, push) {
+                eprintln!("{}", output::red(&format!("Cannot submit: {}", error)));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Pack {
+            command:
+                PackCommand::Add {
+                    source: PackSource::Git { url },
+                },
+        }) => {
+            if let Err(error) = pack::add_git(&url) {
+                eprintln!(
+                    "{}",
+                    output::red(&format!("Cannot add the pack: {}", error))
+                );
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Pack {
+            command: PackCommand::List,
+        }) => pack::list(),
+        Some(Command::Pack {
+            command: PackCommand::Remove { name, dry_run },
+        }) => {
+            if let Err(error) = pack::remove(&name, dry_run) {
+                eprintln!(
+                    "{}",
+                    output::red(&format!("Cannot remove the pack: {}", error))
+                );
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Stats {
+            json,
+            command: None,
+        }) => stats::summary(json),
+        Some(Command::Stats {
+            command: Some(StatsCommand::Recalibrate),
+            ..
+        }) => {
+            if let Err(error) = stats::recalibrate() {
+                eprintln!("{}", output::red(&format!("Cannot recalibrate: {}", error)));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Stats {
+            command: Some(StatsCommand::Variants { logs }),
+            ..
+        }) => {
+            if let Err(error) = stats::variants(&logs) {
+                eprintln!("{}", output::red(&format!("Cannot read stats: {}", error)));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::History { lesson, command }) => {
+            let result = match command {
+                None => history::list(lesson.as_deref()),
+                Some(HistoryCommand::Diff { old, new }) => history::diff(&old, &new),
+            };
+            if let Err(error) = result {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Cache { command: None }) => cache::status(),
+        Some(Command::Cache {
+            command: Some(CacheCommand::Gc { dry_run }),
+        }) => {
+            if let Err(error) = cache::gc(dry_run) {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Dev {
+            command: DevCommand::Cross { target: None },
+        }) => {
+            for target in cross::TARGETS {
+                println!("{:<28} {}", target.triple, target.description);
+            }
+        }
+        Some(Command::Dev {
+            command: DevCommand::Cross {
+                target: Some(target),
+            },
+        }) => {
+            if !cross::walkthrough(&target) {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Dev {
+            command: DevCommand::Bench,
+        }) => {
+            let result = bench::run();
+            println!(
+                "{} edit distances, checksum {}",
+                result.comparisons, result.checksum
+            );
+            println!("time: {:.6}s", result.elapsed.as_secs_f64());
+        }
+        Some(Command::Dev {
+            command: DevCommand::Idioms { file, no_clippy },
+        }) => {
+            if let Err(error) = idioms::score_file(&file, !no_clippy) {
+                eprintln!("{}", output::red(&format!("Cannot score: {}", error)));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Dev {
+            command:
+                DevCommand::Metrics {
+                    path,
+                    max_complexity,
+                },
+        }) => {
+            if let Err(error) = metrics::report(&path, max_complexity) {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Dev {
+            command: DevCommand::Vscode { force, dry_run },
+        }) => {
+            if let Err(error) = vscode::generate(Path::new("."), force, dry_run) {
+                eprintln!(
+                    "{}",
+                    output::red(&format!("Cannot set up VS Code: {}", error))
+                );
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Verify {
+            exercise,
+            dir,
+            watch,
+            build_only,
+        }) => {
+            let verified = match (&exercise, watch, build_only) {
+                (Some(name), _, true) => verify::build(&dir, name),
+                (_, true, _) => verify::watch(&dir, exercise.as_deref()).map(|()| true),
+                _ => verify::run(&dir, exercise.as_deref()),
+            };
+            match verified {
+                Ok(true) => {}
+                Ok(false) => std::process::exit(1),
+                Err(error) => {
+                    eprintln!("{}", output::red(&format!("Cannot verify: {}", error)));
+                    std::process::exit(1);
+                }
+            }
+        }
+        Some(Command::Exercise { command }) => {
+            let result = match command {
+                ExerciseCommand::Reset { name, dir, dry_run } => {
+                    exercise::reset(&dir, &name, dry_run)
+                }
+                ExerciseCommand::Restore {
+                    name,
+                    from,
+                    dir,
+                    dry_run,
+                } => exercise::restore(&dir, &name, from.as_deref(), dry_run),
+                ExerciseCommand::Backups { name, dir } => exercise::list(&dir, &name),
+            };
+            if let Err(error) = result {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Solutions {
+            command:
+                SolutionsCommand::Gallery {
+                    exercise,
+                    dir,
+                    copy,
+                },
+        }) => {
+            if let Err(error) = solutions::gallery(&dir, &exercise, copy.as_deref()) {
+                eprintln!(
+                    "{}",
+                    output::red(&format!("Cannot show solutions: {}", error))
+                );
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Interview {
+            topic,
+            count,
+            hints,
+            dir,
+            command,
+        }) => {
+            let result = match command {
+                None => interview::start(topic.as_deref(), count, hints, &dir),
+                Some(InterviewCommand::Check) => interview::check(),
+                Some(InterviewCommand::Hint) => interview::hint(),
+                Some(InterviewCommand::Skip) => interview::skip(),
+                Some(InterviewCommand::Stop) => interview::stop(),
+                Some(InterviewCommand::Report) => interview::report(),
+            };
+            if let Err(error) = result {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Show {
+            lesson,
+            section,
+            copy,
+        }) => show(&lesson, section.as_deref(), copy),
+        Some(Command::ShowErrors { lesson, demo }) => show_errors(&lesson, demo.as_deref()),
+        Some(Command::Share {
+            lesson,
+            section,
+            open,
+            code,
+        }) => share(&lesson, section.as_deref(), code, open),
+        Some(Command::Crates { topic }) => {
+            let topic = (!topic.is_empty()).then(|| topic.join(" "));
+            if let Err(error) = recommend::run(topic.as_deref()) {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Explain { code }) => {
+            if let Err(error) = explain::run(&code) {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Ref { topic }) => {
+            if let Err(error) = reference::run(topic.as_deref()) {
+                eprintln!("{}", output::red(&error));
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Open {
+            target,
+            section,
+            dir,
+            print,
+        }) => {
+            if let Err(error) = open::run(&target, section.as_deref(), &dir, print) {
+                eprintln!(
+                    "{}",
+                    output::red(&format!("Cannot open {}: {}", target, error))
+                );
+                std::process::exit(1);
+            }
+        }
+        Some(Command::SelfTest) => {
+            if !self_test::run() {
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Tui { split }) => {
+            if let Err(error) = tui::run(split) {
+                eprintln!(
+                    "{}",
+                    output::red(&format!("Cannot open the TUI: {}", error))
+                );
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Report {
+            command: ReportCommand::ShowLast,
+        }) => show_last_report(),
+        Some(Command::Lesson(args)) => run_target(&args[0], args.get(1).map(String::as_str)),
+    }
+}
+
+/// Print the changes `transaction` plans, then make them, or with `dry_run`
+/// say that nothing changed. Every command with `--dry-run` ends here, so
+/// the list it prints is the one a real run works through
+fn carry_out(transaction: Transaction, dry_run: bool) -> Result<(), String> {
+    for line in transaction.describe() {
+        println!("{}", line);
+    }
+    if dry_run {
+        println!(
+            "{}",
+            output::dim("Dry run: nothing was changed; run it again without --dry-run to do this")
+        );
+        return Ok(());
+    }
+    transaction
+        .apply()
+        .map_err(|error| format!("stopped partway: {}", error))
+}
+
+/// Send tracing events to stderr: those RUST_LEARN_LOG selects, else every
+/// debug event from rust-learn's own crates with `--debug`, else none. The
+/// debug events always go to the crash reporter too
+fn init_logging(debug: bool) {
+    let ours = || EnvFilter::new("rust_learn=debug,rust_learn_core=debug,rust_learn_content=debug");
+    let filter = match std::env::var("RUST_LEARN_LOG") {
+        Ok(filter) if !filter.is_empty() => Some(EnvFilter::new(filter)),
+        _ if debug => Some(ours()),
+        _ => None,
+    };
+    let stderr = filter.map(|filter| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_ansi(output::settings().color && std::io::stderr().is_terminal())
+            .with_filter(filter)
+    });
+    let crash_log = tracing_subscriber::fmt::layer()
+        .with_writer(|| crash::LogWriter)
+        .with_ansi(false)
+        .with_filter(ours());
+    tracing_subscriber::registry()
+        .with(stderr)
+        .with(crash_log)
+        .init();
+}
+
+/// Accept both `ownership slices` and `ownership::slices` (or `ownership::5`)
+fn run_target(lesson: &str, section: Option<&str>) {
+    match lesson.split_once("::") {
+        Some((lesson, section)) => run_lesson(lesson, Some(section)),
+        None => run_lesson(lesson, section),
+    }
+}
+
+/// `run_target` a section at a time, from the given section or the first
+fn run_stepped(lesson: &str, section: Option<&str>) {
+    let (lesson, section) = match lesson.split_once("::") {
+        Some((lesson, section)) => (lesson, Some(section)),
+        None => (lesson, section),
+    };
+    let lesson = find_lesson(lesson);
+    let start = section.map_or(&lesson.sections[0], |key| find_section(lesson, key));
+    step::run(lesson, start);
+}
+
+/// Run the whole curriculum in registry order and print the summary (as JSON
+/// with `json`), exiting with 1 if a lesson panicked
+fn run_all(filter: &Filter, json: bool) {
+    let lessons = filter.lessons().unwrap_or_else(|error| {
+        eprintln!("{}", output::red(&format!("Cannot run: {}", error)));
+        std::process::exit(1);
+    });
+    if lessons.is_empty() {
+        println!("No lesson has {}", filter.to_args());
+        return;
+    }
+    run_lessons(&lessons, filter, json);
+}
+
+/// Run `lessons` as (the rest of) a full run, recording each one as it
+/// finishes so `resume` can continue after an interruption. The run is saved
+/// as `all`, with `filter`'s flags after it when there is one
+fn run_lessons(lessons: &[registry::Lesson], filter: &Filter, json: bool) {
+    let mut progress = Progress::load();
+    if filter.is_empty() {
+        progress.start_run("all");
+    } else {
+        progress.start_run(&format!("all {}", filter.to_args()));
+    }
+    save_progress(&progress);
+
+    let outcomes = runner::run_all(lessons, |lesson, outcome| {
+        log_run(lesson.name, outcome.elapsed);
+        result_event(lesson, None, outcome.completed, outcome.elapsed);
+        if outcome.completed {
+            progress.record_run(lesson, None);
+            save_progress(&progress);
+            hooks::fire(&finished(lesson, None, outcome.elapsed));
+        }
+    });
+    progress.finish_run();
+    save_progress(&progress);
+
+    if events::enabled() {
+        events::emit("summary", vec![("report", runner::report(&outcomes))]);
+    } else if json {
+        println!("{}", runner::report(&outcomes));
+    } else {
+        runner::print_summary(&outcomes, registry::name_width());
+    }
+    if outcomes.iter().any(|outcome| !outcome.completed) {
+        std::process::exit(1);
+    }
+}
+
+/// Continue the run that was interrupted, or else start the next lesson
+fn resume() {
+    let progress = Progress::load();
+    let Some((target, started)) = progress.interrupted_run() else {
+        println!(
+            "{}\n",
+            output::dim("Nothing was interrupted; running the next lesson instead")
+        );
+        run_next();
+        return;
+    };
+
+    let since = progress::format_timestamp(started);
+    if let Some(flags) = target.strip_prefix("all")
+        && (flags.is_empty() || flags.starts_with(' '))
+    {
+        // Pick up at the first lesson the interrupted run did not finish
+        let filter = Filter::parse_args(flags).unwrap_or_default();
+        let lessons = filter.lessons().unwrap_or_default();
+        let done = |lesson: &registry::Lesson| {
+            progress
+                .completed_at(lesson.name)
+                .is_some_and(|time| time >= started)
+        };
+        let first = lessons
+            .iter()
+            .position(|lesson| !done(lesson))
+            .unwrap_or(lessons.len());
+        let Some(lesson) = lessons.get(first) else {
+            println!("The run started {} had finished every lesson.", since);
+            let mut progress = progress;
+            progress.finish_run();
+            save_progress(&progress);
+            return;
+        };
+        let note = format!(
+            "Resuming the {} run started {} at {} ({} of {} lessons left)",
+            if filter.is_empty() {
+                "full"
+            } else {
+                flags.trim()
+            },
+            since,
+            lesson.name,
+            lessons.len() - first,
+            lessons.len()
+        );
+        println!("{}\n", output::dim(&note));
+        run_lessons(&lessons[first..], &filter, false);
+    } else {
+        let note = format!("Resuming {}, interrupted after starting {}", target, since);
+        println!("{}\n", output::dim(&note));
+        let target = target.to_string();
+        run_target(&target, None);
+    }
+}
+
+/// Run a lesson `filter` lets through, or a section of `lesson`, that was
+/// not shown recently
+fn run_random(lesson: Option<&str>, filter: &Filter) {
+    let keys: Vec<String> = match lesson {
+        Some(name) => {
+            let lesson = find_lesson(name);
+            lesson
+                .sections
+                .iter()
+                .map(|section| format!("{}::{}", lesson.name, section.name))
+                .collect()
+        }
+        None => {
+            let lessons = filter.lessons().unwrap_or_else(|error| {
+                eprintln!("{}", output::red(&format!("Cannot run: {}", error)));
+                std::process::exit(1);
+            });
+            if lessons.is_empty() {
+                println!("No lesson has {}", filter.to_args());
+                return;
+            }
+            lessons
+                .iter()
+                .map(|lesson| lesson.name.to_string())
+                .collect()
+        }
+    };
+    let candidates: Vec<&str> = keys.iter().map(String::as_str).collect();
+
+    let mut recent = Recent::load();
+    let key = recent
+        .pick(&candidates, &mut random::session("run --random"))
+        .expect("every lesson has sections");
+    recent.record(key);
+    if let Err(error) = recent.save() {
+        let note = format!("Could not remember this pick: {}", error);
+        eprintln!("{}", output::dim(&note));
+    }
+
+    println!(
+        "{}\n",
+        output::dim(&format!(
+            "Practicing {} (seed {})",
+            key,
+            random::seed().value
+        ))
+    );
+    run_target(key, None);
+}
+
+/// Run the first lesson not marked complete whose prerequisites are
+fn run_next() {
+    let progress = Progress::load();
+    if let Some(lesson) = registry::LESSONS
+        .iter()
+        .find(|lesson| progress.is_marked_for_review(lesson.name))
+    {
+        let heading = format!(
+            "Review: {} ({}), marked when it changed",
+            lesson.name, lesson.title
+        );
+        println!("{}\n", output::dim(&heading));
+        run_lesson(lesson.name, None);
+        return;
+    }
+    let Some(lesson) = registry::next(|name| progress.is_complete(name)) else {
+        println!("Every lesson is complete. `rust-learn run --random` practices one again.");
+        return;
+    };
+
+    let heading = format!(
+        "Next: {} {} (chapter {}, {})",
+        registry::number(lesson),
+        lesson.name,
+        lesson.chapter,
+        registry::chapter_title(lesson.chapter)
+    );
+    println!("{}\n", output::dim(&heading));
+    run_lesson(lesson.name, None);
+
+    let progress = Progress::load();
+    let note = match registry::next(|name| progress.is_complete(name)) {
+        Some(after) => format!("`rust-learn next` continues with {}", after.name),
+        None => "That was the last lesson".to_string(),
+    };
+    println!("{}", output::dim(&note));
+}
+
+fn run_lesson(name: &str, section: Option<&str>) {
+    let lesson = find_lesson(name);
+    let start = Instant::now();
+
+    let section = section.map(|key| find_section(lesson, key));
+    let key = match section {
+        Some(section) => format!("{}::{}", lesson.name, section.name),
+        None => lesson.name.to_string(),
+    };
+    let mut progress = Progress::load();
+    show_changes(lesson, section.is_none(), &mut progress);
+    progress.start_run(&key);
+    save_progress(&progress);
+    crash::set_active(Some(key.clone()));
+    let _span = debug_span!(
+        "run",
+        lesson = lesson.name,
+        section = section.map(|section| section.name)
+    )
+    .entered();
+    let watch = watchdog::watch(lesson, section);
+    rust_learn_core::history::start();
+    output::set_running(Some(lesson));
+
+    let ((), allocs) = alloc_counter::count(|| match section {
+        None => (lesson.run)(),
+        Some(section) => (section.run)(),
+    });
+
+    output::set_running(None);
+    drop(watch);
+    log_run(&key, start.elapsed());
+    debug!(elapsed = ?start.elapsed(), allocations = allocs.allocations, "finished");
+    crash::set_active(None);
+
+    let mut progress = Progress::load();
+    let was_complete = progress.is_complete(lesson.name);
+    progress.record_run(lesson, section.map(|section| section.name));
+    progress.finish_run();
+    save_progress(&progress);
+
+    let elapsed = start.elapsed();
+    result_event(lesson, section, true, elapsed);
+    if section.is_some() {
+        hooks::fire(&finished(lesson, section, elapsed));
+    }
+    // Running the last section on its own completes the lesson as well
+    if section.is_none() || (!was_complete && progress.is_complete(lesson.name)) {
+        hooks::fire(&finished(lesson, None, elapsed));
+    }
+
+    let settings = output::settings();
+    if settings.verbose() && !events::enabled() {
+        let mut finished = format!(
+            "Finished {}{} in {}, {} allocations",
+            lesson.name,
+            section
+                .map(|section| format!("::{}", section.name))
+                .unwrap_or_default(),
+            runner::format_duration(elapsed),
+            allocs.allocations
+        );
+        if settings.verbosity >= Verbosity::VeryVerbose {
+            finished += &format!(
+                " ({} bytes, {} reallocations)",
+                allocs.bytes, allocs.reallocations
+            );
+        }
+        println!("{}", output::dim(&finished));
+    }
+    if section.is_none() && settings.verbosity >= Verbosity::Normal && !events::enabled() {
+        for topic in reference::for_lesson(lesson.name) {
+            let link = format!("Look it up offline: rust-learn ref {}", topic.name);
+            println!("{}", output::dim(&link));
+        }
+    }
+}
+
+/// The `result` event for a lesson, or one of its sections, that ran
+fn result_event(
+    lesson: &registry::Lesson,
+    section: Option<&registry::Section>,
+    completed: bool,
+    elapsed: Duration,
+) {
+    events::emit(
+        "result",
+        vec![
+            ("lesson", lesson.name.into()),
+            (
+                "section",
+                section.map_or(Value::Null, |section| section.name.into()),
+            ),
+            ("completed", Value::Bool(completed)),
+            ("seconds", Value::Number(elapsed.as_secs_f64())),
+        ],
+    );
+}
+
+/// For a lesson that changed since the learner completed it: what changed.
+/// Unless the whole lesson is about to run again (which brings it up to
+/// date), offer to mark it for review, so `next` comes back to it
+fn show_changes(lesson: &registry::Lesson, whole: bool, progress: &mut Progress) {
+    let Some(version) = progress.completed_version(lesson.name) else {
+        return;
+    };
+    let changes = lesson.changes_since(version);
+    if changes.is_empty() || (!whole && progress.is_marked_for_review(lesson.name)) {
+        return;
+    }
+    let heading = format!(
+        "{} changed since you completed it (version {} is now {}):",
+        lesson.name,
+        version,
+        lesson.version()
+    );
+    println!("{}", output::bold(&heading));
+    for change in changes {
+        println!("  - {}: {}", change.version, change.summary);
+    }
+    if !whole
+        && input::interactive()
+        && input::prompt_input("Mark it for review, so `rust-learn next` comes back to it? [y/N] ")
+            .is_some_and(|answer| answer.eq_ignore_ascii_case("y"))
+    {
+        progress.mark_for_review(lesson.name);
+        println!("{}", output::dim("Marked for review"));
+    }
+    println!();
+}
+
+/// The hook event for a lesson, or one of its sections, that ran to the end
+fn finished(
+    lesson: &registry::Lesson,
+    section: Option<&registry::Section>,
+    elapsed: Duration,
+) -> Event {
+    // "Ownership - Memory Safety Without Garbage Collection" is "Ownership"
+    let title = lesson.title.split(" - ").next().unwrap_or(lesson.title);
+    let (mut event, title) = match section {
+        Some(section) => (
+            Event::new(
+                EventKind::Section,
+                &format!("{}::{}", lesson.name, section.name),
+                elapsed,
+            ),
+            format!("{}: {}", title, section.title),
+        ),
+        None => (
+            Event::new(EventKind::Lesson, lesson.name, elapsed),
+            title.to_string(),
+        ),
+    };
+    event.title = title;
+    event
+}
+
+/// Ask `questions` quiz questions (about one lesson, or all of them), then
+/// tell the hooks the score. With `predict`, the questions are snippets to
+/// predict the output of
+fn quiz(
+    about: Option<&registry::Lesson>,
+    questions: usize,
+    limit: Option<Duration>,
+    banks: &[PathBuf],
+    predict: bool,
+) {
+    let lessons = quiz_lessons(banks);
+    let start = Instant::now();
+    let about = about.map(|lesson| lesson.name);
+    let mut rng = random::session(if predict { "quiz --predict" } else { "quiz" });
+    let seed = random::seed();
+    if !seed.chosen {
+        let note = format!("Seed {0}: `--seed {0}` asks the same questions", seed.value);
+        println!("{}\n", output::dim(&note));
+    }
+    let score = if predict {
+        predict::run(&lessons, about, questions, &mut rng)
+    } else {
+        quiz::run_quiz(&lessons, about, questions, limit, &mut rng)
+    };
+    log_answers(&score.answers);
+    if score.asked > 0 {
+        log_activity(vec![activity::Event::Quiz {
+            about: about.unwrap_or("all").to_string(),
+            correct: score.correct,
+            asked: score.asked,
+            seconds: start.elapsed().as_secs_f64(),
+        }]);
+        classroom::record(classroom::Record {
+            kind: if predict { "predict" } else { "quiz" },
+            name: about.unwrap_or("all").to_string(),
+            score: score.correct,
+            total: score.asked,
+            outcome: None,
+            elapsed: start.elapsed(),
+            changed: None,
+        });
+        let mut event = Event::new(EventKind::Quiz, "quiz", start.elapsed());
+        event.score = Some((score.correct, score.asked));
+        hooks::fire(&event);
+    }
+}
+
+/// The lessons with more questions in their banks: the bundled banks, then
+/// `banks` under [quiz] in config.toml (relative to the config directory),
+/// then the registered packs', then `--bank`. A bank with mistakes in it
+/// stops the quiz, listing them
+fn quiz_lessons(banks: &[PathBuf]) -> Vec<registry::Lesson> {
+    let mut loaded = Vec::new();
+    let mut errors = Vec::new();
+    for (name, text) in registry::BUNDLED_QUESTIONS {
+        match question_bank::parse(name, text, registry::LESSONS) {
+            Ok(questions) => loaded.extend(questions),
+            Err(problems) => errors.extend(problems),
+        }
+    }
+    let configured = settings::get()
+        .table("quiz")
+        .and_then(|quiz| quiz.strings("banks"))
+        .unwrap_or_default();
+    let config_dir = dirs::config_dir().unwrap_or_default();
+    let packs = packs::list();
+    let paths = configured
+        .into_iter()
+        .map(|path| config_dir.join(path))
+        .chain(packs.iter().filter_map(packs::Pack::questions))
+        .chain(banks.iter().cloned());
+    for path in paths {
+        match question_bank::load(&path, registry::LESSONS) {
+            Ok(questions) => loaded.extend(questions),
+            Err(problems) => errors.extend(problems),
+        }
+    }
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("{}", output::red(error));
+        }
+        eprintln!("Fix the question banks above, or leave them out, to start the quiz.");
+        std::process::exit(1);
+    }
+    question_bank::merge(registry::LESSONS, loaded)
+}
+
+/// Add a run of `key` to the activity log, after the explanation variants it
+/// showed
+fn log_run(key: &str, elapsed: Duration) {
+    history::save(key);
+    let mut events: Vec<activity::Event> = variants::take_shown()
+        .into_iter()
+        .map(|(key, variant)| activity::Event::Shown {
+            key,
+            variant: variant.to_string(),
+        })
+        .collect();
+    events.extend(
+        checkpoint::take_answered()
+            .into_iter()
+            .map(|(key, correct)| activity::Event::Checkpoint { key, correct }),
+    );
+    events.push(activity::Event::Run {
+        key: key.to_string(),
+        seconds: elapsed.as_secs_f64(),
+    });
+    log_activity(events);
+}
+
+/// Add quiz answers to the activity log, each with the explanation variant
+/// of its section the learner saw last, if any
+fn log_answers(answers: &[(String, bool)]) {
+    let log = activity::load();
+    let events = answers
+        .iter()
+        .map(|(key, correct)| activity::Event::Answer {
+            key: key.clone(),
+            variant: activity::last_shown(&log, key).map(String::from),
+            correct: *correct,
+        })
+        .collect();
+    log_activity(events);
+}
+
+fn log_activity(events: Vec<activity::Event>) {
+    if let Err(error) = activity::append(events) {
+        let note = format!("Could not write the activity log: {}", error);
+        eprintln!("{}", output::dim(&note));
+    }
+}
+
+/// Export a lesson, or `lesson::section`, exiting with 1 if that fails
+fn export(lesson: &str, section: Option<&str>, format: &str, path: Option<&std::path::Path>) {
+    let (lesson, section) = match lesson.split_once("::") {
+        Some((lesson, section)) => (lesson, Some(section)),
+        None => (lesson, section),
+    };
+    let lesson = find_lesson(lesson);
+    let section = section.map(|key| find_section(lesson, key));
+    if let Err(error) = export::export(lesson, section, format, path) {
+        eprintln!("{}", output::red(&format!("Cannot export: {}", error)));
+        std::process::exit(1);
+    }
+}
+
+/// Print a playground link for `lesson::section`, exiting with 1 if there
+/// is none
+fn share(lesson: &str, section: Option<&str>, code: bool, open: bool) {
+    let (lesson, section) = match lesson.split_once("::") {
+        Some((lesson, section)) => (lesson, Some(section)),
+        None => (lesson, section),
+    };
+    let lesson = find_lesson(lesson);
+    let Some(key) = section else {
+        eprintln!(
+            "{}",
+            output::red(&format!(
+                "Which section? `rust-learn list {}` shows them; share one with `rust-learn share {} <section>`",
+                lesson.name, lesson.name
+            ))
+        );
+        std::process::exit(1);
+    };
+    let section = find_section(lesson, key);
+    if let Err(error) = share::run(lesson, section, code, open) {
+        eprintln!("{}", output::red(&format!("Cannot share: {}", error)));
+        std::process::exit(1);
+    }
+}
+
+/// Print the code of a lesson, or `lesson::section`, copying it with `copy`
+fn show(lesson: &str, section: Option<&str>, copy: bool) {
+    let (lesson, section) = match lesson.split_once("::") {
+        Some((lesson, section)) => (lesson, Some(section)),
+        None => (lesson, section),
+    };
+    let lesson = find_lesson(lesson);
+    let source = match section.map(|key| find_section(lesson, key)) {
+        Some(section) => lesson.section_source(section).unwrap_or_else(|| {
+            eprintln!(
+                "{}",
+                output::red(&format!(
+                    "{}::{} has no function of that name to show",
+                    lesson.name, section.name
+                ))
+            );
+            std::process::exit(1);
+        }),
+        None => lesson.source,
+    };
+    let source = source.trim_end();
+    println!("{}", highlight::ansi(source));
+    if copy {
+        match clipboard::copy(source) {
+            Ok(to) => eprintln!("{}", output::dim(&format!("Copied to {}", to))),
+            Err(error) => {
+                eprintln!("{}", output::red(&format!("Cannot copy: {}", error)));
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// Each compile-error demo of `lesson` (or just `demo`): what it does wrong,
+/// its code, and what rustc says about it
+fn show_errors(lesson: &str, demo: Option<&str>) {
+    let lesson = find_lesson(lesson);
+    let demos: Vec<&registry::CompileError> = registry::COMPILE_ERRORS
+        .iter()
+        .filter(|error| error.lesson == lesson.name)
+        .collect();
+    if demos.is_empty() {
+        let mut lessons: Vec<&str> = registry::COMPILE_ERRORS
+            .iter()
+            .map(|error| error.lesson)
+            .collect();
+        lessons.dedup();
+        let message = format!("{} has no compile-error demos.", lesson.name);
+        eprintln!(
+            "{} Lessons with some: {}",
+            output::red(&message),
+            lessons.join(", ")
+        );
+        std::process::exit(1);
+    }
+    let shown: Vec<&registry::CompileError> = match demo {
+        Some(name) => demos
+            .iter()
+            .copied()
+            .filter(|error| error.name == name)
+            .collect(),
+        None => demos.clone(),
+    };
+    if shown.is_empty() {
+        let names: Vec<&str> = demos.iter().map(|error| error.name).collect();
+        let message = format!("Unknown demo '{}'.", demo.unwrap_or_default());
+        eprintln!(
+            "{} {} has: {}",
+            output::red(&message),
+            lesson.name,
+            names.join(", ")
+        );
+        std::process::exit(1);
+    }
+
+    let scratch = std::env::temp_dir().join("rust-learn-show-errors");
+    for (index, error) in shown.iter().enumerate() {
+        let heading = format!("{}. {} ({})", index + 1, error.title(), error.name);
+        println!("{}\n", output::bold(&heading));
+        println!("{}\n", highlight::ansi(error.code().trim_end()));
+        let diagnostics = match error.compile(&scratch, output::settings().color) {
+            Ok(diagnostics) => diagnostics,
+            Err(error) => {
+                eprintln!("{}", output::red(&format!("Cannot run rustc: {}", error)));
+                std::process::exit(1);
+            }
+        };
+        if diagnostics.compiled {
+            let note = "This compiles with your rustc, so there is no error to show";
+            println!("{}\n", output::red(note));
+            continue;
+        }
+        println!("{}", output::dim("rustc says:"));
+        println!("{}", diagnostics.stderr.trim_end());
+        println!();
+    }
+    let _ = std::fs::remove_dir_all(&scratch);
+}
+
+fn show_last_report() {
+    let Some(path) = crash::last_report() else {
+        println!("No crash reports. rust-learn writes one when it panics.");
+        return;
+    };
+    match std::fs::read_to_string(&path) {
+        Ok(text) => {
+            println!("{}\n", output::dim(&path.display().to_string()));
+            print!("{}", text);
+        }
+        Err(error) => {
+            let message = format!("Cannot read {}: {}", path.display(), error);
+            eprintln!("{}", output::red(&message));
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Save what was completed, or say on stderr why it could not be
+fn save_progress(progress: &Progress) {
+    if let Err(error) = progress.save() {
+        let note = format!("Could not save progress: {}", error);
+        eprintln!("{}", output::dim(&note));
+    }
+}
+
+/// The lessons `filter` lets through, by chapter, or the sections of one
+fn list(lesson: Option<&str>, filter: &Filter) {
+    let progress = Progress::load();
+    let calibration = Calibration::load();
+    let Some(name) = lesson else {
+        println!("{}", progress_bar(&progress));
+        let width = registry::name_width();
+        let mut listed = 0;
+        for (index, title) in registry::CHAPTERS.iter().enumerate() {
+            let lessons: Vec<&registry::Lesson> = registry::LESSONS
+                .iter()
+                .filter(|lesson| usize::from(lesson.chapter) == index + 1 && filter.matches(lesson))
+                .collect();
+            if lessons.is_empty() {
+                continue;
+            }
+            listed += lessons.len();
+            println!(
+                "\n{}",
+                output::bold(&format!("Chapter {}: {}", index + 1, title))
+            );
+            for lesson in lessons {
+                println!(
+                    "  [{}] {:<4} {:<width$} {:>2} sections {:>3} min  {}",
+                    mark(progress.is_complete(lesson.name)),
+                    registry::number(lesson),
+                    lesson.name,
+                    lesson.sections.len(),
+                    calibration.minutes(lesson),
+                    lesson.title
+                );
+            }
+        }
+        if listed == 0 {
+            println!("\nNo lesson has {}", filter.to_args());
+        }
+        return;
+    };
+
+    let lesson = find_lesson(name);
+    println!("{}", output::bold(lesson.title));
+    println!(
+        "Lesson {} in chapter {}, {}",
+        registry::number(lesson),
+        lesson.chapter,
+        registry::chapter_title(lesson.chapter)
+    );
+    if !lesson.requires.is_empty() {
+        println!("After: {}", lesson.requires.join(", "));
+    }
+    match calibration.get(lesson.name) {
+        Some(estimate) => println!(
+            "Difficulty: {}, about {} minutes for you (as written: {}, {} minutes)",
+            estimate.difficulty.name(),
+            estimate.minutes,
+            lesson.difficulty.name(),
+            lesson.estimated_minutes()
+        ),
+        None => println!(
+            "Difficulty: {}, about {} minutes",
+            lesson.difficulty.name(),
+            lesson.estimated_minutes()
+        ),
+    }
+    if !lesson.tags.is_empty() {
+        println!("Tags: {}", lesson.tags.join(", "));
+    }
+    if let Some(time) = progress.completed_at(lesson.name) {
+        println!("Completed {}", progress::format_timestamp(time));
+    }
+    match progress.completed_version(lesson.name) {
+        Some(version) if version < lesson.version() => println!(
+            "Version {}; you completed version {}{}",
+            lesson.version(),
+            version,
+            if progress.is_marked_for_review(lesson.name) {
+                ", and marked it for review"
+            } else {
+                ""
+            }
+        ),
+        _ => println!("Version {}", lesson.version()),
+    }
+    println!();
+    for (index, section) in lesson.sections.iter().enumerate() {
+        println!(
+            "  [{}] {:>2}. {:<28} {}",
+            mark(progress.is_section_complete(lesson.name, section.name)),
+            index + 1,
+            section.name,
+            section.title
+        );
+    }
+}
+
+/// `lessons` as `list --json` prints them:
+///
+/// ```text
+/// [{"name":"variables","title":"...","chapter":1,"difficulty":"beginner",
+///   "tags":["basics"],"sections":[{"name":"mutability","title":"..."}],
+///   "prerequisites":[],"estimated_minutes":12,"calibrated":null}, ...]
+/// ```
+///
+/// `calibrated` is `{"minutes":..,"difficulty":..}` after `stats recalibrate`
+fn lessons_json(lessons: &[registry::Lesson]) -> Value {
+    let calibration = Calibration::load();
+    let strings = |names: &[&str]| Value::Array(names.iter().map(|&name| name.into()).collect());
+    let lessons = lessons.iter().map(|lesson| {
+        let sections = lesson.sections.iter().map(|section| {
+            Value::Object(vec![
+                ("name".to_string(), section.name.into()),
+                ("title".to_string(), section.title.into()),
+            ])
+        });
+        Value::Object(vec![
+            ("name".to_string(), lesson.name.into()),
+            ("title".to_string(), lesson.title.into()),
+            ("chapter".to_string(), Value::Number(lesson.chapter.into())),
+            ("difficulty".to_string(), lesson.difficulty.name().into()),
+            ("tags".to_string(), strings(lesson.tags)),
+            ("sections".to_string(), Value::Array(sections.collect())),
+            ("prerequisites".to_string(), strings(lesson.requires)),
+            (
+                "estimated_minutes".to_string(),
+                Value::Number(lesson.estimated_minutes() as f64),
+            ),
+            (
+                "calibrated".to_string(),
+                calibration
+                    .get(lesson.name)
+                    .map_or(Value::Null, |estimate| {
+                        Value::Object(vec![
+                            (
+                                "minutes".to_string(),
+                                Value::Number(estimate.minutes as f64),
+                            ),
+                            ("difficulty".to_string(), estimate.difficulty.name().into()),
+                        ])
+                    }),
+            ),
+        ])
+    });
+    Value::Array(lessons.collect())
+}
+
+/// `[x]` for complete, `[ ]` for not
+fn mark(complete: bool) -> &'static str {
+    if complete { "x" } else { " " }
+}
+
+/// `[########------------]  7/23 lessons complete (30%)`
+fn progress_bar(progress: &Progress) -> String {
+    const WIDTH: usize = 20;
+    let total = registry::LESSONS.len();
+    let done = registry::LESSONS
+        .iter()
+        .filter(|lesson| progress.is_complete(lesson.name))
+        .count();
+    let filled = done * WIDTH / total;
+    format!(
+        "[{}{}] {}/{} lessons complete ({}%)",
+        "#".repeat(filled),
+        "-".repeat(WIDTH - filled),
+        done,
+        total,
+        done * 100 / total
+    )
+}
+
+fn search(words: &[String]) {
+    let words: Vec<String> = words.iter().map(|word| word.to_lowercase()).collect();
+    let matches = |texts: &[&str]| {
+        let haystack = texts.join(" ").to_lowercase();
+        words.iter().all(|word| haystack.contains(word.as_str()))
+    };
+
+    let mut found = 0;
+    for lesson in registry::LESSONS {
+        if matches(&[lesson.name, lesson.title]) {
+            let name = format!("{:<40}", lesson.name);
+            println!("{} {}", output::bold(&name), lesson.title);
+            found += 1;
+        }
+        for section in lesson.sections {
+            if matches(&[section.name, section.title]) {
+                let target = format!("{}::{}", lesson.name, section.name);
+                println!("{:<40} {}", target, section.title);
+                found += 1;
+            }
+        }
+    }
+
+    if found == 0 {
+        println!("Nothing matches '{}'", words.join(" "));
+    } else if output::settings().verbose() {
+        println!("\n{} matches", found);
+    }
+}
+
+/// Show a numbered menu of every registered lesson until the user quits
+fn lesson_menu() {
+    let count = registry::LESSONS.len();
+    let prompt = format!("Choose a lesson (1-{}) or 'quit': ", count);
+
+    let width = registry::name_width();
+
+    loop {
+        println!("{}\n", output::bold("=== Rust Learning Lessons ==="));
+        for (index, lesson) in registry::LESSONS.iter().enumerate() {
+            println!("{:>2}. {:<width$} {}", index + 1, lesson.name, lesson.title);
+        }
+        println!();
+
+        match input::validated_choice(&prompt, count) {
+            MenuChoice::Item(index) => run_lesson(registry::LESSONS[index].name, None),
+            MenuChoice::Quit => break,
+        }
+    }
+}
+
+/// The lesson called `name` or, failing that, the one it is an obvious typo or
+/// abbreviation of; otherwise exit with a suggestion or the list of lessons
+fn find_lesson(name: &str) -> &'static registry::Lesson {
+    resolve_lesson(name).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    })
+}
+
+/// `find_lesson` without exiting: the error is the message to print
+fn resolve_lesson(name: &str) -> Result<&'static registry::Lesson, String> {
+    if let Some(lesson) = registry::find(name) {
+        return Ok(lesson);
+    }
+
+    match registry::closest(name) {
+        Some(guess) if guess.confident => {
+            let note = format!("'{}' is not a lesson, using '{}'", name, guess.name);
+            eprintln!("{}", output::dim(&note));
+            return Ok(registry::find(guess.name).expect("closest returns registered names"));
+        }
+        Some(guess) => {
+            return Err(format!(
+                "{} Did you mean '{}'?",
+                output::red(&format!("Unknown lesson '{}'.", name)),
+                guess.name
+            ));
+        }
+        None => {}
+    }
+
+    let mut message = format!(
+        "{} Available lessons:",
+        output::red(&format!("Unknown lesson '{}'.", name))
+    );
+    for lesson in registry::LESSONS {
+        message += &format!("\n  {}", lesson.name);
+    }
+    Err(message)
+}
+
+/// The section of `lesson` called (or numbered) `key`, or exit with its sections
+fn find_section(lesson: &'static registry::Lesson, key: &str) -> &'static registry::Section {
+    resolve_section(lesson, key).unwrap_or_else(|message| {
+        eprintln!("{}", message);
+        std::process::exit(1);
+    })
+}
+
+/// `find_section` without exiting: the error is the message to print
+fn resolve_section(
+    lesson: &'static registry::Lesson,
+    key: &str,
+) -> Result<&'static registry::Section, String> {
+    if let Some(section) = lesson.section(key) {
+        return Ok(section);
+    }
+
+    let mut message = format!(
+        "{} Available sections:",
+        output::red(&format!("Unknown section '{}' in {}.", key, lesson.name))
+    );
+    for (index, section) in lesson.sections.iter().enumerate() {
+        message += &format!(
+            "\n  {:>2}. {:<28} {}",
+            index + 1,
+            section.name,
+            section.title
+        );
+    }
+    Err(message)
+}
@@ -0,0 +1,297 @@
+//! Code Metrics
+//!
+//! `rust-learn dev metrics src/` measures every function in a file or a
+//! directory of `.rs` files:
+//!
+//! - lines, from the `fn` to its closing brace
+//! - nesting depth: how many `if`, `match`, loops and closures deep the
+//!   deepest code is (an `else if` chain counts once)
+//! - complexity, counted the way McCabe's cyclomatic complexity roughly is:
+//!   1, plus one for every `if`, loop, `&&`, `||` and `?`, plus one for each
+//!   `match` arm after the first
+//!
+//! The numbers are for the refactoring kata (measure, split a function, and
+//! measure again) and for reviewing lesson code: `--max-complexity` makes
+//! the command fail when a function goes over, and `self-test` holds the
+//! lessons to `LESSON_LIMITS`.
+
+use rust_learn_core::output;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::spanned::Spanned;
+use syn::visit::{self, Visit};
+use syn::{BinOp, Block, Expr, ExprIf, ImplItemFn, Item, ItemFn, ItemImpl, Type};
+
+/// What one function measures
+#[derive(Debug, PartialEq)]
+pub struct Metrics {
+    /// `name`, or `Type::name` for a method
+    pub name: String,
+    pub line: usize,
+    pub lines: usize,
+    pub depth: usize,
+    pub complexity: usize,
+}
+
+/// The most complexity and nesting `self-test` allows in a lesson function
+pub const LESSON_LIMITS: (usize, usize) = (15, 4);
+
+/// Print the metrics of every function under `path`; `Err` on a file that
+/// cannot be read (or parsed, when it is the only one), or when a function is
+/// over `max_complexity`
+pub fn report(path: &Path, max_complexity: Option<usize>) -> Result<(), String> {
+    let files = rust_files(path).map_err(|error| format!("{}: {}", path.display(), error))?;
+    if files.is_empty() {
+        return Err(format!("no .rs files in {}", path.display()));
+    }
+
+    let mut over = Vec::new();
+    for file in &files {
+        let source = fs::read_to_string(file)
+            .map_err(|error| format!("cannot read {}: {}", file.display(), error))?;
+        let functions = match measure(&source) {
+            Ok(functions) => functions,
+            Err(error) if files.len() > 1 => {
+                let note = format!("{}: skipped, not valid Rust ({})", file.display(), error);
+                println!("{}", output::dim(&note));
+                continue;
+            }
+            Err(error) => return Err(format!("{}: {}", file.display(), error)),
+        };
+        if functions.is_empty() {
+            continue;
+        }
+        println!("{}", output::bold(&file.display().to_string()));
+        println!(
+            "  {:<40} {:>5} {:>6} {:>6} {:>10}",
+            "function", "line", "lines", "depth", "complexity"
+        );
+        for function in functions {
+            let row = format!(
+                "  {:<40} {:>5} {:>6} {:>6} {:>10}",
+                function.name, function.line, function.lines, function.depth, function.complexity
+            );
+            if max_complexity.is_some_and(|max| function.complexity > max) {
+                println!("{}", output::red(&row));
+                over.push(format!(
+                    "{}:{} {}",
+                    file.display(),
+                    function.line,
+                    function.name
+                ));
+            } else {
+                println!("{}", row);
+            }
+        }
+    }
+
+    match max_complexity {
+        Some(max) if !over.is_empty() => Err(format!(
+            "{} over complexity {}:\n  {}",
+            if over.len() == 1 {
+                "a function is"
+            } else {
+                "functions are"
+            },
+            max,
+            over.join("\n  ")
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// `path` itself, or every `.rs` file under it (skipping `target` and hidden
+/// directories), in path order
+fn rust_files(path: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !path.is_dir() {
+        fs::metadata(path)?;
+        return Ok(vec![path.to_path_buf()]);
+    }
+    let mut files = Vec::new();
+    let mut dirs = vec![path.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if path.is_dir() {
+                if name != "target" && !name.starts_with('.') {
+                    dirs.push(path);
+                }
+            } else if name.ends_with(".rs") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Every function in `source` with a body, nested ones and methods included,
+/// in source order
+pub fn measure(source: &str) -> syn::Result<Vec<Metrics>> {
+    let file = syn::parse_file(source)?;
+    let mut functions = Functions::default();
+    functions.visit_file(&file);
+    functions.found.sort_by_key(|metrics| metrics.line);
+    Ok(functions.found)
+}
+
+/// Finds the functions, and the type each method belongs to
+#[derive(Default)]
+struct Functions {
+    found: Vec<Metrics>,
+    self_type: Option<String>,
+}
+
+impl Functions {
+    fn add(&mut self, name: String, item: &impl Spanned, block: &Block) {
+        let span = item.span();
+        let mut body = Body {
+            depth: 0,
+            max_depth: 0,
+            complexity: 1,
+        };
+        body.visit_block(block);
+        self.found.push(Metrics {
+            name,
+            line: span.start().line,
+            lines: span.end().line - span.start().line + 1,
+            depth: body.max_depth,
+            complexity: body.complexity,
+        });
+    }
+}
+
+impl<'ast> Visit<'ast> for Functions {
+    fn visit_item_fn(&mut self, function: &'ast ItemFn) {
+        self.add(function.sig.ident.to_string(), function, &function.block);
+        visit::visit_item_fn(self, function);
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        let outer = self.self_type.replace(match &*item.self_ty {
+            Type::Path(path) => path
+                .path
+                .segments
+                .last()
+                .map_or_else(|| "_".to_string(), |segment| segment.ident.to_string()),
+            _ => "_".to_string(),
+        });
+        visit::visit_item_impl(self, item);
+        self.self_type = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, method: &'ast ImplItemFn) {
+        let name = match &self.self_type {
+            Some(self_type) => format!("{}::{}", self_type, method.sig.ident),
+            None => method.sig.ident.to_string(),
+        };
+        self.add(name, method, &method.block);
+        visit::visit_impl_item_fn(self, method);
+    }
+}
+
+/// Walks one function body, leaving nested items to `Functions`
+struct Body {
+    depth: usize,
+    max_depth: usize,
+    complexity: usize,
+}
+
+impl Body {
+    fn nested(&mut self, visit: impl FnOnce(&mut Self)) {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        visit(self);
+        self.depth -= 1;
+    }
+}
+
+impl<'ast> Visit<'ast> for Body {
+    fn visit_item(&mut self, _: &'ast Item) {}
+
+    fn visit_expr_if(&mut self, if_expr: &'ast ExprIf) {
+        self.complexity += 1;
+        self.nested(|body| {
+            body.visit_expr(&if_expr.cond);
+            body.visit_block(&if_expr.then_branch);
+        });
+        match if_expr.else_branch.as_ref().map(|(_, branch)| &**branch) {
+            Some(Expr::If(else_if)) => self.visit_expr_if(else_if),
+            Some(branch) => self.nested(|body| body.visit_expr(branch)),
+            None => {}
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Match(match_expr) => {
+                self.complexity += match_expr.arms.len().saturating_sub(1);
+                self.nested(|body| visit::visit_expr(body, expr));
+            }
+            Expr::ForLoop(_) | Expr::While(_) | Expr::Loop(_) => {
+                self.complexity += 1;
+                self.nested(|body| visit::visit_expr(body, expr));
+            }
+            Expr::Closure(_) => self.nested(|body| visit::visit_expr(body, expr)),
+            Expr::Binary(binary) if matches!(binary.op, BinOp::And(_) | BinOp::Or(_)) => {
+                self.complexity += 1;
+                visit::visit_expr(self, expr);
+            }
+            Expr::Try(_) => {
+                self.complexity += 1;
+                visit::visit_expr(self, expr);
+            }
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let source = "\
+fn grade(score: u32) -> char {
+    if score >= 90 {
+        'A'
+    } else if score >= 80 && score < 90 {
+        'B'
+    } else {
+        'C'
+    }
+}
+
+struct Parser;
+
+impl Parser {
+    fn count(&self, words: &[&str]) -> Result<usize, String> {
+        let mut count = 0;
+        for word in words {
+            match word.len() {
+                0 => return Err(\"empty word\".into()),
+                1 => {}
+                _ => count += 1,
+            }
+        }
+        Ok(count)
+    }
+}
+";
+        let metrics = measure(source).unwrap();
+        let found: Vec<(&str, usize, usize, usize)> = metrics
+            .iter()
+            .map(|function| {
+                (
+                    function.name.as_str(),
+                    function.lines,
+                    function.depth,
+                    function.complexity,
+                )
+            })
+            .collect();
+        assert_eq!(found, [("grade", 9, 1, 4), ("Parser::count", 11, 2, 4)]);
+    }
+}
@@ -0,0 +1,203 @@
+//! `rust-learn open <exercise|lesson>`: open the file to work on in the
+//! learner's editor, at the line that matters, so the check-edit loop does
+//! not start with hunting for a path.
+//!
+//! - An exercise (`ownership1`) opens at its `// I AM NOT DONE` line, with
+//!   the exercises directory's Cargo.toml brought up to date first
+//! - A lesson (`ownership`, or `ownership slices` / `ownership::slices` for a
+//!   section) opens its source at the lesson's `pub fn`, or the section's.
+//!   In a checkout of rust-learn that is the file under
+//!   `crates/rust-learn-content/src`; anywhere else a copy is written to
+//!   `lessons/` in the workspace (`dirs::workspace_dir`), once, so notes made
+//!   in it are kept
+//!
+//! The editor is `command` under `[editor]` in config.toml, with `{file}`
+//! and `{line}` filled in, else `$VISUAL`, else `$EDITOR`. For those, the
+//! line is passed the way the editor takes it (`+12 file` for vi, nano and
+//! emacs; `--goto file:12` for VS Code; `file:12` for Sublime, Zed and
+//! Helix); other editors just get the file.
+//!
+//! ```toml
+//! [editor]
+//! command = "code --goto {file}:{line}"
+//! ```
+
+use crate::{resolve_lesson, verify};
+use rust_learn_content::registry::Lesson;
+use rust_learn_core::{dirs, exercises, output, settings};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Where the lessons' source files are in a checkout
+const CONTENT_SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../rust-learn-content/src");
+
+/// Open `target` (an exercise in `dir`, a lesson, or `lesson::section`), or
+/// with `print`, only print `file:line`
+pub fn run(target: &str, section: Option<&str>, dir: &Path, print: bool) -> Result<(), String> {
+    let place = locate(target, section, dir)?;
+    if print {
+        println!("{}:{}", place.file.display(), place.line);
+        return Ok(());
+    }
+    let command = editor().ok_or(
+        "no editor to open it with: set $EDITOR, or `command` under [editor] in config.toml",
+    )?;
+    let args = editor_args(&command, &place.file.to_string_lossy(), place.line);
+    let (program, args) = args.split_first().ok_or("the editor command is empty")?;
+    if let Some(exercise) = &place.exercise {
+        let hint = format!("Check it with: rust-learn verify {}", exercise);
+        println!("{}", output::dim(&hint));
+    }
+    let status = Command::new(program)
+        .args(args)
+        .status()
+        .map_err(|error| format!("cannot start {}: {}", program, error))?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", program, status));
+    }
+    Ok(())
+}
+
+/// Where to open
+struct Place {
+    file: PathBuf,
+    line: usize,
+    /// The exercise's name, when it is one
+    exercise: Option<String>,
+}
+
+fn locate(target: &str, section: Option<&str>, dir: &Path) -> Result<Place, String> {
+    let (target, section) = match target.split_once("::") {
+        Some((lesson, section)) => (lesson, Some(section)),
+        None => (target, section),
+    };
+    if section.is_none()
+        && let Ok(mut found) = exercises::find(dir, &[])
+        && let Some(index) = found.iter().position(|exercise| exercise.name == target)
+    {
+        // For rust-analyzer to pick the exercise up once the editor opens it
+        verify::keep_project(dir, &found);
+        let exercise = found.swap_remove(index);
+        let source = fs::read_to_string(&exercise.path)
+            .map_err(|error| format!("cannot read {}: {}", exercise.path.display(), error))?;
+        let line = source
+            .lines()
+            .position(|line| line.trim() == exercises::NOT_DONE)
+            .map_or(1, |index| index + 1);
+        return Ok(Place {
+            file: exercise.path,
+            line,
+            exercise: Some(exercise.name),
+        });
+    }
+
+    let lesson = resolve_lesson(target)?;
+    let line = match section {
+        Some(key) => {
+            let section = lesson.section(key).ok_or_else(|| {
+                format!(
+                    "there is no section '{}' in {} (see `rust-learn list {}`)",
+                    key, lesson.name, lesson.name
+                )
+            })?;
+            lesson.function_line(section.name).unwrap_or(1)
+        }
+        // The function that runs the whole lesson comes first
+        None => lesson
+            .source
+            .lines()
+            .position(|line| line.starts_with("pub fn "))
+            .map_or(1, |index| index + 1),
+    };
+    Ok(Place {
+        file: lesson_file(lesson)?,
+        line,
+        exercise: None,
+    })
+}
+
+/// The lesson's source file in this checkout, else a copy in the workspace
+fn lesson_file(lesson: &Lesson) -> Result<PathBuf, String> {
+    if let Ok(entries) = fs::read_dir(CONTENT_SRC) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if fs::read_to_string(&path).is_ok_and(|text| text == lesson.source) {
+                return Ok(fs::canonicalize(&path).unwrap_or(path));
+            }
+        }
+    }
+    let dir = dirs::workspace_dir()
+        .ok_or("no data directory (set RUST_LEARN_DATA_DIR or HOME)")?
+        .join("lessons");
+    let path = dir.join(format!("{}.rs", lesson.name));
+    if !path.exists() {
+        fs::create_dir_all(&dir)
+            .and_then(|()| fs::write(&path, lesson.source))
+            .map_err(|error| format!("cannot write {}: {}", path.display(), error))?;
+    }
+    Ok(path)
+}
+
+/// `command` under `[editor]`, else `$VISUAL`, else `$EDITOR`
+fn editor() -> Option<String> {
+    let configured = settings::get()
+        .table("editor")
+        .and_then(|table| table.str("command"))
+        .map(String::from);
+    configured
+        .or_else(|| std::env::var("VISUAL").ok())
+        .or_else(|| std::env::var("EDITOR").ok())
+        .filter(|command| !command.trim().is_empty())
+}
+
+/// The program and arguments that open `file` at `line` with `command`
+fn editor_args(command: &str, file: &str, line: usize) -> Vec<String> {
+    let mut words: Vec<String> = command.split_whitespace().map(String::from).collect();
+    if command.contains("{file}") {
+        // Filled in after splitting, so a path with spaces stays one argument
+        for word in &mut words {
+            *word = word
+                .replace("{file}", file)
+                .replace("{line}", &line.to_string());
+        }
+        return words;
+    }
+    let program = words.first().map_or("", |program| {
+        Path::new(program)
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or(program)
+    });
+    let at_line = match program {
+        "vi" | "vim" | "nvim" | "nano" | "emacs" | "emacsclient" | "micro" | "kak" => {
+            vec![format!("+{}", line), file.to_string()]
+        }
+        "code" | "code-insiders" | "codium" => {
+            vec!["--goto".to_string(), format!("{}:{}", file, line)]
+        }
+        "subl" | "zed" | "hx" | "helix" => vec![format!("{}:{}", file, line)],
+        _ => vec![file.to_string()],
+    };
+    words.extend(at_line);
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_editor_args() {
+        assert_eq!(editor_args("nvim", "a.rs", 12), ["nvim", "+12", "a.rs"]);
+        assert_eq!(
+            editor_args("/usr/bin/code --wait", "a.rs", 3),
+            ["/usr/bin/code", "--wait", "--goto", "a.rs:3"]
+        );
+        assert_eq!(
+            editor_args("idea --line {line} {file}", "my dir/a.rs", 7),
+            ["idea", "--line", "7", "my dir/a.rs"]
+        );
+        assert_eq!(editor_args("gedit", "a.rs", 2), ["gedit", "a.rs"]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Content Packs
+//!
+//! `rust-learn pack add git <url>` fetches a teacher's pack of questions and
+//! exercises (`core::packs`) into the data directory: it clones the
+//! repository the first time and pulls it after that, so the same command
+//! picks up each week's new exercises on every machine in the class. The
+//! pack is checked (`packs::lint`) before it is registered; a pull that
+//! brings in mistakes is undone, leaving the last good version in place.
+//! Its exercises as fetched are pinned (`packs::pin`) for `exercise reset`.
+//!
+//! `pack list` says, for each lesson a pack has exercises for, whether
+//! `verify` uses them, leaves them shadowed by another version, or stops at
+//! a collision (`packs::merge`); `pack add` lists the collisions too, so a
+//! teacher's "ownership" never replaces the shipped one by accident.
+//! `pack remove` deletes a pack again (`--dry-run` lists what goes first).
+//!
+//! git itself does the work (`toolchain::git`), so its credentials and
+//! settings apply as usual. Built without the `git` feature, `pack add git`
+//! says so and does nothing.
+
+use rust_learn_content::registry;
+use rust_learn_core::output;
+use rust_learn_core::packs::{self, LessonSet, Pack};
+use std::path::Path;
+
+/// Where `verify` looks for the shipped exercises unless told otherwise
+const BUILTIN_DIR: &str = "exercises";
+
+/// Every lesson's exercises: the shipped ones, then each pack's
+fn lesson_sets(packs: &[Pack]) -> Vec<LessonSet> {
+    let order: Vec<&str> = registry::LESSONS.iter().map(|lesson| lesson.name).collect();
+    let builtin = Some(Path::new(BUILTIN_DIR)).filter(|dir| dir.is_dir());
+    packs::lesson_sets(builtin, packs, &order)
+}
+
+/// Whether `set` is used, shadowed, or collides with another source's
+/// version of its lesson
+fn status(set: &LessonSet, sets: &[LessonSet], precedence: &[String]) -> String {
+    let same: Vec<LessonSet> = sets
+        .iter()
+        .filter(|other| other.lesson == set.lesson)
+        .cloned()
+        .collect();
+    match packs::merge(same, precedence) {
+        Ok(merged) => match merged
+            .shadowed
+            .iter()
+            .find(|(shadowed, _)| shadowed.id() == set.id())
+        {
+            Some((_, by)) => output::dim(&format!("shadowed by {}", by)),
+            None => output::green("in use"),
+        },
+        Err(_) => output::red("collides"),
+    }
+}
+
+/// Print the registered packs, and for each of their lessons whether
+/// `verify` uses its exercises
+pub fn list() {
+    let packs = packs::list();
+    if packs.is_empty() {
+        println!("No packs yet: `rust-learn pack add git <url>` adds one");
+        return;
+    }
+    let sets = lesson_sets(&packs);
+    let precedence = packs::precedence();
+    for pack in &packs {
+        let name = format!("{:<20}", pack.name);
+        println!("{} {}", output::bold(&name), pack.url);
+        if pack.questions().is_some() {
+            println!("  {}", output::dim("questions: added to the quiz"));
+        }
+        for set in sets.iter().filter(|set| set.source == pack.name) {
+            println!(
+                "  {:<28} {:>2} exercises, {}",
+                set.id(),
+                set.exercises.len(),
+                status(set, &sets, &precedence)
+            );
+        }
+    }
+    for problem in collisions(&packs) {
+        eprintln!("{}", output::red(&problem));
+    }
+}
+
+/// The lessons more than one source has exercises for and
+/// `pack_precedence` does not decide (`packs::merge`)
+fn collisions(packs: &[Pack]) -> Vec<String> {
+    packs::merge(lesson_sets(packs), &packs::precedence())
+        .err()
+        .unwrap_or_default()
+}
+
+/// Clone or pull the pack at `url`, check it and register it
+#[cfg(feature = "git")]
+pub fn add_git(url: &str) -> Result<(), String> {
+    use rust_learn_core::{exercises, toolchain};
+    use std::fs;
+
+    let name = packs::name_from_url(url).ok_or_else(|| {
+        format!(
+            "cannot name a pack after {}: its last part should be letters, digits, '.', '-' and '_'",
+            url
+        )
+    })?;
+    let root = packs::root().ok_or("no data directory (set RUST_LEARN_DATA_DIR or HOME)")?;
+    fs::create_dir_all(&root)
+        .map_err(|error| format!("cannot create {}: {}", root.display(), error))?;
+    let dir = root.join(&name);
+
+    let git = |dir: &Path, args: &[&str]| -> Result<String, String> {
+        let output = toolchain::git(dir, args)
+            .map_err(|error| format!("could not run git (is it installed?): {}", error))?;
+        if !output.success {
+            return Err(format!("git {} failed: {}", args[0], output.stderr.trim()));
+        }
+        Ok(output.stdout)
+    };
+
+    let previous = if dir.join(".git").exists() {
+        let origin = git(&dir, &["remote", "get-url", "origin"])?;
+        if origin.trim() != url {
+            return Err(format!(
+                "a pack named {} already comes from {}; remove it first (`rust-learn pack remove {}`)",
+                name,
+                origin.trim(),
+                name
+            ));
+        }
+        let head = git(&dir, &["rev-parse", "HEAD"])?;
+        git(&dir, &["pull", "--quiet", "--ff-only"])?;
+        Some(head.trim().to_string())
+    } else {
+        git(&root, &["clone", "--quiet", "--", url, &name])?;
+        None
+    };
+
+    let problems = packs::lint(&dir, rust_learn_content::registry::LESSONS);
+    if !problems.is_empty() {
+        for problem in &problems {
+            eprintln!("{}", output::red(problem));
+        }
+        match &previous {
+            Some(head) => {
+                git(&dir, &["reset", "--quiet", "--hard", head])?;
+                return Err(format!(
+                    "{} has {} problems; kept the version from before the pull",
+                    name,
+                    problems.len()
+                ));
+            }
+            None => {
+                let _ = fs::remove_dir_all(&dir);
+                return Err(format!(
+                    "{} has {} problems; it was not added",
+                    name,
+                    problems.len()
+                ));
+            }
+        }
+    }
+
+    // Each exercise as the teacher wrote it, for `exercise reset`: from the
+    // commit, since a pull leaves the learner's changes in the files
+    let mut skeletons = Vec::new();
+    for exercise in exercises::find(&dir.join("exercises"), &[]).unwrap_or_default() {
+        let file = format!("{}/{}.rs", exercise.lesson, exercise.name);
+        if let Ok(text) = git(&dir, &["show", &format!("HEAD:exercises/{}", file)]) {
+            skeletons.push((file, text));
+        }
+    }
+    packs::pin(&root, &name, &skeletons)
+        .map_err(|error| format!("cannot keep {}'s exercises: {}", name, error))?;
+    packs::register(&root, &name, url)
+        .map_err(|error| format!("cannot register {}: {}", name, error))?;
+    let verb = if previous.is_some() {
+        "Updated"
+    } else {
+        "Added"
+    };
+    println!("{} pack {}", verb, output::bold(&name));
+    let packs = packs::list_in(&root);
+    for set in lesson_sets(&packs).iter().filter(|set| set.source == name) {
+        println!("  {} ({} exercises)", set.id(), set.exercises.len());
+    }
+    let collisions = collisions(&packs);
+    for problem in &collisions {
+        eprintln!("{}", output::red(problem));
+    }
+    if !collisions.is_empty() {
+        eprintln!(
+            "`verify` stops at these until they are sorted out; `verify <source>/<lesson>` still works"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "git"))]
+pub fn add_git(_url: &str) -> Result<(), String> {
+    Err("this rust-learn was built without the `git` feature, which `pack add git` needs".into())
+}
+
+/// Delete the pack `name` and unregister it; with `dry_run`, only list what
+/// that removes
+pub fn remove(name: &str, dry_run: bool) -> Result<(), String> {
+    let root = packs::root().ok_or("no data directory (set RUST_LEARN_DATA_DIR or HOME)")?;
+    let transaction = packs::removal(&root, name)
+        .map_err(|error| format!("cannot read {}: {}", root.join(name).display(), error))?
+        .ok_or_else(|| {
+            format!(
+                "no pack is named {} (`rust-learn pack list` shows them)",
+                name
+            )
+        })?;
+    crate::carry_out(transaction, dry_run)?;
+    if !dry_run {
+        println!("Removed pack {}", output::bold(name));
+    }
+    Ok(())
+}
@@ -0,0 +1,128 @@
+//! Predict the Output
+//!
+//! `rust-learn quiz --predict` shows a lesson's snippet (`Lesson::snippets`),
+//! a small function cut from the lesson's source, and asks what it prints.
+//! The snippet then really runs, in a child `rust-learn snippet` like the
+//! lessons `export` captures, and the answer is compared with its output
+//! line by line, ignoring trailing spaces. A wrong answer shows the
+//! difference.
+
+use rust_learn_content::registry::{Lesson, Snippet};
+use rust_learn_core::diff::{self, Line};
+use rust_learn_core::output::{self, Glyph};
+use rust_learn_core::quiz::QuizScore;
+use rust_learn_core::random::Rng;
+use rust_learn_core::{highlight, input, toolchain};
+
+/// Ask about up to `questions` snippets, stopping early if the user quits.
+/// With `about`, only that lesson's snippets
+pub fn run(lessons: &[Lesson], about: Option<&str>, questions: usize, rng: &mut Rng) -> QuizScore {
+    let mut pool: Vec<(&Lesson, &Snippet)> = lessons
+        .iter()
+        .filter(|lesson| about.is_none_or(|name| lesson.name == name))
+        .flat_map(|lesson| lesson.snippets.iter().map(move |snippet| (lesson, snippet)))
+        .collect();
+    rng.shuffle(&mut pool);
+
+    let total = questions.min(pool.len());
+    let mut score = QuizScore {
+        asked: 0,
+        correct: 0,
+        answers: Vec::new(),
+    };
+    if total == 0 {
+        match about {
+            Some(name) => println!("{} has no snippets to predict yet", name),
+            None => println!("No lesson has snippets to predict yet"),
+        }
+        return score;
+    }
+
+    println!("=== Predict the Output ===\n");
+
+    for (number, (lesson, snippet)) in pool.into_iter().take(total).enumerate() {
+        println!("Question {}/{}: What does this print?\n", number + 1, total);
+        let source = highlight::ansi(lesson.function_source(snippet.name).unwrap_or_default());
+        for line in source.lines() {
+            println!("    {}", line);
+        }
+        println!("\nType the output, then an empty line ('quit' to stop):");
+        let Some(answer) = read_answer() else {
+            break;
+        };
+
+        let printed = match capture(lesson, snippet) {
+            Ok(printed) => printed,
+            Err(error) => {
+                println!("{}\n", output::red(&error));
+                continue;
+            }
+        };
+        score.asked += 1;
+        let lines = diff::lines(&printed, &answer);
+        let correct = lines.iter().all(Line::is_same);
+        score
+            .answers
+            .push((format!("{}::{}", lesson.name, snippet.name), correct));
+        if correct {
+            score.correct += 1;
+            println!(
+                "{}\n",
+                output::status(Glyph::Ok, "Correct!", output::green, 0)
+            );
+            continue;
+        }
+        println!(
+            "{} {} is what it printed, {} what you wrote:",
+            output::status(Glyph::Fail, "Not quite.", output::red, 0),
+            output::green("-"),
+            output::red("+")
+        );
+        for line in lines {
+            match line {
+                Line::Same(text) => println!("  {}", text),
+                Line::Removed(text) => println!("{}", output::green(&format!("- {}", text))),
+                Line::Added(text) => println!("{}", output::red(&format!("+ {}", text))),
+            }
+        }
+        println!();
+    }
+
+    println!("Score: {}/{}", score.correct, score.asked);
+    score
+}
+
+/// Lines up to an empty one; `None` when the user quits first
+fn read_answer() -> Option<String> {
+    let mut lines = Vec::new();
+    loop {
+        match input::prompt_input("> ") {
+            None if lines.is_empty() => return None,
+            Some(line) if lines.is_empty() && line.eq_ignore_ascii_case("quit") => return None,
+            None => break,
+            Some(line) if line.is_empty() => break,
+            Some(line) => lines.push(line),
+        }
+    }
+    Some(lines.join("\n"))
+}
+
+/// What the snippet prints, from a child `rust-learn snippet`
+fn capture(lesson: &Lesson, snippet: &Snippet) -> Result<String, String> {
+    let exe =
+        std::env::current_exe().map_err(|error| format!("no path to rust-learn: {}", error))?;
+    let dir = std::env::current_dir().map_err(|error| error.to_string())?;
+    let args = [
+        "--non-interactive",
+        "--no-color",
+        "snippet",
+        lesson.name,
+        snippet.name,
+    ];
+    let run = toolchain::run(exe, &dir, &args)
+        .map_err(|error| format!("could not start rust-learn: {}", error))?;
+    if !run.success {
+        return Err(format!("{} did not finish", snippet.name));
+    }
+    Ok(run.stdout)
+}
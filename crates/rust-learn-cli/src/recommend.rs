@@ -0,0 +1,114 @@
+//! `rust-learn crates http`: the curated crate recommendations for a topic
+//! (`rust_learn_core::recommendations`), each with the lesson that shows it
+//! in use. Without a topic it lists them; a crate's name shows the topics
+//! that recommend it.
+
+use rust_learn_content::registry;
+use rust_learn_core::recommendations::{self, Found, Topic};
+use rust_learn_core::{input, output};
+
+/// Lines are wrapped to the terminal, but no wider than this
+const MAX_WIDTH: usize = 100;
+
+/// Every bundled topic; they are checked by `self-test`, so one that does
+/// not parse is a bug
+fn topics() -> Vec<Topic> {
+    recommendations::parse_all(registry::RECOMMENDATIONS)
+        .expect("bundled crate recommendations parse")
+}
+
+pub fn run(query: Option<&str>) -> Result<(), String> {
+    let topics = topics();
+    let Some(query) = query else {
+        let width = topics
+            .iter()
+            .map(|topic| topic.name.len())
+            .max()
+            .unwrap_or(0);
+        for topic in &topics {
+            let aliases = format!("({})", topic.aliases.join(", "));
+            println!(
+                "  {:width$}  {}  {}",
+                topic.name,
+                topic.title,
+                output::dim(&aliases),
+                width = width
+            );
+        }
+        println!(
+            "\n{}",
+            output::dim("See the crates for one with `rust-learn crates <topic>`.")
+        );
+        return Ok(());
+    };
+    match recommendations::find(&topics, query) {
+        Found::Topic(topic) => print(topic, None),
+        Found::Guess(topic, true) => {
+            let note = format!("'{}' is not a topic, using '{}'", query, topic.name);
+            eprintln!("{}", output::dim(&note));
+            print(topic, None);
+        }
+        Found::Guess(topic, false) => {
+            return Err(format!(
+                "Unknown topic '{}'. Did you mean '{}'?",
+                query, topic.name
+            ));
+        }
+        Found::Crate(found) => {
+            for (index, topic) in found.into_iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                print(topic, Some(query));
+            }
+        }
+        Found::Nothing => {
+            let names: Vec<&str> = topics.iter().map(|topic| topic.name.as_str()).collect();
+            return Err(format!(
+                "No recommendations for '{}'. Topics: {}",
+                query,
+                names.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// The topic's crates, with `marked` (a crate looked up by name) in bold
+fn print(topic: &Topic, marked: Option<&str>) {
+    let width = input::terminal_width().unwrap_or(80).min(MAX_WIDTH);
+    println!("{}\n", output::bold(&topic.title));
+    for line in output::wrap(&topic.intro, width) {
+        println!("{}", line);
+    }
+    println!();
+    let column = topic
+        .crates
+        .iter()
+        .map(|krate| krate.name.len())
+        .max()
+        .unwrap_or(0);
+    let hang = " ".repeat(column + 4);
+    for krate in &topic.crates {
+        let padding = " ".repeat(column - krate.name.len());
+        let name = if marked.is_none_or(|marked| marked == krate.name) {
+            output::bold(&krate.name)
+        } else {
+            krate.name.clone()
+        };
+        for (index, line) in output::wrap(&krate.why, width.saturating_sub(hang.len()))
+            .iter()
+            .enumerate()
+        {
+            if index == 0 {
+                println!("  {}{}  {}", name, padding, line);
+            } else {
+                println!("{}{}", hang, line);
+            }
+        }
+        if let Some(lesson) = &krate.lesson {
+            let shown = format!("Lesson: rust-learn {}", lesson);
+            println!("{}{}", hang, output::dim(&shown));
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! `rust-learn ref borrowing`: print a bundled reference excerpt
+//! (`rust_learn_core::reference`), for looking something up mid-lesson
+//! without a network. Without a topic it lists them; a lesson's name lists
+//! the topics that lesson links to.
+
+use rust_learn_content::registry;
+use rust_learn_core::reference::{self, Topic};
+use rust_learn_core::{fuzzy, highlight, input, output};
+
+/// Lines are wrapped to the terminal, but no wider than this
+const MAX_WIDTH: usize = 100;
+
+/// Every bundled topic; they are checked by `self-test`, so one that does
+/// not parse is a bug
+pub fn topics() -> Vec<Topic> {
+    reference::parse_all(registry::REFERENCE).expect("bundled reference topics parse")
+}
+
+/// The topics `lesson` links to
+pub fn for_lesson(lesson: &str) -> Vec<Topic> {
+    topics()
+        .into_iter()
+        .filter(|topic| topic.lessons.iter().any(|name| name == lesson))
+        .collect()
+}
+
+pub fn run(query: Option<&str>) -> Result<(), String> {
+    let topics = topics();
+    let Some(query) = query else {
+        list(&topics.iter().collect::<Vec<_>>());
+        println!(
+            "\n{}",
+            output::dim("Read one with `rust-learn ref <topic>`.")
+        );
+        return Ok(());
+    };
+    if let Some(topic) = topics.iter().find(|topic| topic.name == query) {
+        print(topic);
+        return Ok(());
+    }
+    if let Some(lesson) = registry::find(query) {
+        let linked: Vec<&Topic> = topics
+            .iter()
+            .filter(|topic| topic.lessons.iter().any(|name| name == lesson.name))
+            .collect();
+        if linked.is_empty() {
+            return Err(format!("{} links to no reference topics", lesson.name));
+        }
+        println!("{}\n", output::bold(&format!("For {}:", lesson.name)));
+        list(&linked);
+        return Ok(());
+    }
+    let names: Vec<&str> = topics.iter().map(|topic| topic.name.as_str()).collect();
+    match fuzzy::closest(query, &names) {
+        Some(guess) if guess.confident => {
+            let note = format!("'{}' is not a topic, using '{}'", query, guess.name);
+            eprintln!("{}", output::dim(&note));
+            let topic = topics.iter().find(|topic| topic.name == guess.name);
+            print(topic.expect("closest returns one of the names"));
+            Ok(())
+        }
+        Some(guess) => Err(format!(
+            "Unknown topic '{}'. Did you mean '{}'?",
+            query, guess.name
+        )),
+        None => Err(format!(
+            "Unknown topic '{}'. Topics: {}",
+            query,
+            names.join(", ")
+        )),
+    }
+}
+
+fn list(topics: &[&Topic]) {
+    let width = topics
+        .iter()
+        .map(|topic| topic.name.len())
+        .max()
+        .unwrap_or(0);
+    for topic in topics {
+        println!("  {:width$}  {}", topic.name, topic.title, width = width);
+    }
+}
+
+fn print(topic: &Topic) {
+    let width = input::terminal_width().unwrap_or(80).min(MAX_WIDTH);
+    let paragraph = |text: &str, indent: &str| {
+        for line in output::wrap(&format!("{}{}", indent, text), width) {
+            println!("{}", line);
+        }
+    };
+    println!("{}\n", output::bold(&topic.title));
+    paragraph(&topic.intro, "");
+    println!();
+    if topic.is_table() {
+        let terms = topic
+            .entries
+            .iter()
+            .filter_map(|entry| entry.term.as_deref());
+        let column = terms.map(|term| term.chars().count()).max().unwrap_or(0);
+        for entry in &topic.entries {
+            let term = entry.term.as_deref().unwrap_or_default();
+            let padding = " ".repeat(column - term.chars().count());
+            let hang = " ".repeat(column + 4);
+            let text = output::wrap(&entry.text, width.saturating_sub(hang.len()));
+            for (index, line) in text.iter().enumerate() {
+                if index == 0 {
+                    println!("  {}{}  {}", output::bold(term), padding, line);
+                } else {
+                    println!("{}{}", hang, line);
+                }
+            }
+        }
+    } else {
+        for entry in &topic.entries {
+            match &entry.term {
+                Some(term) => {
+                    println!("- {}", output::bold(term));
+                    paragraph(&entry.text, "  ");
+                }
+                None => paragraph(&entry.text, "- "),
+            }
+            if let Some(code) = &entry.code {
+                println!();
+                for line in highlight::ansi(code.trim_end()).lines() {
+                    println!("    {}", line);
+                }
+            }
+            println!();
+        }
+    }
+    if let Some(see) = &topic.see {
+        if topic.is_table() {
+            println!();
+        }
+        println!("{}", output::dim(&format!("More: {}", see)));
+    }
+}
@@ -0,0 +1,198 @@
+//! REPL
+//!
+//! `rust-learn repl` keeps one session open for exploring: list, run, search
+//! and quiz without starting the program again each time. The session
+//! remembers the commands typed (`history`, `!3` repeats one) and the last
+//! lesson run, so `run 3` means section 3 of it and a bare `run` runs the
+//! same thing again. A lesson that panics ends its run, not the session.
+
+use rust_learn_content::registry::{self, Filter, Lesson, Section};
+use rust_learn_core::progress::Progress;
+use rust_learn_core::{input, output};
+use std::panic::{self, AssertUnwindSafe};
+
+const HELP: &str = "\
+  list [lesson]           lessons by chapter, or the sections of one
+  run <lesson> [section]  run a lesson, or one section of it
+  run <section>           a section of the last lesson, by name or number
+  run                     the last lesson or section again
+  <lesson> [section]      same as run
+  next                    the next lesson that is not complete
+  search <words>          lessons and sections matching every word
+  progress                how many lessons are complete, and what is next
+  quiz [lesson] [count]   questions about one lesson, or all of them (5)
+  history                 the commands so far; !3 repeats the third
+  help                    this list
+  quit                    leave the REPL (Ctrl-D works too)";
+
+#[derive(Default)]
+struct Session {
+    history: Vec<String>,
+    last: Option<(&'static Lesson, Option<&'static Section>)>,
+}
+
+/// Read and run commands until `quit` or the end of input
+pub fn repl() {
+    println!(
+        "{}",
+        output::bold("rust-learn REPL: `help` lists the commands, `quit` leaves")
+    );
+    let mut session = Session::default();
+    while let Some(line) = input::prompt_input("\nrust-learn> ") {
+        let line = match session.recall(&line) {
+            Ok(line) => line,
+            Err(message) => {
+                println!("{}", message);
+                continue;
+            }
+        };
+        if line.is_empty() {
+            continue;
+        }
+        session.history.push(line.clone());
+        let words: Vec<&str> = line.split_whitespace().collect();
+        if matches!(words[0], "quit" | "exit" | "q") {
+            return;
+        }
+        session.execute(&words);
+    }
+    println!();
+}
+
+impl Session {
+    /// `!3` as the third command in the history; any other line as it is
+    fn recall(&self, line: &str) -> Result<String, String> {
+        let Some(number) = line.strip_prefix('!') else {
+            return Ok(line.to_string());
+        };
+        number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| self.history.get(number.checked_sub(1)?))
+            .map(|line| {
+                println!("{}", output::dim(line));
+                line.clone()
+            })
+            .ok_or_else(|| format!("No command {} in the history", line))
+    }
+
+    fn execute(&mut self, words: &[&str]) {
+        match words {
+            ["help" | "?"] => println!("{}", HELP),
+            ["list"] => crate::list(None, &Filter::default()),
+            ["list", lesson] => match crate::resolve_lesson(lesson) {
+                Ok(lesson) => crate::list(Some(lesson.name), &Filter::default()),
+                Err(message) => println!("{}", message),
+            },
+            ["run"] => match self.last {
+                Some((lesson, section)) => self.run(lesson, section),
+                None => println!("Nothing has run yet: try `run variables`"),
+            },
+            ["run", target @ ..] => self.run_target(target),
+            ["next"] => crate::run_next(),
+            ["search", words @ ..] if !words.is_empty() => {
+                let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+                crate::search(&words);
+            }
+            ["progress"] => progress(),
+            ["quiz", rest @ ..] => quiz(rest),
+            ["history"] => {
+                for (index, line) in self.history.iter().enumerate() {
+                    println!("{:>4}  {}", index + 1, line);
+                }
+            }
+            target if registry::find(target[0]).is_some() || target[0].contains("::") => {
+                self.run_target(target)
+            }
+            [command, ..] => println!("Unknown command '{}': `help` lists them", command),
+            [] => {}
+        }
+    }
+
+    /// `run <lesson> [section]`, `run lesson::section`, or `run <section>` of
+    /// the last lesson
+    fn run_target(&mut self, target: &[&str]) {
+        let (lesson, section) = match target {
+            [target] => match target.split_once("::") {
+                Some((lesson, section)) => (lesson, Some(section)),
+                None => (*target, None),
+            },
+            [lesson, section] => (*lesson, Some(*section)),
+            _ => {
+                println!("Usage: run <lesson> [section]");
+                return;
+            }
+        };
+
+        // A section of the lesson that ran last, by name or number
+        if section.is_none()
+            && registry::find(lesson).is_none()
+            && let Some((last, _)) = self.last
+            && let Some(section) = last.section(lesson)
+        {
+            self.run(last, Some(section));
+            return;
+        }
+
+        let lesson = match crate::resolve_lesson(lesson) {
+            Ok(lesson) => lesson,
+            Err(message) => {
+                println!("{}", message);
+                return;
+            }
+        };
+        let section = match section.map(|key| crate::resolve_section(lesson, key)) {
+            Some(Ok(section)) => Some(section),
+            Some(Err(message)) => {
+                println!("{}", message);
+                return;
+            }
+            None => None,
+        };
+        self.run(lesson, section);
+    }
+
+    fn run(&mut self, lesson: &'static Lesson, section: Option<&'static Section>) {
+        self.last = Some((lesson, section));
+        let section = section.map(|section| section.name);
+        let run = panic::catch_unwind(AssertUnwindSafe(|| crate::run_lesson(lesson.name, section)));
+        if run.is_err() {
+            println!(
+                "{}",
+                output::red(&format!("{} panicked; the REPL carries on", lesson.name))
+            );
+        }
+    }
+}
+
+fn progress() {
+    let progress = Progress::load();
+    println!("{}", crate::progress_bar(&progress));
+    match registry::next(|name| progress.is_complete(name)) {
+        Some(lesson) => println!("Next: {} ({})", lesson.name, lesson.title),
+        None => println!("Every lesson is complete"),
+    }
+}
+
+/// `quiz`, `quiz 10`, `quiz vectors` or `quiz vectors 3`
+fn quiz(args: &[&str]) {
+    let (about, count) = match args {
+        [] => (None, None),
+        [count] if count.parse::<usize>().is_ok() => (None, Some(*count)),
+        [lesson] => (Some(*lesson), None),
+        [lesson, count] => (Some(*lesson), Some(*count)),
+        _ => {
+            println!("Usage: quiz [lesson] [count]");
+            return;
+        }
+    };
+    let Ok(count) = count.map_or(Ok(5), str::parse::<usize>) else {
+        println!("Usage: quiz [lesson] [count]");
+        return;
+    };
+    match about.map(crate::resolve_lesson) {
+        Some(Err(message)) => println!("{}", message),
+        Some(Ok(lesson)) => crate::quiz(Some(lesson), count, None, &[], false),
+        None => crate::quiz(None, count, None, &[], false),
+    }
+}
@@ -0,0 +1,513 @@
+//! Self-Test
+//!
+//! `rust-learn self-test` checks everything this binary ships before a
+//! release: every lesson runs to the end without input, the quiz can ask
+//! about every section and the bundled question banks load, no lesson
+//! function grows past `metrics::LESSON_LIMITS`, the compile-fail fixtures
+//! still fail the way their `.stderr` files say, the `show-errors` demos
+//! fail with the error they expect, the `ref` topics and crate
+//! recommendations parse, no exercise or interview problem passes before
+//! it is solved, every exercise's curated solutions do, and every program
+//! `share` makes for the playground compiles.
+//! Lessons run in a child process, like in the TUI, so a panic, an exit or
+//! a hang (stopped by the child's watchdog) in one is reported instead of
+//! ending the test, and with a scratch data directory, so they do not count
+//! as the learner's progress.
+
+use crate::metrics;
+use rust_learn_content::registry;
+use rust_learn_core::output::{self, Glyph};
+use rust_learn_core::runner::format_duration;
+use rust_learn_core::{
+    exercises, hooks, interview, playground, question_bank, quiz, recommendations, reference,
+    toolchain,
+};
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+enum Outcome {
+    Passed,
+    /// What went wrong, a few lines at most
+    Failed(Vec<String>),
+    /// Why the check could not run here
+    Skipped(String),
+}
+
+/// Run every check and print a summary; true when none failed
+pub fn run() -> bool {
+    // Looked up once: the release_profiles lesson rebuilds this binary, after
+    // which the running process's own path no longer resolves
+    let exe = std::env::current_exe();
+    // Lessons record their progress here instead of in the learner's
+    let data_dir = std::env::temp_dir().join("rust-learn-self-test");
+    let _ = fs::remove_dir_all(&data_dir);
+
+    let mut checks: Vec<(String, Box<dyn Fn() -> Outcome + '_>)> = Vec::new();
+    for lesson in registry::LESSONS {
+        let name = lesson.name;
+        let (exe, data_dir) = (&exe, &data_dir);
+        checks.push((
+            format!("lesson {}", name),
+            Box::new(move || run_lesson(exe, data_dir, name)),
+        ));
+    }
+    checks.push(("quiz questions".to_string(), Box::new(quiz_questions)));
+    checks.push(("lesson code metrics".to_string(), Box::new(lesson_metrics)));
+    checks.push((
+        "compile-fail fixtures".to_string(),
+        Box::new(compile_fail_fixtures),
+    ));
+    checks.push((
+        "compile-error demos".to_string(),
+        Box::new(compile_error_demos),
+    ));
+    checks.push(("reference topics".to_string(), Box::new(reference_topics)));
+    checks.push((
+        "crate recommendations".to_string(),
+        Box::new(crate_recommendations),
+    ));
+    checks.push(("exercises".to_string(), Box::new(shipped_exercises)));
+    checks.push((
+        "playground programs".to_string(),
+        Box::new(playground_programs),
+    ));
+    checks.push((
+        "interview problems".to_string(),
+        Box::new(interview_problems),
+    ));
+
+    let (mut passed, mut failed, mut skipped) = (0, 0, 0);
+    for (name, check) in &checks {
+        let start = Instant::now();
+        let outcome = check();
+        let took = format_duration(start.elapsed());
+        match outcome {
+            Outcome::Passed => {
+                passed += 1;
+                println!(
+                    "{:<32} {} {}",
+                    name,
+                    output::status(Glyph::Ok, "ok", output::green, 0),
+                    output::dim(&took)
+                );
+            }
+            Outcome::Failed(lines) => {
+                failed += 1;
+                let status = output::status(Glyph::Fail, "FAILED", output::red, 0);
+                println!("{:<32} {}", name, status);
+                for line in lines {
+                    println!("  | {}", line);
+                }
+            }
+            Outcome::Skipped(reason) => {
+                skipped += 1;
+                let status = output::status(Glyph::Warning, "skipped:", str::to_string, 0);
+                println!("{:<32} {} {}", name, status, reason);
+            }
+        }
+    }
+
+    let summary = format!(
+        "\n{} passed, {} failed, {} skipped",
+        passed, failed, skipped
+    );
+    if failed == 0 {
+        println!("{}", output::green(&summary));
+    } else {
+        println!("{}", output::red(&summary));
+    }
+    failed == 0
+}
+
+/// Run a lesson the way `run --all --non-interactive` would, in a child process
+fn run_lesson(exe: &io::Result<PathBuf>, data_dir: &Path, name: &str) -> Outcome {
+    let exe = match exe {
+        Ok(exe) => exe,
+        Err(error) => return Outcome::Failed(vec![format!("no path to rust-learn: {}", error)]),
+    };
+    let args = ["--non-interactive", "--no-color", "--quiet", "run", name];
+    let env = [
+        ("RUST_LEARN_DATA_DIR", data_dir.as_os_str()),
+        (hooks::DISABLE_VAR, OsStr::new("1")),
+    ];
+    match std::env::current_dir().and_then(|dir| toolchain::run_with_env(exe, &dir, &args, &env)) {
+        Ok(output) if output.success => Outcome::Passed,
+        Ok(output) => Outcome::Failed(first_lines(&output.stderr)),
+        Err(error) => Outcome::Failed(vec![format!("could not start rust-learn: {}", error)]),
+    }
+}
+
+/// The built-in questions and the bundled question banks
+fn quiz_questions() -> Outcome {
+    let mut problems = quiz::problems(registry::LESSONS);
+    for (name, text) in registry::BUNDLED_QUESTIONS {
+        if let Err(errors) = question_bank::parse(name, text, registry::LESSONS) {
+            problems.extend(errors);
+        }
+    }
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// Every lesson function within `metrics::LESSON_LIMITS`, so lesson code
+/// stays simple enough to read
+fn lesson_metrics() -> Outcome {
+    let (max_complexity, max_depth) = metrics::LESSON_LIMITS;
+    let mut problems = Vec::new();
+    for lesson in registry::LESSONS {
+        let functions = match metrics::measure(lesson.source) {
+            Ok(functions) => functions,
+            Err(error) => {
+                problems.push(format!("{}: not valid Rust: {}", lesson.name, error));
+                continue;
+            }
+        };
+        for function in functions {
+            if function.complexity > max_complexity || function.depth > max_depth {
+                problems.push(format!(
+                    "{}::{} (line {}): complexity {}, depth {} (at most {} and {})",
+                    lesson.name,
+                    function.name,
+                    function.line,
+                    function.complexity,
+                    function.depth,
+                    max_complexity,
+                    max_depth
+                ));
+            }
+        }
+    }
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// `cargo test --test compile_fail` in the source tree, which compares each
+/// fixture's rustc errors with its `.stderr` file
+fn compile_fail_fixtures() -> Outcome {
+    if toolchain::cargo_version().is_none() {
+        return Outcome::Skipped("cargo was not found".to_string());
+    }
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    if !workspace.join("crates/rust-learn-core/tests").is_dir() {
+        return Outcome::Skipped("the source tree is not next to this binary".to_string());
+    }
+
+    let args = [
+        "test",
+        "-p",
+        "rust-learn-core",
+        "--test",
+        "compile_fail",
+        "--quiet",
+    ];
+    match toolchain::cargo(&workspace, &args) {
+        Ok(output) if output.success => Outcome::Passed,
+        Ok(output) => {
+            let mut text = output.stdout;
+            text.push_str(&output.stderr);
+            Outcome::Failed(first_lines(&text))
+        }
+        Err(error) => Outcome::Failed(vec![format!("could not run cargo: {}", error)]),
+    }
+}
+
+/// Every `show-errors` demo belongs to a lesson and fails to compile with
+/// the error it expects
+fn compile_error_demos() -> Outcome {
+    if toolchain::rustc_version().is_none() {
+        return Outcome::Skipped("rustc was not found".to_string());
+    }
+    let scratch = std::env::temp_dir().join("rust-learn-self-test-compile-errors");
+    let mut problems = Vec::new();
+    for demo in registry::COMPILE_ERRORS {
+        if registry::find(demo.lesson).is_none() {
+            problems.push(format!(
+                "{}: no lesson is called {}",
+                demo.name, demo.lesson
+            ));
+        }
+        match demo.check(&scratch) {
+            Ok(Ok(())) => {}
+            Ok(Err(problem)) => problems.push(format!("{}: {}", demo.name, problem)),
+            Err(error) => problems.push(format!("{}: could not run rustc: {}", demo.name, error)),
+        }
+    }
+    let _ = fs::remove_dir_all(&scratch);
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// Every `ref` topic parses, has a name of its own, and links only to
+/// lessons that exist
+fn reference_topics() -> Outcome {
+    let topics = match reference::parse_all(registry::REFERENCE) {
+        Ok(topics) => topics,
+        Err(error) => return Outcome::Failed(vec![error]),
+    };
+    let mut problems = Vec::new();
+    for (index, topic) in topics.iter().enumerate() {
+        if topics[..index].iter().any(|other| other.name == topic.name) {
+            problems.push(format!("{}: two topics have this name", topic.name));
+        }
+        for lesson in &topic.lessons {
+            if registry::find(lesson).is_none() {
+                problems.push(format!("{}: no lesson is called {}", topic.name, lesson));
+            }
+        }
+    }
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// Every crate recommendation topic parses, and the lessons and sections
+/// its crates point to exist
+fn crate_recommendations() -> Outcome {
+    let topics = match recommendations::parse_all(registry::RECOMMENDATIONS) {
+        Ok(topics) => topics,
+        Err(error) => return Outcome::Failed(vec![error]),
+    };
+    let mut problems = Vec::new();
+    for topic in &topics {
+        for krate in &topic.crates {
+            let Some(key) = &krate.lesson else {
+                continue;
+            };
+            let (lesson, section) = match key.split_once("::") {
+                Some((lesson, section)) => (lesson, Some(section)),
+                None => (key.as_str(), None),
+            };
+            let found = registry::find(lesson).is_some_and(|lesson| {
+                section.is_none_or(|section| lesson.section(section).is_some())
+            });
+            if !found {
+                problems.push(format!(
+                    "{}: {}: no lesson or section {}",
+                    topic.name, krate.name, key
+                ));
+            }
+        }
+    }
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// Every exercise belongs to a lesson and does not pass as shipped: there
+/// would be nothing to do
+fn shipped_exercises() -> Outcome {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../exercises");
+    let order: Vec<&str> = registry::LESSONS.iter().map(|lesson| lesson.name).collect();
+    let Ok(found) = exercises::find(&dir, &order) else {
+        return Outcome::Skipped("the source tree is not next to this binary".to_string());
+    };
+    let scratch = std::env::temp_dir().join("rust-learn-self-test-exercises");
+    let mut problems = Vec::new();
+    for exercise in &found {
+        if !order.contains(&exercise.lesson.as_str()) {
+            problems.push(format!(
+                "{}: no lesson is called {}",
+                exercise.name, exercise.lesson
+            ));
+        }
+        match exercise.check(&scratch) {
+            Ok(exercises::Outcome::Passed) => {
+                problems.push(format!("{}: passes already", exercise.name))
+            }
+            Ok(_) => {}
+            Err(error) => problems.push(format!("{}: cannot check: {}", exercise.name, error)),
+        }
+        problems.extend(solution_problems(exercise, &scratch));
+    }
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// Every curated solution of `exercise` passes, the way the exercise would
+fn solution_problems(exercise: &exercises::Exercise, scratch: &Path) -> Vec<String> {
+    let solutions = match exercise.solutions() {
+        Ok(solutions) => solutions,
+        Err(error) => {
+            return vec![format!(
+                "{}: cannot read its solutions: {}",
+                exercise.name, error
+            )];
+        }
+    };
+    let mut problems = Vec::new();
+    for solution in solutions {
+        let about = format!("{} solution {}", exercise.name, solution.approach);
+        let checked = exercises::Exercise {
+            path: solution.path,
+            ..exercise.clone()
+        };
+        match checked.check(scratch) {
+            Ok(exercises::Outcome::Passed) => {}
+            Ok(exercises::Outcome::NotDone) => {
+                problems.push(format!("{}: still has `{}`", about, exercises::NOT_DONE))
+            }
+            Ok(exercises::Outcome::Failed(output)) => {
+                problems.push(format!("{}: does not pass", about));
+                problems.extend(first_lines(&output));
+            }
+            Err(error) => problems.push(format!("{}: cannot check: {}", about, error)),
+        }
+    }
+    problems
+}
+
+/// Every program `share` makes compiles: each goes into one scratch crate
+/// as a binary, with the crates the playground has, and one `cargo check`
+/// runs offline against the workspace's lockfile
+fn playground_programs() -> Outcome {
+    if toolchain::cargo_version().is_none() {
+        return Outcome::Skipped("cargo was not found".to_string());
+    }
+    let lockfile = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../Cargo.lock");
+    if !lockfile.is_file() {
+        return Outcome::Skipped("the source tree is not next to this binary".to_string());
+    }
+    let scratch = std::env::temp_dir().join("rust-learn-self-test-playground");
+    let bin = scratch.join("src/bin");
+    let _ = fs::remove_dir_all(&bin);
+    let written = fs::create_dir_all(&bin)
+        .and_then(|()| fs::write(scratch.join("Cargo.toml"), PLAYGROUND_MANIFEST))
+        .and_then(|()| fs::copy(&lockfile, scratch.join("Cargo.lock")).map(|_| ()))
+        .and_then(|()| {
+            for lesson in registry::LESSONS {
+                for section in lesson.sections {
+                    if let Ok(program) = playground::program(lesson, section) {
+                        let name = format!("{}__{}.rs", lesson.name, section.name);
+                        fs::write(bin.join(name), program)?;
+                    }
+                }
+            }
+            Ok(())
+        });
+    if let Err(error) = written {
+        return Outcome::Failed(vec![format!("cannot write the programs: {}", error)]);
+    }
+
+    let args = [
+        "check",
+        "--offline",
+        "--keep-going",
+        "--bins",
+        "--message-format",
+        "short",
+    ];
+    match toolchain::cargo(&scratch, &args) {
+        Ok(output) if output.success => Outcome::Passed,
+        Ok(output) => {
+            // `src/bin/ownership__slices.rs:3:5: error[E0425]: ...`
+            let mut problems: Vec<String> = Vec::new();
+            for line in output.stderr.lines() {
+                let Some(rest) = line.strip_prefix("src/bin/") else {
+                    continue;
+                };
+                let Some((file, error)) = rest.split_once(": error") else {
+                    continue;
+                };
+                let name = file
+                    .split(".rs")
+                    .next()
+                    .unwrap_or(file)
+                    .replacen("__", "::", 1);
+                if !problems
+                    .iter()
+                    .any(|problem| problem.starts_with(&format!("{}:", name)))
+                {
+                    problems.push(format!("{}: error{}", name, error));
+                }
+            }
+            if problems.is_empty() {
+                problems = first_lines(&output.stderr);
+            }
+            Outcome::Failed(problems)
+        }
+        Err(error) => Outcome::Failed(vec![format!("could not run cargo: {}", error)]),
+    }
+}
+
+/// The scratch crate for `playground_programs`
+const PLAYGROUND_MANIFEST: &str = "\
+[package]
+name = \"playground-programs\"
+version = \"0.0.0\"
+edition = \"2024\"
+
+[dependencies]
+tokio = { version = \"1\", features = [\"full\"] }
+tracing = \"0.1\"
+
+[workspace]
+";
+
+/// Every interview problem says what to do, has hints and a test on a large
+/// input, and its starter compiles but fails its tests
+fn interview_problems() -> Outcome {
+    if toolchain::rustc_version().is_none() {
+        return Outcome::Skipped("rustc was not found".to_string());
+    }
+    let scratch = std::env::temp_dir().join("rust-learn-self-test-interview");
+    let mut problems = Vec::new();
+    for problem in registry::INTERVIEW {
+        let key = problem.key();
+        if problem.statement().is_empty() {
+            problems.push(format!("{}: says nothing about what to do", key));
+        }
+        if problem.hints().is_empty() {
+            problems.push(format!("{}: has no `// hint:` lines", key));
+        }
+        if !problem.source.contains("fn large_input()") {
+            problems.push(format!("{}: has no large_input test", key));
+        }
+        let file = scratch.join(format!("{}.rs", problem.name));
+        let checked = fs::create_dir_all(&scratch)
+            .and_then(|()| fs::write(&file, problem.starter()))
+            .and_then(|()| problem.check(&file, &scratch));
+        match checked {
+            Ok(interview::Verdict::Failed(output)) if output.contains("test result: FAILED") => {}
+            Ok(interview::Verdict::Failed(output)) => {
+                problems.push(format!("{}: the starter does not compile", key));
+                problems.extend(first_lines(&output));
+            }
+            Ok(_) => problems.push(format!("{}: passes already", key)),
+            Err(error) => problems.push(format!("{}: cannot check: {}", key, error)),
+        }
+    }
+    let _ = fs::remove_dir_all(&scratch);
+    if problems.is_empty() {
+        Outcome::Passed
+    } else {
+        Outcome::Failed(problems)
+    }
+}
+
+/// The first few non-blank lines of a failed command's output
+fn first_lines(text: &str) -> Vec<String> {
+    text.lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(6)
+        .map(String::from)
+        .collect()
+}
@@ -0,0 +1,48 @@
+//! `rust-learn share ownership 5`: print a play.rust-lang.org link that runs
+//! the section (`playground::program`), and with `--open` open it in the
+//! browser: `xdg-open` on Linux, `open` on macOS, the URL handler on
+//! Windows.
+
+use rust_learn_content::registry::{Lesson, Section};
+use rust_learn_core::{output, playground};
+use std::process::{Command, Stdio};
+
+/// Print the link for `section` (or with `code`, the program itself), and
+/// open it when asked to
+pub fn run(lesson: &Lesson, section: &Section, code: bool, open: bool) -> Result<(), String> {
+    let program = playground::program(lesson, section)?;
+    if code {
+        print!("{}", program);
+        return Ok(());
+    }
+    let url = playground::url(&program);
+    println!("{}", url);
+    if open {
+        open_browser(&url)?;
+        eprintln!("{}", output::dim("Opened in your browser"));
+    }
+    Ok(())
+}
+
+fn open_browser(url: &str) -> Result<(), String> {
+    let mut command = if cfg!(windows) {
+        // Not `cmd /C start`, which would take the link's `&`s as commands
+        let mut command = Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else {
+        Command::new("xdg-open")
+    };
+    let status = command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|error| format!("cannot start a browser: {}", error))?;
+    if !status.success() {
+        return Err(format!("the browser did not start ({})", status));
+    }
+    Ok(())
+}
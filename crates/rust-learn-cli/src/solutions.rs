@@ -0,0 +1,94 @@
+//! `rust-learn solutions gallery <exercise>` shows, once `verify` has passed
+//! an exercise, the curated solutions that come with it
+//! (`Exercise::solutions`): simplest first, each with a note on what it does
+//! well and its code highlighted. They carry no names, so the gallery
+//! compares approaches, not people. Each solution repeats the exercise's
+//! tests, so self-test can check it; those are left out here. `--copy
+//! idiomatic` also puts one solution's code, as shown, on the clipboard
+//! (`clipboard::copy`).
+
+use crate::{clipboard, verify};
+use rust_learn_core::output;
+use rust_learn_core::progress::Progress;
+use rust_learn_core::{exercises, highlight};
+use std::path::Path;
+
+/// Print the solutions of exercise `name` in `dir` or a pack, after the
+/// learner has solved it, copying the one with approach `copy`
+pub fn gallery(dir: &Path, name: &str, copy: Option<&str>) -> Result<(), String> {
+    let exercise = verify::find(dir, name)?;
+    let id = exercise.id();
+    if Progress::load().completed_at(&exercise.key()).is_none() {
+        return Err(format!(
+            "solve {} first: the gallery opens once `rust-learn verify {}` passes it",
+            id, id
+        ));
+    }
+    let solutions = exercise
+        .solutions()
+        .map_err(|error| format!("cannot read the solutions of {}: {}", id, error))?;
+    if solutions.is_empty() {
+        println!("{} has no curated solutions yet", id);
+        return Ok(());
+    }
+    let copied = match copy {
+        Some(approach) => Some(
+            solutions
+                .iter()
+                .find(|solution| solution.approach == approach)
+                .ok_or_else(|| {
+                    let approaches: Vec<&str> = solutions
+                        .iter()
+                        .map(|solution| solution.approach.as_str())
+                        .collect();
+                    format!(
+                        "{} has no {} solution (try {})",
+                        id,
+                        approach,
+                        approaches.join(", ")
+                    )
+                })?,
+        ),
+        None => None,
+    };
+
+    let heading = format!("{}: {} ways to solve it", id, solutions.len());
+    println!("{}\n", output::bold(&heading));
+    for (index, solution) in solutions.iter().enumerate() {
+        let title = format!("{}. {}", index + 1, capitalized(&solution.approach));
+        println!("{}", output::bold(&title));
+        for line in solution.commentary.lines() {
+            println!("{}", output::dim(line));
+        }
+        println!("\n{}\n", highlight::ansi(without_tests(&solution.code)));
+    }
+    let note = format!(
+        "The solutions are in {}/ next to the exercise",
+        exercises::SOLUTIONS_DIR
+    );
+    println!("{}", output::dim(&note));
+    if let Some(solution) = copied {
+        let to = clipboard::copy(without_tests(&solution.code))
+            .map_err(|error| format!("cannot copy: {}", error))?;
+        let copied = format!("Copied the {} solution to {}", solution.approach, to);
+        eprintln!("{}", output::dim(&copied));
+    }
+    Ok(())
+}
+
+/// `code` up to its `#[cfg(test)]` module, without the blank lines before it
+fn without_tests(code: &str) -> &str {
+    let end = code
+        .find("#[cfg(test)]")
+        .filter(|&start| start == 0 || code[..start].ends_with('\n'))
+        .unwrap_or(code.len());
+    code[..end].trim_end()
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
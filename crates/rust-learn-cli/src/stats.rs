@@ -0,0 +1,594 @@
+//! Stats
+//!
+//! `rust-learn stats` sums up the learner's progress: lessons and sections
+//! finished (from the progress store), quizzes passed, the daily streak and
+//! the time spent (from the activity log). A day counts toward the streak
+//! when anything was completed, run or answered on it, in UTC; the streak is
+//! still current until a whole day goes by without one. Checkpoint answers
+//! (`rust_learn_core::checkpoint`) are counted once there are any. `--json`
+//! prints the same numbers on one line.
+//!
+//! `rust-learn stats recalibrate` checks each lesson's estimate against this
+//! learner. A lesson's time is measured from its first run (whole, or any
+//! section) to its completion, when that took one sitting; the learner's pace
+//! (the median of measured over estimated time) scales the lessons not
+//! measured. A quiz score under 50% makes a lesson one difficulty harder, and
+//! 90% or more, on time, one easier. `rust_learn_core::calibration` keeps the
+//! result for `list`.
+//!
+//! `rust-learn stats variants` is for content authors: for every section with
+//! explanation variants (see `rust_learn_core::variants`), how many learners
+//! saw each variant and how they did on the quiz questions about the section
+//! afterwards. It reads the learner's own activity log, or the logs given,
+//! each one counted as a learner, so a class's logs can be compared at once.
+
+use rust_learn_content::registry::{self, Difficulty, Lesson};
+use rust_learn_core::activity::{self, Entry, Event};
+use rust_learn_core::calibration::{Calibration, Estimate};
+use rust_learn_core::json::Value;
+use rust_learn_core::output;
+use rust_learn_core::progress::Progress;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A quiz is passed with this share of right answers, or more
+const PASS_PERCENT: usize = 80;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// A lesson completed longer than this after its first run was not done in
+/// one sitting, so its time says little
+const LONGEST_SITTING: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// Quiz answers about a lesson needed before they move its difficulty
+const ANSWERS_TO_JUDGE: usize = 3;
+
+#[derive(Debug, PartialEq)]
+struct Summary {
+    lessons: usize,
+    sections: usize,
+    quizzes_taken: usize,
+    quizzes_passed: usize,
+    checkpoints_answered: usize,
+    checkpoints_right: usize,
+    streak: usize,
+    longest_streak: usize,
+    seconds: f64,
+}
+
+/// Print the learner's stats, as text or as one line of JSON
+pub fn summary(json: bool) {
+    let progress = Progress::load();
+    let log = activity::load();
+    let summary = summarize(&progress, &log, day(SystemTime::now()));
+    if json {
+        println!("{}", summary_json(&summary));
+        return;
+    }
+    println!(
+        "Lessons finished  {} of {} ({} {})",
+        output::bold(&summary.lessons.to_string()),
+        registry::LESSONS.len(),
+        summary.sections,
+        if summary.sections == 1 {
+            "section"
+        } else {
+            "sections"
+        }
+    );
+    println!(
+        "Quizzes passed    {} of {} ({}% or better)",
+        output::bold(&summary.quizzes_passed.to_string()),
+        summary.quizzes_taken,
+        PASS_PERCENT
+    );
+    if summary.checkpoints_answered > 0 {
+        println!(
+            "Checkpoints       {} of {} answered right",
+            output::bold(&summary.checkpoints_right.to_string()),
+            summary.checkpoints_answered
+        );
+    }
+    println!(
+        "Daily streak      {} (longest {})",
+        output::bold(&days(summary.streak)),
+        days(summary.longest_streak)
+    );
+    println!(
+        "Time spent        {} running lessons and taking quizzes",
+        output::bold(&format_time(summary.seconds))
+    );
+}
+
+fn summarize(progress: &Progress, log: &[Entry], today: u64) -> Summary {
+    let mut active = BTreeSet::new();
+    let (mut lessons, mut sections) = (0, 0);
+    for lesson in registry::LESSONS {
+        if let Some(time) = progress.completed_at(lesson.name) {
+            lessons += 1;
+            active.insert(day(time));
+        }
+        for section in lesson.sections {
+            let key = format!("{}::{}", lesson.name, section.name);
+            if let Some(time) = progress.completed_at(&key) {
+                sections += 1;
+                active.insert(day(time));
+            }
+        }
+    }
+
+    let (mut quizzes_taken, mut quizzes_passed, mut seconds) = (0, 0, 0.0);
+    let (mut checkpoints_answered, mut checkpoints_right) = (0, 0);
+    for entry in log {
+        active.insert(day(entry.time));
+        match &entry.event {
+            Event::Run { seconds: run, .. } => seconds += run,
+            Event::Interview {
+                seconds: interview, ..
+            } => seconds += interview,
+            Event::Quiz {
+                correct,
+                asked,
+                seconds: quiz,
+                ..
+            } => {
+                quizzes_taken += 1;
+                if correct * 100 >= asked * PASS_PERCENT {
+                    quizzes_passed += 1;
+                }
+                seconds += quiz;
+            }
+            Event::Checkpoint { correct, .. } => {
+                checkpoints_answered += 1;
+                checkpoints_right += usize::from(*correct);
+            }
+            _ => {}
+        }
+    }
+
+    let (streak, longest_streak) = streaks(&active, today);
+    Summary {
+        lessons,
+        sections,
+        quizzes_taken,
+        quizzes_passed,
+        checkpoints_answered,
+        checkpoints_right,
+        streak,
+        longest_streak,
+        seconds,
+    }
+}
+
+/// The current and the longest run of consecutive `days`; the current one
+/// ends today or yesterday, or it is 0
+fn streaks(days: &BTreeSet<u64>, today: u64) -> (usize, usize) {
+    let (mut run, mut longest, mut previous) = (0, 0, None);
+    for &day in days {
+        run = if previous.is_some_and(|previous: u64| previous + 1 == day) {
+            run + 1
+        } else {
+            1
+        };
+        longest = usize::max(longest, run);
+        previous = Some(day);
+    }
+    let current = match previous {
+        Some(last) if last + 1 >= today => run,
+        _ => 0,
+    };
+    (current, longest)
+}
+
+/// Days since the Unix epoch, in UTC
+fn day(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / SECONDS_PER_DAY)
+}
+
+fn days(count: usize) -> String {
+    format!("{} {}", count, if count == 1 { "day" } else { "days" })
+}
+
+/// `45s`, `12m` or `2h 14m`
+fn format_time(seconds: f64) -> String {
+    let seconds = seconds as u64;
+    match seconds {
+        0..60 => format!("{}s", seconds),
+        60..3600 => format!("{}m", seconds / 60),
+        _ => format!("{}h {}m", seconds / 3600, seconds % 3600 / 60),
+    }
+}
+
+fn summary_json(summary: &Summary) -> Value {
+    let number = |count: usize| Value::Number(count as f64);
+    Value::Object(vec![
+        ("lessons_finished".to_string(), number(summary.lessons)),
+        ("lessons_total".to_string(), number(registry::LESSONS.len())),
+        ("sections_finished".to_string(), number(summary.sections)),
+        ("quizzes_taken".to_string(), number(summary.quizzes_taken)),
+        ("quizzes_passed".to_string(), number(summary.quizzes_passed)),
+        (
+            "checkpoints_answered".to_string(),
+            number(summary.checkpoints_answered),
+        ),
+        (
+            "checkpoints_right".to_string(),
+            number(summary.checkpoints_right),
+        ),
+        ("streak_days".to_string(), number(summary.streak)),
+        (
+            "longest_streak_days".to_string(),
+            number(summary.longest_streak),
+        ),
+        (
+            "seconds_spent".to_string(),
+            Value::Number(summary.seconds.round()),
+        ),
+    ])
+}
+
+/// What the activity log says about one lesson
+#[derive(Debug, Default, PartialEq)]
+struct Measured {
+    /// From the first run to completion, when that was one sitting of a
+    /// minute or more
+    minutes: Option<usize>,
+    asked: usize,
+    correct: usize,
+}
+
+/// Work out new estimates for every lesson, print the ones that changed and
+/// save them all for `list`
+pub fn recalibrate() -> Result<(), String> {
+    let progress = Progress::load();
+    let log = activity::load();
+    let measured: Vec<Measured> = registry::LESSONS
+        .iter()
+        .map(|lesson| measure(lesson, progress.completed_at(lesson.name), &log))
+        .collect();
+    let timed = measured.iter().filter(|one| one.minutes.is_some()).count();
+    let answers: usize = measured.iter().map(|one| one.asked).sum();
+    if timed == 0 && answers == 0 {
+        println!(
+            "Nothing to measure yet: run lessons section by section and take \
+             quizzes, then recalibrate"
+        );
+        return Ok(());
+    }
+
+    let pace = pace(registry::LESSONS, &measured);
+    println!(
+        "Measured {} {} and {} quiz {}; you take {:.1}x the estimated time\n",
+        timed,
+        if timed == 1 { "lesson" } else { "lessons" },
+        answers,
+        if answers == 1 { "answer" } else { "answers" },
+        pace
+    );
+    let width = registry::name_width();
+    let mut calibration = Calibration::default();
+    let mut changed = 0;
+    for (lesson, measured) in registry::LESSONS.iter().zip(&measured) {
+        let estimate = suggest(lesson, measured, pace);
+        calibration.set(lesson.name, estimate);
+        if estimate.minutes == lesson.estimated_minutes()
+            && estimate.difficulty == lesson.difficulty
+        {
+            continue;
+        }
+        if changed == 0 {
+            println!("{:<width$} {:<12} DIFFICULTY", "LESSON", "MINUTES");
+        }
+        changed += 1;
+        let difficulty = if estimate.difficulty == lesson.difficulty {
+            lesson.difficulty.name().to_string()
+        } else {
+            format!(
+                "{} -> {}",
+                lesson.difficulty.name(),
+                estimate.difficulty.name()
+            )
+        };
+        println!(
+            "{:<width$} {:<12} {}",
+            lesson.name,
+            format!("{} -> {}", lesson.estimated_minutes(), estimate.minutes),
+            difficulty
+        );
+    }
+    if changed == 0 {
+        println!("Every estimate still fits.");
+    }
+
+    let path = calibration
+        .save()
+        .map_err(|error| format!("cannot save the estimates: {}", error))?;
+    let note = format!("Saved to {}; `rust-learn list` uses it now", path.display());
+    println!("\n{}", output::dim(&note));
+    Ok(())
+}
+
+fn measure(lesson: &Lesson, completed: Option<SystemTime>, log: &[Entry]) -> Measured {
+    let section_prefix = format!("{}::", lesson.name);
+    let about = |key: &str| key == lesson.name || key.starts_with(&section_prefix);
+    let mut measured = Measured::default();
+    let mut started: Option<SystemTime> = None;
+    for entry in log {
+        match &entry.event {
+            Event::Run { key, seconds } if about(key) => {
+                let ran = Duration::try_from_secs_f64(*seconds).unwrap_or_default();
+                let start = entry.time.checked_sub(ran).unwrap_or(entry.time);
+                started = Some(started.map_or(start, |first| first.min(start)));
+            }
+            Event::Answer { key, correct, .. } if about(key) => {
+                measured.asked += 1;
+                measured.correct += usize::from(*correct);
+            }
+            _ => {}
+        }
+    }
+    measured.minutes = started
+        .zip(completed)
+        .and_then(|(start, end)| end.duration_since(start).ok())
+        .filter(|span| *span <= LONGEST_SITTING)
+        .map(|span| (span.as_secs() / 60) as usize)
+        .filter(|&minutes| minutes >= 1);
+    measured
+}
+
+/// The median of measured over estimated minutes; 1 with nothing measured
+fn pace(lessons: &[Lesson], measured: &[Measured]) -> f64 {
+    let mut ratios: Vec<f64> = lessons
+        .iter()
+        .zip(measured)
+        .filter_map(|(lesson, measured)| {
+            Some(measured.minutes? as f64 / lesson.estimated_minutes().max(1) as f64)
+        })
+        .collect();
+    if ratios.is_empty() {
+        return 1.0;
+    }
+    ratios.sort_by(f64::total_cmp);
+    ratios[ratios.len() / 2]
+}
+
+fn suggest(lesson: &Lesson, measured: &Measured, pace: f64) -> Estimate {
+    let estimate = lesson.estimated_minutes();
+    let minutes = measured
+        .minutes
+        .unwrap_or_else(|| (estimate as f64 * pace).round() as usize)
+        .max(1);
+    let mut difficulty = lesson.difficulty;
+    if measured.asked >= ANSWERS_TO_JUDGE {
+        let percent = measured.correct * 100 / measured.asked;
+        difficulty = match difficulty {
+            Difficulty::Beginner if percent < 50 => Difficulty::Intermediate,
+            Difficulty::Intermediate if percent < 50 => Difficulty::Advanced,
+            Difficulty::Intermediate if percent >= 90 && minutes <= estimate => {
+                Difficulty::Beginner
+            }
+            Difficulty::Advanced if percent >= 90 && minutes <= estimate => {
+                Difficulty::Intermediate
+            }
+            difficulty => difficulty,
+        };
+    }
+    Estimate {
+        minutes,
+        difficulty,
+    }
+}
+
+/// One variant of one section, over every log
+#[derive(Debug, Default, PartialEq)]
+struct Tally {
+    /// Logs in which it was shown
+    learners: usize,
+    asked: usize,
+    correct: usize,
+}
+
+/// Compare the variants of every section that has them
+pub fn variants(logs: &[PathBuf]) -> Result<(), String> {
+    let logs: Vec<Vec<Entry>> = if logs.is_empty() {
+        vec![activity::load()]
+    } else {
+        logs.iter()
+            .map(|path| {
+                activity::load_from(path)
+                    .map_err(|error| format!("cannot read {}: {}", path.display(), error))
+            })
+            .collect::<Result<_, _>>()?
+    };
+    let tallies = tally(&logs);
+
+    println!(
+        "{}",
+        output::bold(&format!(
+            "Explanation variants, from {} {}",
+            logs.len(),
+            if logs.len() == 1 { "log" } else { "logs" }
+        ))
+    );
+    let mut any = false;
+    for lesson in registry::LESSONS {
+        for section in lesson.sections {
+            if section.variants.is_empty() {
+                continue;
+            }
+            any = true;
+            let key = format!("{}::{}", lesson.name, section.name);
+            println!("\n{}", key);
+            for variant in section.variants {
+                let none = Tally::default();
+                let tally = tallies
+                    .get(&(key.clone(), variant.to_string()))
+                    .unwrap_or(&none);
+                let score = match tally.asked {
+                    0 => "no quiz answers yet".to_string(),
+                    asked => format!(
+                        "quiz {}/{} ({}%)",
+                        tally.correct,
+                        asked,
+                        tally.correct * 100 / asked
+                    ),
+                };
+                println!(
+                    "  {:<12} {:>3} {:<9} {}",
+                    variant,
+                    tally.learners,
+                    if tally.learners == 1 {
+                        "learner"
+                    } else {
+                        "learners"
+                    },
+                    score
+                );
+            }
+        }
+    }
+    if !any {
+        println!("No section has explanation variants yet.");
+    }
+    Ok(())
+}
+
+/// Each (`lesson::section`, variant) in `logs`
+fn tally(logs: &[Vec<Entry>]) -> BTreeMap<(String, String), Tally> {
+    let mut tallies: BTreeMap<(String, String), Tally> = BTreeMap::new();
+    for log in logs {
+        let mut shown = BTreeSet::new();
+        for entry in log {
+            match &entry.event {
+                Event::Shown { key, variant } => {
+                    shown.insert((key.clone(), variant.clone()));
+                }
+                Event::Answer {
+                    key,
+                    variant: Some(variant),
+                    correct,
+                } => {
+                    let tally = tallies.entry((key.clone(), variant.clone())).or_default();
+                    tally.asked += 1;
+                    tally.correct += usize::from(*correct);
+                }
+                _ => {}
+            }
+        }
+        for key in shown {
+            tallies.entry(key).or_default().learners += 1;
+        }
+    }
+    tallies
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_streaks() {
+        let days: BTreeSet<u64> = [10, 11, 12, 20, 21].into();
+        assert_eq!(streaks(&days, 21), (2, 3));
+        assert_eq!(streaks(&days, 22), (2, 3));
+        assert_eq!(streaks(&days, 23), (0, 3));
+        assert_eq!(streaks(&BTreeSet::new(), 23), (0, 0));
+        assert_eq!(format_time(45.2), "45s");
+        assert_eq!(format_time(8040.0), "2h 14m");
+    }
+
+    #[test]
+    fn test_recalibrate() {
+        let lesson = registry::find("borrowing").unwrap();
+        let start = UNIX_EPOCH + Duration::from_secs(1_792_159_407);
+        let minutes = |count: u64| start + Duration::from_secs(count * 60);
+        let answer = |minute, correct| Entry {
+            time: minutes(minute),
+            event: Event::Answer {
+                key: "borrowing::dangling".to_string(),
+                variant: None,
+                correct,
+            },
+        };
+        let log = [
+            Entry {
+                time: start,
+                event: Event::Run {
+                    key: "borrowing::basics".to_string(),
+                    seconds: 0.5,
+                },
+            },
+            answer(50, false),
+            answer(51, false),
+            answer(52, true),
+        ];
+        let measured = measure(lesson, Some(minutes(45)), &log);
+        assert_eq!(
+            measured,
+            Measured {
+                minutes: Some(45),
+                asked: 3,
+                correct: 1
+            }
+        );
+        let estimate = suggest(lesson, &measured, 1.0);
+        assert_eq!(estimate.minutes, 45);
+        assert_eq!(estimate.difficulty, Difficulty::Advanced);
+
+        // Not one sitting: the pace decides instead
+        let measured = measure(lesson, Some(minutes(60 * 24)), &log);
+        assert_eq!(measured.minutes, None);
+        assert_eq!(
+            suggest(lesson, &measured, 2.0).minutes,
+            lesson.estimated_minutes() * 2
+        );
+        assert_eq!(pace(&[*lesson], &[Measured::default()]), 1.0);
+    }
+
+    #[test]
+    fn test_tally() {
+        let entry = |event| Entry {
+            time: UNIX_EPOCH,
+            event,
+        };
+        let shown = |variant: &str| {
+            entry(Event::Shown {
+                key: "ownership::slices".to_string(),
+                variant: variant.to_string(),
+            })
+        };
+        let answer = |variant: &str, correct| {
+            entry(Event::Answer {
+                key: "ownership::slices".to_string(),
+                variant: Some(variant.to_string()),
+                correct,
+            })
+        };
+        let logs = vec![
+            vec![shown("plain"), shown("plain"), answer("plain", true)],
+            vec![shown("analogy"), answer("analogy", true)],
+            vec![shown("plain"), answer("plain", false)],
+        ];
+        let tallies = tally(&logs);
+        let key = |variant: &str| ("ownership::slices".to_string(), variant.to_string());
+        assert_eq!(
+            tallies[&key("plain")],
+            Tally {
+                learners: 2,
+                asked: 2,
+                correct: 1
+            }
+        );
+        assert_eq!(
+            tallies[&key("analogy")],
+            Tally {
+                learners: 1,
+                asked: 1,
+                correct: 1
+            }
+        );
+    }
+}
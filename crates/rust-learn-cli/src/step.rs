@@ -0,0 +1,114 @@
+//! `run <lesson> --step`: one section at a time, moving between them with
+//! single keys
+
+use rust_learn_content::registry;
+use rust_learn_core::{input, output};
+
+const HELP: &str = "\
+  n, Enter  next section
+  p         previous section
+  r         run this section again
+  s         skip to a section, by name or number
+  q         quit
+  ?         this help";
+
+/// What a key asks for, with section `current` of `count` just run
+#[derive(Debug, PartialEq)]
+enum Step {
+    Run(usize),
+    Choose,
+    Help,
+    Quit,
+    /// Nothing to do, and why
+    Stay(&'static str),
+}
+
+fn step(key: char, current: usize, count: usize) -> Step {
+    match key.to_ascii_lowercase() {
+        'n' | ' ' | '\n' if current + 1 == count => Step::Quit,
+        'n' | ' ' | '\n' => Step::Run(current + 1),
+        'p' if current == 0 => Step::Stay("This is the first section"),
+        'p' => Step::Run(current - 1),
+        'r' => Step::Run(current),
+        's' => Step::Choose,
+        '?' | 'h' => Step::Help,
+        'q' => Step::Quit,
+        _ => Step::Stay("Press ? for the keys"),
+    }
+}
+
+/// Where `section` is in `lesson`
+fn position(lesson: &registry::Lesson, section: &registry::Section) -> usize {
+    lesson
+        .sections
+        .iter()
+        .position(|candidate| candidate.name == section.name)
+        .expect("sections are looked up in their own lesson")
+}
+
+/// Run `lesson` from section `start`, waiting for a key after each one.
+/// Not interactive, every section runs in turn
+pub fn run(lesson: &'static registry::Lesson, start: &registry::Section) {
+    let count = lesson.sections.len();
+    let interactive = input::interactive();
+    let mut current = position(lesson, start);
+    loop {
+        crate::run_lesson(lesson.name, Some(lesson.sections[current].name));
+        if !interactive {
+            if current + 1 == count {
+                return;
+            }
+            current += 1;
+            continue;
+        }
+        loop {
+            let prompt = format!(
+                "[{}/{}] n next, p previous, r again, s skip to, q quit, ? help: ",
+                current + 1,
+                count
+            );
+            let Some(key) = input::read_key(&format!("\n{}", output::dim(&prompt))) else {
+                return;
+            };
+            match step(key, current, count) {
+                Step::Run(next) => {
+                    current = next;
+                    break;
+                }
+                Step::Choose => {
+                    let Some(key) = input::prompt_input("Section: ") else {
+                        return;
+                    };
+                    match crate::resolve_section(lesson, &key) {
+                        Ok(section) => {
+                            current = position(lesson, section);
+                            break;
+                        }
+                        Err(message) => println!("{}", message),
+                    }
+                }
+                Step::Help => println!("{}", HELP),
+                Step::Quit => return,
+                Step::Stay(note) => println!("{}", output::dim(note)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step() {
+        assert_eq!(step('n', 0, 3), Step::Run(1));
+        assert_eq!(step('\n', 2, 3), Step::Quit);
+        assert_eq!(step('P', 2, 3), Step::Run(1));
+        assert!(matches!(step('p', 0, 3), Step::Stay(_)));
+        assert_eq!(step('r', 1, 3), Step::Run(1));
+        assert_eq!(step('s', 1, 3), Step::Choose);
+        assert_eq!(step('?', 1, 3), Step::Help);
+        assert_eq!(step('q', 1, 3), Step::Quit);
+        assert!(matches!(step('\x1b', 1, 3), Step::Stay(_)));
+    }
+}
@@ -0,0 +1,486 @@
+//! `rust-learn tui` - the lessons in a two-pane terminal interface
+//!
+//! The lesson list is on the left and the selected lesson's output (or its
+//! source) on the right. Lessons run in a child `rust-learn run` process with
+//! `--non-interactive --no-color`, so their output, and anything cargo or a C
+//! compiler prints for them, lands in the pane instead of on the screen.
+//!
+//! The split view (`v`, or `tui --split`) divides the right pane in two: the
+//! code on the left, the output it printed on the right, each scrolling on
+//! its own. After running one section, the code is that section's function.
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use rust_learn_content::registry::{self, Lesson};
+use rust_learn_core::highlight::{self, Rgb};
+use rust_learn_core::output::{self, LineKind, Palette, Theme};
+use rust_learn_core::progress::Progress;
+use rust_learn_core::{i18n, toolchain};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal};
+
+const HELP: &str = "j/k lesson  n next unfinished  Enter run  1-9 section  s source  v split  c complete  PgUp/PgDn scroll  [/] heading  q quit";
+
+/// The help line in the split view, where `s` moves between the panes
+const SPLIT_HELP: &str = "j/k lesson  Enter run  1-9 section  s/Tab other pane  v one pane  PgUp/PgDn scroll  [/] heading  q quit";
+
+/// Open the interface, in the split view with `split`, and run it until
+/// the user quits
+pub fn run(split: bool) -> io::Result<()> {
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(io::Error::other("the TUI needs a terminal"));
+    }
+
+    let mut terminal = ratatui::try_init()?;
+    let mut app = App::new();
+    if split {
+        app.view = View::Split;
+    }
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum View {
+    Output,
+    Source,
+    /// Source and output side by side
+    Split,
+}
+
+struct App {
+    selected: ListState,
+    view: View,
+    /// Each lesson's output from its last run in this session
+    outputs: HashMap<&'static str, String>,
+    /// The section (from 1) of the lessons whose last run was one section
+    sections: HashMap<&'static str, usize>,
+    scroll: u16,
+    /// The source pane's scroll in the split view; `scroll` is the output's
+    source_scroll: u16,
+    /// In the split view, whether keys scroll the source pane
+    source_focused: bool,
+    /// Rows inside the right pane at the last draw, for paging and clamping
+    page: u16,
+    progress: Progress,
+    status: String,
+    palette: Palette,
+    theme: Theme,
+    /// Highlighted source, kept between draws
+    code: HashMap<&'static str, Vec<Line<'static>>>,
+    quit: bool,
+}
+
+impl App {
+    fn new() -> App {
+        let settings = output::settings();
+        let theme = if settings.color {
+            settings.theme
+        } else {
+            Theme::Mono
+        };
+        App {
+            selected: ListState::default().with_selected(Some(0)),
+            view: View::Output,
+            outputs: HashMap::new(),
+            sections: HashMap::new(),
+            scroll: 0,
+            source_scroll: 0,
+            source_focused: false,
+            page: 0,
+            progress: Progress::load(),
+            status: String::new(),
+            palette: theme.palette(),
+            theme,
+            code: HashMap::new(),
+            quit: false,
+        }
+    }
+
+    fn run(mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key.code, terminal)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn lesson(&self) -> &'static Lesson {
+        &registry::LESSONS[self.selected.selected().unwrap_or(0)]
+    }
+
+    fn handle_key(&mut self, code: KeyCode, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let count = registry::LESSONS.len();
+        let index = self.selected.selected().unwrap_or(0);
+
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Down | KeyCode::Char('j') => self.select((index + 1) % count),
+            KeyCode::Up | KeyCode::Char('k') => self.select((index + count - 1) % count),
+            KeyCode::Char('n') => match (1..=count)
+                .map(|step| (index + step) % count)
+                .find(|&next| !self.progress.is_complete(registry::LESSONS[next].name))
+            {
+                Some(next) => self.select(next),
+                None => self.status = "Every lesson is complete".to_string(),
+            },
+            KeyCode::Enter | KeyCode::Char('r') => self.run_lesson(None, terminal)?,
+            KeyCode::Char(digit @ '1'..='9') => {
+                let number = digit.to_digit(10).unwrap_or(0) as usize;
+                if number <= self.lesson().sections.len() {
+                    self.run_lesson(Some(number), terminal)?;
+                } else {
+                    self.status = format!(
+                        "{} has {} sections",
+                        self.lesson().name,
+                        self.lesson().sections.len()
+                    );
+                }
+            }
+            KeyCode::Char('s') | KeyCode::Tab if self.view == View::Split => {
+                self.source_focused = !self.source_focused;
+            }
+            KeyCode::Char('s') | KeyCode::Tab => {
+                self.view = match self.view {
+                    View::Source => View::Output,
+                    _ => View::Source,
+                };
+                self.scroll = 0;
+            }
+            KeyCode::Char('v') => {
+                self.view = match self.view {
+                    View::Split => View::Output,
+                    _ => View::Split,
+                };
+                self.scroll = 0;
+                self.source_scroll = 0;
+            }
+            KeyCode::Char('c') | KeyCode::Char(' ') => self.toggle_complete(),
+            KeyCode::PageDown | KeyCode::Char('J') => self.scroll_by(self.page as i32),
+            KeyCode::PageUp | KeyCode::Char('K') => self.scroll_by(-(self.page as i32)),
+            KeyCode::Home | KeyCode::Char('g') => *self.focused_scroll() = 0,
+            KeyCode::End | KeyCode::Char('G') => *self.focused_scroll() = u16::MAX,
+            KeyCode::Char(']') => self.jump_to_heading(true),
+            KeyCode::Char('[') => self.jump_to_heading(false),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn select(&mut self, index: usize) {
+        self.selected.select(Some(index));
+        self.scroll = 0;
+        self.source_scroll = 0;
+        self.status.clear();
+    }
+
+    /// The scroll of the pane the keys move
+    fn focused_scroll(&mut self) -> &mut u16 {
+        if self.view == View::Split && self.source_focused {
+            &mut self.source_scroll
+        } else {
+            &mut self.scroll
+        }
+    }
+
+    fn scroll_by(&mut self, rows: i32) {
+        let scroll = self.focused_scroll();
+        *scroll = (*scroll as i32 + rows).clamp(0, u16::MAX as i32) as u16;
+    }
+
+    /// Scroll to the next (or previous) numbered section heading in the pane
+    fn jump_to_heading(&mut self, forward: bool) {
+        let text = self.pane_text();
+        let headings = text
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| output::line_kind(line) == LineKind::Heading)
+            .map(|(row, _)| row as u16);
+        let scroll = self.focused_scroll();
+        let target = if forward {
+            headings.clone().find(|&row| row > *scroll)
+        } else {
+            headings.filter(|&row| row < *scroll).last()
+        };
+        if let Some(row) = target {
+            *scroll = row;
+        }
+    }
+
+    /// Run the lesson (or one section) in a child process and keep its output
+    fn run_lesson(
+        &mut self,
+        section: Option<usize>,
+        terminal: &mut DefaultTerminal,
+    ) -> io::Result<()> {
+        let lesson = self.lesson();
+        let target = match section {
+            Some(number) => format!("{} {}", lesson.name, number),
+            None => lesson.name.to_string(),
+        };
+        self.status = format!("Running {}...", target);
+        terminal.draw(|frame| self.draw(frame))?;
+
+        let number = section.map(|number| number.to_string());
+        let mut args = vec!["--non-interactive", "--no-color"];
+        args.extend(output::settings().verbosity.flag());
+        args.extend(["--lang", i18n::lang()]);
+        args.extend(["run", lesson.name]);
+        args.extend(number.as_deref());
+
+        let output = toolchain::run(std::env::current_exe()?, &std::env::current_dir()?, &args)?;
+        let mut text = output.stdout;
+        if !output.stderr.trim().is_empty() {
+            text.push_str("\n--- stderr ---\n");
+            text.push_str(&output.stderr);
+        }
+        self.outputs.insert(lesson.name, text);
+        match section {
+            Some(number) => self.sections.insert(lesson.name, number),
+            None => self.sections.remove(lesson.name),
+        };
+        // The child recorded the run in the progress file
+        self.progress = Progress::load();
+        if self.view == View::Source {
+            self.view = View::Output;
+        }
+        self.scroll = 0;
+        self.source_scroll = 0;
+        self.status = if output.success {
+            format!("Finished {}", target)
+        } else {
+            format!("{} failed; its error is at the end of the output", target)
+        };
+        Ok(())
+    }
+
+    fn toggle_complete(&mut self) {
+        let name = self.lesson().name;
+        let complete = self.progress.toggle(name);
+        self.status = match self.progress.save() {
+            Ok(()) if complete => format!("Marked {} complete", name),
+            Ok(()) => format!("Marked {} not complete", name),
+            Err(error) => format!("Could not save progress: {}", error),
+        };
+    }
+
+    /// What the right pane (or, split, the focused one) shows for the
+    /// selected lesson
+    fn pane_text(&self) -> String {
+        match self.view {
+            View::Source => self.lesson().source.to_string(),
+            View::Split if self.source_focused => self.split_source().to_string(),
+            View::Output | View::Split => self.output_text(),
+        }
+    }
+
+    /// `source` highlighted (`highlight::lines`), as lines for a pane
+    fn code_lines(&mut self, source: &'static str) -> Vec<Line<'static>> {
+        let theme = self.theme;
+        self.code
+            .entry(source)
+            .or_insert_with(|| {
+                highlight::lines(source, theme)
+                    .into_iter()
+                    .map(|pieces| {
+                        let spans = pieces.into_iter().map(|(style, text)| {
+                            let mut span = Style::new();
+                            if let Some(Rgb(r, g, b)) = style.color {
+                                span = span.fg(Color::Rgb(r, g, b));
+                            }
+                            if style.bold {
+                                span = span.add_modifier(Modifier::BOLD);
+                            }
+                            if style.italic {
+                                span = span.add_modifier(Modifier::ITALIC);
+                            }
+                            Span::styled(text, span)
+                        });
+                        Line::from(spans.collect::<Vec<_>>())
+                    })
+                    .collect()
+            })
+            .clone()
+    }
+
+    /// The selected lesson's last output, or how to run it
+    fn output_text(&self) -> String {
+        let lesson = self.lesson();
+        if let Some(text) = self.outputs.get(lesson.name) {
+            return text.clone();
+        }
+        let mut text = format!(
+            "{}\n\nEnter runs the whole lesson, a number one section:\n\n",
+            lesson.title
+        );
+        for (index, section) in lesson.sections.iter().enumerate() {
+            text.push_str(&format!("{:>2}. {}\n", index + 1, section.title));
+        }
+        text
+    }
+
+    /// The code next to the output in the split view: the function of the
+    /// section that last ran on its own, or else the whole lesson
+    fn split_source(&self) -> &'static str {
+        let lesson = self.lesson();
+        self.sections
+            .get(lesson.name)
+            .and_then(|&number| lesson.sections.get(number - 1))
+            .and_then(|section| lesson.section_source(section))
+            .unwrap_or(lesson.source)
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, footer] =
+            Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [left, right] =
+            Layout::horizontal([Constraint::Length(24), Constraint::Min(0)]).areas(main);
+
+        let items: Vec<ListItem> = registry::LESSONS
+            .iter()
+            .map(|lesson| {
+                let mark = if self.progress.is_complete(lesson.name) {
+                    "[x]"
+                } else {
+                    "[ ]"
+                };
+                ListItem::new(format!("{} {}", mark, lesson.name))
+            })
+            .collect();
+        let title = format!(
+            " Lessons {}/{} ",
+            self.progress.completed_count(),
+            registry::LESSONS.len()
+        );
+        let list = List::new(items)
+            .block(Block::bordered().title(title))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, left, &mut self.selected);
+
+        self.page = right.height.saturating_sub(2);
+        let name = self.lesson().name;
+        match self.view {
+            View::Output => {
+                let text = self.output_text();
+                let lines = text.lines().map(|line| styled(line, &self.palette));
+                let title = format!(" {} - output ", name);
+                render_pane(
+                    frame,
+                    right,
+                    title,
+                    lines.collect(),
+                    &mut self.scroll,
+                    false,
+                );
+            }
+            View::Source => {
+                let lines = self.code_lines(self.lesson().source);
+                let title = format!(" {} - source ", name);
+                render_pane(frame, right, title, lines, &mut self.scroll, false);
+            }
+            View::Split => {
+                let [code, printed] =
+                    Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+                        .areas(right);
+                let lines = self.code_lines(self.split_source());
+                let title = match self.sections.get(name) {
+                    Some(number) => format!(" {} {} - source ", name, number),
+                    None => format!(" {} - source ", name),
+                };
+                let focused = self.source_focused;
+                render_pane(frame, code, title, lines, &mut self.source_scroll, focused);
+
+                let text = self.output_text();
+                let lines = text.lines().map(|line| styled(line, &self.palette));
+                let title = format!(" {} - output ", name);
+                render_pane(
+                    frame,
+                    printed,
+                    title,
+                    lines.collect(),
+                    &mut self.scroll,
+                    !focused,
+                );
+            }
+        }
+
+        let status = match self.view {
+            _ if !self.status.is_empty() => &self.status,
+            View::Split => SPLIT_HELP,
+            _ => HELP,
+        };
+        frame.render_widget(Paragraph::new(status).style(sgr_style("2")), footer);
+    }
+}
+
+/// `lines` in a bordered pane, from row `scroll` (clamped so the last line
+/// is at the bottom at most); a `focused` pane has a bold border
+fn render_pane(
+    frame: &mut Frame,
+    area: Rect,
+    title: String,
+    lines: Vec<Line>,
+    scroll: &mut u16,
+    focused: bool,
+) {
+    let last = (lines.len() as u16).saturating_sub(area.height.saturating_sub(2));
+    *scroll = (*scroll).min(last);
+    let mut block = Block::bordered().title(title);
+    if focused {
+        block = block.border_style(Style::new().add_modifier(Modifier::BOLD));
+    }
+    let pane = Paragraph::new(lines).block(block).scroll((*scroll, 0));
+    frame.render_widget(pane, area);
+}
+
+/// A line of lesson output, colored the way `lesson_line` colors it
+fn styled<'a>(line: &'a str, palette: &Palette) -> Line<'a> {
+    let kind = output::line_kind(line);
+    let rest = sgr_style(palette.line(kind));
+    let highlight = matches!(kind, LineKind::Bullet | LineKind::Text);
+    if !highlight || line.matches('`').count() % 2 == 1 {
+        return Line::styled(line, rest);
+    }
+
+    let code = sgr_style(palette.code);
+    let spans: Vec<Span> = line
+        .split('`')
+        .enumerate()
+        .map(|(index, part)| {
+            if index % 2 == 1 {
+                Span::styled(format!("`{}`", part), code)
+            } else {
+                Span::styled(part, rest)
+            }
+        })
+        .collect();
+    Line::from(spans)
+}
+
+/// The ratatui style for a palette entry's ANSI codes
+fn sgr_style(codes: &str) -> Style {
+    codes
+        .split(';')
+        .fold(Style::new(), |style, code| match code {
+            "1" => style.add_modifier(Modifier::BOLD),
+            "2" => style.add_modifier(Modifier::DIM),
+            "3" => style.add_modifier(Modifier::ITALIC),
+            "4" => style.add_modifier(Modifier::UNDERLINED),
+            "31" => style.fg(Color::Red),
+            "32" => style.fg(Color::Green),
+            "33" => style.fg(Color::Yellow),
+            "34" => style.fg(Color::Blue),
+            "35" => style.fg(Color::Magenta),
+            "36" => style.fg(Color::Cyan),
+            _ => style,
+        })
+}
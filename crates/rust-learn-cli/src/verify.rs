@@ -0,0 +1,265 @@
+//! `rust-learn verify`: check the exercises (`exercises::find`) in lesson
+//! order, say which pass, and record the ones that do as progress
+//! (`exercise::<name>`), firing the `exercise` hooks for each that passes
+//! for the first time. rustc's errors are shown for the first exercise that
+//! fails, which is the one to work on next; naming an exercise checks only it.
+//!
+//! In classroom mode each check is added to the teacher's results
+//! (`classroom::record`). `--watch` checks again whenever an exercise file
+//! changes, and `--build-only` compiles one exercise with debug info into
+//! `target/exercises/`, for a debugger to start (`dev vscode` sets both up).
+//! Each time, the exercises directory's Cargo.toml is brought up to date, so
+//! rust-analyzer knows every exercise.
+//!
+//! The registered content packs' exercises are checked too. A pack's
+//! version of a lesson takes the place of the shipped one only when
+//! `pack_precedence` says so (`packs::merge`); `verify week-3/ownership`
+//! checks one version by name.
+
+use crate::save_progress;
+use rust_learn_content::registry;
+use rust_learn_core::exercises::{self, Exercise, Outcome};
+use rust_learn_core::hooks::{self, Event, EventKind};
+use rust_learn_core::output::{self, Glyph};
+use rust_learn_core::progress::Progress;
+use rust_learn_core::{classroom, dirs, packs};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+/// Where `--build-only` puts the binary, under the current directory
+pub const BUILD_DIR: &str = "target/exercises";
+
+/// How often `--watch` looks for changed files
+const WATCH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The exercises in `dir` and the registered packs, one set per lesson
+/// (`packs::merge`), or just `name`: an exercise, or `source/lesson` for one
+/// source's exercises of a lesson, shadowed or not
+fn select(dir: &Path, name: Option<&str>) -> Result<Vec<Exercise>, String> {
+    let order: Vec<&str> = registry::LESSONS.iter().map(|lesson| lesson.name).collect();
+    let packs = packs::list();
+    let builtin = Some(dir).filter(|dir| dir.is_dir());
+    if builtin.is_none() && packs.is_empty() {
+        return Err(format!(
+            "there are no exercises in {} (--dir picks another directory)",
+            dir.display()
+        ));
+    }
+    let sets = packs::lesson_sets(builtin, &packs, &order);
+    if builtin.is_some() {
+        let shipped: Vec<Exercise> = sets
+            .iter()
+            .filter(|set| set.source == packs::BUILTIN)
+            .flat_map(|set| set.exercises.iter().cloned())
+            .collect();
+        keep_project(dir, &shipped);
+    }
+    if let Some(id) = name.filter(|name| name.contains('/')) {
+        return sets
+            .into_iter()
+            .find(|set| set.id() == id)
+            .map(|set| set.exercises)
+            .ok_or_else(|| format!("there is no {} (`rust-learn pack list` shows them)", id));
+    }
+
+    let merged = packs::merge(sets, &packs::precedence()).map_err(|errors| errors.join("\n"))?;
+    if name.is_none() {
+        for (set, by) in &merged.shadowed {
+            let note = format!("{} shadows {}", by, set.id());
+            println!("{}", output::dim(&note));
+        }
+    }
+    let mut found: Vec<Exercise> = merged
+        .active
+        .into_iter()
+        .flat_map(|set| set.exercises)
+        .collect();
+    if let Some(name) = name {
+        found.retain(|exercise| exercise.name == name);
+        if found.is_empty() {
+            return Err(format!(
+                "there is no exercise '{}' in {}",
+                name,
+                dir.display()
+            ));
+        }
+    }
+    Ok(found)
+}
+
+/// The exercise whose id (`Exercise::id`) is `id`, in `dir` or a pack,
+/// whether its lesson's set is in use or shadowed
+pub fn find(dir: &Path, id: &str) -> Result<Exercise, String> {
+    let order: Vec<&str> = registry::LESSONS.iter().map(|lesson| lesson.name).collect();
+    let builtin = Some(dir).filter(|dir| dir.is_dir());
+    packs::lesson_sets(builtin, &packs::list(), &order)
+        .into_iter()
+        .flat_map(|set| set.exercises)
+        .find(|exercise| exercise.id() == id)
+        .ok_or_else(|| {
+            format!(
+                "there is no exercise '{}' in {} or a pack (a pack's is `<pack>/<name>`)",
+                id,
+                dir.display()
+            )
+        })
+}
+
+/// Keep `dir` a Cargo project for rust-analyzer (`exercises::write_project`);
+/// the exercises are still checked when it cannot be written
+pub fn keep_project(dir: &Path, found: &[Exercise]) {
+    if let Err(error) = exercises::write_project(dir, found) {
+        let message = format!(
+            "Cannot write the Cargo project in {}: {}",
+            dir.display(),
+            error
+        );
+        eprintln!("{}", output::red(&message));
+    }
+}
+
+/// Check the exercises in `dir`, or just `name`; true when all of them pass
+pub fn run(dir: &Path, name: Option<&str>) -> Result<bool, String> {
+    let found = select(dir, name)?;
+
+    let scratch = dirs::scratch_dir("exercises");
+    let mut progress = Progress::load();
+    let mut first_failure: Option<(&Exercise, String)> = None;
+    let mut passed = 0;
+    for exercise in &found {
+        let start = Instant::now();
+        let outcome = exercise
+            .check(&scratch)
+            .map_err(|error| format!("cannot check {}: {}", exercise.name, error))?;
+        record(exercise, &outcome, start.elapsed());
+        let lesson = match &exercise.pack {
+            Some(pack) => format!("{}/{}", pack, exercise.lesson),
+            None => exercise.lesson.clone(),
+        };
+        let label = format!("{} ({})", exercise.name, lesson);
+        match outcome {
+            Outcome::Passed => {
+                passed += 1;
+                let new = progress.mark_complete(&exercise.key());
+                if new {
+                    hooks::fire(&passed_event(exercise, start.elapsed()));
+                }
+                let note = if new { " (new)" } else { "" };
+                let status = output::status(Glyph::Ok, "ok", output::green, 0);
+                println!("{:<32} {}{}", label, status, note);
+            }
+            Outcome::NotDone => {
+                let note = format!("works; delete '{}'", exercises::NOT_DONE);
+                let status = output::status(Glyph::Warning, &note, output::dim, 0);
+                println!("{:<32} {}", label, status);
+            }
+            Outcome::Failed(errors) => {
+                let status = output::status(Glyph::Fail, "fails", output::red, 0);
+                println!("{:<32} {}", label, status);
+                if first_failure.is_none() {
+                    first_failure = Some((exercise, errors));
+                }
+            }
+        }
+    }
+    save_progress(&progress);
+
+    if let Some((exercise, errors)) = first_failure {
+        let next = output::status(Glyph::Hint, "Next up:", output::bold, 0);
+        println!("\n{} {}:", next, exercise.path.display());
+        for line in errors.lines() {
+            println!("  {}", line);
+        }
+    }
+    println!("\n{}/{} exercises pass", passed, found.len());
+    Ok(passed == found.len())
+}
+
+/// The `exercise` hook event for `exercise` passing
+fn passed_event(exercise: &Exercise, elapsed: Duration) -> Event {
+    let mut event = Event::new(EventKind::Exercise, &exercise.id(), elapsed);
+    event.title = format!("the exercise {}", exercise.id());
+    event.path = Some(exercise.path.display().to_string());
+    event
+}
+
+/// Add the check to the classroom results, in classroom mode
+fn record(exercise: &Exercise, outcome: &Outcome, elapsed: Duration) {
+    let outcome = match outcome {
+        Outcome::Passed => "passed",
+        Outcome::NotDone => "not_done",
+        Outcome::Failed(_) => "failed",
+    };
+    classroom::record(classroom::Record {
+        kind: "exercise",
+        name: exercise.id(),
+        score: usize::from(outcome == "passed"),
+        total: 1,
+        outcome: Some(outcome),
+        elapsed,
+        changed: fs::metadata(&exercise.path)
+            .and_then(|meta| meta.modified())
+            .ok(),
+    });
+}
+
+/// `run` now and again whenever an exercise file in `dir` changes, until
+/// the process is stopped
+pub fn watch(dir: &Path, name: Option<&str>) -> Result<(), String> {
+    let mut seen = None;
+    loop {
+        // Taken before checking, so an edit made during the check is not missed
+        let stamps = modified(dir);
+        if seen.as_ref() != Some(&stamps) {
+            if seen.is_some() {
+                println!();
+            }
+            println!("Checking exercises in {}", dir.display());
+            run(dir, name)?;
+            println!("{}", output::dim("Watching for changes; Ctrl-C stops"));
+            seen = Some(stamps);
+        }
+        thread::sleep(WATCH_INTERVAL);
+    }
+}
+
+/// Every exercise file in `dir` and when it last changed
+fn modified(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>)> {
+    let found = exercises::find(dir, &[]).unwrap_or_default();
+    found
+        .into_iter()
+        .map(|exercise| {
+            let time = fs::metadata(&exercise.path)
+                .and_then(|meta| meta.modified())
+                .ok();
+            (exercise.path, time)
+        })
+        .collect()
+}
+
+/// Compile exercise `name` with debug info into `BUILD_DIR`; false (after
+/// printing rustc's errors) when it does not compile
+pub fn build(dir: &Path, name: &str) -> Result<bool, String> {
+    let found = select(dir, Some(name))?;
+    let exercise = &found[0];
+    let binary = Path::new(BUILD_DIR).join(&exercise.name);
+    let built = exercise
+        .build(&binary)
+        .map_err(|error| format!("cannot build {}: {}", exercise.name, error))?;
+    match built {
+        Ok(()) => {
+            println!("Built {}", binary.display());
+            Ok(true)
+        }
+        Err(errors) => {
+            let status = output::status(Glyph::Fail, "does not compile:", output::red, 0);
+            println!("{} {}", exercise.name, status);
+            for line in errors.lines() {
+                println!("  {}", line);
+            }
+            Ok(false)
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! `rust-learn dev vscode`: tasks and a launch configuration for working on
+//! the exercises in VS Code. They act on the exercise open in the editor
+//! (`${fileBasenameNoExtension}` is its name):
+//!
+//! - "rust-learn: check exercise", the default test task: `verify <name>`
+//! - "rust-learn: watch exercise", in the background: `verify --watch <name>`
+//! - "rust-learn: build exercise for debugging": `verify --build-only <name>`
+//! - "rust-learn: debug exercise" in launch.json builds it with that task
+//!   and starts it under CodeLLDB
+//!
+//! rustc's errors in their output are matched, so they show in the Problems
+//! panel. The files' other tasks and configurations are kept; ours (their
+//! labels start with "rust-learn: ") are replaced. A file that is not plain
+//! JSON (VS Code allows comments) is left alone unless `--force` is given,
+//! and `--dry-run` shows which files would be created or replaced.
+
+use crate::verify;
+use rust_learn_core::json::Value;
+use rust_learn_core::transaction::Transaction;
+use std::fs;
+use std::path::Path;
+
+/// What our task labels and configuration names start with
+const PREFIX: &str = "rust-learn: ";
+
+const CHECK: &str = "rust-learn: check exercise";
+const WATCH: &str = "rust-learn: watch exercise";
+const BUILD: &str = "rust-learn: build exercise for debugging";
+const DEBUG: &str = "rust-learn: debug exercise";
+
+/// Write `.vscode/tasks.json` and `.vscode/launch.json` under `dir`, or
+/// with `dry_run` only say so
+pub fn generate(dir: &Path, force: bool, dry_run: bool) -> Result<(), String> {
+    let vscode = dir.join(".vscode");
+    let command = command(dir);
+    let tasks = vec![
+        task(
+            CHECK,
+            &command,
+            &["verify", "${fileBasenameNoExtension}"],
+            false,
+        ),
+        task(
+            WATCH,
+            &command,
+            &["verify", "--watch", "${fileBasenameNoExtension}"],
+            true,
+        ),
+        task(
+            BUILD,
+            &command,
+            &["verify", "--build-only", "${fileBasenameNoExtension}"],
+            false,
+        ),
+    ];
+    let program = format!(
+        "${{workspaceFolder}}/{}/${{fileBasenameNoExtension}}",
+        verify::BUILD_DIR
+    );
+    let launch = vec![object(vec![
+        ("name", DEBUG.into()),
+        ("type", "lldb".into()),
+        ("request", "launch".into()),
+        ("program", Value::String(program)),
+        ("args", Value::Array(Vec::new())),
+        ("cwd", "${workspaceFolder}".into()),
+        ("preLaunchTask", BUILD.into()),
+    ])];
+
+    let mut transaction = Transaction::new();
+    write(
+        &mut transaction,
+        &vscode.join("tasks.json"),
+        "2.0.0",
+        "tasks",
+        "label",
+        tasks,
+        force,
+    )?;
+    write(
+        &mut transaction,
+        &vscode.join("launch.json"),
+        "0.2.0",
+        "configurations",
+        "name",
+        launch,
+        force,
+    )?;
+    crate::carry_out(transaction, dry_run)?;
+    if dry_run {
+        return Ok(());
+    }
+    println!("Run \"Tasks: Run Test Task\" with an exercise open to check it.");
+    println!("Debugging needs the CodeLLDB extension (vadimcn.vscode-lldb).");
+    Ok(())
+}
+
+/// How the tasks start rust-learn: through cargo in a checkout of it, or the
+/// installed binary anywhere else
+fn command(dir: &Path) -> Vec<&'static str> {
+    if dir.join("crates/rust-learn-cli/Cargo.toml").is_file() {
+        vec!["cargo", "run", "--quiet", "--"]
+    } else {
+        vec!["rust-learn"]
+    }
+}
+
+fn task(label: &str, command: &[&str], args: &[&str], background: bool) -> Value {
+    let args = command[1..].iter().chain(args).map(|&arg| arg.into());
+    let mut entries = vec![
+        ("label", label.into()),
+        ("type", "process".into()),
+        ("command", command[0].into()),
+        ("args", Value::Array(args.collect())),
+        ("problemMatcher", problem_matcher(background)),
+    ];
+    if background {
+        entries.push(("isBackground", Value::Bool(true)));
+    }
+    if label == CHECK {
+        entries.push((
+            "group",
+            object(vec![
+                ("kind", "test".into()),
+                ("isDefault", Value::Bool(true)),
+            ]),
+        ));
+    }
+    object(entries)
+}
+
+/// rustc's `error[E0382]: ...` and `--> exercises/x.rs:3:5` lines, indented
+/// or not (`verify` indents them)
+fn problem_matcher(background: bool) -> Value {
+    let mut entries = vec![
+        ("owner", "rust-learn".into()),
+        ("source", "rustc".into()),
+        (
+            "fileLocation",
+            Value::Array(vec!["relative".into(), "${workspaceFolder}".into()]),
+        ),
+        (
+            "pattern",
+            Value::Array(vec![
+                object(vec![
+                    ("regexp", r"^\s*(error|warning)(?:\[\w+\])?: (.*)$".into()),
+                    ("severity", Value::Number(1.0)),
+                    ("message", Value::Number(2.0)),
+                ]),
+                object(vec![
+                    ("regexp", r"^\s*--> (.*):(\d+):(\d+)$".into()),
+                    ("file", Value::Number(1.0)),
+                    ("line", Value::Number(2.0)),
+                    ("column", Value::Number(3.0)),
+                ]),
+            ]),
+        ),
+    ];
+    if background {
+        // `verify --watch` starts every round with this line and ends it
+        // with the count of exercises that pass
+        entries.push((
+            "background",
+            object(vec![
+                ("activeByDefault", Value::Bool(true)),
+                ("beginsPattern", "^Checking exercises in ".into()),
+                ("endsPattern", r"^\d+/\d+ exercises pass$".into()),
+            ]),
+        ));
+    }
+    object(entries)
+}
+
+fn object(entries: Vec<(&str, Value)>) -> Value {
+    Value::Object(
+        entries
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+/// Plan writing `ours` into the `list` array of the file at `path`,
+/// replacing the entries whose `key` starts with `PREFIX` and keeping the
+/// rest
+fn write(
+    transaction: &mut Transaction,
+    path: &Path,
+    version: &str,
+    list: &str,
+    key: &str,
+    ours: Vec<Value>,
+    force: bool,
+) -> Result<(), String> {
+    let existing = match fs::read_to_string(path) {
+        Ok(text) => match Value::parse(&text) {
+            Ok(value) => Some(value),
+            Err(_) if force => None,
+            Err(error) => {
+                return Err(format!(
+                    "{} is not plain JSON ({}), so it was not changed; \
+                     --force replaces it",
+                    path.display(),
+                    error
+                ));
+            }
+        },
+        Err(_) => None,
+    };
+    let kept: Vec<Value> = existing
+        .as_ref()
+        .and_then(|value| value.get(list))
+        .and_then(Value::as_array)
+        .unwrap_or_default()
+        .iter()
+        .filter(|entry| {
+            !entry
+                .get(key)
+                .and_then(Value::as_str)
+                .is_some_and(|name| name.starts_with(PREFIX))
+        })
+        .cloned()
+        .collect();
+    let mut entries: Vec<(String, Value)> = existing
+        .as_ref()
+        .and_then(Value::as_object)
+        .unwrap_or_default()
+        .iter()
+        .filter(|(name, _)| name != "version" && name != list)
+        .cloned()
+        .collect();
+    entries.insert(0, ("version".to_string(), version.into()));
+    entries.push((
+        list.to_string(),
+        Value::Array(kept.into_iter().chain(ours).collect()),
+    ));
+
+    transaction.write(path, Value::Object(entries).pretty() + "\n");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_other_entries() {
+        let dir = std::env::temp_dir().join(format!("rust-learn-vscode-{}", std::process::id()));
+        let path = dir.join(".vscode/tasks.json");
+        fs::create_dir_all(dir.join(".vscode")).unwrap();
+        fs::write(
+            &path,
+            r#"{"version": "2.0.0", "tasks": [{"label": "mine"}, {"label": "rust-learn: old"}]}"#,
+        )
+        .unwrap();
+        generate(&dir, false, true).unwrap();
+        assert!(
+            fs::read_to_string(&path)
+                .unwrap()
+                .contains("rust-learn: old")
+        );
+        generate(&dir, false, false).unwrap();
+        let written = Value::parse(&fs::read_to_string(&path).unwrap()).unwrap();
+        let labels: Vec<&str> = written
+            .get("tasks")
+            .and_then(Value::as_array)
+            .unwrap()
+            .iter()
+            .filter_map(|task| task.get("label").and_then(Value::as_str))
+            .collect();
+        assert_eq!(labels, ["mine", CHECK, WATCH, BUILD]);
+
+        fs::write(&path, "// comments\n{}").unwrap();
+        assert!(generate(&dir, false, false).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
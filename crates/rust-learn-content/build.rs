@@ -0,0 +1,99 @@
+//! Build script for rust-learn-content
+//!
+//! Runs before the crate is compiled and writes `$OUT_DIR/lessons_generated.rs`:
+//! one `LessonSource` per lesson file (its line and section counts), plus the
+//! git commit, profile and target the binary was built with. `src/generated.rs`
+//! includes it.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let src = manifest_dir.join("src");
+
+    // Only rerun when a lesson changes (a directory means "anything inside it"),
+    // not on every build
+    println!("cargo:rerun-if-changed=src");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let mut files: Vec<PathBuf> = fs::read_dir(&src)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .collect();
+    files.sort();
+
+    let mut code = String::new();
+    code.push_str("// @generated by build.rs - do not edit\n\n");
+    code.push_str("pub const LESSON_SOURCES: &[LessonSource] = &[\n");
+    for path in &files {
+        let text = fs::read_to_string(path).unwrap();
+        let Some(sections) = count_sections(&text) else {
+            continue; // not a lesson: no SECTIONS table
+        };
+        let file = path.file_name().unwrap().to_string_lossy();
+        writeln!(
+            code,
+            "    LessonSource {{ file: {:?}, lines: {}, sections: {} }},",
+            file,
+            text.lines().count(),
+            sections
+        )
+        .unwrap();
+    }
+    code.push_str("];\n\n");
+
+    let git_hash = git_hash(&manifest_dir);
+    writeln!(code, "pub const GIT_HASH: Option<&str> = {:?};", git_hash).unwrap();
+    writeln!(
+        code,
+        "pub const BUILD_PROFILE: &str = {:?};",
+        env::var("PROFILE").unwrap()
+    )
+    .unwrap();
+    writeln!(
+        code,
+        "pub const BUILD_TARGET: &str = {:?};",
+        env::var("TARGET").unwrap()
+    )
+    .unwrap();
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("lessons_generated.rs"), code).unwrap();
+}
+
+/// Entries in the file's `pub const SECTIONS` table, if it has one
+fn count_sections(text: &str) -> Option<usize> {
+    let start = text.find("pub const SECTIONS")?;
+    let table = &text[start..];
+    let table = &table[..table.find("];")?];
+    Some(table.matches("section!(").count() + table.matches("Section::new(").count())
+}
+
+/// `git rev-parse --short HEAD`, or `None` outside a git checkout (e.g. a
+/// crates.io download) or without git installed
+fn git_hash(manifest_dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .current_dir(manifest_dir)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    // Rerun when HEAD moves. Watching a path that does not exist would rerun
+    // the script on every build, so only watch what is there.
+    let git_dir = manifest_dir.join("../../.git");
+    for watched in ["HEAD", "refs/heads"] {
+        if git_dir.join(watched).exists() {
+            println!("cargo:rerun-if-changed={}", git_dir.join(watched).display());
+        }
+    }
+
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
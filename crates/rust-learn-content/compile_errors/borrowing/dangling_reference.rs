@@ -0,0 +1,13 @@
+// Returning a reference to a String the function owns, which is dropped at
+// its end
+// expect: error[E0106]: missing lifetime specifier
+
+fn dangle() -> &String {
+    let s = String::from("hello");
+    &s
+}
+
+fn main() {
+    let reference_to_nothing = dangle();
+    println!("{}", reference_to_nothing);
+}
@@ -0,0 +1,9 @@
+// Changing a String while an immutable reference to it is still used
+// expect: error[E0502]: cannot borrow `s` as mutable because it is also borrowed as immutable
+
+fn main() {
+    let mut s = String::from("hello");
+    let s_ref = &s;
+    s.push_str(" world");
+    println!("{}", s_ref);
+}
@@ -0,0 +1,10 @@
+// Two mutable references to the same String at once
+// expect: error[E0499]: cannot borrow `data` as mutable more than once at a time
+
+fn main() {
+    let mut data = String::from("hello");
+    let ref1 = &mut data;
+    let ref2 = &mut data;
+    ref1.push_str(" modified");
+    ref2.push_str(" again");
+}
@@ -0,0 +1,14 @@
+// A list whose cells hold the rest of the list directly: its size is infinite
+// expect: error[E0072]: recursive type `List` has infinite size
+
+enum List {
+    Cons(i32, List),
+    Nil,
+}
+
+fn main() {
+    let list = List::Cons(1, List::Nil);
+    if let List::Cons(first, _) = list {
+        println!("first: {}", first);
+    }
+}
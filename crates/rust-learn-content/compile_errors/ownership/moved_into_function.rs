@@ -0,0 +1,12 @@
+// Using a String after passing it to a function that takes ownership
+// expect: error[E0382]: borrow of moved value: `s`
+
+fn takes_ownership(some_string: String) {
+    println!("{}", some_string);
+}
+
+fn main() {
+    let s = String::from("hello");
+    takes_ownership(s);
+    println!("s: {}", s);
+}
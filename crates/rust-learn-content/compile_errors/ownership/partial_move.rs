@@ -0,0 +1,18 @@
+// Using a struct after moving one of its fields out
+// expect: error[E0382]: borrow of partially moved value: `person`
+
+#[derive(Debug)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn main() {
+    let person = Person {
+        name: String::from("Alice"),
+        age: 30,
+    };
+    let name = person.name;
+    println!("name: '{}'", name);
+    println!("person: {:?}", person);
+}
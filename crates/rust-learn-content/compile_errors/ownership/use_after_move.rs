@@ -0,0 +1,9 @@
+// Using a String after `let s2 = s1;` moved it: there is one owner at a time
+// expect: error[E0382]: borrow of moved value: `s1`
+
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+    println!("s2: {}", s2);
+    println!("s1: {}", s1);
+}
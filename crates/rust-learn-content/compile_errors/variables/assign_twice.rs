@@ -0,0 +1,9 @@
+// Assigning to a variable declared without `mut`
+// expect: error[E0384]: cannot assign twice to immutable variable `x`
+
+fn main() {
+    let x = 5;
+    println!("x is {}", x);
+    x = 6;
+    println!("x is {}", x);
+}
@@ -0,0 +1,43 @@
+// Maximum Subarray
+//
+// Return the largest sum of a contiguous, non-empty run of the numbers.
+// The slice is never empty.
+//
+// time: 20
+// limit: 3
+// hint: Trying every start and end is O(n^2), and the large input has a million numbers.
+// hint: Walk once, keeping the best sum of a run that ends at the current number.
+// hint: That run either extends the previous one or starts again here: max(n, best_ending_here + n) (Kadane's algorithm).
+
+pub fn max_subarray(numbers: &[i64]) -> i64 {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        assert_eq!(max_subarray(&[-2, 1, -3, 4, -1, 2, 1, -5, 4]), 6);
+        assert_eq!(max_subarray(&[1]), 1);
+        assert_eq!(max_subarray(&[5, 4, -1, 7, 8]), 23);
+    }
+
+    #[test]
+    fn all_negative() {
+        assert_eq!(max_subarray(&[-3, -1, -2]), -1);
+    }
+
+    #[test]
+    fn large_input() {
+        // A million numbers that cancel out, with one good run in the middle
+        let mut numbers: Vec<i64> = (0..1_000_000)
+            .map(|n| if n % 2 == 0 { 3 } else { -4 })
+            .collect();
+        for number in &mut numbers[500_000..500_010] {
+            *number = 100;
+        }
+        assert_eq!(max_subarray(&numbers), 1003);
+    }
+}
@@ -0,0 +1,132 @@
+// Range Sum Queries
+//
+// The input is `n q` on the first line, then n numbers, then q queries of
+// two numbers `l r` (1-based, l <= r). For each query write the sum of the
+// numbers l through r, both included, on its own line of the output.
+// There are up to a million numbers and 200,000 queries: read them with the
+// Scanner below (`rust_learn_utils::io::Scanner`, see the fast_input lesson).
+//
+// time: 20
+// limit: 3
+// hint: Summing each range again is O(n) per query, far too slow for 200,000 of them.
+// hint: Keep prefix sums, prefix[i] = numbers[0] + ... + numbers[i - 1]; then sum(l..=r) = prefix[r] - prefix[l - 1].
+// hint: Write every answer with writeln!(output, ...)?; `output` may be a BufWriter, so do not flush per line.
+
+use std::io::{self, BufRead, Write};
+
+pub fn solve<R: BufRead, W: Write>(input: R, output: &mut W) -> io::Result<()> {
+    todo!()
+}
+
+/// Whitespace-separated tokens from a buffered reader, without a String per line
+pub struct Scanner<R> {
+    reader: R,
+    token: Vec<u8>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    pub fn new(reader: R) -> Self {
+        Scanner {
+            reader,
+            token: Vec::new(),
+        }
+    }
+
+    /// The next token, or None at the end of the input
+    pub fn token(&mut self) -> io::Result<Option<&[u8]>> {
+        self.token.clear();
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            let skipped = if self.token.is_empty() {
+                buffer.iter().take_while(|byte| byte.is_ascii_whitespace()).count()
+            } else {
+                0
+            };
+            let rest = &buffer[skipped..];
+            let length = rest
+                .iter()
+                .position(|byte| byte.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            self.token.extend_from_slice(&rest[..length]);
+            let ended = length < rest.len();
+            self.reader.consume(skipped + length);
+            if ended && !self.token.is_empty() {
+                break;
+            }
+        }
+        Ok((!self.token.is_empty()).then_some(self.token.as_slice()))
+    }
+
+    /// The next token as an i64
+    pub fn int(&mut self) -> io::Result<i64> {
+        let invalid = |message| io::Error::new(io::ErrorKind::InvalidData, message);
+        let token = self.token()?.ok_or_else(|| invalid("the input ended early"))?;
+        let (negative, digits) = match token {
+            [b'-', rest @ ..] => (true, rest),
+            _ => (false, token),
+        };
+        let mut value: i64 = 0;
+        for &byte in digits {
+            if !byte.is_ascii_digit() {
+                return Err(invalid("not a number"));
+            }
+            value = value * 10 - i64::from(byte - b'0');
+        }
+        Ok(if negative { value } else { -value })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(input: &str) -> String {
+        let mut output = Vec::new();
+        solve(input.as_bytes(), &mut output).expect("solve returned an error");
+        String::from_utf8(output).expect("the output is text")
+    }
+
+    #[test]
+    fn small() {
+        assert_eq!(run("5 3\n1 2 3 4 5\n1 5\n2 3\n4 4\n"), "15\n5\n4\n");
+        assert_eq!(run("3 2\n-5 10 -5\n1 3\n1 2\n"), "0\n5\n");
+        assert_eq!(run("1 1\n1000000000\n1 1\n"), "1000000000\n");
+    }
+
+    #[test]
+    fn large_input() {
+        // A million numbers and 200,000 wide queries, from a fixed seed
+        let (n, q) = (1_000_000usize, 200_000usize);
+        let mut state: u64 = 42;
+        let mut random = move || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            state >> 33
+        };
+        let numbers: Vec<i64> = (0..n).map(|_| random() as i64 % 2_000_001 - 1_000_000).collect();
+        let mut input = format!("{} {}\n", n, q);
+        for number in &numbers {
+            input.push_str(&number.to_string());
+            input.push('\n');
+        }
+        let mut queries = Vec::with_capacity(q);
+        for _ in 0..q {
+            let (a, b) = (random() as usize % n + 1, random() as usize % n + 1);
+            let (l, r) = (a.min(b), a.max(b));
+            input.push_str(&format!("{} {}\n", l, r));
+            queries.push((l, r));
+        }
+
+        let output = run(&input);
+        let answers: Vec<&str> = output.lines().collect();
+        assert_eq!(answers.len(), q, "one line per query");
+        // Every 10,000th answer, summed the slow way
+        for index in (0..q).step_by(10_000) {
+            let (l, r) = queries[index];
+            let expected: i64 = numbers[l - 1..r].iter().sum();
+            assert_eq!(answers[index], expected.to_string(), "query {}: {} {}", index + 1, l, r);
+        }
+    }
+}
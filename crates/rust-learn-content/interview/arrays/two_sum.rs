@@ -0,0 +1,51 @@
+// Two Sum
+//
+// Given a slice of numbers and a target, return the indices (i, j), with
+// i < j, of two numbers that add up to the target, or None when no two do.
+// When several pairs do, any one of them is right.
+//
+// time: 15
+// limit: 3
+// hint: For each number, the partner it needs is `target - number`.
+// hint: Remember the numbers already seen, by value, so finding the partner is one lookup.
+// hint: A HashMap<i64, usize> from value to index makes the whole thing one pass, O(n).
+
+pub fn two_sum(numbers: &[i64], target: i64) -> Option<(usize, usize)> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(numbers: &[i64], target: i64) {
+        let (i, j) = two_sum(numbers, target).expect("a pair adds up to the target");
+        assert!(i < j, "expected i < j, got ({}, {})", i, j);
+        assert_eq!(numbers[i] + numbers[j], target);
+    }
+
+    #[test]
+    fn small() {
+        check(&[2, 7, 11, 15], 9);
+        check(&[3, 2, 4], 6);
+        check(&[3, 3], 6);
+        check(&[-4, 10, 1, 8], 4);
+    }
+
+    #[test]
+    fn no_pair() {
+        assert_eq!(two_sum(&[], 1), None);
+        assert_eq!(two_sum(&[5], 10), None);
+        assert_eq!(two_sum(&[1, 2, 3], 100), None);
+    }
+
+    #[test]
+    fn large_input() {
+        // A million even numbers, and the only pair that works is at the end
+        let mut numbers: Vec<i64> = (0..1_000_000).map(|n| n * 2).collect();
+        numbers.push(-1);
+        numbers.push(-3);
+        assert_eq!(two_sum(&numbers, -4), Some((1_000_000, 1_000_001)));
+        assert_eq!(two_sum(&numbers[..1_000_000], 7), None);
+    }
+}
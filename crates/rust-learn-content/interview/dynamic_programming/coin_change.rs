@@ -0,0 +1,33 @@
+// Coin Change
+//
+// Return the fewest coins that add up to `amount`, using any number of each
+// coin, or None when no combination does. An amount of 0 takes 0 coins.
+//
+// time: 20
+// limit: 3
+// hint: Trying every combination recursively takes exponential time; the same smaller amounts come up again and again.
+// hint: Work out the answer for every amount from 0 up, so each one only looks at smaller ones.
+// hint: fewest[a] = 1 + min(fewest[a - coin]) over the coins that fit: O(amount * coins).
+
+pub fn coin_change(coins: &[usize], amount: usize) -> Option<usize> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        assert_eq!(coin_change(&[1, 2, 5], 11), Some(3));
+        assert_eq!(coin_change(&[2], 3), None);
+        assert_eq!(coin_change(&[1], 0), Some(0));
+        assert_eq!(coin_change(&[1, 3, 4], 6), Some(2));
+    }
+
+    #[test]
+    fn large_input() {
+        assert_eq!(coin_change(&[7, 11, 13, 17, 19, 23], 100_000), Some(4348));
+        assert_eq!(coin_change(&[4, 6, 10], 99_999), None);
+    }
+}
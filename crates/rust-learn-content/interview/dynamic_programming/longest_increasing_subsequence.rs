@@ -0,0 +1,34 @@
+// Longest Increasing Subsequence
+//
+// Return the length of the longest strictly increasing subsequence: numbers
+// taken in their order, not necessarily next to each other.
+//
+// time: 30
+// limit: 3
+// hint: The O(n^2) answer looks back at every earlier number for each one; the large input has 200,000.
+// hint: Keep, for each length, the smallest number an increasing run of that length can end with.
+// hint: That list stays sorted, so each number replaces one entry found by binary search (`partition_point`): O(n log n).
+
+pub fn longest_increasing(numbers: &[i64]) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        assert_eq!(longest_increasing(&[10, 9, 2, 5, 3, 7, 101, 18]), 4);
+        assert_eq!(longest_increasing(&[0, 1, 0, 3, 2, 3]), 4);
+        assert_eq!(longest_increasing(&[7, 7, 7, 7]), 1);
+        assert_eq!(longest_increasing(&[]), 0);
+    }
+
+    #[test]
+    fn large_input() {
+        // Two hundred thousand numbers in blocks that each count down
+        let numbers: Vec<i64> = (0..200_000).map(|n| (n / 100) * 1000 - n % 100).collect();
+        assert_eq!(longest_increasing(&numbers), 2000);
+    }
+}
@@ -0,0 +1,49 @@
+// Number of Islands
+//
+// The grid is rows of `#` (land) and `.` (water). Land cells next to each
+// other up, down, left or right belong to the same island. Count the
+// islands.
+//
+// time: 25
+// limit: 3
+// hint: Each time you find land you have not visited, that is a new island: visit all of it.
+// hint: A search from that cell (BFS or DFS) marks the island visited; keep a Vec<Vec<bool>> or change the grid.
+// hint: A recursive DFS overflows the stack on a million-cell island; keep the cells to visit in a Vec or VecDeque instead.
+
+pub fn count_islands(grid: &[Vec<u8>]) -> usize {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<u8>> {
+        rows.iter().map(|row| row.as_bytes().to_vec()).collect()
+    }
+
+    #[test]
+    fn small() {
+        assert_eq!(count_islands(&grid(&["##...", "##...", "..#..", "...##"])), 3);
+        assert_eq!(count_islands(&grid(&["###", "#.#", "###"])), 1);
+        assert_eq!(count_islands(&grid(&["#.#", ".#.", "#.#"])), 5);
+        assert_eq!(count_islands(&[]), 0);
+    }
+
+    #[test]
+    fn large_input() {
+        // A 1000 x 1000 grid holding one island that snakes through all of it
+        let size = 1000;
+        let mut cells = vec![vec![b'.'; size]; size];
+        for (row, line) in cells.iter_mut().enumerate() {
+            if row % 2 == 0 {
+                line.fill(b'#');
+            } else if row % 4 == 1 {
+                line[size - 1] = b'#';
+            } else {
+                line[0] = b'#';
+            }
+        }
+        assert_eq!(count_islands(&cells), 1);
+    }
+}
@@ -0,0 +1,43 @@
+// Count in Range
+//
+// `sorted` is in ascending order and may repeat values. For each query
+// (low, high), count the values v with low <= v <= high. Return the counts
+// in the order of the queries.
+//
+// time: 20
+// limit: 3
+// hint: Scanning the slice for every query is O(n * q): here that is 10^11 steps.
+// hint: In a sorted slice, where a range starts and ends can be found by binary search.
+// hint: `sorted.partition_point(|&v| v < low)` is the first index at or above low; do the same for the end.
+
+pub fn count_in_range(sorted: &[i64], queries: &[(i64, i64)]) -> Vec<usize> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        let sorted = [1, 3, 3, 3, 5, 8, 13];
+        let queries = [(3, 3), (0, 100), (4, 7), (9, 12), (5, 13), (20, 30)];
+        assert_eq!(count_in_range(&sorted, &queries), [3, 7, 1, 0, 3, 0]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(count_in_range(&[], &[(0, 10)]), [0]);
+    }
+
+    #[test]
+    fn large_input() {
+        // A million values, each twice, and a hundred thousand queries
+        let sorted: Vec<i64> = (0..1_000_000).map(|n| n / 2).collect();
+        let queries: Vec<(i64, i64)> = (0..100_000).map(|n| (n * 4, n * 4 + 9)).collect();
+        let counts = count_in_range(&sorted, &queries);
+        assert_eq!(counts.len(), 100_000);
+        assert_eq!(counts[0], 20);
+        assert_eq!(counts[99_999], 20);
+    }
+}
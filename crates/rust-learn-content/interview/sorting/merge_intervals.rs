@@ -0,0 +1,44 @@
+// Merge Intervals
+//
+// Merge every overlapping pair of closed intervals (start, end), with
+// start <= end, and return what is left sorted by start. Intervals that only
+// touch, like (1, 3) and (3, 5), overlap.
+//
+// time: 20
+// limit: 3
+// hint: Merging any two that overlap until nothing changes is at least O(n^2).
+// hint: Once the intervals are sorted by start, an interval can only overlap the one merged just before it.
+// hint: Sort (O(n log n)), then walk once, extending the last merged interval or starting a new one.
+
+pub fn merge(intervals: &[(i64, i64)]) -> Vec<(i64, i64)> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        assert_eq!(
+            merge(&[(1, 3), (2, 6), (8, 10), (15, 18)]),
+            [(1, 6), (8, 10), (15, 18)]
+        );
+        assert_eq!(merge(&[(1, 4), (4, 5)]), [(1, 5)]);
+        assert_eq!(merge(&[(5, 7), (1, 2)]), [(1, 2), (5, 7)]);
+        assert_eq!(merge(&[(1, 10), (2, 3), (4, 5)]), [(1, 10)]);
+        assert_eq!(merge(&[]), []);
+    }
+
+    #[test]
+    fn large_input() {
+        // Two hundred thousand separate intervals, given back to front
+        let intervals: Vec<(i64, i64)> = (0..200_000).rev().map(|n| (n * 3, n * 3 + 1)).collect();
+        let merged = merge(&intervals);
+        assert_eq!(merged.len(), 200_000);
+        assert_eq!(merged[0], (0, 1));
+        // ... and as many that all chain into one
+        let chained: Vec<(i64, i64)> = (0..200_000).map(|n| (n, n + 1)).collect();
+        assert_eq!(merge(&chained), [(0, 200_000)]);
+    }
+}
@@ -0,0 +1,43 @@
+// Balanced Brackets
+//
+// Say whether every bracket in the text is closed by the right kind, in the
+// right order: `()`, `[]` and `{}`. Any other character is ignored.
+//
+// time: 15
+// limit: 3
+// hint: Removing matched pairs until none are left works, but each pass is O(n) and there can be n/2 passes.
+// hint: The bracket that must close next is always the most recent one still open.
+// hint: Push each opening bracket on a Vec used as a stack; a closing one must match what you pop.
+
+pub fn is_balanced(text: &str) -> bool {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced() {
+        assert!(is_balanced(""));
+        assert!(is_balanced("()[]{}"));
+        assert!(is_balanced("fn main() { let v = vec![(1, 2)]; }"));
+    }
+
+    #[test]
+    fn unbalanced() {
+        assert!(!is_balanced("(]"));
+        assert!(!is_balanced("([)]"));
+        assert!(!is_balanced("(("));
+        assert!(!is_balanced("))"));
+    }
+
+    #[test]
+    fn large_input() {
+        // Half a million brackets deep
+        let deep = format!("{}{}", "([{".repeat(200_000), "}])".repeat(200_000));
+        assert!(is_balanced(&deep));
+        let broken = format!("{}){}", "(".repeat(300_000), ")".repeat(300_000));
+        assert!(!is_balanced(&broken));
+    }
+}
@@ -0,0 +1,35 @@
+// First Unique Character
+//
+// Return the byte index of the first character that appears exactly once in
+// the text, or None when every character repeats. The text is ASCII.
+//
+// time: 15
+// limit: 3
+// hint: Counting each character's appearances by scanning the whole text again is O(n^2).
+// hint: Count every character in one pass first, then find the first with a count of 1.
+// hint: For ASCII a `[usize; 128]` indexed by the byte is all the counting you need.
+
+pub fn first_unique(text: &str) -> Option<usize> {
+    todo!()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        assert_eq!(first_unique("leetcode"), Some(0));
+        assert_eq!(first_unique("loveleetcode"), Some(2));
+        assert_eq!(first_unique("aabb"), None);
+        assert_eq!(first_unique(""), None);
+    }
+
+    #[test]
+    fn large_input() {
+        // A million repeating letters, and the only unique one last
+        let mut text = "abcdefghijklmnopqrstuvwxy".repeat(40_000);
+        text.push('z');
+        assert_eq!(first_unique(&text), Some(1_000_000));
+    }
+}
@@ -0,0 +1,353 @@
+/// Global Allocators in Rust - Where Box, Vec and String Get Their Memory
+///
+/// Every heap allocation goes through one `#[global_allocator]`. The rust-learn
+/// binary installs a counting allocator (rust-learn-core's `alloc_counter`)
+/// around the system allocator, or around mimalloc when built with
+/// `--features mimalloc`. This lesson counts allocations, runs a few
+/// allocation-heavy benchmarks, and compares the two allocators directly.
+use crate::generated::BUILD_PROFILE;
+use crate::registry::{Question, Section, section};
+use rust_learn_core::alloc_counter::{self, AllocStats};
+use rust_learn_core::runner::format_duration;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::BTreeMap;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+pub fn allocators() {
+    println!("=== Global Allocators Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- allocators <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(what_a_global_allocator_is, "What a Global Allocator Is"),
+    section!(counting_allocator, "A Counting Allocator"),
+    section!(implementing_an_allocator, "Rules for Implementing One"),
+    section!(allocation_benchmarks, "Allocation-Heavy Benchmarks"),
+    section!(comparing_allocators, "System Allocator vs mimalloc"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "what_a_global_allocator_is",
+        prompt: "What does #[global_allocator] on a static do?",
+        choices: &[
+            "Makes it the allocator behind Box, Vec, String and every other heap allocation",
+            "Allocates every global on the heap",
+            "Turns the heap off",
+            "Frees memory automatically",
+        ],
+        explanation: "There is one global allocator per program; the standard library's collections all go through it.",
+    },
+    Question {
+        section: "implementing_an_allocator",
+        prompt: "Why is `unsafe impl GlobalAlloc` unsafe?",
+        choices: &[
+            "Everything trusts it to return memory that fits the Layout, which the compiler cannot check",
+            "Allocating always needs a system call",
+            "It runs before main",
+            "Its methods may panic",
+        ],
+        explanation: "A wrong size or alignment from the allocator corrupts memory in safe code far away, so implementing it is a promise.",
+    },
+];
+
+fn what_a_global_allocator_is() {
+    println!("1. What a Global Allocator Is:");
+    println!("==============================\n");
+
+    explain!("THE DEFAULT:");
+    explain!("============");
+    explain!("Box, Vec, String, HashMap, Rc, Arc ... all ask one allocator for memory.");
+    explain!("Unless a program says otherwise that is `std::alloc::System`: malloc and");
+    explain!("free on Linux and macOS, HeapAlloc on Windows.");
+
+    explain!("\nSWAPPING IT:");
+    explain!("============");
+    explain!("use mimalloc::MiMalloc;");
+    println!();
+    explain!("#[global_allocator]");
+    explain!("static GLOBAL: MiMalloc = MiMalloc;");
+    println!();
+    explain!("One static anywhere in the program is enough; no other code changes.");
+
+    println!("\nIN THIS BINARY:");
+    println!("===============");
+    match alloc_counter::inner_name() {
+        Some(name) => println!(
+            "CountingAlloc<{}> (from crates/rust-learn-cli/src/main.rs)",
+            name
+        ),
+        None => println!("Not the rust-learn binary: the counting allocator is not installed"),
+    }
+
+    explain!("\nCOMMON CHOICES:");
+    explain!("===============");
+    explain!("System            - no dependency, the platform's malloc");
+    explain!("mimalloc          - Microsoft's allocator, fast for many small objects");
+    explain!("tikv-jemallocator - jemalloc, good under heavy multi-threaded load");
+    explain!("Both of the last two are C libraries built by cargo through the cc crate.");
+
+    println!();
+}
+
+fn counting_allocator() {
+    println!("2. A Counting Allocator:");
+    println!("========================\n");
+
+    explain!("THE WRAPPER (rust-learn-core/src/alloc_counter.rs):");
+    explain!("===================================================");
+    explain!("unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {{");
+    explain!("    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {{");
+    explain!("        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);");
+    explain!("        unsafe {{ self.inner.alloc(layout) }}");
+    explain!("    }}");
+    explain!("    // dealloc, realloc and alloc_zeroed count and forward the same way");
+    explain!("}}");
+
+    if alloc_counter::inner_name().is_none() {
+        println!("\n(The counting allocator is not installed, so every count below is 0.)");
+    }
+
+    println!("\nWHAT COMMON CODE ALLOCATES:");
+    println!("===========================");
+    report("[1, 2, 3] on the stack", || black_box([1, 2, 3]).len());
+    report("Box::new(5)", || *black_box(Box::new(5)));
+    report("String::from(\"hi\")", || {
+        black_box(String::from("hi")).len()
+    });
+    report("push 1000 into Vec::new()", || {
+        let mut numbers = Vec::new();
+        for n in 0..1000 {
+            numbers.push(n);
+        }
+        black_box(numbers).len()
+    });
+    report("push 1000 into Vec::with_capacity(1000)", || {
+        let mut numbers = Vec::with_capacity(1000);
+        for n in 0..1000 {
+            numbers.push(n);
+        }
+        black_box(numbers).len()
+    });
+    report("format!(\"{}-{}\", 1, 2)", || {
+        black_box(format!("{}-{}", 1, 2)).len()
+    });
+
+    explain!("\nA Vec that grows doubles its capacity, so 1000 pushes cost one allocation");
+    explain!("plus a realloc per doubling; reserving up front costs the allocation only.");
+
+    println!();
+}
+
+/// Print how many allocations `f` made
+fn report<T>(label: &str, f: impl FnOnce() -> T) {
+    let (_, stats) = alloc_counter::count(f);
+    println!(
+        "{:<42} {:>3} allocs {:>3} reallocs {:>6} bytes",
+        label, stats.allocations, stats.reallocations, stats.bytes
+    );
+}
+
+fn implementing_an_allocator() {
+    println!("3. Rules for Implementing One:");
+    println!("==============================\n");
+
+    explain!("THE CONTRACT:");
+    explain!("=============");
+    explain!("- GlobalAlloc is an `unsafe trait`: the compiler trusts every pointer it returns");
+    explain!("- Return memory of at least layout.size() bytes, aligned to layout.align()");
+    explain!("- Return null on failure; never panic or unwind out of alloc");
+    explain!("- dealloc receives the same layout the memory was allocated with");
+    explain!("- It is a `static` shared by all threads, so it must be Sync");
+    explain!(
+        "- Being a static, it must be built by a const expression (CountingAlloc::new is a const fn)"
+    );
+
+    explain!("\nTHE TRAPS:");
+    explain!("==========");
+    explain!("- Allocating inside alloc (a String, println!, a Mutex that boxes) recurses forever");
+    explain!("  That is why the counters are plain atomics");
+    explain!("- Only one #[global_allocator] may exist in the whole dependency graph");
+    explain!("- Memory from one allocator must never be freed by another");
+
+    println!("\nLAYOUTS:");
+    println!("========");
+    let layouts = [
+        ("u8", Layout::new::<u8>()),
+        ("u64", Layout::new::<u64>()),
+        ("(u8, u32)", Layout::new::<(u8, u32)>()),
+        ("[u16; 10]", Layout::new::<[u16; 10]>()),
+        ("String", Layout::new::<String>()),
+    ];
+    for (name, layout) in layouts {
+        println!(
+            "{:<10} size {:>2}  align {}",
+            name,
+            layout.size(),
+            layout.align()
+        );
+    }
+
+    println!();
+}
+
+fn allocation_benchmarks() {
+    println!("4. Allocation-Heavy Benchmarks:");
+    println!("===============================\n");
+
+    let allocator = alloc_counter::inner_name().unwrap_or("system");
+    println!(
+        "Allocator: {}   Build profile: {}",
+        allocator, BUILD_PROFILE
+    );
+    if BUILD_PROFILE == "debug" {
+        explain!("(Debug builds are slow everywhere; use --release for fair numbers.)");
+    }
+    println!();
+
+    println!(
+        "{:<34} {:>10} {:>8} {:>9}",
+        "workload", "time", "allocs", "reallocs"
+    );
+    for (name, workload) in WORKLOADS {
+        let (elapsed, stats) = best_of(3, *workload);
+        println!(
+            "{:<34} {:>10} {:>8} {:>9}",
+            name,
+            format_duration(elapsed),
+            stats.allocations,
+            stats.reallocations
+        );
+    }
+
+    explain!("\nTO COMPARE ALLOCATORS:");
+    explain!("======================");
+    explain!("cargo run --release -- allocators 4");
+    explain!("cargo run --release --features mimalloc -- allocators 4");
+
+    println!();
+}
+
+const WORKLOADS: &[(&str, fn())] = &[
+    ("100k pushes into Vec::new()", vec_growth),
+    ("100k short Strings", many_strings),
+    ("50k BTreeMap inserts", btree_inserts),
+    ("100k Box<[u8; 64]> alloc + free", box_churn),
+];
+
+/// The fastest of `runs` runs, with the allocations the last one made
+fn best_of(runs: usize, workload: fn()) -> (Duration, AllocStats) {
+    let mut best = Duration::MAX;
+    let mut stats = AllocStats::default();
+    for _ in 0..runs {
+        let start = Instant::now();
+        ((), stats) = alloc_counter::count(workload);
+        best = best.min(start.elapsed());
+    }
+    (best, stats)
+}
+
+fn vec_growth() {
+    let mut numbers = Vec::new();
+    for n in 0..100_000u64 {
+        numbers.push(n);
+    }
+    black_box(numbers);
+}
+
+fn many_strings() {
+    let strings: Vec<String> = (0..100_000).map(|n| n.to_string()).collect();
+    black_box(strings);
+}
+
+fn btree_inserts() {
+    let mut map = BTreeMap::new();
+    for n in 0..50_000u32 {
+        map.insert(n.wrapping_mul(2_654_435_761), n);
+    }
+    black_box(map);
+}
+
+fn box_churn() {
+    for n in 0..100_000 {
+        black_box(Box::new([n as u8; 64]));
+    }
+}
+
+fn comparing_allocators() {
+    println!("5. System Allocator vs mimalloc:");
+    println!("================================\n");
+
+    explain!("Both allocators implement GlobalAlloc, so they can be called directly,");
+    explain!("side by side, whichever one is installed globally. Each row allocates");
+    println!("{} blocks of one size, then frees them all.\n", BLOCKS);
+
+    #[cfg(feature = "mimalloc")]
+    {
+        println!(
+            "{:>8} {:>12} {:>12} {:>8}",
+            "size", "system", "mimalloc", "ratio"
+        );
+        for size in BLOCK_SIZES {
+            let system = time_raw(&System, size);
+            let mimalloc = time_raw(&mimalloc::MiMalloc, size);
+            println!(
+                "{:>8} {:>12} {:>12} {:>7.2}x",
+                size,
+                format_duration(system),
+                format_duration(mimalloc),
+                system.as_secs_f64() / mimalloc.as_secs_f64()
+            );
+        }
+        explain!("\nA ratio above 1 means mimalloc was faster.");
+    }
+
+    #[cfg(not(feature = "mimalloc"))]
+    {
+        println!("{:>8} {:>12}", "size", "system");
+        for size in BLOCK_SIZES {
+            let system = time_raw(&System, size);
+            println!("{:>8} {:>12}", size, format_duration(system));
+        }
+        println!("\nmimalloc is an optional dependency. For the side-by-side table run:");
+        println!("cargo run --release --features mimalloc -- allocators comparing_allocators");
+    }
+
+    explain!("\nWHEN TO SWITCH:");
+    explain!("===============");
+    explain!("- Measure your own program first; allocators win on some workloads only");
+    explain!("- Many small, short-lived allocations across threads favor mimalloc/jemalloc");
+    explain!("- Fewer allocations (with_capacity, reuse buffers) often beats any allocator");
+
+    println!();
+}
+
+const BLOCKS: usize = 10_000;
+const BLOCK_SIZES: [usize; 4] = [16, 64, 512, 4096];
+
+/// Time `BLOCKS` allocations of `size` bytes and their deallocation
+fn time_raw(allocator: &impl GlobalAlloc, size: usize) -> Duration {
+    let layout = Layout::from_size_align(size, 8).unwrap();
+    let mut blocks = Vec::with_capacity(BLOCKS);
+
+    let start = Instant::now();
+    for _ in 0..BLOCKS {
+        // SAFETY: the layout has a non-zero size
+        let ptr = unsafe { allocator.alloc(layout) };
+        if ptr.is_null() {
+            std::alloc::handle_alloc_error(layout);
+        }
+        blocks.push(ptr);
+    }
+    for ptr in blocks {
+        // SAFETY: `ptr` came from this allocator with this layout, and is freed once
+        unsafe { allocator.dealloc(ptr, layout) };
+    }
+    start.elapsed()
+}
@@ -0,0 +1,345 @@
+/// API Design in Rust - Evolving a Function Signature Without Breaking Callers
+///
+/// Once other people depend on your library, every public signature is a promise.
+/// This lesson follows `rust_learn_core::config_loader` from a plain `fn load(path: &str)`
+/// to a generic, builder-based API, and shows which steps are safe in a minor
+/// release and which ones break callers (see crates/rust-learn-core/tests/compile_fail for the proof).
+use crate::registry::{Question, Section, section};
+use rust_learn_core::config_loader::{self, LoadError, LoadOptions, Loader};
+use std::fs;
+use std::path::PathBuf;
+
+pub fn api_design() {
+    println!("=== API Design Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- api_design <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(starting_point, "The Starting Point - fn load(path: &str)"),
+    section!(
+        generalizing_parameters,
+        "Generalizing the Parameter - impl AsRef<Path>"
+    ),
+    section!(builder_options, "Adding Options With a Builder"),
+    section!(
+        non_exhaustive_types,
+        "#[non_exhaustive] Config Structs and Enums"
+    ),
+    section!(deprecated_shims, "#[deprecated] Shims"),
+    section!(semver_implications, "Semver Implications"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "generalizing_parameters",
+        prompt: "Why take `path: impl AsRef<Path>` instead of `path: &str`?",
+        choices: &[
+            "Callers can pass a &str, String, PathBuf or &Path",
+            "It runs faster",
+            "It stops callers passing strings",
+            "It makes the function async",
+        ],
+        explanation: "AsRef<Path> accepts anything that can be viewed as a path, so callers stop converting before every call.",
+    },
+    Question {
+        section: "builder_options",
+        prompt: "Why add options through a builder instead of new parameters to load?",
+        choices: &[
+            "New options can come later without breaking callers",
+            "Builders run faster",
+            "Functions take three parameters at most",
+            "Builders avoid allocating",
+        ],
+        explanation: "Every new parameter breaks every caller; a new builder method breaks no one.",
+    },
+    Question {
+        section: "non_exhaustive_types",
+        prompt: "What does #[non_exhaustive] on a public struct stop other crates from doing?",
+        choices: &[
+            "Building it with a struct literal",
+            "Reading its public fields",
+            "Deriving Debug for it",
+            "Passing it to functions",
+        ],
+        explanation: "Outside the crate, the struct can only be made through its constructors, so adding a field is not a breaking change.",
+    },
+];
+
+fn starting_point() {
+    println!("1. The Starting Point - fn load(path: &str):");
+    println!("===========================================\n");
+
+    let sample = SampleConfig::create();
+    let path = sample.path.as_path();
+
+    println!("VERSION 0.1 - The Simplest Thing That Works:");
+    println!("--------------------------------------------");
+
+    // What the first release looked like
+    mod v1 {
+        pub fn load(path: &str) -> std::io::Result<String> {
+            std::fs::read_to_string(path)
+        }
+    }
+
+    let path_str = path.to_str().expect("temp dir path is valid UTF-8");
+    match v1::load(path_str) {
+        Ok(text) => println!("v1::load read {} bytes", text.len()),
+        Err(e) => println!("v1::load failed: {}", e),
+    }
+
+    explain!("\nTHE PAIN POINTS:");
+    explain!("================");
+    explain!("1. Callers holding a PathBuf must convert it: path.to_str().unwrap()");
+    explain!("2. Non-UTF-8 paths cannot be loaded at all");
+    explain!("3. There is nowhere to put options like 'strict' or 'max size'");
+    explain!("4. Returning io::Error leaks an implementation detail");
+
+    println!();
+}
+
+fn generalizing_parameters() {
+    println!("2. Generalizing the Parameter - impl AsRef<Path>:");
+    println!("=================================================\n");
+
+    let sample = SampleConfig::create();
+    let path = sample.path.as_path();
+
+    println!("VERSION 0.2 - pub fn load(path: impl AsRef<Path>):");
+    println!("--------------------------------------------------");
+
+    // Every caller of the old signature still compiles...
+    let as_str: &str = path.to_str().expect("temp dir path is valid UTF-8");
+    let config = config_loader::load(as_str).expect("sample config loads");
+    println!("load(&str)     -> {} entries", config.entries.len());
+
+    // ...and new kinds of arguments are accepted without conversions
+    let as_string: String = as_str.to_string();
+    let as_path_buf: PathBuf = path.to_path_buf();
+    println!(
+        "load(String)   -> {} entries",
+        config_loader::load(as_string).map_or(0, |c| c.entries.len())
+    );
+    println!(
+        "load(&PathBuf) -> {} entries",
+        config_loader::load(&as_path_buf).map_or(0, |c| c.entries.len())
+    );
+    println!(
+        "load(&Path)    -> {} entries",
+        config_loader::load(path).map_or(0, |c| c.entries.len())
+    );
+
+    explain!("\nIS THIS REALLY NON-BREAKING?");
+    explain!("============================");
+    explain!("Almost. Calls whose argument type was only known because the");
+    explain!("parameter was &str now fail type inference, e.g. load(name.as_ref()).");
+    explain!(
+        "See crates/rust-learn-core/tests/compile_fail/inference_break.rs for the real error."
+    );
+
+    explain!("\nKEEP THE GENERIC SURFACE THIN:");
+    explain!("==============================");
+    explain!("load() immediately calls path.as_ref() and hands a &Path to");
+    explain!("non-generic code, so monomorphization only copies a one-line wrapper.");
+
+    println!();
+}
+
+fn builder_options() {
+    println!("3. Adding Options With a Builder:");
+    println!("=================================\n");
+
+    let sample = SampleConfig::create();
+    let path = sample.path.as_path();
+
+    explain!("WHY NOT ADD A PARAMETER?");
+    explain!("========================");
+    explain!("load(path, true, 4096) breaks every existing caller, and the");
+    explain!("booleans mean nothing at the call site.");
+
+    println!("\nTHE BUILDER - Defaults Plus Named Overrides:");
+    println!("===========================================");
+
+    let config = Loader::new(path)
+        .strict(true)
+        .max_size(4096)
+        .load()
+        .expect("sample config is strict-clean");
+    explain!("Loader::new(path).strict(true).max_size(4096).load()");
+    println!(
+        "Loaded '{}' with {} entries",
+        config.name,
+        config.entries.len()
+    );
+
+    if let Some(level) = config.get("log_level") {
+        println!("log_level = {}", level);
+    }
+
+    println!("\nOPTIONS ARE ENFORCED:");
+    println!("=====================");
+
+    match Loader::new(path).max_size(8).load() {
+        Ok(_) => println!("Unexpectedly loaded an oversized file"),
+        Err(e) => println!("max_size(8): {}", e),
+    }
+
+    let text = "name = demo\nthis line has no equals sign";
+    match config_loader::parse("inline", text, &LoadOptions::default()) {
+        Ok(config) => println!("lenient parse kept {} entries", config.entries.len()),
+        Err(e) => println!("lenient parse failed: {}", e),
+    }
+
+    let mut strict = LoadOptions::default();
+    strict.strict = true;
+    match config_loader::parse("inline", text, &strict) {
+        Ok(config) => println!("strict parse kept {} entries", config.entries.len()),
+        Err(e) => println!("strict parse: {}", e),
+    }
+
+    explain!("\nBUILDER RULES:");
+    explain!("==============");
+    explain!("1. Adding a builder method is a minor (non-breaking) change");
+    explain!("2. Defaults must stay the same, or behaviour silently changes");
+    explain!("3. Keep a one-call shortcut (load) for the common case");
+
+    println!();
+}
+
+fn non_exhaustive_types() {
+    println!("4. #[non_exhaustive] Config Structs and Enums:");
+    println!("==============================================\n");
+
+    let sample = SampleConfig::create();
+    let path = sample.path.as_path();
+
+    println!("NON-EXHAUSTIVE STRUCTS - Room to Add Fields:");
+    println!("===========================================");
+
+    // LoadOptions { strict: true, max_size: 10 } does not compile outside the
+    // defining crate - start from Default and assign the fields you care about.
+    let mut options = LoadOptions::default();
+    options.max_size = 1024;
+    println!("Options built from Default: {:?}", options);
+    explain!("Struct literals and exhaustive destructuring are rejected outside the crate");
+
+    println!("\nNON-EXHAUSTIVE ENUMS - Room to Add Variants:");
+    println!("===========================================");
+
+    let missing = path.with_file_name("does-not-exist.conf");
+    let errors = [
+        config_loader::load(&missing).unwrap_err(),
+        Loader::new(path).max_size(1).load().unwrap_err(),
+    ];
+
+    for error in &errors {
+        match error {
+            LoadError::Io(_) => println!("I/O problem: {}", error),
+            LoadError::TooLarge { .. } => println!("Too large: {}", error),
+            LoadError::Syntax { line, .. } => println!("Syntax error on line {}", line),
+            // Required: a future release may add variants
+            _ => println!("Some other error: {}", error),
+        }
+    }
+
+    explain!("\nTHE TRADE-OFF:");
+    explain!("==============");
+    explain!("Callers can never be sure they handled every case.");
+    explain!("Use it for types that are expected to grow, not for closed sets like Ordering.");
+
+    println!();
+}
+
+#[allow(deprecated)]
+fn deprecated_shims() {
+    println!("5. #[deprecated] Shims:");
+    println!("=======================\n");
+
+    let sample = SampleConfig::create();
+    let path = sample.path.as_path();
+
+    println!("KEEPING OLD ENTRY POINTS ALIVE:");
+    println!("===============================");
+    explain!(
+        "#[deprecated(since = \"0.2.0\", note = \"use `Loader::new(path).strict(true).load()` instead\")]"
+    );
+    explain!("pub fn load_strict(path: &str) -> Result<Config, LoadError>");
+
+    let path_str = path.to_str().expect("temp dir path is valid UTF-8");
+    match config_loader::load_strict(path_str) {
+        Ok(config) => println!("load_strict still works: {} entries", config.entries.len()),
+        Err(e) => println!("load_strict failed: {}", e),
+    }
+
+    explain!("\nWHAT CALLERS SEE:");
+    explain!("=================");
+    explain!("A warning that points to the replacement - not an error.");
+    explain!("Crates built with #![deny(warnings)] or deny(deprecated) DO fail,");
+    explain!("see crates/rust-learn-core/tests/compile_fail/deprecated_denied.rs.");
+
+    explain!("\nSHIM RULES:");
+    explain!("===========");
+    explain!("1. Implement the shim in terms of the new API (one code path)");
+    explain!("2. Say what to use instead in the note");
+    explain!("3. Remove shims only in the next major release");
+
+    println!();
+}
+
+fn semver_implications() {
+    println!("6. Semver Implications:");
+    println!("=======================\n");
+
+    explain!("MINOR RELEASE (0.2 -> 0.3, 1.2 -> 1.3) - Callers Keep Compiling:");
+    explain!("----------------------------------------------------------------");
+    explain!("- Adding a new function, type, or builder method");
+    explain!("- Adding a field or variant to a #[non_exhaustive] type");
+    explain!("- Deprecating an item (it still exists)");
+    explain!("- Generalizing &str to impl AsRef<Path> (with the inference caveat)");
+
+    explain!("\nMAJOR RELEASE (0.x -> 0.(x+1), 1.x -> 2.0) - Callers May Break:");
+    explain!("--------------------------------------------------------------");
+    explain!("- Removing or renaming a public item");
+    explain!("- Adding a parameter to an existing function");
+    explain!("- Adding a pub field to an exhaustive struct (struct literals break)");
+    explain!("- Adding a variant to an exhaustive enum (matches break)");
+    explain!("- Adding #[non_exhaustive] to an existing type");
+
+    explain!("\nCOMPILE-FAIL FIXTURES - Proving It:");
+    explain!("==================================");
+    explain!("crates/rust-learn-core/tests/compile_fail holds one file per breaking change.");
+    explain!("`cargo test -p rust-learn-core --test compile_fail` asserts each one fails with the");
+    explain!("expected rustc error, so the lesson's claims stay true as Rust evolves.");
+
+    println!();
+}
+
+// Helper functions
+
+/// A sample config file in the temp dir, removed again when dropped
+struct SampleConfig {
+    path: PathBuf,
+}
+
+impl SampleConfig {
+    fn create() -> Self {
+        let path = std::env::temp_dir().join(format!("rust-learn-app-{}.conf", std::process::id()));
+        let text = "# sample config for the API design lesson\n\
+                    name = rust-learn\n\
+                    log_level = debug\n\
+                    retries = 3\n";
+        fs::write(&path, text).expect("Failed to write sample config");
+        SampleConfig { path }
+    }
+}
+
+impl Drop for SampleConfig {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
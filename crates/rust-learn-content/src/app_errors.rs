@@ -0,0 +1,583 @@
+/// Application Errors in Rust - Context, Chains and Downcasting
+///
+/// A library returns typed errors its callers can match on (the custom
+/// errors lesson). A program mostly does not match: it stops, tells the
+/// user what it was trying to do and why that failed, and exits. The anyhow
+/// crate is built for that: one error type that anything converts into with
+/// `?`, a `.context("...")` that wraps an error in what the program was
+/// doing, a report that prints the whole chain, and downcasting for the few
+/// errors the program does react to. This lesson builds a small version of
+/// it, `AppError`, and uses it for a server's start-up: read the config,
+/// parse it, validate it.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_core::Error;
+use std::error::Error as StdError;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+
+pub fn app_errors() {
+    println!("=== Application Error Handling Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- app_errors <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(adding_context, "Adding Context"),
+    section!(one_error_type, "One Error Type for the Whole Program"),
+    section!(read_parse_validate, "Read, Parse, Validate"),
+    section!(printing_chains, "Printing the Chain").with_checkpoint(
+        "Should a context message repeat the message of the error it wraps?",
+        false,
+        "The report prints every layer; each one only adds what it knows, like which file.",
+    ),
+    section!(downcasting, "Downcasting to React"),
+    section!(typed_or_context, "Typed Errors or Context?"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "adding_context",
+        prompt: "What does `.context(\"reading server.conf\")` do to an io::Error?",
+        choices: &[
+            "Wraps it in a new error with that message, keeping it as the source",
+            "Replaces its message, dropping the io::Error",
+            "Prints the message to stderr",
+            "Panics with the message",
+        ],
+        explanation: "The io::Error says why; the context says what the program was doing. Both stay in the chain.",
+    },
+    Question {
+        section: "one_error_type",
+        prompt: "Why does AppError (like anyhow::Error) not implement std::error::Error itself?",
+        choices: &[
+            "Its `impl<E: Error> From<E>` would then overlap with std's `impl<T> From<T> for T`",
+            "Error cannot be implemented for structs",
+            "It would make AppError larger",
+            "Display cannot be implemented on a Box",
+        ],
+        explanation: "With AppError: Error, From<AppError> for AppError would come from both impls, which Rust rejects.",
+    },
+    Question {
+        section: "downcasting",
+        prompt: "How does a program that uses one error type still react to a missing file?",
+        choices: &[
+            "Downcast: look for an io::Error of kind NotFound in the chain",
+            "Compare the error's message with \"No such file\"",
+            "It cannot; AppError has erased the type",
+            "Match on AppError's variants",
+        ],
+        explanation: "The boxed errors keep their types; downcast_ref asks each one in the chain for the type.",
+    },
+    Question {
+        section: "typed_or_context",
+        prompt: "Where does an error enum fit better than context on an AppError?",
+        choices: &[
+            "In a library, whose callers match on what went wrong",
+            "In main, just before printing the error",
+            "Nowhere: context replaces enums",
+            "In scripts that run once",
+        ],
+        explanation: "Libraries give callers variants to match; applications wrap them with context and report.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_context_chain)];
+
+/// Any error, plus what the program was doing when it happened: a small
+/// `anyhow::Error`. The newest context is the outermost error, and each
+/// `source()` is the layer under it
+struct AppError(Box<dyn StdError + Send + Sync + 'static>);
+
+/// An error that is only a message, for `AppError::msg` and `bail!`
+#[derive(Debug, Error)]
+#[error("{0}")]
+struct Message(String);
+
+/// A message about what was going on, around the error that stopped it
+#[derive(Debug)]
+struct ContextError {
+    message: String,
+    source: Box<dyn StdError + Send + Sync + 'static>,
+}
+
+impl fmt::Display for ContextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl StdError for ContextError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        // The error inside the Box, not the Box: downcasting needs its type
+        Some(&*self.source)
+    }
+}
+
+impl AppError {
+    /// An error with just a message, like `anyhow!("...")`
+    fn msg(message: impl Into<String>) -> AppError {
+        AppError(Box::new(Message(message.into())))
+    }
+
+    /// The same error, inside one more layer of context
+    fn context(self, message: impl Into<String>) -> AppError {
+        AppError(Box::new(ContextError {
+            message: message.into(),
+            source: self.0,
+        }))
+    }
+
+    /// The outermost error, then each source under it
+    fn chain(&self) -> impl Iterator<Item = &(dyn StdError + 'static)> {
+        let outermost: &(dyn StdError + 'static) = &*self.0;
+        std::iter::successors(Some(outermost), |&error| error.source())
+    }
+
+    /// The innermost error: what actually went wrong
+    fn root_cause(&self) -> &(dyn StdError + 'static) {
+        self.chain().last().expect("the chain has the error itself")
+    }
+
+    /// The first error in the chain that is an `E`
+    fn downcast_ref<E: StdError + 'static>(&self) -> Option<&E> {
+        self.chain().find_map(|error| error.downcast_ref::<E>())
+    }
+}
+
+/// What lets `?` turn any error into an AppError
+impl<E: StdError + Send + Sync + 'static> From<E> for AppError {
+    fn from(error: E) -> Self {
+        AppError(Box::new(error))
+    }
+}
+
+impl fmt::Display for AppError {
+    /// `{}` is the outermost message, `{:#}` every message joined by `: `
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            let messages: Vec<String> = self.chain().map(|error| error.to_string()).collect();
+            write!(f, "{}", messages.join(": "))
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+impl fmt::Debug for AppError {
+    /// The report `fn main() -> Result<(), AppError>` prints when main fails
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)?;
+        let causes: Vec<&(dyn StdError + 'static)> = self.chain().skip(1).collect();
+        if !causes.is_empty() {
+            write!(f, "\n\nCaused by:")?;
+        }
+        for (index, cause) in causes.iter().enumerate() {
+            match causes.len() {
+                1 => write!(f, "\n    {}", cause)?,
+                _ => write!(f, "\n    {}: {}", index, cause)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `.context()` and `.with_context()` on any Result whose error converts
+/// into an AppError, like `anyhow::Context`
+trait Context<T> {
+    fn context(self, message: &str) -> Result<T, AppError>;
+    /// Builds the message only when there is an error
+    fn with_context(self, message: impl FnOnce() -> String) -> Result<T, AppError>;
+}
+
+impl<T, E: Into<AppError>> Context<T> for Result<T, E> {
+    fn context(self, message: &str) -> Result<T, AppError> {
+        self.map_err(|error| error.into().context(message))
+    }
+
+    fn with_context(self, message: impl FnOnce() -> String) -> Result<T, AppError> {
+        self.map_err(|error| error.into().context(message()))
+    }
+}
+
+/// Return an AppError with a formatted message, like `anyhow::bail!`
+macro_rules! bail {
+    ($($arg:tt)*) => {
+        return Err(AppError::msg(format!($($arg)*)))
+    };
+}
+
+fn adding_context() {
+    println!("1. Adding Context:");
+    println!("==================");
+
+    let files = SampleFiles::create(&[]);
+    let path = files.path("server.conf");
+    let bare = fs::read_to_string(&path).unwrap_err();
+    println!("without context: {}", bare);
+    explain!("The io::Error says why it failed, not which file or what the program wanted");
+
+    let wrapped = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", file_name(&path)))
+        .context("cannot start the server")
+        .unwrap_err();
+    println!("with context:    {:#}", wrapped);
+    explain!("Each .context() wraps the error in a message about what was going on;");
+    explain!("  the io::Error stays inside as the source, so nothing is lost");
+    detail!("with_context takes a closure, so format! only runs when there is an error");
+    println!();
+}
+
+fn one_error_type() {
+    println!("2. One Error Type for the Whole Program:");
+    println!("========================================");
+
+    fn port_from(text: &str) -> Result<u32, AppError> {
+        let port: u32 = text.trim().parse()?;
+        if port > 65_535 {
+            bail!("{} is not a port: the highest is 65535", port);
+        }
+        Ok(port)
+    }
+
+    for text in ["8080", "80 80", "70000"] {
+        match port_from(text) {
+            Ok(port) => println!("{:<8} -> Ok({})", format!("{:?}", text), port),
+            Err(error) => println!("{:<8} -> Err({})", format!("{:?}", text), error),
+        }
+    }
+    explain!("`impl<E: Error + Send + Sync> From<E> for AppError` lets ? take any error");
+    explain!("bail! returns a message-only error, for checks that are not another error");
+    detail!("AppError holds a Box<dyn Error + Send + Sync>, so it can cross threads");
+    deep!("AppError does not implement Error itself: `From<E> for AppError` would then");
+    deep!("  overlap with std's `From<T> for T`. anyhow::Error is built the same way");
+    println!();
+}
+
+/// A server's settings, from `key = value` lines
+#[derive(Debug, PartialEq)]
+struct Config {
+    port: u32,
+    workers: u32,
+}
+
+/// What the config parser reports: a typed error, since it is the part of
+/// the program a library would be
+#[derive(Debug, Error)]
+enum ParseError {
+    #[error("line {line}: expected `key = value`")]
+    Syntax { line: usize },
+    #[error("line {line}: {key} is not a number")]
+    Number {
+        line: usize,
+        key: String,
+        source: ParseIntError,
+    },
+    #[error("line {line}: unknown key {key:?}")]
+    UnknownKey { line: usize, key: String },
+}
+
+fn parse_config(text: &str) -> Result<Config, ParseError> {
+    let mut config = Config {
+        port: 8080,
+        workers: 4,
+    };
+    for (index, text) in text.lines().enumerate() {
+        let line = index + 1;
+        let text = text.trim();
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+        let (key, value) = text.split_once('=').ok_or(ParseError::Syntax { line })?;
+        let key = key.trim().to_string();
+        let number = value.trim().parse().map_err(|source| ParseError::Number {
+            line,
+            key: key.clone(),
+            source,
+        })?;
+        match key.as_str() {
+            "port" => config.port = number,
+            "workers" => config.workers = number,
+            _ => return Err(ParseError::UnknownKey { line, key }),
+        }
+    }
+    Ok(config)
+}
+
+fn validate(config: &Config) -> Result<(), AppError> {
+    if config.port < 1024 {
+        bail!("port {} needs root: use 1024 or above", config.port);
+    }
+    if !(1..=64).contains(&config.workers) {
+        bail!("workers must be 1 to 64, not {}", config.workers);
+    }
+    Ok(())
+}
+
+/// Read, parse and validate the config, each step saying what it was doing
+fn load(path: &Path) -> Result<Config, AppError> {
+    let name = file_name(path);
+    let text = fs::read_to_string(path).with_context(|| format!("failed to read {}", name))?;
+    let config = parse_config(&text).with_context(|| format!("failed to parse {}", name))?;
+    validate(&config).with_context(|| format!("{} is not a usable config", name))?;
+    Ok(config)
+}
+
+/// The program itself: load the config, then "start"
+fn start(path: &Path) -> Result<Config, AppError> {
+    let config = load(path).context("cannot start the server")?;
+    Ok(config)
+}
+
+/// The configs the later sections load
+const SAMPLES: &[(&str, &str)] = &[
+    ("good.conf", "# server\nport = 8080\nworkers = 8\n"),
+    ("typo.conf", "port = 8080\nworkers: 8\n"),
+    ("number.conf", "port = 80a0\n"),
+    ("root.conf", "port = 80\n"),
+];
+
+fn read_parse_validate() {
+    println!("3. Read, Parse, Validate:");
+    println!("=========================");
+
+    let files = SampleFiles::create(SAMPLES);
+    for name in [
+        "good.conf",
+        "typo.conf",
+        "number.conf",
+        "root.conf",
+        "missing.conf",
+    ] {
+        match start(&files.path(name)) {
+            Ok(config) => println!("{:<13} {:?}", name, config),
+            Err(error) => println!("{:<13} {}", name, error),
+        }
+    }
+    explain!("`{{}}` shows only the outermost message: every failure reads the same");
+
+    println!();
+    for name in ["typo.conf", "number.conf", "root.conf"] {
+        let error = start(&files.path(name)).unwrap_err();
+        println!("{:<13} {:#}", name, error);
+    }
+    explain!("`{{:#}}` shows every layer on one line: what the program was doing, down to why");
+    explain!("read -> parse -> validate each add their own context, and ? passes it up");
+    detail!("parse_config still returns its own ParseError; .with_context() wraps it");
+    println!();
+}
+
+fn printing_chains() {
+    println!("4. Printing the Chain:");
+    println!("======================");
+
+    let files = SampleFiles::create(SAMPLES);
+    let error = start(&files.path("number.conf")).unwrap_err();
+    println!("Error: {:?}", error);
+    explain!("This is the report `fn main() -> Result<(), AppError>` prints when main fails:");
+    explain!("  the error's Debug, which for AppError is the outermost message and its causes");
+
+    println!();
+    for (depth, cause) in error.chain().enumerate() {
+        println!("{}{}", "  ".repeat(depth), cause);
+    }
+    println!("root cause: {}", error.root_cause());
+    explain!("chain() walks source() from the outside in; root_cause() is the last one");
+    detail!("Write a context as what was being done (\"failed to parse good.conf\"), not why:");
+    detail!("  the why is the error inside it");
+    deep!("anyhow can also capture a backtrace where the error was made (RUST_BACKTRACE=1)");
+    println!();
+}
+
+/// What the program does about a failed start: an exit code, like sysexits.h
+fn exit_code(error: &AppError) -> i32 {
+    if let Some(io) = error.downcast_ref::<io::Error>()
+        && io.kind() == io::ErrorKind::NotFound
+    {
+        return 66;
+    }
+    if error.downcast_ref::<ParseError>().is_some() {
+        return 65;
+    }
+    1
+}
+
+/// Start with the config at `path`, or with the defaults when there is none
+fn start_or_default(path: &Path) -> Result<Config, AppError> {
+    match start(path) {
+        Err(error) if exit_code(&error) == 66 => parse_config("").map_err(AppError::from),
+        other => other,
+    }
+}
+
+fn downcasting() {
+    println!("5. Downcasting to React:");
+    println!("========================");
+
+    let files = SampleFiles::create(SAMPLES);
+    for name in ["number.conf", "root.conf", "missing.conf"] {
+        let error = start(&files.path(name)).unwrap_err();
+        let parse = error.downcast_ref::<ParseError>();
+        println!(
+            "{:<13} exit {:>2}, ParseError in the chain: {}",
+            name,
+            exit_code(&error),
+            parse.is_some()
+        );
+    }
+    explain!("downcast_ref::<T>() looks through the chain for an error of type T;");
+    explain!("  the types are still there inside the boxes, only hidden from the signature");
+
+    let config = start_or_default(&files.path("missing.conf"));
+    println!("missing.conf, falling back: {:?}", config);
+    if let Some(ParseError::Number { line, key, .. }) = start(&files.path("number.conf"))
+        .unwrap_err()
+        .downcast_ref::<ParseError>()
+    {
+        println!("number.conf: point the user at line {} ({})", line, key);
+    }
+    explain!("Most errors are only reported; downcast for the few the program handles");
+    detail!("Matching on the error's message would break when a message changes");
+    println!();
+}
+
+fn typed_or_context() {
+    println!("6. Typed Errors or Context?:");
+    println!("============================");
+
+    let rows = [
+        ("", "Error enum (custom_errors)", "AppError + context"),
+        ("Who reads it", "the calling code", "a person"),
+        ("Callers", "match on variants", "report, maybe downcast"),
+        ("Adding a case", "a new variant", "one more .context()"),
+        ("Fits", "libraries, modules", "main, commands, scripts"),
+    ];
+    for (aspect, typed, context) in rows {
+        println!("{:<14} {:<27} {}", aspect, typed, context);
+    }
+    explain!("They work together: parse_config returns ParseError, start() wraps it in context");
+    explain!("In real projects: thiserror for the enums, anyhow for the application on top");
+    detail!("A library that returns anyhow::Error makes its callers downcast to tell cases apart");
+    println!();
+}
+
+// Helper functions
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Sample files in a directory in the temp dir, removed again when dropped
+struct SampleFiles {
+    root: PathBuf,
+}
+
+impl SampleFiles {
+    fn create(files: &[(&str, &str)]) -> Self {
+        let root =
+            std::env::temp_dir().join(format!("rust-learn-app-errors-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("Failed to create the sample directory");
+        for (name, text) in files {
+            fs::write(root.join(name), text).expect("Failed to write a sample file");
+        }
+        SampleFiles { root }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+}
+
+impl Drop for SampleFiles {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn predict_context_chain() {
+    fn parse_workers(text: &str) -> Result<u32, AppError> {
+        let workers = text.parse::<u32>().context("workers is not a number")?;
+        Ok(workers)
+    }
+
+    let error = parse_workers("four")
+        .context("cannot start the server")
+        .unwrap_err();
+    println!("{}", error);
+    println!("{:#}", error);
+    println!("{}", error.chain().count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_and_formats() {
+        let error = "x"
+            .parse::<u32>()
+            .context("workers is not a number")
+            .context("cannot start")
+            .unwrap_err();
+        assert_eq!(error.to_string(), "cannot start");
+        assert_eq!(
+            format!("{:#}", error),
+            "cannot start: workers is not a number: invalid digit found in string"
+        );
+        assert_eq!(
+            format!("{:?}", error),
+            "cannot start\n\nCaused by:\n    0: workers is not a number\n    1: invalid digit found in string"
+        );
+        assert!(error.root_cause().is::<ParseIntError>());
+
+        let error = AppError::msg("port 80 needs root").context("invalid");
+        assert_eq!(
+            format!("{:?}", error),
+            "invalid\n\nCaused by:\n    port 80 needs root"
+        );
+        assert_eq!(format!("{:?}", AppError::msg("plain")), "plain");
+    }
+
+    #[test]
+    fn test_load_and_downcast() {
+        let files = SampleFiles::create(SAMPLES);
+        assert_eq!(
+            start(&files.path("good.conf")).unwrap(),
+            Config {
+                port: 8080,
+                workers: 8
+            }
+        );
+        let messages = |name: &str| format!("{:#}", start(&files.path(name)).unwrap_err());
+        assert_eq!(
+            messages("typo.conf"),
+            "cannot start the server: failed to parse typo.conf: line 2: expected `key = value`"
+        );
+        assert_eq!(
+            messages("root.conf"),
+            "cannot start the server: root.conf is not a usable config: port 80 needs root: use 1024 or above"
+        );
+
+        let error = start(&files.path("number.conf")).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<ParseError>(),
+            Some(ParseError::Number { line: 1, .. })
+        ));
+        assert!(error.downcast_ref::<ParseIntError>().is_some());
+        assert!(error.downcast_ref::<io::Error>().is_none());
+        assert_eq!(exit_code(&error), 65);
+        let missing = start(&files.path("missing.conf")).unwrap_err();
+        assert_eq!(exit_code(&missing), 66);
+        assert_eq!(exit_code(&start(&files.path("root.conf")).unwrap_err()), 1);
+        assert!(start_or_default(&files.path("missing.conf")).is_ok());
+    }
+}
@@ -0,0 +1,136 @@
+/// array
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn arrays() {
+    println!("=== Array Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- arrays <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(array_example, "Array Literals"),
+    section!(array_with_type, "Arrays With a Type Annotation"),
+    section!(array_with_default_value, "Arrays With a Repeated Value"),
+    section!(array_with_index, "Indexing"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "array_with_type",
+        prompt: "In `let a: [i32; 5] = [1, 2, 3, 4, 5];`, what is the 5 in the type?",
+        choices: &[
+            "The length, which is part of the type",
+            "The capacity it can grow to",
+            "The last element",
+            "The size in bytes",
+        ],
+        explanation: "An array's length is fixed and part of its type: [i32; 5] and [i32; 6] are different types. A Vec can grow.",
+    },
+    Question {
+        section: "array_with_default_value",
+        prompt: "What is `[3; 4]`?",
+        choices: &["[3, 3, 3, 3]", "[4, 4, 4]", "[3, 4]", "[0, 1, 2, 3]"],
+        explanation: "[value; count] repeats value count times.",
+    },
+    Question {
+        section: "array_with_index",
+        prompt: "a has 5 elements and index was read from input as 10. What does `a[index]` do?",
+        choices: &[
+            "Panics: index out of bounds",
+            "Reads whatever memory is there",
+            "Returns 0",
+            "Wraps around to a[0]",
+        ],
+        explanation: "Every index is checked; out of bounds stops the program instead of reading memory it does not own. a.get(index) returns None instead.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_array)];
+
+fn array_example() {
+    println!("1. Array Literals:");
+    println!("==================");
+
+    let a = [1, 2, 3, 4, 5];
+    println!("a is {a:?}");
+
+    println!();
+}
+
+fn array_with_type() {
+    println!("2. Arrays With a Type Annotation:");
+    println!("=================================");
+
+    let a: [i32; 5] = [1, 2, 3, 4, 5];
+    println!("a is {a:?}");
+
+    println!();
+}
+
+#[allow(clippy::const_is_empty, clippy::iter_nth)] // Array methods shown next to their iterator forms
+fn array_with_default_value() {
+    println!("3. Arrays With a Repeated Value:");
+    println!("================================");
+
+    let a: [i32; 5] = [3; 5];
+    println!("a is {a:?}");
+
+    // An array's length is part of its type, so it can never grow or shrink:
+    // a.push(6); // COMPILE ERROR: no method named `push` found for array `[i32; 5]`
+    // a.pop();   // COMPILE ERROR: use a Vec when the length changes
+
+    println!("len: {}", a.len());
+    println!("is_empty: {}", a.is_empty());
+    println!("contains 3: {}", a.contains(&3));
+
+    // Iterator methods work on arrays through .iter()
+    println!("sum: {}", a.iter().sum::<i32>());
+    println!("product: {}", a.iter().product::<i32>());
+    println!("max: {:?}", a.iter().max());
+    println!("min: {:?}", a.iter().min());
+    println!("count: {}", a.iter().count());
+    println!("nth(2): {:?}", a.iter().nth(2));
+    println!("position of 3: {:?}", a.iter().position(|x| x == &3));
+    println!("rposition of 3: {:?}", a.iter().rposition(|x| x == &3));
+    println!("rev: {:?}", a.iter().rev().collect::<Vec<&i32>>());
+    println!("skip(2): {:?}", a.iter().skip(2).collect::<Vec<&i32>>());
+    println!("take(2): {:?}", a.iter().take(2).collect::<Vec<&i32>>());
+    println!(
+        "even: {:?}",
+        a.iter().filter(|x| *x % 2 == 0).collect::<Vec<&i32>>()
+    );
+    println!(
+        "odd: {:?}",
+        a.iter().filter(|x| *x % 2 != 0).collect::<Vec<&i32>>()
+    );
+    println!(
+        "doubled: {:?}",
+        a.iter().map(|x| x * 2).collect::<Vec<i32>>()
+    );
+
+    println!();
+}
+
+fn array_with_index() {
+    println!("4. Indexing:");
+    println!("============");
+
+    let a: [i32; 5] = [1, 2, 3, 4, 5];
+    let first = a[0];
+    let second = a[1];
+    println!("first is {first}, second is {second}");
+
+    println!();
+}
+
+fn predict_array() {
+    let mut grid = [0; 4];
+    grid[1] = 7;
+    grid[3] = grid[1] * 2;
+    println!("{:?} len {}", grid, grid.len());
+}
@@ -1,8 +1,12 @@
+use crate::registry::{Question, Section};
 use std::time::Duration;
 use tokio::time::sleep;
 
 // Basic async function
 async fn hello_world() {
+    println!("1. Basic Async Function:");
+    println!("========================");
+
     println!("Hello from async function!");
 }
 
@@ -36,7 +40,8 @@ async fn risky_operation(should_fail: bool) -> Result<String, &'static str> {
 
 // Function that demonstrates concurrent execution
 async fn run_concurrent_tasks() {
-    println!("\n--- Running concurrent tasks ---");
+    println!("\n2. Running Concurrent Tasks:");
+    println!("============================");
 
     // Spawn multiple tasks concurrently
     let task1 = do_work(1);
@@ -51,7 +56,8 @@ async fn run_concurrent_tasks() {
 
 // Function that demonstrates sequential vs concurrent execution
 async fn compare_execution_modes() {
-    println!("\n--- Sequential vs Concurrent Execution ---");
+    println!("\n3. Sequential vs Concurrent Execution:");
+    println!("======================================");
 
     // Sequential execution
     println!("Sequential execution:");
@@ -85,7 +91,8 @@ async fn compare_execution_modes() {
 
 // Function that demonstrates async/await with Result handling
 async fn handle_async_results() {
-    println!("\n--- Handling Async Results ---");
+    println!("\n4. Handling Async Results:");
+    println!("==========================");
 
     // Handle successful operation
     match risky_operation(false).await {
@@ -102,7 +109,8 @@ async fn handle_async_results() {
 
 // Function that demonstrates async operations with different return types
 async fn demonstrate_async_operations() {
-    println!("\n--- Async Operations with Return Values ---");
+    println!("\n5. Async Operations with Return Values:");
+    println!("=======================================");
 
     // Calculate sums concurrently
     let sum1 = calculate_sum(10, 20);
@@ -117,30 +125,74 @@ async fn demonstrate_async_operations() {
     println!("Total: {}", result1 + result2 + result3);
 }
 
-// Main async function that orchestrates all examples
-async fn run_async_examples() {
+// Entry point: every section runs on its own async runtime
+pub fn async_await() {
     println!("=== Rust Async/Await Examples ===\n");
 
-    // Basic async function call
-    hello_world().await;
-
-    // Run all demonstration functions
-    run_concurrent_tasks().await;
-    compare_execution_modes().await;
-    handle_async_results().await;
-    demonstrate_async_operations().await;
+    for section in SECTIONS {
+        (section.run)();
+    }
 
     println!("\n=== All async examples completed! ===");
 }
 
-// Main function that sets up the async runtime
-fn main() {
-    // Create and run the async runtime
+/// Sections in the order they run; `cargo run -- async_await <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    Section::new("hello_world", "Basic Async Function", || {
+        block_on(hello_world())
+    }),
+    Section::new("run_concurrent_tasks", "Running Concurrent Tasks", || {
+        block_on(run_concurrent_tasks())
+    }),
+    Section::new(
+        "compare_execution_modes",
+        "Sequential vs Concurrent Execution",
+        || block_on(compare_execution_modes()),
+    ),
+    Section::new("handle_async_results", "Handling Async Results", || {
+        block_on(handle_async_results())
+    }),
+    Section::new(
+        "demonstrate_async_operations",
+        "Async Operations with Return Values",
+        || block_on(demonstrate_async_operations()),
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "hello_world",
+        prompt: "What happens when an async fn is called without .await?",
+        choices: &[
+            "It returns a future that does nothing until awaited",
+            "It runs on a new thread",
+            "It runs to completion right away",
+            "It fails to compile",
+        ],
+        explanation: "Futures are lazy: calling an async fn only builds the future, and the compiler warns that it is unused.",
+    },
+    Question {
+        section: "compare_execution_modes",
+        prompt: "Two 100 ms sleeps awaited one after the other take about 200 ms. How do they take about 100 ms?",
+        choices: &[
+            "Await them together with tokio::join!",
+            "Put each in a loop",
+            "Call them without .await",
+            "Mark them unsafe",
+        ],
+        explanation: "join! polls both futures at once, so their waits overlap.",
+    },
+];
+
+// Create an async runtime and drive one future to completion on it
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
     let runtime = tokio::runtime::Runtime::new().unwrap();
-    runtime.block_on(run_async_examples());
+    runtime.block_on(future)
 }
 
 // Example of how to use this module from other parts of your code
+#[allow(dead_code)]
 pub async fn public_async_function() -> String {
     "This is a public async function".to_string()
 }
@@ -0,0 +1,421 @@
+/// Box in Rust - Heap Allocation, Recursive Types and Deref
+///
+/// `Box<T>` is the simplest smart pointer: one owner, one heap allocation,
+/// freed when the box is dropped. The ownership lesson introduces it in two
+/// lines; this lesson shows what it is for. Moving a large value by moving
+/// only its pointer, giving a recursive type like a cons list a size the
+/// compiler can know, holding values of different types as `Box<dyn
+/// Trait>`, and using a box as if it were the value through `Deref`. The
+/// last sections measure it all with `size_of`.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::fmt;
+use std::mem::size_of;
+use std::ops::Deref;
+
+pub fn boxes() {
+    println!("=== Box Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- boxes <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(heap_allocation, "Box::new Puts a Value on the Heap"),
+    section!(recursive_types, "Recursive Types: a Cons List").with_checkpoint(
+        "Does `enum List { Cons(i32, List), Nil }` compile?",
+        false,
+        "A List would contain a List, which contains a List...: its size is infinite. Box<List> is one pointer.",
+    ),
+    section!(box_dyn_trait, "Owning a Trait Object: Box<dyn Trait>"),
+    section!(deref_coercion, "Deref: Using a Box Like the Value"),
+    section!(memory_layout, "Memory Layout with size_of"),
+    section!(when_to_box, "When to Reach for Box"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "heap_allocation",
+        prompt: "What is copied when a Box<[u8; 4096]> is moved into a function?",
+        choices: &[
+            "The pointer: 8 bytes on a 64-bit target",
+            "All 4096 bytes",
+            "Nothing; Box values cannot be moved",
+            "The pointer and a reference count",
+        ],
+        explanation: "The array stays where it is on the heap; only the Box, one pointer, moves.",
+    },
+    Question {
+        section: "recursive_types",
+        prompt: "Why does `Cons(i32, Box<List>)` compile when `Cons(i32, List)` does not?",
+        choices: &[
+            "Box<List> has a fixed size, a pointer, however long the list is",
+            "Box turns off the size check",
+            "Box makes List a trait object",
+            "Recursive types must live in a Vec",
+        ],
+        explanation: "The compiler needs each type's size; a pointer's size does not depend on what it points to.",
+    },
+    Question {
+        section: "deref_coercion",
+        prompt: "Why can `greet(&boxed)` pass a &Box<String> to `fn greet(name: &str)`?",
+        choices: &[
+            "Deref coercion: &Box<String> -> &String -> &str, inserted by the compiler",
+            "Box<String> and &str are the same type",
+            "greet is generic over every pointer",
+            "It cannot; greet(&**boxed) is needed",
+        ],
+        explanation: "Box<T>: Deref<Target = T> and String: Deref<Target = str>, so the compiler derefs twice to match.",
+    },
+    Question {
+        section: "memory_layout",
+        prompt: "How big is Option<Box<i32>> on a 64-bit target?",
+        choices: &[
+            "8 bytes: None is the null pointer a Box can never be",
+            "16 bytes: a tag and the pointer",
+            "12 bytes: a tag and the i32",
+            "4 bytes",
+        ],
+        explanation: "A Box is never null, so Option uses null for None and needs no tag: the niche optimization.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_deref_calls)];
+
+/// A singly linked list, as in Lisp: each cell holds a value and the rest
+#[derive(Debug)]
+enum List {
+    Cons(i32, Box<List>),
+    Nil,
+}
+
+// enum List { Cons(i32, List), Nil }
+//     // COMPILE ERROR: E0072 recursive type `List` has infinite size
+
+impl List {
+    /// The list of `values`, in order
+    fn from_slice(values: &[i32]) -> List {
+        match values {
+            [] => List::Nil,
+            [first, rest @ ..] => List::Cons(*first, Box::new(List::from_slice(rest))),
+        }
+    }
+
+    fn sum(&self) -> i32 {
+        match self {
+            List::Cons(value, rest) => value + rest.sum(),
+            List::Nil => 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            List::Cons(_, rest) => 1 + rest.len(),
+            List::Nil => 0,
+        }
+    }
+}
+
+impl fmt::Display for List {
+    /// `(1, (2, (3, Nil)))`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            List::Cons(value, rest) => write!(f, "({}, {})", value, rest),
+            List::Nil => write!(f, "Nil"),
+        }
+    }
+}
+
+fn heap_allocation() {
+    println!("1. Box::new Puts a Value on the Heap:");
+    println!("=====================================");
+
+    let on_stack = 5;
+    let boxed = Box::new(5);
+    println!(
+        "on_stack + 1 = {}, *boxed + 1 = {}",
+        on_stack + 1,
+        *boxed + 1
+    );
+    explain!("Box::new(5) allocates room for an i32 on the heap and moves the 5 there;");
+    explain!("  `boxed` itself is a pointer on the stack, and *boxed is the i32");
+
+    let image = Box::new([0u8; 4096]);
+    let before: *const [u8; 4096] = &*image;
+    let moved = image;
+    let after: *const [u8; 4096] = &*moved;
+    println!(
+        "moving a Box<[u8; 4096]> copies {} bytes; the array stayed put: {}",
+        size_of::<Box<[u8; 4096]>>(),
+        std::ptr::eq(before, after)
+    );
+    explain!("Moving a box moves the pointer, never the value behind it");
+
+    {
+        let _scratch = Box::new(String::from("freed at the closing brace"));
+    }
+    explain!("When the Box goes out of scope it drops the value, then frees the allocation");
+    detail!("Box::new(value) still builds `value` first and moves it: a huge array can");
+    detail!("  overflow the stack on the way. vec![0; n].into_boxed_slice() builds on the heap");
+    println!();
+}
+
+fn recursive_types() {
+    println!("2. Recursive Types: a Cons List:");
+    println!("================================");
+
+    let list = List::from_slice(&[1, 2, 3]);
+    println!("list        = {}", list);
+    println!("list.len()  = {}", list.len());
+    println!("list.sum()  = {}", list.sum());
+    explain!("Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil)))))):");
+    explain!("  each cell holds its value and a Box with the rest of the list");
+
+    println!(
+        "size_of::<List>() = {} bytes, however long the list",
+        size_of::<List>()
+    );
+    explain!("Without the Box a List would contain a whole List, which contains a List...");
+    explain!("  rustc rejects that with E0072 and suggests the fix: insert some indirection");
+    if let List::Cons(head, tail) = list {
+        println!("head = {}, tail = {}", head, tail);
+    }
+    detail!("Matching moves the tail's Box out, without copying the cells behind it");
+    deep!("Trees are the same shape: struct Node {{ left: Option<Box<Node>>, right: ... }}");
+    println!();
+}
+
+trait Shape {
+    fn name(&self) -> String;
+    fn area(&self) -> f64;
+}
+
+struct Circle {
+    radius: f64,
+}
+
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+impl Shape for Circle {
+    fn name(&self) -> String {
+        format!("circle r={}", self.radius)
+    }
+
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.radius * self.radius
+    }
+}
+
+impl Shape for Rectangle {
+    fn name(&self) -> String {
+        format!("rectangle {}x{}", self.width, self.height)
+    }
+
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+}
+
+/// A shape chosen at runtime: each branch is a different type, so it is
+/// returned boxed
+fn parse_shape(text: &str) -> Option<Box<dyn Shape>> {
+    let (kind, size) = text.split_once(' ')?;
+    match kind {
+        "circle" => Some(Box::new(Circle {
+            radius: size.parse().ok()?,
+        })),
+        "square" => {
+            let side = size.parse().ok()?;
+            Some(Box::new(Rectangle {
+                width: side,
+                height: side,
+            }))
+        }
+        _ => None,
+    }
+}
+
+fn box_dyn_trait() {
+    println!("3. Owning a Trait Object: Box<dyn Trait>:");
+    println!("=========================================");
+
+    let shapes: Vec<Box<dyn Shape>> = ["circle 1", "square 2", "circle 0.5"]
+        .iter()
+        .filter_map(|text| parse_shape(text))
+        .collect();
+    for shape in &shapes {
+        println!("{:<14} area {:.2}", shape.name(), shape.area());
+    }
+    explain!("A Circle and a Rectangle differ in size, so one Vec cannot hold both directly;");
+    explain!("  boxed, each is a pointer plus a vtable, and the Vec holds those");
+
+    let steps: Vec<Box<dyn Fn(i32) -> i32>> = vec![Box::new(|x| x + 1), Box::new(|x| x * 10)];
+    let result = steps.iter().fold(4, |value, step| step(value));
+    println!("(4 + 1) * 10 through boxed closures = {}", result);
+    explain!("Every closure is its own type; Box<dyn Fn> stores different ones together");
+    detail!("Box<dyn Error> from the error handling lessons is the same idea for errors");
+    detail!("The trait objects lesson covers dispatch, vtables and dyn compatibility");
+    println!();
+}
+
+/// A box of our own, to show what `Deref` does for `Box`
+struct MyBox<T>(T);
+
+impl<T> MyBox<T> {
+    fn new(value: T) -> MyBox<T> {
+        MyBox(value)
+    }
+}
+
+impl<T> Deref for MyBox<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+fn deref_coercion() {
+    println!("4. Deref: Using a Box Like the Value:");
+    println!("=====================================");
+
+    let boxed = Box::new(String::from("Ferris"));
+    println!("greet(&boxed)   = {}", greet(&boxed));
+    println!("boxed.len()     = {}", boxed.len());
+    explain!("&Box<String> becomes &String, then &str: deref coercion, inserted by the compiler");
+    explain!("  Method calls deref too, so boxed.len() is String::len");
+
+    let mine = MyBox::new(String::from("Crab"));
+    println!("greet(&mine)    = {}", greet(&mine));
+    println!("*mine == \"Crab\" : {}", *mine == "Crab");
+    explain!("MyBox gets the same treatment from `impl Deref for MyBox<T>`:");
+    explain!("  *mine is *Deref::deref(&mine), and coercion chains as many derefs as it needs");
+
+    let name: String = *boxed;
+    println!("moved out of the box: {}", name);
+    explain!("Only Box lets * move the value out, consuming the box; *mine cannot do that");
+    detail!("DerefMut does the same for &mut: a &mut Box<Vec<T>> works as a &mut Vec<T>");
+    println!();
+}
+
+fn memory_layout() {
+    println!("5. Memory Layout with size_of:");
+    println!("==============================");
+
+    let rows = [
+        ("i32", size_of::<i32>()),
+        ("Box<i32>", size_of::<Box<i32>>()),
+        ("Option<Box<i32>>", size_of::<Option<Box<i32>>>()),
+        ("[u8; 4096]", size_of::<[u8; 4096]>()),
+        ("Box<[u8; 4096]>", size_of::<Box<[u8; 4096]>>()),
+        ("Box<[u8]>", size_of::<Box<[u8]>>()),
+        ("Vec<u8>", size_of::<Vec<u8>>()),
+        ("Box<str>", size_of::<Box<str>>()),
+        ("String", size_of::<String>()),
+        ("Box<dyn Shape>", size_of::<Box<dyn Shape>>()),
+        ("List", size_of::<List>()),
+    ];
+    for (name, size) in rows {
+        println!(
+            "size_of::<{}>(){} = {:>4}",
+            name,
+            " ".repeat(18 - name.len()),
+            size
+        );
+    }
+    explain!("A Box of a sized type is one pointer, whatever it points to");
+    explain!("Option<Box<T>> is no bigger: a Box is never null, so None can be null");
+    explain!("Box<[u8]>, Box<str> and Box<dyn Shape> are fat: a pointer plus a length or vtable");
+    detail!(
+        "Vec and String add a capacity: Box<[T]> and Box<str> are for data that is done growing"
+    );
+    deep!("List is 16 bytes: an i32, a tag, and a pointer, rounded up to the pointer's alignment");
+    println!();
+}
+
+fn when_to_box() {
+    println!("6. When to Reach for Box:");
+    println!("=========================");
+
+    let rows = [
+        ("A recursive type", "List, trees", "gives it a size"),
+        (
+            "Different types, one place",
+            "Vec<Box<dyn Shape>>",
+            "one pointer type",
+        ),
+        (
+            "A large value moved often",
+            "Box<[u8; 4096]>",
+            "moves 8 bytes",
+        ),
+        (
+            "Data done growing",
+            "Box<str>, Box<[T]>",
+            "no spare capacity",
+        ),
+        (
+            "Plain values",
+            "i32, small structs",
+            "no Box: the stack is faster",
+        ),
+    ];
+    for (when, example, why) in rows {
+        println!("{:<27} {:<21} {}", when, example, why);
+    }
+    explain!("Box has one owner. Sharing needs Rc or Arc; changing through a shared");
+    explain!("  pointer needs RefCell or Mutex");
+    detail!("Clippy's box_collection and vec_box flag boxes that only add a pointer hop");
+    println!();
+}
+
+// Helper functions
+
+/// A pointer that says when it is dereferenced
+struct Loud<T>(T);
+
+impl<T> Deref for Loud<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        println!("deref");
+        &self.0
+    }
+}
+
+fn predict_deref_calls() {
+    let name = Loud(String::from("Ferris"));
+    println!("{}", greet(&name));
+    println!("{}", name.len());
+    let inner: &String = &name.0;
+    println!("{}", inner);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_and_shapes() {
+        let list = List::from_slice(&[1, 2, 3]);
+        assert_eq!(list.to_string(), "(1, (2, (3, Nil)))");
+        assert_eq!((list.len(), list.sum()), (3, 6));
+        assert_eq!(List::from_slice(&[]).to_string(), "Nil");
+
+        let square = parse_shape("square 2").unwrap();
+        assert_eq!(square.area(), 4.0);
+        assert!(parse_shape("hexagon 1").is_none());
+        assert!(parse_shape("circle x").is_none());
+        assert_eq!(size_of::<Option<Box<i32>>>(), size_of::<Box<i32>>());
+    }
+}
@@ -3,50 +3,86 @@
 /// Borrowing is Rust's way of allowing you to access data without taking ownership.
 /// It's a fundamental concept that enables safe concurrent access and efficient memory usage.
 /// This comprehensive guide covers all aspects of borrowing from basic to advanced patterns.
-use std::io;
+use crate::registry::{Question, Section, Snippet, section, snippet};
 
 pub fn borrowing() {
     println!("=== Borrowing Learning Examples ===\n");
 
-    // 1. Basic Borrowing Concepts
-    basic_borrowing_concepts();
-
-    // 2. Immutable Borrowing
-    immutable_borrowing();
-
-    // 3. Mutable Borrowing
-    mutable_borrowing();
-
-    // 4. Borrowing Rules and Restrictions
-    borrowing_rules();
-
-    // 5. Borrowing with Functions
-    borrowing_with_functions();
-
-    // 6. Borrowing with Collections
-    borrowing_with_collections();
-
-    // 7. Borrowing with Structs
-    borrowing_with_structs();
-
-    // 8. Advanced Borrowing Patterns
-    advanced_borrowing_patterns();
-
-    // 9. Borrowing and Lifetimes
-    borrowing_and_lifetimes();
-
-    // 10. Common Borrowing Scenarios
-    common_borrowing_scenarios();
+    for section in SECTIONS {
+        (section.run)();
+    }
 }
 
+/// Sections in the order they run; `cargo run -- borrowing <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(basic_borrowing_concepts, "Basic Borrowing Concepts"),
+    section!(immutable_borrowing, "Immutable Borrowing").with_checkpoint(
+        "Can several `&` references to one value be in use at once?",
+        true,
+        "Any number of shared references, as long as there is no `&mut`.",
+    ),
+    section!(mutable_borrowing, "Mutable Borrowing").with_checkpoint(
+        "Can you have two `&mut` to the same value in use at once?",
+        false,
+        "Only one mutable reference at a time, so two of them can never race.",
+    ),
+    section!(borrowing_rules, "Borrowing Rules and Restrictions"),
+    section!(borrowing_with_functions, "Borrowing with Functions"),
+    section!(borrowing_with_collections, "Borrowing with Collections"),
+    section!(borrowing_with_structs, "Borrowing with Structs"),
+    section!(advanced_borrowing_patterns, "Advanced Borrowing Patterns"),
+    section!(borrowing_and_lifetimes, "Borrowing and Lifetimes"),
+    section!(common_borrowing_scenarios, "Common Borrowing Scenarios"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "immutable_borrowing",
+        prompt: "How many immutable references to one value can be in use at once?",
+        choices: &[
+            "Any number, while there is no mutable one",
+            "One",
+            "Two",
+            "None while the owner is in scope",
+        ],
+        explanation: "Readers cannot get in each other's way, so shared references are unlimited; a mutable one has to be alone.",
+    },
+    Question {
+        section: "borrowing_rules",
+        prompt: "Does this compile?\n\n    let mut s = String::from(\"hi\");\n    let r1 = &s;\n    println!(\"{}\", r1);\n    let r2 = &mut s;\n    r2.push('!');",
+        choices: &[
+            "Yes: r1 is last used before r2 is made",
+            "No: s is borrowed twice",
+            "No: r1 is still in scope",
+            "Only if r1 is dropped by hand",
+        ],
+        explanation: "A borrow lasts until its last use, not to the end of the block, so the two borrows never overlap.",
+    },
+    Question {
+        section: "borrowing_and_lifetimes",
+        prompt: "Why does `fn longest(x: &str, y: &str) -> &str` fail to compile?",
+        choices: &[
+            "The compiler cannot tell which argument the result borrows from",
+            "Functions cannot return references",
+            "&str arguments must be String",
+            "Two arguments cannot share a type",
+        ],
+        explanation: "With two reference arguments, the returned reference needs a lifetime saying what it borrows: `fn longest<'a>(x: &'a str, y: &'a str) -> &'a str`.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_borrows)];
+
 fn basic_borrowing_concepts() {
     println!("1. Basic Borrowing Concepts:");
     println!("============================\n");
 
     println!("WHAT IS BORROWING?");
     println!("==================");
-    println!("Borrowing allows you to access data without taking ownership.");
-    println!("It's like borrowing a book from a library - you can read it, but you don't own it.");
+    explain!("Borrowing allows you to access data without taking ownership.");
+    detail!("It's like borrowing a book from a library - you can read it, but you don't own it.");
 
     let s1 = String::from("hello");
     println!("s1 owns: '{}'", s1);
@@ -54,7 +90,7 @@ fn basic_borrowing_concepts() {
     // Immutable borrow
     let len = calculate_length(&s1);
     println!("Length of '{}' is {}", s1, len);
-    println!("s1 is still valid after borrowing!");
+    explain!("s1 is still valid after borrowing!");
 
     println!("\nBORROWING vs OWNERSHIP:");
     println!("=======================");
@@ -71,9 +107,9 @@ fn basic_borrowing_concepts() {
 
     println!("\nBORROWING OPERATORS:");
     println!("===================");
-    println!("&  - Immutable borrow (read-only access)");
-    println!("&mut - Mutable borrow (read-write access)");
-    println!("*  - Dereference operator (access the value)");
+    explain!("&  - Immutable borrow (read-only access)");
+    explain!("&mut - Mutable borrow (read-write access)");
+    explain!("*  - Dereference operator (access the value)");
 
     let x = 42;
     let ref_x = &x; // Immutable borrow
@@ -82,11 +118,15 @@ fn basic_borrowing_concepts() {
     let mut y = 10;
     let ref_y = &mut y; // Mutable borrow
     *ref_y += 5; // Dereference and modify
-    println!("y: {}, ref_y: {}", y, ref_y);
+    println!("ref_y: {}", ref_y);
+    // y can be read again once ref_y is no longer used
+    println!("y: {}", y);
 
     println!();
 }
 
+// Some variables only exist for the commented-out COMPILE ERROR lines
+#[allow(unused_mut)]
 fn immutable_borrowing() {
     println!("2. Immutable Borrowing:");
     println!("=======================\n");
@@ -97,7 +137,7 @@ fn immutable_borrowing() {
     let s = String::from("hello world");
     let s_ref = &s; // Immutable borrow
     println!("s: '{}', s_ref: '{}'", s, s_ref);
-    println!("Both s and s_ref can access the same data");
+    explain!("Both s and s_ref can access the same data");
 
     println!("\nMULTIPLE IMMUTABLE BORROWS:");
     println!("==========================");
@@ -110,7 +150,7 @@ fn immutable_borrowing() {
     println!("ref1: '{}'", ref1);
     println!("ref2: '{}'", ref2);
     println!("ref3: '{}'", ref3);
-    println!("All references point to the same data");
+    explain!("All references point to the same data");
 
     println!("\nIMMUTABLE BORROWING WITH FUNCTIONS:");
     println!("===================================");
@@ -122,7 +162,7 @@ fn immutable_borrowing() {
     println!("Text: '{}'", text);
     println!("Word count: {}", word_count);
     println!("Character count: {}", char_count);
-    println!("text is still valid after multiple function calls");
+    explain!("text is still valid after multiple function calls");
 
     println!("\nIMMUTABLE BORROWING LIMITATIONS:");
     println!("===============================");
@@ -130,7 +170,7 @@ fn immutable_borrowing() {
     let mut s = String::from("hello");
     let s_ref = &s; // Immutable borrow
     // s.push_str(" world");  // COMPILE ERROR: cannot borrow as mutable!
-    println!("Cannot modify data while it's immutably borrowed");
+    explain!("Cannot modify data while it's immutably borrowed");
     println!("s_ref: '{}'", s_ref);
 
     println!();
@@ -159,7 +199,7 @@ fn mutable_borrowing() {
     println!("ref1: '{}'", ref1);
 
     // let ref2 = &mut data;  // COMPILE ERROR: cannot borrow as mutable more than once!
-    println!("Cannot have multiple mutable borrows simultaneously");
+    explain!("Cannot have multiple mutable borrows simultaneously");
 
     println!("\nMUTABLE BORROWING WITH FUNCTIONS:");
     println!("=================================");
@@ -185,16 +225,18 @@ fn mutable_borrowing() {
     println!();
 }
 
+// Some variables only exist for the commented-out COMPILE ERROR lines
+#[allow(unused_mut)]
 fn borrowing_rules() {
     println!("4. Borrowing Rules and Restrictions:");
     println!("===================================\n");
 
-    println!("THE BORROWING RULES:");
-    println!("===================");
-    println!("1. You can have any number of immutable borrows");
-    println!("2. You can have exactly one mutable borrow");
-    println!("3. You cannot have both immutable and mutable borrows");
-    println!("4. References must always be valid");
+    explain!("THE BORROWING RULES:");
+    explain!("===================");
+    explain!("1. You can have any number of immutable borrows");
+    explain!("2. You can have exactly one mutable borrow");
+    explain!("3. You cannot have both immutable and mutable borrows");
+    explain!("4. References must always be valid");
 
     println!("\nRULE 1: Multiple Immutable Borrows:");
     println!("==================================");
@@ -226,7 +268,7 @@ fn borrowing_rules() {
     println!("Immutable borrows: '{}', '{}'", immut_ref, immut_ref2);
 
     // let mut_ref = &mut data;  // COMPILE ERROR: cannot borrow as mutable!
-    println!("Cannot have mutable borrow while immutable borrows exist");
+    explain!("Cannot have mutable borrow while immutable borrows exist");
 
     println!("\nRULE 4: References Must Be Valid:");
     println!("=================================");
@@ -270,7 +312,7 @@ fn borrowing_with_functions() {
     let word_count = count_words(&text); // Borrow text again
     println!("Text: '{}'", text);
     println!("Length: {}, Words: {}", length, word_count);
-    println!("text is still valid after function calls");
+    explain!("text is still valid after function calls");
 
     println!("\nMUTABLE FUNCTION PARAMETERS:");
     println!("============================");
@@ -348,7 +390,7 @@ fn borrowing_with_collections() {
     // Iterate with mutable borrows
     let mut mutable_words = vec![String::from("hello"), String::from("world")];
     for word in &mut mutable_words {
-        word.push_str("!");
+        word.push('!');
     }
     println!("Modified words: {:?}", mutable_words);
 
@@ -517,6 +559,10 @@ fn advanced_borrowing_patterns() {
     println!("Text: '{}'", text);
     println!("Hello: '{}'", hello);
     println!("World: '{}'", world);
+    deep!(
+        "Each slice is a pointer into text plus a length: {} bytes, no copy",
+        std::mem::size_of::<&str>()
+    );
 
     println!("\nBORROWING WITH SMART POINTERS:");
     println!("=============================");
@@ -588,7 +634,7 @@ fn borrowing_and_lifetimes() {
                 return &s[0..i];
             }
         }
-        &s[..]
+        s
     }
 
     let text = String::from("hello world");
@@ -678,13 +724,13 @@ fn common_borrowing_scenarios() {
 
     println!("Modified items: {:?}", items);
 
-    println!("\nBORROWING BEST PRACTICES:");
-    println!("========================");
-    println!("1. Use the smallest scope possible for borrows");
-    println!("2. Prefer immutable borrows when possible");
-    println!("3. Use references to avoid unnecessary copying");
-    println!("4. Understand the borrowing rules thoroughly");
-    println!("5. Use appropriate lifetime annotations");
+    detail!("\nBORROWING BEST PRACTICES:");
+    detail!("========================");
+    detail!("1. Use the smallest scope possible for borrows");
+    detail!("2. Prefer immutable borrows when possible");
+    detail!("3. Use references to avoid unnecessary copying");
+    detail!("4. Understand the borrowing rules thoroughly");
+    detail!("5. Use appropriate lifetime annotations");
 
     println!();
 }
@@ -717,6 +763,7 @@ fn modify_string(s: &mut String) {
     println!("Modified string to: '{}'", s);
 }
 
+#[allow(clippy::ptr_arg)] // &String on purpose: same signature as calculate_length
 fn get_length(s: &String) -> usize {
     s.len()
 }
@@ -733,6 +780,7 @@ fn create_valid_reference() -> &'static str {
     "This is a valid static reference"
 }
 
+#[allow(dead_code)]
 fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
     if x.len() > y.len() { x } else { y }
 }
@@ -759,6 +807,7 @@ impl Person {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 enum Message {
     Quit,
     Move { x: i32, y: i32 },
@@ -772,13 +821,19 @@ struct ImportantExcerpt<'a> {
 }
 
 #[derive(Debug)]
+#[allow(dead_code)]
 struct Config {
     host: String,
     port: u16,
     timeout: u32,
 }
 
-// Main function to run all borrowing examples
-fn main() {
-    borrowing();
+fn predict_borrows() {
+    let mut scores = vec![10, 20];
+    let first = scores[0];
+    scores.push(first + 5);
+    let total: i32 = scores.iter().sum();
+    let last = scores.last_mut().unwrap();
+    *last *= 2;
+    println!("{:?} total {}", scores, total);
 }
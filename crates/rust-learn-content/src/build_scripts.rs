@@ -0,0 +1,163 @@
+/// Build Scripts in Rust - Generating Code at Compile Time
+///
+/// rust-learn-content has a build.rs that reads every lesson file before the
+/// crate is compiled and writes a table of them into OUT_DIR. This lesson prints
+/// that table and walks through how it got there: `cargo:` instructions,
+/// `OUT_DIR` with `include!`, and `cargo:rerun-if-changed`.
+use crate::generated::{BUILD_PROFILE, GIT_HASH, LESSON_SOURCES};
+use crate::registry::{Question, Section, section};
+
+pub fn build_scripts() {
+    println!("=== Build Scripts Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- build_scripts <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(what_build_scripts_do, "What a Build Script Does"),
+    section!(out_dir_and_include, "OUT_DIR and include!"),
+    section!(rerun_if_changed, "cargo:rerun-if-changed"),
+    section!(when_to_use_build_scripts, "When to Use a Build Script"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "out_dir_and_include",
+        prompt: "Where should a build script write the code it generates?",
+        choices: &[
+            "Into OUT_DIR, for include! to pull in",
+            "Into src/, next to lib.rs",
+            "Into the home directory",
+            "Into Cargo.toml",
+        ],
+        explanation: "OUT_DIR belongs to the build, so generated code never ends up in the source tree or in git.",
+    },
+    Question {
+        section: "rerun_if_changed",
+        prompt: "What does printing `cargo:rerun-if-changed=lessons/` do?",
+        choices: &[
+            "Runs the build script again only when lessons/ changes",
+            "Deletes lessons/ before each build",
+            "Runs the script on every build",
+            "Watches lessons/ while the program runs",
+        ],
+        explanation: "Without any rerun-if-changed line, cargo runs the script again when any file in the package changes.",
+    },
+];
+
+fn what_build_scripts_do() {
+    println!("1. What a Build Script Does:");
+    println!("============================\n");
+
+    explain!("BUILD ORDER:");
+    explain!("============");
+    explain!("1. cargo compiles build.rs (next to Cargo.toml) into a small program");
+    explain!("2. cargo runs it, with CARGO_MANIFEST_DIR, OUT_DIR, PROFILE, ... set");
+    explain!("3. cargo reads `cargo:` lines the script prints to stdout");
+    explain!("4. only then is the crate itself compiled");
+
+    println!("\nWHAT THIS CRATE'S build.rs GENERATED:");
+    println!("=====================================");
+    println!("{:<30} {:>6} {:>9}", "file", "lines", "sections");
+    for source in LESSON_SOURCES {
+        println!(
+            "{:<30} {:>6} {:>9}",
+            source.file, source.lines, source.sections
+        );
+    }
+    let total: usize = LESSON_SOURCES.iter().map(|source| source.lines).sum();
+    println!("{} lesson files, {} lines", LESSON_SOURCES.len(), total);
+
+    explain!("\nThis table is a plain `const` - no file is read at run time.");
+
+    println!();
+}
+
+fn out_dir_and_include() {
+    println!("2. OUT_DIR and include!:");
+    println!("========================\n");
+
+    println!("WHERE GENERATED FILES GO:");
+    println!("=========================");
+    explain!("Build scripts may only write inside OUT_DIR, a directory cargo creates");
+    explain!("for each package under target/. For this build it is:");
+    println!("  {}", env!("OUT_DIR"));
+
+    explain!("\nPULLING THE FILE IN:");
+    explain!("====================");
+    explain!("// build.rs");
+    explain!("let out_dir = PathBuf::from(env::var(\"OUT_DIR\").unwrap());");
+    explain!("fs::write(out_dir.join(\"lessons_generated.rs\"), code).unwrap();");
+    println!();
+    explain!("// src/generated.rs");
+    explain!("include!(concat!(env!(\"OUT_DIR\"), \"/lessons_generated.rs\"));");
+
+    explain!("\nWHY NOT WRITE INTO src/:");
+    explain!("========================");
+    explain!("- The source directory may be read-only (crates.io downloads, vendoring)");
+    explain!("- Generated files would show up in git diffs");
+    explain!("- Debug and release builds could overwrite each other's output");
+
+    println!();
+}
+
+fn rerun_if_changed() {
+    println!("3. cargo:rerun-if-changed:");
+    println!("==========================\n");
+
+    explain!("THE DEFAULT:");
+    explain!("============");
+    explain!("Without any rerun-if lines, cargo reruns the script whenever any file");
+    explain!("in the package changes.");
+
+    explain!("\nWHAT THIS build.rs WATCHES:");
+    explain!("===========================");
+    explain!("cargo:rerun-if-changed=src       - any lesson file (directories are scanned)");
+    explain!("cargo:rerun-if-changed=build.rs  - the script itself");
+    explain!("cargo:rerun-if-changed=.git/HEAD - a new commit changes the embedded hash");
+
+    println!("\nEMBEDDED BUILD INFORMATION:");
+    println!("===========================");
+    match GIT_HASH {
+        Some(hash) => println!("git commit:    {}", hash),
+        None => println!("git commit:    unknown (not built from a git checkout)"),
+    }
+    println!("build profile: {}", BUILD_PROFILE);
+
+    explain!("\nA PITFALL:");
+    explain!("==========");
+    explain!("Watching a path that does not exist makes cargo rerun the script on");
+    explain!("every build, so build.rs only watches .git when it is there.");
+
+    println!();
+}
+
+fn when_to_use_build_scripts() {
+    println!("4. When to Use a Build Script:");
+    println!("==============================\n");
+
+    explain!("GOOD REASONS:");
+    explain!("=============");
+    explain!("- Generating Rust code from data (tables, schemas, protobuf)");
+    explain!("- Compiling and linking C code (the cc crate)");
+    explain!("- Probing the system for a library (pkg-config) or the compiler version");
+    explain!("- Embedding build information such as a git hash");
+
+    explain!("\nBETTER DONE ANOTHER WAY:");
+    explain!("========================");
+    explain!("- Code that fits in a macro_rules! or const fn");
+    explain!("- Anything needing the network (builds must work offline)");
+    explain!("- Modifying files outside OUT_DIR");
+    explain!("- Slow work: the script runs before every affected build");
+
+    explain!("\nDEBUGGING:");
+    explain!("==========");
+    explain!("cargo build -vv  # shows the script's `cargo:` output");
+    explain!("cargo:warning=... lines are shown to the user as warnings");
+
+    println!();
+}
@@ -0,0 +1,422 @@
+/// Closures in Rust - Functions That Capture Their Environment
+///
+/// A closure is an anonymous function that can use the variables around it.
+/// How it uses them decides how it captures them: by shared reference, by
+/// mutable reference or by taking ownership, and that in turn decides which
+/// of the `Fn`, `FnMut` and `FnOnce` traits it implements. Functions take
+/// closures through those traits, return them with `impl Fn` or
+/// `Box<dyn Fn>`, and store them in structs, like a cacher that runs an
+/// expensive calculation once per argument.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::thread;
+
+pub fn closures() {
+    println!("=== Closures Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- closures <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(closure_syntax, "Closure Syntax"),
+    section!(capture_by_reference, "Capturing by Reference"),
+    section!(capture_by_mutable_reference, "Capturing by Mutable Reference").with_checkpoint(
+        "While a closure that pushes to `list` is still going to be called, can you print `list`?",
+        false,
+        "The closure holds a mutable borrow of list from where it is defined to its last call; printing needs a shared one.",
+    ),
+    section!(capture_by_move, "Capturing by Move"),
+    section!(fn_traits, "Fn, FnMut and FnOnce").with_checkpoint(
+        "Can a closure that only reads its captures be passed where an FnOnce is expected?",
+        true,
+        "Every Fn is also an FnMut and an FnOnce: a closure that can be called many times can certainly be called once.",
+    ),
+    section!(closures_as_parameters, "Closures as Parameters"),
+    section!(returning_closures, "Returning Closures"),
+    section!(memoization, "Memoization With a Cacher"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "capture_by_move",
+        prompt: "Why does `thread::spawn(|| println!(\"{:?}\", list))` fail to compile without `move`?",
+        choices: &[
+            "The closure borrows list, and the thread might outlive it",
+            "Vectors cannot be sent to other threads",
+            "println! is not allowed in threads",
+            "Closures cannot capture vectors",
+        ],
+        explanation: "spawn needs a 'static closure; `move` gives the thread its own list instead of a borrow.",
+    },
+    Question {
+        section: "fn_traits",
+        prompt: "Which trait does `let consume = move || drop(name);` implement?",
+        choices: &[
+            "Only FnOnce",
+            "Fn, FnMut and FnOnce",
+            "FnMut and FnOnce",
+            "None of them",
+        ],
+        explanation: "Calling it moves name out of the closure, so it can only be called once.",
+    },
+    Question {
+        section: "closures_as_parameters",
+        prompt: "Why does `sort_by_key` take an FnMut rather than an FnOnce?",
+        choices: &[
+            "It calls the closure many times, once or more per element",
+            "It needs to change the slice through the closure",
+            "FnOnce closures cannot return values",
+            "FnMut is faster",
+        ],
+        explanation: "A function asks for the least it needs: sorting calls the key closure repeatedly, so FnOnce is not enough.",
+    },
+    Question {
+        section: "returning_closures",
+        prompt: "A function returns `|x| x + 1` from one branch and `|x| x * 2` from the other. What return type works?",
+        choices: &[
+            "Box<dyn Fn(i32) -> i32>",
+            "impl Fn(i32) -> i32",
+            "Fn(i32) -> i32",
+            "fn(i32) -> i32 is the only option",
+        ],
+        explanation: "Every closure has its own type; impl Fn names one hidden type, a Box<dyn Fn> can hold either.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_counter)];
+
+fn add_one_fn(x: u32) -> u32 {
+    x + 1
+}
+
+fn closure_syntax() {
+    println!("1. Closure Syntax:");
+    println!("==================");
+
+    let add_one_annotated = |x: u32| -> u32 { x + 1 };
+    let add_one_inferred = |x| x + 1;
+    let add_one_short = |x: u32| x + 1;
+    println!("add_one_fn(1)        = {}", add_one_fn(1));
+    println!("add_one_annotated(1) = {}", add_one_annotated(1));
+    println!("add_one_inferred(1)  = {}", add_one_inferred(1u32));
+    println!("add_one_short(1)     = {}", add_one_short(1));
+    explain!("`|x| x + 1` is a function without a name; the types can usually be left out");
+    explain!("The braces are optional when the body is one expression");
+
+    let example = |x| x;
+    let text = example(String::from("hello"));
+    println!("example(String::from(\"hello\")) = {}", text);
+    // let number = example(5);
+    //     // COMPILE ERROR: expected `String`, found integer
+    explain!("The first call fixes an inferred type: `example` takes Strings from then on");
+    detail!("Unlike fn items, closures are not generic; each has one signature");
+    println!();
+}
+
+fn capture_by_reference() {
+    println!("2. Capturing by Reference:");
+    println!("==========================");
+
+    let list = vec![1, 2, 3];
+    let only_borrows = || println!("in the closure: {:?}", list);
+    println!("before calling:  {:?}", list);
+    only_borrows();
+    println!("after calling:   {:?}", list);
+    explain!("The closure only reads list, so it captures a shared reference to it");
+    explain!("Other shared borrows are fine alongside it, before and after the call");
+
+    let threshold = 2;
+    let above: Vec<_> = list.iter().filter(|&&n| n > threshold).collect();
+    println!("above {} = {:?}", threshold, above);
+    detail!("A function could not do that: `fn` items cannot see the local `threshold`");
+    println!();
+}
+
+fn capture_by_mutable_reference() {
+    println!("3. Capturing by Mutable Reference:");
+    println!("==================================");
+
+    let mut list = vec![1, 2, 3];
+    println!("before defining the closure: {:?}", list);
+    let mut borrows_mutably = || list.push(7);
+    // println!("{:?}", list);
+    //     // COMPILE ERROR: cannot borrow `list` as immutable because it is also borrowed as mutable
+    borrows_mutably();
+    borrows_mutably();
+    println!("after calling it twice:      {:?}", list);
+    explain!("Pushing needs `&mut list`, so the closure captures a mutable reference");
+    explain!("The closure variable is `mut` too: calling it changes what it holds");
+    explain!("Between the definition and the last call nothing else may use list");
+    detail!("After the last call the borrow ends, and list can be printed again");
+    println!();
+}
+
+fn capture_by_move() {
+    println!("4. Capturing by Move:");
+    println!("=====================");
+
+    let list = vec![1, 2, 3];
+    println!("before the thread: {:?}", list);
+    thread::spawn(move || println!("from the thread: {:?}", list))
+        .join()
+        .expect("the thread does not panic");
+    // println!("{:?}", list);  // COMPILE ERROR: borrow of moved value: `list`
+    explain!("`move` makes the closure take ownership of list, although it only reads it");
+    explain!("The new thread may outlive this function, so a borrow would not be allowed");
+
+    let name = String::from("Ferris");
+    let owns_name = move || format!("hello, {}", name);
+    println!("owns_name() = {}", owns_name());
+    println!("owns_name() = {}", owns_name());
+    detail!("Moving decides how a closure captures, not how it is called: owns_name still");
+    detail!("  only reads its String, so it can be called again and again");
+    deep!("Copy values are copied in by `move`: the original stays usable");
+    println!();
+}
+
+/// Calls `f` as often as it likes: needs Fn
+fn call_twice<F: Fn() -> String>(f: F) -> String {
+    format!("{} / {}", f(), f())
+}
+
+/// Calls `f` several times, and f may change what it captured
+fn call_three_times<F: FnMut()>(mut f: F) {
+    for _ in 0..3 {
+        f();
+    }
+}
+
+/// Calls `f` once, so f may give away what it captured
+fn call_once<F: FnOnce() -> String>(f: F) -> String {
+    f()
+}
+
+fn fn_traits() {
+    println!("5. Fn, FnMut and FnOnce:");
+    println!("========================");
+
+    let greeting = String::from("hi");
+    let reads = || greeting.clone();
+    println!("call_twice(reads)      = {}", call_twice(reads));
+
+    let mut count = 0;
+    call_three_times(|| count += 1);
+    println!("call_three_times(|| count += 1) -> count = {}", count);
+
+    let name = String::from("Ferris");
+    let gives_away = move || name;
+    println!("call_once(gives_away)  = {}", call_once(gives_away));
+    // gives_away();  // COMPILE ERROR: use of moved value: `gives_away`
+    explain!("Fn      reads its captures: can be called any number of times, even at once");
+    explain!("FnMut   changes its captures: called many times, one call at a time");
+    explain!("FnOnce  moves a capture out: can be called once");
+    explain!("The compiler picks the traits from the body, not from `move`");
+
+    println!(
+        "call_once(|| greeting.clone()) = {}",
+        call_once(|| greeting.clone())
+    );
+    detail!("Every Fn is an FnMut, and every FnMut an FnOnce: a bound of FnOnce accepts all");
+    detail!("  three, so asking for the least you need lets callers pass the most closures");
+    println!();
+}
+
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+/// A generic parameter: one copy of apply per closure type, calls inlined
+fn apply<F: Fn(i32) -> i32>(f: F, value: i32) -> i32 {
+    f(value)
+}
+
+/// The same through a trait object: one copy, any closure
+fn apply_dyn(f: &dyn Fn(i32) -> i32, value: i32) -> i32 {
+    f(value)
+}
+
+/// A function pointer: fn items, and closures that capture nothing
+fn apply_pointer(f: fn(i32) -> i32, value: i32) -> i32 {
+    f(value)
+}
+
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+fn closures_as_parameters() {
+    println!("6. Closures as Parameters:");
+    println!("==========================");
+
+    let offset = 10;
+    println!(
+        "apply(|x| x + offset, 5)         = {}",
+        apply(|x| x + offset, 5)
+    );
+    println!(
+        "apply_dyn(&|x| x + offset, 5)    = {}",
+        apply_dyn(&|x| x + offset, 5)
+    );
+    println!(
+        "apply_pointer(double, 5)         = {}",
+        apply_pointer(double, 5)
+    );
+    println!(
+        "apply_pointer(|x| x - 1, 5)      = {}",
+        apply_pointer(|x| x - 1, 5)
+    );
+    // apply_pointer(|x| x + offset, 5);
+    //     // COMPILE ERROR: closures can only be coerced to `fn` types if they do not capture any variables
+    explain!("`F: Fn(i32) -> i32` (or `impl Fn(i32) -> i32`) takes any closure of that shape");
+    explain!("`&dyn Fn` does too, with one copy of the function; `fn(i32) -> i32` only takes");
+    explain!("  functions and closures that capture nothing");
+
+    let mut rectangles = [
+        Rectangle {
+            width: 10,
+            height: 1,
+        },
+        Rectangle {
+            width: 3,
+            height: 5,
+        },
+        Rectangle {
+            width: 7,
+            height: 12,
+        },
+    ];
+    let mut comparisons = 0;
+    rectangles.sort_by_key(|rectangle| {
+        comparisons += 1;
+        rectangle.width
+    });
+    let sizes: Vec<String> = rectangles
+        .iter()
+        .map(|rectangle| format!("{}x{}", rectangle.width, rectangle.height))
+        .collect();
+    println!("sorted by width: {:?}", sizes);
+    println!("the key closure was called {} times", comparisons);
+    explain!("sort_by_key takes an FnMut: it calls the key closure many times, and this one");
+    explain!("  counts its calls in a captured variable");
+    detail!(
+        "Functions work where closures do: `.map(ToString::to_string)` instead of `.map(|x| x.to_string())`"
+    );
+    println!();
+}
+
+/// One closure type, hidden behind impl Fn
+fn make_adder(amount: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + amount
+}
+
+/// Two closure types, so they go behind a pointer
+fn make_operation(name: &str) -> Box<dyn Fn(i32) -> i32> {
+    if name == "double" {
+        Box::new(|x| x * 2)
+    } else {
+        let amount = name.len() as i32;
+        Box::new(move |x| x + amount)
+    }
+}
+
+fn returning_closures() {
+    println!("7. Returning Closures:");
+    println!("======================");
+
+    let add_five = make_adder(5);
+    println!("make_adder(5)(10) = {}", add_five(10));
+    explain!("Every closure has its own unnamed type, so `-> impl Fn(i32) -> i32` returns it");
+    explain!("`move` is required: `amount` is a parameter and ends with the function");
+
+    let operations: Vec<Box<dyn Fn(i32) -> i32>> = vec![
+        make_operation("double"),
+        make_operation("plus"),
+        Box::new(add_five),
+    ];
+    let results: Vec<i32> = operations.iter().map(|operation| operation(10)).collect();
+    println!("double, plus (+4), add_five applied to 10 = {:?}", results);
+    // fn make_operation(name: &str) -> impl Fn(i32) -> i32 { if ... { |x| x * 2 } else { move |x| x + amount } }
+    //     // COMPILE ERROR: `if` and `else` have incompatible types (no two closures have the same type)
+    explain!("Two branches mean two closure types: return `Box<dyn Fn(i32) -> i32>` instead");
+    detail!("Boxed closures also go in a Vec together, like any trait object");
+    println!();
+}
+
+/// Runs `calculation` once per argument and remembers the results
+struct Cacher<F, T>
+where
+    F: Fn(T) -> T,
+    T: Eq + Hash + Copy,
+{
+    calculation: F,
+    values: HashMap<T, T>,
+    calls: usize,
+}
+
+impl<F, T> Cacher<F, T>
+where
+    F: Fn(T) -> T,
+    T: Eq + Hash + Copy,
+{
+    fn new(calculation: F) -> Cacher<F, T> {
+        Cacher {
+            calculation,
+            values: HashMap::new(),
+            calls: 0,
+        }
+    }
+
+    fn value(&mut self, arg: T) -> T {
+        if let Some(&value) = self.values.get(&arg) {
+            return value;
+        }
+        self.calls += 1;
+        let value = (self.calculation)(arg);
+        self.values.insert(arg, value);
+        value
+    }
+}
+
+fn memoization() {
+    println!("8. Memoization With a Cacher:");
+    println!("=============================");
+
+    let mut slow_square = Cacher::new(|n: u64| {
+        // stands in for something expensive
+        (0..n).map(|_| n).sum()
+    });
+    for arg in [1_000, 20, 1_000, 1_000, 20, 3] {
+        println!("slow_square.value({:>5}) = {}", arg, slow_square.value(arg));
+    }
+    println!(
+        "6 lookups, {} calculations, {} values cached",
+        slow_square.calls,
+        slow_square.values.len()
+    );
+    explain!("Cacher stores the closure in a field of type F, where `F: Fn(T) -> T`");
+    explain!("value() runs it only for an argument it has not seen, then keeps the result");
+    explain!(
+        "`(self.calculation)(arg)` calls the field; `self.calculation(arg)` looks for a method"
+    );
+    detail!("A HashMap keyed by the argument, not one saved value, so each argument is right");
+    deep!("Only pure calculations can be cached like this: same argument, same answer");
+    println!();
+}
+
+fn predict_counter() {
+    let mut count = 0;
+    let mut increment = || {
+        count += 2;
+        count
+    };
+    let first = increment();
+    let second = increment();
+    println!("{} {} {}", first, second, count);
+}
@@ -0,0 +1,124 @@
+/// conditional statements
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn conditional() {
+    println!("=== Conditional Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- conditional <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(if_else, "if / else"),
+    section!(else_if_chains, "else if Chains"),
+    section!(if_in_let, "if in a let Statement"),
+    section!(loop_with_result, "Returning a Value From loop"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "if_else",
+        prompt: "Which line fails to compile?\n\n    let n = 3;\n    if n {\n        println!(\"odd\");\n    }",
+        choices: &["if n {", "let n = 3;", "println!(\"odd\");", "None of them"],
+        explanation: "Rust never turns a number into a bool; the condition has to be one, like `if n != 0`.",
+    },
+    Question {
+        section: "if_in_let",
+        prompt: "Why does `let x = if flag { 5 } else { \"five\" };` fail to compile?",
+        choices: &[
+            "Both branches must have the same type",
+            "if cannot be used in a let",
+            "An else cannot produce a value",
+            "flag must be mutable",
+        ],
+        explanation: "if is an expression here, and x can only have one type, so both branches must agree on it.",
+    },
+    Question {
+        section: "loop_with_result",
+        prompt: "What is result?\n\n    let mut counter = 0;\n    let result = loop {\n        counter += 1;\n        if counter == 10 {\n            break counter * 2;\n        }\n    };",
+        choices: &["20", "10", "()", "It loops forever"],
+        explanation: "break can carry a value out of a loop; the loop ends at 10 and hands back 10 * 2.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_else_if)];
+
+fn if_else() {
+    println!("1. if / else:");
+    println!("=============");
+
+    let number = 6;
+
+    if number < 5 {
+        println!("condition was true");
+    } else {
+        println!("condition was false");
+    }
+
+    println!();
+}
+
+fn else_if_chains() {
+    println!("2. else if Chains:");
+    println!("==================");
+
+    let number = 6;
+
+    if number % 4 == 0 {
+        println!("number is divisible by 4");
+    } else if number % 3 == 0 {
+        println!("number is divisible by 3");
+    } else if number % 2 == 0 {
+        println!("number is divisible by 2");
+    } else {
+        println!("number is not divisible by 4, 3, or 2");
+    }
+
+    println!();
+}
+
+fn if_in_let() {
+    println!("3. if in a let Statement:");
+    println!("=========================");
+
+    let condition = true;
+    let number = if condition { 5 } else { 6 };
+    println!("number is {number}");
+
+    println!();
+}
+
+fn loop_with_result() {
+    println!("4. Returning a Value From loop:");
+    println!("===============================");
+
+    let mut counter = 0;
+    let result = loop {
+        counter += 1;
+        if counter == 10 {
+            break counter * 2;
+        }
+    };
+    println!("result is {result}");
+
+    println!();
+}
+
+fn predict_else_if() {
+    for n in [3, 15, 10] {
+        let label = if n % 15 == 0 {
+            "fizzbuzz"
+        } else if n % 3 == 0 {
+            "fizz"
+        } else if n % 5 == 0 {
+            "buzz"
+        } else {
+            "number"
+        };
+        println!("{} {}", n, label);
+    }
+}
@@ -4,8 +4,64 @@
 /// - const: compile-time constants, immutable, cannot be changed
 /// - let: immutable variables, cannot be reassigned after declaration
 /// - mut: mutable variables, can be changed after declaration
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn const_let_mut_variables() {
+    println!("=== Const, Let and Mut Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- const_let_mut <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(constants, "Constants"),
+    section!(let_variables, "Immutable Variables (let)"),
+    section!(mut_variables, "Mutable Variables (let mut)"),
+    section!(variable_scope_example, "Variable Scope"),
+    section!(type_inference_example, "Type Inference"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "constants",
+        prompt: "Which declaration fails to compile?",
+        choices: &[
+            "const MAX = 100;",
+            "const MAX: u32 = 100;",
+            "let max = 100;",
+            "static NAME: &str = \"rust\";",
+        ],
+        explanation: "A const must always be given its type; only let can leave it to inference.",
+    },
+    Question {
+        section: "variable_scope_example",
+        prompt: "What happens here?\n\n    {\n        let inner = 1;\n    }\n    println!(\"{}\", inner);",
+        choices: &[
+            "It fails to compile: inner is out of scope",
+            "It prints 1",
+            "It prints 0",
+            "It panics",
+        ],
+        explanation: "A variable lives until the end of the block it was declared in, so inner does not exist after the closing brace.",
+    },
+    Question {
+        section: "type_inference_example",
+        prompt: "What type is guess in `let guess = 42;` when nothing else says?",
+        choices: &["i32", "u32", "i64", "usize"],
+        explanation: "An integer literal is an i32 unless something (a suffix, an annotation, how it is used) asks for another type.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_scope)];
+
+fn constants() {
+    println!("1. Constants:");
+    println!("=============");
 
-fn const_let_mut_variables() {
     // CONSTANTS
     // Constants are declared with 'const' and must have a type annotation
     // They are evaluated at compile time and are immutable
@@ -20,9 +76,14 @@ fn const_let_mut_variables() {
     const ONE_HOUR_IN_SECONDS: u32 = 60 * 60;
     const TWO_HOURS_IN_SECONDS: u32 = ONE_HOUR_IN_SECONDS * 2;
     println!("TWO_HOURS_IN_SECONDS is {TWO_HOURS_IN_SECONDS}");
+
+    println!();
 }
 
 fn let_variables() {
+    println!("2. Immutable Variables (let):");
+    println!("=============================");
+
     // IMMUTABLE VARIABLES (let)
     // Variables declared with 'let' are immutable by default
     // This means once assigned, they cannot be changed
@@ -44,9 +105,14 @@ fn let_variables() {
     // You can also shadow with a different type
     let x = x.len(); // x is now a usize (the length of the string)
     println!("x is now the length: {x}");
+
+    println!();
 }
 
 fn mut_variables() {
+    println!("3. Mutable Variables (let mut):");
+    println!("===============================");
+
     // MUTABLE VARIABLES (let mut)
     // Variables declared with 'let mut' can be changed after declaration
     // Use 'mut' when you need to modify the value later
@@ -70,9 +136,14 @@ fn mut_variables() {
     counter += 1;
     counter += 1;
     println!("Counter is: {counter}");
+
+    println!();
 }
 
 fn variable_scope_example() {
+    println!("4. Variable Scope:");
+    println!("==================");
+
     // VARIABLE SCOPE
     // Variables are only valid within their scope (the block where they're declared)
 
@@ -92,9 +163,14 @@ fn variable_scope_example() {
     // println!("Outer trying to access inner: {inner_variable}"); // ❌ Won't compile
 
     println!("Outer: {outer_variable}"); // This works fine
+
+    println!();
 }
 
 fn type_inference_example() {
+    println!("5. Type Inference:");
+    println!("==================");
+
     // TYPE INFERENCE
     // Rust can often infer the type of a variable from its value
 
@@ -114,22 +190,17 @@ fn type_inference_example() {
         "Explicit types: explicit_int={}, explicit_float={}, explicit_bool={}, explicit_string={}",
         explicit_int, explicit_float, explicit_bool, explicit_string
     );
-}
-
-// Main function to demonstrate all concepts
-pub fn demonstrate_variables() {
-    println!("=== CONSTANTS ===");
-    const_let_mut_variables();
 
-    println!("\n=== IMMUTABLE VARIABLES (let) ===");
-    let_variables();
-
-    println!("\n=== MUTABLE VARIABLES (let mut) ===");
-    mut_variables();
-
-    println!("\n=== VARIABLE SCOPE ===");
-    variable_scope_example();
+    println!();
+}
 
-    println!("\n=== TYPE INFERENCE ===");
-    type_inference_example();
+fn predict_scope() {
+    let count = 1;
+    {
+        let count = count + 10;
+        println!("{}", count);
+    }
+    let mut total = count;
+    total += 2;
+    println!("{} {}", count, total);
 }
@@ -0,0 +1,423 @@
+/// Cow in Rust - Borrow Usually, Allocate When You Must
+///
+/// Many string functions leave most of their input alone: normalizing
+/// whitespace, escaping HTML, trimming a prefix. Returning `String` makes
+/// every call allocate a copy, even when nothing changed; returning `&str`
+/// cannot hand back a changed string at all. `Cow<'_, str>` ("clone on
+/// write") is either: `Borrowed` when the input was already fine, `Owned`
+/// when it had to change. This lesson writes a whitespace normalizer and an
+/// HTML escaper that way, counts the allocations they save on a batch of
+/// mostly-clean lines with rust-learn's counting allocator, chains them
+/// without losing the savings, and ends with where `Cow` earns its keep.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_core::alloc_counter;
+use std::borrow::Cow;
+use std::mem::size_of;
+use std::time::{Duration, Instant};
+
+pub fn cow() {
+    println!("=== Cow Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- cow <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(borrow_or_own, "Cow: Borrowed or Owned"),
+    section!(
+        normalizing_whitespace,
+        "A Normalizer That Borrows When It Can"
+    ),
+    section!(counting_allocations, "Counting the Allocations Avoided").with_checkpoint(
+        "Does returning Cow::Borrowed(text) allocate?",
+        false,
+        "Borrowed holds the same &str that came in; only building the Owned String allocates.",
+    ),
+    section!(chaining_and_to_mut, "Chaining, to_mut and into_owned"),
+    section!(when_to_use_cow, "Where Cow Earns Its Keep"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "borrow_or_own",
+        prompt: "A function returns Cow<'_, str>. How does the caller use the text?",
+        choices: &[
+            "Like a &str: Cow derefs to it, whichever variant it is",
+            "It must match on Borrowed and Owned every time",
+            "It must call into_owned() first",
+            "Only through to_string()",
+        ],
+        explanation: "Cow<str> implements Deref<Target = str>, so &*cow, cow.len() and println! all just work.",
+    },
+    Question {
+        section: "normalizing_whitespace",
+        prompt: "When should a normalizer return Cow::Owned?",
+        choices: &[
+            "Only when the text actually has to change",
+            "Always, to be safe",
+            "When the text is long",
+            "When the caller will keep it",
+        ],
+        explanation: "The saving comes from checking first and borrowing the input whenever it is already normal.",
+    },
+    Question {
+        section: "counting_allocations",
+        prompt: "9 in 10 lines are already normal. Roughly how many allocations does the Cow version make, against one per line for String?",
+        choices: &[
+            "About 1 in 10 lines",
+            "The same, one per line",
+            "None at all",
+            "Two per line",
+        ],
+        explanation: "Only the lines that change are copied into a new String; the rest are borrowed.",
+    },
+    Question {
+        section: "chaining_and_to_mut",
+        prompt: "What does Cow::to_mut() do on a Borrowed value?",
+        choices: &[
+            "Clones it into an Owned value, then returns &mut to that",
+            "Panics",
+            "Returns &mut to the borrowed data",
+            "Returns None",
+        ],
+        explanation: "That is the clone on write: the copy happens the first time something needs to change it.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_cow_variants)];
+
+/// Whether `text` is already normal: no whitespace but single spaces, none
+/// at either end
+fn is_normal(text: &str) -> bool {
+    let mut after_space = true;
+    for c in text.chars() {
+        if c.is_whitespace() && (c != ' ' || after_space) {
+            return false;
+        }
+        after_space = c == ' ';
+    }
+    !after_space || text.is_empty()
+}
+
+/// Runs of whitespace become one space, and the ends are trimmed; normal
+/// text is borrowed, not copied
+fn normalize_whitespace(text: &str) -> Cow<'_, str> {
+    if is_normal(text) {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(collapse(text))
+    }
+}
+
+/// `normalize_whitespace` returning `String`: a copy every time
+fn normalize_always(text: &str) -> String {
+    collapse(text)
+}
+
+fn collapse(text: &str) -> String {
+    let mut normal = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !normal.is_empty() {
+            normal.push(' ');
+        }
+        normal.push_str(word);
+    }
+    normal
+}
+
+/// `<`, `>`, `&` and `"` as HTML entities; text without them is borrowed
+fn escape_html(text: &str) -> Cow<'_, str> {
+    if !text.contains(['<', '>', '&', '"']) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 16);
+    for c in text.chars() {
+        match c {
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '&' => escaped.push_str("&amp;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Normalize, then escape, still borrowing when neither changed anything
+/// and allocating at most once more when only the first did
+fn tidy(text: &str) -> Cow<'_, str> {
+    match normalize_whitespace(text) {
+        Cow::Borrowed(normal) => escape_html(normal),
+        Cow::Owned(normal) => match escape_html(&normal) {
+            Cow::Borrowed(_) => Cow::Owned(normal),
+            Cow::Owned(escaped) => Cow::Owned(escaped),
+        },
+    }
+}
+
+#[allow(clippy::ptr_arg)] // Which variant it is is the point; &B would hide it
+fn variant<B: ToOwned + ?Sized>(cow: &Cow<'_, B>) -> &'static str {
+    match cow {
+        Cow::Borrowed(_) => "Borrowed",
+        Cow::Owned(_) => "Owned",
+    }
+}
+
+fn borrow_or_own() {
+    println!("1. Cow: Borrowed or Owned:");
+    println!("==========================");
+
+    explain!("enum Cow<'a, B: ?Sized + ToOwned> {{");
+    explain!("    Borrowed(&'a B),");
+    explain!("    Owned(<B as ToOwned>::Owned),");
+    explain!("}}");
+    explain!("Cow<'a, str> is a &'a str or a String; Cow<'a, [T]> a &'a [T] or a Vec<T>");
+
+    for text in ["already tidy", "  needs\ttidying  "] {
+        let normal = normalize_whitespace(text);
+        println!(
+            "{:<22} -> {:<8} {:?} (len {})",
+            format!("{:?}", text),
+            variant(&normal),
+            normal,
+            normal.len()
+        );
+    }
+    explain!("Both results are used the same way: Cow<str> derefs to &str, so len(),");
+    explain!("  comparisons and {{}} work whichever variant came back");
+    println!(
+        "size_of::<Cow<str>>() = {}, size_of::<String>() = {}, size_of::<&str>() = {}",
+        size_of::<Cow<str>>(),
+        size_of::<String>(),
+        size_of::<&str>()
+    );
+    detail!("Cow<str> is no bigger than String: Borrowed hides in a value String can never hold");
+    println!();
+}
+
+fn normalizing_whitespace() {
+    println!("2. A Normalizer That Borrows When It Can:");
+    println!("=========================================");
+
+    let inputs = [
+        "one two three",
+        "one  two",
+        " leading",
+        "trailing ",
+        "tab\there",
+        "",
+    ];
+    for text in inputs {
+        let normal = normalize_whitespace(text);
+        println!(
+            "{:<17} {:<8} {:?}",
+            format!("{:?}", text),
+            variant(&normal),
+            normal
+        );
+    }
+    explain!("is_normal looks at each char once, without allocating; only text that");
+    explain!("  fails it is copied into a String with the runs collapsed");
+    explain!("The lifetime in Cow<'_, str> ties a Borrowed result to the input, like");
+    explain!("  any &str a function returns");
+    detail!("The check costs a pass over the text, which is cheap next to an allocation");
+    detail!("  and a copy; it pays off whenever most inputs are already fine");
+    println!();
+}
+
+/// `count` lines of text, one in ten with extra whitespace
+fn corpus(count: usize) -> Vec<String> {
+    (0..count)
+        .map(|line| match line % 10 {
+            0 => format!("  line {}   has\tstray  whitespace ", line),
+            _ => format!("line {} is already tidy", line),
+        })
+        .collect()
+}
+
+/// The fastest of five runs of `f`, with the allocations it made
+fn measure(f: impl Fn() -> usize) -> (Duration, alloc_counter::AllocStats) {
+    let mut best = Duration::MAX;
+    let mut stats = alloc_counter::AllocStats::default();
+    for _ in 0..5 {
+        let start = Instant::now();
+        (_, stats) = alloc_counter::count(&f);
+        best = best.min(start.elapsed());
+    }
+    (best, stats)
+}
+
+fn counting_allocations() {
+    println!("3. Counting the Allocations Avoided:");
+    println!("====================================");
+
+    let lines = corpus(10_000);
+    let borrowed = lines
+        .iter()
+        .filter(|line| matches!(normalize_whitespace(line), Cow::Borrowed(_)))
+        .count();
+    println!(
+        "{} lines: {} borrowed, {} owned",
+        lines.len(),
+        borrowed,
+        lines.len() - borrowed
+    );
+
+    let (always_time, always) =
+        measure(|| lines.iter().map(|line| normalize_always(line).len()).sum());
+    let (cow_time, cow) = measure(|| {
+        lines
+            .iter()
+            .map(|line| normalize_whitespace(line).len())
+            .sum()
+    });
+    println!(
+        "{:<20} {:>6} allocations {:>10?}",
+        "-> String", always.allocations, always_time
+    );
+    println!(
+        "{:<20} {:>6} allocations {:>10?}",
+        "-> Cow<str>", cow.allocations, cow_time
+    );
+    match alloc_counter::inner_name() {
+        Some(_) => println!(
+            "allocations avoided: {}",
+            always.allocations.saturating_sub(cow.allocations)
+        ),
+        None => println!("(The counting allocator is not installed, so the counts are 0.)"),
+    }
+    explain!("Returning String copies every line, changed or not. The Cow version");
+    explain!("  copies only the one line in ten that has stray whitespace");
+    explain!("The time saved follows the allocations: fewer calls into the allocator,");
+    explain!("  fewer bytes copied, less memory touched");
+    detail!("Build with --release for realistic times; the counts do not change");
+    println!();
+}
+
+fn chaining_and_to_mut() {
+    println!("4. Chaining, to_mut and into_owned:");
+    println!("===================================");
+
+    for text in [
+        "plain text",
+        "a  <b>bold</b>  move",
+        "fish & chips",
+        "  spaced  out ",
+    ] {
+        let (tidied, stats) = alloc_counter::count(|| tidy(text));
+        println!(
+            "{:<24} {:<8} {} allocation(s)  {:?}",
+            format!("{:?}", text),
+            variant(&tidied),
+            stats.allocations,
+            tidied
+        );
+    }
+    explain!("tidy passes a Borrowed result straight on, and keeps an Owned one when");
+    explain!("  the second step has nothing to change: never more copies than changes");
+
+    let mut title = Cow::Borrowed("Rust");
+    println!("\nbefore to_mut: {} {:?}", variant(&title), title);
+    title.to_mut().push_str(" in Action");
+    println!("after to_mut:  {} {:?}", variant(&title), title);
+    let owned: String = title.into_owned();
+    println!("into_owned():  String {:?}", owned);
+    explain!("to_mut() is the clone on write: a Borrowed value is copied into an Owned");
+    explain!("  one the first time it is changed, and later calls change that copy");
+    explain!("into_owned() gives a String either way, copying only if it was Borrowed");
+    println!();
+}
+
+fn when_to_use_cow() {
+    println!("5. Where Cow Earns Its Keep:");
+    println!("============================");
+
+    let rows = [
+        ("Usually returns its input", "fn f(&str) -> Cow<'_, str>"),
+        ("Always builds new text", "fn f(&str) -> String"),
+        ("Never changes the text", "fn f(&str) -> &str"),
+        ("Fixed text, sometimes built", "Cow<'static, str> field"),
+        (
+            "Takes &'static str or String",
+            "impl Into<Cow<'static, str>>",
+        ),
+    ];
+    println!("{:<30} {}", "WHEN A FUNCTION...", "USE");
+    for (when, signature) in rows {
+        println!("{:<30} {}", when, signature);
+    }
+
+    let messages: [Cow<'static, str>; 2] = [
+        "file not found".into(),
+        format!("line {} is too long", 42).into(),
+    ];
+    for message in &messages {
+        println!("{:<8} {}", variant(message), message);
+    }
+    explain!("An error message is usually a literal and sometimes formatted: as a");
+    explain!("  Cow<'static, str>, the literals are never copied");
+    explain!("Parameters still take &str: let the caller decide how it owns the text");
+    detail!("Serde's #[serde(borrow)] deserializes into Cow<'de, str>: fields without");
+    detail!("  escapes borrow straight from the input");
+    println!();
+}
+
+// Helper functions
+
+fn predict_cow_variants() {
+    let first = normalize_whitespace("a b");
+    let second = normalize_whitespace(" a  b");
+    println!("{} {:?}", variant(&first), first);
+    println!("{} {:?}", variant(&second), second);
+    let mut third = first.clone();
+    third.to_mut().push('!');
+    println!("{} {:?}", variant(&third), third);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_borrows_normal_text() {
+        for text in ["", "one", "one two", "a b c"] {
+            assert!(
+                matches!(normalize_whitespace(text), Cow::Borrowed(_)),
+                "{:?}",
+                text
+            );
+        }
+        for (text, normal) in [
+            (" a", "a"),
+            ("a ", "a"),
+            ("a  b", "a b"),
+            ("a\tb\n", "a b"),
+            ("   ", ""),
+        ] {
+            let result = normalize_whitespace(text);
+            assert!(matches!(result, Cow::Owned(_)), "{:?}", text);
+            assert_eq!(result, normal);
+            assert_eq!(normalize_always(text), normal);
+        }
+    }
+
+    #[test]
+    fn test_escape_and_tidy() {
+        assert!(matches!(escape_html("plain"), Cow::Borrowed(_)));
+        assert_eq!(escape_html("<a & \"b\">"), "&lt;a &amp; &quot;b&quot;&gt;");
+        assert!(matches!(tidy("plain text"), Cow::Borrowed(_)));
+        assert_eq!(tidy("  x  <  y "), "x &lt; y");
+        assert_eq!(tidy(" spaced  out "), "spaced out");
+
+        let lines = corpus(100);
+        let owned = lines
+            .iter()
+            .filter(|line| matches!(normalize_whitespace(line), Cow::Owned(_)))
+            .count();
+        assert_eq!(owned, 10);
+    }
+}
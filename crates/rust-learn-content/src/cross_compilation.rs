@@ -0,0 +1,227 @@
+/// Cross-Compilation in Rust - Targets, Triples and Linkers
+///
+/// rustc can compile for any target it knows, but a working binary also needs
+/// that target's standard library and a linker for it. This lesson takes the
+/// triple this binary was built for apart, checks which of the common targets
+/// this machine can build, and shows how the errors of a failed cross build
+/// read. `rust-learn dev cross <target>` then walks through a real one.
+use crate::generated::BUILD_TARGET;
+use crate::registry::{Question, Section, section};
+use rust_learn_core::cross::{self, Failure};
+use rust_learn_core::toolchain;
+
+pub fn cross_compilation() {
+    println!("=== Cross-Compilation Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- cross_compilation <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(target_triples, "Target Triples"),
+    section!(what_a_cross_build_needs, "What a Cross Build Needs"),
+    section!(this_machine, "Targets and Linkers on This Machine"),
+    section!(reading_failures, "Reading the Failures"),
+    section!(configuring_linkers, "Telling cargo Which Linker to Use"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "target_triples",
+        prompt: "In aarch64-unknown-linux-gnu, what does gnu name?",
+        choices: &[
+            "The C library and ABI (glibc)",
+            "The compiler",
+            "The CPU vendor",
+            "The license",
+        ],
+        explanation: "A triple is architecture, vendor, operating system and environment; musl in its place would mean a static C library.",
+    },
+    Question {
+        section: "configuring_linkers",
+        prompt: "Where do you tell cargo which linker to use for a target?",
+        choices: &[
+            "In .cargo/config.toml, as [target.<triple>] linker",
+            "In Cargo.toml's [dependencies]",
+            "With #[link] in main.rs",
+            "Nowhere; rustc always links by itself",
+        ],
+        explanation: "rustc compiles for any target it has the standard library for, but linking needs a linker that knows the target.",
+    },
+];
+
+fn target_triples() {
+    println!("1. Target Triples:");
+    println!("==================\n");
+
+    println!("THIS BINARY WAS BUILT FOR:");
+    println!("==========================");
+    println!("{}", BUILD_TARGET);
+    let parts: Vec<&str> = BUILD_TARGET.split('-').collect();
+    let labels: &[&str] = match parts.len() {
+        4 => &[
+            "architecture",
+            "vendor",
+            "operating system",
+            "environment (C library, ABI)",
+        ],
+        3 => &["architecture", "vendor", "operating system"],
+        _ => &[],
+    };
+    for (part, label) in parts.iter().zip(labels) {
+        println!("  {:<10} {}", part, label);
+    }
+
+    println!("\nTHE SAME, AS THE PROGRAM SEES IT:");
+    println!("=================================");
+    println!("std::env::consts::ARCH   = {:?}", std::env::consts::ARCH);
+    println!("std::env::consts::OS     = {:?}", std::env::consts::OS);
+    println!("std::env::consts::FAMILY = {:?}", std::env::consts::FAMILY);
+    println!("cfg!(target_env = \"gnu\")  = {}", cfg!(target_env = "gnu"));
+    println!(
+        "cfg!(target_env = \"musl\") = {}",
+        cfg!(target_env = "musl")
+    );
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- A triple is <arch>-<vendor>-<os>[-<env>]; `unknown` means no particular vendor");
+    explain!("- The host triple is what rustc runs on; the target is what it builds for");
+    explain!("- `rustc --print target-list` lists every target rustc knows");
+    explain!("- #[cfg(target_os = \"windows\")] and friends pick code per target at compile time");
+
+    println!();
+}
+
+fn what_a_cross_build_needs() {
+    println!("2. What a Cross Build Needs:");
+    println!("============================\n");
+
+    explain!("THREE THINGS:");
+    explain!("=============");
+    explain!("1. The target's standard library, precompiled: rustup target add <triple>");
+    explain!("2. A linker that writes the target's executable format");
+    explain!("3. The C libraries the program links to, built for the target");
+
+    explain!("\nWHO PROVIDES THE LINKER:");
+    explain!("========================");
+    for target in cross::TARGETS {
+        let linker = target.linker.unwrap_or("default");
+        explain!("{:<27} {:<23} {}", target.triple, linker, target.install);
+    }
+
+    explain!("\nPure Rust crates only need 1 and 2. A crate with a C dependency");
+    explain!("(openssl-sys, for one) needs 3 as well, which is where most pain is.");
+
+    println!();
+}
+
+fn this_machine() {
+    println!("3. Targets and Linkers on This Machine:");
+    println!("=======================================\n");
+
+    let Some(installed) = toolchain::installed_targets() else {
+        println!("rustup was not found, so installed targets are unknown.\n");
+        return;
+    };
+
+    println!("{:<27} {:<10} LINKER", "TARGET", "STD");
+    for target in cross::TARGETS {
+        let std_status = if installed.iter().any(|name| name == target.triple) {
+            "installed"
+        } else {
+            "missing"
+        };
+        let linker_status = match cross::linker_for(target.triple) {
+            Some(linker) if toolchain::version(&linker).is_some() => format!("{} found", linker),
+            Some(linker) => format!("{} missing", linker),
+            None => "default".to_string(),
+        };
+        println!("{:<27} {:<10} {}", target.triple, std_status, linker_status);
+    }
+
+    explain!("\nA target is ready when both columns are: `rust-learn dev cross <target>`");
+    explain!("then builds hello world for it, or explains what is still missing.");
+
+    println!();
+}
+
+fn reading_failures() {
+    println!("4. Reading the Failures:");
+    println!("========================\n");
+
+    explain!("Each first cross build tends to fail in one of a few ways. Here are");
+    explain!("cargo's messages for them, and what `cross::diagnose` makes of each.\n");
+
+    let samples = [
+        (
+            "x86_64-unknown-linux-musl",
+            "error[E0463]: can't find crate for `std`\n  = note: the `x86_64-unknown-linux-musl` target may not be installed",
+        ),
+        (
+            "x86_64-pc-windows-gnu",
+            "error: linker `x86_64-w64-mingw32-gcc` not found\n  = note: No such file or directory (os error 2)",
+        ),
+        (
+            "aarch64-unknown-linux-gnu",
+            "error: linking with `cc` failed: exit status: 1\n  = note: /usr/bin/ld: main.o: Relocations in generic ELF (EM: 183)\n          file in wrong format",
+        ),
+        (
+            "aarch64-unknown-linux-gnu",
+            "error: linking with `aarch64-linux-gnu-gcc` failed: exit status: 1\n  = note: /usr/bin/ld: cannot find -lssl: No such file or directory",
+        ),
+    ];
+
+    for (triple, stderr) in samples {
+        println!("cargo build --target {}:", triple);
+        for line in stderr.lines() {
+            println!("  | {}", line);
+        }
+        let failure = cross::diagnose(stderr);
+        println!("  -> {:?}", failure);
+        if !matches!(failure, Failure::Other(_)) {
+            for line in failure.explain(triple) {
+                explain!("     {}", line);
+            }
+        }
+        println!();
+    }
+}
+
+fn configuring_linkers() {
+    println!("5. Telling cargo Which Linker to Use:");
+    println!("=====================================\n");
+
+    explain!("IN .cargo/config.toml (per project, or ~/.cargo/config.toml):");
+    explain!("=============================================================");
+    explain!("[target.x86_64-pc-windows-gnu]");
+    explain!("linker = \"x86_64-w64-mingw32-gcc\"");
+    explain!("");
+    explain!("[target.aarch64-unknown-linux-gnu]");
+    explain!("linker = \"aarch64-linux-gnu-gcc\"");
+
+    println!("\nOR FOR ONE COMMAND, IN THE ENVIRONMENT:");
+    println!("=======================================");
+    for triple in ["x86_64-pc-windows-gnu", "aarch64-unknown-linux-gnu"] {
+        println!("{}=...", cross::linker_var(triple));
+    }
+
+    explain!("\nWHEN THE C LIBRARIES GET HARD:");
+    explain!("==============================");
+    explain!(
+        "cross (cargo install cross)  - builds inside a Docker image with everything installed"
+    );
+    explain!("cargo-zigbuild               - uses zig cc as a linker for many targets at once");
+    explain!("musl targets                 - one static binary, no C libraries needed at run time");
+
+    detail!("\nWHY A LINKER AT ALL:");
+    detail!("====================");
+    detail!("rustc compiles each crate to object files for the target, but only the");
+    detail!("linker joins them with the standard library and the OS's start-up code");
+    detail!("into an executable the target's loader accepts (ELF, PE or Mach-O).");
+
+    println!();
+}
@@ -0,0 +1,449 @@
+/// Custom Error Types in Rust - By Hand and by Derive
+///
+/// A library's errors are usually one enum per module: a variant for each
+/// way an operation can fail, carrying what the caller needs to react. The
+/// enum implements `Display` for the message, `std::error::Error` for the
+/// cause underneath (`source`), and `From` for the errors `?` should
+/// convert. Written by hand that is a lot of `match`; the thiserror crate
+/// (here rust-learn's own `#[derive(Error)]`, which takes the same
+/// attributes) writes it from one `#[error("...")]` line per variant. Both
+/// versions of a bank transfer's errors below print exactly the same.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_core::Error;
+use std::collections::HashMap;
+use std::error::Error as _;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+
+pub fn custom_errors() {
+    println!("=== Custom Error Types Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- custom_errors <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(domain_error_enum, "A Domain Error Enum"),
+    section!(display_by_hand, "Display by Hand"),
+    section!(source_chaining, "The Error Trait and source()").with_checkpoint(
+        "Should InvalidAmount's Display include the ParseIntError's message too?",
+        false,
+        "The ParseIntError is its source; a report walks the chain and prints it on its own line. Repeating it prints it twice.",
+    ),
+    section!(derived_errors, "The Same Enum, Derived"),
+    section!(choosing, "Choosing an Approach"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "domain_error_enum",
+        prompt: "Why does a library return its own error enum rather than a String?",
+        choices: &[
+            "Callers can match on the variant and read its fields",
+            "Strings cannot be returned in a Result",
+            "Enums print faster",
+            "? does not work with Strings",
+        ],
+        explanation: "A message is for people; a variant like InsufficientFunds { needed, available } is for code.",
+    },
+    Question {
+        section: "source_chaining",
+        prompt: "What should `source()` return for an error that wraps an io::Error?",
+        choices: &[
+            "Some(&the io::Error)",
+            "None, since the message already says it",
+            "The io::Error's message as a String",
+            "A new Box<dyn Error>",
+        ],
+        explanation: "source() links to the cause, so a report or a logger can walk the whole chain.",
+    },
+    Question {
+        section: "derived_errors",
+        prompt: "What does `#[from]` on a variant's field generate, besides making it the source?",
+        choices: &[
+            "A From impl, so ? converts that error into the variant",
+            "A Display impl for the field",
+            "A Clone impl",
+            "Nothing else",
+        ],
+        explanation: "`Ledger(#[from] io::Error)` gives `impl From<io::Error>`, so `fs::read_to_string(path)?` just works.",
+    },
+    Question {
+        section: "choosing",
+        prompt: "When is writing the impls by hand still worth it?",
+        choices: &[
+            "When a message needs logic, or the crate must not add a dependency",
+            "Always: derives are slower at run time",
+            "Never: derives can express everything",
+            "Only for enums with one variant",
+        ],
+        explanation: "Derived and hand-written impls are the same code; hand-written ones can branch, pluralize, or skip a dependency.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_source_chain)];
+
+/// Everything that can go wrong moving money between two accounts
+#[derive(Debug)]
+enum TransferError {
+    InsufficientFunds {
+        needed: u64,
+        available: u64,
+    },
+    AccountNotFound(String),
+    InvalidAmount {
+        input: String,
+        source: ParseIntError,
+    },
+    Ledger(io::Error),
+}
+
+/// Accounts and their balances, in cents
+struct Bank {
+    balances: HashMap<String, u64>,
+}
+
+impl Bank {
+    fn new() -> Bank {
+        let balances = [("alice", 5_000), ("bob", 1_200)];
+        Bank {
+            balances: balances
+                .iter()
+                .map(|(name, cents)| (name.to_string(), *cents))
+                .collect(),
+        }
+    }
+
+    /// Move `amount` (text, as typed) from one account to another
+    fn transfer(&mut self, from: &str, to: &str, amount: &str) -> Result<(), TransferError> {
+        let amount: u64 = amount
+            .trim()
+            .parse()
+            .map_err(|source| TransferError::InvalidAmount {
+                input: amount.to_string(),
+                source,
+            })?;
+        if !self.balances.contains_key(to) {
+            return Err(TransferError::AccountNotFound(to.to_string()));
+        }
+        let available = *self
+            .balances
+            .get(from)
+            .ok_or_else(|| TransferError::AccountNotFound(from.to_string()))?;
+        if available < amount {
+            return Err(TransferError::InsufficientFunds {
+                needed: amount,
+                available,
+            });
+        }
+        *self.balances.get_mut(from).expect("checked above") -= amount;
+        *self.balances.get_mut(to).expect("checked above") += amount;
+        Ok(())
+    }
+}
+
+fn domain_error_enum() {
+    println!("1. A Domain Error Enum:");
+    println!("=======================");
+
+    let mut bank = Bank::new();
+    for (from, to, amount) in [
+        ("alice", "bob", "700"),
+        ("bob", "alice", "9000"),
+        ("alice", "carol", "10"),
+        ("alice", "bob", "ten"),
+    ] {
+        match bank.transfer(from, to, amount) {
+            Ok(()) => println!("{} -> {} {:>5}: ok", from, to, amount),
+            Err(error) => println!("{} -> {} {:>5}: {:?}", from, to, amount, error),
+        }
+    }
+    explain!("One variant per way the transfer can fail, each with the facts about it");
+    explain!("This is the Debug output: derived, for developers, and not yet a message");
+
+    match bank.transfer("bob", "alice", "5000") {
+        Err(TransferError::InsufficientFunds { needed, available }) => {
+            println!(
+                "the caller can react: bob is {} cents short",
+                needed - available
+            )
+        }
+        other => println!("unexpected: {:?}", other),
+    }
+    detail!("A caller matches on the variant and uses its fields; a String would need parsing");
+    println!();
+}
+
+impl fmt::Display for TransferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferError::InsufficientFunds { needed, available } => write!(
+                f,
+                "insufficient funds: needed {}, available {}",
+                needed, available
+            ),
+            TransferError::AccountNotFound(name) => write!(f, "no account named {}", name),
+            TransferError::InvalidAmount { input, .. } => write!(f, "invalid amount {:?}", input),
+            TransferError::Ledger(_) => write!(f, "cannot read the ledger"),
+        }
+    }
+}
+
+fn display_by_hand() {
+    println!("2. Display by Hand:");
+    println!("===================");
+
+    let mut bank = Bank::new();
+    for (from, to, amount) in [("bob", "alice", "9000"), ("alice", "carol", "10")] {
+        if let Err(error) = bank.transfer(from, to, amount) {
+            println!("{{}}   {}", error);
+            println!("{{:?}} {:?}", error);
+        }
+    }
+    explain!("Display is the message for people: one `write!` per variant in a match");
+    explain!("Messages are lowercase with no final period, so they read well inside others");
+    detail!("`{{:?}}` still shows the variant and its fields, for logs and bug reports");
+    println!();
+}
+
+impl std::error::Error for TransferError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TransferError::InvalidAmount { source, .. } => Some(source),
+            TransferError::Ledger(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TransferError {
+    fn from(error: io::Error) -> Self {
+        TransferError::Ledger(error)
+    }
+}
+
+/// Read the day's ledger; `?` turns the io::Error into TransferError::Ledger
+fn read_ledger(path: &str) -> Result<String, TransferError> {
+    let text = fs::read_to_string(path)?;
+    Ok(text)
+}
+
+/// An error and each of its sources, one per line
+fn report(error: &dyn std::error::Error) -> String {
+    let mut text = format!("error: {}", error);
+    let mut cause = error.source();
+    while let Some(error) = cause {
+        text.push_str(&format!("\n  caused by: {}", error));
+        cause = error.source();
+    }
+    text
+}
+
+fn source_chaining() {
+    println!("3. The Error Trait and source():");
+    println!("================================");
+
+    let mut bank = Bank::new();
+    let invalid = bank.transfer("alice", "bob", "12.50").unwrap_err();
+    println!("{}", report(&invalid));
+    let missing = read_ledger("no-such-rust-learn-ledger.txt").unwrap_err();
+    println!("{}", report(&missing));
+    explain!("`impl Error for TransferError` needs Debug and Display, and can add source()");
+    explain!("source() returns the error underneath, so report() can print the whole chain");
+    explain!("`impl From<io::Error>` lets ? in read_ledger wrap the io::Error as Ledger");
+
+    let parse = invalid
+        .source()
+        .and_then(|source| source.downcast_ref::<ParseIntError>());
+    println!("source is a ParseIntError: {}", parse.is_some());
+    detail!("Each layer says what it was doing; the cause says why it failed");
+    deep!(
+        "Error also has description() and cause(), both deprecated in favor of Display and source()"
+    );
+    println!();
+}
+
+/// TransferError again, with the impls written by `#[derive(Error)]`
+#[derive(Debug, Error)]
+enum DerivedTransferError {
+    #[error("insufficient funds: needed {needed}, available {available}")]
+    InsufficientFunds { needed: u64, available: u64 },
+    #[error("no account named {0}")]
+    AccountNotFound(String),
+    #[error("invalid amount {input:?}")]
+    InvalidAmount {
+        input: String,
+        source: ParseIntError,
+    },
+    #[error("cannot read the ledger")]
+    Ledger(#[from] io::Error),
+}
+
+/// read_ledger with the derived type: `#[from]` wrote the From impl
+fn read_ledger_derived(path: &str) -> Result<String, DerivedTransferError> {
+    let text = fs::read_to_string(path)?;
+    Ok(text)
+}
+
+fn derived_errors() {
+    println!("4. The Same Enum, Derived:");
+    println!("==========================");
+
+    let hand = TransferError::InsufficientFunds {
+        needed: 9_000,
+        available: 1_200,
+    };
+    let derived = DerivedTransferError::InsufficientFunds {
+        needed: 9_000,
+        available: 1_200,
+    };
+    println!("by hand: {}", hand);
+    println!("derived: {}", derived);
+    let derived = DerivedTransferError::AccountNotFound("carol".to_string());
+    println!("derived: {}", derived);
+    explain!("#[error(\"...\")] is the variant's message; `{{needed}}` names a field, `{{0}}`");
+    explain!("  a tuple field, and `{{input:?}}` takes a format spec like any format string");
+
+    let invalid = DerivedTransferError::InvalidAmount {
+        input: "12.50".to_string(),
+        source: "12.50".parse::<u64>().unwrap_err(),
+    };
+    println!("{}", report(&invalid));
+    let missing = read_ledger_derived("no-such-rust-learn-ledger.txt").unwrap_err();
+    println!("{}", report(&missing));
+    explain!("`#[from]` makes the field the source and writes `impl From<io::Error>`;");
+    explain!("  a field named `source` (or marked `#[source]`) is the source without From");
+    detail!("The derive writes the same Display, Error and From impls as section 3, no more");
+    deep!("A derive is a procedural macro: a function from the enum's tokens to the impls'");
+    println!();
+}
+
+fn choosing() {
+    println!("5. Choosing an Approach:");
+    println!("========================");
+
+    let rows = [
+        ("Impls by hand", "no dependency; messages can use logic"),
+        (
+            "#[derive(Error)]",
+            "one line per variant; hard to get wrong",
+        ),
+        ("Box<dyn Error>", "any error, for main and scripts"),
+        ("An error String", "prototypes only: nothing to match on"),
+    ];
+    for (approach, when) in rows {
+        println!("{:<18} {}", approach, when);
+    }
+    explain!("Libraries: an enum, so callers can match; derive it once there are more than");
+    explain!("  a few variants, and write by hand when a message needs an `if`");
+    explain!("Applications: often Box<dyn Error> (or anyhow) at the top, enums below");
+
+    let hand = TransferError::AccountNotFound("carol".to_string());
+    let derived = DerivedTransferError::AccountNotFound("carol".to_string());
+    println!(
+        "same message either way: {}",
+        hand.to_string() == derived.to_string()
+    );
+    detail!("Changing from one to the other does not change the type's API: callers cannot tell");
+    deep!("In real projects the derive comes from the thiserror crate, with the same attributes");
+    println!();
+}
+
+fn predict_source_chain() {
+    let error = TransferError::InvalidAmount {
+        input: "1O".to_string(),
+        source: "1O".parse::<u64>().unwrap_err(),
+    };
+    println!("{}", error);
+    let mut cause = error.source();
+    while let Some(inner) = cause {
+        println!("because: {}", inner);
+        cause = inner.source();
+    }
+    println!(
+        "{}",
+        TransferError::AccountNotFound("x".into())
+            .source()
+            .is_none()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The same failure as both types
+    fn both(index: usize) -> (TransferError, DerivedTransferError) {
+        let parse = || "ten".parse::<u64>().unwrap_err();
+        let io = || io::Error::new(io::ErrorKind::NotFound, "ledger.txt is missing");
+        match index {
+            0 => (
+                TransferError::InsufficientFunds {
+                    needed: 9,
+                    available: 3,
+                },
+                DerivedTransferError::InsufficientFunds {
+                    needed: 9,
+                    available: 3,
+                },
+            ),
+            1 => (
+                TransferError::AccountNotFound("carol".into()),
+                DerivedTransferError::AccountNotFound("carol".into()),
+            ),
+            2 => (
+                TransferError::InvalidAmount {
+                    input: "ten".into(),
+                    source: parse(),
+                },
+                DerivedTransferError::InvalidAmount {
+                    input: "ten".into(),
+                    source: parse(),
+                },
+            ),
+            _ => (
+                TransferError::Ledger(io()),
+                DerivedTransferError::from(io()),
+            ),
+        }
+    }
+
+    #[test]
+    fn test_messages() {
+        let expected = [
+            "insufficient funds: needed 9, available 3",
+            "no account named carol",
+            "invalid amount \"ten\"",
+            "cannot read the ledger",
+        ];
+        for (index, message) in expected.iter().enumerate() {
+            let (hand, derived) = both(index);
+            assert_eq!(hand.to_string(), *message);
+            assert_eq!(derived.to_string(), *message);
+        }
+    }
+
+    #[test]
+    fn test_sources() {
+        for index in 0..4 {
+            let (hand, derived) = both(index);
+            let hand = hand.source().map(ToString::to_string);
+            let derived = derived.source().map(ToString::to_string);
+            assert_eq!(hand, derived);
+        }
+        let (hand, _) = both(2);
+        assert!(hand.source().unwrap().is::<ParseIntError>());
+        let (_, derived) = both(3);
+        assert_eq!(
+            derived.source().unwrap().to_string(),
+            "ledger.txt is missing"
+        );
+        assert!(both(0).1.source().is_none());
+    }
+}
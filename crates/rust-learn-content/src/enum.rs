@@ -0,0 +1,110 @@
+/// enum
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn enums() {
+    println!("=== Enum Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- enums <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(enum_example, "Enum Variants and match"),
+    section!(if_let_example, "if let"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "enum_example",
+        prompt: "With `enum Coin { Penny, Nickel, Dime }`, what happens to a match on a Coin that only has arms for Penny and Nickel?",
+        choices: &[
+            "It fails to compile: Dime is not covered",
+            "A Dime matches nothing and is skipped",
+            "It panics when given a Dime",
+            "A Dime takes the Nickel arm",
+        ],
+        explanation: "The compiler checks that every variant is handled, so adding a variant later points you at every match to update.",
+    },
+    Question {
+        section: "if_let_example",
+        prompt: "When does `if let Some(x) = value { ... }` fit better than a match?",
+        choices: &[
+            "When one pattern matters and the rest can be ignored",
+            "When every variant needs its own arm",
+            "Only when value is a bool",
+            "Never; it is unwrap by another name",
+        ],
+        explanation: "if let is a match with one arm and an implicit `_ => {}`; it trades exhaustiveness checking for brevity.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_enum_match)];
+
+// Enums don't implement Display, so derive Debug and print with {:?}
+#[derive(Debug)]
+#[allow(dead_code)] // Quit is only ever matched, never built
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(i32, i32, i32),
+}
+
+fn enum_example() {
+    println!("1. Enum Variants and match:");
+    println!("===========================");
+
+    let msg = Message::Move { x: 1, y: 2 };
+    println!("msg is {msg:?}");
+
+    let msg = Message::Write(String::from("hello"));
+    println!("msg is {msg:?}");
+
+    let msg = Message::ChangeColor(0, 0, 0);
+    println!("msg is {msg:?}");
+
+    // Every variant has an arm, so no `_` arm is needed (it would be unreachable)
+    match msg {
+        Message::Quit => println!("Quit"),
+        Message::Move { x, y } => println!("Move to ({x}, {y})"),
+        Message::Write(s) => println!("Write: {s}"),
+        Message::ChangeColor(r, g, b) => println!("ChangeColor: ({r}, {g}, {b})"),
+    }
+
+    println!();
+}
+
+fn if_let_example() {
+    println!("2. if let:");
+    println!("==========");
+
+    let msg = Message::Move { x: 1, y: 2 };
+    if let Message::Move { x, y } = msg {
+        println!("Move to ({x}, {y})");
+    } else {
+        println!("Not a move message");
+    }
+
+    println!();
+}
+
+fn predict_enum_match() {
+    #[derive(Debug)]
+    enum Light {
+        Red,
+        Yellow,
+        Green,
+    }
+    for light in [Light::Green, Light::Red, Light::Yellow] {
+        let wait = match light {
+            Light::Red => 30,
+            Light::Yellow => 5,
+            Light::Green => 0,
+        };
+        println!("{:?}: wait {}s", light, wait);
+    }
+}
@@ -0,0 +1,461 @@
+/// Error Handling in Rust - Result and the ? Operator
+///
+/// Rust has no exceptions: a function that can fail returns
+/// `Result<T, E>`, and the caller decides what to do with an `Err`. The `?`
+/// operator passes an error up to the caller in one character, converting
+/// it with `From` on the way, and `Box<dyn Error>` holds any error when the
+/// caller only needs to report it. `unwrap` and `expect` turn an error into
+/// a panic, which is right only when the error cannot happen or nothing can
+/// be done about it. The lesson ends by reading a config file, where an I/O
+/// error and a parse error are wrapped in one error type that still knows
+/// its cause.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
+
+pub fn error_handling() {
+    println!("=== Error Handling Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- error_handling <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(result_basics, "Result Basics"),
+    section!(question_mark, "Propagating Errors With ?").with_checkpoint(
+        "Can `?` be used on a Result inside a function that returns `()`?",
+        false,
+        "? returns the error from the function, so the function must return a Result (or an Option, for Option values).",
+    ),
+    section!(unwrap_and_expect, "unwrap and expect"),
+    section!(from_conversions, "Converting Errors With From"),
+    section!(box_dyn_error, "Box<dyn Error>"),
+    section!(reading_a_config, "Worked Example: Reading a Config File").with_checkpoint(
+        "When a config file has a bad number, should the error still carry the ParseIntError?",
+        true,
+        "Keep it as the source: the message says which line, source() says why the number did not parse.",
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "question_mark",
+        prompt: "What does `let n = text.parse::<i32>()?;` do when the parse fails?",
+        choices: &[
+            "Returns Err from the enclosing function, converted with From",
+            "Panics with the parse error",
+            "Sets n to 0",
+            "Skips the rest of the line and carries on",
+        ],
+        explanation: "? is a match: Ok(v) gives v, Err(e) does `return Err(From::from(e))`.",
+    },
+    Question {
+        section: "unwrap_and_expect",
+        prompt: "When is `expect` a good choice over handling the error?",
+        choices: &[
+            "When the error would be a bug, and the message says what was assumed",
+            "Whenever the error is unlikely",
+            "When reading user input",
+            "Never; it is the same as unwrap",
+        ],
+        explanation: "expect documents an invariant; an error a user can cause should be handled or returned.",
+    },
+    Question {
+        section: "from_conversions",
+        prompt: "A function returns Result<T, AppError>. What lets `?` be used on an io::Result in it?",
+        choices: &[
+            "impl From<io::Error> for AppError",
+            "impl Display for AppError",
+            "impl Error for io::Error",
+            "Nothing; ? only works when the error types match",
+        ],
+        explanation: "? calls From::from on the error, so one From impl per wrapped error type is enough.",
+    },
+    Question {
+        section: "box_dyn_error",
+        prompt: "What does a caller lose when a function returns Box<dyn Error> instead of its own enum?",
+        choices: &[
+            "Matching on which error it was, short of downcasting",
+            "The error message",
+            "The ability to use ?",
+            "Nothing at all",
+        ],
+        explanation: "A boxed error still prints and has a source, but its type is only found out with downcast_ref.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_early_return)];
+
+fn result_basics() {
+    println!("1. Result Basics:");
+    println!("=================");
+
+    for text in ["42", "forty-two"] {
+        match text.parse::<i32>() {
+            Ok(number) => println!("{:>11} -> Ok({})", text, number),
+            Err(error) => println!("{:>11} -> Err({})", text, error),
+        }
+    }
+    explain!("`enum Result<T, E> {{ Ok(T), Err(E) }}`: a value, or the reason there is none");
+    explain!("The compiler will not let you use the number without deciding about the Err");
+
+    let good: Result<i32, ParseIntError> = "7".parse();
+    let bad: Result<i32, ParseIntError> = "seven".parse();
+    println!("good.map(|n| n * 2)  = {:?}", good.clone().map(|n| n * 2));
+    println!("bad.unwrap_or(0)     = {}", bad.clone().unwrap_or(0));
+    println!("bad.is_err()         = {}", bad.is_err());
+    println!("good.ok()            = {:?}", good.ok());
+    explain!("Like Option, Result has map, and_then, unwrap_or and friends; ok() turns it");
+    explain!("  into an Option and throws the error away");
+    detail!("Result is #[must_use]: ignoring one, like `\"7\".parse::<i32>();`, is a warning");
+    println!();
+}
+
+/// Adds two numbers given as text; either can fail to parse
+fn add_texts(a: &str, b: &str) -> Result<i32, ParseIntError> {
+    let a: i32 = a.trim().parse()?;
+    let b: i32 = b.trim().parse()?;
+    Ok(a + b)
+}
+
+/// The same without ?, written out the way ? expands
+#[allow(clippy::question_mark)] // The match ? stands for, spelled out on purpose
+fn add_texts_by_hand(a: &str, b: &str) -> Result<i32, ParseIntError> {
+    let a: i32 = match a.trim().parse() {
+        Ok(a) => a,
+        Err(error) => return Err(error),
+    };
+    let b: i32 = match b.trim().parse() {
+        Ok(b) => b,
+        Err(error) => return Err(error),
+    };
+    Ok(a + b)
+}
+
+/// ? works on Option too, in a function that returns an Option
+fn first_char_upper(text: &str) -> Option<char> {
+    let first = text.chars().next()?;
+    Some(first.to_ascii_uppercase())
+}
+
+fn question_mark() {
+    println!("2. Propagating Errors With ?:");
+    println!("=============================");
+
+    println!(
+        "add_texts(\"2\", \" 3\")        = {:?}",
+        add_texts("2", " 3")
+    );
+    println!(
+        "add_texts(\"2\", \"x\")         = {:?}",
+        add_texts("2", "x")
+    );
+    println!(
+        "add_texts_by_hand(\"2\", \"x\") = {:?}",
+        add_texts_by_hand("2", "x")
+    );
+    explain!("`expression?` gives the Ok value, or returns the Err from the whole function");
+    explain!("add_texts and add_texts_by_hand do the same: ? is the match, written short");
+
+    println!(
+        "first_char_upper(\"rust\")   = {:?}",
+        first_char_upper("rust")
+    );
+    println!("first_char_upper(\"\")       = {:?}", first_char_upper(""));
+    // fn main() { let n: i32 = "5".parse()?; }
+    //     // COMPILE ERROR: the `?` operator can only be used in a function that returns `Result` or `Option`
+    explain!("On an Option, ? returns None; the function must return an Option itself");
+    detail!("? does not mix the two: use .ok_or(error)? to turn a None into an Err");
+    println!();
+}
+
+fn unwrap_and_expect() {
+    println!("3. unwrap and expect:");
+    println!("=====================");
+
+    let port: u16 = "8080".parse().unwrap();
+    let retries: u32 = "3".parse().expect("retries is a number literal");
+    println!("\"8080\".parse().unwrap()   = {}", port);
+    println!("\"3\".parse().expect(...)   = {}", retries);
+    // let port: u16 = "80a0".parse().unwrap();
+    //     // PANIC: called `Result::unwrap()` on an `Err` value: ParseIntError { kind: InvalidDigit }
+    // let retries: u32 = "x".parse().expect("retries is a number literal");
+    //     // PANIC: retries is a number literal: ParseIntError { kind: InvalidDigit }
+    explain!("unwrap gives the Ok value or panics; expect panics with your message first");
+    explain!("A panic ends the thread: fine for a bug, wrong for bad input a user can fix");
+
+    let input = "not a number";
+    let fallback = input.parse::<u16>().unwrap_or(8080);
+    let computed = input.parse::<u16>().unwrap_or_else(|error| {
+        println!("  ({}: using 8000)", error);
+        8000
+    });
+    let zero = input.parse::<u16>().unwrap_or_default();
+    println!("unwrap_or(8080)            = {}", fallback);
+    println!("unwrap_or_else(|e| ...)    = {}", computed);
+    println!("unwrap_or_default()        = {}", zero);
+    explain!("When there is a sensible default, use it instead of panicking");
+    explain!("unwrap_or_else runs its closure only for an Err, and is given the error");
+    detail!("Reasonable unwraps: tests, quick prototypes, and values that cannot be wrong,");
+    detail!("  like parsing a literal; write expect with what you assumed in those cases");
+    println!();
+}
+
+/// Everything that can go wrong loading a count from a file
+#[derive(Debug)]
+enum LoadError {
+    Io(io::Error),
+    Parse(ParseIntError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io(error) => write!(f, "could not read the file: {}", error),
+            LoadError::Parse(error) => write!(f, "the file does not hold a number: {}", error),
+        }
+    }
+}
+
+impl Error for LoadError {}
+
+impl From<io::Error> for LoadError {
+    fn from(error: io::Error) -> Self {
+        LoadError::Io(error)
+    }
+}
+
+impl From<ParseIntError> for LoadError {
+    fn from(error: ParseIntError) -> Self {
+        LoadError::Parse(error)
+    }
+}
+
+/// Two kinds of error, one return type: ? converts each with From
+fn load_count(path: &Path) -> Result<u32, LoadError> {
+    let text = fs::read_to_string(path)?;
+    let count = text.trim().parse()?;
+    Ok(count)
+}
+
+fn from_conversions() {
+    println!("4. Converting Errors With From:");
+    println!("===============================");
+
+    let files = SampleFiles::create(&[("count.txt", "12\n"), ("broken.txt", "twelve\n")]);
+    for name in ["count.txt", "broken.txt", "missing.txt"] {
+        match load_count(&files.path(name)) {
+            Ok(count) => println!("{:<12} -> Ok({})", name, count),
+            Err(error) => println!("{:<12} -> Err: {}", name, error),
+        }
+    }
+    explain!("load_count can fail reading (io::Error) or parsing (ParseIntError), and");
+    explain!("  returns LoadError, an enum with a variant for each");
+    explain!("`impl From<io::Error> for LoadError` lets ? convert as it returns");
+
+    let error = load_count(&files.path("broken.txt")).unwrap_err();
+    let kind = match error {
+        LoadError::Io(_) => "Io",
+        LoadError::Parse(_) => "Parse",
+    };
+    println!("the caller can match on it: LoadError::{}", kind);
+    detail!("Its own enum lets the caller treat a missing file and a bad number differently");
+    deep!("Display is for users, Debug for developers; Error needs both");
+    println!();
+}
+
+/// Any error at all, as long as the caller only needs to report it
+fn load_total(path: &Path) -> Result<u32, Box<dyn Error>> {
+    let text = fs::read_to_string(path)?;
+    let mut total = 0;
+    for word in text.split_whitespace() {
+        total += word.parse::<u32>()?;
+    }
+    if total == 0 {
+        return Err("the file has no numbers".into());
+    }
+    Ok(total)
+}
+
+fn box_dyn_error() {
+    println!("5. Box<dyn Error>:");
+    println!("==================");
+
+    let files = SampleFiles::create(&[
+        ("numbers.txt", "1 2 3 4\n"),
+        ("words.txt", "1 two 3\n"),
+        ("empty.txt", ""),
+    ]);
+    for name in ["numbers.txt", "words.txt", "empty.txt", "missing.txt"] {
+        match load_total(&files.path(name)) {
+            Ok(total) => println!("{:<12} -> Ok({})", name, total),
+            Err(error) => println!("{:<12} -> Err: {}", name, error),
+        }
+    }
+    explain!("`Box<dyn Error>` holds any error type, so ? works on each without From impls");
+    explain!("A string becomes one too: `Err(\"the file has no numbers\".into())`");
+
+    let error = load_total(&files.path("words.txt")).unwrap_err();
+    let parse = error.downcast_ref::<ParseIntError>().is_some();
+    let io = error.downcast_ref::<io::Error>().is_some();
+    println!(
+        "downcast_ref::<ParseIntError>() -> {}, ::<io::Error>() -> {}",
+        parse, io
+    );
+    explain!("The type is gone from the signature; downcast_ref asks for it back");
+    detail!("Good for main and for applications; a library's callers usually want an enum");
+    deep!("`fn main() -> Result<(), Box<dyn Error>>` prints the error's Debug and exits 1");
+    println!();
+}
+
+/// Why a config file could not be loaded, with the line it happened on
+#[derive(Debug)]
+enum ConfigError {
+    Read { path: PathBuf, source: io::Error },
+    Syntax { line: usize },
+    Number { line: usize, source: ParseIntError },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, .. } => write!(f, "cannot read {}", path.display()),
+            ConfigError::Syntax { line } => write!(f, "line {}: expected `key = value`", line),
+            ConfigError::Number { line, .. } => write!(f, "line {}: not a number", line),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Read { source, .. } => Some(source),
+            ConfigError::Syntax { .. } => None,
+            ConfigError::Number { source, .. } => Some(source),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Config {
+    width: u32,
+    height: u32,
+}
+
+/// Reads `key = value` lines; the error says where, and its source why
+fn load_config(path: &Path) -> Result<Config, ConfigError> {
+    let text = fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let mut config = Config::default();
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or(ConfigError::Syntax { line: line_number })?;
+        let value: u32 = value.trim().parse().map_err(|source| ConfigError::Number {
+            line: line_number,
+            source,
+        })?;
+        match key.trim() {
+            "width" => config.width = value,
+            "height" => config.height = value,
+            _ => {}
+        }
+    }
+    Ok(config)
+}
+
+/// An error and each of its sources, one per line
+fn report(error: &dyn Error) -> String {
+    let mut text = format!("error: {}", error);
+    let mut cause = error.source();
+    while let Some(error) = cause {
+        text.push_str(&format!("\n  caused by: {}", error));
+        cause = error.source();
+    }
+    text
+}
+
+fn reading_a_config() {
+    println!("6. Worked Example: Reading a Config File:");
+    println!("=========================================");
+
+    let files = SampleFiles::create(&[
+        ("good.conf", "# window\nwidth = 640\nheight = 480\n"),
+        ("typo.conf", "width = 640\nheight 480\n"),
+        ("bad.conf", "width = 640\nheight = 48O\n"),
+    ]);
+    for name in ["good.conf", "typo.conf", "bad.conf", "missing.conf"] {
+        println!("{}:", name);
+        match load_config(&files.path(name)) {
+            Ok(config) => println!("  {:?}", config),
+            Err(error) => {
+                let report = report(&error).replace(&*files.root.to_string_lossy(), "<tmp>");
+                for line in report.lines() {
+                    println!("  {}", line);
+                }
+            }
+        }
+    }
+    explain!("Each layer adds what it knows: load_config the file and the line number,");
+    explain!("  the io::Error or ParseIntError underneath why it failed");
+    explain!("map_err wraps an error with context, then ? returns it");
+    explain!("source() links them, so report() can print the whole chain");
+    detail!("ok_or turns the Option from split_once into a Result, so ? works on it as well");
+    detail!("Don't put the source's message in your own Display too: report() prints both");
+    deep!("Crates like thiserror write these Display, Error and From impls for you");
+    println!();
+}
+
+// Helper functions
+
+/// Sample files in a directory in the temp dir, removed again when dropped
+struct SampleFiles {
+    root: PathBuf,
+}
+
+impl SampleFiles {
+    fn create(files: &[(&str, &str)]) -> Self {
+        let root = std::env::temp_dir().join(format!("rust-learn-errors-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("Failed to create the sample directory");
+        for (name, text) in files {
+            fs::write(root.join(name), text).expect("Failed to write a sample file");
+        }
+        SampleFiles { root }
+    }
+
+    fn path(&self, name: &str) -> PathBuf {
+        self.root.join(name)
+    }
+}
+
+impl Drop for SampleFiles {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn predict_early_return() {
+    fn halve(text: &str) -> Result<i32, ParseIntError> {
+        println!("parsing {}", text);
+        let n: i32 = text.parse()?;
+        println!("halving {}", n);
+        Ok(n / 2)
+    }
+
+    println!("{:?}", halve("8").is_ok());
+    println!("{:?}", halve("eight").is_ok());
+}
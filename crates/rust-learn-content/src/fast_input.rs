@@ -0,0 +1,425 @@
+/// Fast Input in Rust - Reading Millions of Numbers
+///
+/// Competitive programming and interview problems often start with "read n,
+/// then n numbers", where n is a million. The obvious `read_line` loop is
+/// correct but allocates and validates far more than it needs to. This lesson
+/// measures it against reading everything at once and against the `Scanner`
+/// in `rust_learn_utils::io`, which parses integers straight from the input
+/// buffer, then does the same for output.
+use crate::generated::BUILD_PROFILE;
+use crate::registry::{Question, Section, section};
+use rust_learn_core::alloc_counter;
+use rust_learn_core::memory::format_bytes;
+use rust_learn_core::random::Rng;
+use rust_learn_core::runner::format_duration;
+use rust_learn_utils::io::Scanner;
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::time::{Duration, Instant};
+
+pub fn fast_input() {
+    println!("=== Fast Input Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- fast_input <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(read_line_parsing, "The Obvious Way: read_line and parse"),
+    section!(buffered_reading, "BufRead and a Locked stdin"),
+    section!(token_scanner, "A Token Scanner").with_checkpoint(
+        "Does Scanner::token allocate a new Vec for every token it returns?",
+        false,
+        "It clears and refills the same Vec, so once that has grown to the longest token there are no allocations at all.",
+    ),
+    section!(parsing_bytes, "Parsing Integers From Bytes"),
+    section!(benchmark, "Benchmark: A Million Numbers Three Ways"),
+    section!(buffered_output, "Writing Output in Bulk").with_checkpoint(
+        "Does a loop of println! write to stdout once per line, even when the output goes to a file?",
+        true,
+        "Rust's stdout is line-buffered whatever it points at; a BufWriter around stdout().lock() writes in 8 KiB blocks instead.",
+    ),
+    section!(contest_template, "A Contest Template"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "read_line_parsing",
+        prompt: "Why is `let mut line = String::new();` inside a loop over a million lines slow?",
+        choices: &[
+            "It allocates a new buffer for every line; one String cleared each time would do",
+            "String::new reads the whole input",
+            "It locks stdin",
+            "It is not slower than reusing one String",
+        ],
+        explanation: "read_line appends to the String it is given, so one String cleared before each line keeps its allocation.",
+    },
+    Question {
+        section: "buffered_reading",
+        prompt: "What does `io::stdin().lock()` give you?",
+        choices: &[
+            "A StdinLock that implements BufRead and holds the lock until it is dropped",
+            "A copy of everything typed so far",
+            "A stdin no other program can read",
+            "An unbuffered handle to the terminal",
+        ],
+        explanation: "Every read on a plain Stdin takes a lock; taking it once and reading through the StdinLock skips that, and lines() and fill_buf come with BufRead.",
+    },
+    Question {
+        section: "parsing_bytes",
+        prompt: "Why does the Scanner build a number downwards, `value * 10 - digit`, and negate it at the end?",
+        choices: &[
+            "So i64::MIN, whose positive twin does not fit in an i64, still parses",
+            "Subtraction is faster than addition",
+            "To read the digits right to left",
+            "So that overflow wraps instead of failing",
+        ],
+        explanation: "i64 reaches one further below zero than above, so the negative side can hold every value the positive side can.",
+    },
+    Question {
+        section: "buffered_output",
+        prompt: "About how many writes does `BufWriter::new(out)` make to `out` for 100,000 lines of 8 bytes?",
+        choices: &[
+            "About a hundred: one per 8 KiB",
+            "100,000: one per line",
+            "200,000: one per writeln! argument",
+            "Exactly one, at the end",
+        ],
+        explanation: "BufWriter collects small writes in an 8 KiB buffer and hands them on when it is full, and once more when flushed or dropped.",
+    },
+];
+
+/// Numbers in the benchmark input
+const COUNT: usize = 1_000_000;
+
+/// "n" on the first line, then n numbers, one per line; the same every run
+fn generate(count: usize) -> Vec<u8> {
+    let mut rng = Rng::with_seed(77);
+    let mut input = format!("{}\n", count).into_bytes();
+    for _ in 0..count {
+        let number = (rng.next_u64() % 2_000_000_001) as i64 - 1_000_000_000;
+        writeln!(input, "{}", number).expect("writing to a Vec cannot fail");
+    }
+    input
+}
+
+/// How many numbers were read, and their sum, to check every way agrees
+#[derive(Debug, PartialEq)]
+struct Summary {
+    count: usize,
+    sum: i64,
+}
+
+/// A new String for each line, then split and parse
+fn read_line_sum(mut reader: impl BufRead) -> io::Result<Summary> {
+    let mut header = String::new();
+    reader.read_line(&mut header)?;
+    let count: usize = header.trim().parse().map_err(io::Error::other)?;
+    let mut sum = 0;
+    for _ in 0..count {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        for word in line.split_whitespace() {
+            sum += word.parse::<i64>().map_err(io::Error::other)?;
+        }
+    }
+    Ok(Summary { count, sum })
+}
+
+/// The whole input in one String, split in place
+fn read_all_sum(mut reader: impl Read) -> io::Result<Summary> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut words = text.split_ascii_whitespace();
+    let count: usize = words
+        .next()
+        .unwrap_or("0")
+        .parse()
+        .map_err(io::Error::other)?;
+    let mut sum = 0;
+    for word in words.take(count) {
+        sum += word.parse::<i64>().map_err(io::Error::other)?;
+    }
+    Ok(Summary { count, sum })
+}
+
+/// Tokens from the reader's own buffer, integers parsed from bytes
+fn scanner_sum(reader: impl BufRead) -> io::Result<Summary> {
+    let mut scanner = Scanner::new(reader);
+    let count = scanner.int()? as usize;
+    let mut sum = 0;
+    for _ in 0..count {
+        sum += scanner.int()?;
+    }
+    Ok(Summary { count, sum })
+}
+
+fn read_line_parsing() {
+    println!("1. The Obvious Way: read_line and parse:");
+    println!("========================================");
+
+    let input = "3\n10\n-4\n7\n";
+    println!("input: {:?}", input);
+    // On a real run the reader is io::stdin().lock(); a &[u8] is a BufRead too
+    let summary = read_line_sum(input.as_bytes()).expect("the input is well formed");
+    println!("read_line_sum -> {:?}", summary);
+    explain!("Each line: read_line into a new String, split_whitespace, parse::<i64>()");
+    explain!("Correct, and fine for a thousand lines; a million lines costs a million allocations");
+    detail!("read_line also checks every line is valid UTF-8, and parse walks each number again");
+    detail!("Reusing one String (line.clear() before each read_line) removes the allocations");
+    println!();
+}
+
+fn buffered_reading() {
+    println!("2. BufRead and a Locked stdin:");
+    println!("==============================");
+
+    let input = "2\n5 6\n";
+    let lines: Vec<String> = input
+        .as_bytes()
+        .lines()
+        .collect::<io::Result<_>>()
+        .expect("a &[u8] cannot fail to read");
+    println!("lines() on {:?} -> {:?}", input, lines);
+    explain!("BufRead is a reader with a buffer inside: lines(), read_line() and fill_buf()");
+    explain!("`let stdin = io::stdin().lock();` takes stdin's lock once, for every read after it");
+    detail!("Each read on a plain io::stdin() takes and releases that lock");
+
+    let summary = read_all_sum(input.as_bytes()).expect("the input is well formed");
+    println!("read_all_sum -> {:?}", summary);
+    explain!(
+        "read_to_string reads everything at once; split_ascii_whitespace then borrows from it"
+    );
+    detail!("One allocation for the whole input, but the input has to fit in memory twice over");
+    deep!("BufReader::with_capacity(1 << 16, file) reads a file in 64 KiB blocks instead of 8 KiB");
+    println!();
+}
+
+fn token_scanner() {
+    println!("3. A Token Scanner:");
+    println!("===================");
+
+    let input = "3 apples\n-7 2.5\n";
+    let mut scanner = Scanner::new(input.as_bytes());
+    let count = scanner.int().expect("an integer");
+    let fruit: String = scanner.parse().expect("a word");
+    let delta = scanner.int().expect("an integer");
+    let weight: f64 = scanner.parse().expect("a float");
+    println!("input: {:?}", input);
+    println!("int() = {}, parse::<String>() = {:?}", count, fruit);
+    println!("int() = {}, parse::<f64>() = {}", delta, weight);
+    explain!("rust_learn_utils::io::Scanner reads whitespace-separated tokens from any BufRead");
+    explain!("Lines do not matter: tokens are read the same across spaces, tabs and newlines");
+
+    let mut short = Scanner::new("1".as_bytes());
+    short.int().expect("one number");
+    match short.int() {
+        Ok(number) => println!("unexpected: {}", number),
+        Err(error) => println!("reading past the end: {:?}: {}", error.kind(), error),
+    }
+    explain!("Running out of input or a bad number is an io::Error, so ? passes it on");
+    detail!("token() returns a &[u8] into the scanner's one reusable buffer");
+    deep!("A token split across two fills of the reader's buffer is joined in that Vec");
+    println!();
+}
+
+fn parsing_bytes() {
+    println!("4. Parsing Integers From Bytes:");
+    println!("===============================");
+
+    let mut scanner = Scanner::new("-9223372036854775808 9223372036854775808".as_bytes());
+    println!("int() on i64::MIN   -> {:?}", scanner.int().ok());
+    let too_big = scanner.int();
+    println!(
+        "int() on i64::MAX+1 -> {}",
+        too_big.map_or_else(|error| error.to_string(), |number| number.to_string())
+    );
+    explain!("int() skips &str entirely: value = value * 10 - (byte - b'0') for each digit");
+    explain!("Counting downwards lets i64::MIN parse, since i64 reaches one further below zero");
+    detail!("checked_mul and checked_sub turn overflow into an error instead of a wrong number");
+    detail!("str::parse does the same work, plus a UTF-8 check on the token first");
+    println!();
+}
+
+/// One way of reading the benchmark input
+struct Way {
+    name: &'static str,
+    read: fn(&[u8]) -> io::Result<Summary>,
+}
+
+const WAYS: [Way; 3] = [
+    Way {
+        name: "read_line + parse",
+        read: |input| read_line_sum(input),
+    },
+    Way {
+        name: "read_to_string + split",
+        read: |input| read_all_sum(input),
+    },
+    Way {
+        name: "Scanner::int",
+        read: |input| scanner_sum(input),
+    },
+];
+
+fn benchmark() {
+    println!("5. Benchmark: A Million Numbers Three Ways:");
+    println!("===========================================\n");
+
+    let input = generate(COUNT);
+    let megabytes = input.len() as f64 / 1_000_000.0;
+    println!(
+        "input: {} numbers on their own lines, {:.1} MB, in memory",
+        COUNT, megabytes
+    );
+    println!("build: {} profile\n", BUILD_PROFILE);
+
+    println!(
+        "{:<24} {:>10} {:>10} {:>10}  SUM",
+        "WAY", "TIME", "MB/s", "PEAK HEAP"
+    );
+    let mut first: Option<(Duration, Summary)> = None;
+    for way in &WAYS {
+        let start = Instant::now();
+        let (summary, heap) = alloc_counter::peak(|| (way.read)(&input));
+        let summary = summary.expect("the generated input is well formed");
+        let took = start.elapsed();
+        let heap = match alloc_counter::inner_name() {
+            Some(_) => format_bytes(heap as u64),
+            None => "-".to_string(),
+        };
+        println!(
+            "{:<24} {:>10} {:>10.0} {:>10}  {}",
+            way.name,
+            format_duration(took),
+            megabytes / took.as_secs_f64(),
+            heap,
+            summary.sum
+        );
+        match &first {
+            Some((_, expected)) => assert_eq!(&summary, expected, "{} disagrees", way.name),
+            None => first = Some((took, summary)),
+        }
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- All three agree on the sum; they differ in how much work each number costs");
+    explain!("- read_line pays an allocation and a UTF-8 check per line; the others do not");
+    explain!("- read_to_string keeps up with Scanner, but holds the whole input in memory:");
+    explain!("  its PEAK HEAP is the size of the input, where Scanner only holds one token");
+    explain!("  (and, reading stdin, an 8 KiB buffer), however long the input is");
+    detail!("- The input is already in memory here: reading from a pipe adds the same to each");
+    detail!("- PEAK HEAP counts what each way allocated on top of that, at its highest point");
+    detail!("- std ships optimized even in a dev build, so there its parse beats Scanner's loop");
+    detail!("- Run `cargo run --release -- fast_input benchmark` for the numbers that matter");
+    println!();
+}
+
+/// Counts the writes that reach it: a stand-in for system calls
+struct CountingWriter {
+    writes: usize,
+    bytes: usize,
+}
+
+impl Write for CountingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writes += 1;
+        self.bytes += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// One number per line to `out`
+fn write_lines(out: &mut impl Write, count: usize) -> io::Result<()> {
+    for number in 0..count {
+        writeln!(out, "{}", number * 37 % 1_000_000)?;
+    }
+    Ok(())
+}
+
+fn buffered_output() {
+    println!("6. Writing Output in Bulk:");
+    println!("==========================");
+
+    let lines = 100_000;
+    let mut direct = CountingWriter {
+        writes: 0,
+        bytes: 0,
+    };
+    write_lines(&mut direct, lines).expect("counting cannot fail");
+
+    let mut counted = CountingWriter {
+        writes: 0,
+        bytes: 0,
+    };
+    let mut buffered = BufWriter::new(&mut counted);
+    write_lines(&mut buffered, lines).expect("counting cannot fail");
+    buffered.flush().expect("counting cannot fail");
+    drop(buffered);
+
+    println!(
+        "writeln! straight to the writer: {:>7} writes for {} bytes",
+        direct.writes, direct.bytes
+    );
+    println!(
+        "writeln! through a BufWriter:    {:>7} writes for {} bytes",
+        counted.writes, counted.bytes
+    );
+    explain!("Each writeln! hands its pieces on one by one: the number, then the newline");
+    explain!("On stdout each of those can be a system call; BufWriter makes one per 8 KiB");
+    detail!("println! locks stdout for every line, and stdout flushes at every newline");
+    detail!(
+        "`let mut out = BufWriter::new(io::stdout().lock());` then writeln!(out, ...) fixes both"
+    );
+    deep!(
+        "A BufWriter flushes when dropped, but any error then is lost; flush() explicitly to see it"
+    );
+    println!();
+}
+
+/// The shape of a contest solution: all input through one Scanner, all
+/// output through one buffered writer
+fn solve(input: impl BufRead, out: &mut impl Write) -> io::Result<()> {
+    let mut scanner = Scanner::new(input);
+    let cases = scanner.int()?;
+    for _ in 0..cases {
+        let count = scanner.int()? as usize;
+        let numbers = scanner.ints(count)?;
+        let largest = numbers.iter().max().copied().unwrap_or(0);
+        writeln!(out, "{}", largest)?;
+    }
+    Ok(())
+}
+
+fn contest_template() {
+    println!("7. A Contest Template:");
+    println!("======================");
+
+    explain!("fn main() -> io::Result<()> {{");
+    explain!("    let stdin = io::stdin().lock();");
+    explain!("    let mut out = BufWriter::new(io::stdout().lock());");
+    explain!("    solve(stdin, &mut out)?;");
+    explain!("    out.flush()");
+    explain!("}}");
+
+    let input = "2\n3\n4 -1 9\n2\n-5 -3\n";
+    let mut output = Vec::new();
+    solve(input.as_bytes(), &mut output).expect("the input is well formed");
+    println!("\nsolve on {:?}", input);
+    println!("wrote {:?}", String::from_utf8_lossy(&output));
+    explain!("solve takes any BufRead and any Write, so a test feeds it bytes and reads a Vec");
+    explain!(
+        "The interview problems with large inputs start from this shape (`rust-learn interview`)"
+    );
+    detail!(
+        "Contest judges often give 1-2 seconds: with this template the input is rarely the problem"
+    );
+    println!();
+}
@@ -0,0 +1,369 @@
+/// FFI in Rust - A Library C Can Call
+///
+/// The rust-learn-ffi crate wraps the LRU cache from rust-learn-utils in
+/// `extern "C"` functions and is built as a `cdylib`. Its build.rs runs cbindgen
+/// to write the C header. This lesson shows the exported API and the generated
+/// header, then builds the library, compiles a small C program against it and
+/// runs it - all through the toolchain module.
+use crate::generated::BUILD_PROFILE;
+use crate::registry::{Question, Section, section};
+use rust_learn_core::toolchain::{self, CommandOutput};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn ffi() {
+    println!("=== FFI Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- ffi <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(extern_c_functions, "extern \"C\" Functions"),
+    section!(cdylib_crates, "The cdylib Crate Type"),
+    section!(cbindgen_headers, "Generating the Header With cbindgen"),
+    section!(calling_from_c, "Calling the Library From C"),
+    section!(ffi_safety, "Rules at the Boundary"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "extern_c_functions",
+        prompt: "What does `extern \"C\"` on a Rust function change?",
+        choices: &[
+            "It uses the C calling convention, so C code can call it",
+            "It makes the function unsafe",
+            "It compiles it with a C compiler",
+            "It exports it under its plain name",
+        ],
+        explanation: "The calling convention decides how arguments and results are passed; #[unsafe(no_mangle)] is what keeps the plain name.",
+    },
+    Question {
+        section: "cdylib_crates",
+        prompt: "What does `crate-type = [\"cdylib\"]` build?",
+        choices: &[
+            "A shared library (.so, .dylib or .dll) for C to link against",
+            "A Rust library for other crates",
+            "A static executable",
+            "A WebAssembly module",
+        ],
+        explanation: "A cdylib exports only the C interface; an rlib is what other Rust crates link to.",
+    },
+    Question {
+        section: "ffi_safety",
+        prompt: "What should happen to a panic inside a function that C calls?",
+        choices: &[
+            "It must be caught with catch_unwind before it reaches C",
+            "It passes through to C as an exception",
+            "Nothing; C ignores it",
+            "It turns into a return value by itself",
+        ],
+        explanation: "A panic cannot unwind into C code; catch it at the boundary and return an error code instead.",
+    },
+];
+
+fn extern_c_functions() {
+    println!("1. extern \"C\" Functions:");
+    println!("=========================\n");
+
+    explain!("ONE EXPORTED FUNCTION:");
+    explain!("======================");
+    explain!("#[unsafe(no_mangle)]");
+    explain!("pub extern \"C\" fn rl_lru_new(capacity: usize) -> *mut RlLru {{");
+    explain!("    let lru = RlLru {{ cache: LruCache::new(capacity) }};");
+    explain!("    Box::into_raw(Box::new(lru))");
+    explain!("}}");
+
+    explain!("\nWHAT EACH PART DOES:");
+    explain!("====================");
+    explain!("extern \"C\"           - use the C calling convention");
+    explain!("#[unsafe(no_mangle)] - export the plain name `rl_lru_new`, not a mangled one");
+    explain!("*mut RlLru            - C gets a pointer to a type it cannot look inside");
+    explain!("Box::into_raw         - Rust stops managing the memory; C now owns it");
+
+    println!("\nTHE WHOLE API (from the generated header):");
+    println!("==========================================");
+    for line in rust_learn_ffi::HEADER
+        .lines()
+        .filter(|line| line.contains("rl_lru_") && line.ends_with(';'))
+    {
+        println!("  {}", line);
+    }
+
+    println!();
+}
+
+fn cdylib_crates() {
+    println!("2. The cdylib Crate Type:");
+    println!("=========================\n");
+
+    explain!("crates/rust-learn-ffi/Cargo.toml:");
+    explain!("  [lib]");
+    explain!("  crate-type = [\"cdylib\", \"rlib\"]");
+
+    explain!("\nCRATE TYPES:");
+    explain!("============");
+    explain!("rlib       - the default: a Rust library other Rust crates use");
+    explain!("cdylib     - a shared library for other languages (.so, .dylib, .dll)");
+    explain!("staticlib  - a static archive (.a, .lib) linked into a C program");
+    explain!("dylib      - a Rust shared library; rarely what you want");
+
+    println!("\nON THIS PLATFORM:");
+    println!("=================");
+    println!(
+        "cargo build -p rust-learn-ffi writes {}rust_learn_ffi{}",
+        std::env::consts::DLL_PREFIX,
+        std::env::consts::DLL_SUFFIX
+    );
+    explain!("The rlib is there too, so this lesson can read the header from Rust.");
+
+    println!();
+}
+
+fn cbindgen_headers() {
+    println!("3. Generating the Header With cbindgen:");
+    println!("=======================================\n");
+
+    explain!("crates/rust-learn-ffi/build.rs:");
+    explain!("  cbindgen::Builder::new()");
+    explain!("      .with_src(manifest_dir.join(\"src/lib.rs\"))");
+    explain!("      .with_language(cbindgen::Language::C)");
+    explain!("      .with_include_guard(\"RUST_LEARN_LRU_H\")");
+    explain!("      .generate()?");
+    explain!("      .write_to_file(out_dir.join(\"rust_learn_lru.h\"));");
+
+    println!(
+        "\nTHE RESULT ({}, doc comments left out):",
+        rust_learn_ffi::HEADER_NAME
+    );
+    println!("=====================================================");
+    let mut in_comment = false;
+    for line in rust_learn_ffi::HEADER.lines() {
+        if line.starts_with("/**") {
+            in_comment = true;
+        }
+        if !in_comment && !line.is_empty() {
+            println!("  {}", line);
+        }
+        if line.ends_with("*/") {
+            in_comment = false;
+        }
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- `///` doc comments become /** */ comments in the header");
+    explain!("- usize becomes uintptr_t, i64 int64_t, bool bool, *const c_char const char *");
+    explain!("- RlLru is not #[repr(C)], so C only gets `typedef struct RlLru RlLru;`");
+    explain!("- Generated on every build, the header cannot drift from the code");
+
+    println!();
+}
+
+fn calling_from_c() {
+    println!("4. Calling the Library From C:");
+    println!("==============================\n");
+
+    let Some(sandbox) = CSandbox::create() else {
+        return;
+    };
+
+    println!("STEP 1 - Build the cdylib:");
+    println!("--------------------------");
+    let Some(library) = sandbox.build_library() else {
+        return;
+    };
+    println!("built {}", library.display());
+
+    println!("\nSTEP 2 - Compile the C program:");
+    println!("-------------------------------");
+    let Some(program) = sandbox.compile(&library) else {
+        return;
+    };
+
+    println!("\nSTEP 3 - Run it:");
+    println!("----------------");
+    let output = toolchain::run(&program, &sandbox.root, &[]).expect("Failed to run the C program");
+    for line in output.stdout.lines() {
+        println!("  {}", line);
+    }
+    println!(
+        "exit status: {}",
+        if output.success { "success" } else { "failure" }
+    );
+
+    println!();
+}
+
+/// A temp dir for the C program and its header, removed again when dropped
+struct CSandbox {
+    root: PathBuf,
+    workspace: PathBuf,
+}
+
+impl CSandbox {
+    /// `None` (after explaining why) without cargo, a C compiler, or the source tree
+    fn create() -> Option<CSandbox> {
+        match (toolchain::cargo_version(), toolchain::cc_version()) {
+            (Some(cargo), Some(cc)) => println!("Using {}\nUsing {}\n", cargo, cc),
+            _ => {
+                println!("This section needs both cargo and a C compiler (cc, or $CC).");
+                println!("Install them and run the lesson again.\n");
+                return None;
+            }
+        }
+
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+        if !workspace.join("crates/rust-learn-ffi").is_dir() {
+            println!("The rust-learn source tree is not next to this binary, so the");
+            println!("library cannot be built. Run the lesson from a checkout.\n");
+            return None;
+        }
+
+        let root = std::env::temp_dir().join(format!("rust-learn-ffi-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("Failed to create sandbox dir");
+        Some(CSandbox { root, workspace })
+    }
+
+    /// `cargo build -p rust-learn-ffi`, returning the shared library's path
+    fn build_library(&self) -> Option<PathBuf> {
+        let mut args = vec!["build", "-p", "rust-learn-ffi", "--message-format=json"];
+        if BUILD_PROFILE == "release" {
+            args.push("--release");
+        }
+        println!("cargo {}", args.join(" "));
+
+        let output = toolchain::cargo(&self.workspace, &args).expect("Failed to run cargo");
+        let library = output
+            .success
+            .then(|| cdylib_path(&output.stdout))
+            .flatten();
+        if library.is_none() {
+            report_failure("cargo build", &output);
+        }
+        library
+    }
+
+    /// Write the header and C source, then compile and link them
+    fn compile(&self, library: &Path) -> Option<PathBuf> {
+        fs::write(
+            self.root.join(rust_learn_ffi::HEADER_NAME),
+            rust_learn_ffi::HEADER,
+        )
+        .expect("Failed to write the header");
+        fs::write(self.root.join("lru_test.c"), rust_learn_ffi::C_TEST)
+            .expect("Failed to write the C program");
+
+        let lib_dir = library.parent()?.to_str()?;
+        let rpath = format!("-Wl,-rpath,{}", lib_dir);
+        let args = [
+            "lru_test.c",
+            "-I.",
+            "-L",
+            lib_dir,
+            "-lrust_learn_ffi",
+            &rpath,
+            "-o",
+            "lru_test",
+        ];
+        println!("cc {}", args.join(" "));
+
+        let output = toolchain::cc(&self.root, &args).expect("Failed to run the C compiler");
+        if !output.success {
+            report_failure("cc", &output);
+            return None;
+        }
+        println!("cc: OK");
+        Some(self.root.join("lru_test"))
+    }
+}
+
+impl Drop for CSandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn report_failure(what: &str, output: &CommandOutput) {
+    println!("{}: FAILED", what);
+    for line in output
+        .stderr
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(6)
+    {
+        println!("  | {}", line);
+    }
+}
+
+/// The shared library among the artifacts cargo reported with
+/// `--message-format=json` (one JSON object per line)
+fn cdylib_path(messages: &str) -> Option<PathBuf> {
+    let suffix = std::env::consts::DLL_SUFFIX;
+    messages
+        .lines()
+        .filter(|line| line.contains("\"reason\":\"compiler-artifact\""))
+        .filter(|line| line.contains("\"name\":\"rust_learn_ffi\""))
+        .find_map(|line| {
+            let (_, filenames) = line.split_once("\"filenames\":[")?;
+            let (filenames, _) = filenames.split_once(']')?;
+            filenames
+                .split(',')
+                .map(|name| name.trim_matches('"'))
+                .find(|name| name.ends_with(suffix))
+                .map(|name| PathBuf::from(name.replace("\\\\", "\\")))
+        })
+}
+
+fn ffi_safety() {
+    println!("5. Rules at the Boundary:");
+    println!("=========================\n");
+
+    explain!("OWNERSHIP:");
+    explain!("==========");
+    explain!("- Memory is freed by the side that allocated it: rl_lru_new pairs with rl_lru_free");
+    explain!("- Box::into_raw hands a value out; Box::from_raw takes it back exactly once");
+    explain!("- Keys are copied in, so C may free its strings right after the call");
+
+    explain!("\nTRUST NOTHING:");
+    explain!("==============");
+    explain!("- Check every pointer for NULL (ptr.as_mut() returns an Option)");
+    explain!("- Functions that dereference raw pointers are `unsafe extern \"C\"`,");
+    explain!("  with a # Safety section saying what the caller must guarantee");
+    explain!("- CStr::from_ptr reads up to the NUL; a missing NUL is undefined behavior");
+
+    explain!("\nPANICS:");
+    explain!("=======");
+    explain!("A panic may not unwind into C. Since Rust 1.81 an `extern \"C\"` function");
+    explain!("that panics aborts the process instead, so avoid unwrap() in exported code.");
+    explain!("Hidden panics count too: Vec::with_capacity(SIZE_MAX) panics on capacity");
+    explain!("overflow, so rl_lru_new reserves nothing and takes memory as entries come.");
+
+    explain!("\nSHARED STRUCTS:");
+    explain!("===============");
+    explain!("Types whose fields C reads must be #[repr(C)]; opaque handles, like");
+    explain!("RlLru, need not be, and can change without breaking C callers.");
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cdylib_path_from_cargo_messages() {
+        let suffix = std::env::consts::DLL_SUFFIX;
+        let messages = format!(
+            "{{\"reason\":\"compiler-artifact\",\"target\":{{\"name\":\"rust_learn_utils\"}},\"filenames\":[\"/t/librust_learn_utils.rlib\"]}}\n\
+             {{\"reason\":\"compiler-artifact\",\"target\":{{\"name\":\"rust_learn_ffi\"}},\"filenames\":[\"/t/librust_learn_ffi{suffix}\",\"/t/librust_learn_ffi.rlib\"]}}\n\
+             {{\"reason\":\"build-finished\",\"success\":true}}"
+        );
+        let expected = format!("/t/librust_learn_ffi{}", suffix);
+        assert_eq!(cdylib_path(&messages), Some(PathBuf::from(expected)));
+        assert_eq!(cdylib_path("{\"reason\":\"build-finished\"}"), None);
+    }
+}
@@ -0,0 +1,24 @@
+//! Metadata generated at compile time by build.rs
+
+/// One lesson file, as build.rs saw it
+pub struct LessonSource {
+    pub file: &'static str,
+    pub lines: usize,
+    /// Entries in the file's SECTIONS table
+    pub sections: usize,
+}
+
+include!(concat!(env!("OUT_DIR"), "/lessons_generated.rs"));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::LESSONS;
+
+    #[test]
+    fn test_generated_section_counts_match_registry() {
+        let generated: usize = LESSON_SOURCES.iter().map(|source| source.sections).sum();
+        let registered: usize = LESSONS.iter().map(|lesson| lesson.sections.len()).sum();
+        assert_eq!(generated, registered);
+    }
+}
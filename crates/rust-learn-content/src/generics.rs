@@ -0,0 +1,416 @@
+/// Generics in Rust - One Definition for Many Types
+///
+/// A generic function, struct or enum is written once with type parameters
+/// and used with many concrete types. Trait bounds say what a type parameter
+/// must be able to do, a turbofish names the type when the compiler cannot
+/// infer it, and monomorphization turns each use into its own specialized
+/// copy, so generic code costs nothing at runtime.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::any::type_name;
+use std::fmt::{self, Debug, Display};
+use std::mem::size_of;
+
+pub fn generics() {
+    println!("=== Generics Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- generics <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(removing_duplication, "Removing Duplication"),
+    section!(largest_walkthrough, "The largest Walkthrough").with_checkpoint(
+        "Does `fn largest<T>(list: &[T]) -> &T` compile if its body compares two items with `>`?",
+        false,
+        "Without a bound T could be any type, including ones that cannot be compared; `T: PartialOrd` promises `>` works.",
+    ),
+    section!(generic_structs, "Generic Structs"),
+    section!(multiple_type_parameters, "Multiple Type Parameters"),
+    section!(generic_enums, "Generic Enums"),
+    section!(where_clauses, "where Clauses"),
+    section!(turbofish, "The Turbofish ::<>"),
+    section!(monomorphization, "Monomorphization").with_checkpoint(
+        "Does calling a generic function cost more at runtime than calling a hand-written one for the same type?",
+        false,
+        "The compiler makes a copy of the function for each type it is used with, so the call is as direct as a hand-written one.",
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "largest_walkthrough",
+        prompt: "Why does `largest<T: PartialOrd>(list: &[T]) -> T` fail to compile when it returns `list[0]`?",
+        choices: &[
+            "T might not be Copy, so it cannot be moved out of the slice",
+            "PartialOrd cannot compare slices",
+            "Generic functions cannot return T",
+            "list[0] panics on an empty slice",
+        ],
+        explanation: "Returning &T borrows instead; or add a `Copy` bound so the value is copied out.",
+    },
+    Question {
+        section: "multiple_type_parameters",
+        prompt: "With `struct Point<T> { x: T, y: T }`, what is `Point { x: 5, y: 4.0 }`?",
+        choices: &[
+            "A compile error: x and y must be the same T",
+            "A Point<f64>, with 5 converted",
+            "A Point<i32>, with 4.0 truncated",
+            "A Point<T> where T is decided later",
+        ],
+        explanation: "One type parameter means one type for both fields; `Point<T, U>` allows two different ones.",
+    },
+    Question {
+        section: "turbofish",
+        prompt: "Which line compiles?",
+        choices: &[
+            "let n = \"42\".parse::<u8>().unwrap();",
+            "let n = \"42\".parse().unwrap();",
+            "let n = \"42\".parse<u8>().unwrap();",
+            "let n = \"42\".parse(u8).unwrap();",
+        ],
+        explanation: "parse is generic over its return type; with nothing to infer it from, name it with the turbofish ::<>.",
+    },
+    Question {
+        section: "monomorphization",
+        prompt: "A generic function is called with i32, f64 and i32 again. How many copies of it are in the binary?",
+        choices: &["Two", "One", "Three", "None: it is interpreted"],
+        explanation: "One copy per distinct type it is used with: largest::<i32> and largest::<f64>.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_sizes)];
+
+fn largest_i32(list: &[i32]) -> &i32 {
+    let mut largest = &list[0];
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+fn largest_char(list: &[char]) -> &char {
+    let mut largest = &list[0];
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+/// One body for every T that can be compared
+fn largest<T: PartialOrd>(list: &[T]) -> &T {
+    let mut largest = &list[0];
+    for item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+/// Returns the value instead of a reference, so T must be Copy
+fn largest_copy<T: PartialOrd + Copy>(list: &[T]) -> T {
+    let mut largest = list[0];
+    for &item in list {
+        if item > largest {
+            largest = item;
+        }
+    }
+    largest
+}
+
+fn removing_duplication() {
+    println!("1. Removing Duplication:");
+    println!("========================");
+
+    let numbers = vec![34, 50, 25, 100, 65];
+    let chars = vec!['y', 'm', 'a', 'q'];
+    println!("largest_i32({:?})  = {}", numbers, largest_i32(&numbers));
+    println!("largest_char({:?}) = {}", chars, largest_char(&chars));
+    explain!("The two functions have the same body; only the type in the signature differs");
+    explain!(
+        "A generic function writes that body once, with a type parameter T in place of i32 or char"
+    );
+    detail!(
+        "Same move as turning repeated code into a function, one level up: over types instead of values"
+    );
+    println!();
+}
+
+fn largest_walkthrough() {
+    println!("2. The largest Walkthrough:");
+    println!("===========================");
+
+    explain!("STEP 1: name the type parameter");
+    explain!("    fn largest<T>(list: &[T]) -> &T");
+    // fn largest<T>(list: &[T]) -> &T { ... if item > largest ... }
+    //     // COMPILE ERROR: binary operation `>` cannot be applied to type `&T` (E0369)
+    explain!("STEP 2: the body uses `>`, which not every T has: error E0369");
+    explain!("STEP 3: ask for it with a bound");
+    explain!("    fn largest<T: PartialOrd>(list: &[T]) -> &T");
+
+    let numbers = vec![34, 50, 25, 100, 65];
+    let chars = vec!['y', 'm', 'a', 'q'];
+    let words = vec!["pear", "apple", "zucchini", "fig"];
+    println!("largest(&{:?}) = {}", numbers, largest(&numbers));
+    println!("largest(&{:?}) = {}", chars, largest(&chars));
+    println!("largest(&{:?}) = {}", words, largest(&words));
+    explain!("One function, three element types; each call infers T from the slice");
+
+    let floats = [1.5, -2.0, 0.25];
+    println!("largest_copy(&{:?}) = {}", floats, largest_copy(&floats));
+    // fn largest<T: PartialOrd>(list: &[T]) -> T { let mut largest = list[0]; ... }
+    //     // COMPILE ERROR: cannot move out of type `[T]`, a non-copy slice
+    explain!("Returning T instead of &T moves an item out of the slice: add `Copy` to the bound");
+    detail!("Returning &T works for any T, Strings included, and copies nothing");
+    println!();
+}
+
+#[derive(Debug)]
+struct Point<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Point<T> {
+    /// For every Point<T>
+    fn x(&self) -> &T {
+        &self.x
+    }
+}
+
+impl Point<f32> {
+    /// Only for Point<f32>: the others have no sqrt
+    fn distance_from_origin(&self) -> f32 {
+        (self.x.powi(2) + self.y.powi(2)).sqrt()
+    }
+}
+
+fn generic_structs() {
+    println!("3. Generic Structs:");
+    println!("===================");
+
+    let integer = Point { x: 5, y: 10 };
+    let float = Point { x: 3.0_f32, y: 4.0 };
+    println!("integer = {:?}, float = {:?}", integer, float);
+    explain!(
+        "`struct Point<T> {{ x: T, y: T }}` holds two values of the same type, whichever it is"
+    );
+
+    // let wont_work = Point { x: 5, y: 4.0 };  // COMPILE ERROR: expected integer, found floating-point number
+    println!("integer.x() = {}", integer.x());
+    println!(
+        "float.distance_from_origin() = {}",
+        float.distance_from_origin()
+    );
+    explain!("`impl<T> Point<T>` gives every Point the x method");
+    explain!("`impl Point<f32>` adds distance_from_origin to Point<f32> alone");
+    detail!(
+        "The <T> after impl declares T; without it, Point<T> would mean a concrete type named T"
+    );
+    println!();
+}
+
+#[derive(Debug)]
+struct Pair<X, Y> {
+    x: X,
+    y: Y,
+}
+
+impl<X1, Y1> Pair<X1, Y1> {
+    /// Takes x from self and y from other: the method has type parameters
+    /// of its own
+    fn mixup<X2, Y2>(self, other: Pair<X2, Y2>) -> Pair<X1, Y2> {
+        Pair {
+            x: self.x,
+            y: other.y,
+        }
+    }
+}
+
+fn multiple_type_parameters() {
+    println!("4. Multiple Type Parameters:");
+    println!("============================");
+
+    let mixed = Pair { x: 5, y: 4.0 };
+    println!("Pair {{ x: 5, y: 4.0 }} = {:?}", mixed);
+    explain!("`struct Pair<X, Y>` lets x and y be different types, or the same");
+
+    let first = Pair { x: 5, y: 10.4 };
+    let second = Pair { x: "Hello", y: 'c' };
+    let third = first.mixup(second);
+    println!("Pair<i32, f64>.mixup(Pair<&str, char>) = {:?}", third);
+    explain!(
+        "X1 and Y1 come with the struct, X2 and Y2 with the method: the result is Pair<i32, char>"
+    );
+    detail!("More than a few type parameters usually means the type wants splitting up");
+    println!();
+}
+
+/// Like Option<T>, to show there is nothing built in about it
+#[derive(Debug)]
+enum Maybe<T> {
+    Just(T),
+    Nothing,
+}
+
+fn first_even(numbers: &[i32]) -> Maybe<i32> {
+    match numbers.iter().find(|n| *n % 2 == 0) {
+        Some(&n) => Maybe::Just(n),
+        None => Maybe::Nothing,
+    }
+}
+
+fn generic_enums() {
+    println!("5. Generic Enums:");
+    println!("=================");
+
+    println!("first_even(&[3, 8, 5]) = {:?}", first_even(&[3, 8, 5]));
+    println!("first_even(&[1, 3])    = {:?}", first_even(&[1, 3]));
+    let word: Maybe<&str> = Maybe::Just("generic");
+    println!("Maybe<&str>            = {:?}", word);
+    explain!("`enum Maybe<T> {{ Just(T), Nothing }}` is Option<T> under another name");
+
+    let parsed: Result<u8, String> = "300".parse::<u8>().map_err(|error| error.to_string());
+    println!("Result<u8, String>     = {:?}", parsed);
+    explain!("Result<T, E> has two type parameters: one for success, one for the error");
+    detail!("Option and Result are ordinary generic enums from the standard library");
+    println!();
+}
+
+/// The bounds move after the signature, one line per type parameter
+fn summarize<T, U>(label: T, values: &[U]) -> String
+where
+    T: Display,
+    U: Debug + PartialOrd,
+{
+    let top = if values.is_empty() {
+        String::from("none")
+    } else {
+        format!("{:?}", largest(values))
+    };
+    format!("{}: {} values, largest {}", label, values.len(), top)
+}
+
+fn where_clauses() {
+    println!("6. where Clauses:");
+    println!("=================");
+
+    println!("{}", summarize("scores", &[72, 91, 85]));
+    println!(
+        "{}",
+        summarize(String::from("names"), &["Ana", "Zoe", "Li"])
+    );
+    explain!("`fn summarize<T: Display, U: Debug + PartialOrd>(...)` crowds the signature");
+    explain!("A `where` clause lists the same bounds after it, one per line");
+    detail!("where can also bound types that are not parameters, like `Vec<T>: Debug`");
+    println!();
+}
+
+/// Nothing to infer T from except what the caller says
+fn zero_sized<T>() -> usize {
+    size_of::<T>()
+}
+
+fn turbofish() {
+    println!("7. The Turbofish ::<>:");
+    println!("======================");
+
+    // let n = "42".parse().unwrap();  // COMPILE ERROR: type annotations needed
+    let n = "42".parse::<u8>().unwrap();
+    let m: i64 = "-42".parse().unwrap();
+    println!(
+        "\"42\".parse::<u8>() = {}, \"-42\" parsed as i64 = {}",
+        n, m
+    );
+    explain!(
+        "parse is generic over what it returns; name it with ::<u8>, or annotate the variable"
+    );
+
+    let squares = (1..=4).map(|x| x * x).collect::<Vec<u32>>();
+    let letters = "rust".chars().rev().collect::<String>();
+    println!("collect::<Vec<u32>>() = {:?}", squares);
+    println!("collect::<String>()   = {:?}", letters);
+    explain!("collect can build many collections: the turbofish picks one");
+
+    println!("zero_sized::<u64>() = {}", zero_sized::<u64>());
+    println!(
+        "largest::<f64>(&[0.5, 2.5]) = {}",
+        largest::<f64>(&[0.5, 2.5])
+    );
+    explain!("Any generic function can be called with its types spelled out: largest::<f64>(...)");
+    detail!("`Vec::<i32>::new()` is the same thing on a type's associated function");
+    deep!("It is called the turbofish because ::<> looks like a fish swimming fast");
+    println!();
+}
+
+/// Which T this copy of the function was made for
+fn describe<T: Debug>(value: T) -> String {
+    format!(
+        "{:?} as {} ({} bytes)",
+        value,
+        type_name::<T>(),
+        size_of::<T>()
+    )
+}
+
+/// Wrapper showing that one generic struct becomes one type per T
+struct Wrapper<T>(T);
+
+impl<T: Display> fmt::Display for Wrapper<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.0)
+    }
+}
+
+fn monomorphization() {
+    println!("8. Monomorphization:");
+    println!("====================");
+
+    println!("{}", describe(7_u8));
+    println!("{}", describe(7.0_f64));
+    println!("{}", describe("seven"));
+    explain!("Each call uses a different copy of describe, made for that T at compile time");
+
+    let for_i32: fn(&[i32]) -> &i32 = largest::<i32>;
+    let for_char: fn(&[char]) -> &char = largest::<char>;
+    let same = for_i32 as usize == for_char as usize;
+    println!(
+        "largest::<i32> and largest::<char> at the same address: {}",
+        same
+    );
+    explain!("They are two separate functions in the binary: largest::<i32> and largest::<char>");
+
+    println!(
+        "{} {}  size_of::<Wrapper<u8>>() = {}, size_of::<Wrapper<u64>>() = {}",
+        Wrapper(1_u8),
+        Wrapper("text"),
+        size_of::<Wrapper<u8>>(),
+        size_of::<Wrapper<u64>>()
+    );
+    explain!(
+        "Generic types are specialized the same way: Wrapper<u8> and Wrapper<u64> differ in size"
+    );
+    detail!(
+        "So generic code runs as fast as hand-written code, at the cost of binary size and build time"
+    );
+    deep!(
+        "Trait objects (dyn Trait) make the opposite trade: one copy, with a lookup at each call"
+    );
+    println!();
+}
+
+fn size<T>(_: T) -> usize {
+    size_of::<T>()
+}
+
+fn predict_sizes() {
+    println!("{} {} {}", size(1_u8), size(1.0), size((1_u16, 'a')));
+}
@@ -0,0 +1,388 @@
+/// Interior Mutability in Rust - Cell and RefCell
+///
+/// The borrowing rules say a value is either shared (`&T`) or changed
+/// (`&mut T`), never both at once, and the compiler checks them. Some
+/// designs need to change a value behind a shared reference anyway: a cache
+/// filled in by a `&self` method, a counter of calls, one list owned by
+/// several parts of a program. `Cell` and `RefCell` allow that by moving the
+/// check: `Cell` never hands out a reference to its contents, and `RefCell`
+/// counts its borrows while the program runs. This lesson uses both, breaks
+/// the rule on purpose to catch a `BorrowMutError`, shares a `RefCell`
+/// through `Rc`, and ends with when all this is worth it.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::cell::{BorrowMutError, Cell, RefCell};
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::rc::Rc;
+
+pub fn interior_mutability() {
+    println!("=== Interior Mutability Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- interior_mutability <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(mutation_behind_shared, "Changing a Value Behind &self"),
+    section!(cells, "Cell: Copy Values In and Out"),
+    section!(refcells, "RefCell: Borrows Checked at Run Time").with_checkpoint(
+        "Does the compiler reject two overlapping borrow_mut() calls on one RefCell?",
+        false,
+        "RefCell moves the check to run time: the second borrow_mut() panics, and try_borrow_mut() returns Err(BorrowMutError).",
+    ),
+    section!(borrow_errors, "Catching a BorrowMutError"),
+    section!(shared_ownership, "Rc<RefCell<T>>: Shared and Changeable"),
+    section!(when_to_use, "When Interior Mutability Fits"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "cells",
+        prompt: "Why can Cell<T> hand out no &T to its contents?",
+        choices: &[
+            "A set() through another &Cell could change the value while the &T is in use",
+            "Cell stores its value on the heap",
+            "Cell only works with integers",
+            "Cell needs a lock for that",
+        ],
+        explanation: "Cell copies values in and out (get, set, replace, take), so no reference can see a change; that is why it costs nothing at run time.",
+    },
+    Question {
+        section: "refcells",
+        prompt: "A Ref from borrow() is alive when borrow_mut() is called on the same RefCell. What happens?",
+        choices: &[
+            "It panics with \"RefCell already borrowed\"",
+            "It does not compile",
+            "It waits until the Ref is dropped",
+            "It returns a copy of the value",
+        ],
+        explanation: "RefCell counts borrows at run time; try_borrow_mut() reports the same clash as an Err instead of a panic.",
+    },
+    Question {
+        section: "borrow_errors",
+        prompt: "A memoized fib holds self.cache.borrow_mut() while it calls itself. What goes wrong?",
+        choices: &[
+            "The inner call's borrow_mut() finds the cache already borrowed",
+            "The cache is cloned on every call",
+            "It is a compile error",
+            "Nothing: the borrows are on different calls",
+        ],
+        explanation: "A RefMut lives until it is dropped; end it (a block, drop, or a temporary) before calling anything that borrows the same cell.",
+    },
+    Question {
+        section: "shared_ownership",
+        prompt: "What does Rc<RefCell<Vec<String>>> give that Rc<Vec<String>> does not?",
+        choices: &[
+            "Every owner can push to the one Vec",
+            "Sharing between threads",
+            "Cheaper clones",
+            "A Vec that never allocates",
+        ],
+        explanation: "Rc only hands out &T; the RefCell inside allows changes through it, checked at run time.",
+    },
+    Question {
+        section: "when_to_use",
+        prompt: "Several threads share a counter. Which type fits?",
+        choices: &[
+            "AtomicU64, or a Mutex",
+            "Cell<u64>",
+            "RefCell<u64>",
+            "Rc<Cell<u64>>",
+        ],
+        explanation: "Cell and RefCell are not Sync; their thread-safe counterparts are the atomics, Mutex and RwLock.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_borrow_state)];
+
+/// A Fibonacci calculator that remembers what it worked out. Its methods
+/// take `&self`, so the cache and the counter are cells
+struct Fib {
+    cache: RefCell<HashMap<u64, u64>>,
+    calls: Cell<u32>,
+}
+
+impl Fib {
+    fn new() -> Fib {
+        Fib {
+            cache: RefCell::new(HashMap::new()),
+            calls: Cell::new(0),
+        }
+    }
+
+    /// Each borrow of the cache ends before the recursive calls
+    fn get(&self, n: u64) -> u64 {
+        self.calls.set(self.calls.get() + 1);
+        if n < 2 {
+            return n;
+        }
+        if let Some(&known) = self.cache.borrow().get(&n) {
+            return known;
+        }
+        let value = self.get(n - 1) + self.get(n - 2);
+        self.cache.borrow_mut().insert(n, value);
+        value
+    }
+
+    /// Holds the cache for the whole call, recursion included: the second
+    /// call finds it taken. `try_borrow_mut` reports that instead of
+    /// panicking
+    fn get_holding(&self, n: u64) -> Result<u64, BorrowMutError> {
+        self.calls.set(self.calls.get() + 1);
+        if n < 2 {
+            return Ok(n);
+        }
+        let mut cache = self.cache.try_borrow_mut()?;
+        if let Some(&known) = cache.get(&n) {
+            return Ok(known);
+        }
+        let value = self.get_holding(n - 1)? + self.get_holding(n - 2)?;
+        cache.insert(n, value);
+        Ok(value)
+    }
+}
+
+fn mutation_behind_shared() {
+    println!("1. Changing a Value Behind &self:");
+    println!("=================================");
+
+    let fib = Fib::new();
+    println!("fib.get(30) = {}", fib.get(30));
+    println!("calls: {}", fib.calls.get());
+    println!("cached: {} values", fib.cache.borrow().len());
+    explain!("get takes &self, yet it counts its calls and fills a cache");
+    explain!("A plain `calls: u32` would need &mut self:");
+    explain!("  self.calls += 1;  // COMPILE ERROR: E0594 cannot assign through `&` reference");
+    detail!("&mut self would work here, but then every caller needs a unique reference,");
+    detail!("  for what is, from the outside, a read-only question");
+    println!();
+}
+
+fn cells() {
+    println!("2. Cell: Copy Values In and Out:");
+    println!("================================");
+
+    let counter = Cell::new(1);
+    let shared_a = &counter;
+    let shared_b = &counter;
+    shared_a.set(shared_a.get() + 1);
+    shared_b.set(shared_b.get() * 10);
+    println!("two & references changed one Cell: {}", counter.get());
+
+    let old = counter.replace(7);
+    println!("replace(7) returned {}, now {}", old, counter.get());
+    let name = Cell::new(String::from("Ferris"));
+    let taken = name.take();
+    println!(
+        "take() on Cell<String>: {:?}, left {:?}",
+        taken,
+        name.take()
+    );
+
+    println!(
+        "size_of::<Cell<u32>>() = {}, the same as u32",
+        size_of::<Cell<u32>>()
+    );
+    explain!("Cell never lends out a reference to what it holds: get copies it out,");
+    explain!("  set copies it in. Nothing can watch the value change, so nothing checks");
+    explain!("get needs T: Copy; take (T: Default) and replace work for any T");
+    detail!("Cell is not Sync: two threads calling set at once would be a data race");
+    println!();
+}
+
+fn refcells() {
+    println!("3. RefCell: Borrows Checked at Run Time:");
+    println!("========================================");
+
+    let scores = RefCell::new(vec![70, 85]);
+    {
+        let first = scores.borrow();
+        let second = scores.borrow();
+        println!("two borrow()s at once: {:?} and {:?}", *first, *second);
+    }
+    scores.borrow_mut().push(92);
+    println!("after borrow_mut().push(92): {:?}", scores.borrow());
+
+    let reader = scores.borrow();
+    println!(
+        "while a Ref is alive: try_borrow() is_ok = {}, try_borrow_mut() is_ok = {}",
+        scores.try_borrow().is_ok(),
+        scores.try_borrow_mut().is_ok()
+    );
+    drop(reader);
+    println!(
+        "after drop(reader):   try_borrow_mut() is_ok = {}",
+        scores.try_borrow_mut().is_ok()
+    );
+    explain!("The same rules as & and &mut, counted while the program runs:");
+    explain!("  any number of Ref (borrow) or one RefMut (borrow_mut), never both");
+    explain!("Breaking them is not a compile error: borrow_mut() panics with");
+    explain!("  \"RefCell already borrowed\", try_borrow_mut() returns the Err");
+    detail!(
+        "The count is one isize next to the value: size_of::<RefCell<u32>>() = {}",
+        size_of::<RefCell<u32>>()
+    );
+    println!();
+}
+
+fn borrow_errors() {
+    println!("4. Catching a BorrowMutError:");
+    println!("=============================");
+
+    let fib = Fib::new();
+    match fib.get_holding(10) {
+        Ok(value) => println!("get_holding(10) = {}", value),
+        Err(error) => println!(
+            "get_holding(10) failed after {} calls: {} ({:?})",
+            fib.calls.get(),
+            error,
+            error
+        ),
+    }
+    explain!("get_holding keeps `cache` (a RefMut) until it returns, and calls itself");
+    explain!("  in between: the inner call's try_borrow_mut finds the cache taken");
+    explain!("With borrow_mut() that would be a panic, in code that compiled cleanly");
+
+    let fixed = Fib::new();
+    println!("get(10) = {} in {} calls", fixed.get(10), fixed.calls.get());
+    explain!("get borrows only for a lookup or an insert, with nothing called meanwhile");
+    detail!("Keep a RefCell borrow short: take it in a block or a single expression,");
+    detail!("  and never across a call that may reach the same cell (callbacks, recursion)");
+    println!();
+}
+
+/// A log that every part of a program writes to, through its own `Rc`
+#[derive(Clone)]
+struct Logger {
+    source: &'static str,
+    lines: Rc<RefCell<Vec<String>>>,
+}
+
+impl Logger {
+    fn log(&self, message: &str) {
+        self.lines
+            .borrow_mut()
+            .push(format!("[{}] {}", self.source, message));
+    }
+}
+
+fn shared_ownership() {
+    println!("5. Rc<RefCell<T>>: Shared and Changeable:");
+    println!("=========================================");
+
+    let lines = Rc::new(RefCell::new(Vec::new()));
+    let parser = Logger {
+        source: "parser",
+        lines: Rc::clone(&lines),
+    };
+    let checker = Logger {
+        source: "checker",
+        lines: Rc::clone(&lines),
+    };
+    parser.log("read 3 items");
+    checker.log("item 2 is out of range");
+    parser.log("done");
+    println!("Rc::strong_count(&lines) = {}", Rc::strong_count(&lines));
+    for line in lines.borrow().iter() {
+        println!("  {}", line);
+    }
+    drop(parser);
+    println!(
+        "after drop(parser): strong_count = {}",
+        Rc::strong_count(&lines)
+    );
+    explain!("Rc gives several owners of one value, but only & access to it;");
+    explain!("  RefCell inside turns that & into a checked &mut when needed");
+    explain!("Rc<RefCell<T>> is single-threaded; across threads it is Arc<Mutex<T>>");
+    detail!("Two Rc<RefCell<Node>> that point at each other are never freed: that is the");
+    detail!("  job of Weak, which points without owning");
+    println!();
+}
+
+fn when_to_use() {
+    println!("6. When Interior Mutability Fits:");
+    println!("=================================");
+
+    let rows = [
+        (
+            "Cell<T>",
+            "Copy values, counters, flags",
+            "no cost, not Sync",
+        ),
+        (
+            "RefCell<T>",
+            "caches, graphs, test doubles",
+            "borrow count, may panic",
+        ),
+        ("OnceCell<T>", "set once, read after", "no count after set"),
+        ("Mutex<T>", "any T, across threads", "a lock, may block"),
+        ("Atomic*", "integers across threads", "no lock"),
+    ];
+    println!("{:<12} {:<30} {}", "TYPE", "FOR", "COST");
+    for (name, use_for, cost) in rows {
+        println!("{:<12} {:<30} {}", name, use_for, cost);
+    }
+
+    explain!("\nGOOD REASONS:");
+    explain!("=============");
+    explain!("- A method is read-only to its callers but keeps a cache or statistics");
+    explain!("- Data with several owners that all need to change it (Rc<RefCell<T>>)");
+    explain!("- A test double that records the calls a trait's &self methods receive");
+
+    explain!("\nREASONS TO WAIT:");
+    explain!("================");
+    explain!("- &mut self would do: the compiler's check is free and cannot panic");
+    explain!("- The borrow checker complains about one function: restructure it first");
+    explain!("- RefCell everywhere turns compile errors into run-time panics");
+    println!();
+}
+
+// Helper functions
+
+fn predict_borrow_state() {
+    let cell = RefCell::new(5);
+    let first = cell.borrow();
+    println!("{}", cell.try_borrow().is_ok());
+    println!("{}", cell.try_borrow_mut().is_ok());
+    drop(first);
+    *cell.borrow_mut() += 1;
+    println!("{}", cell.borrow());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fib_cache_and_borrow_error() {
+        let fib = Fib::new();
+        assert_eq!(fib.get(30), 832_040);
+        assert_eq!(fib.cache.borrow().len(), 29);
+        let calls = fib.calls.get();
+        assert_eq!(fib.get(30), 832_040);
+        assert_eq!(fib.calls.get(), calls + 1);
+
+        let holding = Fib::new();
+        assert!(holding.get_holding(10).is_err());
+        assert_eq!(holding.get_holding(1).unwrap(), 1);
+        assert!(holding.cache.try_borrow_mut().is_ok());
+    }
+
+    #[test]
+    fn test_loggers_share_one_log() {
+        let lines = Rc::new(RefCell::new(Vec::new()));
+        let a = Logger {
+            source: "a",
+            lines: Rc::clone(&lines),
+        };
+        let b = a.clone();
+        a.log("one");
+        b.log("two");
+        assert_eq!(*lines.borrow(), ["[a] one", "[a] two"]);
+        assert_eq!(Rc::strong_count(&lines), 3);
+    }
+}
@@ -0,0 +1,308 @@
+/// Iterators in Rust - Processing a Series of Items Lazily
+///
+/// An iterator is anything with a `next` method that returns `Some(item)`
+/// until it runs out and then `None`. Implementing that one method for a
+/// custom `Counter` gives it every other method of the `Iterator` trait for
+/// free. Those methods come in two kinds: adapters like `map` and `filter`
+/// build a new iterator and do nothing yet, consumers like `sum` and
+/// `collect` call `next` until the end. `collect` can build many kinds of
+/// container, and a collection gives out its items by reference (`iter`),
+/// by mutable reference (`iter_mut`) or by value (`into_iter`).
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub fn iterators() {
+    println!("=== Iterators Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- iterators <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(iterator_trait, "The Iterator Trait"),
+    section!(custom_iterator, "A Custom Iterator: Counter"),
+    section!(adapters_and_consumers, "Adapters and Consumers").with_checkpoint(
+        "Does `numbers.iter().map(|n| n * 2);` on its own line double anything?",
+        false,
+        "map only builds an adapter; nothing runs until a consumer like sum, collect or a for loop calls next.",
+    ),
+    section!(laziness, "Laziness"),
+    section!(collecting, "Collecting Into Containers"),
+    section!(iter_iter_mut_into_iter, "iter, iter_mut and into_iter").with_checkpoint(
+        "After `for name in names.into_iter() { ... }`, can you still print `names`?",
+        false,
+        "into_iter takes the Vec by value: the loop owns the names and drops them as it finishes.",
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "custom_iterator",
+        prompt: "Which method must a type write itself to implement Iterator?",
+        choices: &["next", "next and len", "iter", "map, filter and collect"],
+        explanation: "Every other Iterator method has a default built on next; the impl also names `type Item`.",
+    },
+    Question {
+        section: "adapters_and_consumers",
+        prompt: "Which of these is a consumer rather than an adapter?",
+        choices: &["sum", "map", "filter", "zip"],
+        explanation: "Adapters return another iterator; consumers like sum, count, fold and collect use it up.",
+    },
+    Question {
+        section: "collecting",
+        prompt: "What does `[\"1\", \"x\", \"3\"].iter().map(|s| s.parse::<i32>()).collect::<Result<Vec<i32>, _>>()` give?",
+        choices: &[
+            "Err, from parsing \"x\"",
+            "Ok(vec![1, 3])",
+            "Ok(vec![1, 0, 3])",
+            "It does not compile",
+        ],
+        explanation: "Collecting into Result stops at the first Err and returns it; all Ok gives Ok of the Vec.",
+    },
+    Question {
+        section: "iter_iter_mut_into_iter",
+        prompt: "What does `for x in &v` call on `v: Vec<i32>`?",
+        choices: &[
+            "v.iter(), so x is &i32",
+            "v.into_iter(), so x is i32",
+            "v.iter_mut(), so x is &mut i32",
+            "Nothing: a reference cannot be looped over",
+        ],
+        explanation: "`&Vec<T>` implements IntoIterator by lending its items, the same as iter().",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_lazy_order)];
+
+fn iterator_trait() {
+    println!("1. The Iterator Trait:");
+    println!("======================");
+
+    let numbers = vec![10, 20, 30];
+    let mut iter = numbers.iter();
+    println!("iter.next() = {:?}", iter.next());
+    println!("iter.next() = {:?}", iter.next());
+    println!("iter.next() = {:?}", iter.next());
+    println!("iter.next() = {:?}", iter.next());
+    explain!("`trait Iterator {{ type Item; fn next(&mut self) -> Option<Self::Item>; }}`");
+    explain!("next gives Some(item) until the items run out, then None");
+    explain!("The iterator is `mut`: calling next moves it along");
+
+    let mut total = 0;
+    for n in numbers.iter() {
+        total += n;
+    }
+    println!("for n in numbers.iter() -> total = {}", total);
+    detail!("A for loop calls next for you until None, and needs no `mut`: it owns the iterator");
+    deep!("`iter.next()` after None usually gives None again; `.fuse()` makes that a promise");
+    println!();
+}
+
+/// Counts from 1 to 5
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.count < 5 {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+fn custom_iterator() {
+    println!("2. A Custom Iterator: Counter:");
+    println!("==============================");
+
+    let mut counter = Counter::new();
+    let first_three = [counter.next(), counter.next(), counter.next()];
+    println!("counter.next() three times = {:?}", first_three);
+    let rest: Vec<u32> = counter.collect();
+    println!("what is left, collected     = {:?}", rest);
+    explain!("Counter keeps its state in a field, and next moves it on by one");
+    explain!("Implementing next (and naming `type Item = u32`) is all Iterator asks for");
+
+    let sum: u32 = Counter::new()
+        .zip(Counter::new().skip(1))
+        .map(|(a, b)| a * b)
+        .filter(|x| x % 3 == 0)
+        .sum();
+    println!(
+        "zip with itself shifted, multiply, keep multiples of 3, sum = {}",
+        sum
+    );
+    explain!("zip, skip, map, filter and sum are default methods, built on Counter's next");
+    detail!("The pairs are (1,2) (2,3) (3,4) (4,5): zip stops when the shorter one ends");
+    detail!("  products 2 6 12 20, multiples of 3 are 6 and 12, so the sum is 18");
+    println!();
+}
+
+fn adapters_and_consumers() {
+    println!("3. Adapters and Consumers:");
+    println!("==========================");
+
+    let numbers = vec![1, 2, 3, 4, 5, 6];
+    let doubled: Vec<i32> = numbers.iter().map(|n| n * 2).collect();
+    let evens: Vec<&i32> = numbers.iter().filter(|n| *n % 2 == 0).collect();
+    let firsts: Vec<(usize, &i32)> = numbers.iter().enumerate().take(2).collect();
+    println!("map(|n| n * 2)        = {:?}", doubled);
+    println!("filter(even)          = {:?}", evens);
+    println!("enumerate().take(2)   = {:?}", firsts);
+    explain!("Adapters take an iterator and return a new one: map, filter, enumerate, take,");
+    explain!("  skip, zip, chain, rev... They only describe the work");
+
+    println!(
+        "sum                        = {}",
+        numbers.iter().sum::<i32>()
+    );
+    println!(
+        "filter(odd).count()        = {}",
+        numbers.iter().filter(|n| *n % 2 == 1).count()
+    );
+    println!("max                        = {:?}", numbers.iter().max());
+    println!(
+        "fold(0, |a, n| a * 10 + n) = {}",
+        numbers.iter().fold(0, |a, n| a * 10 + n)
+    );
+    println!(
+        "any(|n| *n > 5)            = {}",
+        numbers.iter().any(|n| *n > 5)
+    );
+    println!(
+        "position(|n| *n == 4)      = {:?}",
+        numbers.iter().position(|n| *n == 4)
+    );
+    // numbers.iter().map(|n| n * 2);
+    //     // WARNING: unused `Map` that must be used: iterators are lazy and do nothing unless consumed
+    explain!("Consumers call next until they have their answer: sum, count, max, fold,");
+    explain!("  any, position, collect, and for loops. A chain needs one at the end");
+    detail!("any and position stop at the first match; the rest go through every item");
+    println!();
+}
+
+fn laziness() {
+    println!("4. Laziness:");
+    println!("============");
+
+    let words = vec!["apple", "bob", "kayak", "rust"];
+    let palindromes = words
+        .iter()
+        .inspect(|word| println!("  looking at {}", word))
+        .filter(|word| word.chars().rev().eq(word.chars()));
+    println!("the chain is built; nothing has been looked at yet");
+    let found: Vec<&&str> = palindromes.take(2).collect();
+    println!("take(2) -> {:?}", found);
+    explain!("Each item goes through the whole chain before the next one starts");
+    explain!("take(2) stops asking after the second match, so \"rust\" is never looked at");
+
+    let squares: Vec<u64> = (1..).map(|n: u64| n * n).take_while(|&n| n < 50).collect();
+    println!("(1..).map(square).take_while(< 50) = {:?}", squares);
+    explain!("`1..` never ends; laziness makes it usable, as long as something stops it");
+    detail!("No Vec between the steps: a chain costs about what the hand-written loop does");
+    deep!("The compiler turns a chain of adapters into one loop, often vectorized");
+    println!();
+}
+
+fn collecting() {
+    println!("5. Collecting Into Containers:");
+    println!("==============================");
+
+    let letters = ['r', 'u', 's', 't'];
+    let word: String = letters.iter().collect();
+    let upper = letters
+        .iter()
+        .map(|c| c.to_ascii_uppercase())
+        .collect::<String>();
+    println!("String from chars         = {}", word);
+    println!("collect::<String>()       = {}", upper);
+    explain!("collect builds whatever type you ask for, with an annotation or a turbofish");
+
+    let names = ["ana", "bo", "cy", "bo"];
+    let unique: HashSet<&str> = names.iter().copied().collect();
+    let lengths: BTreeMap<&str, usize> = names.iter().map(|name| (*name, name.len())).collect();
+    let scores: HashMap<&str, i32> = names.iter().copied().zip([90, 75, 60, 80]).collect();
+    println!("HashSet (duplicates gone) = {} names", unique.len());
+    println!("BTreeMap of lengths       = {:?}", lengths);
+    println!("HashMap, bo's score       = {}", scores["bo"]);
+    explain!("Pairs collect into maps; a later pair with the same key replaces the earlier");
+    detail!("bo appears twice, so the map keeps 80, the second score");
+
+    let good: Result<Vec<i32>, _> = ["1", "2", "3"].iter().map(|s| s.parse::<i32>()).collect();
+    let bad: Result<Vec<i32>, _> = ["1", "x", "3"].iter().map(|s| s.parse::<i32>()).collect();
+    println!("Result<Vec<i32>, _> of 1 2 3 = {:?}", good);
+    println!(
+        "Result<Vec<i32>, _> of 1 x 3 = {:?}",
+        bad.map_err(|e| e.to_string())
+    );
+    explain!("Collecting Results into Result<Vec<_>, _> stops at the first Err and returns it");
+    detail!("Option works the same way: any None makes the whole collect None");
+    deep!("All of this is one method: collect asks for any type that implements FromIterator");
+    println!();
+}
+
+fn iter_iter_mut_into_iter() {
+    println!("6. iter, iter_mut and into_iter:");
+    println!("================================");
+
+    let mut names = vec![String::from("ana"), String::from("bo")];
+    let lengths: Vec<usize> = names.iter().map(|name| name.len()).collect();
+    println!("iter():      items are &String, lengths {:?}", lengths);
+    explain!("iter() lends each item: `&T`, and names is still usable afterwards");
+
+    for name in names.iter_mut() {
+        name.push('!');
+    }
+    println!("iter_mut():  items are &mut String, now {:?}", names);
+    explain!("iter_mut() lends each item mutably: `&mut T`, to change them in place");
+
+    let owned: Vec<String> = names.into_iter().map(|name| name.to_uppercase()).collect();
+    println!("into_iter(): items are String, moved out: {:?}", owned);
+    // println!("{:?}", names);  // COMPILE ERROR: borrow of moved value: `names`
+    explain!("into_iter() takes the collection by value and gives its items away: `T`");
+
+    let mut numbers = vec![1, 2, 3];
+    for n in &numbers {
+        print!("{} ", n);
+    }
+    for n in &mut numbers {
+        *n *= 10;
+    }
+    println!("-> {:?}", numbers);
+    explain!("`for x in &v` is `v.iter()`, `for x in &mut v` is `v.iter_mut()`, and");
+    explain!("  `for x in v` is `v.into_iter()`, which consumes v");
+    detail!(
+        "On an array, into_iter() gives values since the 2021 edition; on &v it gives references"
+    );
+    println!();
+}
+
+fn predict_lazy_order() {
+    let result: Vec<i32> = [1, 2, 3]
+        .iter()
+        .map(|n| {
+            println!("map {}", n);
+            n * 10
+        })
+        .filter(|n| {
+            println!("filter {}", n);
+            *n != 20
+        })
+        .collect();
+    println!("{:?}", result);
+}
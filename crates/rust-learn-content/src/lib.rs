@@ -0,0 +1,110 @@
+//! rust-learn content
+//!
+//! Every lesson lives in this crate, one module per lesson, and `registry`
+//! lists them in curriculum order. The CLI only ever goes through the registry
+//! (and `locales`, for translated lesson text).
+
+// Lessons print with println! and print! as usual; defined before the
+// modules, these shadow std's so every line goes through the themed renderer
+// in core
+macro_rules! println {
+    () => {
+        ::rust_learn_core::output::lesson_line(::std::format_args!(""))
+    };
+    ($($arg:tt)*) => {
+        ::rust_learn_core::output::lesson_line(::std::format_args!($($arg)*))
+    };
+}
+
+macro_rules! print {
+    ($($arg:tt)*) => {
+        ::rust_learn_core::output::lesson_text(::std::format_args!($($arg)*))
+    };
+}
+
+// println! is for what the code does; these are for the prose around it.
+// `--quiet` drops explain!, and detail! and deep! wait for -v and -vv.
+macro_rules! explain {
+    ($($arg:tt)*) => {
+        ::rust_learn_core::output::explanation(
+            ::rust_learn_core::output::Verbosity::Normal,
+            ::std::format_args!($($arg)*),
+        )
+    };
+}
+
+macro_rules! detail {
+    ($($arg:tt)*) => {
+        ::rust_learn_core::output::explanation(
+            ::rust_learn_core::output::Verbosity::Verbose,
+            ::std::format_args!($($arg)*),
+        )
+    };
+}
+
+macro_rules! deep {
+    ($($arg:tt)*) => {
+        ::rust_learn_core::output::explanation(
+            ::rust_learn_core::output::Verbosity::VeryVerbose,
+            ::std::format_args!($($arg)*),
+        )
+    };
+}
+
+mod generated;
+pub mod locales;
+pub mod registry;
+
+// Lessons build small lists with vec! even where an array would do,
+// because Vec is the collection learners already know.
+mod allocators;
+mod api_design;
+mod app_errors;
+mod array;
+mod async_await;
+#[allow(clippy::useless_vec)]
+mod boxes;
+#[allow(clippy::useless_vec)]
+mod browing;
+mod build_scripts;
+mod closures;
+mod conditonal;
+mod const_let_mut_variables;
+mod cow;
+mod cross_compilation;
+mod custom_errors;
+#[path = "enum.rs"]
+mod enums;
+mod error_handling;
+mod fast_input;
+mod ffi;
+mod generics;
+mod interior_mutability;
+#[allow(clippy::useless_vec)]
+mod iterators;
+#[allow(clippy::useless_vec)]
+#[path = "loop.rs"]
+mod loops;
+#[path = "match.rs"]
+mod matching;
+mod md2html;
+mod model_checking;
+mod no_std;
+#[allow(clippy::useless_vec)]
+mod options_type;
+mod ownership;
+#[allow(clippy::useless_vec)]
+mod panics;
+mod registers;
+mod release_profiles;
+mod semver;
+mod shared_state;
+mod structs;
+mod trait_objects;
+mod traits;
+mod variables;
+#[allow(clippy::useless_vec)]
+mod vectors;
+mod wasi;
+mod weak_references;
+mod workspaces;
@@ -0,0 +1,40 @@
+//! Lesson Translations
+//!
+//! The catalogs `--lang` chooses from, embedded from `locales/` so the binary
+//! carries them. English is what the lessons print, so it has none.
+
+/// The languages `--lang` accepts
+pub const LANGS: [&str; 2] = ["en", "bn"];
+
+/// The catalog text for `lang` (see `rust_learn_core::i18n`), or `None` for
+/// English
+pub fn catalog(lang: &str) -> Option<&'static str> {
+    match lang {
+        "bn" => Some(include_str!("../locales/bn.txt")),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registry::LESSONS;
+    use rust_learn_core::i18n::Catalog;
+
+    #[test]
+    fn test_catalogs_match_the_lessons() {
+        for lang in LANGS.into_iter().filter(|lang| *lang != "en") {
+            let text = catalog(lang).unwrap();
+            assert!(!Catalog::parse(lang, text).unwrap().is_empty());
+            // A translation of a line no lesson prints any more is stale
+            for english in text.lines().filter_map(|line| line.strip_prefix("en: ")) {
+                assert!(
+                    LESSONS.iter().any(|lesson| lesson.source.contains(english)),
+                    "{}: no lesson prints '{}'",
+                    lang,
+                    english
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,415 @@
+///looping rust
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn loops() {
+    println!("=== Loop Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- loops <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(while_example, "while Loops"),
+    section!(for_example, "for Loops Over an Array"),
+    section!(for_range_example, "for Over a Reversed Range"),
+    section!(loop_with_break, "loop With break"),
+    section!(loop_with_break_value, "Returning a Value With break"),
+    section!(loop_with_labels, "Loop Labels"),
+    section!(while_let_example, "while let"),
+    section!(for_with_enumerate, "enumerate"),
+    section!(for_with_reference, "Iterating by Reference"),
+    section!(for_with_mut_reference, "Iterating by Mutable Reference"),
+    section!(for_with_range_inclusive, "Inclusive Ranges"),
+    section!(for_with_step_by, "step_by"),
+    section!(for_with_filter, "filter"),
+    section!(for_with_map, "map"),
+    section!(for_with_zip, "zip"),
+    section!(for_with_chain, "chain"),
+    section!(for_with_take, "take"),
+    section!(for_with_skip, "skip"),
+    section!(for_with_rev, "rev"),
+    section!(for_with_cycle, "cycle"),
+    section!(for_with_windows, "windows"),
+    section!(for_with_chunks, "chunks"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "for_range_example",
+        prompt: "Which numbers does `for n in (1..4).rev()` go through?",
+        choices: &["3, 2, 1", "4, 3, 2, 1", "3, 2, 1, 0", "1, 2, 3"],
+        explanation: "1..4 stops before 4, and rev walks it backwards; 1..=4 would include the 4.",
+    },
+    Question {
+        section: "loop_with_labels",
+        prompt: "Inside two nested loops, what does `break 'outer;` do?",
+        choices: &[
+            "Ends the loop labelled 'outer, and the inner one with it",
+            "Ends only the inner loop",
+            "Starts the next pass of the outer loop",
+            "Returns from the function",
+        ],
+        explanation: "A plain break ends the innermost loop; a label picks which loop to end. `continue 'outer` would start its next pass.",
+    },
+    Question {
+        section: "for_with_windows",
+        prompt: "How many items does `[1, 2, 3, 4].windows(2)` yield?",
+        choices: &["3", "2", "4", "8"],
+        explanation: "Windows overlap: [1, 2], [2, 3] and [3, 4]. chunks(2) would yield 2.",
+    },
+    Question {
+        section: "for_with_reference",
+        prompt: "names is a Vec<String>. Why can it not be used after `for name in names { ... }`?",
+        choices: &[
+            "The loop moved names into its iterator",
+            "for loops empty the vector",
+            "names is still borrowed mutably",
+            "It can; nothing changes",
+        ],
+        explanation: "for calls into_iter on names, which takes it by value; loop over &names to keep it.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_labels), snippet!(predict_adapters)];
+
+// Never run: it prints "again!" until the program is stopped with Ctrl-C
+#[allow(dead_code)]
+fn loop_example() {
+    loop {
+        println!("again!");
+    }
+}
+
+fn while_example() {
+    println!("1. while Loops:");
+    println!("===============");
+
+    let mut number = 3;
+    while number != 0 {
+        println!("{number}!");
+        number -= 1;
+    }
+    println!("LIFTOFF!!!");
+
+    println!();
+}
+
+fn for_example() {
+    println!("2. for Loops Over an Array:");
+    println!("===========================");
+
+    let a = [10, 20, 30, 40, 50];
+    for element in a {
+        println!("the value is: {element}");
+    }
+
+    println!();
+}
+
+fn for_range_example() {
+    println!("3. for Over a Reversed Range:");
+    println!("=============================");
+
+    for number in (1..4).rev() {
+        println!("{number}!");
+    }
+    println!("LIFTOFF!!!");
+
+    println!();
+}
+
+// Additional loop patterns
+
+fn loop_with_break() {
+    println!("4. loop With break:");
+    println!("===================");
+
+    let mut counter = 0;
+    loop {
+        counter += 1;
+        if counter == 5 {
+            break;
+        }
+        println!("Counter: {}", counter);
+    }
+    println!("Loop finished at counter: {}", counter);
+
+    println!();
+}
+
+fn loop_with_break_value() {
+    println!("5. Returning a Value With break:");
+    println!("================================");
+
+    let mut counter = 0;
+    let result = loop {
+        counter += 1;
+        if counter == 10 {
+            break counter * 2; // Return a value from the loop
+        }
+    };
+    println!("Result: {}", result);
+
+    println!();
+}
+
+fn loop_with_labels() {
+    println!("6. Loop Labels:");
+    println!("===============");
+
+    let mut count = 0;
+    'counting_up: loop {
+        println!("count = {}", count);
+        let mut remaining = 10;
+
+        loop {
+            println!("remaining = {}", remaining);
+            if remaining == 9 {
+                break;
+            }
+            if count == 2 {
+                break 'counting_up; // Break the outer loop
+            }
+            remaining -= 1;
+        }
+
+        count += 1;
+    }
+    println!("End count = {}", count);
+
+    println!();
+}
+
+fn while_let_example() {
+    println!("7. while let:");
+    println!("=============");
+
+    let mut stack = Vec::new();
+    stack.push(1);
+    stack.push(2);
+    stack.push(3);
+
+    while let Some(top) = stack.pop() {
+        println!("{}", top);
+    }
+
+    println!();
+}
+
+fn for_with_enumerate() {
+    println!("8. enumerate:");
+    println!("=============");
+
+    let v = vec!['a', 'b', 'c'];
+    for (index, value) in v.iter().enumerate() {
+        println!("{} is at index {}", value, index);
+    }
+
+    println!();
+}
+
+fn for_with_reference() {
+    println!("9. Iterating by Reference:");
+    println!("==========================");
+
+    let v = vec![100, 32, 57];
+    for i in &v {
+        // Using reference to avoid moving
+        println!("{}", i);
+    }
+    println!("Vector is still available: {:?}", v);
+
+    println!();
+}
+
+fn for_with_mut_reference() {
+    println!("10. Iterating by Mutable Reference:");
+    println!("===================================");
+
+    let mut v = vec![100, 32, 57];
+    for i in &mut v {
+        // Mutable reference to modify values
+        *i += 50;
+    }
+    println!("Modified vector: {:?}", v);
+
+    println!();
+}
+
+fn for_with_range_inclusive() {
+    println!("11. Inclusive Ranges:");
+    println!("=====================");
+
+    for number in 1..=5 {
+        // Inclusive range (includes 5)
+        println!("{}", number);
+    }
+
+    println!();
+}
+
+fn for_with_step_by() {
+    println!("12. step_by:");
+    println!("============");
+
+    for number in (0..10).step_by(2) {
+        // Step by 2
+        println!("{}", number);
+    }
+
+    println!();
+}
+
+fn for_with_filter() {
+    println!("13. filter:");
+    println!("===========");
+
+    let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    for number in numbers.iter().filter(|&&x| x % 2 == 0) {
+        println!("Even number: {}", number);
+    }
+
+    println!();
+}
+
+fn for_with_map() {
+    println!("14. map:");
+    println!("========");
+
+    let numbers = vec![1, 2, 3, 4, 5];
+    for doubled in numbers.iter().map(|x| x * 2) {
+        println!("Doubled: {}", doubled);
+    }
+
+    println!();
+}
+
+fn for_with_zip() {
+    println!("15. zip:");
+    println!("========");
+
+    let names = vec!["Alice", "Bob", "Charlie"];
+    let ages = vec![25, 30, 35];
+
+    for (name, age) in names.iter().zip(ages.iter()) {
+        println!("{} is {} years old", name, age);
+    }
+
+    println!();
+}
+
+fn for_with_chain() {
+    println!("16. chain:");
+    println!("==========");
+
+    let first = vec![1, 2, 3];
+    let second = vec![4, 5, 6];
+
+    for number in first.iter().chain(second.iter()) {
+        println!("{}", number);
+    }
+
+    println!();
+}
+
+fn for_with_take() {
+    println!("17. take:");
+    println!("=========");
+
+    let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    for number in numbers.iter().take(3) {
+        // Only take first 3
+        println!("{}", number);
+    }
+
+    println!();
+}
+
+fn for_with_skip() {
+    println!("18. skip:");
+    println!("=========");
+
+    let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+    for number in numbers.iter().skip(3) {
+        // Skip first 3
+        println!("{}", number);
+    }
+
+    println!();
+}
+
+fn for_with_rev() {
+    println!("19. rev:");
+    println!("========");
+
+    let numbers = vec![1, 2, 3, 4, 5];
+    for number in numbers.iter().rev() {
+        // Reverse iteration
+        println!("{}", number);
+    }
+
+    println!();
+}
+
+fn for_with_cycle() {
+    println!("20. cycle:");
+    println!("==========");
+
+    let colors = vec!["red", "green", "blue"];
+    for (i, color) in colors.iter().cycle().take(7).enumerate() {
+        println!("Item {}: {}", i, color);
+    }
+
+    println!();
+}
+
+fn for_with_windows() {
+    println!("21. windows:");
+    println!("============");
+
+    let numbers = vec![1, 2, 3, 4, 5];
+    for window in numbers.windows(3) {
+        // Sliding window of size 3
+        println!("Window: {:?}", window);
+    }
+
+    println!();
+}
+
+fn for_with_chunks() {
+    println!("22. chunks:");
+    println!("===========");
+
+    let numbers = vec![1, 2, 3, 4, 5, 6, 7, 8];
+    for chunk in numbers.chunks(3) {
+        // Split into chunks of size 3
+        println!("Chunk: {:?}", chunk);
+    }
+
+    println!();
+}
+
+fn predict_labels() {
+    'outer: for i in 1..=3 {
+        for j in 1..=3 {
+            if j == 2 {
+                continue 'outer;
+            }
+            if i == 3 {
+                break 'outer;
+            }
+            println!("{} {}", i, j);
+        }
+    }
+}
+
+fn predict_adapters() {
+    let total: i32 = (1..=6).filter(|n| n % 2 == 0).map(|n| n * 10).sum();
+    println!("{}", total);
+    let pairs: Vec<_> = [1, 2, 3].iter().zip("ab".chars()).collect();
+    println!("{:?}", pairs);
+}
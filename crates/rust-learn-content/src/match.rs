@@ -0,0 +1,163 @@
+/// match
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn matching() {
+    println!("=== Match Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- match <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(match_example, "Matching Literal Values"),
+    section!(match_with_multiple_patterns, "Multiple Patterns"),
+    section!(match_with_range, "Ranges"),
+    section!(match_with_binding, "Binding the Matched Value"),
+    section!(match_with_guard, "Match Guards").with_checkpoint(
+        "Does a guard (`if ...`) count toward a match being exhaustive?",
+        false,
+        "The compiler does not look into guards, so a catch-all arm is still needed.",
+    ),
+    section!(match_with_multiple_arms, "Multiple Arms"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "match_example",
+        prompt: "Why does this fail to compile?\n\n    let n: u8 = 3;\n    match n {\n        0 => println!(\"zero\"),\n        1..=9 => println!(\"small\"),\n    }",
+        choices: &[
+            "The arms do not cover every u8",
+            "Ranges are not allowed in patterns",
+            "An arm cannot call println!",
+            "n must be mutable",
+        ],
+        explanation: "A match must be exhaustive; a `_ => ...` arm would cover 10 to 255.",
+    },
+    Question {
+        section: "match_with_guard",
+        prompt: "What does this print?\n\n    let pair = (2, -2);\n    match pair {\n        (x, y) if x + y == 0 => println!(\"opposites\"),\n        (x, _) if x % 2 == 0 => println!(\"even first\"),\n        _ => println!(\"other\"),\n    }",
+        choices: &[
+            "opposites",
+            "even first",
+            "opposites, then even first",
+            "other",
+        ],
+        explanation: "Arms are tried in order, and the first whose pattern and guard both match is the only one that runs.",
+    },
+    Question {
+        section: "match_with_binding",
+        prompt: "In the arm `n @ 1..=12 => ...`, what is n?",
+        choices: &[
+            "The matched value, known to be from 1 to 12",
+            "The number of the arm",
+            "Whether the value matched",
+            "The range 1..=12",
+        ],
+        explanation: "@ binds the value to a name while still testing it against the pattern on its right.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_guards)];
+
+fn match_example() {
+    println!("1. Matching Literal Values:");
+    println!("===========================");
+
+    let x = 5;
+    match x {
+        1 => println!("one"),
+        2 => println!("two"),
+        3 => println!("three"),
+        _ => println!("anything"),
+    }
+
+    println!();
+}
+
+/// match with multiple patterns
+#[allow(clippy::manual_range_patterns)] // `|` is the point here; ranges are next
+fn match_with_multiple_patterns() {
+    println!("2. Multiple Patterns:");
+    println!("=====================");
+
+    let x = 5;
+    match x {
+        1 | 2 | 3 => println!("one, two, or three"),
+        _ => println!("anything"),
+    }
+
+    println!();
+}
+
+/// match with range
+fn match_with_range() {
+    println!("3. Ranges:");
+    println!("==========");
+
+    let x = 5;
+    match x {
+        1..=5 => println!("one through five"),
+        _ => println!("something else"),
+    }
+
+    println!();
+}
+
+/// match with binding
+#[allow(clippy::match_single_binding)] // A lone binding pattern matches anything
+fn match_with_binding() {
+    println!("4. Binding the Matched Value:");
+    println!("=============================");
+
+    let x = 5;
+    match x {
+        x => println!("x is {x}"),
+    }
+
+    println!();
+}
+
+/// match with guard
+fn match_with_guard() {
+    println!("5. Match Guards:");
+    println!("================");
+
+    let x = 5;
+    match x {
+        x if x % 2 == 0 => println!("x is even"),
+        x if x % 2 != 0 => println!("x is odd"),
+        _ => println!("x is not a number"),
+    }
+
+    println!();
+}
+
+/// match with multiple arms
+#[allow(clippy::manual_range_patterns)] // `|` is the point here; ranges are shown above
+fn match_with_multiple_arms() {
+    println!("6. Multiple Arms:");
+    println!("=================");
+
+    let x = 5;
+    match x {
+        1 | 2 | 3 => println!("one, two, or three"),
+        _ => println!("anything"),
+    }
+
+    println!();
+}
+
+fn predict_guards() {
+    for pair in [(2, -2), (4, 1), (3, 3)] {
+        match pair {
+            (x, y) if x + y == 0 => println!("opposites"),
+            (x, _) if x % 2 == 0 => println!("even first"),
+            (x, y) if x == y => println!("twins"),
+            _ => println!("other"),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! md2html - A Tiny Markdown to HTML Converter
+//!
+//! The utility the WASI lesson compiles to WebAssembly. It only uses `std`
+//! and knows nothing about this crate, so the lesson can copy this file
+//! verbatim into a fresh cargo project and build it for `wasm32-wasip1`.
+//!
+//! Supported: `#` headings, paragraphs, `- ` lists, fenced code blocks,
+//! `inline code` and **bold**. Everything else is text.
+
+/// Convert a Markdown document to an HTML fragment, one block per line
+pub fn to_html(markdown: &str) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut in_list = false;
+    let mut in_code = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut in_list);
+            html.push_str(if in_code {
+                "</code></pre>\n"
+            } else {
+                "<pre><code>"
+            });
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            html.push_str(&escape(line));
+            html.push('\n');
+            continue;
+        }
+
+        let trimmed = line.trim();
+        let level = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&level) && trimmed[level..].starts_with(' ') {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut in_list);
+            let text = inline(trimmed[level..].trim());
+            html.push_str(&format!("<h{level}>{text}</h{level}>\n"));
+        } else if let Some(item) = trimmed.strip_prefix("- ") {
+            flush_paragraph(&mut html, &mut paragraph);
+            if !in_list {
+                html.push_str("<ul>\n");
+                in_list = true;
+            }
+            html.push_str(&format!("  <li>{}</li>\n", inline(item)));
+        } else if trimmed.is_empty() {
+            flush_paragraph(&mut html, &mut paragraph);
+            close_list(&mut html, &mut in_list);
+        } else {
+            close_list(&mut html, &mut in_list);
+            paragraph.push(trimmed);
+        }
+    }
+
+    flush_paragraph(&mut html, &mut paragraph);
+    close_list(&mut html, &mut in_list);
+    if in_code {
+        html.push_str("</code></pre>\n");
+    }
+    html
+}
+
+fn flush_paragraph(html: &mut String, paragraph: &mut Vec<&str>) {
+    if !paragraph.is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", inline(&paragraph.join(" "))));
+        paragraph.clear();
+    }
+}
+
+fn close_list(html: &mut String, in_list: &mut bool) {
+    if *in_list {
+        html.push_str("</ul>\n");
+        *in_list = false;
+    }
+}
+
+/// `code` and **bold** inside a line, with everything else escaped
+fn inline(text: &str) -> String {
+    // An unmatched backtick is just a character
+    if !text.matches('`').count().is_multiple_of(2) {
+        return bold(&escape(text));
+    }
+
+    let mut out = String::new();
+    for (index, part) in text.split('`').enumerate() {
+        if index % 2 == 1 {
+            out.push_str(&format!("<code>{}</code>", escape(part)));
+        } else {
+            out.push_str(&bold(&escape(part)));
+        }
+    }
+    out
+}
+
+fn bold(text: &str) -> String {
+    let parts: Vec<&str> = text.split("**").collect();
+    if parts.len().is_multiple_of(2) {
+        // An unmatched ** stays as it is
+        return text.to_string();
+    }
+    let mut out = String::new();
+    for (index, part) in parts.iter().enumerate() {
+        if index % 2 == 1 {
+            out.push_str(&format!("<strong>{part}</strong>"));
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blocks_and_inline_markup() {
+        let markdown = "# Title\n\nSome **bold** and `Vec<T>`\ntext.\n\n- one\n- two\n\n```\nlet x = 1 < 2;\n```";
+        assert_eq!(
+            to_html(markdown),
+            "<h1>Title</h1>\n\
+             <p>Some <strong>bold</strong> and <code>Vec&lt;T&gt;</code> text.</p>\n\
+             <ul>\n  <li>one</li>\n  <li>two</li>\n</ul>\n\
+             <pre><code>let x = 1 &lt; 2;\n</code></pre>\n"
+        );
+    }
+}
@@ -0,0 +1,551 @@
+/// Model Checking in Rust - Exploring Every Interleaving with loom
+///
+/// A stress test runs concurrent code many times and hopes to hit a bad
+/// interleaving; on an x86 machine some bugs can never show up that way,
+/// because its hardware keeps more order than Rust promises. loom runs a
+/// test once per possible interleaving instead, and models the weaker
+/// orderings too. This lesson writes a spinlock and a bounded single-
+/// producer, single-consumer queue on atomics, seeds one bug in each that
+/// the stress tests of the Shared State lesson pass, and shows how the loom
+/// tests at the end of this file catch both. They are behind the `loom`
+/// feature: `cargo test -p rust-learn-content --features loom model_checking`.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_utils::stress;
+use std::sync::atomic::Ordering;
+use sync::{Arc, AtomicBool, AtomicUsize, UnsafeCell, spin_loop};
+
+pub fn model_checking() {
+    println!("=== Model Checking Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- model_checking <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(stress_is_not_enough, "What Stress Tests Cannot Show").with_checkpoint(
+        "A spinlock passes 1,000 rounds of stress tests on an x86 machine. Is it correct on every machine?",
+        false,
+        "x86 keeps stores in order whatever Ordering says; an ARM machine, or loom's model, does not.",
+    ),
+    section!(a_spinlock, "A Spinlock on One AtomicBool"),
+    section!(a_bounded_queue, "A Bounded Single-Producer Queue"),
+    section!(how_loom_works, "How loom Explores Interleavings"),
+    section!(seeded_bugs, "The Seeded Bugs, Caught"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "stress_is_not_enough",
+        prompt: "Why can a stress test on x86 never catch a lock that unlocks with Ordering::Relaxed?",
+        choices: &[
+            "x86 hardware never reorders stores with earlier stores, so the bug cannot happen there",
+            "Relaxed stores are slower to show up",
+            "Stress tests do not use atomics",
+            "It catches it every time",
+        ],
+        explanation: "The bug needs a machine (or a compiler) that moves the data's writes after the unlock; x86's strong ordering hides it.",
+    },
+    Question {
+        section: "a_spinlock",
+        prompt: "A lock's unlock stores with Release. What must the lock's compare_exchange use to pair with it?",
+        choices: &[
+            "Acquire",
+            "Relaxed",
+            "Release",
+            "Nothing: one side is enough",
+        ],
+        explanation: "A Release store and the Acquire load that reads it make the first holder's writes visible to the next.",
+    },
+    Question {
+        section: "a_bounded_queue",
+        prompt: "The producer stores the new tail before it writes the slot. What can the consumer see?",
+        choices: &[
+            "A tail that says the slot is full, while it is still being written",
+            "Nothing wrong: the store is atomic",
+            "A compile error",
+            "Only a slower queue",
+        ],
+        explanation: "Publishing before writing lets the consumer read the slot at the same time: a data race on the slot.",
+    },
+    Question {
+        section: "how_loom_works",
+        prompt: "What does loom::model do with the closure it is given?",
+        choices: &[
+            "Runs it again and again, each time with another order of the threads' atomic steps",
+            "Runs it once on many threads",
+            "Proves it correct without running it",
+            "Runs it under a sanitizer",
+        ],
+        explanation: "loom's scheduler decides who runs at each atomic operation, and tries every choice, up to a preemption bound.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_queue_order)];
+
+/// std's atomics, cell and `Arc`, or loom's models of them while the loom
+/// tests run. The lesson itself always runs on std's
+#[cfg(all(test, feature = "loom"))]
+mod sync {
+    pub use loom::cell::UnsafeCell;
+    pub use loom::hint::spin_loop;
+    pub use loom::sync::Arc;
+    pub use loom::sync::atomic::{AtomicBool, AtomicUsize};
+}
+
+#[cfg(not(all(test, feature = "loom")))]
+mod sync {
+    pub use std::hint::spin_loop;
+    pub use std::sync::Arc;
+    pub use std::sync::atomic::{AtomicBool, AtomicUsize};
+
+    /// std's `UnsafeCell` with loom's way in: a closure gets the pointer,
+    /// so under loom each access can be checked against the others
+    #[derive(Debug)]
+    pub struct UnsafeCell<T>(std::cell::UnsafeCell<T>);
+
+    impl<T> UnsafeCell<T> {
+        pub fn new(value: T) -> UnsafeCell<T> {
+            UnsafeCell(std::cell::UnsafeCell::new(value))
+        }
+
+        pub fn with_mut<R>(&self, f: impl FnOnce(*mut T) -> R) -> R {
+            f(self.0.get())
+        }
+    }
+}
+
+/// A lock that spins instead of sleeping: `locked` says whether someone
+/// holds it, and `value` is only touched by whoever does
+struct SpinLock<T> {
+    locked: AtomicBool,
+    /// `Release`, or the seeded bug's `Relaxed`
+    unlock: Ordering,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: `value` is only reached while holding the lock, by one thread at
+// a time, and the Acquire/Release pair orders those accesses
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    fn new(value: T) -> SpinLock<T> {
+        SpinLock::unlocking_with(value, Ordering::Release)
+    }
+
+    /// The seeded bug: unlocking with `Relaxed` lets the holder's writes
+    /// reach the next holder after the lock does
+    fn seeded(value: T) -> SpinLock<T> {
+        SpinLock::unlocking_with(value, Ordering::Relaxed)
+    }
+
+    fn unlocking_with(value: T, unlock: Ordering) -> SpinLock<T> {
+        SpinLock {
+            locked: AtomicBool::new(false),
+            unlock,
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Run `f` on the value while holding the lock
+    fn with_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+        // SAFETY: holding the lock, this is the only access to the value
+        let result = self.value.with_mut(|value| f(unsafe { &mut *value }));
+        self.locked.store(false, self.unlock);
+        result
+    }
+}
+
+/// What a `Producer` and its `Consumer` share: `capacity` slots, used in
+/// turn. `head` counts the values taken and `tail` the values added, so
+/// `tail - head` are waiting
+struct Ring<T> {
+    slots: Vec<UnsafeCell<Option<T>>>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    /// The seeded bug: publish the new tail before writing the slot
+    publish_first: bool,
+}
+
+// SAFETY: the producer only writes slots the consumer has given up, and the
+// consumer only reads slots the producer has published
+unsafe impl<T: Send> Sync for Ring<T> {}
+
+/// The one sending end of a bounded queue
+struct Producer<T> {
+    ring: Arc<Ring<T>>,
+}
+
+/// The one receiving end of a bounded queue
+struct Consumer<T> {
+    ring: Arc<Ring<T>>,
+}
+
+/// A queue of `capacity` values between one producer and one consumer,
+/// like `std::sync::mpsc::sync_channel` but without blocking
+fn bounded<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    ring(capacity, false)
+}
+
+/// `bounded` with the seeded bug
+fn bounded_seeded<T>(capacity: usize) -> (Producer<T>, Consumer<T>) {
+    ring(capacity, true)
+}
+
+fn ring<T>(capacity: usize, publish_first: bool) -> (Producer<T>, Consumer<T>) {
+    let ring = Arc::new(Ring {
+        slots: (0..capacity.max(1))
+            .map(|_| UnsafeCell::new(None))
+            .collect(),
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        publish_first,
+    });
+    let producer = Producer {
+        ring: Arc::clone(&ring),
+    };
+    (producer, Consumer { ring })
+}
+
+impl<T> Producer<T> {
+    /// Add `value`, or give it back when the queue is full
+    fn push(&self, value: T) -> Result<(), T> {
+        let ring = &*self.ring;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        if tail - ring.head.load(Ordering::Acquire) == ring.slots.len() {
+            return Err(value);
+        }
+        let slot = &ring.slots[tail % ring.slots.len()];
+        if ring.publish_first {
+            ring.tail.store(tail + 1, Ordering::Release);
+        }
+        // SAFETY: the consumer is done with this slot, and (without the
+        // seeded bug) cannot see it until the tail moves past it
+        slot.with_mut(|slot| unsafe { *slot = Some(value) });
+        if !ring.publish_first {
+            ring.tail.store(tail + 1, Ordering::Release);
+        }
+        Ok(())
+    }
+}
+
+impl<T> Consumer<T> {
+    /// Take the oldest value, if there is one
+    fn pop(&self) -> Option<T> {
+        let ring = &*self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        if head == ring.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let slot = &ring.slots[head % ring.slots.len()];
+        // SAFETY: the producer published this slot and will not write it
+        // again until the head moves past it
+        let value = slot.with_mut(|slot| unsafe { (*slot).take() });
+        ring.head.store(head + 1, Ordering::Release);
+        value
+    }
+}
+
+/// Stress `lock` the way the Shared State lesson would: four threads add
+/// to the locked counter, which must end at the number of additions
+fn stress_lock(make: fn(u64) -> SpinLock<u64>) -> Result<stress::Run, stress::Violation> {
+    stress::check(
+        50,
+        4,
+        500,
+        || make(0),
+        |lock, _, _| lock.with_lock(|count| *count += 1),
+        |lock| {
+            let count = lock.with_lock(|count| *count);
+            if count == 2_000 {
+                Ok(())
+            } else {
+                Err(format!("{} additions, expected 2000", count))
+            }
+        },
+    )
+}
+
+/// Send 1..=`count` from a producer thread and collect what the consumer
+/// receives, until the producer is done and the queue is empty
+fn pass_through((producer, consumer): (Producer<u64>, Consumer<u64>), count: u64) -> Vec<u64> {
+    std::thread::scope(|scope| {
+        let sender = scope.spawn(move || {
+            for value in 1..=count {
+                let mut value = value;
+                while let Err(back) = producer.push(value) {
+                    value = back;
+                    std::thread::yield_now();
+                }
+            }
+        });
+        let mut received = Vec::new();
+        loop {
+            if let Some(value) = consumer.pop() {
+                received.push(value);
+            } else if sender.is_finished() {
+                received.extend(std::iter::from_fn(|| consumer.pop()));
+                return received;
+            } else {
+                std::thread::yield_now();
+            }
+        }
+    })
+}
+
+fn stress_is_not_enough() {
+    println!("1. What Stress Tests Cannot Show:");
+    println!("=================================");
+
+    match stress_lock(SpinLock::seeded) {
+        Ok(run) => println!(
+            "seeded spinlock: 50 rounds of {} additions, every one counted",
+            run.operations()
+        ),
+        Err(violation) => println!("seeded spinlock: {}", violation),
+    }
+    println!("running on: {}", std::env::consts::ARCH);
+    explain!("The seeded lock unlocks with Ordering::Relaxed, which promises nothing");
+    explain!("  about the writes made while holding it. On x86 it works anyway: the");
+    explain!("  hardware never lets a store overtake an earlier one");
+    explain!("On ARM, or after the compiler moves a write, the next holder can see the");
+    explain!("  lock free before it sees the count the last holder wrote");
+    detail!("A stress test can only find what the machine it runs on can do, and only");
+    detail!("  when the scheduler happens to do it. A model checker tries every order");
+    println!();
+}
+
+fn a_spinlock() {
+    println!("2. A Spinlock on One AtomicBool:");
+    println!("================================");
+
+    explain!("fn with_lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {{");
+    explain!("    while self.locked");
+    explain!("        .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)");
+    explain!("        .is_err()");
+    explain!("    {{");
+    explain!("        spin_loop();");
+    explain!("    }}");
+    explain!("    let result = self.value.with_mut(|value| f(unsafe {{ &mut *value }}));");
+    explain!("    self.locked.store(false, Ordering::Release);");
+    explain!("    result");
+    explain!("}}");
+
+    match stress_lock(SpinLock::new) {
+        Ok(run) => println!(
+            "\nspinlock: 50 rounds of {} additions in {:?} (the last round)",
+            run.operations(),
+            run.elapsed
+        ),
+        Err(violation) => println!("\nspinlock: {}", violation),
+    }
+    explain!("compare_exchange_weak takes the lock only if it was free, in one step");
+    explain!("The Release store that frees it and the Acquire load that takes it next");
+    explain!("  are a pair: everything before the first happens before everything after");
+    detail!("spin_loop() tells the CPU it is waiting; a real lock would park the thread");
+    detail!("  after a while instead of burning a core (std's Mutex does both)");
+    println!();
+}
+
+fn a_bounded_queue() {
+    println!("3. A Bounded Single-Producer Queue:");
+    println!("===================================");
+
+    let received = pass_through(bounded(4), 100);
+    let total: u64 = received.iter().sum();
+    println!(
+        "1..=100 through a queue of 4 slots: the consumer's total is {}",
+        total
+    );
+    explain!("push writes the slot, then publishes it by storing tail + 1 (Release);");
+    explain!("  pop loads the tail (Acquire), reads the slot, then frees it: head + 1");
+    explain!("Only one Producer and one Consumer exist, and neither is Clone, so only");
+    explain!("  one thread ever moves each index");
+    detail!("The seeded version stores tail + 1 first and writes the slot after: the");
+    detail!("  consumer may read a slot that is still being written");
+    println!();
+}
+
+fn how_loom_works() {
+    println!("4. How loom Explores Interleavings:");
+    println!("===================================");
+
+    explain!("#[test]");
+    explain!("fn loom_spinlock_counts_every_addition() {{");
+    explain!("    loom::model(|| {{");
+    explain!("        let lock = Arc::new(SpinLock::new(0));");
+    explain!("        let other = Arc::clone(&lock);");
+    explain!("        let thread = loom::thread::spawn(move || other.with_lock(|n| *n += 1));");
+    explain!("        lock.with_lock(|n| *n += 1);");
+    explain!("        thread.join().unwrap();");
+    explain!("        assert_eq!(lock.with_lock(|n| *n), 2);");
+    explain!("    }});");
+    explain!("}}");
+
+    explain!("\nWHAT HAPPENS:");
+    explain!("=============");
+    explain!("- loom::model runs the closure many times, on loom's own scheduler");
+    explain!("- Every atomic operation is a point where it may switch threads; each run");
+    explain!("  makes one more choice differently, until every order has been tried");
+    explain!("- A Relaxed load may return any value the memory model allows, not just");
+    explain!("  the latest, so orderings are checked even on x86");
+    explain!("- loom's UnsafeCell records who touched it; two accesses without a");
+    explain!("  happens-before between them fail the test");
+
+    explain!("\nWRITING FOR IT:");
+    explain!("===============");
+    explain!("- The code uses loom's AtomicBool, Arc and UnsafeCell in tests, std's");
+    explain!("  otherwise: the `sync` module in this file picks them");
+    explain!("- Spin loops call loom::hint::spin_loop, so loom switches threads there");
+    explain!("- Keep models small: two or three threads, a few operations each");
+    detail!("LOOM_MAX_PREEMPTIONS=2 bounds how often a thread is interrupted, which");
+    detail!("  keeps bigger models finishing; most bugs need only one or two");
+    println!();
+}
+
+fn seeded_bugs() {
+    println!("5. The Seeded Bugs, Caught:");
+    println!("===========================");
+
+    explain!("cargo test -p rust-learn-content --features loom model_checking");
+    explain!("");
+    println!("{:<42} {:<14} {}", "TEST", "STRESS TEST", "LOOM");
+    let rows = [
+        ("spinlock, Release unlock", "passes", "passes"),
+        ("spinlock, seeded Relaxed unlock", "passes", "fails"),
+        ("queue, write then publish", "passes", "passes"),
+        (
+            "queue, seeded publish then write",
+            "passes, mostly",
+            "fails",
+        ),
+    ];
+    for (test, stressed, loom) in rows {
+        println!("{:<42} {:<14} {}", test, stressed, loom);
+    }
+    let expected: Vec<u64> = (1..=100).collect();
+    let broken = (0..200)
+        .filter(|_| pass_through(bounded_seeded(1), 100) != expected)
+        .count();
+    println!(
+        "\nseeded queue, 200 runs on std threads: {} lost or garbled a value",
+        broken
+    );
+    explain!("A lost value needs the consumer to run in the few instructions between the");
+    explain!("  producer's publish and its write; loom tries exactly that order first time");
+    explain!("\nloom's report for both seeded bugs:");
+    explain!("  Causality violation: Concurrent write accesses to `UnsafeCell`.");
+    explain!("The seeded tests expect that panic (#[should_panic]), so they pass when");
+    explain!("  loom finds the bug: the suite shows loom catching it on every run");
+    detail!("For the lock, the second holder writes the count without having seen the");
+    detail!("  first holder's write: no happens-before, since Relaxed releases nothing");
+    detail!("For the queue, the consumer takes the slot while the producer may still");
+    detail!("  be writing it");
+    println!();
+}
+
+// Helper functions
+
+fn predict_queue_order() {
+    let (producer, consumer) = bounded(2);
+    println!("{:?}", producer.push('a'));
+    println!("{:?}", producer.push('b'));
+    println!("{:?}", producer.push('c'));
+    println!("{:?}", consumer.pop());
+    println!("{:?}", producer.push('d'));
+    while let Some(letter) = consumer.pop() {
+        println!("{}", letter);
+    }
+}
+
+#[cfg(all(test, not(feature = "loom")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spinlock_and_queue() {
+        assert!(stress_lock(SpinLock::new).is_ok());
+
+        let (producer, consumer) = bounded(2);
+        assert_eq!(consumer.pop(), None);
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(producer.push(2), Ok(()));
+        assert_eq!(producer.push(3), Err(3));
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.push(3), Ok(()));
+        assert_eq!((consumer.pop(), consumer.pop()), (Some(2), Some(3)));
+        assert_eq!(consumer.pop(), None);
+    }
+}
+
+/// The model checks: `cargo test -p rust-learn-content --features loom model_checking`
+#[cfg(all(test, feature = "loom"))]
+mod loom_tests {
+    use super::*;
+    use loom::thread;
+
+    /// Two threads add one each under `lock`
+    fn add_twice(lock: SpinLock<u64>) {
+        let lock = Arc::new(lock);
+        let other = Arc::clone(&lock);
+        let thread = thread::spawn(move || other.with_lock(|count| *count += 1));
+        lock.with_lock(|count| *count += 1);
+        thread.join().unwrap();
+        assert_eq!(lock.with_lock(|count| *count), 2);
+    }
+
+    /// One thread sends two values through a queue of one slot while this
+    /// one receives them, in order
+    fn send_two((producer, consumer): (Producer<u32>, Consumer<u32>)) {
+        let thread = thread::spawn(move || {
+            for value in [1, 2] {
+                let mut value = value;
+                while let Err(back) = producer.push(value) {
+                    value = back;
+                    thread::yield_now();
+                }
+            }
+        });
+        let mut received = Vec::new();
+        while received.len() < 2 {
+            match consumer.pop() {
+                Some(value) => received.push(value),
+                None => thread::yield_now(),
+            }
+        }
+        thread.join().unwrap();
+        assert_eq!(received, [1, 2]);
+    }
+
+    #[test]
+    fn loom_spinlock_counts_every_addition() {
+        loom::model(|| add_twice(SpinLock::new(0)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Causality violation")]
+    fn loom_catches_the_relaxed_unlock() {
+        loom::model(|| add_twice(SpinLock::seeded(0)));
+    }
+
+    #[test]
+    fn loom_queue_delivers_in_order() {
+        loom::model(|| send_two(bounded(1)));
+    }
+
+    #[test]
+    #[should_panic(expected = "Causality violation")]
+    fn loom_catches_publishing_before_writing() {
+        loom::model(|| send_two(bounded_seeded(1)));
+    }
+}
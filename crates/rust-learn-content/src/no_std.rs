@@ -0,0 +1,243 @@
+/// no_std in Rust - Code That Runs Without an Operating System
+///
+/// The rust-learn-utils crate is `#![no_std]`: it only uses `core`, plus
+/// `alloc` behind a feature. This lesson explains the three standard library
+/// layers, runs the crate's state machine, retry policy and LRU cache, and
+/// shows what a binary without std has to provide itself.
+use crate::registry::{Question, Section, section};
+use rust_learn_utils::fsm::{Event, State};
+use rust_learn_utils::lru::LruCache;
+use rust_learn_utils::retry::RetryPolicy;
+use std::time::Duration;
+
+pub fn no_std() {
+    println!("=== no_std Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- no_std <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(core_alloc_std, "core, alloc and std"),
+    section!(a_no_std_crate, "A no_std Utility Crate"),
+    section!(panic_handler, "#[panic_handler] and Friends"),
+    section!(structuring_for_reuse, "Structuring Code for Embedded Reuse"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "core_alloc_std",
+        prompt: "Which crate can a #![no_std] crate use without an allocator?",
+        choices: &["core", "alloc", "std", "None of them"],
+        explanation: "core needs no heap and no OS; alloc needs a global allocator, and std needs an operating system.",
+    },
+    Question {
+        section: "panic_handler",
+        prompt: "What must a #![no_std] binary provide that std normally does?",
+        choices: &[
+            "A #[panic_handler] function",
+            "A main returning i32",
+            "An async runtime",
+            "A logger",
+        ],
+        explanation: "Without std nothing decides what a panic does, so the binary must say, usually by halting or resetting.",
+    },
+];
+
+// The utility crate's manifest and root module, as they were when built
+const UTILS_MANIFEST: &str = include_str!("../../rust-learn-utils/Cargo.toml");
+const UTILS_LIB: &str = include_str!("../../rust-learn-utils/src/lib.rs");
+
+fn core_alloc_std() {
+    println!("1. core, alloc and std:");
+    println!("=======================\n");
+
+    explain!("THREE LAYERS:");
+    explain!("=============");
+    explain!("core  - needs nothing: Option, Result, iterators, slices, str, fmt,");
+    explain!("        atomics, Duration, the primitive types' methods");
+    explain!("alloc - needs a heap (a #[global_allocator]): Box, Vec, String, Rc, Arc,");
+    explain!("        BTreeMap, VecDeque, format!");
+    explain!("std   - needs an operating system: files, threads, networking, stdin,");
+    explain!("        Instant, HashMap (its random seed), println!");
+
+    println!("\nSTD RE-EXPORTS THE OTHER TWO:");
+    println!("=============================");
+    println!(
+        "std::option::Option is core::option::Option: {}",
+        std::any::type_name::<Option<u8>>()
+    );
+    println!(
+        "std::vec::Vec is alloc::vec::Vec:            {}",
+        std::any::type_name::<Vec<u8>>()
+    );
+    println!(
+        "std::time::Duration is core::time::Duration: {}",
+        std::any::type_name::<Duration>()
+    );
+
+    explain!("\n#![no_std]:");
+    explain!("===========");
+    explain!("- The crate's prelude comes from core instead of std");
+    explain!("- `use std::...` no longer compiles; `use core::...` does");
+    explain!("- `extern crate alloc;` brings back Vec and Box when there is a heap");
+    explain!("- A no_std library still works in ordinary programs like this one");
+
+    println!();
+}
+
+fn a_no_std_crate() {
+    println!("2. A no_std Utility Crate:");
+    println!("==========================\n");
+
+    explain!("crates/rust-learn-utils/src/lib.rs:");
+    let code = UTILS_LIB
+        .lines()
+        .skip_while(|line| line.starts_with("//!") || line.is_empty());
+    for line in code {
+        println!("  {}", line);
+    }
+
+    explain!("\ncrates/rust-learn-utils/Cargo.toml [features]:");
+    let features = UTILS_MANIFEST
+        .lines()
+        .skip_while(|line| *line != "[features]")
+        .skip(1);
+    for line in features {
+        println!("  {}", line);
+    }
+
+    println!("\nSTATE MACHINE (core only):");
+    println!("==========================");
+    let events = [
+        Event::Connect,
+        Event::Dropped,
+        Event::Established { id: 7 },
+        Event::Connect,
+        Event::Dropped,
+        Event::Dropped,
+        Event::Dropped,
+        Event::Dropped,
+    ];
+    let mut state = State::Disconnected;
+    for event in events {
+        match state.next(event, 3) {
+            Ok(next) => {
+                println!(
+                    "{:<26} + {:<24} -> {:?}",
+                    format!("{:?}", state),
+                    format!("{:?}", event),
+                    next
+                );
+                state = next;
+            }
+            Err(error) => println!("rejected: {}", error),
+        }
+    }
+
+    println!("\nRETRY POLICY (core only):");
+    println!("=========================");
+    let policy = RetryPolicy::DEFAULT;
+    for attempt in 1..=policy.max_attempts {
+        match policy.delay(attempt) {
+            Some(delay) => println!(
+                "after attempt {}: wait {:?} (with jitter: {:?})",
+                attempt,
+                delay,
+                policy
+                    .delay_with_jitter(attempt, 0x9E37_79B9_7F4A_7C15)
+                    .unwrap()
+            ),
+            None => println!("after attempt {}: give up", attempt),
+        }
+    }
+
+    println!("\nLRU CACHE (needs the alloc feature):");
+    println!("====================================");
+    let mut cache = LruCache::new(3);
+    for (key, value) in [("a", 1), ("b", 2), ("c", 3)] {
+        cache.put(key, value);
+    }
+    println!("get(\"a\") = {:?}", cache.get(&"a"));
+    println!("put(\"d\") evicts {:?}", cache.put("d", 4));
+    println!("keys, oldest first: {:?}", cache.keys().collect::<Vec<_>>());
+
+    println!();
+}
+
+fn panic_handler() {
+    println!("3. #[panic_handler] and Friends:");
+    println!("================================\n");
+
+    explain!("WHAT std USUALLY PROVIDES:");
+    explain!("==========================");
+    explain!("- What to do on panic: print the message, unwind, exit the thread");
+    explain!("- main: std sets up arguments and the stack, then calls your main");
+    explain!("- Memory: the system allocator behind Box and Vec");
+
+    explain!("\nA no_std BINARY PROVIDES THEM ITSELF:");
+    explain!("=====================================");
+    explain!("#![no_std]");
+    explain!("#![no_main]");
+    println!();
+    explain!("use core::panic::PanicInfo;");
+    println!();
+    explain!("#[panic_handler]");
+    explain!("fn panic(_info: &PanicInfo) -> ! {{");
+    explain!("    loop {{}} // or reset the chip, or blink an error LED");
+    explain!("}}");
+    println!();
+    explain!("#[unsafe(no_mangle)]");
+    explain!("pub extern \"C\" fn _start() -> ! {{");
+    explain!("    loop {{}}");
+    explain!("}}");
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- Exactly one #[panic_handler] in the final binary; libraries never define one");
+    explain!("- It returns `!`: there is nowhere to return to");
+    explain!("- Set panic = \"abort\" in the profile, since there is no unwinder");
+    explain!("- Crates like panic-halt and cortex-m-rt provide these for real boards");
+    explain!("- A heap needs a #[global_allocator] too (see the allocators lesson)");
+
+    println!();
+}
+
+fn structuring_for_reuse() {
+    println!("4. Structuring Code for Embedded Reuse:");
+    println!("=======================================\n");
+
+    explain!("KEEP THE CORE PURE:");
+    explain!("===================");
+    explain!("- RetryPolicy computes delays; the caller sleeps however it can");
+    explain!("- The random number for jitter is a parameter, not a call to an RNG");
+    explain!("- State::next returns the next state; the caller does the I/O");
+
+    explain!("\nGATE WHAT NEEDS MORE:");
+    explain!("=====================");
+    explain!("#[cfg(feature = \"alloc\")]");
+    explain!("extern crate alloc;");
+    println!();
+    explain!("#[cfg(feature = \"alloc\")]");
+    explain!("pub mod lru;");
+    println!();
+    explain!("Consumers opt in: rust-learn-content uses features = [\"alloc\"].");
+
+    explain!("\nCHECKING IT STAYS no_std:");
+    explain!("=========================");
+    explain!("cargo build -p rust-learn-utils                       # core only");
+    explain!("cargo build -p rust-learn-utils --features alloc");
+    explain!("rustup target add thumbv7em-none-eabihf               # a microcontroller");
+    explain!("cargo build -p rust-learn-utils --target thumbv7em-none-eabihf");
+    explain!("A target without std fails to build the moment anything uses it.");
+
+    explain!("\nTESTING:");
+    explain!("========");
+    explain!("Unit tests still run on the host with `cargo test`: the test harness");
+    explain!("links std even when the library itself is #![no_std].");
+
+    println!();
+}
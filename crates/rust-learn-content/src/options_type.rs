@@ -2,33 +2,67 @@
 ///
 /// The Option type represents a value that might or might not exist.
 /// It's Rust's way of handling null values safely without null pointer errors.
-use std::io;
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_core::input;
 
 pub fn options_type() {
     println!("=== Option Type Learning Examples ===\n");
 
-    // 1. Creating Option Values
-    create_options();
-
-    // 2. Pattern Matching with Option
-    pattern_matching();
-
-    // 3. Option Methods
-    option_methods();
-
-    // 4. Option with Functions
-    option_with_functions();
-
-    // 5. Option with Collections
-    option_with_collections();
-
-    // 6. Option with User Input
-    option_with_input();
-
-    // 7. Advanced Option Patterns
-    advanced_patterns();
+    for section in SECTIONS {
+        (section.run)();
+    }
 }
 
+/// Sections in the order they run; `cargo run -- options_type <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(create_options, "Creating Option Values"),
+    section!(pattern_matching, "Pattern Matching with Option"),
+    section!(option_methods, "Option Methods").with_checkpoint(
+        "Does `None.unwrap_or(0)` panic?",
+        false,
+        "`unwrap_or` gives the default for `None`; only `unwrap` panics.",
+    ),
+    section!(option_with_functions, "Option with Functions"),
+    section!(option_with_collections, "Option with Collections"),
+    section!(option_with_input, "Option with User Input"),
+    section!(advanced_patterns, "Advanced Option Patterns"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "option_methods",
+        prompt: "What is `None.unwrap_or(0)` for an Option<i32>?",
+        choices: &["0", "None", "It panics", "-1"],
+        explanation: "unwrap_or gives the value inside a Some, or the fallback for None. unwrap() would panic.",
+    },
+    Question {
+        section: "pattern_matching",
+        prompt: "A match on an Option<i32> has only a `Some(n) => ...` arm. What happens?",
+        choices: &[
+            "It fails to compile: None is not handled",
+            "None is skipped",
+            "It panics on None",
+            "n is 0 for None",
+        ],
+        explanation: "Option is an enum like any other, so a match on it must handle None too; that is how Rust avoids null surprises.",
+    },
+    Question {
+        section: "option_with_functions",
+        prompt: "In a function returning Option<i32>, what does `let n = value?;` do when value is None?",
+        choices: &[
+            "Returns None from the function right away",
+            "Panics",
+            "Sets n to 0",
+            "Skips that line",
+        ],
+        explanation: "? unwraps a Some and returns early with None otherwise, so a chain of lookups reads top to bottom.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_option_chain)];
+
 fn create_options() {
     println!("1. Creating Option Values:");
 
@@ -96,6 +130,8 @@ fn pattern_matching() {
     println!();
 }
 
+// Calling unwrap_or and friends on literal Some/None values shows what each one does
+#[allow(clippy::unnecessary_literal_unwrap)]
 fn option_methods() {
     println!("3. Option Methods:");
 
@@ -187,6 +223,7 @@ fn option_with_functions() {
     println!();
 }
 
+#[allow(clippy::get_first)] // get(0) next to get(10) shows both outcomes
 fn option_with_collections() {
     println!("5. Option with Collections:");
 
@@ -236,13 +273,9 @@ fn option_with_input() {
 
     println!("Enter a number (or 'quit' to exit):");
 
-    loop {
-        let mut input = String::new();
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
-
-        let input = input.trim();
+    // Sample input stands in for the user when the run is not interactive
+    for input in input::lesson_lines(&["7", "abc", "5000", "quit"]) {
+        let input = input.as_str();
 
         if input == "quit" {
             break;
@@ -266,6 +299,7 @@ fn option_with_input() {
     println!();
 }
 
+#[allow(clippy::manual_inspect)] // map chain shows values flowing through
 fn advanced_patterns() {
     println!("7. Advanced Option Patterns:");
 
@@ -390,7 +424,12 @@ fn get_config_value(key: &str) -> Option<u32> {
     }
 }
 
-// Main function to run all option examples
-fn main() {
-    options_type();
+fn predict_option_chain() {
+    let words = ["7", "x", ""];
+    for word in words {
+        let doubled = word.parse::<i32>().ok().map(|n| n * 2).unwrap_or(-1);
+        println!("{:?} -> {}", word, doubled);
+    }
+    let empty = words.iter().find(|word| word.is_empty());
+    println!("{}", empty.is_some());
 }
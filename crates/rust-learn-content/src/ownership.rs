@@ -3,42 +3,108 @@
 /// Ownership is Rust's most unique feature and has deep implications for the language.
 /// It enables Rust to make memory safety guarantees without needing a garbage collector.
 /// This comprehensive guide covers from basic concepts to advanced patterns.
-use std::io;
+use crate::registry::{Change, Question, Section, Snippet, section, snippet};
+use rust_learn_core::variants;
 
 pub fn ownership() {
     println!("=== Ownership Learning Examples ===\n");
 
-    // 1. Basic Ownership Rules
-    basic_ownership_rules();
-
-    // 2. Ownership and Functions
-    ownership_and_functions();
-
-    // 3. References and Borrowing
-    references_and_borrowing();
-
-    // 4. Mutable References
-    mutable_references();
-
-    // 5. Slices
-    slices();
-
-    // 6. Ownership with Collections
-    ownership_with_collections();
-
-    // 7. Advanced Ownership Patterns
-    advanced_ownership_patterns();
-
-    // 8. Memory Management Deep Dive
-    memory_management_deep_dive();
-
-    // 9. Ownership with Custom Types
-    ownership_with_custom_types();
-
-    // 10. Advanced Borrowing Patterns
-    advanced_borrowing_patterns();
+    for section in SECTIONS {
+        (section.run)();
+    }
 }
 
+/// What changed since the first version, for learners who finished it before
+pub const CHANGELOG: &[Change] = &[
+    Change {
+        version: 2,
+        summary: "Explanations now have depths: -v adds notes for beginners, -vv memory layout",
+    },
+    Change {
+        version: 3,
+        summary: "New section 10, Borrowing With Lifetimes: references returned from functions and kept in structs",
+    },
+];
+
+/// Sections in the order they run; `cargo run -- ownership <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(basic_ownership_rules, "Basic Ownership Rules").with_checkpoint(
+        "After `let s2 = s1;` with a String, can you still use `s1`?",
+        false,
+        "The String moved to `s2`; `s1` is no longer valid.",
+    ),
+    section!(ownership_and_functions, "Ownership and Functions"),
+    section!(references_and_borrowing, "References and Borrowing").with_variants(LENDING),
+    section!(mutable_references, "Mutable References").with_checkpoint(
+        "Can you have two `&mut` to the same value in use at once?",
+        false,
+        "Only one mutable reference at a time, so two of them can never race.",
+    ),
+    section!(slices, "Slices"),
+    section!(ownership_with_collections, "Ownership with Collections"),
+    section!(advanced_ownership_patterns, "Advanced Ownership Patterns"),
+    section!(memory_management_deep_dive, "Memory Management Deep Dive"),
+    section!(ownership_with_custom_types, "Ownership with Custom Types"),
+    section!(borrowing_with_lifetimes, "Borrowing With Lifetimes"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "basic_ownership_rules",
+        prompt: "Which line fails to compile?\n\n    let s1 = String::from(\"hello\");\n    let s2 = s1;\n    println!(\"{}\", s2);\n    println!(\"{}\", s1);",
+        choices: &[
+            "println!(\"{}\", s1);",
+            "let s2 = s1;",
+            "println!(\"{}\", s2);",
+            "None; both lines print hello",
+        ],
+        explanation: "`let s2 = s1` moves the String into s2, so s1 can no longer be used; s1.clone() would keep both.",
+    },
+    Question {
+        section: "basic_ownership_rules",
+        prompt: "Why can x still be used after `let x = 5; let y = x;`?",
+        choices: &[
+            "i32 is Copy, so x was copied, not moved",
+            "y only borrows x",
+            "Integers live on the heap",
+            "The compiler clones every value",
+        ],
+        explanation: "Types that are cheap to copy bit for bit, like integers, bool and char, implement Copy; assigning them copies instead of moving.",
+    },
+    Question {
+        section: "references_and_borrowing",
+        prompt: "After `let len = calculate_length(&s1);`, who owns the String?",
+        choices: &[
+            "s1 still does; the function only borrowed it",
+            "calculate_length",
+            "len",
+            "Nobody; it was dropped",
+        ],
+        explanation: "&s1 lends the String for the length of the call; ownership never moves, so s1 is still usable after it.",
+    },
+    Question {
+        section: "mutable_references",
+        prompt: "Which line fails to compile?\n\n    let mut s = String::from(\"hi\");\n    let r1 = &mut s;\n    let r2 = &mut s;\n    println!(\"{} {}\", r1, r2);",
+        choices: &[
+            "let r2 = &mut s;",
+            "let r1 = &mut s;",
+            "let mut s = String::from(\"hi\");",
+            "None of them",
+        ],
+        explanation: "Only one mutable reference may be in use at a time, and r1 is still used by the println!.",
+    },
+    Question {
+        section: "slices",
+        prompt: "What is the type of `&s[0..5]` when s is a String?",
+        choices: &["&str", "String", "&String", "[char; 5]"],
+        explanation: "A string slice borrows part of the String without copying it; its type is &str, like a string literal's.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_drop_order), snippet!(predict_moves)];
+
 fn basic_ownership_rules() {
     println!("1. Basic Ownership Rules:");
     println!("========================\n");
@@ -56,7 +122,7 @@ fn basic_ownership_rules() {
     let s2 = s1; // s1's value MOVES to s2 (ownership transfer)
     // println!("s1: {}", s1);  // COMPILE ERROR: s1 no longer owns the value!
     println!("s2 now owns: '{}'", s2);
-    println!("s1 is no longer valid after the move");
+    explain!("s1 is no longer valid after the move");
 
     println!("\nRULE 3: When the owner goes out of scope, the value is dropped");
     println!("----------------------------------------------------------");
@@ -65,7 +131,7 @@ fn basic_ownership_rules() {
         println!("s3 in scope: '{}'", s3);
         // s3 will be dropped when this block ends
     } // s3 goes out of scope and is dropped (memory freed)
-    println!("s3 has been dropped and memory freed");
+    explain!("s3 has been dropped and memory freed");
 
     println!("\nSTACK vs HEAP - Understanding Memory Allocation:");
     println!("===============================================");
@@ -74,13 +140,13 @@ fn basic_ownership_rules() {
     let x = 5; // Stack allocated - fixed size, fast access
     let y = x; // COPY (not move) - because i32 implements Copy trait
     println!("Stack: x = {}, y = {} (both valid after assignment)", x, y);
-    println!("Stack allocation: fixed size, fast, automatic cleanup");
+    explain!("Stack allocation: fixed size, fast, automatic cleanup");
 
     // Heap allocation (Move types)
     let s4 = String::from("hello"); // Heap allocated - dynamic size
     let s5 = s4; // MOVE (not copy) - ownership transferred
     println!("Heap: s5 = '{}' (s4 is no longer valid)", s5);
-    println!("Heap allocation: dynamic size, slower, manual cleanup via ownership");
+    explain!("Heap allocation: dynamic size, slower, manual cleanup via ownership");
 
     println!("\nCopy vs Move - Understanding the Difference:");
     println!("===========================================");
@@ -107,10 +173,10 @@ fn ownership_and_functions() {
 
     let s = String::from("hello");
     println!("Before function call: '{}'", s);
-    println!("s owns the String");
+    explain!("s owns the String");
 
     takes_ownership(s); // s's value MOVES into the function
-    println!("After function call: s is no longer valid");
+    explain!("After function call: s is no longer valid");
     // println!("s: {}", s);  // COMPILE ERROR: s was moved!
 
     println!("\nCOPY TYPES - No Ownership Transfer:");
@@ -131,7 +197,7 @@ fn ownership_and_functions() {
     println!("s2 owns: '{}'", s2);
     let s3 = takes_and_gives_back(s2); // s2 moves in, return value moves to s3
     println!("s3 received ownership: '{}'", s3);
-    println!("s2 is no longer valid");
+    explain!("s2 is no longer valid");
 
     println!("\nOWNERSHIP FLOW - Understanding the Journey:");
     println!("==========================================");
@@ -141,15 +207,21 @@ fn ownership_and_functions() {
 
     let moved = original; // Move 1: original → moved
     println!("2. moved owns: '{}'", moved);
-    println!("   original is no longer valid");
+    explain!("   original is no longer valid");
 
     let returned = takes_and_gives_back(moved); // Move 2: moved → function → returned
     println!("3. returned owns: '{}'", returned);
-    println!("   moved is no longer valid");
+    explain!("   moved is no longer valid");
 
     println!();
 }
 
+/// Two ways to explain why s1 is still usable after `&s1`: which one leaves
+/// learners answering the quiz better?
+const LENDING: &[&str] = &["plain", "analogy"];
+
+// Some variables only exist for the commented-out COMPILE ERROR lines
+#[allow(unused_variables)]
 fn references_and_borrowing() {
     println!("3. References and Borrowing:");
     println!("============================\n");
@@ -162,14 +234,20 @@ fn references_and_borrowing() {
 
     let len = calculate_length(&s1); // &s1 creates a reference (borrow)
     println!("The length of '{}' is {}.", s1, len);
-    println!("s1 is still valid after borrowing!");
+    match variants::pick("ownership::references_and_borrowing", LENDING) {
+        "analogy" => {
+            explain!("Like lending a book: calculate_length reads it and hands it back,");
+            explain!("so s1 still owns the String and is still valid");
+        }
+        _ => explain!("s1 is still valid after borrowing!"),
+    }
 
-    println!("\nIMMUTABLE REFERENCES - Read-Only Access:");
-    println!("=======================================");
+    explain!("\nIMMUTABLE REFERENCES - Read-Only Access:");
+    explain!("=======================================");
 
     let s2 = String::from("hello");
     // change(&s2);  // COMPILE ERROR: cannot borrow as mutable!
-    println!("Immutable references cannot modify the data");
+    explain!("Immutable references cannot modify the data");
 
     println!("\nMULTIPLE IMMUTABLE REFERENCES - Shared Read Access:");
     println!("==================================================");
@@ -182,7 +260,7 @@ fn references_and_borrowing() {
         "Multiple immutable references: r1='{}', r2='{}', r3='{}'",
         r1, r2, r3
     );
-    println!("All can read the same data simultaneously");
+    explain!("All can read the same data simultaneously");
 
     println!("\nREFERENCE LIFETIME - Understanding Scope:");
     println!("========================================");
@@ -200,18 +278,20 @@ fn references_and_borrowing() {
 
     let reference_to_nothing = dangle();
     println!("Reference: '{}'", reference_to_nothing);
-    println!("Rust prevents dangling references at compile time");
+    explain!("Rust prevents dangling references at compile time");
 
-    println!("\nBORROWING RULES SUMMARY:");
-    println!("========================");
-    println!("1. You can have any number of immutable references");
-    println!("2. You can have exactly one mutable reference");
-    println!("3. You cannot have both immutable and mutable references");
-    println!("4. References must always be valid");
+    detail!("\nBORROWING RULES SUMMARY:");
+    detail!("========================");
+    detail!("1. You can have any number of immutable references");
+    detail!("2. You can have exactly one mutable reference");
+    detail!("3. You cannot have both immutable and mutable references");
+    detail!("4. References must always be valid");
 
     println!();
 }
 
+// Some variables only exist for the commented-out COMPILE ERROR lines
+#[allow(unused_mut)]
 fn mutable_references() {
     println!("4. Mutable References:");
     println!("======================\n");
@@ -231,7 +311,7 @@ fn mutable_references() {
     let r1 = &mut s1; // First mutable reference
     println!("r1: '{}'", r1);
     // let r2 = &mut s1;  // COMPILE ERROR: cannot borrow as mutable more than once!
-    println!("Cannot have multiple mutable references simultaneously");
+    explain!("Cannot have multiple mutable references simultaneously");
 
     println!("\nIMMUTABLE vs MUTABLE - Cannot Mix:");
     println!("==================================");
@@ -241,7 +321,7 @@ fn mutable_references() {
     let r2 = &s2; // Another immutable reference
     println!("Immutable references: r1='{}', r2='{}'", r1, r2);
     // let r3 = &mut s2;  // COMPILE ERROR: cannot borrow as mutable!
-    println!("Cannot have mutable reference while immutable ones exist");
+    explain!("Cannot have mutable reference while immutable ones exist");
 
     println!("\nREFERENCE SCOPE - Understanding When References End:");
     println!("==================================================");
@@ -256,18 +336,20 @@ fn mutable_references() {
 
     let r3 = &mut s3; // Now we can have a mutable reference
     println!("Mutable reference: r3='{}'", r3);
-    println!("Previous immutable references are out of scope");
+    explain!("Previous immutable references are out of scope");
 
-    println!("\nMUTABLE REFERENCE RULES:");
-    println!("========================");
-    println!("1. Only one mutable reference at a time");
-    println!("2. Cannot have mutable and immutable references simultaneously");
-    println!("3. Mutable references can modify the data");
-    println!("4. Reference scope ends at last use");
+    detail!("\nMUTABLE REFERENCE RULES:");
+    detail!("========================");
+    detail!("1. Only one mutable reference at a time");
+    detail!("2. Cannot have mutable and immutable references simultaneously");
+    detail!("3. Mutable references can modify the data");
+    detail!("4. Reference scope ends at last use");
 
     println!();
 }
 
+// Some variables only exist for the commented-out COMPILE ERROR lines
+#[allow(unused_variables)]
 fn slices() {
     println!("5. Slices:");
     println!("==========\n");
@@ -297,7 +379,7 @@ fn slices() {
     let s1 = String::from("hello world");
     let word = first_word(&s1);
     println!("First word of '{}': '{}'", s1, word);
-    println!("word is of type &str (string slice)");
+    explain!("word is of type &str (string slice)");
 
     println!("\nARRAY SLICES - References to Array Data:");
     println!("========================================");
@@ -307,22 +389,22 @@ fn slices() {
 
     let slice = &a[1..3]; // Slice from index 1 to 2 (exclusive)
     println!("Array slice: {:?}", slice);
-    println!("Slice type: &[i32]");
+    explain!("Slice type: &[i32]");
 
-    println!("\nSLICE ADVANTAGES:");
-    println!("================");
-    println!("1. No copying of data");
-    println!("2. Efficient memory usage");
-    println!("3. Type safety (bounds checking)");
-    println!("4. Clear ownership semantics");
+    detail!("\nSLICE ADVANTAGES:");
+    detail!("================");
+    detail!("1. No copying of data");
+    detail!("2. Efficient memory usage");
+    detail!("3. Type safety (bounds checking)");
+    detail!("4. Clear ownership semantics");
 
-    println!("\nSLICE BOUNDS - Runtime Safety:");
-    println!("=============================");
+    explain!("\nSLICE BOUNDS - Runtime Safety:");
+    explain!("=============================");
 
     let s2 = String::from("hello");
     // let slice = &s2[0..10];  // This would panic at runtime!
-    println!("Slices are bounds-checked at runtime");
-    println!("Invalid slice ranges cause panic");
+    explain!("Slices are bounds-checked at runtime");
+    explain!("Invalid slice ranges cause panic");
 
     println!();
 }
@@ -345,7 +427,7 @@ fn ownership_with_collections() {
     let first = v.remove(0); // Ownership transferred from vector to first
     println!("Removed: '{}'", first);
     println!("Vector after removal: {:?}", v);
-    println!("first now owns the String");
+    explain!("first now owns the String");
 
     println!("\nITERATION WITH OWNERSHIP:");
     println!("=========================");
@@ -359,7 +441,7 @@ fn ownership_with_collections() {
         // s owns each String during iteration
     }
     // println!("v2: {:?}", v2);  // COMPILE ERROR: v2 was moved!
-    println!("v2 is no longer valid after iteration");
+    explain!("v2 is no longer valid after iteration");
 
     println!("\nITERATION WITH REFERENCES:");
     println!("==========================");
@@ -373,7 +455,7 @@ fn ownership_with_collections() {
         // s is a reference to each String
     }
     println!("v3 after iteration: {:?}", v3);
-    println!("v3 is still valid after iteration");
+    explain!("v3 is still valid after iteration");
 
     println!("\nITERATION WITH MUTABLE REFERENCES:");
     println!("==================================");
@@ -383,18 +465,18 @@ fn ownership_with_collections() {
 
     for s in &mut v4 {
         // v4 is mutably borrowed
-        s.push_str("!"); // Modify each String
+        s.push('!'); // Modify each String
         println!("Modified: '{}'", s);
     }
     println!("v4 after modification: {:?}", v4);
 
-    println!("\nCOLLECTION OWNERSHIP RULES:");
-    println!("===========================");
-    println!("1. Collections own their elements");
-    println!("2. Moving out transfers ownership");
-    println!("3. Iterating with 'for' moves the collection");
-    println!("4. Iterating with 'for &' borrows the collection");
-    println!("5. Iterating with 'for &mut' mutably borrows the collection");
+    detail!("\nCOLLECTION OWNERSHIP RULES:");
+    detail!("===========================");
+    detail!("1. Collections own their elements");
+    detail!("2. Moving out transfers ownership");
+    detail!("3. Iterating with 'for' moves the collection");
+    detail!("4. Iterating with 'for &' borrows the collection");
+    detail!("5. Iterating with 'for &mut' mutably borrows the collection");
 
     println!();
 }
@@ -409,8 +491,8 @@ fn advanced_ownership_patterns() {
     let s1 = String::from("hello");
     let s2 = s1.clone(); // Deep copy - both own their data
     println!("s1: '{}', s2: '{}'", s1, s2);
-    println!("Both s1 and s2 are valid after cloning");
-    println!("Clone is expensive but gives you ownership");
+    explain!("Both s1 and s2 are valid after cloning");
+    explain!("Clone is expensive but gives you ownership");
 
     println!("\nCOPY TRAIT - Automatic Copying:");
     println!("==============================");
@@ -418,7 +500,7 @@ fn advanced_ownership_patterns() {
     let x = 5;
     let y = x; // Copy (not move) - because i32 implements Copy
     println!("x: {}, y: {} (both valid after assignment)", x, y);
-    println!("Copy is cheap and automatic for simple types");
+    explain!("Copy is cheap and automatic for simple types");
 
     println!("\nSTRUCT OWNERSHIP - Fields Can Be Moved:");
     println!("======================================");
@@ -439,7 +521,8 @@ fn advanced_ownership_patterns() {
 
     let b = Box::new(5); // Allocate on heap, b owns the Box
     println!("Boxed value: {}", b);
-    println!("Box provides heap allocation with single ownership");
+    explain!("Box provides heap allocation with single ownership");
+    detail!("The boxes lesson goes further: recursive types, Box<dyn Trait> and Deref");
 
     println!("\nRC<T> - Shared Ownership (Single Thread):");
     println!("=========================================");
@@ -458,7 +541,7 @@ fn advanced_ownership_patterns() {
         "data: '{}', clone1: '{}', clone2: '{}'",
         data, data_clone1, data_clone2
     );
-    println!("All references point to the same data");
+    explain!("All references point to the same data");
 
     println!("\nARC<T> - Thread-Safe Shared Ownership:");
     println!("======================================");
@@ -467,7 +550,7 @@ fn advanced_ownership_patterns() {
     let shared_data = Arc::new(String::from("thread-safe data"));
     let shared_clone = Arc::clone(&shared_data);
     println!("shared_data: '{}', clone: '{}'", shared_data, shared_clone);
-    println!("Arc provides thread-safe reference counting");
+    explain!("Arc provides thread-safe reference counting");
 
     println!();
 }
@@ -476,39 +559,51 @@ fn memory_management_deep_dive() {
     println!("8. Memory Management Deep Dive:");
     println!("==============================\n");
 
-    println!("STACK vs HEAP - Detailed Comparison:");
-    println!("===================================");
+    deep!("STACK vs HEAP - Detailed Comparison:");
+    deep!("===================================");
+
+    deep!("STACK:");
+    deep!("- Fixed size, known at compile time");
+    deep!("- Fast allocation and deallocation");
+    deep!("- Automatic cleanup when variable goes out of scope");
+    deep!("- LIFO (Last In, First Out) structure");
+    deep!("- Used for: local variables, function parameters");
+
+    deep!("\nHEAP:");
+    deep!("- Dynamic size, unknown at compile time");
+    deep!("- Slower allocation and deallocation");
+    deep!("- Manual cleanup via ownership system");
+    deep!("- Can be fragmented");
+    deep!("- Used for: large data, data that outlives function");
+
+    deep!("\nON THE STACK, A String IS THREE WORDS:");
+    deep!("======================================");
+    deep!(
+        "size_of::<String>() = {} bytes (pointer, capacity, length)",
+        std::mem::size_of::<String>()
+    );
+    deep!(
+        "size_of::<&str>()   = {} bytes (pointer, length)",
+        std::mem::size_of::<&str>()
+    );
+    deep!("The characters themselves live on the heap, owned through that pointer\n");
 
-    println!("STACK:");
-    println!("- Fixed size, known at compile time");
-    println!("- Fast allocation and deallocation");
-    println!("- Automatic cleanup when variable goes out of scope");
-    println!("- LIFO (Last In, First Out) structure");
-    println!("- Used for: local variables, function parameters");
-
-    println!("\nHEAP:");
-    println!("- Dynamic size, unknown at compile time");
-    println!("- Slower allocation and deallocation");
-    println!("- Manual cleanup via ownership system");
-    println!("- Can be fragmented");
-    println!("- Used for: large data, data that outlives function");
-
-    println!("\nOWNERSHIP AND MEMORY SAFETY:");
-    println!("============================");
-
-    println!("1. No null pointer dereferences");
-    println!("2. No dangling pointers");
-    println!("3. No double frees");
-    println!("4. No use-after-free errors");
-    println!("5. No data races (with proper borrowing)");
-
-    println!("\nMEMORY LEAK PREVENTION:");
-    println!("=======================");
+    explain!("OWNERSHIP AND MEMORY SAFETY:");
+    explain!("============================");
+
+    explain!("1. No null pointer dereferences");
+    explain!("2. No dangling pointers");
+    explain!("3. No double frees");
+    explain!("4. No use-after-free errors");
+    explain!("5. No data races (with proper borrowing)");
 
-    println!("- Automatic cleanup when owner goes out of scope");
-    println!("- No manual memory management required");
-    println!("- Compiler ensures all memory is freed");
-    println!("- No garbage collection overhead");
+    detail!("\nMEMORY LEAK PREVENTION:");
+    detail!("=======================");
+
+    detail!("- Automatic cleanup when owner goes out of scope");
+    detail!("- No manual memory management required");
+    detail!("- Compiler ensures all memory is freed");
+    detail!("- No garbage collection overhead");
 
     println!();
 }
@@ -546,12 +641,13 @@ fn ownership_with_custom_types() {
     let text = String::from("hello world");
     let word = first_word(&text);
     println!("text: '{}', first word: '{}'", text, word);
-    println!("word is a reference to part of text");
+    explain!("word is a reference to part of text");
 
     println!("\nOWNERSHIP IN ENUMS:");
     println!("===================");
 
     #[derive(Debug)]
+    #[allow(dead_code)]
     enum Message {
         Quit,                       // No data
         Move { x: i32, y: i32 },    // Copy data
@@ -569,9 +665,9 @@ fn ownership_with_custom_types() {
     println!();
 }
 
-fn advanced_borrowing_patterns() {
-    println!("10. Advanced Borrowing Patterns:");
-    println!("================================\n");
+fn borrowing_with_lifetimes() {
+    println!("10. Borrowing With Lifetimes:");
+    println!("=============================\n");
 
     println!("BORROWING WITH LIFETIMES:");
     println!("=========================");
@@ -610,12 +706,12 @@ fn advanced_borrowing_patterns() {
     borrows_mutably();
     println!("After closure: {:?}", list);
 
-    println!("\nADVANCED BORROWING RULES:");
-    println!("=========================");
-    println!("1. References must always be valid");
-    println!("2. You can't have data races");
-    println!("3. You can't have use-after-free");
-    println!("4. The compiler enforces these rules");
+    detail!("\nADVANCED BORROWING RULES:");
+    detail!("=========================");
+    detail!("1. References must always be valid");
+    detail!("2. You can't have data races");
+    detail!("3. You can't have use-after-free");
+    detail!("4. The compiler enforces these rules");
 
     println!();
 }
@@ -624,12 +720,12 @@ fn advanced_borrowing_patterns() {
 
 fn takes_ownership(some_string: String) {
     println!("takes_ownership: '{}'", some_string);
-    println!("some_string owns the data");
+    explain!("some_string owns the data");
 } // some_string goes out of scope and is dropped
 
 fn makes_copy(some_integer: i32) {
     println!("makes_copy: {}", some_integer);
-    println!("some_integer is copied, not moved");
+    explain!("some_integer is copied, not moved");
 } // some_integer goes out of scope, but nothing special happens
 
 fn gives_ownership() -> String {
@@ -669,7 +765,7 @@ fn first_word(s: &str) -> &str {
         }
     }
 
-    &s[..]
+    s
 }
 
 fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
@@ -688,7 +784,26 @@ struct ImportantExcerpt<'a> {
     part: &'a str,
 }
 
-// Main function to run all ownership examples
-fn main() {
-    ownership();
+fn predict_drop_order() {
+    struct Noisy(&'static str);
+    impl Drop for Noisy {
+        fn drop(&mut self) {
+            println!("drop {}", self.0);
+        }
+    }
+    let _a = Noisy("a");
+    let b = Noisy("b");
+    {
+        let _c = Noisy("c");
+        println!("inner scope ends");
+    }
+    drop(b);
+    println!("function ends");
+}
+
+fn predict_moves() {
+    let s1 = String::from("hi");
+    let s2 = s1.clone();
+    let s3 = s1;
+    println!("{} {}", s2, s3.len());
 }
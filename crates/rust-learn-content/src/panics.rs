@@ -0,0 +1,457 @@
+/// Panics in Rust - Unwinding and catch_unwind
+///
+/// `Result` is for failures the caller can expect and handle: a missing
+/// file, a typo in the input. A panic is for bugs: an index past the end, a
+/// state the code promised could not happen. Panicking unwinds the thread's
+/// stack, dropping everything on it, and normally ends the thread (and with
+/// it the program, when that is main). This lesson panics on purpose with
+/// each of the panicking macros, reads the message and the backtrace,
+/// recovers with `std::panic::catch_unwind`, watches destructors run during
+/// unwinding, and tests that code panics when it should.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::any::Any;
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::cell::RefCell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+
+pub fn panics() {
+    println!("=== Panics and Unwinding Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- panics <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(panic_or_result, "Panic or Result?"),
+    section!(panicking_macros, "panic!, unreachable!, todo! and Friends"),
+    section!(messages_and_backtraces, "The Message and RUST_BACKTRACE"),
+    section!(catching_panics, "Recovering with catch_unwind").with_checkpoint(
+        "Does catch_unwind still catch a panic in a build with panic = \"abort\"?",
+        false,
+        "With abort the process ends at the panic; there is no unwinding for catch_unwind to stop.",
+    ),
+    section!(unwinding_drops, "Unwinding Runs Destructors"),
+    section!(testing_panics, "Testing Panics with #[should_panic]"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "panic_or_result",
+        prompt: "A function reads a port number the user typed. How should it report \"80a0\"?",
+        choices: &[
+            "Return an Err: bad input is a failure the caller can expect",
+            "panic!, since the number is wrong",
+            "unreachable!, since a port is always a number",
+            "Return 0",
+        ],
+        explanation: "Panics are for bugs in the program; input the user got wrong is an Err for the caller to handle.",
+    },
+    Question {
+        section: "panicking_macros",
+        prompt: "What is todo!() for?",
+        choices: &[
+            "Code not written yet: it compiles as any type and panics if reached",
+            "A comment the compiler collects into a list",
+            "Code that can never run",
+            "Skipping a test",
+        ],
+        explanation: "todo!() has type !, so a function with only todo!() type-checks; running it panics with \"not yet implemented\".",
+    },
+    Question {
+        section: "catching_panics",
+        prompt: "Why does catch_unwind want an UnwindSafe closure?",
+        choices: &[
+            "Data the closure changed may be half-updated after a panic, and then still used",
+            "Panics cannot cross closures otherwise",
+            "It needs the closure to be Send",
+            "To make the closure run faster",
+        ],
+        explanation: "AssertUnwindSafe is the promise that nothing broken by the panic is looked at afterwards, or that it is checked.",
+    },
+    Question {
+        section: "unwinding_drops",
+        prompt: "A thread panics while holding a Mutex lock. What does the next lock() return?",
+        choices: &[
+            "Err(PoisonError), which still gives access to the data",
+            "It blocks forever",
+            "The lock as usual; Mutex does not notice",
+            "It panics at once",
+        ],
+        explanation: "The guard's drop during unwinding marks the Mutex poisoned; into_inner() on the error gets the data back.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_unwind_order)];
+
+/// A panic caught by `catch`: its message and where it happened
+#[derive(Debug)]
+struct Caught {
+    message: String,
+    location: String,
+}
+
+/// Only one section swaps the panic hook at a time, so each puts back the
+/// one it found
+static HOOK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Where the last panic on this thread happened, as the quiet hook saw it
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f` with a panic hook that only notes where a panic happened.
+/// rust-learn's own hook prints the message and writes a crash report, and
+/// the panics in this lesson are on purpose
+fn with_quiet_hook<T>(f: impl FnOnce() -> T) -> T {
+    let _one_at_a_time = HOOK.lock().unwrap_or_else(PoisonError::into_inner);
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(|info| {
+        let location = info
+            .location()
+            .map(|location| format!("{}:{}", file_name(location.file()), location.line()));
+        LOCATION.with(|last| *last.borrow_mut() = location);
+    }));
+    let result = f();
+    panic::set_hook(previous);
+    result
+}
+
+/// Run `f`, turning a panic into an `Err` instead of letting it unwind
+/// further
+fn catch<T>(f: impl FnOnce() -> T) -> Result<T, Caught> {
+    let result = with_quiet_hook(|| panic::catch_unwind(AssertUnwindSafe(f)));
+    result.map_err(|payload| Caught {
+        message: message(&*payload),
+        location: LOCATION
+            .with(|last| last.borrow_mut().take())
+            .unwrap_or_default(),
+    })
+}
+
+/// The text a panic carries: `panic!("literal")` carries a `&'static str`,
+/// `panic!("{}", x)` a `String`, and `panic_any` whatever it was given
+fn message(payload: &(dyn Any + Send)) -> String {
+    if let Some(text) = payload.downcast_ref::<&str>() {
+        text.to_string()
+    } else if let Some(text) = payload.downcast_ref::<String>() {
+        text.clone()
+    } else {
+        "<not a string>".to_string()
+    }
+}
+
+fn panic_or_result() {
+    println!("1. Panic or Result?:");
+    println!("====================");
+
+    let typed = "80a0".parse::<u16>();
+    println!("\"80a0\".parse::<u16>() -> {:?}", typed);
+    explain!("The user typed it wrong: a failure the caller can expect, so it is an Err");
+
+    let scores = vec![90, 72, 85];
+    let past_the_end = scores.len();
+    match catch(|| scores[past_the_end]) {
+        Ok(score) => println!("scores[{}] = {}", past_the_end, score),
+        Err(caught) => println!("scores[{}] panicked: {}", past_the_end, caught.message),
+    }
+    explain!("An index past the end is a bug in the program: no caller can fix it, so it panics");
+    explain!("  scores.get(3) is the Result-like version, for when \"not there\" is expected");
+
+    let rows = [
+        ("Bad input, a missing file", "Result", "the caller decides"),
+        (
+            "A broken invariant, a bug",
+            "panic",
+            "stop before doing harm",
+        ),
+        ("Tests and examples", "unwrap/expect", "a panic fails them"),
+        (
+            "Setup that cannot fail",
+            "expect(\"why\")",
+            "says what was assumed",
+        ),
+    ];
+    println!();
+    for (situation, tool, why) in rows {
+        println!("{:<27} {:<15} {}", situation, tool, why);
+    }
+    detail!("expect(\"...\") reads as the assumption that was broken, better than unwrap()");
+    println!();
+}
+
+fn panicking_macros() {
+    println!("2. panic!, unreachable!, todo! and Friends:");
+    println!("===========================================");
+
+    let cases: [(&str, fn()); 6] = [
+        ("panic!", || panic!("no [server] table in the config")),
+        ("unreachable!", || {
+            unreachable!("a finished job was started again")
+        }),
+        ("todo!", || todo!("retry with backoff")),
+        ("unimplemented!", || unimplemented!("IPv6 addresses")),
+        ("assert_eq!", || assert_eq!(2 + 2, 5, "two workers each")),
+        ("expect", || {
+            loaded_config().expect("the config was loaded at start-up");
+        }),
+    ];
+    for (name, case) in &cases {
+        let caught = catch(*case).unwrap_err();
+        println!(
+            "{:<15} {}",
+            name,
+            caught.message.replace('\n', "\n                ")
+        );
+    }
+    explain!("panic!: this must not go on. unreachable!: the code promised this cannot happen");
+    explain!("todo!/unimplemented!: not written (yet); they compile as any type, so");
+    explain!("  `fn retry() -> Duration {{ todo!() }}` type-checks while you work on the rest");
+    detail!(
+        "assert!/assert_eq! check invariants in normal builds too; debug_assert! only in debug"
+    );
+    println!();
+}
+
+fn messages_and_backtraces() {
+    println!("3. The Message and RUST_BACKTRACE:");
+    println!("==================================");
+
+    let caught = catch(|| {
+        let jobs: Vec<u32> = Vec::new();
+        jobs[0]
+    })
+    .unwrap_err();
+    println!("thread 'main' panicked at {}:", caught.location);
+    println!("{}", caught.message);
+    println!("note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace");
+    explain!("That is what the default hook prints: where, what, and how to see the calls");
+    explain!("  that led there. The location is the line that indexed, not inside Vec:");
+    explain!("  indexing is #[track_caller], so the caller gets the blame");
+
+    println!();
+    let setting = std::env::var("RUST_BACKTRACE").unwrap_or_else(|_| "(not set)".to_string());
+    println!("RUST_BACKTRACE = {}", setting);
+    let status = match Backtrace::capture().status() {
+        BacktraceStatus::Captured => "captured",
+        BacktraceStatus::Disabled => "disabled",
+        _ => "not supported here",
+    };
+    println!("Backtrace::capture() -> {}", status);
+    explain!("RUST_BACKTRACE=1 prints the calls with the panic; =full adds the runtime's frames");
+
+    let backtrace = Backtrace::force_capture().to_string();
+    let ours: Vec<&str> = backtrace
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.contains("panics::"))
+        .take(3)
+        .collect();
+    if ours.is_empty() {
+        println!("(no function names: this build has no debug info)");
+    }
+    for line in ours {
+        println!("  {}", line);
+    }
+    detail!("force_capture() takes one whatever the variable says: the frames above are ours");
+    deep!("RUST_LIB_BACKTRACE sets capture() apart from panics, for error types that keep one");
+    println!();
+}
+
+/// The mean of a job's values; a job with none is a bug that divides by zero
+fn average(values: &[u32]) -> u32 {
+    values.iter().sum::<u32>() / values.len() as u32
+}
+
+/// Run every job, catching a panic in one so the rest still run
+fn run_jobs(jobs: &[&[u32]]) -> Vec<Result<u32, Caught>> {
+    jobs.iter().map(|job| catch(|| average(job))).collect()
+}
+
+fn catching_panics() {
+    println!("4. Recovering with catch_unwind:");
+    println!("================================");
+
+    let jobs: [&[u32]; 3] = [&[3, 5, 7], &[], &[10, 20]];
+    for (index, result) in run_jobs(&jobs).iter().enumerate() {
+        match result {
+            Ok(mean) => println!("job {}: mean {}", index + 1, mean),
+            Err(caught) => println!("job {}: panicked: {}", index + 1, caught.message),
+        }
+    }
+    explain!("catch_unwind(f) runs f and returns Err(payload) if it panicked, stopping the");
+    explain!("  unwinding there: one bad job is reported, and the rest still run");
+    explain!("`rust-learn run --all` does the same for each lesson (rust-learn-core runner.rs)");
+
+    println!();
+    let payloads = [
+        catch(|| panic!("a literal")),
+        catch(|| panic!("job {} failed", 2)),
+        catch(|| panic::panic_any(404_u16)),
+    ];
+    for caught in payloads.iter().filter_map(|result| result.as_ref().err()) {
+        println!("payload: {}", caught.message);
+    }
+    explain!("The payload is a Box<dyn Any + Send>: downcast it to &str or String for the text");
+
+    println!();
+    let mut totals = vec![1, 2];
+    let _ = catch(|| {
+        totals.push(3);
+        panic!("halfway through");
+    });
+    println!("totals after the panic: {:?}", totals);
+    explain!("catch_unwind wants an UnwindSafe closure: &mut data the panic left half-updated");
+    explain!("  is not. AssertUnwindSafe(f) promises the caller checks, or does not care");
+    detail!("Do not use catch_unwind as try/catch: it is for boundaries, like a job runner,");
+    detail!("  a thread pool, or a function C calls (see the ffi lesson)");
+    deep!("With panic = \"abort\" (the release_profiles lesson) nothing is caught at all");
+    println!();
+}
+
+/// Says when it is dropped, and whether the thread is unwinding then
+struct Noisy(&'static str);
+
+impl Drop for Noisy {
+    fn drop(&mut self) {
+        println!("  drop {} (unwinding: {})", self.0, thread::panicking());
+    }
+}
+
+fn unwinding_drops() {
+    println!("5. Unwinding Runs Destructors:");
+    println!("==============================");
+
+    let _ = catch(|| {
+        let _file = Noisy("file");
+        let _buffer = Noisy("buffer");
+        println!("  panicking with both alive");
+        panic!("disk full");
+    });
+    explain!("Unwinding drops every value on the stack, newest first, as a return would:");
+    explain!("  files are closed and locks released even when the code panics");
+
+    println!();
+    let queue = Arc::new(Mutex::new(vec![1, 2]));
+    let worker = Arc::clone(&queue);
+    let joined = with_quiet_hook(|| {
+        thread::spawn(move || {
+            let mut jobs = worker.lock().unwrap();
+            jobs.push(3);
+            panic!("worker crashed holding the lock");
+        })
+        .join()
+    });
+    println!(
+        "worker joined: {}",
+        if joined.is_ok() { "Ok" } else { "Err" }
+    );
+    match queue.lock() {
+        Ok(jobs) => println!("lock: Ok({:?})", *jobs),
+        Err(poisoned) => println!("lock: poisoned, data {:?}", *poisoned.into_inner()),
+    }
+    explain!("A panic on another thread ends only that thread; join() returns it as an Err");
+    explain!("The lock guard dropped during unwinding poisons the Mutex: the data may be");
+    explain!("  half-updated, so the next lock() says so, and into_inner() still gets it");
+    deep!("A panic inside a Drop that runs during unwinding aborts the whole process");
+    println!();
+}
+
+/// A percentage; a whole of 0 is a caller's bug
+fn percent(part: u32, whole: u32) -> u32 {
+    assert!(whole > 0, "percent of nothing: whole is 0");
+    part * 100 / whole
+}
+
+fn testing_panics() {
+    println!("6. Testing Panics with #[should_panic]:");
+    println!("=======================================");
+
+    let test = [
+        "#[test]",
+        "#[should_panic(expected = \"whole is 0\")]",
+        "fn percent_of_nothing_panics() {",
+        "    percent(1, 0);",
+        "}",
+    ];
+    for line in test {
+        println!("  {}", line);
+    }
+    println!("percent(1, 4) = {}", percent(1, 4));
+    if let Err(caught) = catch(|| percent(1, 0)) {
+        println!("percent(1, 0) panicked: {}", caught.message);
+    }
+    explain!("#[should_panic] passes when the test panics, and fails when it returns");
+    explain!("expected = \"...\" also checks the message contains that text, so a panic from");
+    explain!("  somewhere else (an index, an unwrap) does not make the test pass by accident");
+    detail!("A #[should_panic] test must return (); one returning Result cannot use it");
+    detail!("The tests at the bottom of this file are real: cargo test -p rust-learn-content");
+    println!();
+}
+
+// Helper functions
+
+/// The config, which nothing has loaded
+fn loaded_config() -> Option<&'static str> {
+    None
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit(['/', '\\']).next().unwrap_or(path)
+}
+
+fn predict_unwind_order() {
+    let result: Result<(), Caught> = catch(|| {
+        let _first = Noisy("first");
+        let _second = Noisy("second");
+        println!("about to panic");
+        panic!("boom");
+    });
+    if let Err(caught) = result {
+        println!("caught: {}", caught.message);
+    }
+    println!("still running");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_and_jobs() {
+        let caught = catch(|| panic!("job {} failed", 7)).unwrap_err();
+        assert_eq!(caught.message, "job 7 failed");
+        assert!(
+            caught.location.starts_with("panics.rs:"),
+            "{}",
+            caught.location
+        );
+        assert_eq!(catch(|| 5).unwrap(), 5);
+
+        let jobs: [&[u32]; 3] = [&[3, 5, 7], &[], &[10, 20]];
+        let results = run_jobs(&jobs);
+        assert_eq!(results[0].as_ref().unwrap(), &5);
+        assert_eq!(
+            results[1].as_ref().unwrap_err().message,
+            "attempt to divide by zero"
+        );
+        assert_eq!(results[2].as_ref().unwrap(), &15);
+    }
+
+    #[test]
+    #[should_panic(expected = "whole is 0")]
+    fn test_percent_of_nothing_panics() {
+        percent(1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "divide by zero")]
+    fn test_average_of_no_values_panics() {
+        average(&[]);
+    }
+}
@@ -0,0 +1,458 @@
+/// Memory-Mapped Registers in Rust - A Safe Sandbox for Embedded Ideas
+///
+/// On a microcontroller, peripherals are controlled by reading and writing
+/// fixed memory addresses. This lesson fakes a GPIO port and a UART with an
+/// ordinary `#[repr(C)]` struct in memory, accessed through a `Volatile<T>`
+/// wrapper, and covers bitfields, read-modify-write hazards, and typestates
+/// that make "use before init" a compile error.
+use crate::registry::{Question, Section, section};
+use std::cell::UnsafeCell;
+use std::marker::PhantomData;
+use std::mem::offset_of;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+pub fn registers() {
+    println!("=== Registers Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- registers <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(memory_mapped_registers, "Memory-Mapped Registers"),
+    section!(volatile_access, "Volatile Access"),
+    section!(bitfields, "Bitfields: Masks and Shifts"),
+    section!(read_modify_write, "Read-Modify-Write Hazards"),
+    section!(
+        typestate_initialization,
+        "Typestate Peripheral Initialization"
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "volatile_access",
+        prompt: "Why access a register with read_volatile instead of a plain read?",
+        choices: &[
+            "The compiler may not skip, merge or reorder a volatile access",
+            "It is faster",
+            "It makes the read atomic",
+            "It checks the address is valid",
+        ],
+        explanation: "A plain read of the same address twice can be merged into one, but reading a register can have side effects.",
+    },
+    Question {
+        section: "read_modify_write",
+        prompt: "An interrupt sets bits in a register with a read-modify-write while main code does the same. What can go wrong?",
+        choices: &[
+            "One write can undo the other's change",
+            "The register resets",
+            "Nothing; each write is atomic",
+            "The compiler rejects it",
+        ],
+        explanation: "Both read the old value before either writes, so the second write puts back what the first changed.",
+    },
+];
+
+/// One hardware register. Every access is volatile, so the compiler may not
+/// skip, merge or reorder it - on real hardware a read can have side effects.
+#[repr(transparent)]
+struct Volatile<T> {
+    value: UnsafeCell<T>,
+}
+
+impl<T: Copy> Volatile<T> {
+    const fn new(value: T) -> Self {
+        Volatile {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    fn read(&self) -> T {
+        // SAFETY: the pointer comes from a live UnsafeCell, and the sandbox is
+        // single-threaded (Volatile is not Sync), so nothing writes concurrently
+        unsafe { self.value.get().read_volatile() }
+    }
+
+    fn write(&self, value: T) {
+        // SAFETY: as in `read`
+        unsafe { self.value.get().write_volatile(value) }
+    }
+
+    /// Read, change, write back: three steps, not one
+    fn modify(&self, f: impl FnOnce(T) -> T) {
+        self.write(f(self.read()));
+    }
+}
+
+/// A GPIO port laid out like an STM32's: the field order *is* the address map
+#[repr(C)]
+struct GpioRegisters {
+    /// Two bits per pin: 00 input, 01 output, 10 alternate function, 11 analog
+    mode: Volatile<u32>,
+    /// Output level, one bit per pin
+    output: Volatile<u32>,
+    /// Write 1 to bit n to set pin n, to bit n + 16 to clear it; reads as 0
+    set_reset: Volatile<u32>,
+}
+
+impl GpioRegisters {
+    const fn new() -> Self {
+        GpioRegisters {
+            mode: Volatile::new(0),
+            output: Volatile::new(0),
+            set_reset: Volatile::new(0),
+        }
+    }
+
+    /// What the hardware does on its own when set_reset is written
+    fn apply_set_reset(&self) {
+        let request = self.set_reset.read();
+        let set = request & 0xFFFF;
+        let clear = request >> 16;
+        self.output.write((self.output.read() | set) & !clear);
+        self.set_reset.write(0);
+    }
+}
+
+/// The `width` bits of `value` starting at bit `offset`
+fn get_field(value: u32, offset: u32, width: u32) -> u32 {
+    let mask = (1 << width) - 1;
+    (value >> offset) & mask
+}
+
+/// `value` with the `width` bits at `offset` replaced by `field`
+fn set_field(value: u32, offset: u32, width: u32, field: u32) -> u32 {
+    let mask = ((1 << width) - 1) << offset;
+    (value & !mask) | ((field << offset) & mask)
+}
+
+fn memory_mapped_registers() {
+    println!("1. Memory-Mapped Registers:");
+    println!("===========================\n");
+
+    explain!("ON REAL HARDWARE:");
+    explain!("=================");
+    explain!("const GPIOA: *const GpioRegisters = 0x4002_0000 as *const _;");
+    explain!("let gpioa = unsafe {{ &*GPIOA }};  // the struct is overlaid on the address");
+    explain!("gpioa.output.write(1 << 5);        // turns on the LED on pin 5");
+
+    println!("\nIN THIS SANDBOX:");
+    println!("================");
+    explain!("The same struct lives in ordinary memory, so nothing can go wrong.");
+    let gpio = GpioRegisters::new();
+    let base = &gpio as *const GpioRegisters as usize;
+    println!("Simulated GPIO block at {:#x}", base);
+
+    println!("\nTHE LAYOUT (#[repr(C)] keeps field order):");
+    println!("==========================================");
+    let offsets = [
+        ("mode", offset_of!(GpioRegisters, mode)),
+        ("output", offset_of!(GpioRegisters, output)),
+        ("set_reset", offset_of!(GpioRegisters, set_reset)),
+    ];
+    for (name, offset) in offsets {
+        println!(
+            "{:<10} offset {:#04x}  address {:#x}",
+            name,
+            offset,
+            base + offset
+        );
+    }
+    explain!("Without repr(C) the compiler could reorder fields and every offset would be wrong.");
+
+    println!();
+}
+
+fn volatile_access() {
+    println!("2. Volatile Access:");
+    println!("===================\n");
+
+    explain!("WHY NOT A PLAIN u32:");
+    explain!("====================");
+    explain!("// The compiler sees two writes and keeps only the last one,");
+    explain!("// but the hardware was supposed to see both (start, then stop).");
+    explain!("*control = START;");
+    explain!("*control = STOP;");
+    println!();
+    explain!("// The compiler sees a value that never changes and reads it once,");
+    explain!("// but the hardware sets the READY bit while we wait.");
+    explain!("while *status & READY == 0 {{}}");
+
+    explain!("\nTHE WRAPPER:");
+    explain!("============");
+    explain!("#[repr(transparent)]");
+    explain!("struct Volatile<T> {{ value: UnsafeCell<T> }}");
+    println!();
+    explain!("fn read(&self) -> T      {{ unsafe {{ self.value.get().read_volatile() }} }}");
+    explain!("fn write(&self, value: T) {{ unsafe {{ self.value.get().write_volatile(value) }} }}");
+
+    println!("\nIN ACTION:");
+    println!("==========");
+    let gpio = GpioRegisters::new();
+    gpio.output.write(0b1);
+    gpio.output.write(0b11);
+    println!(
+        "Two volatile writes, both performed; output = {:#06b}",
+        gpio.output.read()
+    );
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- UnsafeCell lets a register change through a shared & reference");
+    explain!("- repr(transparent) makes Volatile<u32> exactly as big as a u32");
+    explain!("- Volatile is about the compiler, not threads: it is not atomic");
+
+    println!();
+}
+
+fn bitfields() {
+    println!("3. Bitfields: Masks and Shifts:");
+    println!("===============================\n");
+
+    explain!("THE HELPERS:");
+    explain!("============");
+    explain!("get: (value >> offset) & ((1 << width) - 1)");
+    explain!("set: (value & !mask) | ((field << offset) & mask)");
+
+    let gpio = GpioRegisters::new();
+    println!("\nPIN MODES (2 bits per pin in the mode register):");
+    println!("================================================");
+    const OUTPUT: u32 = 0b01;
+    const ALTERNATE: u32 = 0b10;
+    gpio.mode.modify(|mode| set_field(mode, 5 * 2, 2, OUTPUT));
+    gpio.mode
+        .modify(|mode| set_field(mode, 2 * 2, 2, ALTERNATE));
+    println!("mode = {:#034b}", gpio.mode.read());
+    for pin in [2, 5, 7] {
+        let mode = get_field(gpio.mode.read(), pin * 2, 2);
+        let name = match mode {
+            0b00 => "input",
+            0b01 => "output",
+            0b10 => "alternate",
+            _ => "analog",
+        };
+        println!("pin {} mode {:02b} ({})", pin, mode, name);
+    }
+
+    println!("\nSETTING A FIELD LEAVES ITS NEIGHBOURS ALONE:");
+    println!("============================================");
+    let before = gpio.mode.read();
+    gpio.mode.modify(|mode| set_field(mode, 5 * 2, 2, 0b11));
+    println!("before {:#034b}", before);
+    println!(
+        "after  {:#034b}  (only bits 10-11 changed)",
+        gpio.mode.read()
+    );
+
+    println!();
+}
+
+fn read_modify_write() {
+    println!("4. Read-Modify-Write Hazards:");
+    println!("=============================\n");
+
+    println!("THE RACE:");
+    println!("=========");
+    explain!("main turns on pin 0 while an interrupt turns on pin 1, both with");
+    explain!("`output.modify(|v| v | bit)`. Step by step, the interrupt arriving");
+    explain!("between main's read and main's write:\n");
+
+    let gpio = GpioRegisters::new();
+    let main_read = gpio.output.read();
+    println!("main      reads  output = {:#06b}", main_read);
+    let irq_read = gpio.output.read();
+    println!("interrupt reads  output = {:#06b}", irq_read);
+    gpio.output.write(irq_read | 0b10);
+    println!("interrupt writes output = {:#06b}", gpio.output.read());
+    gpio.output.write(main_read | 0b01);
+    println!("main      writes output = {:#06b}", gpio.output.read());
+    explain!("Pin 1 is off again: main wrote back a value it read too early.");
+
+    println!("\nFIX 1: SET/RESET REGISTERS:");
+    println!("===========================");
+    explain!("Hardware with a write-only set/reset register needs no read at all:");
+    let gpio = GpioRegisters::new();
+    gpio.set_reset.write(1 << 1);
+    gpio.apply_set_reset();
+    println!(
+        "interrupt writes set_reset = 1 << 1   -> output = {:#06b}",
+        gpio.output.read()
+    );
+    gpio.set_reset.write(1 << 0);
+    gpio.apply_set_reset();
+    println!(
+        "main      writes set_reset = 1 << 0   -> output = {:#06b}",
+        gpio.output.read()
+    );
+    gpio.set_reset.write(1 << 16);
+    gpio.apply_set_reset();
+    println!(
+        "main      writes set_reset = 1 << 16  -> output = {:#06b}",
+        gpio.output.read()
+    );
+
+    explain!("\nFIX 2: CRITICAL SECTIONS:");
+    explain!("=========================");
+    explain!("critical_section::with(|_| gpio.output.modify(|v| v | 0b01));");
+    explain!("Interrupts are masked for the three steps, so nothing can come between.");
+
+    println!();
+}
+
+/// Register block of a tiny simulated UART
+#[repr(C)]
+struct UartRegisters {
+    /// Bit 0: enable
+    control: Volatile<u32>,
+    baud_divisor: Volatile<u32>,
+    /// Writing a byte here sends it
+    data: Volatile<u32>,
+}
+
+/// Clock the simulated UART runs from
+const CLOCK_HZ: u32 = 16_000_000;
+
+/// Typestates: zero-sized markers that only exist in the type
+struct Disabled;
+struct Enabled;
+
+struct Uart<'a, State> {
+    registers: &'a UartRegisters,
+    /// Bytes that reached the data register, so the lesson can show them
+    sent: Vec<u8>,
+    _state: PhantomData<State>,
+}
+
+impl<'a> Uart<'a, Disabled> {
+    fn new(registers: &'a UartRegisters) -> Self {
+        Uart {
+            registers,
+            sent: Vec::new(),
+            _state: PhantomData,
+        }
+    }
+
+    /// Consumes the disabled UART: the old value cannot be used afterwards
+    fn enable(self, baud: u32) -> Uart<'a, Enabled> {
+        self.registers.baud_divisor.write(CLOCK_HZ / baud);
+        self.registers.control.modify(|control| control | 1);
+        Uart {
+            registers: self.registers,
+            sent: self.sent,
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<'a> Uart<'a, Enabled> {
+    /// Only an enabled UART has `write`
+    fn write(&mut self, text: &str) {
+        for byte in text.bytes() {
+            self.registers.data.write(byte as u32);
+            self.sent.push(self.registers.data.read() as u8);
+        }
+    }
+
+    fn disable(self) -> Uart<'a, Disabled> {
+        self.registers.control.modify(|control| control & !1);
+        Uart {
+            registers: self.registers,
+            sent: self.sent,
+            _state: PhantomData,
+        }
+    }
+}
+
+static UART_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// The UART can be taken once, like `Peripherals::take()` in embedded crates,
+/// so two drivers can never own the same hardware
+fn take_uart(registers: &UartRegisters) -> Option<Uart<'_, Disabled>> {
+    if UART_TAKEN.swap(true, Ordering::SeqCst) {
+        None
+    } else {
+        Some(Uart::new(registers))
+    }
+}
+
+fn typestate_initialization() {
+    println!("5. Typestate Peripheral Initialization:");
+    println!("=======================================\n");
+
+    explain!("THE TYPES:");
+    explain!("==========");
+    explain!("struct Uart<State> {{ registers: &UartRegisters, _state: PhantomData<State> }}");
+    explain!("impl Uart<Disabled> {{ fn enable(self, baud: u32) -> Uart<Enabled> }}");
+    explain!(
+        "impl Uart<Enabled>  {{ fn write(&mut self, text: &str); fn disable(self) -> Uart<Disabled> }}"
+    );
+
+    let registers = UartRegisters {
+        control: Volatile::new(0),
+        baud_divisor: Volatile::new(0),
+        data: Volatile::new(0),
+    };
+
+    println!("\nUSING IT:");
+    println!("=========");
+    // The section can run more than once in a process (e.g. the menu), so
+    // hand the UART back at the end instead of leaking the "taken" flag
+    let Some(uart) = take_uart(&registers) else {
+        println!("The UART is already taken");
+        return;
+    };
+    explain!("take_uart()  -> Some(Uart<Disabled>)");
+    println!(
+        "take_uart()  -> {:?}  (only one owner)",
+        take_uart(&registers).map(|_| "Uart")
+    );
+
+    // uart.write("hi");
+    // COMPILE ERROR: no method named `write` found for struct `Uart<'_, Disabled>`
+    explain!("uart.write(\"hi\") on Uart<Disabled> does not compile");
+
+    let mut uart = uart.enable(115_200);
+    println!(
+        "enable(115200): control = {:#b}, baud_divisor = {}",
+        registers.control.read(),
+        registers.baud_divisor.read()
+    );
+    uart.write("hi!");
+    println!(
+        "write(\"hi!\") sent {:?}",
+        String::from_utf8_lossy(&uart.sent)
+    );
+
+    let uart = uart.disable();
+    println!("disable(): control = {:#b}", registers.control.read());
+    // uart.write("more");  // COMPILE ERROR again: the Enabled value was moved
+    drop(uart);
+    UART_TAKEN.store(false, Ordering::SeqCst);
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- Disabled and Enabled are zero-sized: the state costs nothing at run time");
+    explain!("- enable(self) consumes the old value, so a stale Uart<Disabled> cannot linger");
+    explain!("- Ownership (take once) plus typestates is how embedded-hal drivers stay safe");
+
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_round_trip_without_touching_neighbours() {
+        let value = 0b1111_0000_1111;
+        let changed = set_field(value, 4, 4, 0b1010);
+        assert_eq!(changed, 0b1111_1010_1111);
+        assert_eq!(get_field(changed, 4, 4), 0b1010);
+        // Bits that do not fit in the field are dropped
+        assert_eq!(set_field(0, 0, 2, 0b111), 0b11);
+    }
+}
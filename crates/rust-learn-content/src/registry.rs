@@ -0,0 +1,1087 @@
+/// Lesson Registry
+///
+/// Every runnable lesson is listed here exactly once, in curriculum order.
+/// The menu and `cargo run -- <lesson> [section]` read this list, so adding a
+/// lesson means adding a module in lib.rs (with its own SECTIONS table) and
+/// one entry below, in its chapter and after the lessons it requires.
+/// Changing a lesson a learner may already have finished means adding a
+/// `Change` to its changelog, so they are told what is new.
+use crate::{
+    allocators, api_design, app_errors, array, async_await, boxes, browing, build_scripts,
+    closures, conditonal, const_let_mut_variables, cow, cross_compilation, custom_errors, enums,
+    error_handling, fast_input, ffi, generics, interior_mutability, iterators, loops, matching,
+    model_checking, no_std, options_type, ownership, panics, registers, release_profiles, semver,
+    shared_state, structs, trait_objects, traits, variables, vectors, wasi, weak_references,
+    workspaces,
+};
+pub use rust_learn_core::compile_errors::CompileError;
+use rust_learn_core::fuzzy::{self, Match};
+pub use rust_learn_core::interview::Problem;
+pub use rust_learn_core::lesson::{Change, Difficulty, Lesson, Question, Section, Snippet};
+pub use rust_learn_core::{section, snippet};
+use tracing::{debug, trace};
+
+/// Chapter titles; a lesson's `chapter` numbers into this from 1
+pub const CHAPTERS: &[&str] = &[
+    "Basics",
+    "Control Flow",
+    "Enums and Arrays",
+    "Ownership",
+    "Collections and Missing Values",
+    "Traits and Generics",
+    "Closures and Iterators",
+    "Error Handling",
+    "Crates and Projects",
+    "Systems Programming",
+    "Targets and Builds",
+    "Concurrency",
+];
+
+/// Question banks that ship with rust-learn, by file name; the quiz adds
+/// them to the lessons' own (`rust_learn_core::question_bank`)
+pub const BUNDLED_QUESTIONS: &[(&str, &str)] = &[(
+    "questions/basics.toml",
+    include_str!("../questions/basics.toml"),
+)];
+
+/// Programs that must not compile, for `rust-learn show-errors`
+/// (`rust_learn_core::compile_errors`), in lesson order
+pub const COMPILE_ERRORS: &[CompileError] = &[
+    CompileError {
+        lesson: "variables",
+        name: "assign_twice",
+        source: include_str!("../compile_errors/variables/assign_twice.rs"),
+    },
+    CompileError {
+        lesson: "ownership",
+        name: "use_after_move",
+        source: include_str!("../compile_errors/ownership/use_after_move.rs"),
+    },
+    CompileError {
+        lesson: "ownership",
+        name: "moved_into_function",
+        source: include_str!("../compile_errors/ownership/moved_into_function.rs"),
+    },
+    CompileError {
+        lesson: "ownership",
+        name: "partial_move",
+        source: include_str!("../compile_errors/ownership/partial_move.rs"),
+    },
+    CompileError {
+        lesson: "borrowing",
+        name: "two_mutable_borrows",
+        source: include_str!("../compile_errors/borrowing/two_mutable_borrows.rs"),
+    },
+    CompileError {
+        lesson: "borrowing",
+        name: "mutate_while_borrowed",
+        source: include_str!("../compile_errors/borrowing/mutate_while_borrowed.rs"),
+    },
+    CompileError {
+        lesson: "borrowing",
+        name: "dangling_reference",
+        source: include_str!("../compile_errors/borrowing/dangling_reference.rs"),
+    },
+    CompileError {
+        lesson: "boxes",
+        name: "recursive_without_box",
+        source: include_str!("../compile_errors/boxes/recursive_without_box.rs"),
+    },
+];
+
+/// Timed algorithm problems for `rust-learn interview`
+/// (`rust_learn_core::interview`), easiest topics first
+pub const INTERVIEW: &[Problem] = &[
+    Problem {
+        topic: "arrays",
+        name: "two_sum",
+        source: include_str!("../interview/arrays/two_sum.rs"),
+    },
+    Problem {
+        topic: "arrays",
+        name: "max_subarray",
+        source: include_str!("../interview/arrays/max_subarray.rs"),
+    },
+    Problem {
+        topic: "arrays",
+        name: "range_sum_queries",
+        source: include_str!("../interview/arrays/range_sum_queries.rs"),
+    },
+    Problem {
+        topic: "strings",
+        name: "balanced_brackets",
+        source: include_str!("../interview/strings/balanced_brackets.rs"),
+    },
+    Problem {
+        topic: "strings",
+        name: "first_unique_char",
+        source: include_str!("../interview/strings/first_unique_char.rs"),
+    },
+    Problem {
+        topic: "searching",
+        name: "count_in_range",
+        source: include_str!("../interview/searching/count_in_range.rs"),
+    },
+    Problem {
+        topic: "sorting",
+        name: "merge_intervals",
+        source: include_str!("../interview/sorting/merge_intervals.rs"),
+    },
+    Problem {
+        topic: "graphs",
+        name: "count_islands",
+        source: include_str!("../interview/graphs/count_islands.rs"),
+    },
+    Problem {
+        topic: "dynamic_programming",
+        name: "coin_change",
+        source: include_str!("../interview/dynamic_programming/coin_change.rs"),
+    },
+    Problem {
+        topic: "dynamic_programming",
+        name: "longest_increasing_subsequence",
+        source: include_str!("../interview/dynamic_programming/longest_increasing_subsequence.rs"),
+    },
+];
+
+/// The lesson sections (`lesson::section`) that teach what each rustc error
+/// code is about, for `rust-learn explain`
+pub const ERROR_CODES: &[(&str, &[&str])] = &[
+    (
+        "E0004",
+        &["match::match_with_multiple_arms", "enums::enum_example"],
+    ),
+    ("E0072", &["boxes::recursive_types"]),
+    (
+        "E0106",
+        &[
+            "borrowing::borrowing_and_lifetimes",
+            "ownership::borrowing_with_lifetimes",
+        ],
+    ),
+    (
+        "E0308",
+        &[
+            "const_let_mut::type_inference_example",
+            "variables::shadowing_with_new_type",
+        ],
+    ),
+    (
+        "E0382",
+        &[
+            "ownership::basic_ownership_rules",
+            "ownership::ownership_and_functions",
+        ],
+    ),
+    (
+        "E0384",
+        &["variables::mutability", "const_let_mut::mut_variables"],
+    ),
+    (
+        "E0499",
+        &[
+            "borrowing::mutable_borrowing",
+            "ownership::mutable_references",
+        ],
+    ),
+    (
+        "E0502",
+        &[
+            "borrowing::borrowing_rules",
+            "ownership::mutable_references",
+        ],
+    ),
+    ("E0505", &["borrowing::borrowing_rules"]),
+    ("E0506", &["borrowing::borrowing_rules"]),
+    ("E0507", &["ownership::references_and_borrowing"]),
+    ("E0515", &["borrowing::borrowing_and_lifetimes"]),
+    ("E0594", &["interior_mutability::mutation_behind_shared"]),
+    (
+        "E0596",
+        &["borrowing::mutable_borrowing", "variables::mutability"],
+    ),
+    (
+        "E0597",
+        &[
+            "borrowing::borrowing_and_lifetimes",
+            "ownership::borrowing_with_lifetimes",
+        ],
+    ),
+];
+
+/// Crates to recommend by topic, for `rust-learn crates`
+/// (`rust_learn_core::recommendations`)
+pub const RECOMMENDATIONS: &[(&str, &str)] = &[
+    (
+        "recommendations/cli.toml",
+        include_str!("../recommendations/cli.toml"),
+    ),
+    (
+        "recommendations/http.toml",
+        include_str!("../recommendations/http.toml"),
+    ),
+    (
+        "recommendations/serialization.toml",
+        include_str!("../recommendations/serialization.toml"),
+    ),
+    (
+        "recommendations/testing.toml",
+        include_str!("../recommendations/testing.toml"),
+    ),
+    (
+        "recommendations/async.toml",
+        include_str!("../recommendations/async.toml"),
+    ),
+    (
+        "recommendations/errors.toml",
+        include_str!("../recommendations/errors.toml"),
+    ),
+    (
+        "recommendations/ffi.toml",
+        include_str!("../recommendations/ffi.toml"),
+    ),
+    (
+        "recommendations/allocators.toml",
+        include_str!("../recommendations/allocators.toml"),
+    ),
+];
+
+/// Reference excerpts for `rust-learn ref`, by file name
+/// (`rust_learn_core::reference`)
+pub const REFERENCE: &[(&str, &str)] = &[
+    (
+        "reference/borrowing.toml",
+        include_str!("../reference/borrowing.toml"),
+    ),
+    (
+        "reference/lifetime_elision.toml",
+        include_str!("../reference/lifetime_elision.toml"),
+    ),
+    (
+        "reference/operators.toml",
+        include_str!("../reference/operators.toml"),
+    ),
+    (
+        "reference/integers.toml",
+        include_str!("../reference/integers.toml"),
+    ),
+];
+
+pub const LESSONS: &[Lesson] = &[
+    Lesson {
+        name: "variables",
+        title: "Variables - Mutability and Shadowing",
+        run: variables::variables,
+        sections: variables::SECTIONS,
+        source: include_str!("variables.rs"),
+        chapter: 1,
+        requires: &[],
+        difficulty: Difficulty::Beginner,
+        tags: &["basics"],
+        changelog: &[],
+        questions: variables::QUESTIONS,
+        snippets: variables::SNIPPETS,
+    },
+    Lesson {
+        name: "const_let_mut",
+        title: "Const, Let and Mut - Three Ways to Declare a Variable",
+        run: const_let_mut_variables::const_let_mut_variables,
+        sections: const_let_mut_variables::SECTIONS,
+        source: include_str!("const_let_mut_variables.rs"),
+        chapter: 1,
+        requires: &["variables"],
+        difficulty: Difficulty::Beginner,
+        tags: &["basics"],
+        changelog: &[],
+        questions: const_let_mut_variables::QUESTIONS,
+        snippets: const_let_mut_variables::SNIPPETS,
+    },
+    Lesson {
+        name: "conditional",
+        title: "Conditionals - if, else if and if in let",
+        run: conditonal::conditional,
+        sections: conditonal::SECTIONS,
+        source: include_str!("conditonal.rs"),
+        chapter: 2,
+        requires: &["variables"],
+        difficulty: Difficulty::Beginner,
+        tags: &["control-flow"],
+        changelog: &[],
+        questions: conditonal::QUESTIONS,
+        snippets: conditonal::SNIPPETS,
+    },
+    Lesson {
+        name: "loops",
+        title: "Loops - loop, while, for and Iterator Adapters",
+        run: loops::loops,
+        sections: loops::SECTIONS,
+        source: include_str!("loop.rs"),
+        chapter: 2,
+        requires: &["conditional"],
+        difficulty: Difficulty::Beginner,
+        tags: &["control-flow"],
+        changelog: &[],
+        questions: loops::QUESTIONS,
+        snippets: loops::SNIPPETS,
+    },
+    Lesson {
+        name: "match",
+        title: "Match - Pattern Matching on Values",
+        run: matching::matching,
+        sections: matching::SECTIONS,
+        source: include_str!("match.rs"),
+        chapter: 2,
+        requires: &["conditional"],
+        difficulty: Difficulty::Beginner,
+        tags: &["control-flow", "patterns"],
+        changelog: &[],
+        questions: matching::QUESTIONS,
+        snippets: matching::SNIPPETS,
+    },
+    Lesson {
+        name: "enums",
+        title: "Enums - Types With Variants",
+        run: enums::enums,
+        sections: enums::SECTIONS,
+        source: include_str!("enum.rs"),
+        chapter: 3,
+        requires: &["match"],
+        difficulty: Difficulty::Beginner,
+        tags: &["types", "patterns"],
+        changelog: &[],
+        questions: enums::QUESTIONS,
+        snippets: enums::SNIPPETS,
+    },
+    Lesson {
+        name: "arrays",
+        title: "Arrays - Fixed-Size Collections",
+        run: array::arrays,
+        sections: array::SECTIONS,
+        source: include_str!("array.rs"),
+        chapter: 3,
+        requires: &["loops"],
+        difficulty: Difficulty::Beginner,
+        tags: &["collections"],
+        changelog: &[],
+        questions: array::QUESTIONS,
+        snippets: array::SNIPPETS,
+    },
+    Lesson {
+        name: "ownership",
+        title: "Ownership - Memory Safety Without Garbage Collection",
+        run: ownership::ownership,
+        sections: ownership::SECTIONS,
+        source: include_str!("ownership.rs"),
+        chapter: 4,
+        requires: &["variables", "loops"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["memory"],
+        changelog: ownership::CHANGELOG,
+        questions: ownership::QUESTIONS,
+        snippets: ownership::SNIPPETS,
+    },
+    Lesson {
+        name: "borrowing",
+        title: "Borrowing - Accessing Data Without Ownership",
+        run: browing::borrowing,
+        sections: browing::SECTIONS,
+        source: include_str!("browing.rs"),
+        chapter: 4,
+        requires: &["ownership"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["memory"],
+        changelog: &[],
+        questions: browing::QUESTIONS,
+        snippets: browing::SNIPPETS,
+    },
+    Lesson {
+        name: "structs",
+        title: "Structs - Grouping Data With Methods",
+        run: structs::structs,
+        sections: structs::SECTIONS,
+        source: include_str!("structs.rs"),
+        chapter: 4,
+        requires: &["borrowing"],
+        difficulty: Difficulty::Beginner,
+        tags: &["types"],
+        changelog: &[],
+        questions: structs::QUESTIONS,
+        snippets: structs::SNIPPETS,
+    },
+    Lesson {
+        name: "vectors",
+        title: "Vectors - Dynamic Arrays",
+        run: vectors::vectors,
+        sections: vectors::SECTIONS,
+        source: include_str!("vectors.rs"),
+        chapter: 5,
+        requires: &["arrays", "borrowing"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["collections", "memory"],
+        changelog: &[Change {
+            version: 2,
+            summary: "Two new sections measure how sorting and lookups grow with n, from O(1) to O(n^2)",
+        }],
+        questions: vectors::QUESTIONS,
+        snippets: vectors::SNIPPETS,
+    },
+    Lesson {
+        name: "options_type",
+        title: "Option Type - Handling Optional Values",
+        run: options_type::options_type,
+        sections: options_type::SECTIONS,
+        source: include_str!("options_type.rs"),
+        chapter: 5,
+        requires: &["enums"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["types", "errors"],
+        changelog: &[],
+        questions: options_type::QUESTIONS,
+        snippets: options_type::SNIPPETS,
+    },
+    Lesson {
+        name: "traits",
+        title: "Traits - Shared Behavior",
+        run: traits::traits,
+        sections: traits::SECTIONS,
+        source: include_str!("traits.rs"),
+        chapter: 6,
+        requires: &["structs"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["types"],
+        changelog: &[],
+        questions: traits::QUESTIONS,
+        snippets: traits::SNIPPETS,
+    },
+    Lesson {
+        name: "generics",
+        title: "Generics - One Definition for Many Types",
+        run: generics::generics,
+        sections: generics::SECTIONS,
+        source: include_str!("generics.rs"),
+        chapter: 6,
+        requires: &["traits"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["types"],
+        changelog: &[],
+        questions: generics::QUESTIONS,
+        snippets: generics::SNIPPETS,
+    },
+    Lesson {
+        name: "trait_objects",
+        title: "Trait Objects - Dynamic Dispatch With dyn Trait",
+        run: trait_objects::trait_objects,
+        sections: trait_objects::SECTIONS,
+        source: include_str!("trait_objects.rs"),
+        chapter: 6,
+        requires: &["generics"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["types"],
+        changelog: &[],
+        questions: trait_objects::QUESTIONS,
+        snippets: trait_objects::SNIPPETS,
+    },
+    Lesson {
+        name: "boxes",
+        title: "Box - Heap Allocation, Recursive Types and Deref",
+        run: boxes::boxes,
+        sections: boxes::SECTIONS,
+        source: include_str!("boxes.rs"),
+        chapter: 6,
+        requires: &["trait_objects"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["memory", "types"],
+        changelog: &[],
+        questions: boxes::QUESTIONS,
+        snippets: boxes::SNIPPETS,
+    },
+    Lesson {
+        name: "interior_mutability",
+        title: "Interior Mutability - Cell, RefCell and Rc<RefCell<T>>",
+        run: interior_mutability::interior_mutability,
+        sections: interior_mutability::SECTIONS,
+        source: include_str!("interior_mutability.rs"),
+        chapter: 6,
+        requires: &["boxes"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["memory", "types"],
+        changelog: &[],
+        questions: interior_mutability::QUESTIONS,
+        snippets: interior_mutability::SNIPPETS,
+    },
+    Lesson {
+        name: "weak_references",
+        title: "Weak References - Trees, Cycles and Weak::upgrade",
+        run: weak_references::weak_references,
+        sections: weak_references::SECTIONS,
+        source: include_str!("weak_references.rs"),
+        chapter: 6,
+        requires: &["interior_mutability"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["memory", "types"],
+        changelog: &[],
+        questions: weak_references::QUESTIONS,
+        snippets: weak_references::SNIPPETS,
+    },
+    Lesson {
+        name: "cow",
+        title: "Cow - Borrow Usually, Allocate When You Must",
+        run: cow::cow,
+        sections: cow::SECTIONS,
+        source: include_str!("cow.rs"),
+        chapter: 6,
+        requires: &["generics"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["memory", "performance"],
+        changelog: &[],
+        questions: cow::QUESTIONS,
+        snippets: cow::SNIPPETS,
+    },
+    Lesson {
+        name: "closures",
+        title: "Closures - Functions That Capture Their Environment",
+        run: closures::closures,
+        sections: closures::SECTIONS,
+        source: include_str!("closures.rs"),
+        chapter: 7,
+        requires: &["trait_objects"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["functional"],
+        changelog: &[],
+        questions: closures::QUESTIONS,
+        snippets: closures::SNIPPETS,
+    },
+    Lesson {
+        name: "iterators",
+        title: "Iterators - Processing a Series of Items Lazily",
+        run: iterators::iterators,
+        sections: iterators::SECTIONS,
+        source: include_str!("iterators.rs"),
+        chapter: 7,
+        requires: &["closures"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["functional", "collections"],
+        changelog: &[],
+        questions: iterators::QUESTIONS,
+        snippets: iterators::SNIPPETS,
+    },
+    Lesson {
+        name: "error_handling",
+        title: "Error Handling - Result and the ? Operator",
+        run: error_handling::error_handling,
+        sections: error_handling::SECTIONS,
+        source: include_str!("error_handling.rs"),
+        chapter: 8,
+        requires: &["options_type", "traits"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["errors", "io"],
+        changelog: &[],
+        questions: error_handling::QUESTIONS,
+        snippets: error_handling::SNIPPETS,
+    },
+    Lesson {
+        name: "custom_errors",
+        title: "Custom Error Types - By Hand and by Derive",
+        run: custom_errors::custom_errors,
+        sections: custom_errors::SECTIONS,
+        source: include_str!("custom_errors.rs"),
+        chapter: 8,
+        requires: &["error_handling"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["errors", "types"],
+        changelog: &[],
+        questions: custom_errors::QUESTIONS,
+        snippets: custom_errors::SNIPPETS,
+    },
+    Lesson {
+        name: "app_errors",
+        title: "Application Errors - Context, Chains and Downcasting",
+        run: app_errors::app_errors,
+        sections: app_errors::SECTIONS,
+        source: include_str!("app_errors.rs"),
+        chapter: 8,
+        requires: &["custom_errors"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["errors", "cli"],
+        changelog: &[],
+        questions: app_errors::QUESTIONS,
+        snippets: app_errors::SNIPPETS,
+    },
+    Lesson {
+        name: "panics",
+        title: "Panics - Unwinding, Backtraces and catch_unwind",
+        run: panics::panics,
+        sections: panics::SECTIONS,
+        source: include_str!("panics.rs"),
+        chapter: 8,
+        requires: &["error_handling"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["errors", "testing"],
+        changelog: &[],
+        questions: panics::QUESTIONS,
+        snippets: panics::SNIPPETS,
+    },
+    Lesson {
+        name: "api_design",
+        title: "API Design - Evolving a Function Signature Without Breaking Callers",
+        run: api_design::api_design,
+        sections: api_design::SECTIONS,
+        source: include_str!("api_design.rs"),
+        chapter: 9,
+        requires: &["borrowing", "options_type"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["crates", "types"],
+        changelog: &[],
+        questions: api_design::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "semver",
+        title: "Semver - Publishing Versions Without Breaking Dependents",
+        run: semver::semver,
+        sections: semver::SECTIONS,
+        source: include_str!("semver.rs"),
+        chapter: 9,
+        requires: &["api_design"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["crates", "tooling"],
+        changelog: &[],
+        questions: semver::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "workspaces",
+        title: "Workspaces - Splitting a Project Into Crates",
+        run: workspaces::workspaces,
+        sections: workspaces::SECTIONS,
+        source: include_str!("workspaces.rs"),
+        chapter: 9,
+        requires: &["semver"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["crates", "tooling"],
+        changelog: &[],
+        questions: workspaces::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "build_scripts",
+        title: "Build Scripts - Generating Code at Compile Time",
+        run: build_scripts::build_scripts,
+        sections: build_scripts::SECTIONS,
+        source: include_str!("build_scripts.rs"),
+        chapter: 9,
+        requires: &["workspaces"],
+        difficulty: Difficulty::Advanced,
+        tags: &["crates", "tooling"],
+        changelog: &[],
+        questions: build_scripts::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "fast_input",
+        title: "Fast Input - Reading Millions of Numbers",
+        run: fast_input::fast_input,
+        sections: fast_input::SECTIONS,
+        source: include_str!("fast_input.rs"),
+        chapter: 10,
+        requires: &["vectors", "traits"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["performance", "io"],
+        changelog: &[],
+        questions: fast_input::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "allocators",
+        title: "Global Allocators - Counting Allocations and Swapping in mimalloc",
+        run: allocators::allocators,
+        sections: allocators::SECTIONS,
+        source: include_str!("allocators.rs"),
+        chapter: 10,
+        requires: &["vectors"],
+        difficulty: Difficulty::Advanced,
+        tags: &["memory", "systems"],
+        changelog: &[],
+        questions: allocators::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "no_std",
+        title: "no_std - Code That Runs Without an Operating System",
+        run: no_std::no_std,
+        sections: no_std::SECTIONS,
+        source: include_str!("no_std.rs"),
+        chapter: 10,
+        requires: &["allocators"],
+        difficulty: Difficulty::Advanced,
+        tags: &["systems", "embedded"],
+        changelog: &[],
+        questions: no_std::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "registers",
+        title: "Registers - Simulated Memory-Mapped Hardware",
+        run: registers::registers,
+        sections: registers::SECTIONS,
+        source: include_str!("registers.rs"),
+        chapter: 10,
+        requires: &["no_std"],
+        difficulty: Difficulty::Advanced,
+        tags: &["systems", "embedded", "unsafe"],
+        changelog: &[],
+        questions: registers::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "ffi",
+        title: "FFI - A Rust Library C Can Call, With a cbindgen Header",
+        run: ffi::ffi,
+        sections: ffi::SECTIONS,
+        source: include_str!("ffi.rs"),
+        chapter: 10,
+        requires: &["borrowing", "build_scripts"],
+        difficulty: Difficulty::Advanced,
+        tags: &["systems", "unsafe", "interop"],
+        changelog: &[],
+        questions: ffi::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "wasi",
+        title: "WASI - A Rust Utility Sandboxed Under wasmtime or Node",
+        run: wasi::wasi,
+        sections: wasi::SECTIONS,
+        source: include_str!("wasi.rs"),
+        chapter: 11,
+        requires: &["workspaces"],
+        difficulty: Difficulty::Advanced,
+        tags: &["targets", "tooling"],
+        changelog: &[],
+        questions: wasi::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "cross_compilation",
+        title: "Cross-Compilation - Targets, Triples and Linkers",
+        run: cross_compilation::cross_compilation,
+        sections: cross_compilation::SECTIONS,
+        source: include_str!("cross_compilation.rs"),
+        chapter: 11,
+        requires: &["build_scripts"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["targets", "tooling"],
+        changelog: &[],
+        questions: cross_compilation::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "release_profiles",
+        title: "Release Profiles - Building Optimized Binaries of rust-learn Itself",
+        run: release_profiles::release_profiles,
+        sections: release_profiles::SECTIONS,
+        source: include_str!("release_profiles.rs"),
+        chapter: 11,
+        requires: &["workspaces"],
+        difficulty: Difficulty::Intermediate,
+        tags: &["performance", "tooling"],
+        changelog: &[],
+        questions: release_profiles::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "async_await",
+        title: "Async/Await - Concurrent Tasks With Tokio",
+        run: async_await::async_await,
+        sections: async_await::SECTIONS,
+        source: include_str!("async_await.rs"),
+        chapter: 12,
+        requires: &["borrowing", "options_type"],
+        difficulty: Difficulty::Advanced,
+        tags: &["concurrency"],
+        changelog: &[],
+        questions: async_await::QUESTIONS,
+        snippets: &[],
+    },
+    Lesson {
+        name: "shared_state",
+        title: "Shared State - Atomics, Mutexes and Stress Tests",
+        run: shared_state::shared_state,
+        sections: shared_state::SECTIONS,
+        source: include_str!("shared_state.rs"),
+        chapter: 12,
+        requires: &["closures"],
+        difficulty: Difficulty::Advanced,
+        tags: &["concurrency", "testing"],
+        changelog: &[],
+        questions: shared_state::QUESTIONS,
+        snippets: shared_state::SNIPPETS,
+    },
+    Lesson {
+        name: "model_checking",
+        title: "Model Checking - Every Interleaving with loom",
+        run: model_checking::model_checking,
+        sections: model_checking::SECTIONS,
+        source: include_str!("model_checking.rs"),
+        chapter: 12,
+        requires: &["shared_state"],
+        difficulty: Difficulty::Advanced,
+        tags: &["concurrency", "testing"],
+        changelog: &[],
+        questions: model_checking::QUESTIONS,
+        snippets: model_checking::SNIPPETS,
+    },
+];
+
+/// Look up a lesson by its short name (case-insensitive)
+pub fn find(name: &str) -> Option<&'static Lesson> {
+    let lesson = LESSONS
+        .iter()
+        .find(|lesson| lesson.name.eq_ignore_ascii_case(name));
+    trace!(name, found = lesson.is_some(), "find");
+    lesson
+}
+
+/// The length of the longest lesson name, for sizing name columns
+pub fn name_width() -> usize {
+    LESSONS
+        .iter()
+        .map(|lesson| lesson.name.len())
+        .max()
+        .unwrap_or(0)
+}
+
+/// The title of chapter `number`
+pub fn chapter_title(number: u8) -> &'static str {
+    CHAPTERS[usize::from(number) - 1]
+}
+
+/// A lesson's place in the curriculum, e.g. "4.2" for the second lesson of chapter 4
+pub fn number(lesson: &Lesson) -> String {
+    let position = LESSONS
+        .iter()
+        .filter(|other| other.chapter == lesson.chapter)
+        .position(|other| other.name == lesson.name)
+        .expect("lesson is registered");
+    format!("{}.{}", lesson.chapter, position + 1)
+}
+
+/// The first lesson, in curriculum order, that is not complete yet but whose
+/// prerequisites all are
+pub fn next(is_complete: impl Fn(&str) -> bool) -> Option<&'static Lesson> {
+    let lesson = LESSONS.iter().find(|lesson| {
+        !is_complete(lesson.name) && lesson.requires.iter().all(|name| is_complete(name))
+    });
+    debug!(next = lesson.map(|lesson| lesson.name), "next lesson");
+    lesson
+}
+
+/// Which lessons `list`, `run --all` and `run --random` take: every one, or
+/// those with a tag, a difficulty, or both
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Filter {
+    pub tag: Option<String>,
+    pub difficulty: Option<Difficulty>,
+}
+
+impl Filter {
+    pub fn is_empty(&self) -> bool {
+        self.tag.is_none() && self.difficulty.is_none()
+    }
+
+    pub fn matches(&self, lesson: &Lesson) -> bool {
+        self.tag.as_ref().is_none_or(|tag| {
+            lesson
+                .tags
+                .iter()
+                .any(|other| other.eq_ignore_ascii_case(tag))
+        }) && self
+            .difficulty
+            .is_none_or(|difficulty| lesson.difficulty == difficulty)
+    }
+
+    /// The lessons that match, in curriculum order; `Err` names the tags
+    /// there are when no lesson has this one
+    pub fn lessons(&self) -> Result<Vec<Lesson>, String> {
+        if let Some(tag) = &self.tag
+            && !tags().iter().any(|other| other.eq_ignore_ascii_case(tag))
+        {
+            return Err(format!(
+                "no lesson is tagged '{}' (tags: {})",
+                tag,
+                tags().join(", ")
+            ));
+        }
+        Ok(LESSONS
+            .iter()
+            .filter(|lesson| self.matches(lesson))
+            .copied()
+            .collect())
+    }
+
+    /// The filter as command-line flags, `--tag memory --difficulty beginner`
+    pub fn to_args(&self) -> String {
+        let mut args = Vec::new();
+        if let Some(tag) = &self.tag {
+            args.push(format!("--tag {}", tag));
+        }
+        if let Some(difficulty) = self.difficulty {
+            args.push(format!("--difficulty {}", difficulty.name()));
+        }
+        args.join(" ")
+    }
+
+    /// Read back what `to_args` wrote
+    pub fn parse_args(text: &str) -> Result<Filter, String> {
+        let mut filter = Filter::default();
+        let words: Vec<&str> = text.split_whitespace().collect();
+        for pair in words.chunks(2) {
+            match pair {
+                ["--tag", tag] => filter.tag = Some(tag.to_string()),
+                ["--difficulty", difficulty] => filter.difficulty = Some(difficulty.parse()?),
+                _ => return Err(format!("cannot read the filter '{}'", text)),
+            }
+        }
+        Ok(filter)
+    }
+}
+
+/// Every tag a lesson has, sorted
+pub fn tags() -> Vec<&'static str> {
+    let mut tags: Vec<&'static str> = LESSONS
+        .iter()
+        .flat_map(|lesson| lesson.tags.iter().copied())
+        .collect();
+    tags.sort_unstable();
+    tags.dedup();
+    tags
+}
+
+/// The lesson name closest to a mistyped or shortened one ("ownrship", "vec")
+pub fn closest(name: &str) -> Option<Match<'static>> {
+    let names: Vec<&'static str> = LESSONS.iter().map(|lesson| lesson.name).collect();
+    let guess = fuzzy::closest(name, &names);
+    debug!(
+        name,
+        guess = guess.as_ref().map(|guess| guess.name),
+        confident = guess.as_ref().is_some_and(|guess| guess.confident),
+        "closest lesson"
+    );
+    guess
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lessons_have_unique_names_and_sections() {
+        assert_eq!(name_width(), "interior_mutability".len());
+        for (index, lesson) in LESSONS.iter().enumerate() {
+            assert!(
+                !lesson.sections.is_empty(),
+                "{} has no sections",
+                lesson.name
+            );
+            assert!(
+                LESSONS[..index]
+                    .iter()
+                    .all(|other| other.name != lesson.name),
+                "{} is registered twice",
+                lesson.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_codes_link_to_sections() {
+        for (code, sections) in ERROR_CODES {
+            for key in *sections {
+                let (lesson, section) = key.split_once("::").unwrap();
+                assert!(
+                    find(lesson)
+                        .and_then(|lesson| lesson.section(section))
+                        .is_some(),
+                    "{} links to {}, which is not a section",
+                    code,
+                    key
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_chapters_and_prerequisites_follow_registry_order() {
+        let mut chapter = 1;
+        for (index, lesson) in LESSONS.iter().enumerate() {
+            assert!(
+                lesson.chapter == chapter || lesson.chapter == chapter + 1,
+                "{} skips or goes back a chapter",
+                lesson.name
+            );
+            chapter = lesson.chapter;
+            for name in lesson.requires {
+                assert!(
+                    LESSONS[..index].iter().any(|earlier| earlier.name == *name),
+                    "{} requires {}, which is not an earlier lesson",
+                    lesson.name,
+                    name
+                );
+            }
+        }
+        assert_eq!(usize::from(chapter), CHAPTERS.len());
+
+        assert_eq!(number(find("borrowing").unwrap()), "4.2");
+        assert_eq!(next(|_| false).unwrap().name, "variables");
+        let done = ["variables", "const_let_mut", "conditional", "loops"];
+        assert_eq!(next(|name| done.contains(&name)).unwrap().name, "match");
+        assert!(next(|_| true).is_none());
+    }
+
+    #[test]
+    fn test_quiz_questions_have_no_problems() {
+        let problems = rust_learn_core::quiz::problems(LESSONS);
+        assert!(problems.is_empty(), "{:#?}", problems);
+        for (name, text) in BUNDLED_QUESTIONS {
+            if let Err(errors) = rust_learn_core::question_bank::parse(name, text, LESSONS) {
+                panic!("{} does not parse: {:#?}", name, errors);
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter() {
+        let filter = Filter {
+            tag: Some("Memory".to_string()),
+            difficulty: Some(Difficulty::Intermediate),
+        };
+        let names: Vec<&str> = filter
+            .lessons()
+            .unwrap()
+            .iter()
+            .map(|lesson| lesson.name)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "ownership",
+                "borrowing",
+                "vectors",
+                "boxes",
+                "interior_mutability",
+                "weak_references",
+                "cow"
+            ]
+        );
+        assert_eq!(Filter::parse_args(&filter.to_args()), Ok(filter));
+        assert_eq!(Filter::default().lessons().unwrap().len(), LESSONS.len());
+        assert!(
+            Filter {
+                tag: Some("gardening".to_string()),
+                difficulty: None,
+            }
+            .lessons()
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,309 @@
+/// Release Profiles in Rust - Building Optimized Binaries of rust-learn Itself
+///
+/// A cargo profile is a named set of compiler settings. This lesson shows the
+/// profiles in the workspace's Cargo.toml, builds the rust-learn binary with
+/// dev, release and the custom release-lto profile through the toolchain
+/// module, and compares their sizes and how fast each runs the same workload
+/// (`rust-learn dev bench`).
+use crate::generated::BUILD_PROFILE;
+use crate::registry::{Question, Section, section};
+use rust_learn_core::runner::format_duration;
+use rust_learn_core::toolchain;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+pub fn release_profiles() {
+    println!("=== Release Profiles Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- release_profiles <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(cargo_profiles, "Profiles in Cargo.toml"),
+    section!(building_three_ways, "Building rust-learn Three Ways"),
+    section!(benchmarking_builds, "Benchmarking the Builds"),
+    section!(what_each_setting_trades, "What Each Setting Trades"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "cargo_profiles",
+        prompt: "What does `lto = true` in [profile.release] trade?",
+        choices: &[
+            "A longer build for faster, smaller code across crates",
+            "A smaller build for slower code",
+            "Debug info for speed",
+            "Nothing; it is the default",
+        ],
+        explanation: "Link-time optimization lets the optimizer see every crate at once, at the cost of link time.",
+    },
+    Question {
+        section: "what_each_setting_trades",
+        prompt: "What does `panic = \"abort\"` give up?",
+        choices: &[
+            "Unwinding: no catch_unwind, and no destructors on panic",
+            "Panicking altogether",
+            "Debug symbols",
+            "Overflow checks",
+        ],
+        explanation: "Aborting right away makes the binary smaller, but a panic can no longer be caught or clean up.",
+    },
+];
+
+/// The workspace manifest, with the custom profiles this lesson builds
+const WORKSPACE_MANIFEST: &str = include_str!("../../../Cargo.toml");
+
+/// The profiles the lesson builds, fastest to build first
+const PROFILES: [&str; 3] = ["dev", "release", "release-lto"];
+
+fn cargo_profiles() {
+    println!("1. Profiles in Cargo.toml:");
+    println!("==========================\n");
+
+    explain!("BUILT IN:");
+    explain!("=========");
+    explain!("dev      cargo build            opt-level 0, debug info, overflow checks");
+    explain!("release  cargo build --release  opt-level 3, no debug info, no overflow checks");
+    explain!("test and bench inherit from dev and release");
+
+    println!("\nCUSTOM, IN THIS WORKSPACE'S Cargo.toml:");
+    println!("=======================================");
+    let lines: Vec<&str> = WORKSPACE_MANIFEST.lines().collect();
+    if let Some(first) = lines.iter().position(|line| line.starts_with("[profile.")) {
+        // Start at the comment above the first profile
+        let mut start = first;
+        while start > 0 && lines[start - 1].starts_with('#') {
+            start -= 1;
+        }
+        for line in &lines[start..] {
+            if line.starts_with('[') && !line.starts_with("[profile.") {
+                break;
+            }
+            println!("  {}", line);
+        }
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- `inherits` starts from another profile and overrides a few settings");
+    explain!("- cargo build --profile release-lto writes to target/release-lto/");
+    explain!("- Profiles only count in the workspace root; a dependency's are ignored");
+
+    println!();
+}
+
+fn building_three_ways() {
+    println!("2. Building rust-learn Three Ways:");
+    println!("==================================\n");
+
+    explain!("A first optimized build takes minutes; later runs reuse it.\n");
+
+    let Some(builds) = build_all() else {
+        return;
+    };
+
+    println!("\n{:<12} {:>10} {:>12}", "PROFILE", "SIZE", "BUILD TIME");
+    for build in &builds {
+        println!(
+            "{:<12} {:>10} {:>12}",
+            build.profile,
+            format_size(build.size),
+            format_duration(build.took)
+        );
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- Most of a dev binary is debug info, which maps machine code back to source");
+    explain!("- LTO lets the linker inline and drop code across crates, so the binary shrinks");
+    explain!("- A build time under a second means cargo found nothing to rebuild");
+
+    println!();
+}
+
+fn benchmarking_builds() {
+    println!("3. Benchmarking the Builds:");
+    println!("===========================\n");
+
+    let Some(builds) = build_all() else {
+        return;
+    };
+
+    let workspace = workspace_dir();
+    println!("\n$ rust-learn dev bench   (edit distance between every pair of 600 words)");
+    let mut dev_time = None;
+    for build in &builds {
+        let output = toolchain::run(&build.binary, &workspace, &["dev", "bench"])
+            .expect("Failed to run the built binary");
+        let Some(seconds) = bench_seconds(&output.stdout) else {
+            println!("{:<12} did not report a time", build.profile);
+            continue;
+        };
+        let base = *dev_time.get_or_insert(seconds);
+        println!(
+            "{:<12} {:>10}   {:>5.1}x the dev build",
+            build.profile,
+            format_duration(Duration::from_secs_f64(seconds)),
+            base / seconds
+        );
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- Release is several times faster than dev here; how much depends on the code");
+    explain!("- LTO moves the time by a few percent either way; run twice before trusting it");
+    explain!("- Benchmark release builds; dev timings say little about real speed");
+
+    println!();
+}
+
+fn what_each_setting_trades() {
+    println!("4. What Each Setting Trades:");
+    println!("============================\n");
+
+    explain!("SETTINGS:");
+    explain!("=========");
+    explain!(
+        "opt-level = 0..3, \"s\", \"z\"  speed of the code vs. build time; s and z optimize size"
+    );
+    explain!("debug = true / false        debug info: debuggers and backtraces vs. binary size");
+    explain!("lto = \"fat\" / \"thin\"        cross-crate optimization vs. link time");
+    explain!("codegen-units = 1           better optimization vs. parallel compilation");
+    explain!("panic = \"abort\"             smaller code, but catch_unwind no longer catches");
+    explain!("strip = true                no symbols: smaller, with unreadable backtraces");
+    explain!("overflow-checks = true      integer overflow panics instead of wrapping");
+
+    println!("\nTHIS BINARY:");
+    println!("============");
+    println!("built with the {} profile", BUILD_PROFILE);
+    println!("cfg!(debug_assertions) = {}", cfg!(debug_assertions));
+
+    detail!("\nWHICH PROFILE WHEN:");
+    detail!("===================");
+    detail!("dev          - while writing code: the fastest builds");
+    detail!("release      - what you ship, and what you benchmark");
+    detail!("release-lto  - a release where build time does not matter, like a tagged version");
+    detail!("release-small - size first: embedded targets, WebAssembly, small downloads.");
+    detail!("               Its panic = \"abort\" means `run --all` stops at the first panic.");
+
+    println!();
+}
+
+/// One build of rust-learn
+struct Build {
+    profile: &'static str,
+    binary: PathBuf,
+    size: u64,
+    took: Duration,
+}
+
+fn workspace_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("../..")
+}
+
+/// Build rust-learn with each profile, or `None` (after explaining why)
+fn build_all() -> Option<Vec<Build>> {
+    let Some(version) = toolchain::cargo_version() else {
+        println!("cargo was not found, so rust-learn cannot be rebuilt here.");
+        println!("Install Rust with rustup and run the lesson again.\n");
+        return None;
+    };
+    let workspace = workspace_dir();
+    if !workspace.join("crates/rust-learn-cli").is_dir() {
+        println!("The rust-learn source tree is not next to this binary, so it");
+        println!("cannot be rebuilt. Run the lesson from a checkout.\n");
+        return None;
+    }
+    println!("Using {}", version);
+
+    let mut builds = Vec::new();
+    for profile in PROFILES {
+        println!("$ cargo build -p rust-learn-cli --profile {}", profile);
+        let start = Instant::now();
+        let args = [
+            "build",
+            "-p",
+            "rust-learn-cli",
+            "--profile",
+            profile,
+            "--message-format=json",
+        ];
+        let output = toolchain::cargo(&workspace, &args).expect("Failed to run cargo");
+        let took = start.elapsed();
+
+        let Some(binary) = output
+            .success
+            .then(|| executable_path(&output.stdout))
+            .flatten()
+        else {
+            println!("cargo build: FAILED");
+            for line in output
+                .stderr
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .take(6)
+            {
+                println!("  | {}", line);
+            }
+            return None;
+        };
+        let size = fs::metadata(&binary).map(|meta| meta.len()).unwrap_or(0);
+        builds.push(Build {
+            profile,
+            binary,
+            size,
+            took,
+        });
+    }
+    Some(builds)
+}
+
+/// The rust-learn executable among the artifacts cargo reported with
+/// `--message-format=json`
+fn executable_path(messages: &str) -> Option<PathBuf> {
+    messages
+        .lines()
+        .filter(|line| line.contains("\"reason\":\"compiler-artifact\""))
+        .find_map(|line| {
+            let (_, rest) = line.split_once("\"executable\":\"")?;
+            let (path, _) = rest.split_once('"')?;
+            Some(PathBuf::from(path.replace("\\\\", "\\")))
+        })
+}
+
+/// The seconds from `dev bench`'s `time: 0.123456s` line
+fn bench_seconds(stdout: &str) -> Option<f64> {
+    stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("time: "))
+        .and_then(|time| time.trim_end_matches('s').parse().ok())
+}
+
+fn format_size(bytes: u64) -> String {
+    format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_executable_path_from_cargo_messages() {
+        let messages = "{\"reason\":\"compiler-artifact\",\"target\":{\"name\":\"rust_learn_core\"},\"executable\":null}\n\
+             {\"reason\":\"compiler-artifact\",\"target\":{\"name\":\"rust-learn\"},\"executable\":\"/t/release-lto/rust-learn\",\"fresh\":true}\n\
+             {\"reason\":\"build-finished\",\"success\":true}";
+        assert_eq!(
+            executable_path(messages),
+            Some(PathBuf::from("/t/release-lto/rust-learn"))
+        );
+        assert_eq!(
+            bench_seconds("179700 edit distances\ntime: 0.250000s\n"),
+            Some(0.25)
+        );
+    }
+}
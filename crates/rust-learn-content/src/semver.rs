@@ -0,0 +1,508 @@
+/// Semver in Rust - Publishing Versions Without Breaking Dependents
+///
+/// Cargo trusts version numbers: a `tinylib = "1.0"` requirement means "any 1.x
+/// release is a drop-in replacement". This lesson publishes releases of a tiny
+/// crate into a temp-dir sandbox, runs `cargo check` on a dependent after each
+/// one (through the toolchain module), and shows what happens when a breaking
+/// change is labeled as minor versus major.
+use crate::registry::{Question, Section, section};
+use rust_learn_core::toolchain::{self, CommandOutput};
+use std::fmt;
+use std::fs;
+use std::path::PathBuf;
+
+pub fn semver() {
+    println!("=== Semver Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- semver <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(
+        version_requirements,
+        "Version Numbers and Caret Requirements"
+    ),
+    section!(publishing_releases, "Publishing Compatible Releases"),
+    section!(breaking_minor_release, "A Breaking Change Shipped as Minor"),
+    section!(breaking_major_release, "The Same Change as a Major Release"),
+    section!(
+        semver_checks_reasoning,
+        "cargo semver-checks Style Reasoning"
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "version_requirements",
+        prompt: "Which versions does `serde = \"1.2\"` accept?",
+        choices: &[
+            "1.2.0 and up, below 2.0.0",
+            "Exactly 1.2.0",
+            "1.2.x only",
+            "Any version",
+        ],
+        explanation: "A bare version is a caret requirement: ^1.2 takes every compatible release up to the next major.",
+    },
+    Question {
+        section: "version_requirements",
+        prompt: "Under `foo = \"0.3\"`, which release can cargo update pick up?",
+        choices: &["0.3.9", "0.4.0", "1.0.0", "None; 0.x versions are pinned"],
+        explanation: "Before 1.0 the minor number acts as the major: ^0.3 takes 0.3.x but not 0.4.",
+    },
+    Question {
+        section: "breaking_minor_release",
+        prompt: "You remove a public function and publish 1.4.0 after 1.3.0. What happens?",
+        choices: &[
+            "Users who run cargo update get 1.4.0 and their builds break",
+            "crates.io refuses to publish it",
+            "Only users of 2.0 are affected",
+            "cargo keeps everyone on 1.3.0",
+        ],
+        explanation: "Requirements like \"1.3\" accept any 1.x, trusting it to be compatible; removing an item needs a major release.",
+    },
+];
+
+fn version_requirements() {
+    println!("1. Version Numbers and Caret Requirements:");
+    println!("==========================================\n");
+
+    explain!("MAJOR.MINOR.PATCH:");
+    explain!("==================");
+    explain!("MAJOR - incompatible API changes");
+    explain!("MINOR - new functionality, backwards compatible");
+    explain!("PATCH - bug fixes, backwards compatible");
+
+    println!("\nCARET REQUIREMENTS - What \"1.2.3\" Means in Cargo.toml:");
+    println!("======================================================");
+    explain!("A bare version is a caret requirement: ^1.2.3");
+    explain!("Cargo may pick any version with the same left-most non-zero number");
+
+    let requirements = ["1.2.3", "0.2.3", "0.0.3"];
+    let candidates = [
+        "1.2.3", "1.2.4", "1.9.0", "2.0.0", "0.2.9", "0.3.0", "0.0.3", "0.0.4",
+    ];
+
+    for requirement in requirements {
+        let req = Version::parse(requirement).expect("valid requirement");
+        let matching: Vec<&str> = candidates
+            .iter()
+            .copied()
+            .filter(|candidate| {
+                let candidate = Version::parse(candidate).expect("valid candidate");
+                caret_matches(&req, &candidate)
+            })
+            .collect();
+        println!("^{:<6} accepts {:?}", requirement, matching);
+    }
+
+    explain!("\nWHY 0.x IS SPECIAL:");
+    explain!("===================");
+    explain!("Before 1.0, the MINOR number acts as the major: 0.2 -> 0.3 may break.");
+    explain!("In 0.0.x every release may break, so ^0.0.3 only matches 0.0.3.");
+
+    println!();
+}
+
+fn publishing_releases() {
+    println!("2. Publishing Compatible Releases:");
+    println!("==================================\n");
+
+    let Some(sandbox) = Sandbox::create() else {
+        return;
+    };
+
+    println!("RELEASE 1.0.0 - The First Version:");
+    println!("----------------------------------");
+    let lib = sandbox.publish("1.0.0", TINYLIB_V1);
+    sandbox.write_app("1.0", &lib, APP_V1);
+    explain!("app depends on: tinylib = \"1.0\"");
+    report_check(&sandbox.check());
+
+    println!("\nRELEASE 1.1.0 - Adding a Function (Minor):");
+    println!("------------------------------------------");
+    let lib = sandbox.publish("1.1.0", TINYLIB_V1_1);
+    sandbox.write_app("1.0", &lib, APP_V1);
+    explain!("app still says \"1.0\", cargo picks 1.1.0 because ^1.0 allows it");
+    report_check(&sandbox.check());
+
+    explain!("\nWHY IT WORKED:");
+    explain!("==============");
+    explain!("Adding a public item never breaks code that did not use it.");
+
+    println!();
+}
+
+fn breaking_minor_release() {
+    println!("3. A Breaking Change Shipped as Minor:");
+    println!("======================================\n");
+
+    let Some(sandbox) = Sandbox::create() else {
+        return;
+    };
+
+    println!("RELEASE 1.2.0 - greet() Gains a Parameter:");
+    println!("------------------------------------------");
+    explain!("- pub fn greet(name: &str) -> String");
+    explain!("+ pub fn greet(name: &str, excited: bool) -> String");
+
+    let lib = sandbox.publish("1.2.0", TINYLIB_BREAKING);
+    sandbox.write_app("1.0", &lib, APP_V1);
+    explain!("\napp still says \"1.0\", so `cargo update` silently picks 1.2.0:");
+    report_check(&sandbox.check());
+
+    explain!("\nTHE DAMAGE:");
+    explain!("===========");
+    explain!("Every dependent using ^1.0 breaks on its next `cargo update`,");
+    explain!("without anyone changing their own Cargo.toml.");
+
+    println!();
+}
+
+fn breaking_major_release() {
+    println!("4. The Same Change as a Major Release:");
+    println!("======================================\n");
+
+    let Some(sandbox) = Sandbox::create() else {
+        return;
+    };
+
+    println!("RELEASE 2.0.0 - Same Code, Honest Version:");
+    println!("------------------------------------------");
+    let lib = sandbox.publish("2.0.0", TINYLIB_BREAKING);
+    sandbox.write_app("1.0", &lib, APP_V1);
+    explain!("app says \"1.0\", cargo refuses to use 2.0.0:");
+    report_check(&sandbox.check());
+
+    println!("\nOPTING IN - Update the Requirement and the Code:");
+    println!("------------------------------------------------");
+    sandbox.write_app("2.0", &lib, APP_V2);
+    explain!("tinylib = \"2.0\", greet(\"Ferris\", true)");
+    report_check(&sandbox.check());
+
+    explain!("\nWHY THIS IS BETTER:");
+    explain!("===================");
+    explain!("With a real registry, cargo would simply keep using the newest 1.x.");
+    explain!("Upgrading to 2.0 is a deliberate edit, done when the dependent is ready.");
+
+    println!();
+}
+
+fn semver_checks_reasoning() {
+    println!("5. cargo semver-checks Style Reasoning:");
+    println!("=======================================\n");
+
+    println!("COMPARING PUBLIC API SURFACES:");
+    println!("==============================");
+    explain!("cargo semver-checks compares the rustdoc JSON of two releases.");
+    explain!("The idea fits in a few lines: diff the public items, then classify.");
+
+    let v1_0 = [ApiItem::new("tinylib::greet", "fn(&str) -> String")];
+    let v1_1 = [
+        ApiItem::new("tinylib::greet", "fn(&str) -> String"),
+        ApiItem::new("tinylib::greet_all", "fn(&[&str]) -> Vec<String>"),
+    ];
+    let v1_2 = [
+        ApiItem::new("tinylib::greet", "fn(&str, bool) -> String"),
+        ApiItem::new("tinylib::greet_all", "fn(&[&str]) -> Vec<String>"),
+    ];
+
+    for (from, to, old, new) in [
+        ("1.0.0", "1.1.0", &v1_0[..], &v1_1[..]),
+        ("1.1.0", "1.2.0", &v1_1[..], &v1_2[..]),
+    ] {
+        println!("\n{} -> {}:", from, to);
+        let changes = diff_api(old, new);
+        for change in &changes {
+            println!("  {}", change);
+        }
+        println!("  required bump: {:?}", required_bump(&changes));
+    }
+
+    explain!("\nWHAT THE REAL TOOL ALSO CATCHES:");
+    explain!("================================");
+    explain!("- A type losing Send/Sync or an auto trait");
+    explain!("- A new variant on an exhaustive enum");
+    explain!("- A struct gaining a private field (no more struct literals)");
+    explain!("- A trait gaining a method without a default");
+
+    explain!("\nRUN IT BEFORE cargo publish:");
+    explain!("============================");
+    explain!("cargo install cargo-semver-checks");
+    explain!("cargo semver-checks check-release");
+
+    println!();
+}
+
+// The releases of the tiny crate the sandbox "publishes"
+
+const TINYLIB_V1: &str = r#"pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+"#;
+
+const TINYLIB_V1_1: &str = r#"pub fn greet(name: &str) -> String {
+    format!("Hello, {}!", name)
+}
+
+pub fn greet_all(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| greet(name)).collect()
+}
+"#;
+
+const TINYLIB_BREAKING: &str = r#"pub fn greet(name: &str, excited: bool) -> String {
+    if excited { format!("Hello, {}!!!", name) } else { format!("Hello, {}.", name) }
+}
+
+pub fn greet_all(names: &[&str]) -> Vec<String> {
+    names.iter().map(|name| greet(name, false)).collect()
+}
+"#;
+
+const APP_V1: &str = r#"fn main() {
+    println!("{}", tinylib::greet("Ferris"));
+}
+"#;
+
+const APP_V2: &str = r#"fn main() {
+    println!("{}", tinylib::greet("Ferris", true));
+}
+"#;
+
+/// A temp dir holding "published" tinylib releases and one dependent app,
+/// removed again when dropped
+struct Sandbox {
+    root: PathBuf,
+}
+
+impl Sandbox {
+    /// `None` (after explaining why) when cargo is not available
+    fn create() -> Option<Sandbox> {
+        match toolchain::cargo_version() {
+            Some(version) => println!("Using {}\n", version),
+            None => {
+                println!("cargo was not found, so this section cannot run the simulation.");
+                println!("Install Rust with rustup and run the lesson again.\n");
+                return None;
+            }
+        }
+
+        let root = std::env::temp_dir().join(format!("rust-learn-semver-{}", std::process::id()));
+        fs::create_dir_all(&root).expect("Failed to create sandbox dir");
+        Some(Sandbox { root })
+    }
+
+    /// Write tinylib at `version` into its own directory, like a registry upload
+    fn publish(&self, version: &str, lib_source: &str) -> PathBuf {
+        let dir = self.root.join(format!("tinylib-{}", version));
+        fs::create_dir_all(dir.join("src")).expect("Failed to create crate dir");
+        let manifest = format!(
+            "[package]\nname = \"tinylib\"\nversion = \"{}\"\nedition = \"2021\"\n",
+            version
+        );
+        fs::write(dir.join("Cargo.toml"), manifest).expect("Failed to write manifest");
+        fs::write(dir.join("src/lib.rs"), lib_source).expect("Failed to write lib.rs");
+        println!("published tinylib {}", version);
+        dir
+    }
+
+    /// (Re)write the dependent app with the given requirement on tinylib
+    fn write_app(&self, requirement: &str, lib_dir: &std::path::Path, main_source: &str) {
+        let dir = self.root.join("app");
+        fs::create_dir_all(dir.join("src")).expect("Failed to create app dir");
+        let manifest = format!(
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\ntinylib = {{ version = \"{}\", path = {:?} }}\n",
+            requirement, lib_dir
+        );
+        fs::write(dir.join("Cargo.toml"), manifest).expect("Failed to write manifest");
+        fs::write(dir.join("src/main.rs"), main_source).expect("Failed to write main.rs");
+        // A stale lockfile would pin the previous release
+        let _ = fs::remove_file(dir.join("Cargo.lock"));
+    }
+
+    /// `cargo check` the app against whatever tinylib it currently points at
+    fn check(&self) -> CommandOutput {
+        let target = self.root.join("target");
+        let target = target.to_str().expect("temp dir path is valid UTF-8");
+        toolchain::cargo(
+            &self.root.join("app"),
+            &["check", "--quiet", "--target-dir", target],
+        )
+        .expect("Failed to run cargo")
+    }
+}
+
+impl Drop for Sandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn report_check(output: &CommandOutput) {
+    if output.success {
+        println!("cargo check: OK");
+        return;
+    }
+
+    println!("cargo check: FAILED");
+    // The first error and its location are enough to see what broke
+    for line in output
+        .stderr
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(6)
+    {
+        println!("  | {}", line);
+    }
+}
+
+// Helper types
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version {
+    major: u64,
+    minor: u64,
+    patch: u64,
+}
+
+impl Version {
+    fn parse(text: &str) -> Option<Version> {
+        let mut parts = text.split('.').map(|part| part.parse::<u64>().ok());
+        let version = Version {
+            major: parts.next()??,
+            minor: parts.next().unwrap_or(Some(0))?,
+            patch: parts.next().unwrap_or(Some(0))?,
+        };
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(version)
+    }
+}
+
+/// Does `candidate` satisfy the caret requirement `^req`?
+fn caret_matches(req: &Version, candidate: &Version) -> bool {
+    if candidate < req {
+        return false;
+    }
+    if req.major > 0 {
+        candidate.major == req.major
+    } else if req.minor > 0 {
+        candidate.major == 0 && candidate.minor == req.minor
+    } else {
+        candidate == req
+    }
+}
+
+struct ApiItem {
+    path: &'static str,
+    signature: &'static str,
+}
+
+impl ApiItem {
+    fn new(path: &'static str, signature: &'static str) -> Self {
+        ApiItem { path, signature }
+    }
+}
+
+enum ApiChange {
+    Added(&'static str),
+    Removed(&'static str),
+    Changed {
+        path: &'static str,
+        from: &'static str,
+        to: &'static str,
+    },
+}
+
+impl fmt::Display for ApiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ApiChange::Added(path) => write!(f, "added   {}", path),
+            ApiChange::Removed(path) => write!(f, "removed {}", path),
+            ApiChange::Changed { path, from, to } => {
+                write!(f, "changed {}: {} => {}", path, from, to)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Bump {
+    Patch,
+    Minor,
+    Major,
+}
+
+fn diff_api(old: &[ApiItem], new: &[ApiItem]) -> Vec<ApiChange> {
+    let mut changes = Vec::new();
+
+    for item in old {
+        match new.iter().find(|candidate| candidate.path == item.path) {
+            None => changes.push(ApiChange::Removed(item.path)),
+            Some(now) if now.signature != item.signature => changes.push(ApiChange::Changed {
+                path: item.path,
+                from: item.signature,
+                to: now.signature,
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for item in new {
+        if !old.iter().any(|before| before.path == item.path) {
+            changes.push(ApiChange::Added(item.path));
+        }
+    }
+
+    changes
+}
+
+/// Removing or changing anything public is major; only additions is minor
+fn required_bump(changes: &[ApiChange]) -> Bump {
+    if changes
+        .iter()
+        .any(|change| !matches!(change, ApiChange::Added(_)))
+    {
+        Bump::Major
+    } else if changes.is_empty() {
+        Bump::Patch
+    } else {
+        Bump::Minor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn v(text: &str) -> Version {
+        Version::parse(text).unwrap()
+    }
+
+    #[test]
+    fn test_caret_requirements() {
+        assert!(caret_matches(&v("1.2.3"), &v("1.9.0")));
+        assert!(!caret_matches(&v("1.2.3"), &v("2.0.0")));
+        assert!(!caret_matches(&v("1.2.3"), &v("1.2.2")));
+        assert!(caret_matches(&v("0.2.3"), &v("0.2.9")));
+        assert!(!caret_matches(&v("0.2.3"), &v("0.3.0")));
+        assert!(!caret_matches(&v("0.0.3"), &v("0.0.4")));
+    }
+
+    #[test]
+    fn test_required_bump() {
+        let old = [ApiItem::new("a", "fn()")];
+        let added = [ApiItem::new("a", "fn()"), ApiItem::new("b", "fn()")];
+        let changed = [ApiItem::new("a", "fn(bool)")];
+
+        assert_eq!(required_bump(&diff_api(&old, &old)), Bump::Patch);
+        assert_eq!(required_bump(&diff_api(&old, &added)), Bump::Minor);
+        assert_eq!(required_bump(&diff_api(&old, &changed)), Bump::Major);
+        assert_eq!(required_bump(&diff_api(&added, &old)), Bump::Major);
+    }
+}
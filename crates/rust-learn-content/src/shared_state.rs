@@ -0,0 +1,439 @@
+/// Shared State in Rust - Atomics, Mutexes and Stress Tests
+///
+/// Rust's `Send` and `Sync` rules make a data race on memory a compile error,
+/// but they cannot see races in behavior: two threads that each read a
+/// counter and write back one more, or a bank transfer that checks a balance
+/// under one lock and debits it under another, compile fine and still lose
+/// updates. This lesson builds both mistakes and their fixes (`fetch_add`
+/// and `compare_exchange` on atomics, one lock held across the whole
+/// transfer), and uses `rust_learn_utils::stress` to run each on several
+/// threads at once and check an invariant afterwards, which is how the
+/// mistakes show up.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_utils::stress::{self, Violation};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::thread;
+
+pub fn shared_state() {
+    println!("=== Shared State Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- shared_state <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(sharing_between_threads, "Sharing Between Threads"),
+    section!(atomic_counters, "Atomic Counters: load/store vs fetch_add"),
+    section!(bank_accounts, "A Bank: Check and Debit Under One Lock").with_checkpoint(
+        "Two transfers lock their accounts in the order they name them: a->b and b->a. Can they deadlock?",
+        true,
+        "Each can hold its first lock while waiting for the other's; locking in a fixed order (by index) rules that out.",
+    ),
+    section!(stressing_your_code, "Stress-Testing Your Own Code"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "sharing_between_threads",
+        prompt: "Code that shares an AtomicU32 between threads compiles. What has the compiler ruled out?",
+        choices: &[
+            "Data races on memory, not wrong results from how the threads interleave",
+            "Every concurrency bug",
+            "Deadlocks",
+            "Lost updates",
+        ],
+        explanation: "Send and Sync stop unsynchronized access; a load followed by a store is synchronized and still loses updates.",
+    },
+    Question {
+        section: "atomic_counters",
+        prompt: "Four threads each run `let n = c.load(..); c.store(n + 1, ..)` 1,000 times. What is c at the end?",
+        choices: &[
+            "At most 4,000, often less",
+            "Exactly 4,000",
+            "Exactly 1,000",
+            "It does not compile",
+        ],
+        explanation: "Another thread can store between the load and the store; fetch_add does both as one step.",
+    },
+    Question {
+        section: "bank_accounts",
+        prompt: "What should a stress test of transfers between accounts check afterwards?",
+        choices: &[
+            "That the total of all balances is what it was before",
+            "That every transfer returned true",
+            "That it finished quickly",
+            "That no thread panicked, and nothing else",
+        ],
+        explanation: "Transfers move money without creating or destroying it, so the total is the invariant a race would break.",
+    },
+    Question {
+        section: "stressing_your_code",
+        prompt: "A stress test passes 100 rounds. What does that show?",
+        choices: &[
+            "That no race showed up in those runs, not that there is none",
+            "That the code is free of races",
+            "That the code cannot deadlock",
+            "Nothing, since threads are random",
+        ],
+        explanation: "Stress tests make bad interleavings likely, not certain; a model checker like loom tries them all.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_fetch_add)];
+
+fn sharing_between_threads() {
+    println!("1. Sharing Between Threads:");
+    println!("===========================");
+
+    let words = ["alpha", "beta", "gamma", "delta"];
+    let letters = AtomicU64::new(0);
+    thread::scope(|scope| {
+        for word in &words {
+            let letters = &letters;
+            scope.spawn(move || letters.fetch_add(word.len() as u64, Ordering::Relaxed));
+        }
+    });
+    println!("letters in {:?}: {}", words, letters.into_inner());
+    explain!("thread::scope lets threads borrow from the stack: they all end before it returns");
+    explain!("Sharing &AtomicU64 between them compiles because AtomicU64 is Sync");
+
+    explain!("\nWHAT THE COMPILER RULES OUT:");
+    explain!("============================");
+    explain!("- A plain u64 changed from two threads: &mut u64 cannot be shared (E0499)");
+    explain!("- Rc moved to another thread: Rc is not Send (E0277)");
+    explain!("- RefCell shared between threads: RefCell is not Sync (E0277)");
+
+    explain!("\nWHAT IT CANNOT SEE:");
+    explain!("===================");
+    explain!("- Two correct steps in the wrong order: read, another thread writes, write");
+    explain!("- A lock taken twice, with the state changing in between");
+    explain!("- Locks taken in different orders by different threads (deadlock)");
+    detail!("These are races in behavior, not in memory. Tests find them by running the");
+    detail!("  code on many threads at once, many times, and checking what must hold");
+    println!();
+}
+
+/// A counter that reads, then writes: every step is atomic, the increment is not
+struct RacyCounter(AtomicU32);
+
+impl RacyCounter {
+    fn increment(&self) {
+        let seen = self.0.load(Ordering::SeqCst);
+        // Let another thread in between, as the scheduler sometimes does
+        thread::yield_now();
+        self.0.store(seen + 1, Ordering::SeqCst);
+    }
+}
+
+/// A counter that increments in one step
+struct Counter(AtomicU32);
+
+impl Counter {
+    fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Raise `max` to `value` if it is larger, retrying when another thread
+/// changed `max` since it was read
+fn record_max(max: &AtomicU32, value: u32) {
+    let mut current = max.load(Ordering::Relaxed);
+    while value > current {
+        match max.compare_exchange_weak(current, value, Ordering::Relaxed, Ordering::Relaxed) {
+            Ok(_) => return,
+            Err(actual) => current = actual,
+        }
+    }
+}
+
+/// `check` that a counter ended at `expected`
+fn counted(expected: u32) -> impl Fn(&AtomicU32) -> Result<(), String> {
+    move |count| {
+        let count = count.load(Ordering::SeqCst);
+        if count == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} increments, expected {}: {} lost",
+                count,
+                expected,
+                expected - count
+            ))
+        }
+    }
+}
+
+fn atomic_counters() {
+    println!("2. Atomic Counters: load/store vs fetch_add:");
+    println!("============================================");
+
+    let (threads, iterations) = (4, 1_000);
+    let expected = (threads * iterations) as u32;
+    let racy = stress::check(
+        20,
+        threads,
+        iterations,
+        || RacyCounter(AtomicU32::new(0)),
+        |counter, _, _| counter.increment(),
+        |counter| counted(expected)(&counter.0),
+    );
+    match racy {
+        Ok(_) => println!("load then store: no update lost this time, in 20 rounds"),
+        Err(violation) => println!("load then store: {}", violation),
+    }
+    explain!("Between the load and the store another thread can store too; one of the");
+    explain!("  two increments is then written over. Usually, not always: run it again");
+
+    let counter = Counter(AtomicU32::new(0));
+    let run = stress::run_concurrently(threads, iterations, |_, _| counter.increment());
+    println!(
+        "fetch_add:       {} of {} increments, every time",
+        counter.0.into_inner(),
+        run.operations()
+    );
+    explain!("fetch_add reads and writes as one indivisible step");
+
+    let max = AtomicU32::new(0);
+    stress::run_concurrently(threads, iterations, |thread, iteration| {
+        record_max(&max, (iteration * threads + thread) as u32)
+    });
+    println!("compare_exchange max: {}", max.into_inner());
+    explain!("There is no fetch_* for every update; compare_exchange writes only if the");
+    explain!("  value is still the one read, and says what it is if not, to retry with");
+    detail!("fetch_max exists for this one. Relaxed is enough for a lone counter: only");
+    detail!("  the count matters, not what other memory looks like when it changes");
+    println!();
+}
+
+/// Accounts in cents, each behind its own lock
+struct Bank {
+    accounts: Vec<Mutex<u64>>,
+}
+
+impl Bank {
+    fn new(balances: &[u64]) -> Bank {
+        Bank {
+            accounts: balances
+                .iter()
+                .map(|&balance| Mutex::new(balance))
+                .collect(),
+        }
+    }
+
+    fn account(&self, index: usize) -> MutexGuard<'_, u64> {
+        self.accounts[index]
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn total(&self) -> u64 {
+        (0..self.accounts.len())
+            .map(|index| *self.account(index))
+            .sum()
+    }
+
+    /// Checks the balance under one lock and debits it under another, so the
+    /// debit can use a balance that has changed since
+    fn transfer_split(&self, from: usize, to: usize, amount: u64) -> bool {
+        let balance = *self.account(from);
+        if balance < amount {
+            return false;
+        }
+        thread::yield_now();
+        *self.account(from) = balance - amount;
+        *self.account(to) += amount;
+        true
+    }
+
+    /// Holds both locks from the check to the credit, always taking the
+    /// lower account's first so two transfers cannot wait on each other
+    fn transfer(&self, from: usize, to: usize, amount: u64) -> bool {
+        if from == to {
+            return true;
+        }
+        let (first, second) = (from.min(to), from.max(to));
+        let mut first = self.account(first);
+        let mut second = self.account(second);
+        let (source, target) = if from < to {
+            (&mut *first, &mut *second)
+        } else {
+            (&mut *second, &mut *first)
+        };
+        if *source < amount {
+            return false;
+        }
+        *source -= amount;
+        *target += amount;
+        true
+    }
+}
+
+const BALANCES: [u64; 4] = [1_000, 1_000, 1_000, 1_000];
+
+/// Money moves between accounts but is never made or lost
+fn conserved(bank: &Bank) -> Result<(), String> {
+    let (total, expected) = (bank.total(), BALANCES.iter().sum::<u64>());
+    if total == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "the bank holds {} cents, it started with {}",
+            total, expected
+        ))
+    }
+}
+
+/// Thread `thread` moves money from its account to the next, in amounts
+/// that vary with `iteration`
+fn stress_transfers(
+    transfer: fn(&Bank, usize, usize, u64) -> bool,
+) -> Result<stress::Run, Violation> {
+    stress::check(
+        20,
+        BALANCES.len(),
+        500,
+        || Bank::new(&BALANCES),
+        |bank, thread, iteration| {
+            transfer(
+                bank,
+                thread,
+                (thread + 1) % BALANCES.len(),
+                (iteration % 7) as u64,
+            );
+        },
+        conserved,
+    )
+}
+
+fn bank_accounts() {
+    println!("3. A Bank: Check and Debit Under One Lock:");
+    println!("==========================================");
+
+    match stress_transfers(Bank::transfer_split) {
+        Ok(_) => println!("split transfer: the total held this time, in 20 rounds"),
+        Err(violation) => println!("split transfer: {}", violation),
+    }
+    explain!("Each step locks, so nothing is a data race; but the debit writes back a");
+    explain!("  balance read before another thread's credit, and the credit is gone");
+
+    match stress_transfers(Bank::transfer) {
+        Ok(run) => println!(
+            "transfer:       the total held in 20 rounds of {} transfers",
+            run.operations()
+        ),
+        Err(violation) => println!("transfer:       {}", violation),
+    }
+    explain!("Holding the locks from the check to the credit makes the transfer one step");
+    explain!("  for every other thread: they see it all or none of it");
+    detail!("Thread 3 moves money to account 0 while thread 0 moves from 0 to 1: locking");
+    detail!("  `from` first could leave each holding one lock and waiting for the other");
+    deep!("With one Mutex around all the accounts there is no order to get wrong, but");
+    deep!("  transfers between unrelated accounts then wait for each other too");
+    println!();
+}
+
+fn stressing_your_code() {
+    println!("4. Stress-Testing Your Own Code:");
+    println!("================================");
+
+    explain!("rust_learn_utils::stress is a library, for your own tests:");
+    explain!("");
+    explain!("  use rust_learn_utils::stress;");
+    explain!("");
+    explain!("  #[test]");
+    explain!("  fn test_transfers_keep_the_total() {{");
+    explain!("      let result = stress::check(");
+    explain!("          50,                          // rounds, each with fresh state");
+    explain!("          8, 1_000,                    // threads, iterations per thread");
+    explain!("          || Bank::new(&[100; 8]),");
+    explain!(
+        "          |bank, thread, i| {{ bank.transfer(thread, (thread + 1) % 8, i as u64 % 5); }},"
+    );
+    explain!(
+        "          |bank| if bank.total() == 800 {{ Ok(()) }} else {{ Err(bank.total().to_string()) }},"
+    );
+    explain!("      );");
+    explain!("      assert!(result.is_ok(), \"{{}}\", result.unwrap_err());");
+    explain!("  }}");
+
+    let hits = AtomicU64::new(0);
+    let run = stress::run_concurrently(8, 10_000, |_, _| {
+        hits.fetch_add(1, Ordering::Relaxed);
+    });
+    println!(
+        "\n8 threads x 10,000 fetch_adds: {} in {:?}",
+        hits.into_inner(),
+        run.elapsed
+    );
+    explain!("Every thread waits at a Barrier until all have started, so they overlap");
+    explain!("  from the first iteration instead of one finishing before the next begins");
+
+    explain!("\nGETTING THE MOST OUT OF IT:");
+    explain!("===========================");
+    explain!("- Check an invariant (a total, a length, a count), not every step");
+    explain!("- More rounds beat more iterations: each round starts the race afresh");
+    explain!(
+        "- Run tests with --release too: the optimizer reorders and keeps values in registers"
+    );
+    explain!(
+        "- Passing means no race showed up, not that there is none; loom tries every interleaving"
+    );
+    detail!("A panic in the operation reaches the test once every thread has finished,");
+    detail!("  so an assert! inside it fails the test as usual");
+    println!();
+}
+
+// Helper functions
+
+fn predict_fetch_add() {
+    let count = AtomicU32::new(10);
+    let before = count.fetch_add(5, Ordering::SeqCst);
+    println!("fetch_add returned {}", before);
+    let run = stress::run_concurrently(4, 250, |_, _| {
+        count.fetch_add(1, Ordering::Relaxed);
+    });
+    println!("{} operations", run.operations());
+    println!("count is {}", count.into_inner());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_atomic_updates_survive_stress() {
+        let result = stress::check(
+            10,
+            4,
+            500,
+            || Counter(AtomicU32::new(0)),
+            |counter, _, _| counter.increment(),
+            |counter| counted(2_000)(&counter.0),
+        );
+        assert!(result.is_ok(), "{:?}", result);
+
+        let max = AtomicU32::new(0);
+        stress::run_concurrently(4, 500, |thread, iteration| {
+            record_max(&max, (iteration * 4 + thread) as u32)
+        });
+        assert_eq!(max.into_inner(), 1_999);
+    }
+
+    #[test]
+    fn test_transfers_keep_the_total() {
+        let result = stress_transfers(Bank::transfer);
+        assert!(result.is_ok(), "{}", result.unwrap_err());
+
+        let bank = Bank::new(&[5, 0]);
+        assert!(!bank.transfer(0, 1, 6));
+        assert!(bank.transfer(0, 1, 5));
+        assert!(bank.transfer(1, 0, 2));
+        assert_eq!((*bank.account(0), *bank.account(1)), (2, 3));
+        assert!(!bank.transfer_split(1, 0, 4));
+    }
+}
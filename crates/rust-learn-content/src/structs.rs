@@ -0,0 +1,309 @@
+/// Structs in Rust - Grouping Data With Methods
+///
+/// A struct names a group of values and gives each a field name, so related
+/// data travels together. `impl` blocks then attach functions to it: methods
+/// that take `self` in one of three ways, and associated functions like `new`
+/// that do not take it at all.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn structs() {
+    println!("=== Structs Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- structs <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(defining_structs, "Defining and Instantiating Structs"),
+    section!(field_init_shorthand, "Field Init Shorthand"),
+    section!(struct_update_syntax, "Struct Update Syntax").with_checkpoint(
+        "After `let b = User { email: ..., ..a };`, can you still use `a.username` (a String)?",
+        false,
+        "`..a` moved the username String into b; only a's Copy fields and the ones b replaced are still usable.",
+    ),
+    section!(tuple_structs, "Tuple Structs"),
+    section!(unit_structs, "Unit-Like Structs"),
+    section!(impl_blocks, "impl Blocks and Methods"),
+    section!(associated_functions, "Associated Functions vs Methods"),
+    section!(self_receivers, "self, &self and &mut self").with_checkpoint(
+        "Can you call a `&mut self` method on a value bound with plain `let`?",
+        false,
+        "The method borrows the value mutably, so the binding has to be `let mut`.",
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "field_init_shorthand",
+        prompt: "In `fn build(email: String) -> User { User { email, active: true } }`, what does `email` alone mean?",
+        choices: &[
+            "email: email, the parameter of the same name",
+            "An empty String for the field",
+            "A reference to the parameter",
+            "It does not compile without `email: email`",
+        ],
+        explanation: "When a variable has the same name as the field, the field init shorthand lets you write it once.",
+    },
+    Question {
+        section: "tuple_structs",
+        prompt: "With `struct Meters(f64);` and `struct Feet(f64);`, can a function taking Meters be given a Feet?",
+        choices: &[
+            "No: they are different types, even with the same field",
+            "Yes: both hold an f64",
+            "Only with `as Meters`",
+            "Only if the value is positive",
+        ],
+        explanation: "Each tuple struct is its own type, which is the point of a newtype: the compiler keeps units apart.",
+    },
+    Question {
+        section: "associated_functions",
+        prompt: "How do you call an associated function `fn square(size: u32) -> Rectangle` defined in `impl Rectangle`?",
+        choices: &[
+            "Rectangle::square(3)",
+            "rect.square(3)",
+            "square(3)",
+            "Rectangle.square(3)",
+        ],
+        explanation: "It takes no self, so it is called on the type with ::, like String::from; methods are called on a value with a dot.",
+    },
+    Question {
+        section: "self_receivers",
+        prompt: "A method takes `self` (not &self). What happens to the value it is called on?",
+        choices: &[
+            "It moves into the method and cannot be used afterwards",
+            "It is copied, whatever its type",
+            "It is borrowed until the method returns",
+            "It is borrowed mutably",
+        ],
+        explanation: "`self` takes ownership, so it suits methods that turn the value into something else, like into_iter or a builder's build.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_counter)];
+
+#[derive(Debug)]
+struct User {
+    active: bool,
+    username: String,
+    email: String,
+    sign_in_count: u64,
+}
+
+#[derive(Debug)]
+struct Color(u8, u8, u8);
+
+#[derive(Debug)]
+struct Meters(f64);
+
+#[derive(Debug)]
+struct AlwaysEqual;
+
+#[derive(Debug)]
+struct Rectangle {
+    width: u32,
+    height: u32,
+}
+
+impl Rectangle {
+    /// An associated function: no `self`, called as `Rectangle::new`
+    fn new(width: u32, height: u32) -> Rectangle {
+        Rectangle { width, height }
+    }
+
+    fn square(size: u32) -> Rectangle {
+        Rectangle {
+            width: size,
+            height: size,
+        }
+    }
+
+    /// A method reading the value: `&self` is short for `self: &Rectangle`
+    fn area(&self) -> u32 {
+        self.width * self.height
+    }
+
+    fn can_hold(&self, other: &Rectangle) -> bool {
+        self.width > other.width && self.height > other.height
+    }
+
+    /// A method changing the value in place
+    fn scale(&mut self, factor: u32) {
+        self.width *= factor;
+        self.height *= factor;
+    }
+
+    /// A method consuming the value, turning it into something else
+    fn into_dimensions(self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+}
+
+fn build_user(email: String, username: String) -> User {
+    User {
+        active: true,
+        username,
+        email,
+        sign_in_count: 1,
+    }
+}
+
+fn defining_structs() {
+    println!("1. Defining and Instantiating Structs:");
+    println!("======================================");
+
+    let mut user1 = User {
+        active: true,
+        username: String::from("someusername123"),
+        email: String::from("someone@example.com"),
+        sign_in_count: 1,
+    };
+    println!("user1.email = {}", user1.email);
+    explain!("Fields can be given in any order; each is read with a dot");
+
+    user1.email = String::from("anotheremail@example.com");
+    println!("changed email = {}", user1.email);
+    explain!("Changing a field needs the whole instance to be mut; Rust has no mut fields");
+    user1.sign_in_count += 1;
+    println!("sign-ins = {}", user1.sign_in_count);
+
+    println!("user1 = {:?}", user1);
+    detail!("{{:?}} prints every field because of #[derive(Debug)] on the struct");
+    deep!("The fields sit next to each other in one value; the Strings point to the heap");
+    println!();
+}
+
+fn field_init_shorthand() {
+    println!("2. Field Init Shorthand:");
+    println!("========================");
+
+    let user = build_user(String::from("learner@example.com"), String::from("learner"));
+    println!("{} <{}>", user.username, user.email);
+    explain!("In build_user, `username` stands for `username: username`");
+    explain!("It works whenever a variable has the same name as the field");
+    println!();
+}
+
+fn struct_update_syntax() {
+    println!("3. Struct Update Syntax:");
+    println!("========================");
+
+    let user1 = build_user(String::from("first@example.com"), String::from("first"));
+    let user2 = User {
+        email: String::from("second@example.com"),
+        ..user1
+    };
+    println!("user2 = {:?}", user2);
+    explain!("`..user1` fills every field not listed from user1");
+
+    // println!("{}", user1.username);  // COMPILE ERROR: username moved to user2
+    println!("user1.email is still usable: {}", user1.email);
+    println!("user1.active is still usable: {}", user1.active);
+    explain!("The username String MOVED into user2, so user1 as a whole is partly moved");
+    detail!("Copy fields like bool and u64 are copied, and email was not taken at all");
+    println!();
+}
+
+fn tuple_structs() {
+    println!("4. Tuple Structs:");
+    println!("=================");
+
+    let black = Color(0, 0, 0);
+    let distance = Meters(42.5);
+    println!("black = {:?}, red part = {}", black, black.0);
+    println!("distance = {} m", distance.0);
+    explain!("Tuple structs name the type but not the fields: read them as .0, .1, ...");
+
+    let Color(red, green, blue) = black;
+    println!("destructured: {} {} {}", red, green, blue);
+    explain!("A one-field tuple struct (a newtype) keeps Meters and plain f64s apart");
+    println!();
+}
+
+fn unit_structs() {
+    println!("5. Unit-Like Structs:");
+    println!("=====================");
+
+    let subject = AlwaysEqual;
+    println!("subject = {:?}", subject);
+    println!("size = {} bytes", std::mem::size_of::<AlwaysEqual>());
+    explain!("A struct with no fields takes no memory");
+    explain!("Useful to implement a trait on when there is no data to keep");
+    println!();
+}
+
+fn impl_blocks() {
+    println!("6. impl Blocks and Methods:");
+    println!("===========================");
+
+    let rect = Rectangle {
+        width: 30,
+        height: 50,
+    };
+    println!("area of {:?} = {}", rect, rect.area());
+    explain!("Methods are defined in `impl Rectangle` and take self first");
+
+    let small = Rectangle::new(10, 40);
+    println!("rect can hold small: {}", rect.can_hold(&small));
+    explain!("rect.area() is sugar for Rectangle::area(&rect); Rust borrows for you");
+    detail!("Automatic referencing adds the &, &mut or * the method's self needs");
+    println!();
+}
+
+fn associated_functions() {
+    println!("7. Associated Functions vs Methods:");
+    println!("===================================");
+
+    let square = Rectangle::square(3);
+    let rect = Rectangle::new(4, 5);
+    println!("Rectangle::square(3) = {:?}", square);
+    println!("Rectangle::new(4, 5) = {:?}", rect);
+    explain!("Associated functions have no self and are called on the type with ::");
+    explain!("Constructors like new are associated functions; String::from is one too");
+    println!("square.area() = {}", square.area());
+    explain!("Methods have self and are called on a value with a dot");
+    println!();
+}
+
+fn self_receivers() {
+    println!("8. self, &self and &mut self:");
+    println!("=============================");
+
+    let mut rect = Rectangle::new(2, 3);
+    println!("&self     - rect.area() reads it: {}", rect.area());
+    rect.scale(10);
+    println!("&mut self - rect.scale(10) changed it: {:?}", rect);
+    explain!("&mut self needs `let mut rect`, and no other borrow of rect in use");
+
+    let (width, height) = rect.into_dimensions();
+    println!(
+        "self      - rect.into_dimensions() took it: {} x {}",
+        width, height
+    );
+    // println!("{:?}", rect);  // COMPILE ERROR: rect moved into into_dimensions
+    explain!("self moves the value in; the method decides what is left of it");
+    detail!("Pick the least: &self to read, &mut self to change, self to consume");
+    println!();
+}
+
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn tick(&mut self) -> u32 {
+        self.count += 1;
+        self.count
+    }
+}
+
+fn predict_counter() {
+    let mut counter = Counter { count: 0 };
+    counter.tick();
+    let second = counter.tick();
+    let copy = Counter { ..counter };
+    println!("{} {}", second, copy.count + counter.count);
+}
@@ -0,0 +1,421 @@
+/// Trait Objects in Rust - Dynamic Dispatch
+///
+/// A generic function is copied for each type it is used with, so every value
+/// it handles has one type known at compile time. A trait object, `dyn Trait`
+/// behind a pointer like `Box` or `&`, is the other way: values of different
+/// types share one vector or one function, and each method call is looked up
+/// at runtime in a table that travels with the pointer. Only traits whose
+/// methods can be called that way can be made into objects.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::fmt;
+use std::hint::black_box;
+use std::mem::size_of;
+use std::time::Instant;
+
+pub fn trait_objects() {
+    println!("=== Trait Objects Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- trait_objects <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(box_dyn, "Box<dyn Trait>"),
+    section!(heterogeneous_vec, "A GUI Screen: Vec<Box<dyn Draw>>").with_checkpoint(
+        "Can a `Vec<T>` with `T: Draw` hold a Button and a SelectBox at the same time?",
+        false,
+        "A generic T is one type for the whole Vec; holding both needs Vec<Box<dyn Draw>>.",
+    ),
+    section!(borrowed_dyn, "&dyn Trait Without a Box"),
+    section!(returning_dyn, "Returning Different Types"),
+    section!(fat_pointers, "What a dyn Pointer Holds"),
+    section!(object_safety, "Object Safety").with_checkpoint(
+        "Can you make a `Box<dyn Clone>`?",
+        false,
+        "clone returns Self, whose size a trait object does not know, so Clone is not dyn compatible.",
+    ),
+    section!(static_vs_dynamic, "Generics vs Trait Objects"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "heterogeneous_vec",
+        prompt: "Which type holds a Button and a TextField, both implementing Draw, in one vector?",
+        choices: &[
+            "Vec<Box<dyn Draw>>",
+            "Vec<dyn Draw>",
+            "Vec<impl Draw>",
+            "Vec<T> where T: Draw",
+        ],
+        explanation: "The elements have different sizes, so each goes behind a pointer; dyn Draw alone has no size known at compile time.",
+    },
+    Question {
+        section: "fat_pointers",
+        prompt: "On a 64-bit target, how big is a `&dyn Draw`?",
+        choices: &[
+            "16 bytes: a pointer to the value and one to its vtable",
+            "8 bytes, like any reference",
+            "The size of the value it points to",
+            "It depends on the trait's number of methods",
+        ],
+        explanation: "A trait object reference is a fat pointer: the data pointer plus a pointer to the table of the type's methods.",
+    },
+    Question {
+        section: "object_safety",
+        prompt: "Which method keeps a trait from being used as `dyn Trait`?",
+        choices: &[
+            "fn compare<T: Shape>(&self, other: &T) -> bool;",
+            "fn area(&self) -> f64;",
+            "fn name(&self) -> String;",
+            "fn resize(&mut self, factor: f64);",
+        ],
+        explanation: "A generic method would need one vtable entry per possible T; add `where Self: Sized` to leave it out of the object instead.",
+    },
+    Question {
+        section: "static_vs_dynamic",
+        prompt: "What does dynamic dispatch cost compared with a generic function?",
+        choices: &[
+            "A lookup per call, and calls the compiler cannot inline",
+            "A heap allocation per call",
+            "Nothing at all",
+            "A copy of the value per call",
+        ],
+        explanation: "The method's address is read from the vtable at runtime; in exchange there is one copy of the code instead of one per type.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_dispatch)];
+
+/// Something that can be drawn on the screen
+trait Draw {
+    fn draw(&self) -> String;
+}
+
+struct Button {
+    width: u32,
+    height: u32,
+    label: String,
+}
+
+impl Draw for Button {
+    fn draw(&self) -> String {
+        format!("[ {} ] ({}x{})", self.label, self.width, self.height)
+    }
+}
+
+struct SelectBox {
+    width: u32,
+    options: Vec<String>,
+}
+
+impl Draw for SelectBox {
+    fn draw(&self) -> String {
+        format!("<{}> (width {})", self.options.join(" | "), self.width)
+    }
+}
+
+/// Added later, possibly by another crate: Screen still draws it
+struct TextField {
+    placeholder: String,
+}
+
+impl Draw for TextField {
+    fn draw(&self) -> String {
+        format!("|{:_<12}|", self.placeholder)
+    }
+}
+
+fn button(label: &str) -> Button {
+    Button {
+        width: 50,
+        height: 10,
+        label: label.to_string(),
+    }
+}
+
+fn select_box() -> SelectBox {
+    SelectBox {
+        width: 75,
+        options: vec!["Yes".to_string(), "Maybe".to_string(), "No".to_string()],
+    }
+}
+
+fn box_dyn() {
+    println!("1. Box<dyn Trait>:");
+    println!("==================");
+
+    let component: Box<dyn Draw> = Box::new(button("OK"));
+    println!("component.draw() = {}", component.draw());
+    explain!(
+        "`Box<dyn Draw>` owns some value whose type implements Draw; which type is known only at runtime"
+    );
+
+    let component: Box<dyn Draw> = Box::new(select_box());
+    println!("component.draw() = {}", component.draw());
+    explain!("The same variable now holds a SelectBox: its type is Box<dyn Draw> either way");
+    detail!(
+        "`dyn` marks the trait used as a type; without it, Box<Draw> is an error in edition 2021+"
+    );
+    println!();
+}
+
+/// Draws whatever it holds, whichever types those are
+struct Screen {
+    components: Vec<Box<dyn Draw>>,
+}
+
+impl Screen {
+    fn run(&self) {
+        for (index, component) in self.components.iter().enumerate() {
+            println!("  {}: {}", index, component.draw());
+        }
+    }
+}
+
+fn heterogeneous_vec() {
+    println!("2. A GUI Screen: Vec<Box<dyn Draw>>:");
+    println!("====================================");
+
+    let screen = Screen {
+        components: vec![
+            Box::new(select_box()),
+            Box::new(button("OK")),
+            Box::new(TextField {
+                placeholder: "name".to_string(),
+            }),
+        ],
+    };
+    screen.run();
+    explain!("Three different types in one Vec: each element is a Box<dyn Draw>");
+    explain!(
+        "Screen only knows they can draw; a new type implementing Draw works without changing it"
+    );
+    // let screen = Screen { components: vec![Box::new(String::from("Hi"))] };
+    //     // COMPILE ERROR: the trait `Draw` is not implemented for `String`
+    detail!("This is the duck typing of dynamic languages, checked at compile time");
+    println!();
+}
+
+/// Borrows any drawable value: no Box, no allocation
+fn describe(component: &dyn Draw) -> String {
+    format!("drawing {} characters", component.draw().len())
+}
+
+fn borrowed_dyn() {
+    println!("3. &dyn Trait Without a Box:");
+    println!("============================");
+
+    let ok = button("OK");
+    let choice = select_box();
+    println!("describe(&ok)     = {}", describe(&ok));
+    println!("describe(&choice) = {}", describe(&choice));
+    explain!("&Button becomes &dyn Draw on its own where a &dyn Draw is expected");
+    explain!("Any pointer works: &dyn, &mut dyn, Box<dyn>, Rc<dyn>, Arc<dyn>");
+
+    let mut text = String::new();
+    let out: &mut dyn fmt::Write = &mut text;
+    write!(out, "written through &mut dyn fmt::Write").expect("a String cannot fail");
+    println!("{}", text);
+    detail!("std uses it too: fmt::Formatter writes through a &mut dyn fmt::Write");
+    println!();
+}
+
+/// Two branches, two types: impl Draw could not say this
+fn widget(kind: &str) -> Box<dyn Draw> {
+    if kind == "button" {
+        Box::new(button("Go"))
+    } else {
+        Box::new(TextField {
+            placeholder: kind.to_string(),
+        })
+    }
+}
+
+fn returning_dyn() {
+    println!("4. Returning Different Types:");
+    println!("=============================");
+
+    for kind in ["button", "email"] {
+        println!("widget({:?}) = {}", kind, widget(kind).draw());
+    }
+    explain!(
+        "`-> impl Draw` means ONE hidden type; `-> Box<dyn Draw>` may be a different one each call"
+    );
+    // fn widget(kind: &str) -> impl Draw { if ... { button("Go") } else { TextField { .. } } }
+    //     // COMPILE ERROR: `if` and `else` have incompatible types
+    detail!("This is how the traits lesson's two-branch example compiles");
+    println!();
+}
+
+fn fat_pointers() {
+    println!("5. What a dyn Pointer Holds:");
+    println!("============================");
+
+    println!("size_of::<&Button>()        = {}", size_of::<&Button>());
+    println!("size_of::<&dyn Draw>()      = {}", size_of::<&dyn Draw>());
+    println!(
+        "size_of::<Box<dyn Draw>>()  = {}",
+        size_of::<Box<dyn Draw>>()
+    );
+    explain!(
+        "A trait object pointer is twice as wide: a pointer to the value, and one to a vtable"
+    );
+    explain!("The vtable has the type's draw, its size and alignment, and how to drop it");
+
+    let ok = button("OK");
+    let first: &dyn Draw = &ok;
+    let second: &dyn Draw = &ok;
+    println!(
+        "two &dyn Draw to one Button point to the same data: {}",
+        std::ptr::addr_eq(first, second)
+    );
+    detail!("component.draw() reads draw's address from the vtable, then calls it");
+    deep!("There is one vtable per (type, trait) pair, made by the compiler");
+    println!();
+}
+
+/// Every method can be called through a vtable, so `dyn Shape` works
+trait Shape {
+    fn area(&self) -> f64;
+    fn name(&self) -> String;
+
+    /// Generic, so left out of the vtable: not callable on a dyn Shape
+    fn larger_than<T: Shape>(&self, other: &T) -> bool
+    where
+        Self: Sized,
+    {
+        self.area() > other.area()
+    }
+}
+
+struct Circle(f64);
+struct Square(f64);
+
+impl Shape for Circle {
+    fn area(&self) -> f64 {
+        std::f64::consts::PI * self.0 * self.0
+    }
+    fn name(&self) -> String {
+        format!("circle r={}", self.0)
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.0 * self.0
+    }
+    fn name(&self) -> String {
+        format!("square side={}", self.0)
+    }
+}
+
+fn object_safety() {
+    println!("6. Object Safety:");
+    println!("=================");
+
+    let shapes: Vec<Box<dyn Shape>> = vec![Box::new(Circle(1.0)), Box::new(Square(2.0))];
+    for shape in &shapes {
+        println!("{:<14} area {:.2}", shape.name(), shape.area());
+    }
+    println!(
+        "Circle(1.0).larger_than(&Square(2.0)) = {}",
+        Circle(1.0).larger_than(&Square(2.0))
+    );
+    explain!(
+        "A trait can be `dyn` (is dyn compatible, or object safe) when each method can go in a vtable:"
+    );
+    explain!("  - no generic methods: one vtable entry cannot cover every T");
+    explain!("  - no `Self` in arguments or the return type: the object's real type is unknown");
+    explain!("  - no associated functions without self, like `fn new() -> Self`");
+
+    // let cloneable: Box<dyn Clone> = Box::new(5);
+    //     // COMPILE ERROR: the trait `Clone` is not dyn compatible (E0038)
+    explain!("Clone returns Self, so Box<dyn Clone> does not compile (E0038)");
+    detail!(
+        "`where Self: Sized` on a method leaves it out of the vtable: larger_than stays generic"
+    );
+    detail!("  and Shape stays dyn compatible; the method just cannot be called on a dyn Shape");
+    println!();
+}
+
+/// One copy per T: calls to area are direct, and can be inlined
+fn total_area_static<T: Shape>(shapes: &[T]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+/// One copy for every shape: each call goes through the vtable
+fn total_area_dynamic(shapes: &[Box<dyn Shape>]) -> f64 {
+    shapes.iter().map(|shape| shape.area()).sum()
+}
+
+fn static_vs_dynamic() {
+    println!("7. Generics vs Trait Objects:");
+    println!("=============================");
+
+    let count = 200_000;
+    let squares: Vec<Square> = (0..count).map(|i| Square(i as f64 % 10.0)).collect();
+    let boxed: Vec<Box<dyn Shape>> = (0..count)
+        .map(|i| Box::new(Square(i as f64 % 10.0)) as Box<dyn Shape>)
+        .collect();
+
+    let start = Instant::now();
+    let static_total = black_box(total_area_static(black_box(&squares)));
+    let static_time = start.elapsed();
+    let start = Instant::now();
+    let dynamic_total = black_box(total_area_dynamic(black_box(&boxed)));
+    let dynamic_time = start.elapsed();
+    let profile = if cfg!(debug_assertions) {
+        "a dev"
+    } else {
+        "an optimized"
+    };
+    println!("{} build:", profile);
+    println!(
+        "static:  total {} in {:>8.1?} (Vec<Square>)",
+        static_total, static_time
+    );
+    println!(
+        "dynamic: total {} in {:>8.1?} (Vec<Box<dyn Shape>>)",
+        dynamic_total, dynamic_time
+    );
+    explain!("Same answer; the generic version knows it is calling Square::area and can inline it");
+    explain!("The dyn version reads each call from a vtable and follows a Box to each value");
+    detail!("In a release build that makes the dyn loop several times slower; a dev build inlines");
+    detail!(
+        "nothing, so there the two come out close. Try `cargo run --release -- trait_objects 7`"
+    );
+
+    explain!("\nCHOOSE:");
+    explain!("=======");
+    explain!("generics (static)      one type per use, fastest calls, a code copy per type");
+    explain!("trait objects (dyn)    mixed types at runtime, one code copy, a lookup per call");
+    deep!("Part of the gap is the Boxes: each Square lives in its own allocation");
+    println!();
+}
+
+trait Speak {
+    fn speak(&self) -> String {
+        "...".to_string()
+    }
+}
+
+struct Dog;
+struct Fish;
+
+impl Speak for Dog {
+    fn speak(&self) -> String {
+        "Woof".to_string()
+    }
+}
+
+impl Speak for Fish {}
+
+fn predict_dispatch() {
+    let animals: Vec<Box<dyn Speak>> = vec![Box::new(Fish), Box::new(Dog), Box::new(Fish)];
+    let words: Vec<String> = animals.iter().map(|animal| animal.speak()).collect();
+    println!("{} {}", words.join(" "), animals.len());
+}
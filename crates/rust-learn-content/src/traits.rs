@@ -0,0 +1,380 @@
+/// Traits in Rust - Shared Behavior
+///
+/// A trait names a set of methods a type can promise to have. Types opt in
+/// with an `impl Trait for Type` block, and functions can then accept any type
+/// that has the behavior: with a trait bound on a generic parameter, or with
+/// the `impl Trait` shorthand. Traits can build on other traits, and an
+/// `impl` can apply only to the types that meet a bound.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::fmt;
+
+pub fn traits() {
+    println!("=== Traits Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- traits <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(defining_traits, "Defining and Implementing a Trait"),
+    section!(default_methods, "Default Methods"),
+    section!(trait_bounds, "Trait Bounds and where Clauses"),
+    section!(impl_trait_arguments, "impl Trait in Argument Position"),
+    section!(impl_trait_returns, "Returning impl Trait").with_checkpoint(
+        "Can a function returning `impl Summary` return a Tweet from one branch and a NewsArticle from another?",
+        false,
+        "`impl Trait` hides one concrete type chosen by the function; two different types need a Box<dyn Summary> instead.",
+    ),
+    section!(supertraits, "Supertraits"),
+    section!(conditional_impls, "Conditional Implementations").with_checkpoint(
+        "Given `impl<T: Display + PartialOrd> Pair<T> { fn largest(&self) }`, can you call largest on a Pair<Vec<i32>>?",
+        false,
+        "Vec<i32> is not Display, so that impl block does not apply to Pair<Vec<i32>>; the rest of Pair's methods still do.",
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "default_methods",
+        prompt: "A trait gives `summarize` a default body. What must an `impl` of the trait do about it?",
+        choices: &[
+            "Nothing: it may keep the default or override it",
+            "It must override summarize",
+            "It must call the default with super::summarize()",
+            "It must mark it #[default]",
+        ],
+        explanation: "Default methods are inherited unless the impl defines its own; only the methods without a body must be written.",
+    },
+    Question {
+        section: "trait_bounds",
+        prompt: "Which signature accepts any `T` that is both Display and Clone?",
+        choices: &[
+            "fn show<T>(item: &T) where T: Display + Clone",
+            "fn show<T: Display, Clone>(item: &T)",
+            "fn show<T>(item: &T) where T: Display, Clone",
+            "fn show<T: Display | Clone>(item: &T)",
+        ],
+        explanation: "Bounds on one parameter are joined with +; `where` moves them after the signature when they get long.",
+    },
+    Question {
+        section: "impl_trait_arguments",
+        prompt: "What is the difference between `fn a(x: &impl Summary, y: &impl Summary)` and `fn b<T: Summary>(x: &T, y: &T)`?",
+        choices: &[
+            "b needs x and y to be the same type; a does not",
+            "None: they are the same function",
+            "a uses dynamic dispatch, b does not",
+            "a only takes references to traits, b takes values",
+        ],
+        explanation: "Each `impl Trait` argument is its own anonymous type parameter; one named T forces both to the same type.",
+    },
+    Question {
+        section: "supertraits",
+        prompt: "`trait OutlinePrint: fmt::Display` means what for a type implementing OutlinePrint?",
+        choices: &[
+            "It must implement Display as well",
+            "It gets Display implemented for free",
+            "It can no longer implement Display",
+            "Its Display output is outlined automatically",
+        ],
+        explanation: "A supertrait is a requirement: OutlinePrint's methods may use to_string() because every implementor is Display too.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_default)];
+
+/// Something that can be summarized in a line
+trait Summary {
+    /// Required: every implementor writes this one
+    fn summarize_author(&self) -> String;
+
+    /// Provided: implementors get it unless they write their own
+    fn summarize(&self) -> String {
+        format!("(Read more from {}...)", self.summarize_author())
+    }
+}
+
+struct NewsArticle {
+    headline: String,
+    location: String,
+    author: String,
+}
+
+struct Tweet {
+    username: String,
+    content: String,
+    retweets: u32,
+}
+
+impl Summary for NewsArticle {
+    fn summarize_author(&self) -> String {
+        self.author.clone()
+    }
+
+    /// Overrides the default
+    fn summarize(&self) -> String {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+}
+
+impl Summary for Tweet {
+    fn summarize_author(&self) -> String {
+        format!("@{}", self.username)
+    }
+}
+
+impl fmt::Display for Tweet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "@{}: {} ({} retweets)",
+            self.username, self.content, self.retweets
+        )
+    }
+}
+
+fn article() -> NewsArticle {
+    NewsArticle {
+        headline: String::from("Penguins win the Stanley Cup Championship!"),
+        location: String::from("Pittsburgh, PA, USA"),
+        author: String::from("Iceburgh"),
+    }
+}
+
+fn tweet() -> Tweet {
+    Tweet {
+        username: String::from("horse_ebooks"),
+        content: String::from("of course, as you probably already know, people"),
+        retweets: 3,
+    }
+}
+
+fn defining_traits() {
+    println!("1. Defining and Implementing a Trait:");
+    println!("=====================================");
+
+    let article = article();
+    let tweet = tweet();
+    println!("article author: {}", article.summarize_author());
+    println!("tweet author:   {}", tweet.summarize_author());
+    explain!("`trait Summary` lists method signatures; `impl Summary for Tweet` fills them in");
+    explain!("Two unrelated types now share a behavior, each in its own way");
+    detail!("The trait must be in scope (`use`) to call its methods on a value");
+    deep!(
+        "You may implement a trait for a type only if the trait or the type is local to your crate (the orphan rule)"
+    );
+    println!();
+}
+
+fn default_methods() {
+    println!("2. Default Methods:");
+    println!("===================");
+
+    println!("tweet:   {}", tweet().summarize());
+    println!("article: {}", article().summarize());
+    explain!("Tweet kept the default summarize; NewsArticle overrode it");
+    explain!("The default calls summarize_author, which every implementor must write");
+    detail!("An impl only has to write the methods without a body");
+    println!();
+}
+
+/// Any T that is Summary; monomorphized into one copy per type used
+fn notify<T: Summary>(item: &T) -> String {
+    format!("Breaking news! {}", item.summarize())
+}
+
+/// Several bounds, written after the signature
+fn announce<T>(item: &T) -> String
+where
+    T: Summary + fmt::Display,
+{
+    format!("{} | {}", item, item.summarize_author())
+}
+
+fn trait_bounds() {
+    println!("3. Trait Bounds and where Clauses:");
+    println!("==================================");
+
+    println!("{}", notify(&article()));
+    println!("{}", notify(&tweet()));
+    explain!("`fn notify<T: Summary>(item: &T)` takes any type that implements Summary");
+
+    println!("{}", announce(&tweet()));
+    // announce(&article());  // COMPILE ERROR: NewsArticle doesn't implement Display
+    explain!("`T: Summary + Display` asks for both; a `where` clause keeps the signature readable");
+    detail!("The compiler makes a copy of notify for each T it is called with: no runtime cost");
+    println!();
+}
+
+fn describe(first: &impl Summary, second: &impl Summary) -> String {
+    format!(
+        "{} / {}",
+        first.summarize_author(),
+        second.summarize_author()
+    )
+}
+
+fn impl_trait_arguments() {
+    println!("4. impl Trait in Argument Position:");
+    println!("===================================");
+
+    println!("{}", describe(&article(), &tweet()));
+    explain!("`item: &impl Summary` is shorthand for a generic parameter with a Summary bound");
+    explain!("Each `impl Summary` is its own type: here one is a NewsArticle, the other a Tweet");
+    detail!("With `<T: Summary>(first: &T, second: &T)` both would have to be the same type");
+    detail!("A caller cannot name the type with ::<> when the parameter is impl Trait");
+    println!();
+}
+
+fn returns_summarizable() -> impl Summary {
+    tweet()
+}
+
+/// Iterator adapters have long types; impl Trait hides them
+fn evens(limit: u32) -> impl Iterator<Item = u32> {
+    (0..limit).filter(|n| n % 2 == 0)
+}
+
+fn impl_trait_returns() {
+    println!("5. Returning impl Trait:");
+    println!("========================");
+
+    let item = returns_summarizable();
+    println!("{}", item.summarize());
+    explain!("The caller only knows it got *some* type that implements Summary");
+
+    let numbers: Vec<u32> = evens(10).collect();
+    println!("evens(10) = {:?}", numbers);
+    explain!("It shines for iterators and closures, whose real types are long or unnameable");
+
+    // fn pick(switch: bool) -> impl Summary {
+    //     if switch { article() } else { tweet() }  // COMPILE ERROR: `if` and `else` have incompatible types
+    // }
+    explain!(
+        "The function still returns ONE concrete type; two different ones need Box<dyn Summary>"
+    );
+    println!();
+}
+
+/// Implementors must be Display, so outline_print can use to_string
+trait OutlinePrint: fmt::Display {
+    fn outline_print(&self) {
+        let output = self.to_string();
+        let len = output.len();
+        println!("{}", "*".repeat(len + 4));
+        println!("*{}*", " ".repeat(len + 2));
+        println!("* {} *", output);
+        println!("*{}*", " ".repeat(len + 2));
+        println!("{}", "*".repeat(len + 4));
+    }
+}
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl OutlinePrint for Point {}
+
+fn supertraits() {
+    println!("6. Supertraits:");
+    println!("===============");
+
+    Point { x: 1, y: -3 }.outline_print();
+    explain!("`trait OutlinePrint: Display` means every OutlinePrint type is also Display");
+    explain!("So the default outline_print can call self.to_string()");
+    // impl OutlinePrint for NewsArticle {}  // COMPILE ERROR: NewsArticle doesn't implement Display
+    detail!("Without the Display impl, `impl OutlinePrint for Point {{}}` would not compile");
+    println!();
+}
+
+struct Pair<T> {
+    x: T,
+    y: T,
+}
+
+impl<T> Pair<T> {
+    /// For every Pair<T>
+    fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: fmt::Display + PartialOrd> Pair<T> {
+    /// Only for the Pairs whose T can be compared and printed
+    fn largest(&self) -> String {
+        if self.x >= self.y {
+            format!("The largest member is x = {}", self.x)
+        } else {
+            format!("The largest member is y = {}", self.y)
+        }
+    }
+}
+
+/// Implemented below for every Display type at once
+trait Shout {
+    fn shout(&self) -> String;
+}
+
+impl<T: fmt::Display> Shout for T {
+    fn shout(&self) -> String {
+        self.to_string().to_uppercase()
+    }
+}
+
+fn conditional_impls() {
+    println!("7. Conditional Implementations:");
+    println!("===============================");
+
+    println!("{}", Pair::new(3, 7).largest());
+    println!("{}", Pair::new("pear", "apple").largest());
+    let lists = Pair::new(vec![1], vec![2]);
+    println!("Pair of Vecs made too: {:?} and {:?}", lists.x, lists.y);
+    // lists.largest();  // COMPILE ERROR: Vec<i32> doesn't implement Display
+    explain!("`impl<T: Display + PartialOrd> Pair<T>` adds largest only where T meets the bound");
+
+    println!("{}", "blanket".shout());
+    println!("{}", 42.shout());
+    println!("{}", tweet().shout());
+    explain!("`impl<T: Display> Shout for T` is a blanket impl: every Display type gets shout");
+    detail!("std does the same: ToString is implemented for every T: Display");
+    println!();
+}
+
+trait Greet {
+    fn name(&self) -> String;
+    fn greet(&self) -> String {
+        format!("Hello, {}!", self.name())
+    }
+}
+
+struct English;
+struct Pirate;
+
+impl Greet for English {
+    fn name(&self) -> String {
+        "world".to_string()
+    }
+}
+
+impl Greet for Pirate {
+    fn name(&self) -> String {
+        "matey".to_string()
+    }
+    fn greet(&self) -> String {
+        format!("Ahoy, {}!", self.name())
+    }
+}
+
+fn predict_default() {
+    println!("{} {}", English.greet(), Pirate.greet());
+}
@@ -0,0 +1,137 @@
+/// variables
+use crate::registry::{Question, Section, Snippet, section, snippet};
+
+pub fn variables() {
+    println!("=== Variables Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- variables <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(mutability, "Mutability").with_checkpoint(
+        "Can you assign a new value to a variable declared with plain `let`?",
+        false,
+        "Variables are immutable unless declared with `let mut`.",
+    ),
+    section!(shadowing, "Shadowing").with_checkpoint(
+        "Does `let x = x + 1;` change the original `x`?",
+        false,
+        "It declares a new `x` that shadows the old one; the old value is untouched.",
+    ),
+    section!(shadowing_with_new_type, "Shadowing With a New Type"),
+    section!(reading_into_a_string, "Reading Into a String"),
+    section!(growing_a_string, "Growing a String"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "mutability",
+        prompt: "Which line fails to compile?\n\n    let x = 5;\n    println!(\"{}\", x);\n    x = 6;",
+        choices: &[
+            "x = 6;",
+            "let x = 5;",
+            "println!(\"{}\", x);",
+            "None of them",
+        ],
+        explanation: "x was declared without mut, so it cannot be assigned again; `let mut x = 5;` would fix it.",
+    },
+    Question {
+        section: "shadowing",
+        prompt: "What does this print?\n\n    let x = 5;\n    let x = x + 1;\n    {\n        let x = x * 2;\n    }\n    println!(\"{}\", x);",
+        choices: &["6", "12", "5", "It does not compile"],
+        explanation: "The inner `let x` shadows x only until its block ends; after it, x is the 6 from the second let.",
+    },
+    Question {
+        section: "shadowing_with_new_type",
+        prompt: "Why does `let spaces = \"   \"; let spaces = spaces.len();` compile, when `let mut spaces = \"   \"; spaces = spaces.len();` does not?",
+        choices: &[
+            "Shadowing makes a new variable, which may have a new type",
+            "len() only works on shadowed variables",
+            "A mut variable cannot hold a string",
+            "The second one is missing a semicolon",
+        ],
+        explanation: "mut lets a variable change its value but never its type; a second let is a new variable that happens to reuse the name.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_shadowing)];
+
+fn mutability() {
+    println!("1. Mutability:");
+    println!("==============");
+
+    let mut x = 5;
+    println!("x is {x}");
+    x = 6;
+    println!("x is {x}");
+
+    println!();
+}
+
+fn shadowing() {
+    println!("2. Shadowing:");
+    println!("=============");
+
+    let y = 5;
+    let y = y + 1;
+    {
+        let y = y + 2;
+        println!("y is {y}");
+    }
+    println!("y is {y}");
+
+    println!();
+}
+
+fn shadowing_with_new_type() {
+    println!("3. Shadowing With a New Type:");
+    println!("=============================");
+
+    let spaces = "   ";
+    let spaces = spaces.len();
+    println!("spaces is {spaces}");
+
+    println!();
+}
+
+fn reading_into_a_string() {
+    println!("4. Reading Into a String:");
+    println!("=========================");
+
+    // With real input this would be:
+    //     io::stdin().read_line(&mut guess).expect("Failed to read line");
+    // read_line appends the typed line (newline included) to a mutable String.
+    // user_input.rs reads from the keyboard; here the line is canned
+    // so the lesson can run without waiting for input.
+    let mut guess = String::new();
+    guess.push_str("42\n");
+    println!("guess is {guess}");
+
+    println!();
+}
+
+fn growing_a_string() {
+    println!("5. Growing a String:");
+    println!("====================");
+
+    let mut x = String::from("hello");
+    x.push_str(", world");
+    println!("x is {x}");
+
+    println!();
+}
+
+fn predict_shadowing() {
+    let x = 5;
+    let x = x + 1;
+    {
+        let x = x * 2;
+        println!("inner x = {}", x);
+    }
+    println!("outer x = {}", x);
+}
@@ -0,0 +1,455 @@
+/// Vectors in Rust - Dynamic Arrays
+///
+/// Vectors are growable arrays that can store multiple values of the same type.
+/// They are one of the most commonly used data structures in Rust.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use rust_learn_core::input;
+use rust_learn_core::random::Rng;
+use rust_learn_utils::complexity::Harness;
+use std::collections::HashMap;
+use std::hint::black_box;
+
+pub fn vectors() {
+    println!("=== Vectors Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- vectors <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(create_vectors, "Creating Vectors"),
+    section!(modify_vectors, "Adding and Removing Elements"),
+    section!(access_elements, "Accessing Vector Elements").with_checkpoint(
+        "Does `v.get(10)` panic on a vector of three elements?",
+        false,
+        "`get` returns `None`; it is `v[10]` that panics.",
+    ),
+    section!(iterate_vectors, "Iterating Over Vectors"),
+    section!(vector_methods, "Vector Methods"),
+    section!(vector_with_input, "Vector with User Input"),
+    section!(
+        vector_of_different_types,
+        "Vector of Different Types (using enums)"
+    ),
+    section!(sorting_growth, "Measuring Growth: Sorting"),
+    section!(lookup_growth, "Measuring Growth: Vec vs HashMap Lookups").with_checkpoint(
+        "Does `v.contains(&x)` get slower as v grows, when x is usually not in it?",
+        true,
+        "contains checks the elements one by one, so a miss looks at all of them: O(n). A HashMap lookup does not grow that way.",
+    ),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "access_elements",
+        prompt: "v has 3 elements. What does `v.get(10)` return?",
+        choices: &["None", "It panics", "0", "The last element"],
+        explanation: "get returns an Option for an index that may be out of range; v[10] would panic instead.",
+    },
+    Question {
+        section: "modify_vectors",
+        prompt: "Which line fails to compile?\n\n    let mut v = vec![1, 2, 3];\n    let first = &v[0];\n    v.push(4);\n    println!(\"{}\", first);",
+        choices: &[
+            "v.push(4);",
+            "let first = &v[0];",
+            "println!(\"{}\", first);",
+            "let mut v = vec![1, 2, 3];",
+        ],
+        explanation: "push may move the elements to a bigger allocation, leaving first dangling, so v cannot change while first is in use.",
+    },
+    Question {
+        section: "iterate_vectors",
+        prompt: "How do you add 50 to every element of v in a loop?",
+        choices: &[
+            "for x in &mut v { *x += 50; }",
+            "for x in &v { *x += 50; }",
+            "for x in &mut v { x += 50; }",
+            "for x in v.iter() { x += 50; }",
+        ],
+        explanation: "&mut v hands out a mutable reference to each element, and * reaches the value behind it.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_vector_methods)];
+
+fn create_vectors() {
+    println!("1. Creating Vectors:");
+
+    // Empty vector with type annotation
+    let v1: Vec<i32> = Vec::new();
+    println!("Empty vector: {:?}", v1);
+
+    // Vector with initial values using vec! macro
+    let v2 = vec![1, 2, 3, 4, 5];
+    println!("Vector with values: {:?}", v2);
+
+    // Vector with repeated values
+    let v3 = vec![0; 5]; // Creates [0, 0, 0, 0, 0]
+    println!("Vector with repeated values: {:?}", v3);
+
+    // Vector with capacity
+    let v4: Vec<i32> = Vec::with_capacity(10);
+    println!(
+        "Vector with capacity 10: {:?} (len: {}, capacity: {})",
+        v4,
+        v4.len(),
+        v4.capacity()
+    );
+
+    println!();
+}
+
+fn modify_vectors() {
+    println!("2. Adding and Removing Elements:");
+
+    let mut v = Vec::new();
+
+    // Adding elements
+    v.push(1);
+    v.push(2);
+    v.push(3);
+    println!("After pushing: {:?}", v);
+
+    // Removing elements
+    let last = v.pop();
+    println!("Popped value: {:?}", last);
+    println!("After popping: {:?}", v);
+
+    // Inserting at specific index
+    v.insert(1, 10);
+    println!("After inserting 10 at index 1: {:?}", v);
+
+    // Removing at specific index
+    v.remove(0);
+    println!("After removing at index 0: {:?}", v);
+
+    // Clearing the vector
+    v.clear();
+    println!("After clearing: {:?}", v);
+
+    println!();
+}
+
+fn access_elements() {
+    println!("3. Accessing Vector Elements:");
+
+    let v = vec![10, 20, 30, 40, 50];
+
+    // Using indexing (panics if out of bounds)
+    println!("First element: {}", v[0]);
+    println!("Third element: {}", v[2]);
+
+    // Using get() method (returns Option)
+    match v.get(2) {
+        Some(value) => println!("Element at index 2: {}", value),
+        None => println!("Index 2 is out of bounds"),
+    }
+
+    match v.get(10) {
+        Some(value) => println!("Element at index 10: {}", value),
+        None => println!("Index 10 is out of bounds"),
+    }
+
+    // Using if let for cleaner code
+    if let Some(value) = v.get(1) {
+        println!("Element at index 1: {}", value);
+    }
+
+    // Last element
+    if let Some(last) = v.last() {
+        println!("Last element: {}", last);
+    }
+
+    println!();
+}
+
+fn iterate_vectors() {
+    println!("4. Iterating Over Vectors:");
+
+    let v = vec![1, 2, 3, 4, 5];
+
+    // Iterating with for loop (immutable)
+    print!("Values: ");
+    for value in &v {
+        print!("{} ", value);
+    }
+    println!();
+
+    // Iterating with indices
+    print!("Indices and values: ");
+    for (index, value) in v.iter().enumerate() {
+        print!("[{}:{}] ", index, value);
+    }
+    println!();
+
+    // Iterating with mutable reference
+    let mut v2 = vec![1, 2, 3, 4, 5];
+    println!("Original: {:?}", v2);
+
+    for value in &mut v2 {
+        *value *= 2; // Double each value
+    }
+    println!("After doubling: {:?}", v2);
+
+    // Using iterators
+    let sum: i32 = v2.iter().sum();
+    println!("Sum of all elements: {}", sum);
+
+    let doubled: Vec<i32> = v2.iter().map(|x| x * 2).collect();
+    println!("Doubled again: {:?}", doubled);
+
+    println!();
+}
+
+fn vector_methods() {
+    println!("5. Vector Methods:");
+
+    let mut v = vec![3, 1, 4, 1, 5, 9, 2, 6];
+    println!("Original vector: {:?}", v);
+
+    // Length and capacity
+    println!("Length: {}, Capacity: {}", v.len(), v.capacity());
+
+    // Check if empty
+    println!("Is empty: {}", v.is_empty());
+
+    // Sort
+    v.sort();
+    println!("After sorting: {:?}", v);
+
+    // Reverse
+    v.reverse();
+    println!("After reversing: {:?}", v);
+
+    // Deduplicate
+    v.sort();
+    v.dedup();
+    println!("After deduplication: {:?}", v);
+
+    // Contains
+    println!("Contains 5: {}", v.contains(&5));
+    println!("Contains 7: {}", v.contains(&7));
+
+    // Find
+    if let Some(index) = v.iter().position(|&x| x == 5) {
+        println!("5 found at index: {}", index);
+    }
+
+    // Filter
+    let even_numbers: Vec<i32> = v.iter().filter(|&&x| x % 2 == 0).cloned().collect();
+    println!("Even numbers: {:?}", even_numbers);
+
+    println!();
+}
+
+fn vector_with_input() {
+    println!("6. Vector with User Input:");
+
+    let mut numbers = Vec::new();
+
+    println!("Enter numbers (type 'done' to finish):");
+
+    // Sample input stands in for the user when the run is not interactive
+    for input in input::lesson_lines(&["4", "8", "fifteen", "15", "done"]) {
+        let input = input.as_str();
+
+        if input == "done" {
+            break;
+        }
+
+        match input.parse::<i32>() {
+            Ok(num) => {
+                numbers.push(num);
+                println!("Added: {}", num);
+            }
+            Err(_) => {
+                println!("Invalid number, please try again");
+            }
+        }
+    }
+
+    if !numbers.is_empty() {
+        println!("Your numbers: {:?}", numbers);
+        println!("Sum: {}", numbers.iter().sum::<i32>());
+        println!(
+            "Average: {:.2}",
+            numbers.iter().sum::<i32>() as f64 / numbers.len() as f64
+        );
+    } else {
+        println!("No numbers entered");
+    }
+
+    println!();
+}
+
+#[allow(clippy::vec_init_then_push)] // pushing one by one is the point here
+fn vector_of_different_types() {
+    println!("7. Vector of Different Types (using enums):");
+
+    #[derive(Debug)]
+    enum Value {
+        Integer(i32),
+        Float(f64),
+        Text(String),
+    }
+
+    let mut mixed_data = Vec::new();
+
+    mixed_data.push(Value::Integer(42));
+    mixed_data.push(Value::Float(std::f64::consts::PI));
+    mixed_data.push(Value::Text(String::from("Hello, Rust!")));
+    mixed_data.push(Value::Integer(100));
+    mixed_data.push(Value::Float(std::f64::consts::E));
+
+    println!("Mixed data vector:");
+    for (index, value) in mixed_data.iter().enumerate() {
+        match value {
+            Value::Integer(i) => println!("[{}] Integer: {}", index, i),
+            Value::Float(f) => println!("[{}] Float: {:.2}", index, f),
+            Value::Text(s) => println!("[{}] Text: {}", index, s),
+        }
+    }
+
+    // Filter by type
+    let integers: Vec<i32> = mixed_data
+        .iter()
+        .filter_map(|v| {
+            if let Value::Integer(i) = v {
+                Some(*i)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    println!("Only integers: {:?}", integers);
+
+    println!();
+}
+
+/// `n` numbers in no particular order, the same every run
+fn shuffled(n: usize) -> Vec<u64> {
+    let mut rng = Rng::with_seed(n as u64);
+    (0..n).map(|_| rng.next_u64() % 1_000_000).collect()
+}
+
+/// Sorts by moving each element left past the bigger ones: simple, and
+/// quadratic
+fn insertion_sort(v: &mut [u64]) {
+    for i in 1..v.len() {
+        let mut j = i;
+        while j > 0 && v[j - 1] > v[j] {
+            v.swap(j - 1, j);
+            j -= 1;
+        }
+    }
+}
+
+fn sorting_growth() {
+    println!("8. Measuring Growth: Sorting:");
+
+    // Each size doubles; the fastest of three runs counts
+    let harness = Harness {
+        start: 2_000,
+        factor: 2,
+        steps: 6,
+        repeats: 3,
+    };
+    println!("v.sort() on shuffled numbers:");
+    println!("{}", harness.measure(shuffled, |v| v.sort()));
+    explain!("Doubling n a little more than doubles the time: O(n log n)");
+
+    let small = Harness {
+        start: 250,
+        steps: 5,
+        ..harness
+    };
+    println!("\ninsertion_sort on shuffled numbers:");
+    println!("{}", small.measure(shuffled, |v| insertion_sort(v)));
+    explain!("Doubling n about quadruples the time: O(n^2), fine for 250 items, not for a million");
+    detail!(
+        "rust_learn_utils::complexity::Harness times each size and fits the times to each class"
+    );
+    detail!("The clock is noisy, so run it twice before trusting a close call like n vs n log n");
+    println!();
+}
+
+/// A vector of 0..n, the same numbers in a HashMap, and keys to look up,
+/// half of them missing
+struct Lookups {
+    vector: Vec<u64>,
+    map: HashMap<u64, u64>,
+    keys: Vec<u64>,
+}
+
+/// Keys looked up per measurement, whatever the size
+const LOOKUPS: u64 = 200;
+
+fn lookups(n: usize) -> Lookups {
+    let n = n as u64;
+    Lookups {
+        vector: (0..n).collect(),
+        map: (0..n).map(|key| (key, key * 2)).collect(),
+        keys: (0..LOOKUPS)
+            .map(|i| if i % 2 == 0 { i * 7919 % n } else { n + i })
+            .collect(),
+    }
+}
+
+fn lookup_growth() {
+    println!("9. Measuring Growth: Vec vs HashMap Lookups:");
+
+    let harness = Harness {
+        start: 1_000,
+        factor: 4,
+        steps: 4,
+        repeats: 5,
+    };
+    println!("{} lookups with v.contains(&key):", LOOKUPS);
+    let contains = harness.measure(lookups, |data| {
+        let found = data.keys.iter().filter(|key| data.vector.contains(key));
+        black_box(found.count());
+    });
+    println!("{}", contains);
+    explain!("contains looks at the elements one by one: 4x the elements, about 4x the time");
+
+    println!(
+        "\n{} lookups with v.binary_search(&key) on the sorted vector:",
+        LOOKUPS
+    );
+    let binary = harness.measure(lookups, |data| {
+        let found = data
+            .keys
+            .iter()
+            .filter(|key| data.vector.binary_search(key).is_ok());
+        black_box(found.count());
+    });
+    println!("{}", binary);
+    explain!("binary_search halves the range each step: 4x the elements is only two more steps");
+
+    println!("\n{} lookups with map.contains_key(&key):", LOOKUPS);
+    let hashed = harness.measure(lookups, |data| {
+        let found = data.keys.iter().filter(|key| data.map.contains_key(key));
+        black_box(found.count());
+    });
+    println!("{}", hashed);
+    explain!("A HashMap goes straight to the key's bucket: the time stays flat. That is O(1)");
+    detail!("Flat, not zero: hashing the key costs the same at every size");
+    deep!(
+        "Past the CPU caches (a few MB) every lookup misses the cache, and the flat line steps up once"
+    );
+    println!();
+}
+
+fn predict_vector_methods() {
+    let mut v = vec![3, 1, 3, 2, 1];
+    v.sort();
+    v.dedup();
+    v.retain(|&n| n != 2);
+    println!("{:?}", v);
+    println!("{:?} {:?}", v.first(), v.get(5));
+}
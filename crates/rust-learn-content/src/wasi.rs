@@ -0,0 +1,449 @@
+/// WASI in Rust - One Utility, Sandboxed Under wasmtime or Node
+///
+/// WebAssembly with the WebAssembly System Interface runs outside the browser
+/// with files, arguments and stdout - but only the files it is handed. This
+/// lesson builds the md2html converter for `wasm32-wasip1`, runs it under
+/// wasmtime (or Node's built-in WASI when wasmtime is missing), shows a read
+/// outside its sandbox failing, and contrasts all that with wasm-bindgen.
+use crate::md2html;
+use crate::registry::{Question, Section, section};
+use rust_learn_core::dirs;
+use rust_learn_core::toolchain::{self, CommandOutput};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub fn wasi() {
+    println!("=== WASI Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- wasi <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(webassembly_targets, "WebAssembly Targets"),
+    section!(the_utility, "The Utility: A Markdown Converter"),
+    section!(building_for_wasi, "Building for wasm32-wasip1"),
+    section!(running_the_module, "Running Under wasmtime or Node"),
+    section!(sandboxed_files, "Sandboxed Filesystem Access"),
+    section!(wasm_bindgen_path, "The Browser Path: wasm-bindgen"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "webassembly_targets",
+        prompt: "Which target runs under wasmtime with access to files and arguments?",
+        choices: &[
+            "wasm32-wasip1",
+            "wasm32-unknown-unknown",
+            "x86_64-unknown-linux-gnu",
+            "thumbv7em-none-eabihf",
+        ],
+        explanation: "WASI gives a WebAssembly module a system interface; wasm32-unknown-unknown has none, which suits the browser.",
+    },
+    Question {
+        section: "sandboxed_files",
+        prompt: "A WASI program fails to open a file that exists. What is the likely reason?",
+        choices: &[
+            "Its directory was not preopened, e.g. with wasmtime --dir",
+            "WASI has no files",
+            "Files must be embedded with include_str!",
+            "wasm32 cannot read text",
+        ],
+        explanation: "A WASI module can only reach the directories the runtime hands it.",
+    },
+];
+
+const TARGET: &str = "wasm32-wasip1";
+
+/// The converter's source, copied into the sandbox as the guest's module
+const MD2HTML_SOURCE: &str = include_str!("md2html.rs");
+
+/// The guest's entry point: convert the file named by the first argument
+const GUEST_MAIN: &str = r#"mod md2html;
+
+use std::{env, fs, process};
+
+fn main() {
+    let Some(path) = env::args().nth(1) else {
+        eprintln!("usage: md2html <file.md>");
+        process::exit(2);
+    };
+    match fs::read_to_string(&path) {
+        Ok(markdown) => print!("{}", md2html::to_html(&markdown)),
+        Err(error) => {
+            eprintln!("md2html: cannot read {}: {}", path, error);
+            process::exit(1);
+        }
+    }
+}
+"#;
+
+const GUEST_MANIFEST: &str = r#"[package]
+name = "md2html"
+version = "0.1.0"
+edition = "2024"
+
+[profile.release]
+opt-level = "s"
+"#;
+
+/// Node's `node:wasi` module, with the same single preopened directory
+const NODE_RUNNER: &str = r#"const { readFileSync } = require("node:fs");
+const { WASI } = require("node:wasi");
+
+const [wasmPath, ...args] = process.argv.slice(2);
+const wasi = new WASI({
+  version: "preview1",
+  args: ["md2html", ...args],
+  preopens: { "/docs": "docs" },
+  returnOnExit: true,
+});
+WebAssembly.instantiate(readFileSync(wasmPath), wasi.getImportObject())
+  .then(({ instance }) => process.exit(wasi.start(instance)));
+"#;
+
+const SAMPLE: &str = "# Notes\n\nWASI gives a module **only** the directories it is handed.\n\n- build with `cargo build --target wasm32-wasip1`\n- run with `wasmtime run --dir docs::/docs`\n";
+
+fn webassembly_targets() {
+    println!("1. WebAssembly Targets:");
+    println!("=======================\n");
+
+    explain!("THE THREE YOU WILL MEET:");
+    explain!("========================");
+    explain!("wasm32-unknown-unknown - no OS at all; the browser path, with wasm-bindgen");
+    explain!("wasm32-wasip1          - WASI preview 1: files, args, env, clocks, stdout");
+    explain!("wasm32-wasip2          - WASI 0.2, built on the component model");
+    explain!("(wasm32-wasi was renamed wasm32-wasip1 in Rust 1.78 and removed in 1.84.)");
+
+    println!("\nON THIS MACHINE:");
+    println!("================");
+    match toolchain::installed_targets() {
+        Some(targets) => {
+            for target in &targets {
+                println!("installed: {}", target);
+            }
+            if !targets.iter().any(|target| target == TARGET) {
+                println!("{} is missing: rustup target add {}", TARGET, TARGET);
+            }
+        }
+        None => println!("rustup was not found, so installed targets are unknown"),
+    }
+    for runtime in ["wasmtime", "node"] {
+        match toolchain::version(runtime) {
+            Some(version) => println!("{:<9} {}", runtime, version),
+            None => println!("{:<9} not installed", runtime),
+        }
+    }
+
+    println!();
+}
+
+fn the_utility() {
+    println!("2. The Utility: A Markdown Converter:");
+    println!("=====================================\n");
+
+    explain!("THE GUEST PROGRAM (src/main.rs):");
+    explain!("================================");
+    for line in GUEST_MAIN.lines() {
+        explain!("  {}", line);
+    }
+    explain!("md2html.rs is plain std Rust: nothing in it knows about WebAssembly.");
+
+    println!("\nRUN NATIVELY FIRST:");
+    println!("===================");
+    println!("{}", SAMPLE.trim_end());
+    println!("  ->");
+    println!("{}", md2html::to_html(SAMPLE).trim_end());
+
+    println!();
+}
+
+fn building_for_wasi() {
+    println!("3. Building for wasm32-wasip1:");
+    println!("==============================\n");
+
+    let Some(sandbox) = WasiSandbox::create() else {
+        return;
+    };
+    let Some(module) = sandbox.build() else {
+        return;
+    };
+
+    let size = fs::metadata(&module).map(|meta| meta.len()).unwrap_or(0);
+    println!("built {} ({} KiB)", module.display(), size / 1024);
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- The same source builds natively and for WASI; std's fs and env map to WASI calls");
+    explain!("- The .wasm file is the whole program: it runs on any OS with a WASI runtime");
+    explain!("- opt-level = \"s\" keeps the download small; wasm-opt can shrink it further");
+
+    println!();
+}
+
+fn running_the_module() {
+    println!("4. Running Under wasmtime or Node:");
+    println!("==================================\n");
+
+    let Some(sandbox) = WasiSandbox::create() else {
+        return;
+    };
+    let Some(runtime) = Runtime::detect() else {
+        println!("Neither wasmtime nor node is installed, so the module cannot run here.");
+        println!("Install wasmtime from https://wasmtime.dev and run the lesson again.\n");
+        return;
+    };
+    let Some(module) = sandbox.build() else {
+        return;
+    };
+
+    sandbox.write_docs();
+    println!("{}", runtime.command_line("/docs/notes.md"));
+    let output = sandbox.run(&runtime, &module, "/docs/notes.md");
+    println!("{}", output.stdout.trim_end());
+    report_exit(&output);
+
+    explain!("\nTWO RUNTIMES, ONE MODULE:");
+    explain!("=========================");
+    explain!("wasmtime: --dir docs::/docs maps the host's ./docs to /docs inside the module");
+    explain!("Node:     new WASI({{ preopens: {{ \"/docs\": \"docs\" }} }}) does the same");
+    explain!("Node's WASI is for trusted code; wasmtime is built to contain untrusted code.");
+
+    println!();
+}
+
+fn sandboxed_files() {
+    println!("5. Sandboxed Filesystem Access:");
+    println!("===============================\n");
+
+    explain!("CAPABILITIES, NOT PERMISSIONS:");
+    explain!("==============================");
+    explain!("A native program may open any path the user can. A WASI module starts");
+    explain!("with no filesystem at all; the runtime hands it preopened directories,");
+    explain!("and every path must resolve inside one of them.\n");
+
+    let Some(sandbox) = WasiSandbox::create() else {
+        return;
+    };
+    let Some(runtime) = Runtime::detect() else {
+        println!("Neither wasmtime nor node is installed, so the module cannot run here.\n");
+        return;
+    };
+    let Some(module) = sandbox.build() else {
+        return;
+    };
+    sandbox.write_docs();
+    let secret = sandbox.root.join("secret.txt");
+    println!("On the host, {} exists next to docs/", secret.display());
+
+    for path in ["/docs/notes.md", "/docs/../secret.txt", "/etc/hostname"] {
+        let output = sandbox.run(&runtime, &module, path);
+        let result = if output.success {
+            format!("read ({} bytes of HTML)", output.stdout.len())
+        } else {
+            first_error(&output)
+        };
+        println!("{:<20} -> {}", path, result);
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- `..` cannot climb out of a preopened directory");
+    explain!("- Paths outside every preopen do not exist, as far as the module can tell");
+    explain!("- The host decides per run what a module may touch, without trusting its code");
+
+    println!();
+}
+
+fn wasm_bindgen_path() {
+    println!("6. The Browser Path: wasm-bindgen:");
+    println!("==================================\n");
+
+    explain!("THE SAME CONVERTER FOR A WEB PAGE:");
+    explain!("==================================");
+    explain!("// Cargo.toml: crate-type = [\"cdylib\"], wasm-bindgen = \"0.2\"");
+    explain!("use wasm_bindgen::prelude::*;");
+    explain!("");
+    explain!("#[wasm_bindgen]");
+    explain!("pub fn to_html(markdown: &str) -> String {{");
+    explain!("    md2html::to_html(markdown)");
+    explain!("}}");
+    explain!("");
+    explain!("$ wasm-pack build --target web");
+    explain!("import init, {{ to_html }} from \"./pkg/md2html.js\";");
+    explain!("await init();");
+    explain!("preview.innerHTML = to_html(editor.value);");
+
+    explain!("\nWASI vs wasm-bindgen:");
+    explain!("=====================");
+    explain!("                 WASI (wasm32-wasip1)      wasm-bindgen (wasm32-unknown-unknown)");
+    explain!("runs in          wasmtime, Node, servers   browsers (and Node, through JS glue)");
+    explain!("entry point      fn main, like a CLI       exported functions JavaScript calls");
+    explain!("files            preopened directories     none; the page passes data in");
+    explain!("strings          bytes via args and files  converted by generated JS glue");
+    explain!("std::fs          works, inside the sandbox compiles, but every call fails");
+
+    println!();
+}
+
+/// Which WASI runtime runs the module: wasmtime when installed, else Node
+enum Runtime {
+    Wasmtime,
+    Node,
+}
+
+impl Runtime {
+    fn detect() -> Option<Runtime> {
+        if let Some(version) = toolchain::version("wasmtime") {
+            println!("Using {}", version);
+            Some(Runtime::Wasmtime)
+        } else if let Some(version) = toolchain::version("node") {
+            println!("Using node {} (wasmtime not installed)", version);
+            Some(Runtime::Node)
+        } else {
+            None
+        }
+    }
+
+    fn command_line(&self, path: &str) -> String {
+        match self {
+            Runtime::Wasmtime => format!("$ wasmtime run --dir docs::/docs md2html.wasm {}", path),
+            Runtime::Node => format!("$ node run_wasi.cjs md2html.wasm {}", path),
+        }
+    }
+}
+
+/// A temp dir holding the guest crate, its docs/ preopen and a secret.txt
+/// outside it, removed again when dropped. The build goes to a target dir that
+/// is kept, so later sections and later runs reuse it.
+struct WasiSandbox {
+    root: PathBuf,
+    target_dir: PathBuf,
+}
+
+impl WasiSandbox {
+    /// `None` (after explaining why) without cargo
+    fn create() -> Option<WasiSandbox> {
+        match toolchain::cargo_version() {
+            Some(version) => println!("Using {}", version),
+            None => {
+                println!("cargo was not found, so this section cannot build the module.");
+                println!("Install Rust with rustup and run the lesson again.\n");
+                return None;
+            }
+        }
+
+        let root = std::env::temp_dir().join(format!("rust-learn-wasi-{}", std::process::id()));
+        fs::create_dir_all(root.join("src")).expect("Failed to create sandbox dir");
+        fs::write(root.join("Cargo.toml"), GUEST_MANIFEST).expect("Failed to write manifest");
+        fs::write(root.join("src/main.rs"), GUEST_MAIN).expect("Failed to write main.rs");
+        fs::write(root.join("src/md2html.rs"), MD2HTML_SOURCE).expect("Failed to write md2html.rs");
+
+        let target_dir = dirs::scratch_dir("wasi-target");
+        Some(WasiSandbox { root, target_dir })
+    }
+
+    /// `cargo build --release --target wasm32-wasip1`, returning the module's path
+    fn build(&self) -> Option<PathBuf> {
+        if let Some(targets) = toolchain::installed_targets()
+            && !targets.iter().any(|target| target == TARGET)
+        {
+            println!(
+                "The {} target is not installed, so the module cannot be built.",
+                TARGET
+            );
+            println!("Add it with: rustup target add {}\n", TARGET);
+            return None;
+        }
+
+        let target_dir = self.target_dir.to_str()?;
+        let args = [
+            "build",
+            "--release",
+            "--quiet",
+            "--target",
+            TARGET,
+            "--target-dir",
+            target_dir,
+        ];
+        println!("$ cargo build --release --target {}", TARGET);
+        let output = toolchain::cargo(&self.root, &args).expect("Failed to run cargo");
+        if !output.success {
+            println!("cargo build: FAILED");
+            for line in output
+                .stderr
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .take(6)
+            {
+                println!("  | {}", line);
+            }
+            return None;
+        }
+
+        let module = self
+            .target_dir
+            .join(TARGET)
+            .join("release")
+            .join("md2html.wasm");
+        module.is_file().then_some(module)
+    }
+
+    /// docs/notes.md inside the preopen, secret.txt just outside it
+    fn write_docs(&self) {
+        fs::create_dir_all(self.root.join("docs")).expect("Failed to create docs dir");
+        fs::write(self.root.join("docs/notes.md"), SAMPLE).expect("Failed to write notes.md");
+        fs::write(self.root.join("secret.txt"), "not for the module\n")
+            .expect("Failed to write secret.txt");
+    }
+
+    /// Run the module on `path` (as the module sees it), with ./docs preopened as /docs
+    fn run(&self, runtime: &Runtime, module: &Path, path: &str) -> CommandOutput {
+        let module = module.to_str().expect("temp dir path is valid UTF-8");
+        let output = match runtime {
+            Runtime::Wasmtime => toolchain::run(
+                "wasmtime",
+                &self.root,
+                &["run", "--dir", "docs::/docs", module, path],
+            ),
+            Runtime::Node => {
+                fs::write(self.root.join("run_wasi.cjs"), NODE_RUNNER)
+                    .expect("Failed to write run_wasi.cjs");
+                toolchain::run(
+                    "node",
+                    &self.root,
+                    &["--no-warnings", "run_wasi.cjs", module, path],
+                )
+            }
+        };
+        output.expect("Failed to run the WebAssembly runtime")
+    }
+}
+
+impl Drop for WasiSandbox {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+fn report_exit(output: &CommandOutput) {
+    if output.success {
+        println!("exit status: success");
+    } else {
+        println!("exit status: failure - {}", first_error(output));
+    }
+}
+
+/// The module's own error message, without the runtime's backtrace
+fn first_error(output: &CommandOutput) -> String {
+    output
+        .stderr
+        .lines()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("(no error message)")
+        .trim()
+        .to_string()
+}
@@ -0,0 +1,389 @@
+/// Weak References in Rust - Trees, Cycles and Weak::upgrade
+///
+/// `Rc` frees its value when the last strong reference goes, so two values
+/// that hold `Rc`s to each other keep each other alive forever: a leak,
+/// which safe Rust allows. `Weak` is the other kind of reference: it points
+/// at the value without keeping it alive, and `upgrade` turns it back into
+/// an `Rc` if the value is still there. This lesson leaks a parent and child
+/// on purpose, then builds a tree whose children own strongly and whose
+/// parent links are weak, printing the strong and weak counts at each step
+/// as nodes are added, moved and dropped.
+use crate::registry::{Question, Section, Snippet, section, snippet};
+use std::cell::{Cell, RefCell};
+use std::rc::{Rc, Weak};
+
+pub fn weak_references() {
+    println!("=== Weak References Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- weak_references <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(strong_cycles_leak, "Strong Cycles Leak").with_checkpoint(
+        "Can safe Rust code leak memory?",
+        true,
+        "Leaking is memory-safe, so the compiler allows it: an Rc cycle (or Box::leak, or mem::forget) is never freed.",
+    ),
+    section!(weak_parent_links, "A Tree With Weak Parent Links"),
+    section!(upgrading, "Weak::upgrade: Is It Still There?"),
+    section!(mutating_the_tree, "Counts as the Tree Changes"),
+    section!(when_to_use_weak, "Where Weak Belongs"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "strong_cycles_leak",
+        prompt: "A parent holds Rc<child> and the child holds Rc<parent>. Both variables go out of scope. What is freed?",
+        choices: &[
+            "Nothing: each still has a strong count of 1, from the other",
+            "Both, when the scope ends",
+            "The child only",
+            "It does not compile",
+        ],
+        explanation: "Each node's count only reaches zero when the other is dropped, which never happens.",
+    },
+    Question {
+        section: "weak_parent_links",
+        prompt: "In a tree, which links should be Weak?",
+        choices: &[
+            "Child to parent: parents own their children, not the other way",
+            "Parent to child",
+            "Both",
+            "Neither: Rc both ways is fine",
+        ],
+        explanation: "Ownership runs down the tree; links back up are Weak so they never form a cycle of strong counts.",
+    },
+    Question {
+        section: "upgrading",
+        prompt: "What does Weak::upgrade return once every Rc to the value is dropped?",
+        choices: &[
+            "None",
+            "A dangling Rc",
+            "It panics",
+            "The last value, cloned",
+        ],
+        explanation: "upgrade gives Some(Rc) only while the strong count is above zero; otherwise the value is already dropped.",
+    },
+    Question {
+        section: "mutating_the_tree",
+        prompt: "A node has strong count 0 but weak count 2. What is true?",
+        choices: &[
+            "Its value is dropped, but the allocation stays until the Weaks go",
+            "Its value is still alive",
+            "It is leaked",
+            "That cannot happen",
+        ],
+        explanation: "The counts live next to the value; the value goes at strong 0, the allocation at strong and weak 0.",
+    },
+];
+
+/// Small programs whose output `quiz --predict` asks for
+pub const SNIPPETS: &[Snippet] = &[snippet!(predict_upgrade)];
+
+thread_local! {
+    /// Nodes of either kind made on this thread and not yet dropped
+    static LIVE: Cell<usize> = const { Cell::new(0) };
+}
+
+fn live() -> usize {
+    LIVE.with(Cell::get)
+}
+
+/// A node that links to its parent with a strong `Rc`: the mistake
+struct StrongNode {
+    name: &'static str,
+    parent: RefCell<Option<Rc<StrongNode>>>,
+    children: RefCell<Vec<Rc<StrongNode>>>,
+}
+
+impl StrongNode {
+    fn new(name: &'static str) -> Rc<StrongNode> {
+        LIVE.with(|live| live.set(live.get() + 1));
+        Rc::new(StrongNode {
+            name,
+            parent: RefCell::new(None),
+            children: RefCell::new(Vec::new()),
+        })
+    }
+}
+
+impl Drop for StrongNode {
+    fn drop(&mut self) {
+        LIVE.with(|live| live.set(live.get() - 1));
+        println!("  drop {}", self.name);
+    }
+}
+
+/// A tree node: it owns its children and only points at its parent
+struct Node {
+    name: &'static str,
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+impl Node {
+    fn new(name: &'static str) -> Rc<Node> {
+        LIVE.with(|live| live.set(live.get() + 1));
+        Rc::new(Node {
+            name,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(Vec::new()),
+        })
+    }
+
+    /// Make `child` the last child of `parent`, taking it from its old
+    /// parent if it had one
+    fn adopt(parent: &Rc<Node>, child: Rc<Node>) {
+        child.detach();
+        *child.parent.borrow_mut() = Rc::downgrade(parent);
+        parent.children.borrow_mut().push(child);
+    }
+
+    /// Take this node out of its parent's children; the caller's `Rc` may
+    /// then be the last
+    fn detach(&self) {
+        let Some(parent) = self.parent.borrow().upgrade() else {
+            return;
+        };
+        parent
+            .children
+            .borrow_mut()
+            .retain(|child| !std::ptr::eq(&**child, self));
+        *self.parent.borrow_mut() = Weak::new();
+    }
+
+    /// `root/branch/leaf`, following the parent links up while they last
+    fn path(&self) -> String {
+        match self.parent.borrow().upgrade() {
+            Some(parent) => format!("{}/{}", parent.path(), self.name),
+            None => self.name.to_string(),
+        }
+    }
+
+    fn names(nodes: &[Rc<Node>]) -> Vec<&'static str> {
+        nodes.iter().map(|node| node.name).collect()
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        LIVE.with(|live| live.set(live.get() - 1));
+        println!("  drop {}", self.name);
+    }
+}
+
+/// `name: strong = 1, weak = 0`
+fn counts<T>(name: &str, rc: &Rc<T>) -> String {
+    format!(
+        "{:<7} strong = {}, weak = {}",
+        name,
+        Rc::strong_count(rc),
+        Rc::weak_count(rc)
+    )
+}
+
+fn strong_cycles_leak() {
+    println!("1. Strong Cycles Leak:");
+    println!("======================");
+
+    let before = live();
+    {
+        let parent = StrongNode::new("parent");
+        let child = StrongNode::new("child");
+        parent.children.borrow_mut().push(Rc::clone(&child));
+        *child.parent.borrow_mut() = Some(Rc::clone(&parent));
+        println!("{}", counts("parent", &parent));
+        println!("{}", counts("child", &child));
+        println!("end of scope: dropping `parent` and `child`...");
+    }
+    println!("nodes still alive: {}", live() - before);
+    explain!("Dropping the variables took each count from 2 to 1, not 0: the parent");
+    explain!("  holds the child and the child holds the parent. No drop ran, and the");
+    explain!("  two nodes stay allocated until the program ends");
+    explain!("Leaks are memory-safe, so this compiles; Rc cannot tell a cycle apart");
+    detail!("Rust only promises nothing is freed twice or used after it is freed;");
+    detail!("  Box::leak and mem::forget leak on purpose, Rc cycles by accident");
+    println!();
+}
+
+fn weak_parent_links() {
+    println!("2. A Tree With Weak Parent Links:");
+    println!("=================================");
+
+    let leaf = Node::new("leaf");
+    println!("{}", counts("leaf", &leaf));
+    {
+        let branch = Node::new("branch");
+        Node::adopt(&branch, Rc::clone(&leaf));
+        println!("after adopt(&branch, leaf):");
+        println!("{}", counts("branch", &branch));
+        println!("{}", counts("leaf", &leaf));
+        println!("leaf.path() = {}", leaf.path());
+        println!("end of scope: dropping `branch`...");
+    }
+    println!("{}", counts("leaf", &leaf));
+    println!("leaf.path() = {}", leaf.path());
+    explain!("branch owns leaf (the Rc in children): leaf's strong count is 2");
+    explain!("leaf only points at branch (a Weak): branch's weak count is 1, its");
+    explain!("  strong count stays 1, and dropping `branch` frees it");
+    explain!("Rc::downgrade(&rc) makes a Weak; it adds to the weak count only");
+    drop(leaf);
+    println!();
+}
+
+fn upgrading() {
+    println!("3. Weak::upgrade: Is It Still There?:");
+    println!("=====================================");
+
+    let root = Node::new("root");
+    let child = Node::new("child");
+    Node::adopt(&root, Rc::clone(&child));
+    let parent = child.parent.borrow().upgrade();
+    match &parent {
+        Some(parent) => println!(
+            "child's parent: {} ({})",
+            parent.name,
+            counts("root", parent)
+        ),
+        None => println!("child has no parent"),
+    }
+    explain!("upgrade() returns Some(Rc): while it is held, root's strong count is 2");
+    drop(parent);
+
+    println!("dropping `root`...");
+    drop(root);
+    let parent = child.parent.borrow().upgrade();
+    println!("upgrade() now: {:?}", parent.map(|parent| parent.name));
+    println!("child.path() = {}", child.path());
+    println!(
+        "Weak::<Node>::new().upgrade().is_none() = {}",
+        Weak::<Node>::new().upgrade().is_none()
+    );
+    explain!("root's value is gone, so every Weak to it upgrades to None");
+    explain!("The child lives on: `child` still held it, and dropping root only");
+    explain!("  dropped root's own Rc to it");
+    detail!("Always match on upgrade(): a Weak is a question, not a promise");
+    drop(child);
+    println!();
+}
+
+fn mutating_the_tree() {
+    println!("4. Counts as the Tree Changes:");
+    println!("==============================");
+
+    let root = Node::new("root");
+    let docs = Node::new("docs");
+    let src = Node::new("src");
+    let main = Node::new("main.rs");
+    Node::adopt(&root, Rc::clone(&docs));
+    Node::adopt(&root, Rc::clone(&src));
+    Node::adopt(&docs, Rc::clone(&main));
+    println!("{}", main.path());
+    println!("{}", counts("docs", &docs));
+    println!("{}", counts("main.rs", &main));
+
+    println!("\nmove main.rs from docs to src:");
+    Node::adopt(&src, Rc::clone(&main));
+    println!("{}", main.path());
+    println!("{}", counts("docs", &docs));
+    println!("{}", counts("src", &src));
+    println!("{}", counts("main.rs", &main));
+
+    println!("\nremove docs, then drop our handle to it:");
+    docs.detach();
+    println!(
+        "root's children: {:?}",
+        Node::names(&root.children.borrow())
+    );
+    println!("{}", counts("docs", &docs));
+    drop(docs);
+
+    println!("\ndrop our handles to main.rs and src, then root:");
+    drop((main, src));
+    let before = live();
+    drop(root);
+    println!("nodes freed: {}", before - live());
+    explain!("Moving a node moves one strong count (from docs' children to src's) and");
+    explain!("  one weak count (main.rs's parent link now counts against src)");
+    explain!("Dropping root frees the whole tree: each node's last strong reference is");
+    explain!("  in its parent's children, so the drops run top-down");
+    println!();
+}
+
+fn when_to_use_weak() {
+    println!("5. Where Weak Belongs:");
+    println!("======================");
+
+    let rows = [
+        ("Tree, DOM, scene graph", "children: Rc, parent: Weak"),
+        ("Doubly linked list", "next: Rc, prev: Weak"),
+        ("Observers, event listeners", "subject holds Weak to each"),
+        ("Cache of shared values", "map of Weak; upgrade or reload"),
+        ("Graph with cycles", "an arena: Vec<Node> and indices"),
+    ];
+    println!("{:<28} {}", "SHAPE", "LINKS");
+    for (shape, links) in rows {
+        println!("{:<28} {}", shape, links);
+    }
+    explain!("\nRULES OF THUMB:");
+    explain!("===============");
+    explain!("- Decide who owns whom; owners hold Rc, everything else holds Weak");
+    explain!("- A Weak keeps the allocation (not the value) until it is dropped too");
+    explain!("- Across threads it is Arc and std::sync::Weak, with the same rules");
+    explain!("- Many back-links, or cycles that are the point: use indices into a Vec");
+    println!();
+}
+
+// Helper functions
+
+fn predict_upgrade() {
+    let shared = Rc::new(5);
+    let weak = Rc::downgrade(&shared);
+    println!("{:?}", weak.upgrade());
+    println!("{} {}", Rc::strong_count(&shared), Rc::weak_count(&shared));
+    drop(shared);
+    println!("{:?}", weak.upgrade());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_links_and_drops() {
+        let root = Node::new("root");
+        let a = Node::new("a");
+        let b = Node::new("b");
+        Node::adopt(&root, Rc::clone(&a));
+        Node::adopt(&a, Rc::clone(&b));
+        assert_eq!(b.path(), "root/a/b");
+        assert_eq!((Rc::strong_count(&a), Rc::weak_count(&a)), (2, 1));
+
+        Node::adopt(&root, Rc::clone(&b));
+        assert_eq!(b.path(), "root/b");
+        assert_eq!(Node::names(&root.children.borrow()), ["a", "b"]);
+        assert!(a.children.borrow().is_empty());
+        assert_eq!(Rc::weak_count(&a), 0);
+
+        drop((a, b));
+        let before = live();
+        drop(root);
+        assert_eq!(before - live(), 3);
+    }
+
+    #[test]
+    fn test_strong_cycle_leaks() {
+        let before = live();
+        {
+            let parent = StrongNode::new("parent");
+            let child = StrongNode::new("child");
+            *child.parent.borrow_mut() = Some(Rc::clone(&parent));
+            parent.children.borrow_mut().push(child);
+        }
+        assert_eq!(live() - before, 2);
+    }
+}
@@ -0,0 +1,376 @@
+/// Workspaces in Rust - Splitting a Project Into Crates
+///
+/// This project is itself a cargo workspace, so the lesson uses it as the
+/// example: the manifests are compiled into the binary with `include_str!` and
+/// read back to show the crate graph, path dependencies, the shared lockfile,
+/// and how features are unified across members.
+use crate::registry::{Question, Section, section};
+use rust_learn_core::Explain;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+pub fn workspaces() {
+    println!("=== Workspaces Learning Examples ===\n");
+
+    for section in SECTIONS {
+        (section.run)();
+    }
+}
+
+/// Sections in the order they run; `cargo run -- workspaces <name|number>` runs just one
+pub const SECTIONS: &[Section] = &[
+    section!(workspace_layout, "A Workspace of Six Crates"),
+    section!(
+        path_dependencies,
+        "Path Dependencies and Inherited Settings"
+    ),
+    section!(shared_lockfile, "One Lockfile, One target Directory"),
+    section!(feature_unification, "Feature Unification"),
+    section!(proc_macro_crates, "Why the Derive Macro Has Its Own Crate"),
+];
+
+/// Quiz questions about the lesson; the right answer comes first
+pub const QUESTIONS: &[Question] = &[
+    Question {
+        section: "shared_lockfile",
+        prompt: "Where is Cargo.lock in a workspace?",
+        choices: &[
+            "One at the root, shared by every member",
+            "One in each member crate",
+            "Only in the binary crate",
+            "In target/",
+        ],
+        explanation: "All members resolve their dependencies together, so they agree on every version and share one target directory.",
+    },
+    Question {
+        section: "feature_unification",
+        prompt: "Two members depend on the same crate with different features. What gets built?",
+        choices: &[
+            "One copy with the union of the features",
+            "Two copies, one per set of features",
+            "Only the first member's features",
+            "Nothing; cargo reports a conflict",
+        ],
+        explanation: "Features are additive, and cargo builds each dependency once per build with every feature anyone asked for.",
+    },
+    Question {
+        section: "proc_macro_crates",
+        prompt: "Why does the derive macro live in a crate of its own?",
+        choices: &[
+            "A proc-macro crate can export nothing but procedural macros",
+            "Macros compile faster alone",
+            "A workspace needs six crates",
+            "So it can use std",
+        ],
+        explanation: "A crate with proc-macro = true is built for the compiler to run, so the traits it derives live in another crate.",
+    },
+];
+
+// This workspace's manifests, as they were when the binary was built
+const ROOT_MANIFEST: &str = include_str!("../../../Cargo.toml");
+const MEMBER_MANIFESTS: &[(&str, &str)] = &[
+    (
+        "crates/rust-learn-cli",
+        include_str!("../../rust-learn-cli/Cargo.toml"),
+    ),
+    (
+        "crates/rust-learn-content",
+        include_str!("../../rust-learn-content/Cargo.toml"),
+    ),
+    (
+        "crates/rust-learn-core",
+        include_str!("../../rust-learn-core/Cargo.toml"),
+    ),
+    (
+        "crates/rust-learn-derive",
+        include_str!("../../rust-learn-derive/Cargo.toml"),
+    ),
+    (
+        "crates/rust-learn-ffi",
+        include_str!("../../rust-learn-ffi/Cargo.toml"),
+    ),
+    (
+        "crates/rust-learn-utils",
+        include_str!("../../rust-learn-utils/Cargo.toml"),
+    ),
+];
+
+/// One crate of the workspace, as read from its Cargo.toml
+#[derive(Explain)]
+struct Member {
+    name: String,
+    /// Other workspace members listed under [dependencies]
+    depends_on: Vec<String>,
+}
+
+fn workspace_layout() {
+    println!("1. A Workspace of Six Crates:");
+    println!("==============================\n");
+
+    println!("THE ROOT MANIFEST:");
+    println!("==================");
+    explain!("The root Cargo.toml has [workspace] but no [package]: a virtual manifest.");
+    explain!("It only lists the members and what they share.");
+    for path in workspace_members(ROOT_MANIFEST) {
+        println!("  member: {}", path);
+    }
+
+    explain!("\nWHAT EACH CRATE DOES:");
+    explain!("=====================");
+    explain!("rust-learn-core    - lesson types, input, running cargo, counting allocations");
+    explain!("rust-learn-derive  - #[derive(Explain)], a proc-macro crate");
+    explain!("rust-learn-utils   - #![no_std] helpers: state machine, retry, LRU cache");
+    explain!("rust-learn-ffi     - the LRU cache as a C library, with a cbindgen header");
+    explain!("rust-learn-content - every lesson, and the registry listing them");
+    explain!("rust-learn-cli     - the `rust-learn` binary: argument parsing and the menu");
+
+    println!("\nTHE CRATE GRAPH:");
+    println!("================");
+    for member in members() {
+        if member.depends_on.is_empty() {
+            println!("{:<20} (no workspace dependencies)", member.name);
+        } else {
+            println!("{:<20} -> {}", member.name, member.depends_on.join(", "));
+        }
+    }
+
+    explain!("\nWHY SPLIT AT ALL:");
+    explain!("=================");
+    explain!("- Editing a lesson only recompiles rust-learn-content and the binary");
+    explain!("- Dependencies point one way: core knows nothing about lessons");
+    explain!("- Each crate has a small public API, checked by the compiler");
+
+    println!();
+}
+
+fn path_dependencies() {
+    println!("2. Path Dependencies and Inherited Settings:");
+    println!("============================================\n");
+
+    println!("DECLARED ONCE, AT THE ROOT:");
+    println!("===========================");
+    for line in section_lines(ROOT_MANIFEST, "[workspace.dependencies]") {
+        println!("  {}", line);
+    }
+
+    println!("\nINHERITED BY MEMBERS:");
+    println!("=====================");
+    let (path, manifest) = MEMBER_MANIFESTS[0];
+    println!("{}/Cargo.toml:", path);
+    for line in manifest.lines().filter(|line| line.contains(".workspace")) {
+        println!("  {}", line);
+    }
+
+    explain!("\nKEY POINTS:");
+    explain!("===========");
+    explain!("- `path = \"...\"` builds the crate from disk instead of crates.io");
+    explain!("- `rust-learn-core.workspace = true` means \"whatever the root says\"");
+    explain!("- `version.workspace = true` keeps every crate on the same version");
+    explain!("- To publish, a path dependency also needs a `version`, which is used");
+    explain!("  on crates.io while `path` is used locally");
+
+    println!();
+}
+
+fn shared_lockfile() {
+    println!("3. One Lockfile, One target Directory:");
+    println!("======================================\n");
+
+    println!("ONE Cargo.lock FOR EVERY MEMBER:");
+    println!("================================");
+    explain!("Members have no Cargo.lock of their own; the workspace root has one.");
+    explain!("Every crate resolves to the same version of each dependency.");
+
+    let lockfile = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../Cargo.lock");
+    match fs::read_to_string(&lockfile) {
+        Ok(text) => {
+            let packages = locked_packages(&text);
+            let local: Vec<&str> = packages
+                .iter()
+                .filter(|(_, from_registry)| !from_registry)
+                .map(|(name, _)| *name)
+                .collect();
+            println!("\nThis workspace's Cargo.lock:");
+            println!("  {} packages in total", packages.len());
+            println!("  {} from a registry", packages.len() - local.len());
+            println!("  {} local: {}", local.len(), local.join(", "));
+        }
+        Err(_) => {
+            println!("\n(No Cargo.lock next to the workspace - run `cargo build` to create it)");
+        }
+    }
+
+    explain!("\nONE target/ DIRECTORY:");
+    explain!("======================");
+    explain!("All members build into the root target/, so tokio is compiled once");
+    explain!("even though only rust-learn-content uses it directly.");
+
+    explain!("\nUSEFUL COMMANDS:");
+    explain!("================");
+    explain!("cargo build                      # default-members: the rust-learn binary");
+    explain!("cargo test --workspace           # every member");
+    explain!("cargo test -p rust-learn-core    # one member");
+
+    println!();
+}
+
+fn feature_unification() {
+    println!("4. Feature Unification:");
+    println!("=======================\n");
+
+    println!("ONE COPY OF EACH CRATE, WITH THE UNION OF FEATURES:");
+    println!("===================================================");
+    explain!("If two members depend on the same crate with different features,");
+    explain!("cargo builds it once with every requested feature enabled.");
+
+    // rust-learn-content asks for tokio "full"; imagine the CLI also used tokio
+    let requests: &[(&str, &[&str])] = &[
+        ("rust-learn-content", &["full"]),
+        ("rust-learn-cli (hypothetical)", &["rt", "macros"]),
+    ];
+    for (member, features) in requests {
+        println!("  {:<30} tokio features {:?}", member, features);
+    }
+    println!(
+        "  {:<30} tokio features {:?}",
+        "=> built once with",
+        unify(requests)
+    );
+
+    explain!("\nWHY FEATURES MUST BE ADDITIVE:");
+    explain!("==============================");
+    explain!("A feature enabled by one member is enabled for all of them.");
+    explain!("So a feature may add APIs, but must never remove or change them.");
+
+    explain!("\nWHAT RESOLVER 2 AND 3 DO NOT UNIFY:");
+    explain!("===================================");
+    explain!("- Features of build-dependencies and proc-macros vs. normal dependencies");
+    explain!("- Features of dev-dependencies, unless building tests or examples");
+    explain!("- Features of dependencies for other target platforms");
+    explain!("This workspace uses resolver = \"3\" (the edition 2024 default).");
+
+    explain!("\nSEE IT FOR YOURSELF:");
+    explain!("====================");
+    explain!("cargo tree -e features -i tokio");
+
+    println!();
+}
+
+fn proc_macro_crates() {
+    println!("5. Why the Derive Macro Has Its Own Crate:");
+    println!("==========================================\n");
+
+    explain!("PROC MACROS RUN INSIDE THE COMPILER:");
+    explain!("====================================");
+    explain!("A proc macro is compiled for the machine running cargo, then loaded");
+    explain!("by rustc while it compiles other crates. That needs a crate with");
+    explain!("[lib] proc-macro = true, and such a crate can export nothing but macros.");
+
+    explain!("\nTHE USUAL SPLIT:");
+    explain!("================");
+    explain!("rust-learn-derive  defines  #[derive(Explain)]");
+    explain!("rust-learn-core    defines  trait Explain, and re-exports the derive");
+    explain!("rust-learn-content uses     #[derive(Explain)] on its own types");
+
+    println!("\nTHE DERIVE IN ACTION:");
+    println!("=====================");
+    explain!("The `Member` type used in section 1 derives Explain:");
+    println!("{}", Member::definition());
+
+    println!();
+}
+
+// Helper functions
+
+fn members() -> Vec<Member> {
+    let names: Vec<String> = MEMBER_MANIFESTS
+        .iter()
+        .map(|(_, manifest)| package_name(manifest))
+        .collect();
+
+    MEMBER_MANIFESTS
+        .iter()
+        .zip(&names)
+        .map(|((_, manifest), name)| Member {
+            name: name.clone(),
+            depends_on: section_lines(manifest, "[dependencies]")
+                .into_iter()
+                .filter_map(|line| line.split(['.', ' ', '=']).next())
+                .filter(|dep| names.iter().any(|name| name == dep))
+                .map(str::to_string)
+                .collect(),
+        })
+        .collect()
+}
+
+/// The quoted paths in the root manifest's `members = [...]` list
+fn workspace_members(manifest: &str) -> Vec<&str> {
+    let Some(start) = manifest.find("members = [") else {
+        return Vec::new();
+    };
+    let list = &manifest[start..];
+    let list = &list[..list.find(']').unwrap_or(list.len())];
+    list.split('"').skip(1).step_by(2).collect()
+}
+
+/// `name = "..."` from a manifest's [package] table
+fn package_name(manifest: &str) -> String {
+    section_lines(manifest, "[package]")
+        .into_iter()
+        .find_map(|line| line.strip_prefix("name = "))
+        .map(|name| name.trim_matches('"').to_string())
+        .unwrap_or_default()
+}
+
+/// The non-empty lines of one `[table]` of a manifest
+fn section_lines<'a>(manifest: &'a str, header: &str) -> Vec<&'a str> {
+    manifest
+        .lines()
+        .skip_while(|line| line.trim() != header)
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .collect()
+}
+
+/// Every `[[package]]` in a Cargo.lock, and whether it came from a registry
+fn locked_packages(lockfile: &str) -> Vec<(&str, bool)> {
+    lockfile
+        .split("[[package]]")
+        .skip(1)
+        .filter_map(|package| {
+            let name = package
+                .lines()
+                .find_map(|line| line.strip_prefix("name = "))?
+                .trim_matches('"');
+            Some((name, package.contains("source = ")))
+        })
+        .collect()
+}
+
+fn unify<'a>(requests: &[(&str, &[&'a str])]) -> BTreeSet<&'a str> {
+    requests
+        .iter()
+        .flat_map(|(_, features)| features.iter().copied())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifests_match_the_workspace() {
+        let listed: Vec<&str> = workspace_members(ROOT_MANIFEST);
+        let included: Vec<&str> = MEMBER_MANIFESTS.iter().map(|(path, _)| *path).collect();
+        assert_eq!(listed, included);
+
+        let members = members();
+        let cli = members
+            .iter()
+            .find(|member| member.name == "rust-learn-cli")
+            .unwrap();
+        assert_eq!(cli.depends_on, ["rust-learn-content", "rust-learn-core"]);
+    }
+}
@@ -0,0 +1,24 @@
+/// Compile-error demos for `rust-learn show-errors`
+///
+/// Each file in compile_errors/ must fail to compile with the error on its
+/// `// expect:` line, and belong to a lesson that exists.
+use rust_learn_content::registry;
+
+#[test]
+fn demos_fail_with_the_expected_error() {
+    let scratch = std::env::temp_dir().join(format!(
+        "rust-learn-compile-errors-test-{}",
+        std::process::id()
+    ));
+    for demo in registry::COMPILE_ERRORS {
+        assert!(
+            registry::find(demo.lesson).is_some(),
+            "{}: no lesson is called {}",
+            demo.name,
+            demo.lesson
+        );
+        let result = demo.check(&scratch).expect("rustc should run");
+        assert_eq!(result, Ok(()), "{}/{}", demo.lesson, demo.name);
+    }
+    let _ = std::fs::remove_dir_all(&scratch);
+}
@@ -0,0 +1,57 @@
+//! Build script for rust-learn-core
+//!
+//! Writes `$OUT_DIR/skeletons_generated.rs`: every file in `skeletons/`, the
+//! shipped exercises as they are before anyone works on them, embedded with
+//! `include_str!` so `exercise reset` has them whatever happens to the
+//! learner's copies in `exercises/`. `src/exercises.rs` includes it.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let skeletons = manifest_dir.join("skeletons");
+
+    println!("cargo:rerun-if-changed=skeletons");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let mut files = Vec::new();
+    for lesson in read_dir(&skeletons) {
+        if lesson.is_dir() {
+            files.extend(
+                read_dir(&lesson)
+                    .into_iter()
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "rs")),
+            );
+        }
+    }
+    files.sort();
+
+    let mut code = String::new();
+    code.push_str("// @generated by build.rs - do not edit\n\n");
+    code.push_str("const SKELETONS: &[(&str, &str)] = &[\n");
+    for path in &files {
+        let name = path.strip_prefix(&skeletons).unwrap();
+        let name: Vec<_> = name.iter().map(|part| part.to_string_lossy()).collect();
+        writeln!(
+            code,
+            "    ({:?}, include_str!({:?})),",
+            name.join("/"),
+            path.display().to_string()
+        )
+        .unwrap();
+    }
+    code.push_str("];\n");
+
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    fs::write(out_dir.join("skeletons_generated.rs"), code).unwrap();
+}
+
+/// The entries of `dir`, none when it cannot be read
+fn read_dir(dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(dir)
+        .map(|entries| entries.map(|entry| entry.unwrap().path()).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,18 @@
+// borrowing1.rs
+//
+// `first` still borrows `names` when `push` needs to borrow it mutably.
+// Reorder the lines (without removing any) so the borrows no longer overlap.
+//
+// The borrowing lesson covers this: rust-learn run borrowing
+//
+// Delete the `// I AM NOT DONE` line when it compiles and runs.
+
+// I AM NOT DONE
+
+fn main() {
+    let mut names = vec![String::from("Ferris")];
+    let first = &names[0];
+    names.push(String::from("Corro"));
+    println!("the first name is {}", first);
+    println!("{} names", names.len());
+}
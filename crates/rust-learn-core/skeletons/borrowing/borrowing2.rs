@@ -0,0 +1,32 @@
+// borrowing2.rs
+//
+// `shout` should change the string it is given, without taking ownership of
+// it. Fix the signature and the call in the test.
+//
+// The borrowing lesson covers this: rust-learn run borrowing
+//
+// Delete the `// I AM NOT DONE` line when the test passes.
+
+// I AM NOT DONE
+
+fn shout(text: &String) {
+    text.push('!');
+}
+
+fn main() {
+    let mut text = String::from("hello");
+    shout(&text);
+    println!("{}", text);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_an_exclamation_mark() {
+        let mut text = String::from("borrow");
+        shout(&text);
+        assert_eq!(text, "borrow!");
+    }
+}
@@ -0,0 +1,29 @@
+// match1.rs
+//
+// A match has to cover every value. Handle the missing variant so this
+// compiles, without adding a `_` arm.
+//
+// The match lesson covers this: rust-learn run match
+//
+// Delete the `// I AM NOT DONE` line when it compiles and runs.
+
+// I AM NOT DONE
+
+enum Light {
+    Red,
+    Yellow,
+    Green,
+}
+
+fn action(light: Light) -> &'static str {
+    match light {
+        Light::Red => "stop",
+        Light::Green => "go",
+    }
+}
+
+fn main() {
+    for light in [Light::Red, Light::Yellow, Light::Green] {
+        println!("{}", action(light));
+    }
+}
@@ -0,0 +1,17 @@
+// ownership1.rs
+//
+// `greeting` is moved into `copy`, so it cannot be printed afterwards. Make
+// this compile without changing the two println! lines.
+//
+// The ownership lesson covers this: rust-learn run ownership
+//
+// Delete the `// I AM NOT DONE` line when it compiles and runs.
+
+// I AM NOT DONE
+
+fn main() {
+    let greeting = String::from("hello");
+    let copy = greeting;
+    println!("{} from greeting", greeting);
+    println!("{} from copy", copy);
+}
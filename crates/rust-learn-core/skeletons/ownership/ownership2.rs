@@ -0,0 +1,32 @@
+// ownership2.rs
+//
+// `fill_vec` takes ownership of the vector it is given. Make it add 88 to
+// the end and give the vector back, so the test passes.
+//
+// The ownership lesson covers this: rust-learn run ownership
+//
+// Delete the `// I AM NOT DONE` line when the test passes.
+
+// I AM NOT DONE
+
+fn fill_vec(vec: Vec<i32>) -> Vec<i32> {
+    vec.push(88);
+    vec
+}
+
+fn main() {
+    let numbers = fill_vec(vec![22, 44, 66]);
+    println!("{:?}", numbers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_the_vector() {
+        let original = vec![22, 44, 66];
+        let filled = fill_vec(original);
+        assert_eq!(filled, [22, 44, 66, 88]);
+    }
+}
@@ -0,0 +1,17 @@
+// variables1.rs
+//
+// Variables cannot change unless they are declared to. Make this compile
+// without removing either assignment.
+//
+// The variables lesson covers this: rust-learn run variables
+//
+// Delete the `// I AM NOT DONE` line when it compiles and runs.
+
+// I AM NOT DONE
+
+fn main() {
+    let count = 5;
+    println!("count starts at {}", count);
+    count = count + 1;
+    println!("count is now {}", count);
+}
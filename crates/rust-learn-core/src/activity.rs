@@ -0,0 +1,258 @@
+//! Activity Log
+//!
+//! `activity.log` in the data directory (or wherever `[store] path` keeps
+//! progress, see `store::dir`) keeps what the learner ran, for how long,
+//! what they were shown and how they answered, one event per line, oldest
+//! first:
+//!
+//! ```text
+//! 2026-10-16T14:03:27Z shown ownership::references_and_borrowing analogy
+//! 2026-10-16T14:03:27Z run ownership::references_and_borrowing 0.004
+//! 2026-10-16T14:09:40Z answer ownership::references_and_borrowing analogy correct
+//! 2026-10-16T14:09:40Z answer ownership::slices - wrong
+//! 2026-10-16T14:09:41Z quiz ownership 4/5 62.5
+//! 2026-10-16T14:12:05Z checkpoint borrowing::mutable_borrowing correct
+//! 2026-10-16T14:31:50Z interview arrays::two_sum solved 612.4 1
+//! ```
+//!
+//! Lines are only ever added, so the log of every learner in a class can be
+//! collected and read together (`rust-learn stats variants a.log b.log`). A
+//! line that does not parse is skipped.
+
+use crate::interview::Outcome;
+use crate::progress::{format_timestamp, parse_timestamp};
+use crate::store;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+const FILE_NAME: &str = "activity.log";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A lesson or `lesson::section` ran for `seconds`, to the end or not
+    Run { key: String, seconds: f64 },
+    /// A quiz about `about` (a lesson, or `all`) took `seconds`
+    Quiz {
+        about: String,
+        correct: usize,
+        asked: usize,
+        seconds: f64,
+    },
+    /// Variant `variant` of section `key` (`lesson::section`) was shown
+    Shown { key: String, variant: String },
+    /// A quiz question about section `key`, after `variant` was shown when
+    /// the section has variants (`-` in the log when it has none)
+    Answer {
+        key: String,
+        variant: Option<String>,
+        correct: bool,
+    },
+    /// The checkpoint question after section `key` (see `checkpoint`)
+    Checkpoint { key: String, correct: bool },
+    /// An interview problem (`topic::name`) was solved, late or not, or
+    /// skipped after `seconds`, with `hints` taken
+    Interview {
+        key: String,
+        outcome: Outcome,
+        seconds: f64,
+        hints: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub time: SystemTime,
+    pub event: Event,
+}
+
+impl Entry {
+    fn parse(line: &str) -> Option<Entry> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let time = parse_timestamp(words.first()?)?;
+        let event = match words[1..] {
+            ["run", key, seconds] => Event::Run {
+                key: key.to_string(),
+                seconds: seconds.parse().ok()?,
+            },
+            ["quiz", about, score, seconds] => {
+                let (correct, asked) = score.split_once('/')?;
+                Event::Quiz {
+                    about: about.to_string(),
+                    correct: correct.parse().ok()?,
+                    asked: asked.parse().ok()?,
+                    seconds: seconds.parse().ok()?,
+                }
+            }
+            ["shown", key, variant] => Event::Shown {
+                key: key.to_string(),
+                variant: variant.to_string(),
+            },
+            ["answer", key, variant, result @ ("correct" | "wrong")] => Event::Answer {
+                key: key.to_string(),
+                variant: Some(variant)
+                    .filter(|&variant| variant != "-")
+                    .map(String::from),
+                correct: result == "correct",
+            },
+            ["checkpoint", key, result @ ("correct" | "wrong")] => Event::Checkpoint {
+                key: key.to_string(),
+                correct: result == "correct",
+            },
+            ["interview", key, outcome, seconds, hints] => Event::Interview {
+                key: key.to_string(),
+                outcome: Outcome::parse(outcome)?,
+                seconds: seconds.parse().ok()?,
+                hints: hints.parse().ok()?,
+            },
+            _ => return None,
+        };
+        Some(Entry { time, event })
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ", format_timestamp(self.time))?;
+        match &self.event {
+            Event::Run { key, seconds } => write!(f, "run {} {:.3}", key, seconds),
+            Event::Quiz {
+                about,
+                correct,
+                asked,
+                seconds,
+            } => write!(f, "quiz {} {}/{} {:.1}", about, correct, asked, seconds),
+            Event::Shown { key, variant } => write!(f, "shown {} {}", key, variant),
+            Event::Answer {
+                key,
+                variant,
+                correct,
+            } => write!(
+                f,
+                "answer {} {} {}",
+                key,
+                variant.as_deref().unwrap_or("-"),
+                if *correct { "correct" } else { "wrong" }
+            ),
+            Event::Checkpoint { key, correct } => write!(
+                f,
+                "checkpoint {} {}",
+                key,
+                if *correct { "correct" } else { "wrong" }
+            ),
+            Event::Interview {
+                key,
+                outcome,
+                seconds,
+                hints,
+            } => write!(
+                f,
+                "interview {} {} {:.1} {}",
+                key,
+                outcome.name(),
+                seconds,
+                hints
+            ),
+        }
+    }
+}
+
+/// activity.log next to the progress store (`store::dir`)
+pub fn path() -> Option<PathBuf> {
+    Some(store::dir()?.join(FILE_NAME))
+}
+
+/// Add `events` to the learner's log, as happening now
+pub fn append(events: Vec<Event>) -> io::Result<()> {
+    if events.is_empty() {
+        return Ok(());
+    }
+    let path = path().ok_or_else(|| io::Error::other("no data directory"))?;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let time = SystemTime::now();
+    let mut text = String::new();
+    for event in events {
+        text.push_str(&format!("{}\n", Entry { time, event }));
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())
+}
+
+/// The learner's log; empty when there is none yet
+pub fn load() -> Vec<Entry> {
+    path()
+        .and_then(|path| load_from(&path).ok())
+        .unwrap_or_default()
+}
+
+/// The log at `path`, oldest first
+pub fn load_from(path: &Path) -> io::Result<Vec<Entry>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    Ok(text.lines().filter_map(Entry::parse).collect())
+}
+
+/// The variant of section `key` shown most recently in `entries`
+pub fn last_shown<'a>(entries: &'a [Entry], key: &str) -> Option<&'a str> {
+    entries.iter().rev().find_map(|entry| match &entry.event {
+        Event::Shown {
+            key: shown,
+            variant,
+        } if shown == key => Some(variant.as_str()),
+        _ => None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries() {
+        let text = "\
+2026-10-16T14:03:27Z shown ownership::slices analogy
+not a line
+2026-10-16T14:04:00Z shown ownership::slices plain
+2026-10-16T14:09:40Z answer ownership::slices plain wrong
+2026-10-16T14:09:41Z quiz ownership 4/5 62.5
+2026-10-16T14:09:42Z answer ownership::moves - correct
+2026-10-16T14:12:05Z checkpoint ownership::moves wrong
+2026-10-16T14:31:50Z interview arrays::two_sum late 1502.0 2
+";
+        let entries: Vec<Entry> = text.lines().filter_map(Entry::parse).collect();
+        assert_eq!(entries.len(), 7);
+        assert_eq!(last_shown(&entries, "ownership::slices"), Some("plain"));
+        assert_eq!(last_shown(&entries, "ownership::moves"), None);
+        assert_eq!(
+            entries[2].to_string(),
+            "2026-10-16T14:09:40Z answer ownership::slices plain wrong"
+        );
+        assert_eq!(
+            entries[3].to_string(),
+            "2026-10-16T14:09:41Z quiz ownership 4/5 62.5"
+        );
+        assert!(matches!(
+            &entries[4].event,
+            Event::Answer { variant: None, .. }
+        ));
+        assert_eq!(
+            entries[4].to_string(),
+            "2026-10-16T14:09:42Z answer ownership::moves - correct"
+        );
+        assert_eq!(
+            entries[5].to_string(),
+            "2026-10-16T14:12:05Z checkpoint ownership::moves wrong"
+        );
+        assert_eq!(
+            entries[6].to_string(),
+            "2026-10-16T14:31:50Z interview arrays::two_sum late 1502.0 2"
+        );
+    }
+}
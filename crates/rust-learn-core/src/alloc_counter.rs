@@ -0,0 +1,136 @@
+//! Counting Allocator
+//!
+//! `CountingAlloc` wraps another allocator and counts every call that goes
+//! through it. The rust-learn binary installs it as the `#[global_allocator]`
+//! (around the system allocator, or mimalloc with `--features mimalloc`), so
+//! lessons can show how many allocations a piece of code makes, and how much
+//! heap it had in use at most.
+
+use std::alloc::{GlobalAlloc, Layout};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static DEALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static REALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES_ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+/// Bytes allocated and not yet freed, and the most there have been
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+static INNER_NAME: OnceLock<&'static str> = OnceLock::new();
+
+pub struct CountingAlloc<A> {
+    inner: A,
+    name: &'static str,
+}
+
+impl<A> CountingAlloc<A> {
+    /// `const` because a `#[global_allocator]` must be a `static`
+    pub const fn new(inner: A, name: &'static str) -> Self {
+        CountingAlloc { inner, name }
+    }
+
+    fn grow(size: usize) {
+        let live = LIVE_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(live, Ordering::Relaxed);
+    }
+
+    fn record_name(&self) {
+        // Neither `get` nor `set` allocates, so both are safe inside `alloc`
+        if INNER_NAME.get().is_none() {
+            let _ = INNER_NAME.set(self.name);
+        }
+    }
+}
+
+// SAFETY: every call is forwarded unchanged to `inner`, which upholds the
+// GlobalAlloc contract; the counters are atomics and never allocate.
+unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.record_name();
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        Self::grow(layout.size());
+        unsafe { self.inner.alloc(layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        self.record_name();
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        Self::grow(layout.size());
+        unsafe { self.inner.alloc_zeroed(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        DEALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        LIVE_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        unsafe { self.inner.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        REALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES_ALLOCATED.fetch_add(new_size.saturating_sub(layout.size()), Ordering::Relaxed);
+        if new_size >= layout.size() {
+            Self::grow(new_size - layout.size());
+        } else {
+            LIVE_BYTES.fetch_sub(layout.size() - new_size, Ordering::Relaxed);
+        }
+        unsafe { self.inner.realloc(ptr, layout, new_size) }
+    }
+}
+
+/// Totals since the program started
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: usize,
+    pub deallocations: usize,
+    pub reallocations: usize,
+    /// Bytes requested, counting growth by `realloc`; never decreases
+    pub bytes: usize,
+}
+
+impl AllocStats {
+    /// What happened between an earlier snapshot and this one
+    pub fn since(self, earlier: AllocStats) -> AllocStats {
+        AllocStats {
+            allocations: self.allocations - earlier.allocations,
+            deallocations: self.deallocations - earlier.deallocations,
+            reallocations: self.reallocations - earlier.reallocations,
+            bytes: self.bytes - earlier.bytes,
+        }
+    }
+}
+
+pub fn snapshot() -> AllocStats {
+    AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        deallocations: DEALLOCATIONS.load(Ordering::Relaxed),
+        reallocations: REALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES_ALLOCATED.load(Ordering::Relaxed),
+    }
+}
+
+/// The allocator `CountingAlloc` forwards to, or `None` when it is not the
+/// global allocator (unit tests, or another binary using these crates)
+pub fn inner_name() -> Option<&'static str> {
+    INNER_NAME.get().copied()
+}
+
+/// Run `f` and report the allocations it made (all threads are counted)
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+    let before = snapshot();
+    let result = f();
+    (result, snapshot().since(before))
+}
+
+/// Run `f` and report the most heap it had in use at once, above what was
+/// in use when it started (all threads are counted). Calls may nest: the
+/// peak an outer call sees includes the inner ones'
+pub fn peak<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let start = LIVE_BYTES.load(Ordering::Relaxed);
+    let outer = PEAK_BYTES.swap(start, Ordering::Relaxed);
+    let result = f();
+    let peak = PEAK_BYTES.fetch_max(outer, Ordering::Relaxed);
+    (result, peak.saturating_sub(start))
+}
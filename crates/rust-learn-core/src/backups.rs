@@ -0,0 +1,276 @@
+//! Exercise Backups
+//!
+//! `rust-learn exercise reset ownership1` puts an exercise back the way it
+//! was shipped (`Exercise::skeleton`), and `exercise restore` brings an
+//! earlier attempt back. Neither loses work: what is in the file is first
+//! copied to `backups/<source>/<name>/<stamp>.rs` in the data directory,
+//! the source being the pack or `builtin` and the stamp the UTC time, like
+//! `20261016T203629Z`. The copy is left out only when the file is the
+//! skeleton itself or the newest backup already holds it. Backups stay
+//! until the learner deletes them.
+//!
+//! `reset` and `restore` only plan, as a `Transaction`, so `--dry-run`
+//! shows the copy and the overwrite before either happens.
+
+use crate::dirs;
+use crate::exercises::Exercise;
+use crate::packs;
+use crate::progress::format_timestamp;
+use crate::transaction::Transaction;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// One saved attempt
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    /// When it was taken, like `20261016T203629Z`; a second one in the same
+    /// second gets `-2`
+    pub stamp: String,
+    pub path: PathBuf,
+}
+
+/// `backups` in the data directory
+pub fn root() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("backups"))
+}
+
+fn dir_in(root: &Path, exercise: &Exercise) -> PathBuf {
+    let source = exercise.pack.as_deref().unwrap_or(packs::BUILTIN);
+    root.join(source).join(&exercise.name)
+}
+
+/// The backups of `exercise` under `root`, oldest first
+pub fn list_in(root: &Path, exercise: &Exercise) -> Vec<Backup> {
+    let Ok(entries) = fs::read_dir(dir_in(root, exercise)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "rs"))
+        .filter_map(|path| {
+            let stamp = path.file_stem()?.to_string_lossy().into_owned();
+            Some(Backup { stamp, path })
+        })
+        .collect();
+    backups.sort_by(|a, b| a.stamp.cmp(&b.stamp));
+    backups
+}
+
+/// `time` as a stamp: `format_timestamp` without its `-` and `:`, so it
+/// can be a file name everywhere
+pub fn stamp(time: SystemTime) -> String {
+    format_timestamp(time).replace(['-', ':'], "")
+}
+
+/// The backup `from` names, by its stamp or the start of it, written either
+/// way (`20261016T2036` or `2026-10-16T20:36`); the newest without `from`.
+/// A whole stamp is that backup even when a `-2` one starts with it
+pub fn choose<'a>(backups: &'a [Backup], from: Option<&str>) -> Result<&'a Backup, String> {
+    let Some(from) = from else {
+        return backups
+            .last()
+            .ok_or_else(|| "there are no backups".to_string());
+    };
+    let prefix = from.replace(['-', ':'], "").to_ascii_uppercase();
+    if let Some(exact) = backups.iter().find(|backup| backup.stamp == prefix) {
+        return Ok(exact);
+    }
+    let matching: Vec<&Backup> = backups
+        .iter()
+        .filter(|backup| !prefix.is_empty() && backup.stamp.starts_with(&prefix))
+        .collect();
+    match matching[..] {
+        [] => Err(format!("no backup was taken at {}", from)),
+        [backup] => Ok(backup),
+        _ => Err(format!(
+            "{} is the start of {} backups' times; give more of it",
+            from,
+            matching.len()
+        )),
+    }
+}
+
+/// Plan putting `exercise` back as shipped, after backing up what is there
+pub fn reset(root: &Path, exercise: &Exercise, now: SystemTime) -> Result<Transaction, String> {
+    let skeleton = exercise
+        .skeleton()
+        .map_err(|error| format!("cannot read the shipped {}: {}", exercise.id(), error))?
+        .ok_or_else(|| {
+            format!(
+                "{} was not shipped with rust-learn or fetched with a pack, so there is no version to go back to",
+                exercise.path.display()
+            )
+        })?;
+    let current = fs::read_to_string(&exercise.path).ok();
+    let mut transaction = Transaction::new();
+    if current.as_deref() == Some(skeleton.as_str()) {
+        return Ok(transaction);
+    }
+    if let Some(current) = &current {
+        plan_backup(root, exercise, current, now, &mut transaction);
+    }
+    transaction.write(&exercise.path, skeleton);
+    Ok(transaction)
+}
+
+/// Plan putting the backup `from` (`choose`) back in place of `exercise`,
+/// after backing up what is there, unless it is the skeleton
+pub fn restore(
+    root: &Path,
+    exercise: &Exercise,
+    from: Option<&str>,
+    now: SystemTime,
+) -> Result<Transaction, String> {
+    let backups = list_in(root, exercise);
+    if backups.is_empty() {
+        return Err(format!(
+            "{} has no backups; `exercise reset` takes one",
+            exercise.id()
+        ));
+    }
+    let chosen = choose(&backups, from)?;
+    let contents = fs::read_to_string(&chosen.path)
+        .map_err(|error| format!("cannot read {}: {}", chosen.path.display(), error))?;
+    let current = fs::read_to_string(&exercise.path).ok();
+    let mut transaction = Transaction::new();
+    if current.as_deref() == Some(contents.as_str()) {
+        return Ok(transaction);
+    }
+    let skeleton = exercise.skeleton().ok().flatten();
+    if let Some(current) = current.filter(|current| Some(current) != skeleton.as_ref()) {
+        plan_backup(root, exercise, &current, now, &mut transaction);
+    }
+    transaction.write(&exercise.path, contents);
+    Ok(transaction)
+}
+
+/// Plan copying `current` to a new backup of `exercise`, unless the newest
+/// backup already holds it
+fn plan_backup(
+    root: &Path,
+    exercise: &Exercise,
+    current: &str,
+    now: SystemTime,
+    transaction: &mut Transaction,
+) {
+    let backups = list_in(root, exercise);
+    if backups
+        .last()
+        .is_some_and(|newest| fs::read_to_string(&newest.path).is_ok_and(|text| text == current))
+    {
+        return;
+    }
+    let dir = dir_in(root, exercise);
+    let base = stamp(now);
+    let mut path = dir.join(format!("{}.rs", base));
+    let mut count = 1;
+    while path.exists() {
+        count += 1;
+        path = dir.join(format!("{}-{}.rs", base, count));
+    }
+    transaction.write(path, current);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use std::time::Duration;
+
+    #[test]
+    fn test_reset_and_restore() {
+        let dir = std::env::temp_dir().join(format!("rust-learn-backups-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        let root = dir.join("backups");
+        let exercises = dir.join("exercises");
+        fs::create_dir_all(exercises.join("ownership")).unwrap();
+        let exercise = Exercise {
+            name: "ownership1".to_string(),
+            lesson: "ownership".to_string(),
+            path: exercises.join("ownership/ownership1.rs"),
+            pack: None,
+        };
+        let skeleton = exercise.skeleton().unwrap().unwrap();
+        assert!(skeleton.contains(crate::exercises::NOT_DONE));
+        // The learner's work committed on top of the shipped file, as
+        // `assignment submit` does, is still not what reset goes back to
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&exercises)
+                .output();
+            assert!(
+                output.is_ok_and(|output| output.status.success()),
+                "git {:?}",
+                args
+            );
+        };
+        let commit = |text: &str| {
+            fs::write(&exercise.path, text).unwrap();
+            git(&["add", "--all"]);
+            git(&[
+                "-c",
+                "user.name=t",
+                "-c",
+                "user.email=t@t",
+                "commit",
+                "-qm",
+                "work",
+            ]);
+        };
+        git(&["init", "-q"]);
+        commit(&skeleton);
+        commit(&format!("{}// an hour of work\n", skeleton));
+        let at = |seconds: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(seconds);
+
+        let plan = reset(&root, &exercise, at(60)).unwrap();
+        assert_eq!(plan.changes().len(), 2);
+        assert!(fs::read_to_string(&exercise.path).unwrap().contains("hour"));
+        plan.apply().unwrap();
+        assert_eq!(fs::read_to_string(&exercise.path).unwrap(), skeleton);
+        assert!(reset(&root, &exercise, at(61)).unwrap().is_empty());
+
+        let backups = list_in(&root, &exercise);
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].stamp, "19700101T000100Z");
+        assert!(backups[0].path.starts_with(root.join("builtin/ownership1")));
+
+        // The skeleton is not backed up again on the way back
+        let plan = restore(&root, &exercise, Some("1970-01-01T00:01"), at(62)).unwrap();
+        assert_eq!(plan.changes().len(), 1);
+        plan.apply().unwrap();
+        assert!(fs::read_to_string(&exercise.path).unwrap().contains("hour"));
+
+        commit("fn main() { /* more work */ }\n");
+        reset(&root, &exercise, at(60)).unwrap().apply().unwrap();
+        assert_eq!(fs::read_to_string(&exercise.path).unwrap(), skeleton);
+        let stamps: Vec<String> = list_in(&root, &exercise)
+            .into_iter()
+            .map(|backup| backup.stamp)
+            .collect();
+        assert_eq!(stamps, ["19700101T000100Z", "19700101T000100Z-2"]);
+
+        // An exercise rust-learn did not ship has nothing to go back to
+        let own = Exercise {
+            name: "mine1".to_string(),
+            ..exercise.clone()
+        };
+        assert!(
+            reset(&root, &own, at(63))
+                .unwrap_err()
+                .contains("not shipped")
+        );
+
+        let backups = list_in(&root, &exercise);
+        assert_eq!(choose(&backups, None).unwrap(), &backups[1]);
+        assert!(choose(&backups, Some("19700101")).is_err());
+        assert_eq!(
+            choose(&backups, Some("19700101T000100Z")).unwrap(),
+            &backups[0]
+        );
+        assert!(choose(&backups, Some("2026")).is_err());
+        assert!(choose(&[], None).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
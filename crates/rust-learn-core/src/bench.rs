@@ -0,0 +1,52 @@
+//! A Fixed Workload for Comparing Builds
+//!
+//! `rust-learn dev bench` times this. The release profiles lesson runs it in
+//! the dev, release and release-lto builds of rust-learn and compares them, so
+//! the work has to be identical every time (a fixed seed) and heavy on the
+//! kind of code optimization helps most: tight loops, bounds checks and
+//! small function calls.
+
+use crate::fuzzy;
+use crate::random::Rng;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Words to compare with each other, every pair once
+const WORDS: usize = 600;
+
+pub struct BenchResult {
+    /// Edit distances computed
+    pub comparisons: usize,
+    /// Sum of every distance, the same in every build
+    pub checksum: u64,
+    pub elapsed: Duration,
+}
+
+/// Compute the edit distance between every pair of a fixed list of words
+pub fn run() -> BenchResult {
+    let mut rng = Rng::with_seed(2024);
+    let words: Vec<String> = (0..WORDS)
+        .map(|_| {
+            let len = 4 + rng.below(8);
+            (0..len)
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect()
+        })
+        .collect();
+
+    let start = Instant::now();
+    let mut checksum = 0u64;
+    let mut comparisons = 0;
+    for (index, a) in words.iter().enumerate() {
+        for b in &words[index + 1..] {
+            checksum += fuzzy::edit_distance(black_box(a), black_box(b)) as u64;
+            comparisons += 1;
+        }
+    }
+
+    BenchResult {
+        comparisons,
+        checksum,
+        elapsed: start.elapsed(),
+    }
+}
@@ -0,0 +1,139 @@
+//! Recalibrated Estimates
+//!
+//! Every lesson comes with a difficulty and an estimate of its minutes
+//! (`Lesson::estimated_minutes`). `rust-learn stats recalibrate` compares them
+//! with how long this learner really took and how they did on the quiz, and
+//! saves what it suggests in calibration.toml, next to the progress store
+//! (`store::dir`; the data directory unless `[store] path` says otherwise):
+//!
+//! ```toml
+//! [[lesson]]
+//! name = "ownership"
+//! minutes = 41
+//! difficulty = "advanced"
+//! ```
+//!
+//! `list` shows these in place of the lesson's own, and a lesson missing from
+//! the file keeps its own. Running `recalibrate` again replaces the file.
+
+use crate::lesson::{Difficulty, Lesson};
+use crate::settings::Settings;
+use crate::store;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const FILE_NAME: &str = "calibration.toml";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    pub minutes: usize,
+    pub difficulty: Difficulty,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Calibration {
+    lessons: BTreeMap<String, Estimate>,
+}
+
+impl Calibration {
+    /// The saved calibration; empty without a file, or with one that does
+    /// not parse
+    pub fn load() -> Calibration {
+        let text = path().and_then(|path| fs::read_to_string(path).ok());
+        text.map(|text| Calibration::parse(&text))
+            .unwrap_or_default()
+    }
+
+    /// `[[lesson]]` entries missing a key, or with a value out of range, are
+    /// skipped
+    pub fn parse(text: &str) -> Calibration {
+        let mut calibration = Calibration::default();
+        let Ok(settings) = Settings::parse(text) else {
+            return calibration;
+        };
+        for table in settings.tables("lesson") {
+            let estimate = (|| {
+                let name = table.str("name")?;
+                let minutes = usize::try_from(table.integer("minutes")?).ok()?;
+                let difficulty = table.str("difficulty")?.parse().ok()?;
+                Some((
+                    name.to_string(),
+                    Estimate {
+                        minutes,
+                        difficulty,
+                    },
+                ))
+            })();
+            if let Some((name, estimate)) = estimate {
+                calibration.lessons.insert(name, estimate);
+            }
+        }
+        calibration
+    }
+
+    pub fn set(&mut self, lesson: &str, estimate: Estimate) {
+        self.lessons.insert(lesson.to_string(), estimate);
+    }
+
+    pub fn get(&self, lesson: &str) -> Option<Estimate> {
+        self.lessons.get(lesson).copied()
+    }
+
+    /// The calibrated minutes, or else the lesson's own estimate
+    pub fn minutes(&self, lesson: &Lesson) -> usize {
+        self.get(lesson.name)
+            .map_or_else(|| lesson.estimated_minutes(), |estimate| estimate.minutes)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut text = String::from("# Written by `rust-learn stats recalibrate`\n");
+        for (name, estimate) in &self.lessons {
+            text.push_str(&format!(
+                "\n[[lesson]]\nname = \"{}\"\nminutes = {}\ndifficulty = \"{}\"\n",
+                name,
+                estimate.minutes,
+                estimate.difficulty.name()
+            ));
+        }
+        text
+    }
+
+    /// Replace calibration.toml, returning where it is
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = path().ok_or_else(|| io::Error::other("no data directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, self.to_toml())?;
+        Ok(path)
+    }
+}
+
+/// calibration.toml next to the progress store (`store::dir`)
+pub fn path() -> Option<PathBuf> {
+    Some(store::dir()?.join(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut calibration = Calibration::default();
+        calibration.set(
+            "ownership",
+            Estimate {
+                minutes: 41,
+                difficulty: Difficulty::Advanced,
+            },
+        );
+        let text = calibration.to_toml();
+        assert_eq!(Calibration::parse(&text), calibration);
+
+        let skipped = "[[lesson]]\nname = \"loops\"\nminutes = -3\ndifficulty = \"easy\"\n";
+        assert_eq!(Calibration::parse(skipped), Calibration::default());
+    }
+}
@@ -0,0 +1,152 @@
+//! Checkpoints
+//!
+//! A section can end with one quick yes/no question
+//! (`Section::with_checkpoint`), so reading a lesson means answering as you
+//! go. With `--checkpoints`, or `checkpoints = true` under `[lessons]` in
+//! config.toml, each one is asked once its section has printed everything:
+//! when the next section's heading comes, or when the lesson ends. Only an
+//! interactive run asks. The CLI writes the answers to the activity log.
+
+use crate::events;
+use crate::input;
+use crate::json::Value;
+use crate::lesson::{Lesson, Section};
+use crate::output::{self, Glyph};
+use crate::settings;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A yes/no question about a section
+pub struct Checkpoint {
+    /// Asked as it is, with `[y/n]` after it
+    pub prompt: &'static str,
+    /// Whether the right answer is yes
+    pub answer: bool,
+    /// Why, shown after any answer
+    pub explanation: &'static str,
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The section that printed last, and its checkpoint, still to be asked
+static PENDING: Mutex<Option<(String, &'static Checkpoint)>> = Mutex::new(None);
+
+/// `lesson::section` and whether it was answered right, since the last
+/// `take_answered`
+static ANSWERED: Mutex<Vec<(String, bool)>> = Mutex::new(Vec::new());
+
+/// Ask checkpoints from now on if `requested` (`--checkpoints`) or config.toml
+/// asks for them, and there is someone to answer
+pub fn init(requested: bool) {
+    let configured = settings::get()
+        .table("lessons")
+        .and_then(|table| table.bool("checkpoints"))
+        .unwrap_or(false);
+    let enabled = (requested || configured) && input::interactive();
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// `section` of `lesson` is starting: ask the checkpoint of the one before
+/// it, and keep this one's for when it is done
+pub fn section_started(lesson: &Lesson, section: &'static Section) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    ask_pending();
+    if let Some(checkpoint) = &section.checkpoint {
+        let key = format!("{}::{}", lesson.name, section.name);
+        *lock(&PENDING) = Some((key, checkpoint));
+    }
+}
+
+/// The lesson (or the section run on its own) is done: ask the last
+/// section's checkpoint
+pub fn lesson_finished() {
+    if ENABLED.load(Ordering::Relaxed) {
+        ask_pending();
+    }
+}
+
+/// Every checkpoint answered since the last call, in order
+pub fn take_answered() -> Vec<(String, bool)> {
+    std::mem::take(&mut *lock(&ANSWERED))
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn ask_pending() {
+    let Some((key, checkpoint)) = lock(&PENDING).take() else {
+        return;
+    };
+    if let Some(correct) = ask(&key, checkpoint) {
+        lock(&ANSWERED).push((key, correct));
+    }
+}
+
+/// Ask until the answer is yes or no; `None` when it is skipped (Enter or
+/// `s`) or input ends
+fn ask(key: &str, checkpoint: &Checkpoint) -> Option<bool> {
+    if events::enabled() {
+        events::emit(
+            "checkpoint",
+            vec![("key", key.into()), ("prompt", checkpoint.prompt.into())],
+        );
+    } else {
+        let label = output::status(Glyph::Hint, "Checkpoint:", output::bold, 0);
+        println!("\n{} {}", label, checkpoint.prompt);
+    }
+    let said = loop {
+        match input::read_key("[y/n, Enter skips] ")?.to_ascii_lowercase() {
+            '\n' | 's' => return None,
+            key => match parse_key(key) {
+                Some(said) => break said,
+                None if events::enabled() => {}
+                None => println!("{}", output::dim("Press y or n")),
+            },
+        }
+    };
+    let correct = said == checkpoint.answer;
+    if events::enabled() {
+        events::emit(
+            "checkpoint_answer",
+            vec![
+                ("key", key.into()),
+                ("correct", Value::Bool(correct)),
+                ("explanation", checkpoint.explanation.into()),
+            ],
+        );
+        return Some(correct);
+    }
+    let status = if correct {
+        output::status(Glyph::Ok, "Right.", output::green, 0)
+    } else {
+        output::status(Glyph::Fail, "Not quite.", output::red, 0)
+    };
+    println!("{} {}\n", status, checkpoint.explanation);
+    Some(correct)
+}
+
+/// `y` or `n`
+fn parse_key(key: char) -> Option<bool> {
+    match key {
+        'y' => Some(true),
+        'n' => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key() {
+        assert_eq!(parse_key('y'), Some(true));
+        assert_eq!(parse_key('n'), Some(false));
+        assert_eq!(parse_key('x'), None);
+    }
+}
@@ -0,0 +1,259 @@
+//! Classroom Mode
+//!
+//! Once a student id is set, with `--student s042`, `RUST_LEARN_STUDENT` or
+//! `student` under `[classroom]` in config.toml (in that order), every quiz
+//! and every exercise `verify` checks adds a row to a results file for the
+//! teacher to collect:
+//!
+//! ```text
+//! time,student,kind,name,attempt,score,total,outcome,seconds
+//! 2026-10-16T14:09:41Z,s042,quiz,ownership,1,4,5,,62.5
+//! 2026-10-16T14:20:03Z,s042,exercise,ownership1,2,1,1,passed,0.812
+//! ```
+//!
+//! The file is `RUST_LEARN_CLASS_REPORT`, else `report` under `[classroom]`,
+//! else `classroom.csv` in the data directory; several students can share
+//! one on a class drive. A file ending in `.json` gets one JSON object a line
+//! instead, with the same keys. `kind` is `quiz`, `predict` or `exercise`;
+//! an exercise's `outcome` is `passed`, `not_done` or `failed`. `attempt`
+//! counts the student's rows for the same thing, the new one included. An
+//! exercise only counts again once its file has changed since its last row,
+//! so running `verify` twice is not two attempts.
+
+use crate::json::Value;
+use crate::progress::{format_timestamp, parse_timestamp};
+use crate::{dirs, output, settings};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+const STUDENT_VAR: &str = "RUST_LEARN_STUDENT";
+const REPORT_VAR: &str = "RUST_LEARN_CLASS_REPORT";
+const FILE_NAME: &str = "classroom.csv";
+
+/// The first row of a new CSV file, and the keys of a JSON row
+const COLUMNS: [&str; 9] = [
+    "time", "student", "kind", "name", "attempt", "score", "total", "outcome", "seconds",
+];
+
+static STUDENT: OnceLock<Option<String>> = OnceLock::new();
+
+/// One quiz taken or exercise checked
+pub struct Record {
+    /// `quiz`, `predict` or `exercise`
+    pub kind: &'static str,
+    /// The lesson a quiz was about (or `all`), or the exercise
+    pub name: String,
+    pub score: usize,
+    pub total: usize,
+    /// How an exercise went: `passed`, `not_done` or `failed`
+    pub outcome: Option<&'static str>,
+    pub elapsed: Duration,
+    /// When what was attempted last changed: a row is only added when that
+    /// is after the last one for it
+    pub changed: Option<SystemTime>,
+}
+
+/// Record results for `flag` (`--student`), `RUST_LEARN_STUDENT` or the
+/// configured student, if any. An id is letters, digits, `.`, `-`, `_` and `@`
+pub fn init(flag: Option<String>) -> Result<(), String> {
+    let student = flag
+        .or_else(|| std::env::var(STUDENT_VAR).ok())
+        .or_else(|| {
+            settings::get()
+                .table("classroom")
+                .and_then(|table| table.str("student"))
+                .map(String::from)
+        });
+    if let Some(id) = &student
+        && !is_valid_id(id)
+    {
+        return Err(format!(
+            "'{}' cannot be a student id: use letters, digits, '.', '-', '_' and '@'",
+            id
+        ));
+    }
+    let _ = STUDENT.set(student.filter(|id| !id.is_empty()));
+    Ok(())
+}
+
+fn is_valid_id(id: &str) -> bool {
+    id.chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_' | '@'))
+}
+
+/// The student results are recorded for, when classroom mode is on
+pub fn student() -> Option<&'static str> {
+    STUDENT.get()?.as_deref()
+}
+
+/// Where results go
+pub fn report_path() -> Option<PathBuf> {
+    std::env::var_os(REPORT_VAR)
+        .map(PathBuf::from)
+        .or_else(|| {
+            settings::get()
+                .table("classroom")
+                .and_then(|table| table.str("report"))
+                .map(PathBuf::from)
+        })
+        .or_else(|| Some(dirs::data_dir()?.join(FILE_NAME)))
+}
+
+/// Add `record` to the results file, in classroom mode. A file that cannot
+/// be written is reported on stderr; the quiz or check itself still counts
+pub fn record(record: Record) {
+    let (Some(student), Some(path)) = (student(), report_path()) else {
+        return;
+    };
+    if let Err(error) = append(&path, student, &record, SystemTime::now()) {
+        eprintln!(
+            "{}",
+            output::red(&format!(
+                "Cannot add the result to {}: {}",
+                path.display(),
+                error
+            ))
+        );
+    }
+}
+
+fn append(path: &Path, student: &str, record: &Record, time: SystemTime) -> io::Result<()> {
+    let json = path
+        .extension()
+        .is_some_and(|extension| extension == "json");
+    let existing = fs::read_to_string(path).unwrap_or_default();
+    let earlier: Vec<SystemTime> = existing
+        .lines()
+        .filter_map(|line| parse_row(line, json))
+        .filter(|row| row.student == student && row.kind == record.kind && row.name == record.name)
+        .map(|row| row.time)
+        .collect();
+    // Rows keep whole seconds, so the change is compared at that precision
+    let changed = record
+        .changed
+        .and_then(|changed| parse_timestamp(&format_timestamp(changed)));
+    if let (Some(changed), Some(last)) = (changed, earlier.iter().max())
+        && changed <= *last
+    {
+        return Ok(());
+    }
+
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut text = String::new();
+    if existing.is_empty() && !json {
+        text.push_str(&COLUMNS.join(","));
+        text.push('\n');
+    }
+    let seconds = (record.elapsed.as_secs_f64() * 1000.0).round() / 1000.0;
+    let values = [
+        Value::from(format_timestamp(time).as_str()),
+        Value::from(student),
+        Value::from(record.kind),
+        Value::from(record.name.as_str()),
+        Value::Number((earlier.len() + 1) as f64),
+        Value::Number(record.score as f64),
+        Value::Number(record.total as f64),
+        record.outcome.map_or(Value::Null, Value::from),
+        Value::Number(seconds),
+    ];
+    if json {
+        let entries = COLUMNS
+            .iter()
+            .map(|column| column.to_string())
+            .zip(values)
+            .collect();
+        text.push_str(&format!("{}\n", Value::Object(entries)));
+    } else {
+        let cells: Vec<String> = values
+            .iter()
+            .map(|value| match value {
+                Value::String(text) => text.clone(),
+                Value::Null => String::new(),
+                value => value.to_string(),
+            })
+            .collect();
+        text.push_str(&format!("{}\n", cells.join(",")));
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())
+}
+
+/// What counting attempts needs from a row
+struct Row {
+    time: SystemTime,
+    student: String,
+    kind: String,
+    name: String,
+}
+
+fn parse_row(line: &str, json: bool) -> Option<Row> {
+    if json {
+        let value = Value::parse(line).ok()?;
+        let text = |key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+        Some(Row {
+            time: parse_timestamp(&text("time")?)?,
+            student: text("student")?,
+            kind: text("kind")?,
+            name: text("name")?,
+        })
+    } else {
+        // Every field is an id or a number, so none has a comma in it
+        let mut cells = line.split(',');
+        Some(Row {
+            time: parse_timestamp(cells.next()?)?,
+            student: cells.next()?.to_string(),
+            kind: cells.next()?.to_string(),
+            name: cells.next()?.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_append_counts_attempts() {
+        let path =
+            std::env::temp_dir().join(format!("rust-learn-class-{}.csv", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let start = SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let exercise = |changed: u64| Record {
+            kind: "exercise",
+            name: "ownership1".to_string(),
+            score: 0,
+            total: 1,
+            outcome: Some("failed"),
+            elapsed: Duration::from_millis(812),
+            changed: Some(start + Duration::from_secs(changed)),
+        };
+        append(&path, "s042", &exercise(0), start + Duration::from_secs(10)).unwrap();
+        // Not changed since: not another attempt
+        append(&path, "s042", &exercise(5), start + Duration::from_secs(20)).unwrap();
+        append(
+            &path,
+            "s042",
+            &exercise(15),
+            start + Duration::from_secs(30),
+        )
+        .unwrap();
+        append(&path, "s7", &exercise(15), start + Duration::from_secs(30)).unwrap();
+        let text = fs::read_to_string(&path).unwrap();
+        let rows: Vec<&str> = text.lines().collect();
+        assert_eq!(
+            rows,
+            [
+                "time,student,kind,name,attempt,score,total,outcome,seconds",
+                "2023-11-14T22:13:30Z,s042,exercise,ownership1,1,0,1,failed,0.812",
+                "2023-11-14T22:13:50Z,s042,exercise,ownership1,2,0,1,failed,0.812",
+                "2023-11-14T22:13:50Z,s7,exercise,ownership1,1,0,1,failed,0.812",
+            ]
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
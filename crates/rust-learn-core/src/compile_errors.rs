@@ -0,0 +1,165 @@
+//! Compile Errors
+//!
+//! Lessons often leave a line commented out because it "would be a COMPILE
+//! ERROR". A compile-error demo is that line in a whole program, kept as a
+//! `.rs` file that must not compile, so `rust-learn show-errors ownership`
+//! can show what rustc really says. The first comment line says what the
+//! demo does wrong, and an `// expect:` line gives the error rustc has to
+//! report, checked the way trybuild checks a `.stderr` file, but on that one
+//! line, so a new rustc rewording its notes does not break it:
+//!
+//! ```text
+//! // Using a String after `let s2 = s1;` moved it
+//! // expect: error[E0382]: borrow of moved value: `s1`
+//! ```
+
+use crate::toolchain;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// A program that must fail to compile with a known error
+pub struct CompileError {
+    /// The lesson it belongs to
+    pub lesson: &'static str,
+    /// The file name without `.rs`, like `use_after_move`
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+/// What rustc made of a demo
+pub struct Diagnostics {
+    /// Whether it compiled after all, which a demo must not
+    pub compiled: bool,
+    /// What rustc printed
+    pub stderr: String,
+}
+
+impl CompileError {
+    /// What the demo does wrong: its leading comment, up to `// expect:`
+    pub fn title(&self) -> String {
+        let lines: Vec<&str> = self
+            .source
+            .lines()
+            .map_while(|line| line.strip_prefix("//"))
+            .map(str::trim)
+            .take_while(|line| !line.starts_with("expect:"))
+            .collect();
+        lines.join(" ")
+    }
+
+    /// The error rustc has to report, from the `// expect:` line
+    pub fn expected(&self) -> Option<&'static str> {
+        self.source
+            .lines()
+            .find_map(|line| line.strip_prefix("// expect:"))
+            .map(str::trim)
+    }
+
+    /// The program without its header comment: what is shown and compiled,
+    /// so rustc's line numbers match what the learner reads
+    pub fn code(&self) -> &'static str {
+        let start = self
+            .source
+            .lines()
+            .take_while(|line| line.starts_with("//") || line.trim().is_empty())
+            .map(|line| line.len() + 1)
+            .sum::<usize>()
+            .min(self.source.len());
+        &self.source[start..]
+    }
+
+    /// Type-check the demo as `<name>.rs` in `scratch` (nothing is linked),
+    /// with colored diagnostics when `color` is set
+    pub fn compile(&self, scratch: &Path, color: bool) -> io::Result<Diagnostics> {
+        fs::create_dir_all(scratch)?;
+        let file = format!("{}.rs", self.name);
+        fs::write(scratch.join(&file), self.code())?;
+        let color = if color { "always" } else { "never" };
+        let args = [
+            "--edition",
+            "2024",
+            "--crate-type",
+            "bin",
+            "--emit=metadata",
+            "--color",
+            color,
+            &file,
+        ];
+        // In `scratch`, so the diagnostics name the file and nothing else
+        let output = toolchain::rustc(scratch, &args)?;
+        Ok(Diagnostics {
+            compiled: output.success,
+            stderr: output.stderr,
+        })
+    }
+
+    /// Compile the demo and say what is wrong with the result: it compiled,
+    /// or it failed with some other error than the expected one
+    pub fn check(&self, scratch: &Path) -> io::Result<Result<(), String>> {
+        let Some(expected) = self.expected() else {
+            return Ok(Err("there is no `// expect:` line".to_string()));
+        };
+        let diagnostics = self.compile(scratch, false)?;
+        Ok(if diagnostics.compiled {
+            Err("it compiles".to_string())
+        } else if diagnostics.stderr.lines().any(|line| line == expected) {
+            Ok(())
+        } else {
+            let found = diagnostics
+                .stderr
+                .lines()
+                .find(|line| line.starts_with("error"))
+                .unwrap_or("no error");
+            Err(format!("expected `{}`, rustc said `{}`", expected, found))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolchain::{CommandOutput, Fake};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_header_and_check() {
+        let demo = CompileError {
+            lesson: "ownership",
+            name: "use_after_move",
+            source: "// Using a String after it moved\n\
+                     // to s2\n\
+                     // expect: error[E0382]: borrow of moved value: `s1`\n\
+                     \n\
+                     fn main() {}\n",
+        };
+        assert_eq!(demo.title(), "Using a String after it moved to s2");
+        assert_eq!(
+            demo.expected(),
+            Some("error[E0382]: borrow of moved value: `s1`")
+        );
+        assert_eq!(demo.code(), "fn main() {}\n");
+
+        let scratch =
+            std::env::temp_dir().join(format!("rust-learn-compile-errors-{}", std::process::id()));
+        let rustc = Arc::new(Fake::new(|_| CommandOutput {
+            success: false,
+            stderr: "error[E0382]: borrow of moved value: `s2`\n".to_string(),
+            ..CommandOutput::default()
+        }));
+        let result = toolchain::with_runner(rustc.clone(), || demo.check(&scratch)).unwrap();
+        assert_eq!(
+            result,
+            Err("expected `error[E0382]: borrow of moved value: `s1``, \
+                 rustc said `error[E0382]: borrow of moved value: `s2``"
+                .to_string())
+        );
+        assert_eq!(rustc.invocations()[0].dir, scratch);
+        assert!(
+            rustc.invocations()[0]
+                .args
+                .contains(&"use_after_move.rs".to_string())
+        );
+        fs::remove_dir_all(&scratch).unwrap();
+    }
+}
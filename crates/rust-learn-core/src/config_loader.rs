@@ -0,0 +1,195 @@
+/// Config Loader - A Tiny Library With an Evolving API
+///
+/// This module is the "library" used by the API design lesson (rust-learn-content).
+/// Its public API has been through three releases, and every change was made
+/// so that existing callers kept compiling:
+/// - 0.1: `load(path: &str)`
+/// - 0.2: `load(path: impl AsRef<Path>)` and the `Loader` builder for options
+/// - 0.3: `LoadOptions`/`LoadError` became `#[non_exhaustive]`, old helpers are `#[deprecated]` shims
+///
+/// The breaking variants of these changes live in tests/compile_fail as fixtures.
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A parsed `key = value` configuration file.
+///
+/// The fields are public and the struct is exhaustive, so adding a field is a
+/// breaking change (callers building it with a struct literal stop compiling).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub name: String,
+    pub entries: Vec<(String, String)>,
+}
+
+impl Config {
+    /// Look up the value for `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Options controlling how a config file is loaded.
+///
+/// `#[non_exhaustive]` means other crates cannot build this with a struct
+/// literal, so new options can be added in a minor release.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct LoadOptions {
+    /// Reject lines that are not `key = value` instead of skipping them.
+    pub strict: bool,
+    /// Refuse files larger than this many bytes.
+    pub max_size: u64,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            strict: false,
+            max_size: 64 * 1024,
+        }
+    }
+}
+
+/// Everything that can go wrong while loading a config file.
+///
+/// `#[non_exhaustive]` forces callers to keep a `_` arm, so new variants are
+/// not a breaking change.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LoadError {
+    Io(io::Error),
+    TooLarge { size: u64, max: u64 },
+    Syntax { line: usize, text: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(e) => write!(f, "could not read config: {}", e),
+            LoadError::TooLarge { size, max } => {
+                write!(f, "config is {} bytes, limit is {} bytes", size, max)
+            }
+            LoadError::Syntax { line, text } => {
+                write!(f, "line {}: expected `key = value`, found '{}'", line, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LoadError::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(e: io::Error) -> Self {
+        LoadError::Io(e)
+    }
+}
+
+/// Builder for loading a config with non-default options.
+///
+/// New options become new builder methods, which never breaks callers.
+#[derive(Debug, Clone)]
+pub struct Loader {
+    path: PathBuf,
+    options: LoadOptions,
+}
+
+impl Loader {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        Loader {
+            path: path.as_ref().to_path_buf(),
+            options: LoadOptions::default(),
+        }
+    }
+
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.options.strict = strict;
+        self
+    }
+
+    pub fn max_size(mut self, bytes: u64) -> Self {
+        self.options.max_size = bytes;
+        self
+    }
+
+    pub fn options(mut self, options: LoadOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn load(&self) -> Result<Config, LoadError> {
+        let size = fs::metadata(&self.path)?.len();
+        if size > self.options.max_size {
+            return Err(LoadError::TooLarge {
+                size,
+                max: self.options.max_size,
+            });
+        }
+
+        let text = fs::read_to_string(&self.path)?;
+        let name = self
+            .path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        parse(&name, &text, &self.options)
+    }
+}
+
+/// Load a config file with default options.
+///
+/// 0.1 took `path: &str`; every `&str` caller still compiles against
+/// `impl AsRef<Path>`, and `String`, `PathBuf` and `&Path` now work too.
+pub fn load(path: impl AsRef<Path>) -> Result<Config, LoadError> {
+    Loader::new(path).load()
+}
+
+/// 0.1's way of asking for strict parsing, kept as a shim over the builder.
+#[deprecated(
+    since = "0.2.0",
+    note = "use `Loader::new(path).strict(true).load()` instead"
+)]
+pub fn load_strict(path: &str) -> Result<Config, LoadError> {
+    Loader::new(path).strict(true).load()
+}
+
+/// Parse config text that has already been read into memory.
+pub fn parse(name: &str, text: &str, options: &LoadOptions) -> Result<Config, LoadError> {
+    let mut entries = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        match line.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                entries.push((key.trim().to_string(), value.trim().to_string()));
+            }
+            _ if options.strict => {
+                return Err(LoadError::Syntax {
+                    line: index + 1,
+                    text: line.to_string(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok(Config {
+        name: name.to_string(),
+        entries,
+    })
+}
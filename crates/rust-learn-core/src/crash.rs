@@ -0,0 +1,244 @@
+//! Crash Reports
+//!
+//! When rust-learn panics, the panic hook installed by `install` writes a
+//! report to `crashes/` under the cache directory: the panic message and a
+//! backtrace, the machine and toolchain, the lesson that was running and the
+//! last debug log lines. The learner gets its path, and `rust-learn report
+//! show-last` prints it, so a bug report can include all of it.
+
+use crate::dirs;
+use crate::events;
+use crate::json::Value;
+use crate::progress::format_timestamp;
+use crate::toolchain;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, Write};
+use std::panic::{self, PanicHookInfo};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// How many debug log lines a report keeps
+const LOG_LINES: usize = 200;
+
+/// What a report needs that only the rest of the program knows
+static STATE: Mutex<State> = Mutex::new(State {
+    active: None,
+    log: LogTail::new(),
+});
+
+struct State {
+    /// `lesson` or `lesson::section`, while one runs
+    active: Option<String>,
+    log: LogTail,
+}
+
+/// The last `LOG_LINES` lines written to it
+struct LogTail {
+    lines: VecDeque<String>,
+    /// The start of a line whose end has not been written yet
+    partial: String,
+}
+
+impl LogTail {
+    const fn new() -> LogTail {
+        LogTail {
+            lines: VecDeque::new(),
+            partial: String::new(),
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) {
+        self.partial.push_str(&String::from_utf8_lossy(bytes));
+        while let Some(end) = self.partial.find('\n') {
+            let line: String = self.partial.drain(..=end).collect();
+            self.lines.push_back(line.trim_end().to_string());
+            if self.lines.len() > LOG_LINES {
+                self.lines.pop_front();
+            }
+        }
+    }
+}
+
+/// Keeps what is written to it for the next crash report; give it to the log
+/// subscriber as a writer (`.with_writer(|| crash::LogWriter)`)
+pub struct LogWriter;
+
+impl Write for LogWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        if let Ok(mut state) = STATE.lock() {
+            state.log.push(bytes);
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Name the lesson (or `lesson::section`) that is running, or `None` when it
+/// has finished
+pub fn set_active(key: Option<String>) {
+    if let Ok(mut state) = STATE.lock() {
+        state.active = key;
+    }
+}
+
+/// Write a report on every panic, after the usual panic message.
+/// `version` is the binary's, for the report
+pub fn install(version: &'static str) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        // A closed pipe (`rust-learn list | head`) is not a crash worth a report
+        if message(info).contains("failed printing to stdout") {
+            return;
+        }
+        let location = info.location().map(|location| location.to_string());
+        events::emit(
+            "panic",
+            vec![
+                ("message", Value::String(message(info))),
+                ("location", location.map_or(Value::Null, Value::String)),
+            ],
+        );
+        match write_report(version, info) {
+            Some(path) => eprintln!(
+                "rust-learn crashed. A report was written to {}\n\
+                 `rust-learn report show-last` shows it; please attach it to a bug report.",
+                path.display()
+            ),
+            None => eprintln!("rust-learn crashed, and the crash report could not be written."),
+        }
+    }));
+}
+
+/// The newest report, if there is one
+pub fn last_report() -> Option<PathBuf> {
+    let dir = dirs::cache_dir()?.join("crashes");
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .max()
+}
+
+fn write_report(version: &str, info: &PanicHookInfo) -> Option<PathBuf> {
+    // The panic may have happened while the state was locked
+    let (active, log) = match STATE.try_lock() {
+        Ok(state) => (
+            state.active.clone(),
+            state.log.lines.iter().cloned().collect(),
+        ),
+        Err(_) => (None, Vec::new()),
+    };
+
+    let now = format_timestamp(SystemTime::now());
+    let location = info
+        .location()
+        .map(|location| location.to_string())
+        .unwrap_or_default();
+    let text = report(
+        &now,
+        version,
+        &format!("{} at {}", message(info), location),
+        &Backtrace::force_capture().to_string(),
+        active.as_deref(),
+        &log,
+    );
+
+    let dir = dirs::cache_dir()?.join("crashes");
+    fs::create_dir_all(&dir).ok()?;
+    // 2026-10-16T14:03:27Z becomes crash-20261016T140327Z.txt, a valid name everywhere
+    let path = dir.join(format!("crash-{}.txt", now.replace(['-', ':'], "")));
+    fs::write(&path, text).ok()?;
+    Some(path)
+}
+
+/// The report's text
+fn report(
+    time: &str,
+    version: &str,
+    panic: &str,
+    backtrace: &str,
+    active: Option<&str>,
+    log: &[String],
+) -> String {
+    let mut text = String::new();
+    text.push_str(&format!(
+        "rust-learn {} crash report, {}\n\n",
+        version, time
+    ));
+    text.push_str(&format!("PANIC\n{}\n\n", panic));
+    text.push_str(&format!(
+        "RUNNING\n{}\n\n",
+        active.unwrap_or("no lesson (the runner itself)")
+    ));
+
+    text.push_str("ENVIRONMENT\n");
+    text.push_str(&format!(
+        "os: {} {}\n",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    ));
+    let tools = [
+        ("rustc", toolchain::version("rustc")),
+        ("cargo", toolchain::cargo_version()),
+    ];
+    for (tool, version) in tools {
+        let version = version.unwrap_or_else(|| "not found".to_string());
+        text.push_str(&format!("{}: {}\n", tool, version));
+    }
+
+    text.push_str(&format!("\nBACKTRACE\n{}\n", backtrace.trim_end()));
+
+    text.push_str(&format!("\nLAST {} DEBUG LOG LINES\n", log.len()));
+    for line in log {
+        text.push_str(line);
+        text.push('\n');
+    }
+    text
+}
+
+/// The panic's message, when it is a string (it almost always is)
+fn message(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "(not a string)".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_tail_keeps_last_lines() {
+        let mut tail = LogTail::new();
+        for number in 0..LOG_LINES + 5 {
+            tail.push(format!("line {}\n", number).as_bytes());
+        }
+        tail.push(b"half a ");
+        assert_eq!(tail.lines.len(), LOG_LINES);
+        assert_eq!(tail.lines[0], "line 5");
+        tail.push(b"line\n");
+        assert_eq!(tail.lines.back().unwrap(), "half a line");
+
+        let text = report(
+            "2026-10-16T14:03:27Z",
+            "0.1.0",
+            "boom at src/main.rs:1:1",
+            "0: main",
+            Some("ownership::slices"),
+            &["DEBUG run: started".to_string()],
+        );
+        assert!(text.starts_with("rust-learn 0.1.0 crash report, 2026-10-16T14:03:27Z"));
+        assert!(text.contains("RUNNING\nownership::slices\n"));
+        assert!(text.ends_with("LAST 1 DEBUG LOG LINES\nDEBUG run: started\n"));
+    }
+}
@@ -0,0 +1,405 @@
+//! Cross-Compilation
+//!
+//! `rust-learn dev cross <target>` builds a hello-world crate for another
+//! target and says what went wrong when it does not work. A cross build needs
+//! three things: the target's standard library (from rustup), a linker that
+//! understands the target, and whatever C libraries it links against. The
+//! walkthrough checks the first two up front, then builds, then reads cargo's
+//! errors with `diagnose` when the build fails anyway.
+
+use crate::dirs;
+use crate::output::{self, Glyph};
+use crate::toolchain;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A target the walkthrough knows how to explain
+pub struct Target {
+    pub triple: &'static str,
+    pub description: &'static str,
+    /// The linker cargo needs to be told about, or `None` when the default
+    /// (the host's `cc`, or the `rust-lld` shipped with Rust) works
+    pub linker: Option<&'static str>,
+    /// How to install that linker
+    pub install: &'static str,
+    /// How to run what was built
+    pub run: &'static str,
+}
+
+pub const TARGETS: &[Target] = &[
+    Target {
+        triple: "x86_64-unknown-linux-musl",
+        description: "64-bit Linux, statically linked against musl instead of glibc",
+        linker: None,
+        install: "Rust ships musl's start files; on Linux the host cc links them",
+        run: "./hello runs on any x86_64 Linux, whatever its glibc version",
+    },
+    Target {
+        triple: "aarch64-unknown-linux-gnu",
+        description: "64-bit ARM Linux (Raspberry Pi 4 and later, AWS Graviton)",
+        linker: Some("aarch64-linux-gnu-gcc"),
+        install: "apt install gcc-aarch64-linux-gnu",
+        run: "copy it to an ARM machine, or: qemu-aarch64 -L /usr/aarch64-linux-gnu ./hello",
+    },
+    Target {
+        triple: "x86_64-pc-windows-gnu",
+        description: "64-bit Windows, linked with the MinGW-w64 toolchain",
+        linker: Some("x86_64-w64-mingw32-gcc"),
+        install: "apt install gcc-mingw-w64-x86-64 (macOS: brew install mingw-w64)",
+        run: "copy hello.exe to Windows, or: wine hello.exe",
+    },
+    Target {
+        triple: "wasm32-unknown-unknown",
+        description: "WebAssembly with no OS, for browsers through wasm-bindgen",
+        linker: None,
+        install: "rust-lld ships with Rust",
+        run: "nothing runs main here; see `rust-learn wasi wasm_bindgen_path`",
+    },
+    Target {
+        triple: "wasm32-wasip1",
+        description: "WebAssembly with WASI: files, args and stdout in a sandbox",
+        linker: None,
+        install: "rust-lld ships with Rust",
+        run: "wasmtime hello.wasm (see `rust-learn wasi`)",
+    },
+];
+
+/// The known target called `triple`
+pub fn find(triple: &str) -> Option<&'static Target> {
+    TARGETS.iter().find(|target| target.triple == triple)
+}
+
+/// The environment variable cargo reads a target's linker from, e.g.
+/// `CARGO_TARGET_X86_64_PC_WINDOWS_GNU_LINKER`
+pub fn linker_var(triple: &str) -> String {
+    format!(
+        "CARGO_TARGET_{}_LINKER",
+        triple.to_uppercase().replace(['-', '.'], "_")
+    )
+}
+
+/// The linker a build for `triple` will use, if it is not the default one:
+/// the `CARGO_TARGET_<TRIPLE>_LINKER` setting, else the one the target needs
+pub fn linker_for(triple: &str) -> Option<String> {
+    std::env::var(linker_var(triple))
+        .ok()
+        .or_else(|| find(triple)?.linker.map(String::from))
+}
+
+/// Why a cross build failed, read from cargo's stderr
+#[derive(Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// rustc has no standard library for the target
+    TargetNotInstalled,
+    /// rustc has never heard of the target
+    UnknownTarget,
+    /// The linker program does not exist
+    LinkerNotFound(String),
+    /// The linker ran but does not understand the target's object files
+    WrongLinker(String),
+    /// The linker could not find a C library
+    MissingLibrary(String),
+    /// Anything else: the first error line
+    Other(String),
+}
+
+/// Recognise the usual ways a first cross-compile fails
+pub fn diagnose(stderr: &str) -> Failure {
+    let lower = stderr.to_lowercase();
+    let after = |marker: &str| Some(&stderr[stderr.find(marker)? + marker.len()..]);
+    let quoted = |marker: &str| Some(after(marker)?.split('`').next()?.to_string());
+
+    if lower.contains("could not find specification for target")
+        || lower.contains("error loading target specification")
+    {
+        return Failure::UnknownTarget;
+    }
+    if lower.contains("can't find crate for `std`")
+        || lower.contains("can't find crate for `core`")
+        || lower.contains("target may not be installed")
+    {
+        return Failure::TargetNotInstalled;
+    }
+    if let Some(linker) = quoted("linker `")
+        && lower.contains("not found")
+    {
+        return Failure::LinkerNotFound(linker);
+    }
+    if let Some(library) = quoted("could not find native static library `") {
+        return Failure::MissingLibrary(library);
+    }
+    if let Some(rest) = after("cannot find -l").or_else(|| after("unable to find library -l")) {
+        let library = rest.split([':', ' ', '\'', '\n']).next().unwrap_or(rest);
+        return Failure::MissingLibrary(library.to_string());
+    }
+    if let Some(linker) = quoted("linking with `") {
+        return Failure::WrongLinker(linker);
+    }
+
+    let first = stderr
+        .lines()
+        .find(|line| line.starts_with("error"))
+        .or_else(|| stderr.lines().find(|line| !line.trim().is_empty()))
+        .unwrap_or("(no error message)");
+    Failure::Other(first.trim().to_string())
+}
+
+impl Failure {
+    /// What went wrong and what to do about it, one line each
+    pub fn explain(&self, triple: &str) -> Vec<String> {
+        let install_hint = find(triple)
+            .map(|target| target.install)
+            .unwrap_or("install the cross linker your OS packages for this target");
+        match self {
+            Failure::TargetNotInstalled => vec![
+                format!("rustc has no standard library for {}.", triple),
+                format!("Fix: rustup target add {}", triple),
+            ],
+            Failure::UnknownTarget => vec![
+                format!("'{}' is not a target rustc knows.", triple),
+                "Fix: pick one from `rustc --print target-list`".to_string(),
+            ],
+            Failure::LinkerNotFound(linker) => vec![
+                format!(
+                    "The code compiled, but the linker `{}` is not installed.",
+                    linker
+                ),
+                format!("Fix: {}", install_hint),
+                format!(
+                    "Or point cargo at another one: {}=<program>",
+                    linker_var(triple)
+                ),
+            ],
+            Failure::WrongLinker(linker) => vec![
+                format!(
+                    "`{}` ran, but it links for the host, not for {}.",
+                    linker, triple
+                ),
+                format!("Fix: {}", install_hint),
+                format!("Then: {}=<that linker>", linker_var(triple)),
+            ],
+            Failure::MissingLibrary(library) => vec![
+                format!(
+                    "The linker has no copy of the C library `{}` for {}.",
+                    library, triple
+                ),
+                "Fix: install the target's version of it (a -dev package for that".to_string(),
+                "architecture), or build it from source with the cross compiler.".to_string(),
+            ],
+            Failure::Other(line) => vec![
+                format!("Not a failure this walkthrough recognises: {}", line),
+                "Run the cargo command above yourself to see the whole message.".to_string(),
+            ],
+        }
+    }
+}
+
+const HELLO_MANIFEST: &str = r#"[package]
+name = "hello"
+version = "0.1.0"
+edition = "2024"
+"#;
+
+const HELLO_MAIN: &str = r#"fn main() {
+    println!("Hello from {} on {}", std::env::consts::OS, std::env::consts::ARCH);
+}
+"#;
+
+/// A hello-world crate in a temp dir, removed again when dropped
+struct Scratch {
+    root: PathBuf,
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.root);
+    }
+}
+
+/// Guide a build of hello world for `triple`, returning whether it succeeded
+pub fn walkthrough(triple: &str) -> bool {
+    let target = find(triple);
+    println!(
+        "{}",
+        output::bold(&format!("=== Cross-compiling for {} ===", triple))
+    );
+    match target {
+        Some(target) => println!("{}\n", target.description),
+        None => {
+            println!("Not one of the targets this walkthrough knows; trying it anyway.");
+            let known: Vec<&str> = TARGETS.iter().map(|target| target.triple).collect();
+            println!("Known: {}\n", known.join(", "));
+        }
+    }
+
+    println!("STEP 1 - The target's standard library:");
+    match toolchain::installed_targets() {
+        Some(targets) if targets.iter().any(|installed| installed == triple) => {
+            println!(
+                "{} {} is installed\n",
+                output::status(Glyph::Ok, "ok", output::green, 0),
+                triple
+            );
+        }
+        Some(_) => {
+            let failure = match toolchain::known_targets() {
+                Some(known) if !known.iter().any(|name| name == triple) => Failure::UnknownTarget,
+                _ => Failure::TargetNotInstalled,
+            };
+            let (label, problem) = match failure {
+                Failure::UnknownTarget => ("unknown", "is not a rustc target"),
+                _ => ("missing", "is not installed"),
+            };
+            println!(
+                "{} {} {}",
+                output::status(Glyph::Fail, label, output::red, 0),
+                triple,
+                problem
+            );
+            print_explanation(&failure, triple);
+            return false;
+        }
+        None => println!("rustup was not found, so the build will tell\n"),
+    }
+
+    println!("STEP 2 - A linker for the target:");
+    match linker_for(triple) {
+        Some(linker) => match toolchain::version(&linker) {
+            Some(version) => println!(
+                "{} {}: {}\n",
+                output::status(Glyph::Ok, "ok", output::green, 0),
+                linker,
+                version
+            ),
+            None => {
+                println!(
+                    "{} {} was not found",
+                    output::status(Glyph::Fail, "missing", output::red, 0),
+                    linker
+                );
+                print_explanation(&Failure::LinkerNotFound(linker), triple);
+                return false;
+            }
+        },
+        None => println!(
+            "{} the default linker works for this target\n",
+            output::status(Glyph::Ok, "ok", output::green, 0)
+        ),
+    }
+
+    println!("STEP 3 - Build:");
+    let Some(version) = toolchain::cargo_version() else {
+        println!(
+            "{} cargo was not found; install Rust with rustup",
+            output::status(Glyph::Fail, "missing", output::red, 0)
+        );
+        return false;
+    };
+    println!("Using {}", version);
+    let scratch = Scratch {
+        root: std::env::temp_dir().join(format!("rust-learn-cross-{}", std::process::id())),
+    };
+    fs::create_dir_all(scratch.root.join("src")).expect("Failed to create scratch dir");
+    fs::write(scratch.root.join("Cargo.toml"), HELLO_MANIFEST).expect("Failed to write manifest");
+    fs::write(scratch.root.join("src/main.rs"), HELLO_MAIN).expect("Failed to write main.rs");
+
+    let target_dir = dirs::scratch_dir("cross-target");
+    let target_dir_arg = target_dir.to_string_lossy();
+    println!("$ cargo build --target {}", triple);
+    let output = toolchain::cargo(
+        &scratch.root,
+        &["build", "--target", triple, "--target-dir", &target_dir_arg],
+    )
+    .expect("Failed to run cargo");
+    if !output.success {
+        println!(
+            "{}",
+            output::status(Glyph::Fail, "cargo build failed", output::red, 0)
+        );
+        print_explanation(&diagnose(&output.stderr), triple);
+        return false;
+    }
+    println!(
+        "{} built\n",
+        output::status(Glyph::Ok, "ok", output::green, 0)
+    );
+
+    println!("STEP 4 - The result:");
+    let artifact = artifact_path(&target_dir, triple);
+    match fs::read(&artifact) {
+        Ok(bytes) => {
+            println!("{} ({} KiB)", artifact.display(), bytes.len() / 1024);
+            println!("format: {}", binary_format(&bytes));
+        }
+        Err(_) => println!("cargo succeeded, but {} is not there", artifact.display()),
+    }
+    if let Some(target) = target {
+        println!("to run it: {}", target.run);
+    }
+    true
+}
+
+fn print_explanation(failure: &Failure, triple: &str) {
+    for line in failure.explain(triple) {
+        println!("  {}", line);
+    }
+    println!();
+}
+
+/// Where cargo puts the hello binary for `triple`
+fn artifact_path(target_dir: &Path, triple: &str) -> PathBuf {
+    let file = if triple.contains("windows") {
+        "hello.exe"
+    } else if triple.starts_with("wasm") {
+        "hello.wasm"
+    } else {
+        "hello"
+    };
+    target_dir.join(triple).join("debug").join(file)
+}
+
+/// What kind of executable `bytes` is, from its first few bytes
+pub fn binary_format(bytes: &[u8]) -> &'static str {
+    match bytes {
+        [0x7f, b'E', b'L', b'F', 1, ..] => "ELF, 32-bit (Linux and most Unixes)",
+        [0x7f, b'E', b'L', b'F', 2, ..] => "ELF, 64-bit (Linux and most Unixes)",
+        [b'M', b'Z', ..] => "PE (a Windows .exe)",
+        [0, b'a', b's', b'm', ..] => "WebAssembly module",
+        [0xcf, 0xfa, 0xed, 0xfe, ..] => "Mach-O, 64-bit (macOS)",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnose_common_failures() {
+        let missing_std = "error[E0463]: can't find crate for `std`\n  = note: the `x86_64-unknown-linux-musl` target may not be installed";
+        assert_eq!(diagnose(missing_std), Failure::TargetNotInstalled);
+
+        let no_linker = "error: linker `x86_64-w64-mingw32-gcc` not found\n  |\n  = note: No such file or directory (os error 2)";
+        assert_eq!(
+            diagnose(no_linker),
+            Failure::LinkerNotFound("x86_64-w64-mingw32-gcc".to_string())
+        );
+
+        let wrong_linker = "error: linking with `cc` failed: exit status: 1\n  = note: /usr/bin/ld: hello.o: Relocations in generic ELF (EM: 183)\n          file in wrong format";
+        assert_eq!(
+            diagnose(wrong_linker),
+            Failure::WrongLinker("cc".to_string())
+        );
+
+        let no_library = "error: linking with `aarch64-linux-gnu-gcc` failed\n  = note: /usr/bin/ld: cannot find -lssl: No such file or directory";
+        assert_eq!(
+            diagnose(no_library),
+            Failure::MissingLibrary("ssl".to_string())
+        );
+
+        assert_eq!(
+            linker_var("x86_64-pc-windows-gnu"),
+            "CARGO_TARGET_X86_64_PC_WINDOWS_GNU_LINKER"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+//! Line Diffs
+//!
+//! Two texts compared a line at a time, the way `diff` does: the longest run
+//! of lines they share, and what was removed or added around it. `quiz
+//! --predict` compares what a snippet printed with the learner's answer, and
+//! `history diff` two runs of a lesson.
+
+/// One line of `lines`' result
+#[derive(Debug, PartialEq)]
+pub enum Line<'a> {
+    Same(&'a str),
+    /// In the old text, but not the new one
+    Removed(&'a str),
+    /// In the new text, but not the old one
+    Added(&'a str),
+}
+
+impl Line<'_> {
+    pub fn is_same(&self) -> bool {
+        matches!(self, Line::Same(_))
+    }
+}
+
+/// `old` against `new`. Trailing spaces and blank lines at the end do not
+/// count
+pub fn lines<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let lines = |text: &'a str| -> Vec<&'a str> {
+        let mut lines: Vec<&str> = text.lines().map(str::trim_end).collect();
+        while lines.last() == Some(&"") {
+            lines.pop();
+        }
+        lines
+    };
+    let (old, new) = (lines(old), lines(new));
+
+    // shared[i][j]: lines shared by old[i..] and new[j..]
+    let mut shared = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            shared[i][j] = if old[i] == new[j] {
+                shared[i + 1][j + 1] + 1
+            } else {
+                shared[i + 1][j].max(shared[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut result = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            result.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && shared[i + 1][j] >= shared[i][j + 1]) {
+            result.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            result.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(
+            lines(
+                "inner x = 12\nouter x = 6\n",
+                "inner x = 12  \nouter x = 6\n\n"
+            ),
+            [Line::Same("inner x = 12"), Line::Same("outer x = 6")]
+        );
+        assert_eq!(
+            lines(
+                "drop c\ndrop b\nfunction ends\ndrop a\n",
+                "drop c\ndrop a\nfunction ends"
+            ),
+            [
+                Line::Same("drop c"),
+                Line::Removed("drop b"),
+                Line::Removed("function ends"),
+                Line::Same("drop a"),
+                Line::Added("function ends"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,85 @@
+//! Where rust-learn Keeps Files
+//!
+//! Data the learner would miss (progress, recent picks) goes in the data
+//! directory; things that can be thrown away (crash reports, build output)
+//! in the cache directory; the learner's own settings (config.toml) in the config
+//! directory. Each can be moved with an environment variable, which is also
+//! how tests and `self-test` keep away from the learner's own files.
+
+use std::path::PathBuf;
+
+/// `$RUST_LEARN_DATA_DIR`, else the platform's data directory plus `rust-learn`:
+/// `$XDG_DATA_HOME` or `~/.local/share` on Unix, `%APPDATA%` on Windows
+pub fn data_dir() -> Option<PathBuf> {
+    if let Some(dir) = var("RUST_LEARN_DATA_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let base = if cfg!(windows) {
+        PathBuf::from(var("APPDATA")?)
+    } else if let Some(dir) = var("XDG_DATA_HOME") {
+        PathBuf::from(dir)
+    } else {
+        PathBuf::from(var("HOME")?).join(".local/share")
+    };
+    Some(base.join("rust-learn"))
+}
+
+/// `$RUST_LEARN_CACHE_DIR`, else the platform's cache directory plus
+/// `rust-learn`: `$XDG_CACHE_HOME` or `~/.cache` on Unix, `%LOCALAPPDATA%` on
+/// Windows
+pub fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = var("RUST_LEARN_CACHE_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let base = if cfg!(windows) {
+        PathBuf::from(var("LOCALAPPDATA")?)
+    } else if let Some(dir) = var("XDG_CACHE_HOME") {
+        PathBuf::from(dir)
+    } else {
+        PathBuf::from(var("HOME")?).join(".cache")
+    };
+    Some(base.join("rust-learn"))
+}
+
+/// `$RUST_LEARN_CONFIG_DIR`, else the platform's config directory plus
+/// `rust-learn`: `$XDG_CONFIG_HOME` or `~/.config` on Unix, `%APPDATA%` on
+/// Windows
+pub fn config_dir() -> Option<PathBuf> {
+    if let Some(dir) = var("RUST_LEARN_CONFIG_DIR") {
+        return Some(PathBuf::from(dir));
+    }
+    let base = if cfg!(windows) {
+        PathBuf::from(var("APPDATA")?)
+    } else if let Some(dir) = var("XDG_CONFIG_HOME") {
+        PathBuf::from(dir)
+    } else {
+        PathBuf::from(var("HOME")?).join(".config")
+    };
+    Some(base.join("rust-learn"))
+}
+
+/// The learner's exercise workspace, inside the data directory; what
+/// `assignment submit` commits
+pub fn workspace_dir() -> Option<PathBuf> {
+    Some(data_dir()?.join("workspace"))
+}
+
+/// Build output kept between runs so the next build is quicker, like the
+/// exercises' binaries or the WASI module's target directory: `scratch/<name>`
+/// in the cache directory, or in the temp directory without one. `cache gc`
+/// clears out what is old (`gc`)
+pub fn scratch_dir(name: &str) -> PathBuf {
+    scratch_root().join(name)
+}
+
+/// The directory holding every `scratch_dir`
+pub fn scratch_root() -> PathBuf {
+    cache_dir()
+        .unwrap_or_else(|| std::env::temp_dir().join("rust-learn"))
+        .join("scratch")
+}
+
+/// An environment variable that is set and not empty
+fn var(name: &str) -> Option<std::ffi::OsString> {
+    std::env::var_os(name).filter(|value| !value.is_empty())
+}
@@ -0,0 +1,137 @@
+//! Error Codes
+//!
+//! `rust-learn explain E0502` asks rustc for its explanation of an error
+//! code (`rustc --explain`), which is Markdown, and splits it into blocks to
+//! print: prose, headings and code examples. The lessons that teach what the
+//! error is about are linked from `registry::ERROR_CODES`.
+
+use crate::toolchain;
+use std::io;
+use std::path::Path;
+
+/// A piece of an explanation
+#[derive(Debug, PartialEq)]
+pub enum Block {
+    /// A `#` heading, without the `#`s
+    Heading(String),
+    /// A paragraph, as rustc wrapped it, with `[text][name]` links made
+    /// plain `text`
+    Prose(String),
+    /// A fenced example, erroneous or fixed (rustc drops the fence's
+    /// `compile_fail`, so the prose above says which)
+    Code(String),
+    /// A link target (`[name]: url`), kept to print last
+    Link(String),
+}
+
+/// `e502`, `502` and `E0502` as `E0502`; `None` when it is not a code
+pub fn normalize(code: &str) -> Option<String> {
+    let code = code.trim();
+    let digits = code
+        .strip_prefix(['E', 'e'])
+        .unwrap_or(code)
+        .trim_start_matches('0');
+    let number: u16 = digits.parse().ok().filter(|&number| number > 0)?;
+    (number < 10_000).then(|| format!("E{:04}", number))
+}
+
+/// rustc's explanation of `code` (already `normalize`d), or `None` when it
+/// does not know the code
+pub fn explain(code: &str) -> io::Result<Option<String>> {
+    let output = toolchain::rustc(Path::new("."), &["--explain", code])?;
+    Ok(output.success.then_some(output.stdout))
+}
+
+/// Split an explanation into blocks
+pub fn blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut lines = markdown.lines();
+    let flush = |paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>| {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Prose(plain_links(&paragraph.join("\n"))));
+            paragraph.clear();
+        }
+    };
+    while let Some(line) = lines.next() {
+        if line.starts_with("```") {
+            flush(&mut paragraph, &mut blocks);
+            let code: Vec<&str> = lines.by_ref().take_while(|line| *line != "```").collect();
+            blocks.push(Block::Code(code.join("\n")));
+        } else if line.starts_with('#') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(
+                line.trim_start_matches('#').trim().to_string(),
+            ));
+        } else if line.starts_with('[') && line.contains("]: ") {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Link(line.to_string()));
+        } else if line.trim().is_empty() {
+            flush(&mut paragraph, &mut blocks);
+        } else {
+            paragraph.push(line);
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+/// `[text][name]` and `[text](url)` as `text`
+fn plain_links(text: &str) -> String {
+    let mut plain = String::new();
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']').map(|close| open + close) else {
+            break;
+        };
+        let after = &rest[close + 1..];
+        let target_end = match after.chars().next() {
+            Some('[') => after.find(']'),
+            Some('(') => after.find(')'),
+            _ => None,
+        };
+        plain.push_str(&rest[..open]);
+        match target_end {
+            Some(end) => {
+                plain.push_str(&rest[open + 1..close]);
+                rest = &after[end + 1..];
+            }
+            None => {
+                plain.push_str(&rest[open..=close]);
+                rest = after;
+            }
+        }
+    }
+    plain.push_str(rest);
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_and_blocks() {
+        assert_eq!(normalize("e502").as_deref(), Some("E0502"));
+        assert_eq!(normalize("382").as_deref(), Some("E0382"));
+        assert_eq!(normalize("E0106").as_deref(), Some("E0106"));
+        assert_eq!(normalize("borrow"), None);
+        assert_eq!(normalize("E0"), None);
+
+        let text = "A value was borrowed twice.\n\nErroneous code example:\n\n\
+                    ```compile_fail,E0499\nlet a = &mut x;\nlet b = &mut x;\n```\n\n\
+                    # Notes\n\nSee the [References][refs] section\nof the Book.\n\n\
+                    [refs]: https://doc.rust-lang.org/book/\n";
+        assert_eq!(
+            blocks(text),
+            [
+                Block::Prose("A value was borrowed twice.".to_string()),
+                Block::Prose("Erroneous code example:".to_string()),
+                Block::Code("let a = &mut x;\nlet b = &mut x;".to_string()),
+                Block::Heading("Notes".to_string()),
+                Block::Prose("See the References section\nof the Book.".to_string()),
+                Block::Link("[refs]: https://doc.rust-lang.org/book/".to_string()),
+            ]
+        );
+    }
+}
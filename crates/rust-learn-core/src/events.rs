@@ -0,0 +1,118 @@
+//! Event Stream
+//!
+//! `--events ndjson` turns a run's output into one JSON object per line on
+//! stdout, for tools that drive rust-learn (an editor extension, a grading
+//! script). Everything a lesson would print becomes an event instead, with
+//! its name under `"event"`:
+//!
+//! ```text
+//! {"event":"lesson_start","lesson":"borrowing","title":"Borrowing - ..."}
+//! {"event":"section_start","lesson":"borrowing","section":"immutable_borrowing","title":"Immutable Borrowing"}
+//! {"event":"line","text":"Multiple immutable borrows: hello, hello"}
+//! {"event":"prompt","text":"> "}
+//! {"event":"input","text":"3","sample":true}
+//! {"event":"section_end","lesson":"borrowing","section":"immutable_borrowing"}
+//! {"event":"panic","message":"assertion failed: ...","location":"src/vectors.rs:42:5"}
+//! {"event":"result","lesson":"borrowing","section":null,"completed":true,"seconds":0.004}
+//! {"event":"summary","report":{"lessons":[...],"total_seconds":150.2,...}}
+//! ```
+//!
+//! `line` is the text without color, translated like the terminal's; `text`
+//! is part of a line, from a lesson's `print!`. `summary` ends `run --all`
+//! with what `--report json` prints (`runner::report`). With `--checkpoints`
+//! a question is a `checkpoint` event and its answer `checkpoint_answer`.
+//! Commands other than running lessons print as usual.
+
+use crate::json::Value;
+use std::io::{self, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The formats `--events` takes
+pub const FORMATS: [&str; 1] = ["ndjson"];
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// `lesson` and `section` of the section started last and not ended yet
+static OPEN: Mutex<Option<(String, String)>> = Mutex::new(None);
+
+/// Stream events instead of printing, from now on
+pub fn init(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Print event `name` with `fields`, when the stream is on
+pub fn emit(name: &str, fields: Vec<(&str, Value)>) {
+    if !enabled() {
+        return;
+    }
+    println!("{}", event(name, fields));
+    // A tool reading the stream should not wait for a buffer to fill
+    let _ = io::stdout().flush();
+}
+
+fn event(name: &str, fields: Vec<(&str, Value)>) -> Value {
+    let mut entries = vec![("event".to_string(), Value::from(name))];
+    entries.extend(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value)),
+    );
+    Value::Object(entries)
+}
+
+/// `section` of `lesson` starts, which ends the one before it
+pub fn section_start(lesson: &str, section: &str, title: &str) {
+    if !enabled() {
+        return;
+    }
+    end_section();
+    emit(
+        "section_start",
+        vec![
+            ("lesson", lesson.into()),
+            ("section", section.into()),
+            ("title", title.into()),
+        ],
+    );
+    *OPEN.lock().unwrap_or_else(|poisoned| poisoned.into_inner()) =
+        Some((lesson.to_string(), section.to_string()));
+}
+
+/// The section started last is over, if one is open
+pub fn end_section() {
+    let open = OPEN
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take();
+    if let Some((lesson, section)) = open {
+        emit(
+            "section_end",
+            vec![
+                ("lesson", Value::String(lesson)),
+                ("section", Value::String(section)),
+            ],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event() {
+        let line = event(
+            "input",
+            vec![("text", "a \"b\"".into()), ("sample", Value::Bool(true))],
+        );
+        assert_eq!(
+            line.to_string(),
+            r#"{"event":"input","text":"a \"b\"","sample":true}"#
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! Exercises
+//!
+//! Small Rust files that do not work yet, one directory per lesson
+//! (`exercises/ownership/ownership1.rs`), for the learner to fix. Each starts
+//! with a comment saying what to do and carries an `// I AM NOT DONE` line.
+//! An exercise passes once it compiles, its program or its tests succeed, and
+//! that line is gone, so a learner can get it compiling and keep working on
+//! it before moving on.
+//!
+//! An exercise with a `#[test]` in it is built with `rustc --test` and its
+//! tests run; any other is built as a program and run. `Exercise::skeleton`
+//! is the version that was shipped, for `exercise reset` (see `backups`):
+//! for rust-learn's own exercises the copy in `skeletons/`, built into the
+//! binary, since the one in `exercises/` is the learner's to change (and to
+//! commit); for a pack's, the copy `pack add` pinned (`packs::pin`).
+//!
+//! An exercise can come with curated solutions, `<lesson>/solutions/<name>/`
+//! next to it, one file per approach (`beginner.rs`, `idiomatic.rs`,
+//! `performance.rs`, ...) starting with a comment on what it does well
+//! (`Exercise::solutions`). Each is the whole exercise, tests included, so
+//! it can be checked like one.
+//!
+//! So that rust-analyzer completes and flags errors while a learner edits,
+//! the exercises directory is also kept a Cargo project (`write_project`):
+//! one target per exercise in a `Cargo.toml`, a `rust-toolchain.toml` asking
+//! for rust-analyzer and the std sources, and a `.gitignore` for `target/`.
+
+use crate::{packs, toolchain};
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// The line a learner deletes when an exercise is done
+pub const NOT_DONE: &str = "// I AM NOT DONE";
+
+/// The directory, next to a lesson's exercises, with their solutions
+pub const SOLUTIONS_DIR: &str = "solutions";
+
+/// Approaches shown in this order, simplest first; others follow by name
+const APPROACHES: &[&str] = &["beginner", "idiomatic", "performance"];
+
+/// The first line of a Cargo.toml `write_project` wrote: one without it is
+/// someone else's and left alone
+const MANIFEST_MARK: &str = "# Written by rust-learn";
+
+// `SKELETONS`: `("<lesson>/<name>.rs", text)` for each file in `skeletons/`,
+// written by build.rs
+include!(concat!(env!("OUT_DIR"), "/skeletons_generated.rs"));
+
+const TOOLCHAIN: &str = "\
+[toolchain]
+channel = \"stable\"
+components = [\"rust-analyzer\", \"rust-src\", \"clippy\", \"rustfmt\"]
+";
+
+/// One exercise file
+#[derive(Debug, Clone)]
+pub struct Exercise {
+    /// The file name without `.rs`, like `ownership1`
+    pub name: String,
+    /// The lesson it belongs to: the directory it is in
+    pub lesson: String,
+    pub path: PathBuf,
+    /// The content pack it comes from (`packs`); `None` for the exercises
+    /// shipped with rust-learn
+    pub pack: Option<String>,
+}
+
+/// One curated solution of an exercise
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// The file name without `.rs`, like `idiomatic`
+    pub approach: String,
+    /// The comment lines it starts with, without their `//`
+    pub commentary: String,
+    /// The rest of the file
+    pub code: String,
+    pub path: PathBuf,
+}
+
+/// How checking an exercise went
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    Passed,
+    /// It works, but the `// I AM NOT DONE` line is still there
+    NotDone,
+    /// It does not compile, or its program or tests fail; with what rustc or
+    /// the program printed
+    Failed(String),
+}
+
+impl Exercise {
+    /// Its name, after its pack's for a pack's exercise (`week-3/ownership1`),
+    /// so it is told apart from a shipped one of the same name
+    pub fn id(&self) -> String {
+        match &self.pack {
+            Some(pack) => format!("{}/{}", pack, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Progress key for a passed exercise
+    pub fn key(&self) -> String {
+        format!("exercise::{}", self.id())
+    }
+
+    /// The exercise as it was shipped, whatever the learner has done to the
+    /// file since. `None` for one rust-learn did not ship, or a pack's that
+    /// has no pinned copy
+    pub fn skeleton(&self) -> io::Result<Option<String>> {
+        let file = format!("{}/{}.rs", self.lesson, self.name);
+        let Some(pack) = &self.pack else {
+            return Ok(SKELETONS
+                .iter()
+                .find(|(name, _)| *name == file)
+                .map(|(_, text)| text.to_string()));
+        };
+        let Some(path) = packs::root().map(|root| packs::pinned(&root, pack).join(&file)) else {
+            return Ok(None);
+        };
+        match fs::read_to_string(path) {
+            Ok(text) => Ok(Some(text)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// The curated solutions in `solutions/<name>/` next to the exercise,
+    /// in `APPROACHES` order; none when there is no such directory
+    pub fn solutions(&self) -> io::Result<Vec<Solution>> {
+        let dir = self.path.with_file_name(SOLUTIONS_DIR).join(&self.name);
+        if !dir.is_dir() {
+            return Ok(Vec::new());
+        }
+        let mut solutions = Vec::new();
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|extension| extension == "rs")
+                && let Some(approach) = path.file_stem()
+            {
+                let (commentary, code) = split_commentary(&fs::read_to_string(&path)?);
+                solutions.push(Solution {
+                    approach: approach.to_string_lossy().into_owned(),
+                    commentary,
+                    code,
+                    path,
+                });
+            }
+        }
+        let rank = |approach: &str| {
+            APPROACHES
+                .iter()
+                .position(|known| *known == approach)
+                .unwrap_or(APPROACHES.len())
+        };
+        solutions.sort_by(|a, b| {
+            (rank(&a.approach), &a.approach).cmp(&(rank(&b.approach), &b.approach))
+        });
+        Ok(solutions)
+    }
+
+    /// Compile the exercise into `scratch` and run it
+    pub fn check(&self, scratch: &Path) -> io::Result<Outcome> {
+        let source = fs::read_to_string(&self.path)?;
+        let tests = source.contains("#[test]");
+        let binary = scratch.join(&self.name);
+        if let Err(errors) = self.build(&binary)? {
+            return Ok(Outcome::Failed(errors));
+        }
+        let run_args: &[&str] = if tests { &["--quiet"] } else { &[] };
+        // A failing test's message is enough; a backtrace buries it
+        let env: &[(&str, &OsStr)] = &[("RUST_BACKTRACE", "0".as_ref())];
+        let run = toolchain::run_with_env(&binary, scratch, run_args, env)?;
+        if !run.success {
+            return Ok(Outcome::Failed(format!("{}{}", run.stdout, run.stderr)));
+        }
+        Ok(if is_done(&source) {
+            Outcome::Passed
+        } else {
+            Outcome::NotDone
+        })
+    }
+
+    /// Compile the exercise, with debug info, to `binary`: a program, or its
+    /// test runner when it has tests. The error is what rustc printed
+    pub fn build(&self, binary: &Path) -> io::Result<Result<(), String>> {
+        let source = fs::read_to_string(&self.path)?;
+        if let Some(dir) = binary.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let path = self.path.to_string_lossy();
+        let binary_path = binary.to_string_lossy();
+        let mut args = vec!["--edition", "2024", "-g", "-o", &binary_path, &path];
+        if source.contains("#[test]") {
+            args.push("--test");
+        }
+        // From here, so rustc's errors name the file the way the learner does
+        let build = toolchain::rustc(Path::new("."), &args)?;
+        Ok(if build.success {
+            Ok(())
+        } else {
+            Err(build.stderr)
+        })
+    }
+}
+
+/// Whether the `// I AM NOT DONE` line is gone
+pub fn is_done(source: &str) -> bool {
+    !source.lines().any(|line| line.trim() == NOT_DONE)
+}
+
+/// A solution's opening comment lines, without their `//`, and the code
+/// after them
+fn split_commentary(source: &str) -> (String, String) {
+    let mut commentary = Vec::new();
+    let mut rest = source;
+    while let Some(line) = rest.lines().next() {
+        let Some(comment) = line.trim_start().strip_prefix("//") else {
+            break;
+        };
+        commentary.push(comment.strip_prefix(' ').unwrap_or(comment));
+        rest = rest[line.len()..].strip_prefix('\n').unwrap_or_default();
+    }
+    (
+        commentary.join("\n"),
+        rest.trim_start_matches('\n').to_string(),
+    )
+}
+
+/// Every exercise under `dir`, lesson by lesson in `order` (lessons not in it
+/// come last), then by name
+pub fn find(dir: &Path, order: &[&str]) -> io::Result<Vec<Exercise>> {
+    let mut exercises = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        let lesson = entry.file_name().to_string_lossy().into_owned();
+        for file in fs::read_dir(entry.path())? {
+            let path = file?.path();
+            if path.extension().is_some_and(|extension| extension == "rs")
+                && let Some(name) = path.file_stem()
+            {
+                exercises.push(Exercise {
+                    name: name.to_string_lossy().into_owned(),
+                    lesson: lesson.clone(),
+                    path,
+                    pack: None,
+                });
+            }
+        }
+    }
+    let rank = |lesson: &str| {
+        order
+            .iter()
+            .position(|name| *name == lesson)
+            .unwrap_or(order.len())
+    };
+    exercises.sort_by(|a, b| {
+        (rank(&a.lesson), &a.lesson, natural(&a.name)).cmp(&(
+            rank(&b.lesson),
+            &b.lesson,
+            natural(&b.name),
+        ))
+    });
+    Ok(exercises)
+}
+
+/// Make `dir` a Cargo project with a target for each of `exercises`, its
+/// own workspace so it does not join one it happens to be inside. The
+/// Cargo.toml is rewritten when the exercises change; `rust-toolchain.toml`
+/// and `.gitignore` are only written when missing, so they can be edited
+pub fn write_project(dir: &Path, exercises: &[Exercise]) -> io::Result<()> {
+    let manifest_path = dir.join("Cargo.toml");
+    let existing = fs::read_to_string(&manifest_path).ok();
+    if existing
+        .as_ref()
+        .is_some_and(|text| !text.starts_with(MANIFEST_MARK))
+    {
+        return Ok(());
+    }
+    let manifest = manifest(dir, exercises);
+    if existing.as_ref() != Some(&manifest) {
+        fs::write(&manifest_path, manifest)?;
+    }
+    for (name, text) in [
+        ("rust-toolchain.toml", TOOLCHAIN),
+        (".gitignore", "/target\n"),
+    ] {
+        let path = dir.join(name);
+        if !path.exists() {
+            fs::write(path, text)?;
+        }
+    }
+    Ok(())
+}
+
+/// An exercise with tests is a `[[test]]` target, since it may have no
+/// `main`; any other a `[[bin]]`
+fn manifest(dir: &Path, exercises: &[Exercise]) -> String {
+    let mut text = format!(
+        "{}; `rust-learn verify` updates it when exercises come and go\n\
+         [package]\n\
+         name = \"exercises\"\n\
+         version = \"0.0.0\"\n\
+         edition = \"2024\"\n\
+         publish = false\n\
+         autobins = false\n\
+         autotests = false\n\
+         \n\
+         [workspace]\n",
+        MANIFEST_MARK
+    );
+    for exercise in exercises {
+        let tests =
+            fs::read_to_string(&exercise.path).is_ok_and(|source| source.contains("#[test]"));
+        let path = exercise.path.strip_prefix(dir).unwrap_or(&exercise.path);
+        let path: Vec<_> = path.iter().map(|part| part.to_string_lossy()).collect();
+        text.push_str(&format!(
+            "\n[[{}]]\nname = \"{}\"\npath = \"{}\"\n",
+            if tests { "test" } else { "bin" },
+            exercise.name,
+            path.join("/")
+        ));
+    }
+    text
+}
+
+/// `vectors2` before `vectors10`: the name without its trailing number, then
+/// the number
+fn natural(name: &str) -> (&str, u64) {
+    let stem = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    (stem, name[stem.len()..].parse().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::toolchain::CommandOutput;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_find_and_is_done() {
+        let dir = std::env::temp_dir().join(format!("rust-learn-exercises-{}", std::process::id()));
+        for (lesson, name) in [
+            ("vectors", "vectors10"),
+            ("vectors", "vectors2"),
+            ("ownership", "ownership1"),
+            ("extra", "extra1"),
+        ] {
+            fs::create_dir_all(dir.join(lesson)).unwrap();
+            fs::write(dir.join(lesson).join(format!("{}.rs", name)), "").unwrap();
+        }
+        fs::write(dir.join("vectors").join("notes.txt"), "").unwrap();
+        fs::write(
+            dir.join("vectors").join("vectors2.rs"),
+            "#[test]\nfn t() {}",
+        )
+        .unwrap();
+
+        let found = find(&dir, &["ownership", "vectors"]).unwrap();
+        let names: Vec<&str> = found
+            .iter()
+            .map(|exercise| exercise.name.as_str())
+            .collect();
+        assert_eq!(names, ["ownership1", "vectors2", "vectors10", "extra1"]);
+
+        write_project(&dir, &found).unwrap();
+        let manifest = fs::read_to_string(dir.join("Cargo.toml")).unwrap();
+        assert!(manifest.starts_with(MANIFEST_MARK));
+        assert!(manifest.contains("\n[workspace]\n"));
+        assert!(
+            manifest
+                .contains("[[bin]]\nname = \"ownership1\"\npath = \"ownership/ownership1.rs\"\n")
+        );
+        assert!(
+            manifest.contains("[[test]]\nname = \"vectors2\"\npath = \"vectors/vectors2.rs\"\n")
+        );
+        assert!(dir.join("rust-toolchain.toml").exists());
+        // Someone else's manifest is kept
+        fs::write(dir.join("Cargo.toml"), "[package]\n").unwrap();
+        write_project(&dir, &found[..1]).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join("Cargo.toml")).unwrap(),
+            "[package]\n"
+        );
+
+        // rustc's errors are the failure, and nothing is run after them
+        let rustc = Arc::new(toolchain::Fake::new(|_| CommandOutput {
+            success: false,
+            stderr: "error[E0382]".to_string(),
+            ..CommandOutput::default()
+        }));
+        let outcome = toolchain::with_runner(rustc.clone(), || found[0].check(&dir)).unwrap();
+        assert_eq!(outcome, Outcome::Failed("error[E0382]".to_string()));
+        assert_eq!(rustc.invocations().len(), 1);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!is_done("// I AM NOT DONE\nfn main() {}"));
+        assert!(!is_done("fn main() {}\n    // I AM NOT DONE  \n"));
+        assert!(is_done("// I AM DONE\nfn main() {}"));
+    }
+
+    #[test]
+    fn test_skeletons_match_the_shipped_exercises() {
+        let shipped = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../exercises");
+        let mut files: Vec<String> = find(&shipped, &[])
+            .unwrap()
+            .iter()
+            .map(|exercise| format!("{}/{}.rs", exercise.lesson, exercise.name))
+            .collect();
+        files.sort();
+        let skeletons: Vec<&str> = SKELETONS.iter().map(|(file, _)| *file).collect();
+        assert_eq!(files, skeletons);
+        assert!(SKELETONS.iter().all(|(_, text)| !is_done(text)));
+    }
+
+    #[test]
+    fn test_solutions() {
+        let dir = std::env::temp_dir().join(format!("rust-learn-solutions-{}", std::process::id()));
+        let solutions = dir.join("vectors").join(SOLUTIONS_DIR).join("vectors2");
+        fs::create_dir_all(&solutions).unwrap();
+        fs::write(dir.join("vectors").join("vectors2.rs"), "").unwrap();
+        for approach in ["performance", "recursive", "beginner", "idiomatic"] {
+            let text = format!("// {} way\n//\n// Why.\n\nfn main() {{}}\n", approach);
+            fs::write(solutions.join(format!("{}.rs", approach)), text).unwrap();
+        }
+        fs::write(solutions.join("notes.txt"), "").unwrap();
+
+        let found = find(&dir, &[]).unwrap();
+        assert_eq!(found.len(), 1, "solutions are not exercises");
+        let found = found[0].solutions().unwrap();
+        let approaches: Vec<&str> = found
+            .iter()
+            .map(|solution| solution.approach.as_str())
+            .collect();
+        assert_eq!(
+            approaches,
+            ["beginner", "idiomatic", "performance", "recursive"]
+        );
+        assert_eq!(found[0].commentary, "beginner way\n\nWhy.");
+        assert_eq!(found[0].code, "fn main() {}\n");
+
+        fs::remove_dir_all(solutions).unwrap();
+        assert!(find(&dir, &[]).unwrap()[0].solutions().unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
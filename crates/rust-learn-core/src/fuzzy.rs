@@ -0,0 +1,119 @@
+//! Fuzzy Name Matching
+//!
+//! Finds the name closest to one the user mistyped, so `rust-learn ownrship`
+//! can run `ownership` and `rust-learn vec` can run `vectors`.
+
+/// The candidate closest to a query
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'a> {
+    pub name: &'a str,
+    /// Close enough to use without asking: the query starts exactly one
+    /// name, or exactly one name is a single edit away
+    pub confident: bool,
+}
+
+/// Levenshtein distance: the fewest single-character insertions, deletions
+/// and substitutions that turn `a` into `b`
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // One row of the table at a time; previous[j] is the distance between the
+    // part of `a` seen so far and the first j characters of `b`
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitute = previous[j] + usize::from(a_char != b_char);
+            let delete = previous[j + 1] + 1;
+            let insert = current[j] + 1;
+            current.push(substitute.min(delete).min(insert));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// The candidate closest to `query` (ignoring case), or `None` when nothing
+/// is close enough to be worth suggesting
+pub fn closest<'a>(query: &str, candidates: &[&'a str]) -> Option<Match<'a>> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+
+    let prefixed: Vec<&str> = candidates
+        .iter()
+        .copied()
+        .filter(|name| name.to_lowercase().starts_with(&query))
+        .collect();
+    if let [name] = prefixed[..] {
+        return Some(Match {
+            name,
+            confident: true,
+        });
+    }
+
+    let mut scored: Vec<(usize, &str)> = candidates
+        .iter()
+        .map(|name| (edit_distance(&query, &name.to_lowercase()), *name))
+        .collect();
+    scored.sort_by_key(|&(distance, _)| distance);
+
+    // Allow about one typo per three characters
+    let (distance, name) = *scored.first()?;
+    if distance > 1 + query.chars().count() / 3 {
+        return None;
+    }
+    let tied = scored.get(1).is_some_and(|&(next, _)| next == distance);
+
+    Some(Match {
+        name,
+        confident: distance == 1 && !tied,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NAMES: &[&str] = &["ownership", "borrowing", "vectors", "variables", "loops"];
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("ownrship", "ownership"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("loops", "loops"), 0);
+    }
+
+    #[test]
+    fn test_closest() {
+        let closest = |query| closest(query, NAMES);
+
+        assert_eq!(
+            closest("ownrship"),
+            Some(Match {
+                name: "ownership",
+                confident: true
+            })
+        );
+        assert_eq!(
+            closest("vec"),
+            Some(Match {
+                name: "vectors",
+                confident: true
+            })
+        );
+        // "v" starts two names, and "barowing" is two edits from "borrowing"
+        assert_eq!(closest("v"), None);
+        assert_eq!(
+            closest("barowing"),
+            Some(Match {
+                name: "borrowing",
+                confident: false
+            })
+        );
+        assert_eq!(closest("generics"), None);
+    }
+}
@@ -0,0 +1,302 @@
+//! Cleaning Up Old Files
+//!
+//! Checking exercises and interview problems, cross-compiling and building
+//! the WASI module keep their build output in the scratch directory
+//! (`dirs::scratch_dir`), a crash leaves a report, and every run adds to the
+//! history. Builds in particular grow to gigabytes, so `collect` removes
+//! what the `[cache]` table in config.toml calls old:
+//!
+//! ```toml
+//! [cache]
+//! max_age_days = 30    # builds and crash reports untouched for this long
+//! max_size_mib = 2048  # then the oldest builds, until the rest fit
+//! history_days = 180   # runs older than this leave the history
+//! auto = true          # check once a day when rust-learn starts
+//! ```
+//!
+//! A build is one entry in one of the scratch directories (an exercise's
+//! binary, or a cargo target directory's `debug`), and its age is that of the
+//! newest file in it, so a target directory that is still used stays.
+//!
+//! `prepare` only plans the removals, as a `Transaction`, so
+//! `cache gc --dry-run` can list them; `collect` applies them.
+
+use crate::dirs;
+use crate::history;
+use crate::settings;
+use crate::transaction::Transaction;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// When the last automatic check ran, as the file's modification time
+const STAMP: &str = "gc-checked";
+
+/// What counts as old; see the module comment for the config keys
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Policy {
+    pub max_age: Duration,
+    pub max_size: u64,
+    pub history_age: Duration,
+    pub auto: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            max_age: 30 * DAY,
+            max_size: 2048 * 1024 * 1024,
+            history_age: 180 * DAY,
+            auto: true,
+        }
+    }
+}
+
+impl Policy {
+    /// The `[cache]` table in config.toml over the defaults; a negative
+    /// number is ignored
+    pub fn configured() -> Policy {
+        let mut policy = Policy::default();
+        let Some(table) = settings::get().table("cache") else {
+            return policy;
+        };
+        let number = |key: &str| table.integer(key).and_then(|n| u64::try_from(n).ok());
+        if let Some(days) = number("max_age_days") {
+            policy.max_age = days_to_duration(days);
+        }
+        if let Some(mib) = number("max_size_mib") {
+            policy.max_size = mib.saturating_mul(1024 * 1024);
+        }
+        if let Some(days) = number("history_days") {
+            policy.history_age = days_to_duration(days);
+        }
+        if let Some(auto) = table.bool("auto") {
+            policy.auto = auto;
+        }
+        policy
+    }
+}
+
+/// A file or directory `collect` may remove
+#[derive(Debug, Clone, PartialEq)]
+pub struct Item {
+    pub path: PathBuf,
+    /// When it, or the newest file in it, last changed
+    pub modified: SystemTime,
+    pub bytes: u64,
+}
+
+/// The places `collect` looks after, with how much each holds
+#[derive(Debug)]
+pub struct Usage {
+    pub name: &'static str,
+    pub path: PathBuf,
+    pub bytes: u64,
+}
+
+/// What `collect` removes
+#[derive(Debug, Default)]
+pub struct Report {
+    pub builds: Vec<Item>,
+    pub crash_reports: Vec<Item>,
+    /// Runs dropped from the history, and the bytes their transcripts took
+    pub runs: usize,
+    pub history_bytes: u64,
+}
+
+impl Report {
+    pub fn freed(&self) -> u64 {
+        let items = self.builds.iter().chain(&self.crash_reports);
+        items.map(|item| item.bytes).sum::<u64>() + self.history_bytes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.builds.is_empty() && self.crash_reports.is_empty() && self.runs == 0
+    }
+}
+
+fn crash_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join("crashes"))
+}
+
+/// How much the builds, crash reports and history take up now
+pub fn usage() -> Vec<Usage> {
+    let mut places = vec![("builds", Some(dirs::scratch_root()))];
+    places.push(("crash reports", crash_dir()));
+    places.push(("history", dirs::data_dir().map(|dir| dir.join("history"))));
+    places
+        .into_iter()
+        .filter_map(|(name, path)| {
+            let path = path?;
+            let bytes = measure(&path).map_or(0, |(bytes, _)| bytes);
+            Some(Usage { name, path, bytes })
+        })
+        .collect()
+}
+
+/// Plan removing what `policy` calls old, as of `now`, changing nothing yet
+pub fn prepare(policy: &Policy, now: SystemTime) -> io::Result<(Report, Transaction)> {
+    let mut report = Report::default();
+    let mut transaction = Transaction::new();
+
+    let builds = builds()?;
+    for index in plan(&builds, policy, now) {
+        let item = &builds[index];
+        transaction.remove_sized(&item.path, item.bytes);
+        report.builds.push(item.clone());
+    }
+
+    if let Some(dir) = crash_dir() {
+        let reports = entries(&dir)?;
+        let old = reports
+            .into_iter()
+            .filter(|item| age(item, now) > policy.max_age);
+        for item in old {
+            transaction.remove_sized(&item.path, item.bytes);
+            report.crash_reports.push(item);
+        }
+    }
+
+    if let Some(cutoff) = now.checked_sub(policy.history_age) {
+        let (runs, bytes) = history::prune(cutoff, &mut transaction)?;
+        report.runs = runs;
+        report.history_bytes = bytes;
+    }
+    Ok((report, transaction))
+}
+
+/// Remove what `policy` calls old, as of `now`
+pub fn collect(policy: &Policy, now: SystemTime) -> io::Result<Report> {
+    let (report, transaction) = prepare(policy, now)?;
+    transaction.apply()?;
+    Ok(report)
+}
+
+/// Run `collect` with the configured policy if `auto` is on and it has not
+/// run today. Errors are dropped: a full disk should not stop a lesson
+pub fn auto() -> Option<Report> {
+    let policy = Policy::configured();
+    if !policy.auto {
+        return None;
+    }
+    let stamp = dirs::cache_dir()?.join(STAMP);
+    let now = SystemTime::now();
+    let checked = fs::metadata(&stamp).and_then(|meta| meta.modified()).ok();
+    if checked.is_some_and(|checked| now.duration_since(checked).unwrap_or_default() < DAY) {
+        return None;
+    }
+    fs::create_dir_all(stamp.parent()?).ok()?;
+    fs::write(&stamp, "").ok()?;
+    collect(&policy, now).ok()
+}
+
+/// Indexes into `builds` to remove: everything older than the maximum age,
+/// then the oldest of the rest until they fit in the maximum size
+pub fn plan(builds: &[Item], policy: &Policy, now: SystemTime) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..builds.len()).collect();
+    order.sort_by_key(|&index| builds[index].modified);
+    let mut size: u64 = builds.iter().map(|item| item.bytes).sum();
+    let mut remove = Vec::new();
+    for index in order {
+        let item = &builds[index];
+        if age(item, now) > policy.max_age || size > policy.max_size {
+            size -= item.bytes;
+            remove.push(index);
+        }
+    }
+    remove
+}
+
+fn days_to_duration(days: u64) -> Duration {
+    Duration::from_secs(days.saturating_mul(DAY.as_secs()))
+}
+
+fn age(item: &Item, now: SystemTime) -> Duration {
+    now.duration_since(item.modified).unwrap_or_default()
+}
+
+/// Every build: the entries of each directory in the scratch directory
+fn builds() -> io::Result<Vec<Item>> {
+    let areas = match fs::read_dir(dirs::scratch_root()) {
+        Ok(areas) => areas,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut builds = Vec::new();
+    for area in areas {
+        let path = area?.path();
+        if path.is_dir() {
+            builds.extend(entries(&path)?);
+        }
+    }
+    Ok(builds)
+}
+
+/// The entries of `dir`, measured; none when it does not exist
+fn entries(dir: &Path) -> io::Result<Vec<Item>> {
+    let read = match fs::read_dir(dir) {
+        Ok(read) => read,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    let mut items = Vec::new();
+    for entry in read {
+        let path = entry?.path();
+        let (bytes, modified) = measure(&path)?;
+        items.push(Item {
+            path,
+            modified,
+            bytes,
+        });
+    }
+    Ok(items)
+}
+
+/// The size of `path` and everything in it, and the newest modification
+/// time among them
+fn measure(path: &Path) -> io::Result<(u64, SystemTime)> {
+    let meta = fs::symlink_metadata(path)?;
+    // A directory's own length is its entry table, not what it holds
+    let mut bytes = if meta.is_dir() { 0 } else { meta.len() };
+    let mut modified = meta.modified()?;
+    if meta.is_dir() {
+        for entry in fs::read_dir(path)? {
+            let (inner_bytes, inner_modified) = measure(&entry?.path())?;
+            bytes += inner_bytes;
+            modified = modified.max(inner_modified);
+        }
+    }
+    Ok((bytes, modified))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan() {
+        let now = SystemTime::UNIX_EPOCH + 400 * DAY;
+        let item = |name: &str, days_old: u32, mib: u64| Item {
+            path: PathBuf::from(name),
+            modified: now - days_old * DAY,
+            bytes: mib * 1024 * 1024,
+        };
+        let builds = [
+            item("exercises/ownership1", 40, 1),
+            item("wasi-target/release", 2, 300),
+            item("cross-target/debug", 10, 500),
+            item("exercises/vectors2", 1, 1),
+        ];
+        let policy = Policy {
+            max_size: 400 * 1024 * 1024,
+            ..Policy::default()
+        };
+        // Too old first, then the oldest of the rest until 400 MiB is enough
+        assert_eq!(plan(&builds, &policy, now), [0, 2]);
+        let roomy = Policy::default();
+        assert_eq!(plan(&builds, &roomy, now), [0]);
+    }
+}
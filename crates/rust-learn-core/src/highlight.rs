@@ -0,0 +1,416 @@
+//! Syntax Highlighting
+//!
+//! Rust source shown anywhere (`--show-source`, `quiz --predict`, the TUI's
+//! split view, `export --format html`) is colored here, so it looks the same
+//! in all of them. `lines` splits source into styled pieces; `ansi` and
+//! `html` render them for a terminal and a page.
+//!
+//! Built with the `syntect` feature, syntect does the work with one of its
+//! themes. Otherwise a small highlighter of our own (`tokens`) colors
+//! keywords, strings, comments, numbers, macros and type names. Both can be
+//! changed in config.toml:
+//!
+//! ```toml
+//! [highlight]
+//! # syntect's theme for the terminal (default: base16-ocean.dark, or
+//! # InspiredGitHub with --theme light)
+//! theme = "Solarized (dark)"
+//! # The built-in highlighter's colors, and the page's
+//! keyword = "#ff79c6"
+//! comment = "#6272a4"
+//! ```
+//!
+//! The built-in colors follow the theme (`theme::Theme`), and pages are
+//! always light; `--theme mono` uses bold and italics only.
+
+use crate::output::{self, Theme};
+use crate::settings;
+
+/// A 24-bit color
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    /// `#rrggbb`
+    pub fn parse(text: &str) -> Option<Rgb> {
+        let hex = text.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+        let channel = |at: usize| u8::from_str_radix(hex.get(at..at + 2)?, 16).ok();
+        Some(Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+}
+
+/// How one piece of source looks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Style {
+    pub color: Option<Rgb>,
+    pub bold: bool,
+    pub italic: bool,
+}
+
+/// What the built-in highlighter tells apart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Keyword,
+    String,
+    Comment,
+    Number,
+    Macro,
+    Type,
+    Plain,
+}
+
+impl Kind {
+    /// Its key under `[highlight]`
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Keyword => "keyword",
+            Kind::String => "string",
+            Kind::Comment => "comment",
+            Kind::Number => "number",
+            Kind::Macro => "macro",
+            Kind::Type => "type",
+            Kind::Plain => "plain",
+        }
+    }
+
+    /// Its look in `theme`, unless config.toml picks a color
+    fn style(self, theme: Theme) -> Style {
+        // Dark, light and high-contrast; the high-contrast colors are
+        // saturated mid-tones, readable on black and on white
+        let hex = |dark: &str, light: &str, high: &str| match theme {
+            Theme::Dark => Rgb::parse(dark),
+            Theme::Light => Rgb::parse(light),
+            Theme::HighContrast => Rgb::parse(high),
+            Theme::Mono => None,
+        };
+        let color = match self {
+            Kind::Keyword => hex("#c678dd", "#a626a4", "#af00d7"),
+            Kind::String => hex("#98c379", "#50a14f", "#008700"),
+            Kind::Comment => hex("#7f848e", "#a0a1a7", "#875f00"),
+            Kind::Number => hex("#d19a66", "#986801", "#d75f00"),
+            Kind::Macro => hex("#61afef", "#4078f2", "#005fff"),
+            Kind::Type => hex("#e5c07b", "#c18401", "#af0000"),
+            Kind::Plain => None,
+        };
+        let configured = settings::get()
+            .table("highlight")
+            .and_then(|table| table.str(self.name()))
+            .and_then(Rgb::parse);
+        let bold = match theme {
+            Theme::HighContrast => self == Kind::Keyword || self == Kind::Type,
+            Theme::Mono => self == Kind::Keyword,
+            Theme::Dark | Theme::Light => false,
+        };
+        Style {
+            color: if theme == Theme::Mono {
+                None
+            } else {
+                configured.or(color)
+            },
+            bold,
+            italic: self == Kind::Comment,
+        }
+    }
+}
+
+/// Keywords the built-in highlighter colors
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
+    "unsafe", "use", "where", "while",
+];
+
+/// `source` cut into pieces by the built-in highlighter. Together the pieces
+/// are `source` again
+pub fn tokens(source: &str) -> Vec<(Kind, &str)> {
+    let mut tokens: Vec<(Kind, &str)> = Vec::new();
+    let mut index = 0;
+    while let Some(c) = source[index..].chars().next() {
+        let rest = &source[index..];
+        let word = |keep: fn(char) -> bool| {
+            rest.char_indices()
+                .find(|&(_, c)| !keep(c))
+                .map_or(rest.len(), |(at, _)| at)
+        };
+        let (kind, length) = if rest.starts_with("//") {
+            (Kind::Comment, rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(body) = rest.strip_prefix("/*") {
+            (
+                Kind::Comment,
+                body.find("*/").map_or(rest.len(), |at| at + 4),
+            )
+        } else if c == '"' || rest.starts_with("r\"") || rest.starts_with("r#") {
+            (Kind::String, string_length(rest))
+        } else if let Some(length) = char_length(rest) {
+            (Kind::String, length)
+        } else if c.is_ascii_digit() {
+            let end = word(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+            (Kind::Number, end)
+        } else if c.is_alphabetic() || c == '_' {
+            let end = word(|c| c.is_alphanumeric() || c == '_');
+            if rest[end..].starts_with('!') {
+                (Kind::Macro, end + 1)
+            } else if KEYWORDS.contains(&&rest[..end]) {
+                (Kind::Keyword, end)
+            } else if c.is_uppercase() {
+                (Kind::Type, end)
+            } else {
+                (Kind::Plain, end)
+            }
+        } else {
+            (Kind::Plain, c.len_utf8())
+        };
+        match tokens.last_mut() {
+            // Runs of plain text stay in one piece
+            Some((Kind::Plain, text)) if kind == Kind::Plain => {
+                *text = &source[index - text.len()..index + length];
+            }
+            _ => tokens.push((kind, &rest[..length])),
+        }
+        index += length;
+    }
+    tokens
+}
+
+/// The length of the string literal `rest` starts with: `"..."` with
+/// escapes, or a raw `r"..."` / `r#"..."#`
+fn string_length(rest: &str) -> usize {
+    if let Some(raw) = rest.strip_prefix('r') {
+        let hashes = raw.len() - raw.trim_start_matches('#').len();
+        let open = 1 + hashes + 1;
+        let close = format!("\"{}", "#".repeat(hashes));
+        return rest
+            .get(open..)
+            .and_then(|body| body.find(&close))
+            .map_or(rest.len(), |at| open + at + close.len());
+    }
+    let bytes = rest.as_bytes();
+    let mut at = 1;
+    while at < bytes.len() {
+        match bytes[at] {
+            b'\\' => at += 2,
+            b'"' => return at + 1,
+            _ => at += 1,
+        }
+    }
+    rest.len()
+}
+
+/// The length of the char literal `rest` starts with (`'a'`, `'\n'`), or
+/// `None` when it is not one, like the lifetime `'a`
+fn char_length(rest: &str) -> Option<usize> {
+    let mut chars = rest.strip_prefix('\'')?.chars();
+    match (chars.next()?, chars.next()?) {
+        ('\\', _) => Some(3 + rest.get(3..)?.find('\'')? + 1),
+        (c, '\'') => Some(1 + c.len_utf8() + 1),
+        _ => None,
+    }
+}
+
+/// `source` a line at a time, each cut into styled pieces, for a terminal
+/// in `theme`
+pub fn lines(source: &str, theme: Theme) -> Vec<Vec<(Style, &str)>> {
+    styled(source, theme, false)
+}
+
+/// `lines`, or for a page, which ignores the terminal's syntect theme
+#[cfg_attr(not(feature = "syntect"), allow(unused_variables))]
+fn styled(source: &str, theme: Theme, page: bool) -> Vec<Vec<(Style, &str)>> {
+    #[cfg(feature = "syntect")]
+    if !matches!(theme, Theme::Mono | Theme::HighContrast)
+        && let Some(lines) = engine::lines(source, theme, page)
+    {
+        return lines;
+    }
+
+    let mut lines = vec![Vec::new()];
+    for (kind, text) in tokens(source) {
+        let style = kind.style(theme);
+        for (number, part) in text.split('\n').enumerate() {
+            if number > 0 {
+                lines.push(Vec::new());
+            }
+            if !part.is_empty() {
+                lines
+                    .last_mut()
+                    .expect("starts with one line")
+                    .push((style, part));
+            }
+        }
+    }
+    if source.ends_with('\n') {
+        lines.pop();
+    }
+    lines
+}
+
+/// `source` colored for the terminal, in the current theme; as it is without
+/// color
+pub fn ansi(source: &str) -> String {
+    let settings = output::settings();
+    if !settings.color {
+        return source.to_string();
+    }
+    let mut out = Vec::new();
+    for line in lines(source, settings.theme) {
+        let mut text = String::new();
+        for (style, part) in line {
+            let mut codes = Vec::new();
+            if style.bold {
+                codes.push("1".to_string());
+            }
+            if style.italic {
+                codes.push("3".to_string());
+            }
+            if let Some(Rgb(r, g, b)) = style.color {
+                codes.push(format!("38;2;{};{};{}", r, g, b));
+            }
+            if codes.is_empty() {
+                text.push_str(part);
+            } else {
+                text.push_str(&format!("\x1b[{}m{}\x1b[0m", codes.join(";"), part));
+            }
+        }
+        out.push(text);
+    }
+    out.join("\n")
+}
+
+/// `source` as HTML for a light page, with a `<span style="...">` around
+/// each colored piece
+pub fn html(source: &str) -> String {
+    let mut out = Vec::new();
+    for line in styled(source, Theme::Light, true) {
+        let mut text = String::new();
+        for (style, part) in line {
+            let mut css = Vec::new();
+            if let Some(Rgb(r, g, b)) = style.color {
+                css.push(format!("color: #{:02x}{:02x}{:02x}", r, g, b));
+            }
+            if style.bold {
+                css.push("font-weight: bold".to_string());
+            }
+            if style.italic {
+                css.push("font-style: italic".to_string());
+            }
+            if css.is_empty() {
+                text.push_str(&escape_html(part));
+            } else {
+                text.push_str(&format!(
+                    "<span style=\"{}\">{}</span>",
+                    css.join("; "),
+                    escape_html(part)
+                ));
+            }
+        }
+        out.push(text);
+    }
+    out.join("\n")
+}
+
+/// `text` safe to put inside HTML
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// syntect, with its bundled syntaxes and themes
+#[cfg(feature = "syntect")]
+mod engine {
+    use super::{Rgb, Style};
+    use crate::output::Theme;
+    use crate::settings;
+    use std::sync::OnceLock;
+    use syntect::easy::HighlightLines;
+    use syntect::highlighting::{FontStyle, ThemeSet};
+    use syntect::parsing::SyntaxSet;
+    use tracing::debug;
+
+    fn sets() -> &'static (SyntaxSet, ThemeSet) {
+        static SETS: OnceLock<(SyntaxSet, ThemeSet)> = OnceLock::new();
+        SETS.get_or_init(|| {
+            (
+                SyntaxSet::load_defaults_nonewlines(),
+                ThemeSet::load_defaults(),
+            )
+        })
+    }
+
+    /// `None` when the theme in config.toml is not one of syntect's
+    pub fn lines(source: &str, theme: Theme, page: bool) -> Option<Vec<Vec<(Style, &str)>>> {
+        let (syntaxes, themes) = sets();
+        let configured = settings::get()
+            .table("highlight")
+            .and_then(|table| table.str("theme"));
+        let name = match configured {
+            Some(name) if !page => name,
+            _ if theme == Theme::Light => "InspiredGitHub",
+            _ => "base16-ocean.dark",
+        };
+        let Some(theme) = themes.themes.get(name) else {
+            debug!(name, "unknown syntect theme");
+            return None;
+        };
+        let syntax = syntaxes.find_syntax_by_extension("rs")?;
+        let mut highlighter = HighlightLines::new(syntax, theme);
+        let mut lines = Vec::new();
+        for line in source.lines() {
+            let pieces = highlighter.highlight_line(line, syntaxes).ok()?;
+            lines.push(
+                pieces
+                    .into_iter()
+                    .map(|(style, text)| {
+                        let color = style.foreground;
+                        let style = Style {
+                            color: Some(Rgb(color.r, color.g, color.b)),
+                            bold: style.font_style.contains(FontStyle::BOLD),
+                            italic: style.font_style.contains(FontStyle::ITALIC),
+                        };
+                        (style, text)
+                    })
+                    .collect(),
+            );
+        }
+        Some(lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokens() {
+        let source = "let c = '<'; // x\nprintln!(\"{}\", r#\"a\"#, 'a, 1_000);";
+        assert_eq!(
+            tokens(source),
+            [
+                (Kind::Keyword, "let"),
+                (Kind::Plain, " c = "),
+                (Kind::String, "'<'"),
+                (Kind::Plain, "; "),
+                (Kind::Comment, "// x"),
+                (Kind::Plain, "\n"),
+                (Kind::Macro, "println!"),
+                (Kind::Plain, "("),
+                (Kind::String, "\"{}\""),
+                (Kind::Plain, ", "),
+                (Kind::String, "r#\"a\"#"),
+                (Kind::Plain, ", 'a, "),
+                (Kind::Number, "1_000"),
+                (Kind::Plain, ");"),
+            ]
+        );
+        assert_eq!(tokens("'\\''")[0], (Kind::String, "'\\''"));
+
+        #[cfg(not(feature = "syntect"))]
+        assert_eq!(
+            html("let s = \"<\";"),
+            "<span style=\"color: #a626a4\">let</span> s = \
+             <span style=\"color: #50a14f\">&quot;&lt;&quot;</span>;"
+        );
+    }
+}
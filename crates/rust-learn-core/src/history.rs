@@ -0,0 +1,301 @@
+//! Run History
+//!
+//! Every lesson or section run keeps a transcript: the lines the lesson
+//! printed, its prompts and what the learner typed, as they were shown.
+//! Transcripts are normalized (no colors, no trailing spaces, timings and
+//! addresses replaced by `<time>` and `<addr>`), so a different timing alone
+//! does not make two runs differ, then stored by content in the data
+//! directory:
+//!
+//! ```text
+//! history/objects/3f2a9c0d11b2e4f7.txt   one file per distinct transcript
+//! history/runs.log                       one line per run, oldest first:
+//! 2026-10-16T14:03:27Z vectors::vector_with_input 3f2a9c0d11b2e4f7
+//! ```
+//!
+//! A run's id is its transcript's FNV-1a hash, so a run that printed the
+//! same as an earlier one shares its file. `rust-learn history` lists the
+//! runs and `history diff` compares two of them. `cache gc` drops old runs
+//! (`prune`), and with them the transcripts no other run shares.
+
+use crate::dirs;
+use crate::progress::{format_timestamp, parse_timestamp};
+use crate::random::fnv1a;
+use crate::transaction::Transaction;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+/// The run being recorded, if any
+static TRANSCRIPT: Mutex<Option<String>> = Mutex::new(None);
+
+/// Start recording a new transcript, dropping one not taken by `finish`
+pub fn start() {
+    if let Ok(mut transcript) = TRANSCRIPT.lock() {
+        *transcript = Some(String::new());
+    }
+}
+
+/// Add `text` to the transcript, when one is being recorded
+pub fn record(text: &str) {
+    if let Ok(mut transcript) = TRANSCRIPT.lock()
+        && let Some(transcript) = transcript.as_mut()
+    {
+        transcript.push_str(text);
+    }
+}
+
+/// Stop recording and take what was recorded
+pub fn finish() -> Option<String> {
+    TRANSCRIPT
+        .lock()
+        .ok()
+        .and_then(|mut transcript| transcript.take())
+}
+
+/// One line of runs.log
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub time: SystemTime,
+    /// `lesson` or `lesson::section`
+    pub key: String,
+    /// The transcript's hash, 16 hex digits
+    pub id: String,
+}
+
+impl Run {
+    fn parse(line: &str) -> Option<Run> {
+        let mut fields = line.split_whitespace();
+        let time = parse_timestamp(fields.next()?)?;
+        let key = fields.next()?.to_string();
+        let id = fields.next()?.to_string();
+        Some(Run { time, key, id })
+    }
+
+    /// The lesson the run belongs to
+    pub fn lesson(&self) -> &str {
+        self.key.split("::").next().unwrap_or(&self.key)
+    }
+
+    /// The run as runs.log writes it, with its newline
+    fn line(&self) -> String {
+        format!("{} {} {}\n", format_timestamp(self.time), self.key, self.id)
+    }
+
+    /// The first 8 digits of the id, which is how runs are named
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(8)]
+    }
+}
+
+fn dir() -> io::Result<PathBuf> {
+    dirs::data_dir()
+        .map(|dir| dir.join("history"))
+        .ok_or_else(|| io::Error::other("no data directory"))
+}
+
+fn object_path(id: &str) -> io::Result<PathBuf> {
+    Ok(dir()?.join("objects").join(format!("{}.txt", id)))
+}
+
+/// Normalize `transcript`, store it unless it is already there, and add the
+/// run to runs.log
+pub fn save(key: &str, transcript: &str) -> io::Result<Run> {
+    let text = normalize(transcript);
+    let run = Run {
+        time: SystemTime::now(),
+        key: key.to_string(),
+        id: format!("{:016x}", fnv1a(text.as_bytes())),
+    };
+    let object = object_path(&run.id)?;
+    if !object.exists() {
+        if let Some(parent) = object.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&object, &text)?;
+    }
+    let mut log = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir()?.join("runs.log"))?;
+    log.write_all(run.line().as_bytes())?;
+    Ok(run)
+}
+
+/// Every run recorded, oldest first; a line that does not parse is skipped
+pub fn runs() -> io::Result<Vec<Run>> {
+    let text = match fs::read_to_string(dir()?.join("runs.log")) {
+        Ok(text) => text,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => return Err(error),
+    };
+    Ok(text.lines().filter_map(Run::parse).collect())
+}
+
+/// The latest run whose id starts with `prefix` (at least 4 digits)
+pub fn find(prefix: &str) -> Result<Run, String> {
+    if prefix.len() < 4 || !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(format!(
+            "'{}' is not a run id: give at least 4 of its hex digits, as `rust-learn history` shows them",
+            prefix
+        ));
+    }
+    let prefix = prefix.to_ascii_lowercase();
+    let runs = runs().map_err(|error| format!("cannot read the history: {}", error))?;
+    let matching: Vec<&Run> = runs
+        .iter()
+        .filter(|run| run.id.starts_with(&prefix))
+        .collect();
+    match matching.last() {
+        None => Err(format!("no run has an id starting with {}", prefix)),
+        Some(last) if matching.iter().any(|run| run.id != last.id) => Err(format!(
+            "{} is the start of more than one run's id; give more digits",
+            prefix
+        )),
+        Some(last) => Ok((*last).clone()),
+    }
+}
+
+/// Plan dropping the runs from before `cutoff` from runs.log, then the
+/// transcripts no run refers to any more. Returns the number of runs dropped
+/// and the bytes the transcripts take
+pub fn prune(cutoff: SystemTime, transaction: &mut Transaction) -> io::Result<(usize, u64)> {
+    let (old, kept): (Vec<Run>, Vec<Run>) = runs()?.into_iter().partition(|run| run.time < cutoff);
+    if !old.is_empty() {
+        let text: String = kept.iter().map(Run::line).collect();
+        transaction.write(dir()?.join("runs.log"), text);
+    }
+    let entries = match fs::read_dir(dir()?.join("objects")) {
+        Ok(entries) => entries,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok((old.len(), 0)),
+        Err(error) => return Err(error),
+    };
+    let mut freed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        let id = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .unwrap_or("");
+        if !kept.iter().any(|run| run.id == id) {
+            let bytes = fs::metadata(&path)?.len();
+            freed += bytes;
+            transaction.remove_sized(path, bytes);
+        }
+    }
+    Ok((old.len(), freed))
+}
+
+/// The normalized transcript of `run`
+pub fn load(run: &Run) -> io::Result<String> {
+    fs::read_to_string(object_path(&run.id)?)
+}
+
+/// Colors, trailing spaces and trailing blank lines removed, and what changes
+/// from run to run however the learner answers (`12.3ms`, `850ns`,
+/// `0x7ffd5a3c`) replaced by `<time>` and `<addr>`
+pub fn normalize(transcript: &str) -> String {
+    let mut text = String::new();
+    for line in strip_colors(transcript).lines() {
+        text.push_str(&mask(line.trim_end()));
+        text.push('\n');
+    }
+    while text.ends_with("\n\n") {
+        text.pop();
+    }
+    text
+}
+
+/// `text` without ANSI escape sequences like `\x1b[1;32m`
+fn strip_colors(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            // CSI sequences end at their first letter
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+const TIME_UNITS: [&str; 5] = ["ns", "µs", "us", "ms", "s"];
+
+/// `line` with durations and hex addresses masked
+fn mask(line: &str) -> String {
+    let mut masked = String::with_capacity(line.len());
+    let mut rest = line;
+    let mut after_word = false;
+    while let Some(c) = rest.chars().next() {
+        if !after_word && c.is_ascii_digit() {
+            let hex = rest.strip_prefix("0x").map_or(0, |digits| {
+                digits.chars().take_while(char::is_ascii_hexdigit).count()
+            });
+            if hex >= 6 {
+                masked.push_str("<addr>");
+                rest = &rest[2 + hex..];
+                after_word = true;
+                continue;
+            }
+            let number = number_length(rest);
+            let unit = TIME_UNITS.iter().find(|unit| {
+                rest[number..].starts_with(*unit)
+                    && !rest[number + unit.len()..]
+                        .starts_with(|c: char| c.is_alphanumeric() || c == '_')
+            });
+            if let Some(unit) = unit {
+                masked.push_str("<time>");
+                rest = &rest[number + unit.len()..];
+            } else {
+                masked.push_str(&rest[..number]);
+                rest = &rest[number..];
+            }
+            after_word = true;
+            continue;
+        }
+        masked.push(c);
+        after_word = c.is_alphanumeric() || c == '_' || c == '.';
+        rest = &rest[c.len_utf8()..];
+    }
+    masked
+}
+
+/// The length of the number `text` starts with: digits, maybe a fraction
+fn number_length(text: &str) -> usize {
+    let digits = |text: &str| text.bytes().take_while(u8::is_ascii_digit).count();
+    let whole = digits(text);
+    match text[whole..].strip_prefix('.') {
+        Some(fraction) if digits(fraction) > 0 => whole + 1 + digits(fraction),
+        _ => whole,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let transcript = "\x1b[1;32m1. Slices:\x1b[0m  \n\
+             took 12.3ms, then 850ns and 2s\n\
+             Box at 0x7ffd5a3c10, 0x10 is 16\n\
+             u8s: 3 sections, 10 items, v1.5\n\
+             Answer: 42\n\n\n";
+        assert_eq!(
+            normalize(transcript),
+            "1. Slices:\n\
+             took <time>, then <time> and <time>\n\
+             Box at <addr>, 0x10 is 16\n\
+             u8s: 3 sections, 10 items, v1.5\n\
+             Answer: 42\n"
+        );
+    }
+}
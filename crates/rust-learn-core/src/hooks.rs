@@ -0,0 +1,507 @@
+//! Event Hooks
+//!
+//! Finishing a lesson, a section, an exercise or a quiz can tell other
+//! tools about it.
+//! Each `[[hooks]]` table in config.toml names the events it wants and one
+//! action:
+//!
+//! ```toml
+//! [[hooks]]
+//! on = ["lesson", "quiz"]
+//! run = "notify-send rust-learn \"$RUST_LEARN_NAME done\""  # sh -c, JSON on stdin
+//! timeout = 5                                                # seconds, default 10
+//!
+//! [[hooks]]
+//! on = "lesson"
+//! webhook = "http://classroom.local:8080/progress"           # POSTs the JSON
+//!
+//! [[hooks]]
+//! on = ["lesson", "section", "exercise", "quiz"]
+//! csv = "/mnt/class/alice/log.csv"                           # appends a row
+//!
+//! [[hooks]]
+//! on = ["lesson", "quiz"]
+//! announce = "https://hooks.slack.com/services/T0/B0/XXXX"   # or a Discord one
+//! ```
+//!
+//! `announce` posts a sentence to a Slack or Discord incoming webhook instead
+//! of the JSON: "Alice completed Ownership", "Alice scored 9/10 on a quiz".
+//! The name comes from `[profile] name`, or else the login name. `service =
+//! "slack"` or `"discord"` says which, when the URL does not. Webhooks need
+//! the `http` feature.
+//!
+//! Every action gets the event as one JSON object:
+//! `{"event":"lesson","name":"loops","time":"2026-10-16T14:03:27Z","seconds":1.2}`,
+//! plus `"correct"` and `"asked"` for a quiz, and `"path"`, the file, for an
+//! exercise `verify` passed for the first time (`ownership1`, or
+//! `week-3/ownership1` from a pack).
+//!
+//! A hook runs on its own thread and has `timeout` seconds. One that fails,
+//! hangs or panics gets a dim note on stderr and nothing else: the lesson is
+//! still complete and the exit code does not change.
+
+use crate::json::Value;
+use crate::progress::format_timestamp;
+use crate::settings::{self, Table};
+use crate::{output, watchdog};
+use std::fs::{self, OpenOptions};
+use std::io::{self, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{OnceLock, mpsc};
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tracing::debug;
+
+/// Set (to anything) to run no hooks, as in the child processes of the
+/// self-test and `export`, which are not the learner finishing anything
+pub const DISABLE_VAR: &str = "RUST_LEARN_NO_HOOKS";
+
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The first row of a new CSV file
+const CSV_HEADER: &str = "time,event,name,seconds,correct,asked";
+
+/// What was finished
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventKind {
+    Lesson,
+    Section,
+    Exercise,
+    Quiz,
+}
+
+impl EventKind {
+    /// The name `on` uses
+    pub fn name(self) -> &'static str {
+        match self {
+            EventKind::Lesson => "lesson",
+            EventKind::Section => "section",
+            EventKind::Exercise => "exercise",
+            EventKind::Quiz => "quiz",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Event {
+    pub kind: EventKind,
+    /// A lesson, `lesson::section`, an exercise's id, or `quiz`
+    pub name: String,
+    /// What an announcement calls it, like `Ownership` or `Ownership: Slices`
+    pub title: String,
+    pub time: SystemTime,
+    pub elapsed: Duration,
+    /// Correct answers and questions asked, for a quiz
+    pub score: Option<(usize, usize)>,
+    /// The file, for an exercise
+    pub path: Option<String>,
+}
+
+impl Event {
+    /// `name` finished just now, after `elapsed`
+    pub fn new(kind: EventKind, name: &str, elapsed: Duration) -> Event {
+        Event {
+            kind,
+            name: name.to_string(),
+            title: name.to_string(),
+            time: SystemTime::now(),
+            elapsed,
+            score: None,
+            path: None,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        let mut entries = vec![
+            ("event".to_string(), self.kind.name().into()),
+            ("name".to_string(), self.name.as_str().into()),
+            (
+                "time".to_string(),
+                format_timestamp(self.time).as_str().into(),
+            ),
+            ("seconds".to_string(), Value::Number(round(self.elapsed))),
+        ];
+        if let Some((correct, asked)) = self.score {
+            entries.push(("correct".to_string(), Value::Number(correct as f64)));
+            entries.push(("asked".to_string(), Value::Number(asked as f64)));
+        }
+        if let Some(path) = &self.path {
+            entries.push(("path".to_string(), path.as_str().into()));
+        }
+        Value::Object(entries)
+    }
+
+    /// One sentence about it, for a chat channel
+    pub fn announcement(&self, who: &str) -> String {
+        match (self.kind, self.score) {
+            (EventKind::Quiz, Some((correct, asked))) => {
+                format!("{} scored {}/{} on a quiz", who, correct, asked)
+            }
+            _ => format!("{} completed {}", who, self.title),
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        let (correct, asked) = match self.score {
+            Some((correct, asked)) => (correct.to_string(), asked.to_string()),
+            None => Default::default(),
+        };
+        format!(
+            "{},{},{},{},{},{}\n",
+            format_timestamp(self.time),
+            self.kind.name(),
+            self.name,
+            round(self.elapsed),
+            correct,
+            asked
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Action {
+    Run(String),
+    Webhook(String),
+    Csv(String),
+    Announce(String, Service),
+}
+
+/// The chat service behind an `announce` URL; each wants its own JSON
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Service {
+    Slack,
+    Discord,
+}
+
+impl Service {
+    /// From `service`, or else guessed from the URL
+    fn new(name: Option<&str>, url: &str) -> Result<Service, String> {
+        match name {
+            Some("slack") => Ok(Service::Slack),
+            Some("discord") => Ok(Service::Discord),
+            Some(other) => Err(format!("unknown service '{}' (try slack, discord)", other)),
+            None if url.contains("discord.com") || url.contains("discordapp.com") => {
+                Ok(Service::Discord)
+            }
+            None => Ok(Service::Slack),
+        }
+    }
+
+    fn body(self, message: &str) -> Value {
+        let key = match self {
+            Service::Slack => "text",
+            Service::Discord => "content",
+        };
+        Value::Object(vec![(key.to_string(), message.into())])
+    }
+}
+
+impl Action {
+    fn describe(&self) -> String {
+        match self {
+            Action::Run(command) => format!("run `{}`", command),
+            Action::Webhook(url) => format!("webhook {}", url),
+            Action::Csv(path) => format!("csv {}", path),
+            Action::Announce(url, _) => format!("announce {}", url),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Hook {
+    on: Vec<String>,
+    action: Action,
+    timeout: Duration,
+}
+
+/// The hook a `[[hooks]]` table describes
+fn hook(table: &Table) -> Result<Hook, String> {
+    let on = table
+        .strings("on")
+        .ok_or("needs `on`, the events to run for")?;
+    for event in &on {
+        if ![
+            EventKind::Lesson,
+            EventKind::Section,
+            EventKind::Exercise,
+            EventKind::Quiz,
+        ]
+        .iter()
+        .any(|kind| kind.name() == *event)
+        {
+            return Err(format!(
+                "unknown event '{}' (try lesson, section, exercise, quiz)",
+                event
+            ));
+        }
+    }
+    let actions: Vec<Action> = [
+        table
+            .str("run")
+            .map(|command| Action::Run(command.to_string())),
+        table
+            .str("webhook")
+            .map(|url| Action::Webhook(url.to_string())),
+        table.str("csv").map(|path| Action::Csv(path.to_string())),
+        match table.str("announce") {
+            Some(url) => Some(Action::Announce(
+                url.to_string(),
+                Service::new(table.str("service"), url)?,
+            )),
+            None => None,
+        },
+    ]
+    .into_iter()
+    .flatten()
+    .collect();
+    let [action] = <[Action; 1]>::try_from(actions)
+        .map_err(|_| "needs exactly one of `run`, `webhook`, `csv` or `announce`")?;
+    let timeout = match table.integer("timeout") {
+        Some(seconds) => {
+            Duration::from_secs(u64::try_from(seconds).map_err(|_| "`timeout` cannot be negative")?)
+        }
+        None => DEFAULT_TIMEOUT,
+    };
+    Ok(Hook {
+        on: on.into_iter().map(String::from).collect(),
+        action,
+        timeout,
+    })
+}
+
+/// Run every configured hook that wants `event`, each for at most its
+/// timeout; problems are noted on stderr and otherwise ignored
+pub fn fire(event: &Event) {
+    if std::env::var_os(DISABLE_VAR).is_some() {
+        return;
+    }
+    for (number, hook) in configured() {
+        if !hook.on.iter().any(|on| on == event.kind.name()) {
+            continue;
+        }
+        if let Err(error) = run_isolated(hook, event) {
+            note(&format!(
+                "Hook {} ({}) failed: {}",
+                number,
+                hook.action.describe(),
+                error
+            ));
+        }
+    }
+}
+
+/// The hooks in config.toml, numbered from 1, read once; one that makes no
+/// sense is noted the first time and left out
+fn configured() -> &'static [(usize, Hook)] {
+    static HOOKS: OnceLock<Vec<(usize, Hook)>> = OnceLock::new();
+    HOOKS.get_or_init(|| {
+        let mut hooks = Vec::new();
+        for (index, table) in settings::get().tables("hooks").enumerate() {
+            match hook(table) {
+                Ok(hook) => hooks.push((index + 1, hook)),
+                Err(error) => note(&format!(
+                    "Skipping hook {} in config.toml: {}",
+                    index + 1,
+                    error
+                )),
+            }
+        }
+        hooks
+    })
+}
+
+/// Run `hook` on a thread of its own and wait up to its timeout. A hook that
+/// is still going after that is left behind (a command is killed)
+fn run_isolated(hook: &Hook, event: &Event) -> Result<(), String> {
+    let _span = tracing::debug_span!("hook", action = %hook.action.describe()).entered();
+    let start = Instant::now();
+    let (sender, receiver) = mpsc::channel();
+    let (action, timeout, event) = (hook.action.clone(), hook.timeout, event.clone());
+    thread::Builder::new()
+        .name("hook".to_string())
+        .spawn(move || {
+            let result = run(&action, &event, timeout).map_err(|error| error.to_string());
+            let _ = sender.send(result);
+        })
+        .map_err(|error| error.to_string())?;
+
+    // Waiting on a hook is not the lesson hanging
+    let _paused = watchdog::pause();
+    // A little longer than the hook's own deadline, so it can report it
+    let result = match receiver.recv_timeout(timeout + Duration::from_millis(500)) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            Err(format!("no answer after {}s", timeout.as_secs()))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err("it panicked".to_string()),
+    };
+    debug!(elapsed = ?start.elapsed(), ok = result.is_ok(), "hook finished");
+    result
+}
+
+fn run(action: &Action, event: &Event, timeout: Duration) -> io::Result<()> {
+    let json = event.to_json().to_string();
+    match action {
+        Action::Run(command) => run_command(command, event, &json, timeout),
+        Action::Webhook(url) => post(url, &json, timeout),
+        Action::Csv(path) => append_csv(path, event),
+        Action::Announce(url, service) => {
+            let body = service.body(&event.announcement(&settings::learner()));
+            post(url, &body.to_string(), timeout)
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+fn post(url: &str, json: &str, timeout: Duration) -> io::Result<()> {
+    crate::http::post(url, json, timeout)
+}
+
+#[cfg(not(feature = "http"))]
+fn post(_url: &str, _json: &str, _timeout: Duration) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "this rust-learn was built without the `http` feature",
+    ))
+}
+
+/// `sh -c command` (`cmd /C` on Windows) with the event on stdin and in
+/// `RUST_LEARN_EVENT`, `RUST_LEARN_NAME` and `RUST_LEARN_JSON`, and an
+/// exercise's file in `RUST_LEARN_PATH`
+fn run_command(command: &str, event: &Event, json: &str, timeout: Duration) -> io::Result<()> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    let mut process = Command::new(shell);
+    process
+        .args([flag, command])
+        .env("RUST_LEARN_EVENT", event.kind.name())
+        .env("RUST_LEARN_NAME", &event.name)
+        .env("RUST_LEARN_JSON", json);
+    if let Some(path) = &event.path {
+        process.env("RUST_LEARN_PATH", path);
+    }
+    let mut child = process
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that does not read its input closes the pipe early
+        let _ = writeln!(stdin, "{}", json);
+    }
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            if status.success() {
+                return Ok(());
+            }
+            let mut stderr = String::new();
+            if let Some(mut pipe) = child.stderr.take() {
+                pipe.read_to_string(&mut stderr)?;
+            }
+            let first = stderr.lines().next().unwrap_or_default();
+            return Err(io::Error::other(
+                format!("{} {}", status, first).trim_end().to_string(),
+            ));
+        }
+        if Instant::now() >= deadline {
+            child.kill()?;
+            child.wait()?;
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("killed after {}s", timeout.as_secs()),
+            ));
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+}
+
+/// Add a row for `event`, writing the header first into a new file
+fn append_csv(path: &str, event: &Event) -> io::Result<()> {
+    let is_new = fs::metadata(path)
+        .map(|meta| meta.len() == 0)
+        .unwrap_or(true);
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    let mut text = String::new();
+    if is_new {
+        text.push_str(CSV_HEADER);
+        text.push('\n');
+    }
+    text.push_str(&event.csv_row());
+    file.write_all(text.as_bytes())
+}
+
+/// Seconds, to the millisecond
+fn round(elapsed: Duration) -> f64 {
+    (elapsed.as_secs_f64() * 1000.0).round() / 1000.0
+}
+
+fn note(message: &str) {
+    eprintln!("{}", output::dim(message));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::settings::Settings;
+    use std::time::UNIX_EPOCH;
+
+    #[test]
+    fn test_hooks_from_settings() {
+        let settings = Settings::parse(
+            "[[hooks]]\non = [\"lesson\", \"quiz\"]\nrun = \"true\"\ntimeout = 2\n\
+             [[hooks]]\non = \"lesson\"\nwebhook = \"http://x\"\ncsv = \"log.csv\"\n\
+             [[hooks]]\non = \"exercise\"\ncsv = \"log.csv\"\n\
+             [[hooks]]\non = \"exercises\"\ncsv = \"log.csv\"\n",
+        )
+        .unwrap();
+        let hooks: Vec<_> = settings.tables("hooks").map(hook).collect();
+        assert_eq!(
+            hooks[0],
+            Ok(Hook {
+                on: vec!["lesson".to_string(), "quiz".to_string()],
+                action: Action::Run("true".to_string()),
+                timeout: Duration::from_secs(2),
+            })
+        );
+        assert_eq!(
+            hooks[1],
+            Err("needs exactly one of `run`, `webhook`, `csv` or `announce`".to_string())
+        );
+        assert_eq!(
+            hooks[2].as_ref().map(|hook| hook.on.clone()),
+            Ok(vec!["exercise".to_string()])
+        );
+        assert!(hooks[3].is_err());
+        let discord = "https://discord.com/api/webhooks/1/x";
+        assert_eq!(
+            Service::new(None, discord).map(|service| service.body("Hi").to_string()),
+            Ok(r#"{"content":"Hi"}"#.to_string())
+        );
+        assert_eq!(Service::new(Some("slack"), discord), Ok(Service::Slack));
+
+        let mut event = Event::new(EventKind::Quiz, "quiz", Duration::from_millis(1500));
+        event.time = UNIX_EPOCH;
+        event.score = Some((4, 5));
+        assert_eq!(
+            event.to_json().to_string(),
+            r#"{"event":"quiz","name":"quiz","time":"1970-01-01T00:00:00Z","seconds":1.5,"correct":4,"asked":5}"#
+        );
+        assert_eq!(event.csv_row(), "1970-01-01T00:00:00Z,quiz,quiz,1.5,4,5\n");
+        assert_eq!(event.announcement("Alice"), "Alice scored 4/5 on a quiz");
+        event = Event::new(EventKind::Exercise, "week-3/ownership1", Duration::ZERO);
+        event.time = UNIX_EPOCH;
+        event.path = Some("exercises/ownership/ownership1.rs".to_string());
+        assert_eq!(
+            event.to_json().to_string(),
+            r#"{"event":"exercise","name":"week-3/ownership1","time":"1970-01-01T00:00:00Z","seconds":0,"path":"exercises/ownership/ownership1.rs"}"#
+        );
+        event = Event::new(EventKind::Lesson, "ownership", Duration::ZERO);
+        event.title = "Ownership".to_string();
+        assert_eq!(event.announcement("Alice"), "Alice completed Ownership");
+    }
+}
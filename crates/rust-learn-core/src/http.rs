@@ -0,0 +1,184 @@
+//! HTTP POSTs
+//!
+//! Webhook hooks send one JSON body and only care whether the server took it,
+//! and the remote build backend (`remote`) reads the answer a line at a time
+//! as it comes. That needs very little HTTP: a plain `http://` URL is spoken
+//! to over a `TcpStream` here, and `https://` is handed to `curl`, which
+//! knows about certificates. Built only with the `http` feature (on by
+//! default in the CLI).
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// POST `json` to an `http://` URL; `https://` goes through curl, which knows
+/// about certificates
+pub fn post(url: &str, json: &str, timeout: Duration) -> io::Result<()> {
+    if url.starts_with("https://") {
+        let seconds = timeout.as_secs().max(1).to_string();
+        let args = [
+            "-fsS",
+            "-m",
+            &seconds,
+            "-H",
+            "Content-Type: application/json",
+        ];
+        let mut child = Command::new("curl")
+            .args(args)
+            .args(["--data-binary", "@-", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(json.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(stderr.trim().to_string()));
+    }
+
+    let mut stream = connect(url, timeout, json)?;
+    let mut reply = String::new();
+    stream.read_to_string(&mut reply)?;
+    check_status(reply.lines().next().unwrap_or_default())
+}
+
+/// POST `json` and hand each line of the answer to `on_line` as it arrives,
+/// for servers that stream their reply
+pub fn post_lines(
+    url: &str,
+    json: &str,
+    timeout: Duration,
+    on_line: &mut dyn FnMut(&str),
+) -> io::Result<()> {
+    if url.starts_with("https://") {
+        let seconds = timeout.as_secs().max(1).to_string();
+        let args = [
+            "-fsS",
+            "-N",
+            "-m",
+            &seconds,
+            "-H",
+            "Content-Type: application/json",
+        ];
+        let mut child = Command::new("curl")
+            .args(args)
+            .args(["--data-binary", "@-", url])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(json.as_bytes())?;
+        }
+        if let Some(stdout) = child.stdout.take() {
+            for line in BufReader::new(stdout).lines() {
+                on_line(&line?);
+            }
+        }
+        let output = child.wait_with_output()?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(stderr.trim().to_string()));
+    }
+
+    let mut reader = BufReader::new(connect(url, timeout, json)?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    check_status(line.trim_end())?;
+    let mut chunked = false;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            chunked |= name.trim().eq_ignore_ascii_case("transfer-encoding")
+                && value.trim().eq_ignore_ascii_case("chunked");
+        }
+    }
+    if !chunked {
+        for line in reader.lines() {
+            on_line(&line?);
+        }
+        return Ok(());
+    }
+    // A streaming server sends its lines in chunks, which need not end on
+    // a line
+    let mut pending = Vec::new();
+    loop {
+        line.clear();
+        reader.read_line(&mut line)?;
+        let size = line.split(';').next().unwrap_or_default().trim();
+        let size = usize::from_str_radix(size, 16)
+            .map_err(|_| io::Error::other(format!("bad chunk size '{}'", size)))?;
+        if size == 0 {
+            break;
+        }
+        let mut chunk = vec![0; size + 2];
+        reader.read_exact(&mut chunk)?;
+        pending.extend_from_slice(&chunk[..size]);
+        while let Some(end) = pending.iter().position(|&byte| byte == b'\n') {
+            let text: Vec<u8> = pending.drain(..=end).collect();
+            on_line(String::from_utf8_lossy(&text).trim_end());
+        }
+    }
+    if !pending.is_empty() {
+        on_line(String::from_utf8_lossy(&pending).trim_end());
+    }
+    Ok(())
+}
+
+/// Connect to an `http://` URL and send the POST
+fn connect(url: &str, timeout: Duration, json: &str) -> io::Result<TcpStream> {
+    let rest = url.strip_prefix("http://").ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "the URL must start with http:// or https://",
+        )
+    })?;
+    let (host, path) = match rest.find('/') {
+        Some(index) => (&rest[..index], &rest[index..]),
+        None => (rest, "/"),
+    };
+    let address = if host.contains(':') {
+        host.to_string()
+    } else {
+        format!("{}:80", host)
+    };
+    let address = address.to_socket_addrs()?.next().ok_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, format!("no address for {}", host))
+    })?;
+
+    let mut stream = TcpStream::connect_timeout(&address, timeout)?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+    write!(
+        stream,
+        "POST {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: rust-learn\r\n\
+         Content-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        path,
+        host,
+        json.len(),
+        json
+    )?;
+    Ok(stream)
+}
+
+/// An error unless the status line is a 2xx one
+fn check_status(status: &str) -> io::Result<()> {
+    match status.split_whitespace().nth(1) {
+        Some(code) if code.starts_with('2') => Ok(()),
+        _ => Err(io::Error::other(format!(
+            "the server answered '{}'",
+            status
+        ))),
+    }
+}
@@ -0,0 +1,162 @@
+//! Translated Lesson Text
+//!
+//! Lessons are written in English. With `--lang bn`, the CLI loads a
+//! `Catalog` of translations (embedded in the content crate) and `output`
+//! looks up what lessons print: every line of prose (`explain!`, `detail!`,
+//! `deep!`) and every heading. Anything else a lesson prints, which is the
+//! output of its code, is never translated. A line with no translation is
+//! printed in English.
+//!
+//! A catalog pairs each English line, exactly as the lesson prints it
+//! without its indentation, with its translation:
+//!
+//! ```text
+//! # variables
+//! en: 1. Mutability:
+//! bn: ১. পরিবর্তনশীলতা:
+//! ```
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// English lines and their translations
+#[derive(Debug, Default)]
+pub struct Catalog {
+    lang: String,
+    lines: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Read `en:`/`<lang>:` pairs; blank lines and `#` comments are skipped
+    pub fn parse(lang: &str, text: &str) -> Result<Catalog, String> {
+        let mut catalog = Catalog {
+            lang: lang.to_string(),
+            lines: HashMap::new(),
+        };
+        let translated = format!("{}:", lang);
+        let mut english: Option<(usize, &str)> = None;
+        for (index, line) in text.lines().enumerate() {
+            let number = index + 1;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(text) = line.strip_prefix("en:") {
+                if let Some((first, _)) = english {
+                    return Err(format!(
+                        "line {}: line {} has no translation",
+                        number, first
+                    ));
+                }
+                english = Some((number, text.trim()));
+            } else if let Some(text) = line.strip_prefix(translated.as_str()) {
+                let Some((_, source)) = english.take() else {
+                    return Err(format!(
+                        "line {}: a translation needs an en: line above",
+                        number
+                    ));
+                };
+                if catalog
+                    .lines
+                    .insert(source.to_string(), text.trim().to_string())
+                    .is_some()
+                {
+                    return Err(format!("line {}: '{}' is translated twice", number, source));
+                }
+            } else {
+                return Err(format!(
+                    "line {}: expected `en:` or `{}` at the start",
+                    number, translated
+                ));
+            }
+        }
+        match english {
+            Some((number, _)) => Err(format!("line {}: no translation follows", number)),
+            None => Ok(catalog),
+        }
+    }
+
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// `text` with each line translated where the catalog has it, keeping the
+    /// line's indentation
+    pub fn translate<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut translated = false;
+        let lines: Vec<Cow<str>> = text
+            .split('\n')
+            .map(|line| {
+                let body = line.trim_start();
+                match self.lines.get(body.trim_end()) {
+                    Some(translation) => {
+                        translated = true;
+                        let indent = &line[..line.len() - body.len()];
+                        Cow::Owned(format!("{}{}", indent, translation))
+                    }
+                    None => Cow::Borrowed(line),
+                }
+            })
+            .collect();
+        if translated {
+            Cow::Owned(lines.join("\n"))
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Translate lesson text with `catalog` from now on; later calls are ignored
+pub fn init(catalog: Catalog) {
+    let _ = CATALOG.set(catalog);
+}
+
+/// The language lessons are shown in: `en` until `init`
+pub fn lang() -> &'static str {
+    CATALOG.get().map_or("en", Catalog::lang)
+}
+
+/// `text` translated, or as it is when there is no catalog
+pub fn translate(text: &str) -> Cow<'_, str> {
+    match CATALOG.get() {
+        Some(catalog) => catalog.translate(text),
+        None => Cow::Borrowed(text),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog() {
+        let catalog = Catalog::parse(
+            "bn",
+            "# variables\nen: 1. Mutability:\nbn: ১. পরিবর্তনশীলতা:\n\nen: x can change\nbn: x বদলাতে পারে\n",
+        )
+        .unwrap();
+        assert_eq!(catalog.len(), 2);
+        assert_eq!(
+            catalog.translate("\n1. Mutability:\n   x can change\nx is 5"),
+            "\n১. পরিবর্তনশীলতা:\n   x বদলাতে পারে\nx is 5"
+        );
+        assert!(matches!(catalog.translate("x is 5"), Cow::Borrowed(_)));
+
+        assert_eq!(
+            Catalog::parse("bn", "en: one\nen: two\n").unwrap_err(),
+            "line 2: line 1 has no translation"
+        );
+        assert!(Catalog::parse("bn", "bn: orphan\n").is_err());
+        assert!(Catalog::parse("bn", "en: one\n").is_err());
+    }
+}
@@ -0,0 +1,320 @@
+/// Input helpers shared by the menu and interactive lessons
+///
+/// These follow `prompt_input` and `validated_input` from user_input.rs, with one
+/// difference: end of input (Ctrl-D or a closed pipe) is reported as `None`
+/// instead of being read as an endless stream of empty lines.
+///
+/// Lessons that read stdin go through `lesson_lines`, which falls back to
+/// sample input when the run is not interactive (`--non-interactive`, or
+/// stdin is a pipe, a file or CI), so they never block waiting for a user.
+///
+/// `read_key` reads a single key press without waiting for Enter, by putting
+/// the terminal in raw mode with `stty` for as long as it waits. Ctrl-C then
+/// comes in as a key, not a signal, so the terminal is restored before it
+/// exits the way the signal would, instead of being left without echo.
+///
+/// `prompt_input_until` waits for a line only until a deadline (the timed
+/// quiz). From its first call, a thread reads stdin and hands lines over, so
+/// the wait can end without one; later prompts read from that thread too.
+use crate::events;
+use crate::history;
+use crate::json::Value;
+use crate::output;
+use crate::watchdog;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Mutex, OnceLock, mpsc};
+use std::thread;
+use std::time::Instant;
+
+static INTERACTIVE: OnceLock<bool> = OnceLock::new();
+
+/// Decide once whether lessons read stdin; later calls are ignored
+pub fn init(non_interactive: bool) {
+    let _ = INTERACTIVE.set(!non_interactive && io::stdin().is_terminal());
+}
+
+/// Whether lessons should read stdin (detected from stdin before `init`)
+pub fn interactive() -> bool {
+    *INTERACTIVE.get_or_init(|| io::stdin().is_terminal())
+}
+
+/// Print a prompt and read one trimmed line, or `None` at end of input
+pub fn prompt_input(prompt: &str) -> Option<String> {
+    show_prompt(prompt);
+
+    let _paused = watchdog::pause();
+    let line = match LINES.get() {
+        // Once the reader thread has stdin, lines come from it
+        Some(lines) => lock(lines).recv().ok(),
+        None => {
+            let mut input = String::new();
+            match io::stdin().read_line(&mut input) {
+                Ok(0) | Err(_) => None,
+                Ok(_) => Some(input),
+            }
+        }
+    };
+    let line = line.map(|line| line.trim().to_string());
+    if let Some(line) = &line {
+        input_event(line, false);
+    }
+    line
+}
+
+/// A prompt's time ran out before a line came
+#[derive(Debug, PartialEq)]
+pub struct TimedOut;
+
+/// Lines of stdin, read on a thread of their own from the first prompt that
+/// has a time limit on
+static LINES: OnceLock<Mutex<mpsc::Receiver<String>>> = OnceLock::new();
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// `prompt_input` that gives up at `deadline`. The line being typed then is
+/// not lost: the next prompt gets it
+pub fn prompt_input_until(prompt: &str, deadline: Instant) -> Result<Option<String>, TimedOut> {
+    show_prompt(prompt);
+
+    let lines = LINES.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in io::stdin().lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+        Mutex::new(receiver)
+    });
+    let _paused = watchdog::pause();
+    let wait = deadline.saturating_duration_since(Instant::now());
+    let line = match lock(lines).recv_timeout(wait) {
+        Ok(line) => Some(line.trim().to_string()),
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            // End the prompt's line
+            println!();
+            return Err(TimedOut);
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => None,
+    };
+    if let Some(line) = &line {
+        input_event(line, false);
+    }
+    Ok(line)
+}
+
+/// Print `prompt` without ending the line, or stream it as a `prompt` event
+fn show_prompt(prompt: &str) {
+    history::record(prompt);
+    if events::enabled() {
+        events::emit("prompt", vec![("text", prompt.into())]);
+        return;
+    }
+    print!("{}", prompt);
+    io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// An `input` event for a line read, or taken from sample input
+fn input_event(line: &str, sample: bool) {
+    if !sample {
+        history::record(&format!("{}\n", line));
+    }
+    events::emit(
+        "input",
+        vec![("text", line.into()), ("sample", Value::Bool(sample))],
+    );
+}
+
+/// The terminal without line buffering, echo or Ctrl-C's signal, until
+/// dropped
+struct RawMode {
+    saved: String,
+}
+
+impl RawMode {
+    /// `None` when stdin is not a terminal `stty` can change
+    fn enable() -> Option<RawMode> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+        Some(RawMode {
+            saved: saved.trim().to_string(),
+        })
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&[self.saved.as_str()]);
+    }
+}
+
+/// The terminal's width in columns: `$COLUMNS`, or else what `stty size`
+/// reports; `None` without a terminal to ask
+pub fn terminal_width() -> Option<usize> {
+    if let Some(columns) = std::env::var("COLUMNS")
+        .ok()
+        .and_then(|text| text.parse().ok())
+    {
+        return Some(columns);
+    }
+    let size = stty(&["size"])?;
+    size.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Run `stty` on our terminal, returning what it printed
+fn stty(args: &[&str]) -> Option<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Print a prompt and read one key press (`'\n'` for Enter), or `None` at
+/// end of input. Without a terminal to put in raw mode, the first character
+/// of a line is read instead
+pub fn read_key(prompt: &str) -> Option<char> {
+    show_prompt(prompt);
+    let key = key_press();
+    if let Some(key) = key {
+        input_event(&key.to_string(), false);
+    }
+    key
+}
+
+fn key_press() -> Option<char> {
+    let _paused = watchdog::pause();
+    let Some(raw) = RawMode::enable() else {
+        let mut line = String::new();
+        return match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => None,
+            Ok(_) => Some(line.trim().chars().next().unwrap_or('\n')),
+        };
+    };
+    // A key can send more than one byte (an arrow key sends three); take
+    // them all, so the rest are not read as keys of their own
+    let mut stdin = io::stdin().lock();
+    let key = match stdin.fill_buf() {
+        Ok([]) | Err(_) => None,
+        // Ctrl-C: exit as SIGINT would, with the terminal back as it was
+        Ok([3, ..]) => {
+            drop(raw);
+            println!();
+            std::process::exit(130);
+        }
+        // Ctrl-D
+        Ok([4, ..]) => None,
+        Ok([b'\r' | b'\n', ..]) => Some('\n'),
+        Ok(bytes) => Some(char::from(bytes[0])),
+    };
+    let read = stdin.fill_buf().map_or(0, <[u8]>::len);
+    stdin.consume(read);
+    // The key was not echoed; end the prompt's line
+    if !events::enabled() {
+        println!();
+    }
+    key
+}
+
+pub enum MenuChoice {
+    /// Zero-based index of the chosen item
+    Item(usize),
+    Quit,
+}
+
+/// Keep asking until the user picks a number between 1 and `count` or quits
+pub fn validated_choice(prompt: &str, count: usize) -> MenuChoice {
+    choice(prompt, count, None).unwrap_or(MenuChoice::Quit)
+}
+
+/// `validated_choice` that gives up at `deadline`
+pub fn choice_until(prompt: &str, count: usize, deadline: Instant) -> Result<MenuChoice, TimedOut> {
+    choice(prompt, count, Some(deadline))
+}
+
+fn choice(prompt: &str, count: usize, deadline: Option<Instant>) -> Result<MenuChoice, TimedOut> {
+    loop {
+        let input = match deadline {
+            Some(deadline) => prompt_input_until(prompt, deadline)?,
+            None => prompt_input(prompt),
+        };
+        let Some(input) = input else {
+            return Ok(MenuChoice::Quit);
+        };
+
+        if input.eq_ignore_ascii_case("quit") || input.eq_ignore_ascii_case("q") {
+            return Ok(MenuChoice::Quit);
+        }
+
+        match input.parse::<usize>() {
+            Ok(num) if (1..=count).contains(&num) => return Ok(MenuChoice::Item(num - 1)),
+            Ok(_) => println!("Number must be between 1 and {}", count),
+            Err(_) => println!("Please enter a number or 'quit'"),
+        }
+    }
+}
+
+/// Headings of the sections that used sample input instead of the user's
+static SAMPLE_INPUT: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+/// The headings (`5. Reading Numbers:`) of the sections that have used
+/// sample input since the last call, because the run was not interactive
+pub fn take_sample_input_headings() -> Vec<String> {
+    SAMPLE_INPUT
+        .lock()
+        .map(|mut headings| std::mem::take(&mut *headings))
+        .unwrap_or_default()
+}
+
+/// Lines of input for a lesson; ends at end of input or after the last sample
+pub struct LessonLines {
+    samples: std::slice::Iter<'static, &'static str>,
+    interactive: bool,
+}
+
+/// The user's lines when interactive, otherwise `samples`, echoed as if typed
+pub fn lesson_lines(samples: &'static [&'static str]) -> LessonLines {
+    let interactive = interactive();
+    if !interactive {
+        if output::settings().lesson_output() && !events::enabled() {
+            println!("(not interactive - using sample input)");
+        }
+        if let Ok(mut headings) = SAMPLE_INPUT.lock() {
+            headings.extend(output::last_heading());
+        }
+    }
+    LessonLines {
+        samples: samples.iter(),
+        interactive,
+    }
+}
+
+impl Iterator for LessonLines {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        if self.interactive {
+            return prompt_input("> ");
+        }
+
+        let sample = self.samples.next()?;
+        history::record(&format!("> {}\n", sample));
+        if events::enabled() {
+            input_event(sample, true);
+        } else if output::settings().lesson_output() {
+            println!("> {}", sample);
+        }
+        Some(sample.to_string())
+    }
+}
@@ -0,0 +1,516 @@
+//! Interview Practice
+//!
+//! `rust-learn interview` serves algorithm problems one after another, each
+//! against the clock. A problem is a `.rs` file with a function to write and
+//! the tests it has to pass, shipped in rust-learn-content
+//! (`registry::INTERVIEW`) in a directory per topic. Its header says what to
+//! do, how many minutes to take, how long the tests may run, and the hints,
+//! which stay out of the learner's copy until asked for:
+//!
+//! ```text
+//! // Two Sum
+//! //
+//! // Given a slice of numbers and a target, return the indices ...
+//! //
+//! // time: 15
+//! // limit: 3
+//! // hint: For each number, the partner it needs is `target - number`.
+//! ```
+//!
+//! Every problem has a test on a large input, so an answer that is right but
+//! O(n^2) where O(n) is expected runs out of the limit and does not pass.
+//!
+//! The session in progress is kept in interview.toml in the data directory
+//! (`Session`); how each problem went goes to the activity log
+//! (`Event::Interview`), which the readiness report per topic is made from.
+
+use crate::activity::{Entry, Event};
+use crate::dirs;
+use crate::progress::{format_timestamp, parse_timestamp};
+use crate::settings::Settings;
+use crate::toolchain;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+const FILE_NAME: &str = "interview.toml";
+
+/// Minutes for a problem without a `// time:` line
+const DEFAULT_MINUTES: u64 = 20;
+
+/// Seconds its tests may run without a `// limit:` line
+const DEFAULT_LIMIT: u64 = 3;
+
+/// What each hint taken costs a problem's readiness score
+const HINT_COST: f64 = 0.15;
+
+/// A timed problem
+pub struct Problem {
+    /// The directory it is in, like `graphs`
+    pub topic: &'static str,
+    /// The file name without `.rs`, like `count_islands`
+    pub name: &'static str,
+    pub source: &'static str,
+}
+
+/// How checking a problem went
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Passed,
+    /// It does not compile or a test fails; with what rustc or the tests
+    /// printed
+    Failed(String),
+    /// The tests ran past the problem's limit
+    TooSlow,
+}
+
+/// How an attempt at a problem ended
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    /// Passed within the problem's minutes
+    Solved,
+    /// Passed, but after its minutes were up
+    Late,
+    Skipped,
+}
+
+impl Outcome {
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Solved => "solved",
+            Outcome::Late => "late",
+            Outcome::Skipped => "skipped",
+        }
+    }
+
+    pub fn parse(text: &str) -> Option<Outcome> {
+        match text {
+            "solved" => Some(Outcome::Solved),
+            "late" => Some(Outcome::Late),
+            "skipped" => Some(Outcome::Skipped),
+            _ => None,
+        }
+    }
+}
+
+impl Problem {
+    /// `topic::name`, as the activity log has it
+    pub fn key(&self) -> String {
+        format!("{}::{}", self.topic, self.name)
+    }
+
+    /// The leading comment lines, without their `//`
+    fn header(&self) -> impl Iterator<Item = &'static str> {
+        self.source
+            .lines()
+            .map_while(|line| line.strip_prefix("//"))
+            .map(str::trim)
+    }
+
+    /// The first line of the header
+    pub fn title(&self) -> &'static str {
+        self.header().next().unwrap_or(self.name)
+    }
+
+    /// What to do: the header after the title, up to `// time:`
+    pub fn statement(&self) -> String {
+        let lines: Vec<&str> = self
+            .header()
+            .skip(1)
+            .take_while(|line| !is_field(line))
+            .collect();
+        lines.join("\n").trim().to_string()
+    }
+
+    fn field(&self, key: &str) -> Option<&'static str> {
+        self.header().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            (name == key).then(|| value.trim())
+        })
+    }
+
+    /// Minutes to solve it in
+    pub fn minutes(&self) -> u64 {
+        self.field("time")
+            .and_then(|minutes| minutes.parse().ok())
+            .unwrap_or(DEFAULT_MINUTES)
+    }
+
+    /// How long its tests may run
+    pub fn limit(&self) -> Duration {
+        let seconds = self
+            .field("limit")
+            .and_then(|seconds| seconds.parse().ok())
+            .unwrap_or(DEFAULT_LIMIT);
+        Duration::from_secs(seconds)
+    }
+
+    /// The hints, gentlest first
+    pub fn hints(&self) -> Vec<&'static str> {
+        self.header()
+            .filter_map(|line| line.strip_prefix("hint:"))
+            .map(str::trim)
+            .collect()
+    }
+
+    /// The file the learner starts from: the problem without its hints
+    pub fn starter(&self) -> String {
+        self.source
+            .lines()
+            .filter(|line| !line.starts_with("// hint:"))
+            .map(|line| format!("{}\n", line))
+            .collect()
+    }
+
+    /// Build the tests in `file` with optimizations into `scratch` and run
+    /// them, stopping them at the problem's limit
+    pub fn check(&self, file: &Path, scratch: &Path) -> io::Result<Verdict> {
+        fs::create_dir_all(scratch)?;
+        let binary = scratch.join(self.name);
+        let binary_path = binary.to_string_lossy();
+        let path = file.to_string_lossy();
+        let args = [
+            "--edition",
+            "2024",
+            "--test",
+            "-C",
+            "opt-level=2",
+            "-o",
+            &binary_path,
+            &path,
+        ];
+        // From here, so rustc's errors name the file the way the learner does
+        let build = toolchain::rustc(Path::new("."), &args)?;
+        if !build.success {
+            return Ok(Verdict::Failed(build.stderr));
+        }
+        // A failing test's message is enough; a backtrace buries it
+        let env: &[(&str, &OsStr)] = &[("RUST_BACKTRACE", "0".as_ref())];
+        let run = toolchain::run_limited(&binary, scratch, &["--quiet"], env, self.limit())?;
+        Ok(if run.timed_out {
+            Verdict::TooSlow
+        } else if run.success {
+            Verdict::Passed
+        } else {
+            Verdict::Failed(format!("{}{}", run.stdout, run.stderr))
+        })
+    }
+}
+
+fn is_field(line: &str) -> bool {
+    line.split_once(':')
+        .is_some_and(|(name, _)| matches!(name, "time" | "limit" | "hint"))
+}
+
+/// How the last attempt at each problem ended, by key
+fn last_attempts(entries: &[Entry]) -> BTreeMap<&str, (Outcome, usize)> {
+    let mut attempts = BTreeMap::new();
+    for entry in entries {
+        if let Event::Interview {
+            key,
+            outcome,
+            hints,
+            ..
+        } = &entry.event
+        {
+            attempts.insert(key.as_str(), (*outcome, *hints));
+        }
+    }
+    attempts
+}
+
+/// Up to `count` problems (on `topic` only, when given) for a session:
+/// the ones never tried first, then skipped, then late ones, then those
+/// already solved in time, each in the order they come in `problems`
+pub fn pick<'a>(
+    problems: &'a [Problem],
+    topic: Option<&str>,
+    count: usize,
+    entries: &[Entry],
+) -> Vec<&'a Problem> {
+    let attempts = last_attempts(entries);
+    let mut picked: Vec<&Problem> = problems
+        .iter()
+        .filter(|problem| topic.is_none_or(|topic| problem.topic == topic))
+        .collect();
+    picked.sort_by_key(|problem| match attempts.get(problem.key().as_str()) {
+        None => 0,
+        Some((Outcome::Skipped, _)) => 1,
+        Some((Outcome::Late, _)) => 2,
+        Some((Outcome::Solved, _)) => 3,
+    });
+    picked.truncate(count);
+    picked
+}
+
+/// How ready the learner is on one topic, from the last attempt at each of
+/// its problems
+#[derive(Debug, PartialEq)]
+pub struct Readiness {
+    pub topic: &'static str,
+    /// Problems on the topic
+    pub problems: usize,
+    pub solved: usize,
+    pub late: usize,
+    pub skipped: usize,
+    /// Hints taken on the attempts counted
+    pub hints: usize,
+    /// From 0 to 1: a problem solved in time counts 1, a late one half, less
+    /// for each hint; one skipped or never tried counts nothing
+    pub score: f64,
+}
+
+impl Readiness {
+    pub fn attempted(&self) -> usize {
+        self.solved + self.late + self.skipped
+    }
+
+    /// A word for the score
+    pub fn verdict(&self) -> &'static str {
+        match self.score {
+            score if score >= 0.8 => "ready",
+            score if score >= 0.5 => "getting there",
+            _ if self.attempted() == 0 => "not started",
+            _ => "needs practice",
+        }
+    }
+}
+
+/// Readiness for each topic, in the order the topics first come in
+/// `problems`
+pub fn readiness(problems: &[Problem], entries: &[Entry]) -> Vec<Readiness> {
+    let attempts = last_attempts(entries);
+    let mut report: Vec<Readiness> = Vec::new();
+    for problem in problems {
+        let index = match report.iter().position(|topic| topic.topic == problem.topic) {
+            Some(index) => index,
+            None => {
+                report.push(Readiness {
+                    topic: problem.topic,
+                    problems: 0,
+                    solved: 0,
+                    late: 0,
+                    skipped: 0,
+                    hints: 0,
+                    score: 0.0,
+                });
+                report.len() - 1
+            }
+        };
+        let topic = &mut report[index];
+        topic.problems += 1;
+        let Some(&(outcome, hints)) = attempts.get(problem.key().as_str()) else {
+            continue;
+        };
+        let points = match outcome {
+            Outcome::Solved => {
+                topic.solved += 1;
+                1.0
+            }
+            Outcome::Late => {
+                topic.late += 1;
+                0.5
+            }
+            Outcome::Skipped => {
+                topic.skipped += 1;
+                0.0
+            }
+        };
+        topic.hints += hints;
+        topic.score += (points - HINT_COST * hints as f64).max(0.0);
+    }
+    for topic in &mut report {
+        topic.score /= topic.problems as f64;
+    }
+    report
+}
+
+/// The interview in progress
+#[derive(Debug, PartialEq)]
+pub struct Session {
+    /// Keys of the problems still to do, the current one first
+    pub queue: Vec<String>,
+    /// How many the session started with
+    pub total: usize,
+    /// When the current problem was served
+    pub started: SystemTime,
+    /// Hints taken on the current problem
+    pub hints: usize,
+    /// Whether the hints are shown up front (`--hints`)
+    pub show_hints: bool,
+    /// Where the problem files are written
+    pub dir: PathBuf,
+}
+
+impl Session {
+    /// The saved session, if one is in progress
+    pub fn load() -> Option<Session> {
+        let text = fs::read_to_string(path()?).ok()?;
+        Session::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Option<Session> {
+        let settings = Settings::parse(text).ok()?;
+        let root = settings.root();
+        let queue: Vec<String> = root
+            .strings("queue")?
+            .into_iter()
+            .map(String::from)
+            .collect();
+        if queue.is_empty() {
+            return None;
+        }
+        Some(Session {
+            total: usize::try_from(root.integer("total")?).ok()?,
+            started: parse_timestamp(root.str("started")?)?,
+            hints: usize::try_from(root.integer("hints")?).ok()?,
+            show_hints: root.bool("show_hints").unwrap_or(false),
+            dir: PathBuf::from(root.str("dir")?),
+            queue,
+        })
+    }
+
+    pub fn to_toml(&self) -> String {
+        let queue: Vec<String> = self.queue.iter().map(|key| format!("{:?}", key)).collect();
+        format!(
+            "# Written by `rust-learn interview`\n\
+             queue = [{}]\ntotal = {}\nstarted = \"{}\"\nhints = {}\nshow_hints = {}\ndir = {:?}\n",
+            queue.join(", "),
+            self.total,
+            format_timestamp(self.started),
+            self.hints,
+            self.show_hints,
+            self.dir.to_string_lossy()
+        )
+    }
+
+    /// The current problem's key
+    pub fn current(&self) -> &str {
+        &self.queue[0]
+    }
+
+    /// Its position in the session, from 1
+    pub fn number(&self) -> usize {
+        self.total + 1 - self.queue.len()
+    }
+
+    /// Time spent on the current problem
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed().unwrap_or_default()
+    }
+
+    /// Move on to the next problem; false when that was the last
+    pub fn advance(&mut self) -> bool {
+        self.queue.remove(0);
+        self.started = SystemTime::now();
+        self.hints = 0;
+        !self.queue.is_empty()
+    }
+
+    /// Replace interview.toml
+    pub fn save(&self) -> io::Result<()> {
+        let path = path().ok_or_else(|| io::Error::other("no data directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, self.to_toml())
+    }
+}
+
+/// End the session in progress, if there is one
+pub fn clear() -> io::Result<()> {
+    let Some(path) = path() else {
+        return Ok(());
+    };
+    match fs::remove_file(path) {
+        Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
+
+/// interview.toml in the data directory
+pub fn path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_header_pick_and_readiness() {
+        let problems = [
+            Problem {
+                topic: "arrays",
+                name: "two_sum",
+                source: "// Two Sum\n//\n// Find the pair.\n// Any pair.\n//\n// time: 15\n\
+                         // hint: Use a map.\n// hint: One pass.\n\npub fn two_sum() {}\n",
+            },
+            Problem {
+                topic: "arrays",
+                name: "max_subarray",
+                source: "// Maximum Subarray\n// limit: 5\n",
+            },
+            Problem {
+                topic: "graphs",
+                name: "count_islands",
+                source: "// Islands\n",
+            },
+        ];
+        assert_eq!(problems[0].title(), "Two Sum");
+        assert_eq!(problems[0].statement(), "Find the pair.\nAny pair.");
+        assert_eq!(problems[0].minutes(), 15);
+        assert_eq!(problems[0].limit(), Duration::from_secs(DEFAULT_LIMIT));
+        assert_eq!(problems[1].limit(), Duration::from_secs(5));
+        assert_eq!(problems[0].hints(), ["Use a map.", "One pass."]);
+        assert!(!problems[0].starter().contains("hint"));
+        assert!(problems[0].starter().ends_with("pub fn two_sum() {}\n"));
+
+        let entry = |key: &str, outcome, hints| Entry {
+            time: SystemTime::UNIX_EPOCH,
+            event: Event::Interview {
+                key: key.to_string(),
+                outcome,
+                seconds: 600.0,
+                hints,
+            },
+        };
+        let entries = [
+            entry("arrays::two_sum", Outcome::Skipped, 0),
+            entry("arrays::two_sum", Outcome::Solved, 2),
+            entry("arrays::max_subarray", Outcome::Late, 0),
+        ];
+        let picked: Vec<&str> = pick(&problems, None, 2, &entries)
+            .iter()
+            .map(|problem| problem.name)
+            .collect();
+        assert_eq!(picked, ["count_islands", "max_subarray"]);
+        assert_eq!(pick(&problems, Some("graphs"), 5, &entries).len(), 1);
+
+        let report = readiness(&problems, &entries);
+        assert_eq!(report.len(), 2);
+        assert_eq!(
+            (report[0].solved, report[0].late, report[0].hints),
+            (1, 1, 2)
+        );
+        assert!((report[0].score - 0.6).abs() < 1e-9);
+        assert_eq!(report[0].verdict(), "getting there");
+        assert_eq!(report[1].verdict(), "not started");
+
+        let session = Session {
+            queue: vec![
+                "arrays::two_sum".to_string(),
+                "graphs::count_islands".to_string(),
+            ],
+            total: 3,
+            started: parse_timestamp("2026-10-16T14:03:27Z").unwrap(),
+            hints: 1,
+            show_hints: false,
+            dir: PathBuf::from("interview"),
+        };
+        assert_eq!(Session::parse(&session.to_toml()), Some(session));
+    }
+}
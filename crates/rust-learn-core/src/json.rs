@@ -0,0 +1,348 @@
+//! JSON Values
+//!
+//! Just enough JSON for rust-learn's own files and reports: `Value` parses a
+//! document and prints itself back compactly, or indented (`pretty`) for
+//! files people edit by hand. Objects keep their keys in the
+//! order they were written, so output is stable and easy to diff.
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Parse a whole document; the error says what was expected where
+    pub fn parse(text: &str) -> Result<Value, String> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            at: 0,
+        };
+        let value = parser.value()?;
+        parser.skip_whitespace();
+        if parser.at < parser.chars.len() {
+            return Err(parser.error("end of input"));
+        }
+        Ok(value)
+    }
+
+    /// The value under `key`, when this is an object that has it
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(entries) => entries
+                .iter()
+                .find(|(name, _)| name == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Number(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    pub fn as_object(&self) -> Option<&[(String, Value)]> {
+        match self {
+            Value::Object(entries) => Some(entries),
+            _ => None,
+        }
+    }
+
+    /// JSON with one entry a line, indented by four spaces a level
+    pub fn pretty(&self) -> String {
+        let mut text = String::new();
+        self.write_pretty(&mut text, 0);
+        text
+    }
+
+    fn write_pretty(&self, text: &mut String, depth: usize) {
+        let indent = |depth: usize| "    ".repeat(depth);
+        match self {
+            Value::Array(values) if !values.is_empty() => {
+                text.push_str("[\n");
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        text.push_str(",\n");
+                    }
+                    text.push_str(&indent(depth + 1));
+                    value.write_pretty(text, depth + 1);
+                }
+                text.push_str(&format!("\n{}]", indent(depth)));
+            }
+            Value::Object(entries) if !entries.is_empty() => {
+                text.push_str("{\n");
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        text.push_str(",\n");
+                    }
+                    text.push_str(&format!("{}{}: ", indent(depth + 1), quote(key)));
+                    value.write_pretty(text, depth + 1);
+                }
+                text.push_str(&format!("\n{}}}", indent(depth)));
+            }
+            value => text.push_str(&value.to_string()),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(text: &str) -> Value {
+        Value::String(text.to_string())
+    }
+}
+
+/// Compact JSON, on one line
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Number(number) => write!(f, "{}", number),
+            Value::String(text) => write!(f, "{}", quote(text)),
+            Value::Array(values) => {
+                write!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "]")
+            }
+            Value::Object(entries) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in entries.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}:{}", quote(key), value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+/// `text` as a quoted JSON string
+pub fn quote(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+struct Parser {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Parser {
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.object(),
+            Some('[') => self.array(),
+            Some('"') => self.string().map(Value::String),
+            Some('t') => self.word("true", Value::Bool(true)),
+            Some('f') => self.word("false", Value::Bool(false)),
+            Some('n') => self.word("null", Value::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => self.number(),
+            _ => Err(self.error("a value")),
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.at += 1;
+        let mut entries = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.at += 1;
+            return Ok(Value::Object(entries));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("a key in quotes"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            self.expect(':')?;
+            entries.push((key, self.value()?));
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some('}') => return Ok(Value::Object(entries)),
+                _ => return Err(self.error("',' or '}'")),
+            }
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.at += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.at += 1;
+            return Ok(Value::Array(values));
+        }
+        loop {
+            values.push(self.value()?);
+            self.skip_whitespace();
+            match self.next() {
+                Some(',') => continue,
+                Some(']') => return Ok(Value::Array(values)),
+                _ => return Err(self.error("',' or ']'")),
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.at += 1;
+        let mut text = String::new();
+        loop {
+            match self.next() {
+                Some('"') => return Ok(text),
+                Some('\\') => match self.next() {
+                    Some('n') => text.push('\n'),
+                    Some('t') => text.push('\t'),
+                    Some('r') => text.push('\r'),
+                    Some('b') => text.push('\u{8}'),
+                    Some('f') => text.push('\u{c}'),
+                    Some('u') => {
+                        let hex: String = self.chars.iter().skip(self.at).take(4).collect();
+                        let code = u32::from_str_radix(&hex, 16)
+                            .map_err(|_| self.error("four hex digits"))?;
+                        self.at += 4;
+                        text.push(char::from_u32(code).unwrap_or('\u{fffd}'));
+                    }
+                    Some(c) => text.push(c),
+                    None => return Err(self.error("an escaped character")),
+                },
+                Some(c) => text.push(c),
+                None => return Err(self.error("a closing '\"'")),
+            }
+        }
+    }
+
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.at;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+        {
+            self.at += 1;
+        }
+        let text: String = self.chars[start..self.at].iter().collect();
+        text.parse()
+            .map(Value::Number)
+            .map_err(|_| format!("'{}' at character {} is not a number", text, start + 1))
+    }
+
+    fn word(&mut self, word: &str, value: Value) -> Result<Value, String> {
+        for expected in word.chars() {
+            if self.next() != Some(expected) {
+                return Err(self.error(word));
+            }
+        }
+        Ok(value)
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), String> {
+        if self.next() == Some(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("'{}'", c)))
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek();
+        self.at += 1;
+        c
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.at += 1;
+        }
+    }
+
+    fn error(&self, expected: &str) -> String {
+        format!(
+            "expected {} at character {}",
+            expected,
+            self.at.min(self.chars.len()) + 1
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_print() {
+        let text = r#"{"name": "ownership", "done": true, "seconds": 0.25,
+                       "sections": ["a \"b\"", "c\n"], "run": null}"#;
+        let value = Value::parse(text).unwrap();
+        assert_eq!(value.get("name").and_then(Value::as_str), Some("ownership"));
+        assert_eq!(value.get("seconds").and_then(Value::as_f64), Some(0.25));
+        assert_eq!(
+            value
+                .get("sections")
+                .and_then(Value::as_array)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            value.to_string(),
+            r#"{"name":"ownership","done":true,"seconds":0.25,"sections":["a \"b\"","c\n"],"run":null}"#
+        );
+        assert_eq!(Value::parse(&value.to_string()), Ok(value.clone()));
+        let pretty = value.pretty();
+        assert!(pretty.starts_with("{\n    \"name\": \"ownership\",\n"));
+        assert!(pretty.contains("\"sections\": [\n        \"a \\\"b\\\"\",\n"));
+        assert_eq!(Value::parse(&pretty), Ok(value));
+        assert_eq!(
+            Value::parse("[1, 2"),
+            Err("expected ',' or ']' at character 6".to_string())
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Lessons and Sections
+//!
+//! The types every lesson is described with. The list of lessons itself lives
+//! in rust-learn-content (its `registry` module), next to the lessons.
+
+pub use crate::checkpoint::Checkpoint;
+pub use crate::quiz::Question;
+
+#[derive(Clone, Copy)]
+pub struct Lesson {
+    /// Short name typed on the command line, e.g. `cargo run -- ownership`
+    pub name: &'static str,
+    /// One-line description shown in the menu
+    pub title: &'static str,
+    /// Runs every section of the lesson
+    pub run: fn(),
+    /// The lesson's sections, in the order `run` executes them
+    pub sections: &'static [Section],
+    /// The lesson's source file, for reading alongside its output
+    pub source: &'static str,
+    /// Curriculum chapter, from 1; a chapter groups the lessons on one topic
+    pub chapter: u8,
+    /// Lessons to finish first, by name; they come earlier in the registry
+    pub requires: &'static [&'static str],
+    pub difficulty: Difficulty,
+    /// Topics the lesson covers, lowercase: `memory`, `concurrency`, ...
+    pub tags: &'static [&'static str],
+    /// What changed in each version after the first, oldest first
+    pub changelog: &'static [Change],
+    /// Multiple-choice questions about the lesson, for `quiz`
+    pub questions: &'static [Question],
+    /// Small functions whose output `quiz --predict` asks the learner for
+    pub snippets: &'static [Snippet],
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Difficulty {
+    Beginner,
+    Intermediate,
+    Advanced,
+}
+
+impl Difficulty {
+    pub const NAMES: [&str; 3] = ["beginner", "intermediate", "advanced"];
+
+    pub fn name(self) -> &'static str {
+        Self::NAMES[self as usize]
+    }
+}
+
+impl std::str::FromStr for Difficulty {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, String> {
+        match name {
+            "beginner" => Ok(Difficulty::Beginner),
+            "intermediate" => Ok(Difficulty::Intermediate),
+            "advanced" => Ok(Difficulty::Advanced),
+            _ => Err(format!(
+                "unknown difficulty '{}' (try {})",
+                name,
+                Self::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// One new version of a lesson's content, and what changed in it
+pub struct Change {
+    /// 2 for the first change; versions only go up
+    pub version: u32,
+    /// One line a learner who finished the old version should read
+    pub summary: &'static str,
+}
+
+impl Lesson {
+    /// The content version: 1 as first written, then the newest in `changelog`
+    pub fn version(&self) -> u32 {
+        self.changelog.last().map_or(1, |change| change.version)
+    }
+
+    /// About how long the lesson takes to work through: a minute for every
+    /// section and for every 30 lines of its source
+    pub fn estimated_minutes(&self) -> usize {
+        self.sections.len() + self.source.lines().count() / 30
+    }
+
+    /// The changes made after `version`, oldest first
+    pub fn changes_since(&self, version: u32) -> &'static [Change] {
+        let first = self
+            .changelog
+            .iter()
+            .position(|change| change.version > version)
+            .unwrap_or(self.changelog.len());
+        &self.changelog[first..]
+    }
+
+    /// Find a section by name or by its 1-based number ("slices" or "5")
+    pub fn section(&self, key: &str) -> Option<&'static Section> {
+        match key.parse::<usize>() {
+            Ok(number) => number
+                .checked_sub(1)
+                .and_then(|index| self.sections.get(index)),
+            Err(_) => self
+                .sections
+                .iter()
+                .find(|section| section.name.eq_ignore_ascii_case(key)),
+        }
+    }
+
+    /// The function `section` runs, cut from the lesson's source: from the
+    /// comments and attributes above `fn <name>` to its closing brace.
+    /// `None` when there is no such function
+    pub fn section_source(&self, section: &Section) -> Option<&'static str> {
+        self.function_source(section.name)
+    }
+
+    /// `fn <name>` cut from the lesson's source, as `section_source` does
+    pub fn function_source(&self, name: &str) -> Option<&'static str> {
+        let source = self.source;
+        let mut offset = 0;
+        let lines: Vec<(usize, &str)> = source
+            .split_inclusive('\n')
+            .map(|line| {
+                offset += line.len();
+                (offset - line.len(), line)
+            })
+            .collect();
+
+        let signature = lines.iter().position(|(_, line)| defines(line, name))?;
+        let mut first = signature;
+        while first > 0 {
+            let above = lines[first - 1].1.trim_start();
+            if !above.starts_with("//") && !above.starts_with("#[") {
+                break;
+            }
+            first -= 1;
+        }
+        // rustfmt puts the closing brace at the signature's indentation
+        let line = lines[signature].1;
+        let indent = &line[..line.len() - line.trim_start().len()];
+        let closing = format!("{}}}", indent);
+        let last = signature
+            + lines[signature..]
+                .iter()
+                .position(|(_, line)| line.trim_end() == closing)?;
+
+        let (end, line) = lines[last];
+        Some(&source[lines[first].0..end + line.trim_end().len()])
+    }
+
+    /// The line, from 1, of the lesson's source that starts `fn <name>`
+    pub fn function_line(&self, name: &str) -> Option<usize> {
+        let index = self.source.lines().position(|line| defines(line, name))?;
+        Some(index + 1)
+    }
+}
+
+/// Whether `line` is the signature of `fn <name>`
+fn defines(line: &str, name: &str) -> bool {
+    let line = line.trim_start();
+    let line = line.strip_prefix("pub ").unwrap_or(line);
+    let line = line.strip_prefix("async ").unwrap_or(line);
+    line.strip_prefix("fn ")
+        .and_then(|rest| rest.strip_prefix(name))
+        .is_some_and(|rest| rest.starts_with(['(', '<']))
+}
+
+/// The section a printed heading like `3. Slices:` starts
+pub fn section_by_heading(sections: &'static [Section], heading: &str) -> Option<&'static Section> {
+    let (_, title) = heading.split_once(". ")?;
+    let title = title.strip_suffix(':')?;
+    sections.iter().find(|section| section.title == title)
+}
+
+/// One numbered section of a lesson, runnable on its own
+pub struct Section {
+    /// Name used on the command line, e.g. `cargo run -- ownership slices`
+    pub name: &'static str,
+    /// Heading the section prints, without its number
+    pub title: &'static str,
+    pub run: fn(),
+    /// Other ways the section explains the same thing, by name; see `variants`
+    pub variants: &'static [&'static str],
+    /// A yes/no question asked once it has run; see `checkpoint`
+    pub checkpoint: Option<Checkpoint>,
+}
+
+impl Section {
+    pub const fn new(name: &'static str, title: &'static str, run: fn()) -> Self {
+        Section {
+            name,
+            title,
+            run,
+            variants: &[],
+            checkpoint: None,
+        }
+    }
+
+    /// The section with explanation `variants`, which it passes to
+    /// `variants::pick`
+    pub const fn with_variants(self, variants: &'static [&'static str]) -> Self {
+        Section { variants, ..self }
+    }
+
+    /// The section with a `checkpoint` question: `answer` is whether the
+    /// right one is yes
+    pub const fn with_checkpoint(
+        self,
+        prompt: &'static str,
+        answer: bool,
+        explanation: &'static str,
+    ) -> Self {
+        Section {
+            checkpoint: Some(Checkpoint {
+                prompt,
+                answer,
+                explanation,
+            }),
+            ..self
+        }
+    }
+}
+
+/// `section!(slices, "Slices")` - a section named after the function that runs it
+#[macro_export]
+macro_rules! section {
+    ($run:ident, $title:expr) => {
+        $crate::lesson::Section::new(stringify!($run), $title, $run)
+    };
+}
+
+/// A small function a lesson shows the learner, who predicts what it prints
+pub struct Snippet {
+    /// The function's name, to find its source and run it on its own
+    pub name: &'static str,
+    pub run: fn(),
+}
+
+/// `snippet!(predict_shadowing)` - a snippet named after its function
+#[macro_export]
+macro_rules! snippet {
+    ($run:ident) => {
+        $crate::lesson::Snippet {
+            name: stringify!($run),
+            run: $run,
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop() {}
+
+    const SECTIONS: &[Section] = &[Section::new("slices", "Slices", noop)];
+
+    #[test]
+    fn test_section_source() {
+        let lesson = Lesson {
+            name: "ownership",
+            title: "Ownership",
+            run: noop,
+            sections: SECTIONS,
+            source: "use std::fmt;\n\n\
+                     // Borrowing part of a String\n\
+                     fn slices() {\n    \
+                         let s = String::from(\"hi {\");\n\
+                     }\n\n\
+                     fn slices_helper() {}\n",
+            chapter: 4,
+            requires: &[],
+            difficulty: Difficulty::Beginner,
+            tags: &[],
+            changelog: &[],
+            questions: &[],
+            snippets: &[],
+        };
+        assert_eq!(
+            lesson.section_source(&SECTIONS[0]),
+            Some(
+                "// Borrowing part of a String\nfn slices() {\n    let s = String::from(\"hi {\");\n}"
+            )
+        );
+        assert_eq!(lesson.function_line("slices"), Some(4));
+        assert_eq!(lesson.function_line("slices_helper"), Some(8));
+        assert_eq!(lesson.estimated_minutes(), 1);
+        assert_eq!("advanced".parse(), Ok(Difficulty::Advanced));
+        assert!("expert".parse::<Difficulty>().is_err());
+    }
+}
@@ -0,0 +1,68 @@
+//! rust-learn core
+//!
+//! The pieces every other crate in the workspace builds on: the lesson and
+//! section types, menu input, running cargo, and the `Explain` and `Error`
+//! derives. Code that lessons treat as a third-party library (config_loader)
+//! also lives here, so it has a public API that the lessons and the
+//! compile-fail fixtures in tests/ use from the outside.
+
+pub mod activity;
+pub mod alloc_counter;
+pub mod backups;
+pub mod bench;
+pub mod calibration;
+pub mod checkpoint;
+pub mod classroom;
+pub mod compile_errors;
+pub mod config_loader;
+pub mod crash;
+pub mod cross;
+pub mod diff;
+pub mod dirs;
+pub mod error_codes;
+pub mod events;
+pub mod exercises;
+pub mod fuzzy;
+pub mod gc;
+pub mod highlight;
+pub mod history;
+pub mod hooks;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod i18n;
+pub mod input;
+pub mod interview;
+pub mod json;
+pub mod lesson;
+pub mod memory;
+pub mod output;
+pub mod packs;
+pub mod playground;
+pub mod progress;
+pub mod question_bank;
+pub mod quiz;
+pub mod random;
+pub mod recent;
+pub mod recommendations;
+pub mod reference;
+#[cfg(feature = "http")]
+pub mod remote;
+pub mod runner;
+pub mod settings;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod store;
+pub mod theme;
+pub mod toolchain;
+pub mod transaction;
+pub mod variants;
+pub mod watchdog;
+
+pub use rust_learn_derive::{Error, Explain};
+
+/// A type that can show its own definition, as written in the source.
+///
+/// Derive it with `#[derive(Explain)]` instead of implementing it by hand.
+pub trait Explain {
+    fn definition() -> &'static str;
+}
@@ -0,0 +1,242 @@
+//! Peak Memory
+//!
+//! How much memory a lesson needed at most. The best answer is how far the
+//! process's resident set rose above what it held when the lesson began: its
+//! peak (VmHWM in `/proc/self/status` on Linux, `PeakWorkingSetSize` on
+//! Windows) less its size then (VmRSS, `WorkingSetSize`), so memory earlier
+//! lessons left behind does not count. Linux lets a process reset its peak,
+//! so there each lesson gets its own; on Windows the peak only ever grows,
+//! and a lesson that stays below an earlier one is measured by the heap it
+//! had in use at most instead, from `alloc_counter`. So is every lesson on
+//! other Unix systems, whose `getrusage` has a peak but no current size.
+//! When neither is available there is no figure at all.
+
+use crate::alloc_counter;
+use std::fmt;
+
+/// Where a peak figure came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// The resident memory the process gained (heap, stack, code and all):
+    /// its peak while the code ran, less what it held when the code began
+    Resident,
+    /// Only the heap the code had in use, above what was in use before it
+    Heap,
+}
+
+impl Source {
+    pub fn name(self) -> &'static str {
+        match self {
+            Source::Resident => "resident",
+            Source::Heap => "heap",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Peak {
+    pub bytes: u64,
+    pub source: Source,
+}
+
+/// `12.4 MiB`, with a `*` after heap figures
+impl fmt::Display for Peak {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mark = match self.source {
+            Source::Resident => "",
+            Source::Heap => "*",
+        };
+        f.pad(&format!("{}{}", format_bytes(self.bytes), mark))
+    }
+}
+
+/// `512 B`, `48.0 KiB`, `12.4 MiB`
+pub fn format_bytes(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    let value = bytes as f64;
+    if value < KIB {
+        format!("{} B", bytes)
+    } else if value < KIB * KIB {
+        format!("{:.1} KiB", value / KIB)
+    } else if value < KIB * KIB * KIB {
+        format!("{:.1} MiB", value / (KIB * KIB))
+    } else {
+        format!("{:.2} GiB", value / (KIB * KIB * KIB))
+    }
+}
+
+/// Run `f` and report the most memory it needed
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<Peak>) {
+    let reset = reset_peak_resident();
+    let before = peak_resident();
+    let start = resident();
+    let (result, heap) = alloc_counter::peak(f);
+    let after = peak_resident();
+
+    // Without a reset, a peak that did not move was set by something earlier
+    let resident = match (before, after, start) {
+        (Some(before), Some(after), Some(start)) if reset || after > before => {
+            Some(after.saturating_sub(start))
+        }
+        _ => None,
+    };
+    let peak = match resident {
+        Some(bytes) => Some(Peak {
+            bytes,
+            source: Source::Resident,
+        }),
+        None => alloc_counter::inner_name().map(|_| Peak {
+            bytes: heap as u64,
+            source: Source::Heap,
+        }),
+    };
+    (result, peak)
+}
+
+/// The most resident memory the process has used, or since the last reset
+#[cfg(target_os = "linux")]
+pub fn peak_resident() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status_bytes(&status, "VmHWM:")
+}
+
+/// The resident memory the process uses now
+#[cfg(target_os = "linux")]
+pub fn resident() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    status_bytes(&status, "VmRSS:")
+}
+
+/// A `VmHWM:   12345 kB` line of `/proc/self/status`, in bytes
+#[cfg(target_os = "linux")]
+fn status_bytes(status: &str, field: &str) -> Option<u64> {
+    let line = status.lines().find_map(|line| line.strip_prefix(field))?;
+    let kib: u64 = line.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some(kib * 1024)
+}
+
+/// Writing 5 to clear_refs sets VmHWM back to the current resident size
+#[cfg(target_os = "linux")]
+fn reset_peak_resident() -> bool {
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn peak_resident() -> Option<u64> {
+    /// `struct rusage`: two `timeval`s, then 14 longs starting with ru_maxrss
+    #[repr(C)]
+    struct Rusage {
+        times: [i64; 4],
+        maxrss: std::ffi::c_long,
+        rest: [std::ffi::c_long; 13],
+    }
+    unsafe extern "C" {
+        fn getrusage(who: std::ffi::c_int, usage: *mut Rusage) -> std::ffi::c_int;
+    }
+    const RUSAGE_SELF: std::ffi::c_int = 0;
+
+    let mut usage = std::mem::MaybeUninit::<Rusage>::zeroed();
+    // SAFETY: getrusage fills in the struct it is given, laid out as above
+    if unsafe { getrusage(RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+        return None;
+    }
+    // SAFETY: zeroed, then filled in by a successful call
+    let maxrss = unsafe { usage.assume_init() }.maxrss as u64;
+    // macOS counts bytes, the BSDs kilobytes
+    if cfg!(target_vendor = "apple") {
+        Some(maxrss)
+    } else {
+        Some(maxrss * 1024)
+    }
+}
+
+/// `getrusage` has no current size to take the peak from
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn resident() -> Option<u64> {
+    None
+}
+
+#[cfg(windows)]
+pub fn peak_resident() -> Option<u64> {
+    process_memory().map(|counters| counters.peak_working_set as u64)
+}
+
+#[cfg(windows)]
+pub fn resident() -> Option<u64> {
+    process_memory().map(|counters| counters.working_set as u64)
+}
+
+/// `PROCESS_MEMORY_COUNTERS`
+#[cfg(windows)]
+#[repr(C)]
+struct Counters {
+    size: u32,
+    page_faults: u32,
+    peak_working_set: usize,
+    working_set: usize,
+    rest: [usize; 6],
+}
+
+#[cfg(windows)]
+fn process_memory() -> Option<Counters> {
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetCurrentProcess() -> *mut std::ffi::c_void;
+        fn K32GetProcessMemoryInfo(
+            process: *mut std::ffi::c_void,
+            counters: *mut Counters,
+            size: u32,
+        ) -> i32;
+    }
+
+    let size = std::mem::size_of::<Counters>() as u32;
+    let mut counters = Counters {
+        size,
+        page_faults: 0,
+        peak_working_set: 0,
+        working_set: 0,
+        rest: [0; 6],
+    };
+    // SAFETY: the pseudo-handle of the current process needs no closing, and
+    // the struct is the size the call is told it is
+    let ok = unsafe { K32GetProcessMemoryInfo(GetCurrentProcess(), &mut counters, size) };
+    (ok != 0).then_some(counters)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn peak_resident() -> Option<u64> {
+    None
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn resident() -> Option<u64> {
+    None
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_resident() -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_peak_figures() {
+        #[cfg(target_os = "linux")]
+        {
+            let status = "Name:\trust-learn\nVmPeak:\t  20000 kB\nVmHWM:\t    5120 kB\nVmRSS:\t    4096 kB\n";
+            assert_eq!(status_bytes(status, "VmHWM:"), Some(5 * 1024 * 1024));
+            assert_eq!(status_bytes(status, "VmRSS:"), Some(4 * 1024 * 1024));
+            assert_eq!(status_bytes("VmRSS:\t 4096 kB\n", "VmHWM:"), None);
+        }
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(3 * 1024 * 1024 + 400 * 1024), "3.4 MiB");
+        let heap = Peak {
+            bytes: 48 * 1024,
+            source: Source::Heap,
+        };
+        assert_eq!(format!("{:>10}", heap), " 48.0 KiB*");
+    }
+}
@@ -0,0 +1,653 @@
+//! Output Settings
+//!
+//! Process-wide settings chosen on the command line (`-v`, `-q`, `-qq`,
+//! `--no-color`, `--theme`, `--show-source`). The CLI calls `init` once at startup; everything
+//! else reads `settings()`.
+//!
+//! Lesson text goes through `lesson_line`, which recognises the lessons' own
+//! layout (numbered headings, CAPS subheadings, `===` underlines, bullets and
+//! `backticked` code) and colors each part from the current theme (`theme`). Prose goes
+//! through `explanation` instead, which drops it below the verbosity it needs.
+//! Both translate prose and headings when `i18n` has a catalog.
+//! With `show_source`, each section's function is printed above its heading.
+
+use crate::checkpoint;
+use crate::events;
+use crate::highlight;
+use crate::history;
+use crate::i18n;
+use crate::input;
+use crate::json::Value;
+use crate::lesson::{self, Lesson};
+use crate::settings;
+pub use crate::theme::{Palette, Theme};
+use std::borrow::Cow;
+use std::fmt;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Settings {
+    /// How much explanation lessons print, and whether to report timings
+    pub verbosity: Verbosity,
+    /// Use ANSI colors
+    pub color: bool,
+    /// Colors for lesson text, when `color` is on
+    pub theme: Theme,
+    /// Print each section's source before its output
+    pub show_source: bool,
+    /// Wrap prose to this many columns (`wrap`); `None` leaves lines as
+    /// they are
+    pub width: Option<usize>,
+    /// Write `Glyph`s as `[ok]`-style text instead of emoji
+    pub ascii: bool,
+}
+
+/// Whether the terminal can be trusted with emoji: a UTF-8 locale, and not
+/// the Linux console, whose font has none
+fn emoji_terminal() -> bool {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let utf8 = locale.contains("utf-8") || locale.contains("utf8");
+    utf8 && std::env::var("TERM").is_ok_and(|term| term != "linux")
+}
+
+/// The widest prose gets without `max_width` in config.toml
+const DEFAULT_MAX_WIDTH: usize = 100;
+
+impl Settings {
+    /// Color only when it was not turned off (`--no-color` or the `NO_COLOR`
+    /// environment variable) and stdout is a terminal rather than a pipe or
+    /// file. Prose is wrapped on a terminal too, to its width but no wider
+    /// than `max_width` under `[output]` in config.toml (100 by default; 0
+    /// never wraps)
+    pub fn detect(verbosity: Verbosity, no_color: bool) -> Self {
+        let terminal = std::io::stdout().is_terminal();
+        let color = !no_color && std::env::var_os("NO_COLOR").is_none() && terminal;
+        let max_width = settings::get()
+            .table("output")
+            .and_then(|table| table.integer("max_width"))
+            .map_or(DEFAULT_MAX_WIDTH, |width| width.max(0) as usize);
+        let width = match input::terminal_width() {
+            _ if !terminal || max_width == 0 => None,
+            Some(columns) => Some(columns.min(max_width)),
+            None => Some(max_width),
+        };
+        Settings {
+            verbosity,
+            color,
+            theme: Theme::default(),
+            show_source: false,
+            width,
+            ascii: !terminal || !emoji_terminal(),
+        }
+    }
+
+    /// `-v` or more: print extra detail, such as how long a lesson took
+    pub fn verbose(&self) -> bool {
+        self.verbosity >= Verbosity::Verbose
+    }
+
+    /// Anything above `-qq`: lessons print as they run
+    pub fn lesson_output(&self) -> bool {
+        self.verbosity > Verbosity::Summary
+    }
+}
+
+/// How much of a lesson's explanation to print. Demonstration output (the
+/// results of running the code) is printed at every level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// `-qq`: no lesson output at all, only results and summaries such as
+    /// `run --all`'s table, for scripts and grading
+    Summary,
+    /// `--quiet`: only the code's results, under the section headings
+    Quiet,
+    /// The notes, rules and code listings around the results
+    #[default]
+    Normal,
+    /// `-v`: longer explanations for beginners as well
+    Verbose,
+    /// `-vv`: what happens underneath too, such as memory layout
+    VeryVerbose,
+}
+
+impl Verbosity {
+    /// From the number of `-v` and of `-q` flags (which win)
+    pub fn from_flags(verbose: u8, quiet: u8) -> Self {
+        match (quiet, verbose) {
+            (1, _) => Verbosity::Quiet,
+            (2.., _) => Verbosity::Summary,
+            (0, 0) => Verbosity::Normal,
+            (0, 1) => Verbosity::Verbose,
+            (0, _) => Verbosity::VeryVerbose,
+        }
+    }
+
+    /// The flag that selects it, for passing on to a child `rust-learn`
+    pub fn flag(self) -> Option<&'static str> {
+        match self {
+            Verbosity::Summary => Some("-qq"),
+            Verbosity::Quiet => Some("--quiet"),
+            Verbosity::Normal => None,
+            Verbosity::Verbose => Some("-v"),
+            Verbosity::VeryVerbose => Some("-vv"),
+        }
+    }
+}
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+/// Set the settings for the rest of the process; later calls are ignored
+pub fn init(settings: Settings) {
+    let _ = SETTINGS.set(settings);
+}
+
+/// The current settings (plain output at normal verbosity before `init`)
+pub fn settings() -> Settings {
+    SETTINGS.get().copied().unwrap_or_default()
+}
+
+/// `text` in the theme's colors for `part` of its palette
+fn paint(text: &str, part: fn(&Palette) -> &'static str) -> String {
+    let settings = settings();
+    let code = part(&settings.theme.palette());
+    if settings.color && !code.is_empty() {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Labels and totals (`Palette::emphasis`)
+pub fn bold(text: &str) -> String {
+    paint(text, |palette| palette.emphasis)
+}
+
+/// Right answers and checks that passed (`Palette::success`)
+pub fn green(text: &str) -> String {
+    paint(text, |palette| palette.success)
+}
+
+/// Wrong answers and failures (`Palette::error`)
+pub fn red(text: &str) -> String {
+    paint(text, |palette| palette.error)
+}
+
+/// Notes around the output (`Palette::muted`)
+pub fn dim(text: &str) -> String {
+    paint(text, |palette| palette.muted)
+}
+
+/// A mark for a result: an emoji, or text like `[ok]` with `--ascii` or
+/// where emoji may not show (output that is not a UTF-8 terminal)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    Ok,
+    Fail,
+    Warning,
+    Hint,
+}
+
+impl Glyph {
+    pub fn symbol(self) -> &'static str {
+        match (self, settings().ascii) {
+            (Glyph::Ok, false) => "\u{2705}",
+            (Glyph::Fail, false) => "\u{274c}",
+            (Glyph::Warning, false) => "\u{26a0}\u{fe0f}",
+            (Glyph::Hint, false) => "\u{1f4a1}",
+            (Glyph::Ok, true) => "[ok]",
+            (Glyph::Fail, true) => "[x]",
+            (Glyph::Warning, true) => "[!]",
+            (Glyph::Hint, true) => "[i]",
+        }
+    }
+
+    /// The columns it takes up: emoji are two wide
+    pub fn width(self) -> usize {
+        if settings().ascii {
+            self.symbol().len()
+        } else {
+            2
+        }
+    }
+}
+
+/// `glyph` then `text` in `color` (`green`, `red`, ...), padded to `width`
+/// columns; the padding is counted before color adds its escape codes
+pub fn status(glyph: Glyph, text: &str, color: fn(&str) -> String, width: usize) -> String {
+    let used = glyph.width() + 1 + text.chars().count();
+    let padding = " ".repeat(width.saturating_sub(used));
+    format!("{} {}{}", glyph.symbol(), color(text), padding)
+}
+
+/// What a line of lesson text is, judged by its layout alone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    Rule,
+    Heading,
+    Subheading,
+    Comment,
+    Bullet,
+    /// Anything else, mostly the code's own output
+    Text,
+}
+
+/// Classify one line the way `lesson_line` colors it
+pub fn line_kind(line: &str) -> LineKind {
+    let trimmed = line.trim();
+
+    if trimmed.len() >= 3 && trimmed.chars().all(|c| c == '=') {
+        LineKind::Rule
+    } else if is_heading(trimmed) {
+        LineKind::Heading
+    } else if is_subheading(trimmed) {
+        LineKind::Subheading
+    } else if trimmed.starts_with("//") {
+        LineKind::Comment
+    } else if trimmed.starts_with("- ") {
+        LineKind::Bullet
+    } else {
+        LineKind::Text
+    }
+}
+
+/// Print one line (or several, split on `\n`) of lesson text. Lessons call
+/// this through their `println!`, so without color it is a plain `println!`.
+pub fn lesson_line(args: fmt::Arguments) {
+    print_lesson_text(&args.to_string(), false);
+}
+
+/// Print lesson text without ending the line, as is; lessons call this
+/// through their `print!`
+pub fn lesson_text(args: fmt::Arguments) {
+    if !settings().lesson_output() {
+        return;
+    }
+    history::record(&args.to_string());
+    if events::enabled() {
+        events::emit("text", vec![("text", Value::String(args.to_string()))]);
+    } else {
+        print!("{}", args);
+    }
+}
+
+/// Print lesson text, translated (`i18n`) where it is prose or a heading.
+/// Headings are remembered in English, which is how sections are found, and
+/// a translated line is colored as the English one would be
+fn print_lesson_text(text: &str, prose: bool) {
+    let settings = settings();
+    // Which sections ran is still told by their headings
+    remember_headings(text);
+    start_sections(text, settings.lesson_output());
+    if !settings.lesson_output() {
+        return;
+    }
+    let mut lines: Vec<(LineKind, Cow<str>)> = text
+        .split('\n')
+        .map(|line| {
+            let kind = line_kind(line);
+            if prose || matches!(kind, LineKind::Heading | LineKind::Subheading) {
+                (kind, i18n::translate(line))
+            } else {
+                (kind, Cow::Borrowed(line))
+            }
+        })
+        .collect();
+
+    if settings.verbosity == Verbosity::Quiet {
+        // The prose between two blank lines is gone, so keep only one of them
+        lines.retain(|(_, line)| {
+            let blank = line.trim().is_empty();
+            let after_blank = LAST_LINE_BLANK.swap(blank, Ordering::Relaxed);
+            !(blank && after_blank)
+        });
+        if lines.is_empty() {
+            return;
+        }
+    }
+
+    for (_, line) in &lines {
+        history::record(line);
+        history::record("\n");
+    }
+    if events::enabled() {
+        for (_, line) in lines {
+            events::emit("line", vec![("text", line.as_ref().into())]);
+        }
+        return;
+    }
+    print_sources(text);
+
+    if let Some(width) = settings.width {
+        lines = lines
+            .into_iter()
+            .flat_map(|(kind, line)| {
+                if !(prose || kind == LineKind::Bullet) || line.chars().count() <= width {
+                    return vec![(kind, line)];
+                }
+                wrap(&line, width)
+                    .into_iter()
+                    .map(|part| (kind, Cow::Owned(part)))
+                    .collect()
+            })
+            .collect();
+    }
+
+    let palette = settings.theme.palette();
+    let rendered: Vec<Cow<str>> = lines
+        .into_iter()
+        .map(|(kind, line)| {
+            if settings.color {
+                Cow::Owned(render(&line, kind, &palette))
+            } else {
+                line
+            }
+        })
+        .collect();
+    println!("{}", rendered.join("\n"));
+}
+
+/// `line` broken at spaces into lines of at most `width` columns. A list
+/// item (`- `, `3. `, or a `name   - description` row) goes on under its
+/// text, anything else under its own indentation. Code is never broken: a
+/// line that looks like code (`looks_like_code`) stays whole, and so does a
+/// `backticked` span, as does a word longer than `width`
+pub fn wrap(line: &str, width: usize) -> Vec<String> {
+    if line.chars().count() <= width || looks_like_code(line) {
+        return vec![line.to_string()];
+    }
+    let text = line.trim_start();
+    let indent = line.len() - text.len();
+    let digits = text.chars().take_while(char::is_ascii_digit).count();
+    let marker = if text.starts_with("- ") || text.starts_with("* ") {
+        2
+    } else if digits > 0 && text[digits..].starts_with(". ") {
+        digits + 2
+    } else if let Some(at) = text
+        .find(" - ")
+        .filter(|&at| !text[..at].trim_end().contains(' '))
+    {
+        text[..at].chars().count() + 3
+    } else {
+        0
+    };
+    let hang = " ".repeat(indent + marker);
+
+    // Words, with a backticked span kept as one
+    let mut words: Vec<String> = Vec::new();
+    let mut open = false;
+    for word in text.split(' ') {
+        match words.last_mut() {
+            Some(last) if open => {
+                last.push(' ');
+                last.push_str(word);
+            }
+            _ => words.push(word.to_string()),
+        }
+        if word.matches('`').count() % 2 == 1 {
+            open = !open;
+        }
+    }
+
+    let mut lines = Vec::new();
+    let mut current = line[..indent].to_string();
+    let mut empty = true;
+    for word in words {
+        let fits = current.chars().count() + 1 + word.chars().count() <= width;
+        if !empty && !word.is_empty() && !fits {
+            lines.push(current);
+            current = hang.clone();
+            empty = true;
+        }
+        if !empty {
+            current.push(' ');
+        }
+        current.push_str(&word);
+        empty = empty && word.is_empty();
+    }
+    lines.push(current);
+    lines
+}
+
+/// Indented four spaces, a comment or an attribute, or a line ending like a
+/// statement or a block: code a lesson shows, not prose to wrap
+fn looks_like_code(line: &str) -> bool {
+    let trimmed = line.trim();
+    line.starts_with("    ")
+        || line.starts_with('\t')
+        || trimmed.starts_with("//")
+        || trimmed.starts_with("#[")
+        || trimmed.starts_with('}')
+        || trimmed.ends_with(['{', '}', ';'])
+}
+
+/// Whether the last lesson line printed was blank, for `--quiet`
+static LAST_LINE_BLANK: AtomicBool = AtomicBool::new(false);
+
+/// The numbered section headings printed, like `3. Slices:`, in order
+static HEADINGS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn remember_headings(text: &str) {
+    let headings = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()) && is_heading(line));
+    if let Ok(mut remembered) = HEADINGS.lock() {
+        remembered.extend(headings.map(String::from));
+    }
+}
+
+/// The numbered section headings printed since `clear_headings`, which tell
+/// which sections of a lesson ran
+pub fn headings() -> Vec<String> {
+    HEADINGS
+        .lock()
+        .map(|headings| headings.clone())
+        .unwrap_or_default()
+}
+
+/// The last of `headings`: the section running now
+pub fn last_heading() -> Option<String> {
+    HEADINGS.lock().ok()?.last().cloned()
+}
+
+pub fn clear_headings() {
+    if let Ok(mut headings) = HEADINGS.lock() {
+        headings.clear();
+    }
+}
+
+/// The lesson running now, whose sections `print_sources` shows
+static RUNNING: Mutex<Option<Lesson>> = Mutex::new(None);
+
+/// Note which lesson is running (`None` once it has finished), so its
+/// sections' source can be shown above their headings
+pub fn set_running(lesson: Option<&Lesson>) {
+    match lesson {
+        Some(lesson) => events::emit(
+            "lesson_start",
+            vec![
+                ("lesson", lesson.name.into()),
+                ("title", lesson.title.into()),
+            ],
+        ),
+        None => {
+            checkpoint::lesson_finished();
+            events::end_section();
+        }
+    }
+    if let Ok(mut running) = RUNNING.lock() {
+        *running = lesson.copied();
+    }
+}
+
+/// Tell `events`, and `checkpoint` when lessons print (`ask`), about each
+/// section of the running lesson whose heading is in `text`, before the
+/// heading prints
+fn start_sections(text: &str, ask: bool) {
+    let Some(running) = RUNNING.lock().ok().and_then(|running| *running) else {
+        return;
+    };
+    for line in text.lines().map(str::trim) {
+        if let Some(section) = lesson::section_by_heading(running.sections, line) {
+            if ask {
+                checkpoint::section_started(&running, section);
+            }
+            events::section_start(running.name, section.name, section.title);
+        }
+    }
+}
+
+/// With `show_source`, print the function behind each section heading in
+/// `text`, before the heading itself
+fn print_sources(text: &str) {
+    if !settings().show_source {
+        return;
+    }
+    let Some(running) = RUNNING.lock().ok().and_then(|running| *running) else {
+        return;
+    };
+    for line in text.lines().map(str::trim) {
+        let Some(section) = lesson::section_by_heading(running.sections, line) else {
+            continue;
+        };
+        let Some(source) = running.section_source(section) else {
+            continue;
+        };
+        let label = format!("--- Source: {}::{} ---", running.name, section.name);
+        let source = highlight::ansi(source.trim_end());
+        println!("\n{}\n{}\n{}", dim(&label), source, dim("---"));
+    }
+}
+
+/// Print explanation text, the way `lesson_line` does, unless the verbosity
+/// is below `level`. Lessons call this through `explain!`, `detail!` and `deep!`.
+pub fn explanation(level: Verbosity, args: fmt::Arguments) {
+    if settings().verbosity >= level {
+        print_lesson_text(&args.to_string(), true);
+    }
+}
+
+/// `line` colored as a `kind` line (`line_kind`, or the kind of the English
+/// line it translates)
+fn render(line: &str, kind: LineKind, palette: &Palette) -> String {
+    match kind {
+        kind @ (LineKind::Bullet | LineKind::Text) => {
+            highlight_code(line, palette.code, palette.line(kind))
+        }
+        kind => style(line, palette.line(kind)),
+    }
+}
+
+/// `=== Ownership Learning Examples ===` or `3. Slices:`
+fn is_heading(line: &str) -> bool {
+    if line.starts_with("===") && line.ends_with("===") && line.len() > 6 {
+        return true;
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && line[digits..].starts_with(". ") && line.ends_with(':')
+}
+
+/// `KEY POINTS:` or `THE LAYOUT (#[repr(C)] keeps field order):`
+fn is_subheading(line: &str) -> bool {
+    let Some(label) = line.strip_suffix(':') else {
+        return false;
+    };
+    let first_word = label.split(' ').next().unwrap_or("");
+    let shouting = |text: &str| {
+        text.chars().any(|c| c.is_ascii_uppercase()) && !text.chars().any(|c| c.is_lowercase())
+    };
+    shouting(label) || (first_word.len() >= 2 && shouting(first_word))
+}
+
+/// Color `backticked` spans with `code`, the rest of the line with `rest`
+fn highlight_code(line: &str, code: &str, rest: &str) -> String {
+    // An unmatched backtick is just a character
+    if line.matches('`').count() % 2 == 1 {
+        return style(line, rest);
+    }
+
+    let mut out = String::new();
+    for (index, part) in line.split('`').enumerate() {
+        if index % 2 == 1 {
+            out.push_str(&style(&format!("`{}`", part), code));
+        } else {
+            out.push_str(&style(part, rest));
+        }
+    }
+    out
+}
+
+fn style(text: &str, code: &str) -> String {
+    if code.is_empty() || text.is_empty() {
+        text.to_string()
+    } else {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_by_line_kind() {
+        let palette = Theme::Dark.palette();
+        let render = |line| render(line, line_kind(line), &palette);
+        let heading = render("3. Slices:");
+        assert_eq!(heading, "\x1b[1;36m3. Slices:\x1b[0m");
+        assert!(render("KEY POINTS:").starts_with("\x1b[1;33m"));
+        assert!(render("THE LAYOUT (#[repr(C)] keeps field order):").starts_with("\x1b[1;33m"));
+        assert!(render("==========").starts_with("\x1b[2m"));
+        // Program output is left alone, apart from code in backticks
+        assert_eq!(render("Sum: 27"), "Sum: 27");
+        assert_eq!(render("Enter a number:"), "Enter a number:");
+        assert_eq!(render("use `Vec` here"), "use \x1b[32m`Vec`\x1b[0m here");
+        assert_eq!(render("a ` b"), "a ` b");
+    }
+
+    #[test]
+    fn test_wrap() {
+        assert_eq!(
+            wrap(
+                "  - Each value has one owner, and `the value is dropped` here",
+                40
+            ),
+            [
+                "  - Each value has one owner, and",
+                "    `the value is dropped` here",
+            ]
+        );
+        assert_eq!(
+            wrap("mimalloc   - fast for many small objects", 30),
+            ["mimalloc   - fast for many", "             small objects"]
+        );
+        let code = "unsafe impl<A: GlobalAlloc> GlobalAlloc for CountingAlloc<A> {";
+        assert_eq!(wrap(code, 20), [code]);
+        assert_eq!(wrap("short", 20), ["short"]);
+    }
+
+    #[test]
+    fn test_status_pads_by_columns() {
+        // Before `init`: emoji, and no color
+        assert_eq!(status(Glyph::Fail, "no", green, 8), "\u{274c} no   ");
+        assert_eq!(status(Glyph::Ok, "ok", green, 0), "\u{2705} ok");
+    }
+
+    #[test]
+    fn test_verbosity_from_flags() {
+        assert_eq!(Verbosity::from_flags(0, 0), Verbosity::Normal);
+        assert_eq!(Verbosity::from_flags(1, 0), Verbosity::Verbose);
+        assert_eq!(Verbosity::from_flags(3, 0), Verbosity::VeryVerbose);
+        assert_eq!(Verbosity::from_flags(2, 1), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_flags(0, 2), Verbosity::Summary);
+        assert!(Verbosity::Summary < Verbosity::Quiet);
+        assert!(Verbosity::Quiet < Verbosity::Normal);
+    }
+}
@@ -0,0 +1,521 @@
+//! Content Packs
+//!
+//! Questions and exercises a teacher publishes for the whole class, as a
+//! directory laid out like this:
+//!
+//! ```text
+//! questions/         quiz banks, .toml or .json (see `question_bank`)
+//! exercises/<lesson>/<name>.rs
+//! exercises/<lesson>/solutions/<name>/<approach>.rs   curated solutions
+//! ```
+//!
+//! Packs live in `packs/<name>` under the data directory, and `packs.txt`
+//! there lists the registered ones, one `name url` line each. Only a pack
+//! that passes `lint` is registered: the quiz then adds its questions, and
+//! `verify` its exercises. Fetching a pack is the CLI's job (`pack add git`,
+//! feature `git`); this module knows the layout. `removal` plans taking one
+//! out again, for `pack remove`.
+//!
+//! A learner works on a pack's exercises where they are, so `pack add` also
+//! pins a copy of each as the teacher wrote it, in `.skeletons/<name>` next
+//! to the packs (`pin`), for `exercise reset` to go back to.
+//!
+//! A pack's `exercises/ownership/` is its version of the ownership lesson's
+//! exercises, `week-3/ownership`; the ones shipped with rust-learn are
+//! `builtin/ownership`. When several sources have the same lesson, `merge`
+//! picks one by `pack_precedence` under `[profile]` in config.toml, highest
+//! first, and the rest are shadowed:
+//!
+//! ```toml
+//! [profile]
+//! pack_precedence = ["week-3", "builtin"]
+//! ```
+//!
+//! A lesson none of whose sources is listed is a collision, and an error,
+//! so a pack never replaces exercises by accident. `source/lesson` always
+//! names one version, shadowed or not.
+
+use crate::exercises::{self, Exercise, NOT_DONE};
+use crate::lesson::Lesson;
+use crate::question_bank;
+use crate::transaction::Transaction;
+use crate::{dirs, settings};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const INDEX_FILE: &str = "packs.txt";
+const QUESTIONS_DIR: &str = "questions";
+const EXERCISES_DIR: &str = "exercises";
+/// Under `root`; no pack can be named after it, as it starts with a `.`
+const SKELETONS_DIR: &str = ".skeletons";
+
+/// The source name of the exercises shipped with rust-learn, in
+/// `pack_precedence` and `builtin/<lesson>`; no pack can have it
+pub const BUILTIN: &str = "builtin";
+
+/// A registered pack
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pack {
+    pub name: String,
+    /// Where it was fetched from
+    pub url: String,
+    pub path: PathBuf,
+}
+
+impl Pack {
+    /// Its quiz banks, if it has any
+    pub fn questions(&self) -> Option<PathBuf> {
+        Some(self.path.join(QUESTIONS_DIR)).filter(|dir| dir.is_dir())
+    }
+
+    /// Its exercises, if it has any
+    pub fn exercises(&self) -> Option<PathBuf> {
+        Some(self.path.join(EXERCISES_DIR)).filter(|dir| dir.is_dir())
+    }
+}
+
+/// `packs` in the data directory, where every pack is kept
+pub fn root() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join("packs"))
+}
+
+/// The registered packs; none without a data directory or an index
+pub fn list() -> Vec<Pack> {
+    root().map(|root| list_in(&root)).unwrap_or_default()
+}
+
+/// The packs registered in `root`'s index, in the order they were added
+pub fn list_in(root: &Path) -> Vec<Pack> {
+    let text = fs::read_to_string(root.join(INDEX_FILE)).unwrap_or_default();
+    text.lines()
+        .filter_map(|line| line.trim().split_once(' '))
+        .map(|(name, url)| Pack {
+            name: name.to_string(),
+            url: url.trim().to_string(),
+            path: root.join(name),
+        })
+        .collect()
+}
+
+/// Add `name` to `root`'s index, or update its url if it is there already
+pub fn register(root: &Path, name: &str, url: &str) -> io::Result<()> {
+    let mut packs = list_in(root);
+    match packs.iter_mut().find(|pack| pack.name == name) {
+        Some(pack) => pack.url = url.to_string(),
+        None => packs.push(Pack {
+            name: name.to_string(),
+            url: url.to_string(),
+            path: root.join(name),
+        }),
+    }
+    fs::create_dir_all(root)?;
+    fs::write(root.join(INDEX_FILE), index_text(&packs))
+}
+
+/// Where `pin` keeps the pack `name`'s exercises as fetched, laid out like
+/// its `exercises/` directory
+pub fn pinned(root: &Path, name: &str) -> PathBuf {
+    root.join(SKELETONS_DIR).join(name)
+}
+
+/// Pin `skeletons`, each `(<lesson>/<name>.rs, text)`, as the pack `name`'s
+/// exercises as fetched, in place of any pinned before
+pub fn pin(root: &Path, name: &str, skeletons: &[(String, String)]) -> io::Result<()> {
+    let dir = pinned(root, name);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    for (file, text) in skeletons {
+        let path = dir.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, text)?;
+    }
+    Ok(())
+}
+
+/// Plan removing the pack `name` from `root`: its directory, its pinned
+/// exercises, and its line in the index. `None` when no such pack is
+/// registered
+pub fn removal(root: &Path, name: &str) -> io::Result<Option<Transaction>> {
+    let mut packs = list_in(root);
+    let Some(index) = packs.iter().position(|pack| pack.name == name) else {
+        return Ok(None);
+    };
+    let pack = packs.remove(index);
+    let mut transaction = Transaction::new();
+    for dir in [pack.path.clone(), pinned(root, name)] {
+        if dir.exists() {
+            transaction.remove(dir)?;
+        }
+    }
+    transaction.write(root.join(INDEX_FILE), index_text(&packs));
+    Ok(Some(transaction))
+}
+
+fn index_text(packs: &[Pack]) -> String {
+    packs
+        .iter()
+        .map(|pack| format!("{} {}\n", pack.name, pack.url))
+        .collect()
+}
+
+/// The name a pack from `url` is kept under: the last part of the url
+/// without `.git`, like `week-3` for `https://git.school.example/rust/week-3.git`.
+/// `None` when that is not letters, digits, `.`, `-` and `_`
+pub fn name_from_url(url: &str) -> Option<String> {
+    let last = url.trim_end_matches('/').rsplit(['/', ':', '\\']).next()?;
+    let name = last.strip_suffix(".git").unwrap_or(last);
+    let valid = !name.is_empty()
+        && name != BUILTIN
+        && !name.starts_with(['.', '-'])
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    valid.then(|| name.to_string())
+}
+
+/// One source's exercises for one lesson
+#[derive(Debug, Clone)]
+pub struct LessonSet {
+    /// `builtin`, or the pack's name
+    pub source: String,
+    pub lesson: String,
+    pub exercises: Vec<Exercise>,
+}
+
+impl LessonSet {
+    /// `source/lesson`, like `week-3/ownership`
+    pub fn id(&self) -> String {
+        format!("{}/{}", self.source, self.lesson)
+    }
+}
+
+/// One set of exercises per lesson, after `merge`
+#[derive(Debug, Default)]
+pub struct Merged {
+    /// The sets in use, in lesson order
+    pub active: Vec<LessonSet>,
+    /// Each set left out, with the id of the one used instead
+    pub shadowed: Vec<(LessonSet, String)>,
+}
+
+/// `pack_precedence` under `[profile]` in config.toml
+pub fn precedence() -> Vec<String> {
+    settings::get()
+        .table("profile")
+        .and_then(|profile| profile.strings("pack_precedence"))
+        .unwrap_or_default()
+        .into_iter()
+        .map(String::from)
+        .collect()
+}
+
+/// Every lesson's exercises in `builtin` (the shipped `exercises/`
+/// directory) and in each pack, lesson by lesson in `order` like
+/// `exercises::find`; for one lesson, the built-in set comes first, then
+/// the packs in the order they were added
+pub fn lesson_sets(builtin: Option<&Path>, packs: &[Pack], order: &[&str]) -> Vec<LessonSet> {
+    let sources = builtin
+        .map(|dir| (BUILTIN.to_string(), dir.to_path_buf()))
+        .into_iter()
+        .chain(
+            packs
+                .iter()
+                .filter_map(|pack| Some((pack.name.clone(), pack.exercises()?))),
+        );
+    let mut sets: Vec<LessonSet> = Vec::new();
+    for (source, dir) in sources {
+        let pack = (source != BUILTIN).then(|| source.clone());
+        for mut exercise in exercises::find(&dir, order).unwrap_or_default() {
+            exercise.pack = pack.clone();
+            match sets.last_mut() {
+                Some(set) if set.source == source && set.lesson == exercise.lesson => {
+                    set.exercises.push(exercise)
+                }
+                _ => sets.push(LessonSet {
+                    source: source.clone(),
+                    lesson: exercise.lesson.clone(),
+                    exercises: vec![exercise],
+                }),
+            }
+        }
+    }
+    let rank = |lesson: &str| {
+        order
+            .iter()
+            .position(|name| *name == lesson)
+            .unwrap_or(order.len())
+    };
+    // Stable, so each lesson's sources stay in the order they were read
+    sets.sort_by(|a, b| (rank(&a.lesson), &a.lesson).cmp(&(rank(&b.lesson), &b.lesson)));
+    sets
+}
+
+/// Pick one set for each lesson out of `sets`: the only one, or the one
+/// whose source comes first in `precedence`. A lesson in several sources,
+/// none of them in `precedence`, is a collision; the errors name each one
+pub fn merge(sets: Vec<LessonSet>, precedence: &[String]) -> Result<Merged, Vec<String>> {
+    let rank = |source: &str| precedence.iter().position(|name| name == source);
+    let mut merged = Merged::default();
+    let mut errors = Vec::new();
+    let mut sets = sets.into_iter().peekable();
+    while let Some(first) = sets.next() {
+        let mut same = vec![first];
+        while let Some(next) = sets.next_if(|next| next.lesson == same[0].lesson) {
+            same.push(next);
+        }
+        if same.len() == 1 {
+            merged.active.extend(same);
+            continue;
+        }
+        let winner = same
+            .iter()
+            .enumerate()
+            .filter_map(|(index, set)| Some((rank(&set.source)?, index)))
+            .min()
+            .map(|(_, index)| index);
+        let Some(winner) = winner else {
+            let ids: Vec<String> = same.iter().map(LessonSet::id).collect();
+            errors.push(format!(
+                "{} has exercises in {}: list the one to use first in `pack_precedence` under [profile] in config.toml, or pick one by name ({})",
+                same[0].lesson,
+                sources_text(&same),
+                ids.join(", ")
+            ));
+            continue;
+        };
+        let used = same.remove(winner);
+        let id = used.id();
+        merged.active.push(used);
+        merged
+            .shadowed
+            .extend(same.into_iter().map(|set| (set, id.clone())));
+    }
+    if errors.is_empty() {
+        Ok(merged)
+    } else {
+        Err(errors)
+    }
+}
+
+/// `builtin and week-3`, or `builtin, week-3 and extra`
+fn sources_text(sets: &[LessonSet]) -> String {
+    let names: Vec<&str> = sets.iter().map(|set| set.source.as_str()).collect();
+    match names.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {}", rest.join(", "), last),
+        _ => names.join(""),
+    }
+}
+
+/// What is wrong with the pack at `dir`, checked against `lessons`: its
+/// questions like any bank (`question_bank::load`), and each exercise for a
+/// lesson that exists and a `// I AM NOT DONE` line. Empty when it is fine
+pub fn lint(dir: &Path, lessons: &[Lesson]) -> Vec<String> {
+    let questions = dir.join(QUESTIONS_DIR);
+    let exercises_dir = dir.join(EXERCISES_DIR);
+    if !questions.is_dir() && !exercises_dir.is_dir() {
+        return vec![format!(
+            "{} has neither a {}/ nor an {}/ directory",
+            dir.display(),
+            QUESTIONS_DIR,
+            EXERCISES_DIR
+        )];
+    }
+    let mut problems = Vec::new();
+    if questions.is_dir()
+        && let Err(errors) = question_bank::load(&questions, lessons)
+    {
+        problems.extend(errors);
+    }
+    if exercises_dir.is_dir() {
+        match exercises::find(&exercises_dir, &[]) {
+            Ok(found) => {
+                for exercise in found {
+                    problems.extend(exercise_problem(&exercise, lessons));
+                }
+            }
+            Err(error) => problems.push(format!(
+                "cannot read {}: {}",
+                exercises_dir.display(),
+                error
+            )),
+        }
+    }
+    problems
+}
+
+fn exercise_problem(exercise: &exercises::Exercise, lessons: &[Lesson]) -> Option<String> {
+    let path = exercise.path.display();
+    if !lessons.iter().any(|lesson| lesson.name == exercise.lesson) {
+        return Some(format!(
+            "{}: there is no lesson named {}",
+            path, exercise.lesson
+        ));
+    }
+    match fs::read_to_string(&exercise.path) {
+        Ok(source) if exercises::is_done(&source) => Some(format!(
+            "{}: has no `{}` line, so it would pass untouched",
+            path, NOT_DONE
+        )),
+        Ok(_) => None,
+        Err(error) => Some(format!("cannot read {}: {}", path, error)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_from_url() {
+        let name = |url| name_from_url(url);
+        assert_eq!(
+            name("https://git.school.example/rust/week-3.git").as_deref(),
+            Some("week-3")
+        );
+        assert_eq!(
+            name("git@git.school.example:rust/pack_1/").as_deref(),
+            Some("pack_1")
+        );
+        assert_eq!(name("/srv/packs/extra").as_deref(), Some("extra"));
+        assert_eq!(name("https://git.school.example/.git"), None);
+        assert_eq!(name("https://git.school.example/builtin.git"), None);
+        assert_eq!(name("https://git.school.example/a%20b"), None);
+    }
+
+    #[test]
+    fn test_register_and_lint() {
+        let root = std::env::temp_dir().join(format!("rust-learn-packs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        register(&root, "week-3", "https://example.com/old.git").unwrap();
+        register(&root, "extra", "/srv/extra").unwrap();
+        register(&root, "week-3", "https://example.com/week-3.git").unwrap();
+        let packs = list_in(&root);
+        let names: Vec<(&str, &str)> = packs
+            .iter()
+            .map(|pack| (pack.name.as_str(), pack.url.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                ("week-3", "https://example.com/week-3.git"),
+                ("extra", "/srv/extra")
+            ]
+        );
+
+        let lessons = [Lesson {
+            name: "loops",
+            title: "Loops",
+            run: || {},
+            sections: &[],
+            source: "",
+            chapter: 1,
+            requires: &[],
+            difficulty: crate::lesson::Difficulty::Beginner,
+            tags: &[],
+            changelog: &[],
+            questions: &[],
+            snippets: &[],
+        }];
+        let pack = &packs[0];
+        assert_eq!(lint(&pack.path, &lessons).len(), 1);
+        let exercises = pack.path.join("exercises");
+        fs::create_dir_all(exercises.join("loops")).unwrap();
+        fs::create_dir_all(exercises.join("nope")).unwrap();
+        fs::write(exercises.join("loops/loops1.rs"), format!("{}\n", NOT_DONE)).unwrap();
+        fs::write(exercises.join("loops/loops2.rs"), "fn main() {}\n").unwrap();
+        fs::write(exercises.join("nope/nope1.rs"), format!("{}\n", NOT_DONE)).unwrap();
+        let problems = lint(&pack.path, &lessons);
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].contains("loops2.rs: has no"));
+        assert!(problems[1].contains("no lesson named nope"));
+        assert!(pack.exercises().is_some() && pack.questions().is_none());
+
+        let skeleton = |text: &str| vec![("loops/loops1.rs".to_string(), text.to_string())];
+        pin(&root, "week-3", &skeleton("old")).unwrap();
+        pin(&root, "week-3", &skeleton(NOT_DONE)).unwrap();
+        let pinned_file = pinned(&root, "week-3").join("loops/loops1.rs");
+        assert_eq!(fs::read_to_string(&pinned_file).unwrap(), NOT_DONE);
+        assert_eq!(list_in(&root).len(), 2, "pinned copies are not packs");
+
+        assert!(removal(&root, "missing").unwrap().is_none());
+        let transaction = removal(&root, "week-3").unwrap().unwrap();
+        assert_eq!(transaction.changes().len(), 3);
+        assert!(pack.path.exists());
+        transaction.apply().unwrap();
+        assert!(!pack.path.exists() && !pinned_file.exists());
+        assert_eq!(list_in(&root).len(), 1);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_merge() {
+        let root = std::env::temp_dir().join(format!("rust-learn-merge-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for file in [
+            "builtin/loops/loops1.rs",
+            "builtin/ownership/ownership1.rs",
+            "week-3/exercises/ownership/ownership1.rs",
+            "extra/exercises/ownership/borrow1.rs",
+            "extra/exercises/vectors/vectors1.rs",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let packs: Vec<Pack> = ["week-3", "extra"]
+            .iter()
+            .map(|name| Pack {
+                name: name.to_string(),
+                url: String::new(),
+                path: root.join(name),
+            })
+            .collect();
+        let order = ["loops", "ownership", "vectors"];
+        let sets = lesson_sets(Some(&root.join("builtin")), &packs, &order);
+        let ids = |sets: &[LessonSet]| sets.iter().map(LessonSet::id).collect::<Vec<_>>();
+        assert_eq!(
+            ids(&sets),
+            [
+                "builtin/loops",
+                "builtin/ownership",
+                "week-3/ownership",
+                "extra/ownership",
+                "extra/vectors"
+            ]
+        );
+
+        let errors = merge(sets.clone(), &[]).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("ownership has exercises in builtin, week-3 and extra:"));
+
+        let merged = merge(sets.clone(), &["week-3".to_string()]).unwrap();
+        assert_eq!(
+            ids(&merged.active),
+            ["builtin/loops", "week-3/ownership", "extra/vectors"]
+        );
+        let shadowed: Vec<(String, &str)> = merged
+            .shadowed
+            .iter()
+            .map(|(set, by)| (set.id(), by.as_str()))
+            .collect();
+        assert_eq!(
+            shadowed,
+            [
+                ("builtin/ownership".to_string(), "week-3/ownership"),
+                ("extra/ownership".to_string(), "week-3/ownership")
+            ]
+        );
+        let exercise = &merged.active[1].exercises[0];
+        assert_eq!(exercise.key(), "exercise::week-3/ownership1");
+        assert_eq!(merged.active[0].exercises[0].key(), "exercise::loops1");
+
+        let precedence = ["extra".to_string(), BUILTIN.to_string()];
+        let merged = merge(sets, &precedence).unwrap();
+        assert_eq!(merged.active[1].id(), "extra/ownership");
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
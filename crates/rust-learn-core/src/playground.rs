@@ -0,0 +1,823 @@
+//! Rust Playground Links
+//!
+//! `rust-learn share ownership 5` turns a section into a program that runs
+//! on its own at play.rust-lang.org: the section's function, the lesson's
+//! other top-level items it uses (functions, types with their `impl`s,
+//! traits whose methods it calls, constants, statics and `thread_local!`s,
+//! macros, modules, and what those use in turn), the lesson's `use` lines
+//! for std and for the crates the playground has, stand-ins for `explain!`,
+//! `detail!` and `deep!` that just print, and a `main` calling the section,
+//! on a Tokio runtime when it is an `async fn`. The program goes into the
+//! link itself, percent-encoded, so nothing is uploaded until the learner
+//! opens it.
+//!
+//! The source is read line by line, past comments and string literals (raw
+//! ones too), so code quoted in a string is not taken for an item. A section
+//! that needs rust-learn's own crates (reading input, running cargo) or reads
+//! files as it builds could not compile there, so `program` says so instead.
+
+use crate::lesson::{Lesson, Section};
+use std::ops::Range;
+
+const PLAYGROUND: &str = "https://play.rust-lang.org/";
+
+/// Crates the lessons use that the playground has as well
+const PLAYGROUND_CRATES: &[&str] = &["std", "core", "alloc", "tokio", "tracing"];
+
+/// Items that only register the lesson with rust-learn
+const REGISTRATION: &[&str] = &["SECTIONS", "QUESTIONS", "SNIPPETS"];
+
+/// Paths into rust-learn itself
+const OWN_PATHS: &[&str] = &[
+    "crate",
+    "super",
+    "rust_learn_core",
+    "rust_learn_utils",
+    "rust_learn_content",
+    "rust_learn_ffi",
+];
+
+/// Macros that read files or the build's environment as the program builds
+const BUILD_READS: &[&str] = &["include_str!", "include_bytes!", "include!", "env!"];
+
+/// Printing macros the lessons use besides std's
+const PRELUDE: &str = "\
+macro_rules! explain { ($($arg:tt)*) => { println!($($arg)*) }; }
+macro_rules! detail { ($($arg:tt)*) => { println!($($arg)*) }; }
+macro_rules! deep { ($($arg:tt)*) => { println!($($arg)*) }; }
+";
+
+/// `section` of `lesson` as a whole program, or why there is none: the
+/// section has no function of its name at the top of the source, or it
+/// needs what only rust-learn has
+pub fn program(lesson: &Lesson, section: &Section) -> Result<String, String> {
+    let about = format!("{}::{}", lesson.name, section.name);
+    let items: Vec<Item> = items(lesson.source)
+        .into_iter()
+        .filter(|item| !item.registers() && !item.test_only())
+        .collect();
+    let uses = uses(lesson.source);
+    let first = items
+        .iter()
+        .position(|item| item.kind == Kind::Fn && item.name == section.name)
+        .ok_or_else(|| format!("{} has no function of that name to share", about))?;
+    let methods: Vec<Vec<&str>> = items.iter().map(trait_methods).collect();
+    let mut included = vec![false; items.len()];
+    let mut used = vec![false; uses.len()];
+    included[first] = true;
+    // Whatever the included items mention, until nothing new turns up
+    let code = loop {
+        let code: Vec<String> = items
+            .iter()
+            .zip(&included)
+            .filter(|(_, included)| **included)
+            .map(|(item, _)| without_text(item.source))
+            .collect();
+        let mut added = false;
+        for (index, use_) in uses.iter().enumerate() {
+            if !used[index] && code.iter().any(|code| use_.is_used_in(code)) {
+                used[index] = true;
+                added = true;
+            }
+        }
+        for (index, item) in items.iter().enumerate() {
+            if included[index] {
+                continue;
+            }
+            let wanted = match item.kind {
+                Kind::Impl => items
+                    .iter()
+                    .zip(&included)
+                    .any(|(other, included)| *included && other.name == item.name),
+                // A module also goes along when a used `use` starts there
+                Kind::Mod => {
+                    code.iter().any(|code| mentions(code, item.name))
+                        || uses
+                            .iter()
+                            .zip(&used)
+                            .any(|(use_, used)| *used && use_.root == item.name)
+                }
+                _ => code.iter().any(|code| {
+                    mentions(code, item.name)
+                        || methods[index]
+                            .iter()
+                            .any(|method| code.contains(&format!(".{}(", method)))
+                }),
+            };
+            if wanted {
+                included[index] = true;
+                added = true;
+            }
+        }
+        if !added {
+            break code.join("\n");
+        }
+    };
+
+    let modules: Vec<&str> = items
+        .iter()
+        .zip(&included)
+        .filter(|(item, included)| **included && item.kind == Kind::Mod)
+        .map(|(item, _)| item.name)
+        .collect();
+    let mut own: Vec<&str> = uses
+        .iter()
+        .zip(&used)
+        .filter(|(use_, used)| {
+            **used && !PLAYGROUND_CRATES.contains(&use_.root) && !modules.contains(&use_.root)
+        })
+        .flat_map(|(use_, _)| use_.names_used_in(&code))
+        .collect();
+    own.extend(OWN_PATHS.iter().filter(|path| code_uses_path(&code, path)));
+    if !own.is_empty() {
+        own.dedup();
+        return Err(format!(
+            "{} uses rust-learn's own code ({}), which the playground does not have",
+            about,
+            own.join(", ")
+        ));
+    }
+    let reads: Vec<&str> = BUILD_READS
+        .iter()
+        .copied()
+        .filter(|read| {
+            code.match_indices(read)
+                .any(|(at, _)| whole_word(&code, at, read.len() - 1))
+        })
+        .collect();
+    if !reads.is_empty() {
+        return Err(format!(
+            "{} reads rust-learn's files as it builds ({}), which the playground cannot",
+            about,
+            reads.join(", ")
+        ));
+    }
+
+    let mut program = format!(
+        "// {}::{} from rust-learn: {}\n#![allow(unused)]\n\n{}",
+        lesson.name, section.name, section.title, PRELUDE
+    );
+    let kept: Vec<&Use> = uses
+        .iter()
+        .zip(&used)
+        .filter(|(use_, used)| {
+            PLAYGROUND_CRATES.contains(&use_.root) || (**used && modules.contains(&use_.root))
+        })
+        .map(|(use_, _)| use_)
+        .collect();
+    if kept.iter().any(|use_| use_.root == "alloc") {
+        program.push_str("\nextern crate alloc;\n");
+    }
+    if !kept.is_empty() {
+        program.push('\n');
+        for use_ in kept {
+            program.push_str(use_.source);
+            program.push('\n');
+        }
+    }
+    for (item, _) in items
+        .iter()
+        .zip(&included)
+        .filter(|(_, included)| **included)
+    {
+        program.push('\n');
+        program.push_str(item.source.trim_end());
+        program.push('\n');
+    }
+    if items[first].is_async() {
+        program.push_str(&format!(
+            "\n#[tokio::main]\nasync fn main() {{\n    {}().await;\n}}\n",
+            section.name
+        ));
+    } else {
+        program.push_str(&format!("\nfn main() {{\n    {}();\n}}\n", section.name));
+    }
+    Ok(program)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Kind {
+    Fn,
+    /// A struct, enum, trait, type alias, const, static, `thread_local!` or
+    /// `macro_rules!`
+    Type,
+    /// An `impl` block, named after the type it is for, or after its trait
+    /// for a blanket impl like `impl<T: Display> Shout for T`
+    Impl,
+    /// A `mod name { ... }` written out in the lesson
+    Mod,
+}
+
+/// A top-level item of a lesson's source, with the comments and attributes
+/// above it
+struct Item<'a> {
+    kind: Kind,
+    name: &'a str,
+    source: &'a str,
+}
+
+impl Item<'_> {
+    /// `SECTIONS` and the like, and the lesson's own function that runs them
+    fn registers(&self) -> bool {
+        match self.kind {
+            Kind::Type => REGISTRATION.contains(&self.name),
+            Kind::Fn => mentions(&without_text(self.source), "SECTIONS"),
+            _ => false,
+        }
+    }
+
+    /// Whether a `#[cfg(...)]` above it keeps it to test builds
+    fn test_only(&self) -> bool {
+        self.source.lines().any(|line| {
+            line.starts_with("#[cfg(") && mentions(line, "test") && !line.contains("not(")
+        })
+    }
+
+    fn is_async(&self) -> bool {
+        self.source
+            .lines()
+            .find(|line| !line.starts_with("//") && !line.starts_with("#["))
+            .is_some_and(|line| line.contains("async fn "))
+    }
+}
+
+/// A top-level `use` statement: the path it starts with, and the names it
+/// brings in
+struct Use<'a> {
+    root: &'a str,
+    names: Vec<&'a str>,
+    source: &'a str,
+}
+
+impl<'a> Use<'a> {
+    /// The names `code` uses: a type or constant anywhere as a word, a
+    /// function, module or macro only where it is called or a path goes on,
+    /// so a local variable of the same name does not count
+    fn names_used_in(&self, code: &str) -> Vec<&'a str> {
+        self.names
+            .iter()
+            .copied()
+            .filter(|name| uses_name(code, name))
+            .collect()
+    }
+
+    fn is_used_in(&self, code: &str) -> bool {
+        self.names.iter().any(|name| uses_name(code, name))
+    }
+}
+
+/// The byte ranges of `source`'s comments, and of its string and character
+/// literals; `true` for a comment
+fn literals(source: &str) -> Vec<(Range<usize>, bool)> {
+    let bytes = source.as_bytes();
+    let identifier = |at: usize| {
+        at > 0 && {
+            let c = bytes[at - 1];
+            c.is_ascii_alphanumeric() || c == b'_'
+        }
+    };
+    let mut literals = Vec::new();
+    let mut at = 0;
+    while at < bytes.len() {
+        let rest = &source[at..];
+        let found = if rest.starts_with("//") {
+            Some((rest.find('\n').map_or(source.len(), |end| at + end), true))
+        } else if rest.starts_with("/*") {
+            // Block comments nest
+            let mut depth = 0;
+            let mut end = at;
+            while end < bytes.len() {
+                if source[end..].starts_with("/*") {
+                    depth += 1;
+                    end += 2;
+                } else if source[end..].starts_with("*/") {
+                    depth -= 1;
+                    end += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    end += 1;
+                }
+            }
+            Some((end.min(source.len()), true))
+        } else if let Some(hashes) = raw_string_start(rest).filter(|_| !identifier(at)) {
+            // `r#"...a "quote" inside..."#`: no escapes, ends at `"` and as
+            // many `#` as it began with
+            let open = rest.find('"').unwrap_or(0) + 1;
+            let close = format!("\"{}", "#".repeat(hashes));
+            Some((
+                rest[open..]
+                    .find(&close)
+                    .map_or(source.len(), |end| at + open + end + close.len()),
+                false,
+            ))
+        } else if bytes[at] == b'"' {
+            let mut end = at + 1;
+            while end < bytes.len() && bytes[end] != b'"' {
+                end += if bytes[end] == b'\\' { 2 } else { 1 };
+            }
+            Some(((end + 1).min(source.len()), false))
+        } else if bytes[at] == b'\'' {
+            // A character, or else a lifetime or label
+            let inner = &rest[1..];
+            if inner.starts_with('\\') {
+                inner[2..]
+                    .find('\'')
+                    .map(|end| (at + 1 + 2 + end + 1, false))
+            } else {
+                inner.chars().next().and_then(|c| {
+                    inner[c.len_utf8()..]
+                        .starts_with('\'')
+                        .then(|| (at + 1 + c.len_utf8() + 1, false))
+                })
+            }
+        } else {
+            None
+        };
+        match found {
+            Some((end, comment)) => {
+                literals.push((at..end, comment));
+                at = end;
+            }
+            None => at += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    literals
+}
+
+/// How many `#` the raw string `text` starts with has, when it starts with one
+fn raw_string_start(text: &str) -> Option<usize> {
+    let rest = text.strip_prefix("br").or_else(|| text.strip_prefix('r'))?;
+    let hashes = rest.len() - rest.trim_start_matches('#').len();
+    rest[hashes..].starts_with('"').then_some(hashes)
+}
+
+/// The lines of `source` with their offsets, and whether each starts outside
+/// `literals`
+fn lines<'a>(source: &'a str, literals: &[(Range<usize>, bool)]) -> Vec<(usize, &'a str, bool)> {
+    let mut offset = 0;
+    source
+        .split_inclusive('\n')
+        .map(|line| {
+            offset += line.len();
+            let start = offset - line.len();
+            let outside = !literals
+                .iter()
+                .any(|(range, _)| range.start < start && start < range.end);
+            (start, line, outside)
+        })
+        .collect()
+}
+
+/// Where the item starting at `start` ends: just past the `}` that closes
+/// its brackets, or a `;` outside them, skipping `literals`
+fn item_end(source: &str, start: usize, literals: &[(Range<usize>, bool)]) -> Option<usize> {
+    let mut depth = 0;
+    let mut at = start;
+    let mut next = literals.partition_point(|(range, _)| range.start < start);
+    while at < source.len() {
+        if let Some((range, _)) = literals.get(next).filter(|(range, _)| range.start == at) {
+            at = range.end;
+            next += 1;
+            continue;
+        }
+        match source.as_bytes()[at] {
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' => depth -= 1,
+            b'}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(at + 1);
+                }
+            }
+            b';' if depth == 0 => return Some(at + 1),
+            _ => {}
+        }
+        at += 1;
+    }
+    None
+}
+
+/// The items that start at the first column, each up to where its brackets
+/// close
+fn items(source: &str) -> Vec<Item<'_>> {
+    let literals = literals(source);
+    let lines = lines(source, &literals);
+    let mut items = Vec::new();
+    let mut index = 0;
+    while index < lines.len() {
+        let (start, line, outside) = lines[index];
+        let Some((kind, mut name)) = item_start(line).filter(|_| outside) else {
+            index += 1;
+            continue;
+        };
+        let mut first = index;
+        while first > 0 && {
+            let (_, above, outside) = lines[first - 1];
+            outside && (above.starts_with("//") || above.starts_with("#["))
+        } {
+            first -= 1;
+        }
+        let Some(end) = item_end(source, start, &literals) else {
+            break;
+        };
+        let last = index
+            + lines[index..]
+                .iter()
+                .position(|(start, line, _)| end <= start + line.len())
+                .unwrap_or(0);
+        let (end, line, _) = lines[last];
+        let source = &source[lines[first].0..end + line.len()];
+        // `thread_local!` goes by the static it declares
+        if name.is_empty() {
+            name = source
+                .lines()
+                .find_map(|line| line.trim_start().split_once("static "))
+                .map_or("", |(_, rest)| word(rest));
+        }
+        items.push(Item { kind, name, source });
+        index = last + 1;
+    }
+    items
+}
+
+/// What kind of item `line` starts, and its name; an empty name for a
+/// `thread_local!`, whose statics follow
+fn item_start(line: &str) -> Option<(Kind, &str)> {
+    let mut line = ["pub(crate) ", "pub(super) ", "pub "]
+        .into_iter()
+        .find_map(|visibility| line.strip_prefix(visibility))
+        .unwrap_or(line);
+    while let Some(rest) = ["async ", "unsafe ", "extern \"C\" "]
+        .into_iter()
+        .find_map(|qualifier| line.strip_prefix(qualifier))
+        .or_else(|| {
+            line.strip_prefix("const ")
+                .filter(|rest| rest.starts_with("fn "))
+        })
+    {
+        line = rest;
+    }
+    if line.starts_with("thread_local!") {
+        return Some((Kind::Type, ""));
+    }
+    if let Some(rest) = line.strip_prefix("macro_rules! ") {
+        return Some((Kind::Type, word(rest)));
+    }
+    if let Some(rest) = line.strip_prefix("impl") {
+        // `impl<T> Trait for Type<T> {`: past the generics, the type is what
+        // follows `for`, or the first path when there is none
+        let mut rest = rest.trim_start();
+        let mut generics = "";
+        if rest.starts_with('<') {
+            let mut depth = 0;
+            let end = rest.find(|c| {
+                depth += match c {
+                    '<' => 1,
+                    '>' => -1,
+                    _ => 0,
+                };
+                depth == 0
+            })?;
+            generics = &rest[1..end];
+            rest = rest[end + 1..].trim_start();
+        }
+        let (implemented, target) = match rest.split_once(" for ") {
+            Some((implemented, target)) => (Some(implemented), word(target)),
+            None => (None, word(rest)),
+        };
+        // A blanket impl goes with its trait: its type is one of its generics
+        if let Some(implemented) = implemented
+            && generics
+                .split(',')
+                .any(|param| word(param.trim()) == target)
+        {
+            let path = implemented.split('<').next().unwrap_or(implemented);
+            return Some((Kind::Impl, word(path.rsplit("::").next().unwrap_or(path))));
+        }
+        return Some((Kind::Impl, target));
+    }
+    let (kind, rest) = [
+        ("fn ", Kind::Fn),
+        ("struct ", Kind::Type),
+        ("enum ", Kind::Type),
+        ("trait ", Kind::Type),
+        ("type ", Kind::Type),
+        ("const ", Kind::Type),
+        ("static ", Kind::Type),
+        ("mod ", Kind::Mod),
+    ]
+    .into_iter()
+    .find_map(|(keyword, kind)| Some((kind, line.strip_prefix(keyword)?)))?;
+    let end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))?;
+    // `mod tests;` is another file
+    if kind == Kind::Mod && !rest[end..].trim_start().starts_with('{') {
+        return None;
+    }
+    Some((kind, &rest[..end]))
+}
+
+/// The `use` statements at the first column, each up to its `;`
+fn uses(source: &str) -> Vec<Use<'_>> {
+    let mut uses = Vec::new();
+    for (start, line, outside) in lines(source, &literals(source)) {
+        if !outside || !line.starts_with("use ") {
+            continue;
+        }
+        let Some(end) = source[start..].find(';') else {
+            break;
+        };
+        let source = &source[start..start + end + 1];
+        let path = &source["use ".len()..end];
+        uses.push(Use {
+            root: word(path.trim_start_matches("::")),
+            names: use_names(path),
+            source,
+        });
+    }
+    uses
+}
+
+/// The names `use path;` brings in: the last segment of each path, what
+/// follows `as`, and for `self` the module it stands for
+fn use_names(path: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    // The segment before each open `{`, for a `self` inside it
+    let mut modules = Vec::new();
+    let mut last = "";
+    let mut renamed = false;
+    let mut rest = path;
+    loop {
+        rest = rest.trim_start();
+        let Some(c) = rest.chars().next() else {
+            break;
+        };
+        if c.is_alphanumeric() || c == '_' {
+            let name = word(rest);
+            rest = &rest[name.len()..];
+            if name == "as" {
+                renamed = true;
+                continue;
+            }
+            let after = rest.trim_start();
+            if !after.starts_with("::") && word(after) != "as" {
+                let leaf = if name == "self" {
+                    modules.last().copied().unwrap_or(last)
+                } else {
+                    name
+                };
+                if leaf != "_" {
+                    names.push(leaf);
+                }
+                renamed = false;
+            } else if !renamed {
+                last = name;
+            }
+            continue;
+        }
+        match c {
+            '{' => modules.push(last),
+            '}' => {
+                modules.pop();
+            }
+            _ => {}
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    names
+}
+
+/// The identifier `text` starts with
+fn word(text: &str) -> &str {
+    let end = text
+        .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    &text[..end]
+}
+
+/// The methods `item` declares when it is a trait, so that calling one
+/// counts as using it
+fn trait_methods<'a>(item: &Item<'a>) -> Vec<&'a str> {
+    let is_trait = item.source.lines().any(|line| {
+        line.strip_prefix("pub ")
+            .unwrap_or(line)
+            .starts_with("trait ")
+    });
+    if !is_trait {
+        return Vec::new();
+    }
+    item.source
+        .lines()
+        .filter_map(|line| line.trim_start().strip_prefix("fn "))
+        .map(word)
+        .collect()
+}
+
+/// `code` without its comments and with its string and character literals
+/// emptied, so a word in them is not taken for a use
+fn without_text(code: &str) -> String {
+    let mut kept = String::new();
+    let mut at = 0;
+    for (range, comment) in literals(code) {
+        kept.push_str(&code[at..range.start]);
+        if comment {
+            kept.extend(code[range.clone()].matches('\n'));
+        } else {
+            kept.push_str("\"\"");
+        }
+        at = range.end;
+    }
+    kept.push_str(&code[at..]);
+    kept
+}
+
+/// Whether `code` uses `name` as a whole word
+fn mentions(code: &str, name: &str) -> bool {
+    code.match_indices(name)
+        .any(|(at, _)| whole_word(code, at, name.len()))
+}
+
+fn whole_word(code: &str, at: usize, len: usize) -> bool {
+    let word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+    !word(code[..at].chars().next_back()) && !word(code[at + len..].chars().next())
+}
+
+/// Whether `code` uses the imported `name` rather than something else of
+/// that name: not as the tail of a longer path, and when it is lowercase,
+/// only where it is called or a path goes on
+fn uses_name(code: &str, name: &str) -> bool {
+    code.match_indices(name).any(|(at, _)| {
+        if !whole_word(code, at, name.len()) || code[..at].ends_with("::") {
+            return false;
+        }
+        if name.starts_with(|c: char| c.is_uppercase()) {
+            return true;
+        }
+        let after = code[at + name.len()..].trim_start();
+        after.starts_with("::") || after.starts_with('(') || after.starts_with('!')
+    })
+}
+
+/// Whether `code` starts a path at `path`, like `crate::registry`
+fn code_uses_path(code: &str, path: &str) -> bool {
+    code.match_indices(path).any(|(at, _)| {
+        whole_word(code, at, path.len())
+            && !code[..at].ends_with("::")
+            && code[at + path.len()..].starts_with("::")
+    })
+}
+
+/// A link that opens `code` in the playground, on stable with the 2024
+/// edition
+pub fn url(code: &str) -> String {
+    format!(
+        "{}?version=stable&mode=debug&edition=2024&code={}",
+        PLAYGROUND,
+        percent_encode(code)
+    )
+}
+
+/// Everything but unreserved characters as `%XX` bytes
+fn percent_encode(text: &str) -> String {
+    let mut encoded = String::new();
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(char::from(byte));
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::Difficulty;
+
+    fn lesson(source: &'static str, sections: &'static [Section]) -> Lesson {
+        Lesson {
+            name: "ownership",
+            title: "Ownership",
+            run: || {},
+            sections,
+            source,
+            chapter: 1,
+            requires: &[],
+            difficulty: Difficulty::Beginner,
+            tags: &[],
+            changelog: &[],
+            questions: &[],
+            snippets: &[],
+        }
+    }
+
+    #[test]
+    fn test_program_and_url() {
+        const SECTIONS: &[Section] = &[Section::new("slices", "Slices", || {})];
+        let lesson = lesson(
+            "use crate::registry::Section;\nuse std::fmt;\n\n\
+             fn slices() {\n    println!(\"{}\", first_word(\"a b\").shout());\n}\n\n\
+             // The first word\n\
+             fn first_word(s: &str) -> &str {\n    Word(s).0\n}\n\n\
+             struct Word<'a>(&'a str);\n\n\
+             impl<'a> Word<'a> {\n    fn new() {}\n}\n\n\
+             fn unused() {}\n\n\
+             trait Shout {\n    fn shout(&self) -> String;\n}\n\n\
+             impl<T: fmt::Display> Shout for T {\n    fn shout(&self) -> String {\n        \
+             self.to_string()\n    }\n}\n",
+            SECTIONS,
+        );
+        let program = program(&lesson, &SECTIONS[0]).unwrap();
+        assert!(program.starts_with("// ownership::slices from rust-learn: Slices\n"));
+        assert!(program.contains("\nuse std::fmt;\n"));
+        assert!(!program.contains("crate::registry"));
+        assert!(program.contains("// The first word\nfn first_word(s: &str) -> &str {"));
+        assert!(program.contains("\nstruct Word<'a>(&'a str);\n\nimpl<'a> Word<'a> {"));
+        assert!(program.contains("\ntrait Shout {"));
+        assert!(program.contains("\nimpl<T: fmt::Display> Shout for T {"));
+        assert!(!program.contains("fn unused"));
+        assert!(program.ends_with("fn main() {\n    slices();\n}\n"));
+
+        assert_eq!(
+            url("fn main() {}"),
+            "https://play.rust-lang.org/?version=stable&mode=debug&edition=2024\
+             &code=fn%20main%28%29%20%7B%7D"
+        );
+    }
+
+    #[test]
+    fn test_program_items() {
+        const SECTIONS: &[Section] = &[
+            Section::new("waiting", "Waiting", || {}),
+            Section::new("counting", "Counting", || {}),
+            Section::new("quoting", "Quoting", || {}),
+        ];
+        let lesson = lesson(
+            "use crate::registry::{Section, section};\nuse std::cell::Cell;\n\
+             use tokio::time::sleep;\nuse sync::Arc;\n\n\
+             pub fn ownership() {\n    for section in SECTIONS {\n        (section.run)();\n    }\n}\n\n\
+             pub const SECTIONS: &[Section] = &[\n    section!(waiting, \"Waiting\"),\n];\n\n\
+             async fn waiting() {\n    sleep(std::time::Duration::ZERO).await;\n}\n\n\
+             thread_local! {\n    static LIVE: Cell<usize> = const { Cell::new(0) };\n}\n\n\
+             mod sync {\n    pub use std::sync::Arc;\n}\n\n\
+             macro_rules! twice {\n    ($e:expr) => { $e * 2 };\n}\n\n\
+             fn counting() {\n    let ownership = Arc::new(twice!(1));\n    \
+             LIVE.with(|live| live.set(*ownership));\n}\n\n\
+             fn quoting() {\n    let code = r#\"\nfn fake() {\n}\n\"#;\n    \
+             let bracket = '}';\n    println!(\"{}{}\", code, bracket);\n}\n\n\
+             #[cfg(test)]\nmod tests {\n    fn counting() {}\n}\n",
+            SECTIONS,
+        );
+
+        let waiting = program(&lesson, &SECTIONS[0]).unwrap();
+        assert!(waiting.contains("\nuse tokio::time::sleep;\n"));
+        assert!(waiting.ends_with("#[tokio::main]\nasync fn main() {\n    waiting().await;\n}\n"));
+
+        // A variable named after the lesson does not bring in its entry
+        // function or SECTIONS; the local module comes with its `use`
+        let counting = program(&lesson, &SECTIONS[1]).unwrap();
+        assert!(counting.contains("\nthread_local! {\n    static LIVE"));
+        assert!(counting.contains("\nmacro_rules! twice {"));
+        assert!(counting.contains("\nuse sync::Arc;\n"));
+        assert!(counting.contains("\nmod sync {"));
+        assert!(!counting.contains("SECTIONS"));
+        assert!(!counting.contains("mod tests"));
+
+        // Code inside a raw string is not an item, and `'}'` ends nothing
+        let quoting = program(&lesson, &SECTIONS[2]).unwrap();
+        assert!(quoting.contains("    let bracket = '}';\n    println!"));
+        assert!(!quoting.contains("fn counting"));
+        assert!(quoting.ends_with("fn main() {\n    quoting();\n}\n"));
+    }
+
+    #[test]
+    fn test_program_refuses_rust_learn_code() {
+        const SECTIONS: &[Section] = &[
+            Section::new("timing", "Timing", || {}),
+            Section::new("reading", "Reading", || {}),
+            Section::new("missing", "Missing", || {}),
+        ];
+        let lesson = lesson(
+            "use rust_learn_utils::format_duration;\n\n\
+             fn timing() {\n    println!(\"{}\", format_duration(1));\n}\n\n\
+             fn reading() {\n    println!(\"{}\", include_str!(\"../Cargo.toml\"));\n}\n",
+            SECTIONS,
+        );
+        assert_eq!(
+            program(&lesson, &SECTIONS[0]).unwrap_err(),
+            "ownership::timing uses rust-learn's own code (format_duration), \
+             which the playground does not have"
+        );
+        assert_eq!(
+            program(&lesson, &SECTIONS[1]).unwrap_err(),
+            "ownership::reading reads rust-learn's files as it builds (include_str!), \
+             which the playground cannot"
+        );
+        assert_eq!(
+            program(&lesson, &SECTIONS[2]).unwrap_err(),
+            "ownership::missing has no function of that name to share"
+        );
+    }
+}
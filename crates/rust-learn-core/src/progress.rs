@@ -0,0 +1,391 @@
+//! Completed Lessons and Sections
+//!
+//! Which lessons and sections the learner has finished, and when. Running a
+//! lesson to the end (or marking it in the TUI) completes it; running each of
+//! its sections one at a time does too. A run that has started but not
+//! finished is kept too, so `rust-learn resume` can pick up after Ctrl-C.
+//! Each completed lesson's content version is kept as well, so a lesson that
+//! changed since can say what is new, and be marked for review. The
+//! record is kept by the configured `Store` (progress.toml under the user's
+//! data directory unless config.toml says otherwise), so it survives between
+//! runs.
+
+use crate::dirs;
+use crate::lesson::Lesson;
+use crate::settings::{self, Settings};
+use crate::store::{self, Snapshot, Store};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::debug;
+
+/// One lesson name per line, without times; read when the store has nothing
+/// saved yet
+const OLD_FILE_NAME: &str = "completed.txt";
+
+pub struct Progress {
+    /// Where `save` writes; `None` when no data directory could be found
+    store: Option<Box<dyn Store>>,
+    /// `lesson` or `lesson::section`, and when it was completed
+    completed: BTreeMap<String, SystemTime>,
+    /// The run in progress: `all`, a lesson or `lesson::section`, and when it
+    /// started
+    run: Option<(String, SystemTime)>,
+    /// The content version of each lesson when it was completed
+    versions: BTreeMap<String, u32>,
+    /// Lessons marked to go through again, which `next` comes back to first
+    review: BTreeSet<String>,
+}
+
+impl Progress {
+    /// The saved progress, or none at all if nothing was saved yet
+    pub fn load() -> Progress {
+        let store = match store::from_settings(settings::get()) {
+            Ok(store) => store,
+            Err(error) => {
+                static WARNED: AtomicBool = AtomicBool::new(false);
+                if !WARNED.swap(true, Ordering::Relaxed) {
+                    eprintln!("{} in config.toml; using progress.toml instead", error);
+                }
+                store::from_settings(&Settings::default()).unwrap_or(None)
+            }
+        };
+        let Some(store) = store else {
+            debug!("no data directory, progress will not be saved");
+            return Progress {
+                store: None,
+                completed: BTreeMap::new(),
+                run: None,
+                versions: BTreeMap::new(),
+                review: BTreeSet::new(),
+            };
+        };
+        Progress::with_store(store)
+    }
+
+    /// The progress saved in `store`; a store that cannot be read is empty.
+    /// When it has nothing saved yet, the lessons listed in the old
+    /// completed.txt are brought over
+    pub fn with_store(store: Box<dyn Store>) -> Progress {
+        let snapshot = match store.load() {
+            Ok(Some(snapshot)) => snapshot,
+            Ok(None) => Snapshot {
+                completed: read_old_file(),
+                ..Snapshot::default()
+            },
+            Err(error) => {
+                debug!(location = store.location(), %error, "cannot read progress");
+                Snapshot::default()
+            }
+        };
+        debug!(
+            location = store.location(),
+            entries = snapshot.completed.len(),
+            "loaded progress"
+        );
+        Progress {
+            store: Some(store),
+            completed: snapshot.completed,
+            run: snapshot.run,
+            versions: snapshot.versions,
+            review: snapshot.review,
+        }
+    }
+
+    pub fn is_complete(&self, lesson: &str) -> bool {
+        self.completed.contains_key(lesson)
+    }
+
+    /// A section counts as complete on its own or as part of its whole lesson
+    pub fn is_section_complete(&self, lesson: &str, section: &str) -> bool {
+        self.is_complete(lesson) || self.is_complete(&format!("{}::{}", lesson, section))
+    }
+
+    /// When `key` (a lesson, or `lesson::section`) was completed
+    pub fn completed_at(&self, key: &str) -> Option<SystemTime> {
+        self.completed.get(key).copied()
+    }
+
+    /// The completed keys that start with `prefix`, like `exercise::`, in
+    /// order, with when
+    pub fn completed_under<'a>(
+        &'a self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = (&'a str, SystemTime)> + 'a {
+        self.completed
+            .range(prefix.to_string()..)
+            .take_while(move |(key, _)| key.starts_with(prefix))
+            .map(|(key, time)| (key.as_str(), *time))
+    }
+
+    /// Mark `lesson` complete, or not complete (with all its sections) if it
+    /// was; returns the new state
+    pub fn toggle(&mut self, lesson: &str) -> bool {
+        if self.completed.remove(lesson).is_some() {
+            let prefix = format!("{}::", lesson);
+            self.completed.retain(|key, _| !key.starts_with(&prefix));
+            self.versions.remove(lesson);
+            self.review.remove(lesson);
+            false
+        } else {
+            self.mark_complete(lesson);
+            true
+        }
+    }
+
+    /// Mark `key` (a lesson, or `lesson::section`) complete now; false if it
+    /// already was
+    pub fn mark_complete(&mut self, key: &str) -> bool {
+        if self.completed.contains_key(key) {
+            return false;
+        }
+        self.completed.insert(key.to_string(), SystemTime::now());
+        debug!(key, "completed");
+        true
+    }
+
+    /// Record a run of `lesson`, or of one of its sections, that finished.
+    /// The section that completes the set completes the lesson too. Running
+    /// the whole lesson again brings it up to date: its version is the
+    /// current one and it is no longer marked for review
+    pub fn record_run(&mut self, lesson: &Lesson, section: Option<&str>) {
+        let Some(section) = section else {
+            self.mark_complete(lesson.name);
+            self.versions
+                .insert(lesson.name.to_string(), lesson.version());
+            self.review.remove(lesson.name);
+            return;
+        };
+        self.mark_complete(&format!("{}::{}", lesson.name, section));
+        if lesson
+            .sections
+            .iter()
+            .all(|other| self.is_section_complete(lesson.name, other.name))
+            && self.mark_complete(lesson.name)
+        {
+            self.versions
+                .insert(lesson.name.to_string(), lesson.version());
+        }
+    }
+
+    /// The version of `lesson` the learner completed (1 when it was completed
+    /// before lessons had versions), or `None` when it is not complete
+    pub fn completed_version(&self, lesson: &str) -> Option<u32> {
+        self.is_complete(lesson)
+            .then(|| self.versions.get(lesson).copied().unwrap_or(1))
+    }
+
+    /// Ask `next` to come back to `lesson` before new ones
+    pub fn mark_for_review(&mut self, lesson: &str) {
+        self.review.insert(lesson.to_string());
+    }
+
+    pub fn is_marked_for_review(&self, lesson: &str) -> bool {
+        self.review.contains(lesson)
+    }
+
+    /// Note that a run of `target` (`all`, a lesson or `lesson::section`)
+    /// has started; `finish_run` clears it
+    pub fn start_run(&mut self, target: &str) {
+        self.run = Some((target.to_string(), SystemTime::now()));
+    }
+
+    pub fn finish_run(&mut self) {
+        self.run = None;
+    }
+
+    /// The run that started but never finished, and when it started
+    pub fn interrupted_run(&self) -> Option<(&str, SystemTime)> {
+        self.run
+            .as_ref()
+            .map(|(target, started)| (target.as_str(), *started))
+    }
+
+    /// How many whole lessons are complete
+    pub fn completed_count(&self) -> usize {
+        self.completed
+            .keys()
+            .filter(|key| !key.contains("::"))
+            .count()
+    }
+
+    /// Hand everything to the store
+    pub fn save(&self) -> io::Result<()> {
+        let Some(store) = &self.store else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no data directory (set RUST_LEARN_DATA_DIR or HOME)",
+            ));
+        };
+        debug!(
+            location = store.location(),
+            entries = self.completed.len(),
+            "saving progress"
+        );
+        store.save(&Snapshot {
+            completed: self.completed.clone(),
+            run: self.run.clone(),
+            versions: self.versions.clone(),
+            review: self.review.clone(),
+        })
+    }
+}
+
+/// The lessons in completed.txt, all completed when the file was last written
+fn read_old_file() -> BTreeMap<String, SystemTime> {
+    let Some(old) = dirs::data_dir().map(|dir| dir.join(OLD_FILE_NAME)) else {
+        return BTreeMap::new();
+    };
+    let Ok(text) = fs::read_to_string(&old) else {
+        return BTreeMap::new();
+    };
+    let modified = fs::metadata(&old)
+        .and_then(|meta| meta.modified())
+        .unwrap_or_else(|_| SystemTime::now());
+    let completed = text
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|lesson| (lesson.to_string(), modified))
+        .collect::<BTreeMap<_, _>>();
+    debug!(path = %old.display(), entries = completed.len(), "read old progress");
+    completed
+}
+
+/// A UTC time as TOML writes it: `2026-10-16T14:03:27Z`
+pub fn format_timestamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (days, rest) = (seconds / 86_400, seconds % 86_400);
+    let (year, month, day) = civil_from_days(days as i64);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest / 60 % 60,
+        rest % 60
+    )
+}
+
+/// The inverse of `format_timestamp`
+pub fn parse_timestamp(text: &str) -> Option<SystemTime> {
+    let (date, time) = text.strip_suffix('Z')?.split_once('T')?;
+    let mut date = date.splitn(3, '-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.splitn(3, ':').map(|part| part.parse::<u64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    let days = u64::try_from(days_from_civil(year, month, day)).ok()?;
+    let seconds = days * 86_400 + hour * 3600 + minute * 60 + second;
+    Some(UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
+/// Year, month and day of a day count since 1970-01-01 (Howard Hinnant's
+/// `civil_from_days`)
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Days since 1970-01-01 of a date, the inverse of `civil_from_days`
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month_index = if month > 2 { month - 3 } else { month + 9 };
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::{Difficulty, Section};
+
+    fn noop() {}
+
+    const SECTIONS: &[Section] = &[
+        Section::new("one", "One", noop),
+        Section::new("two", "Two", noop),
+    ];
+
+    #[test]
+    fn test_record_save_and_load() {
+        let dir = std::env::temp_dir().join(format!("rust-learn-progress-{}", std::process::id()));
+        let lesson = Lesson {
+            name: "loops",
+            title: "Loops",
+            run: noop,
+            sections: SECTIONS,
+            source: "",
+            chapter: 1,
+            requires: &[],
+            difficulty: Difficulty::Beginner,
+            tags: &[],
+            changelog: &[],
+            questions: &[],
+            snippets: &[],
+        };
+
+        let store = || Box::new(store::JsonFile::new(dir.join(store::JsonFile::FILE_NAME)));
+        let mut progress = Progress::with_store(store());
+        assert_eq!(progress.completed_count(), 0);
+        assert!(progress.toggle("ownership"));
+        assert!(progress.mark_complete("exercise::ownership1"));
+        let exercises: Vec<&str> = progress
+            .completed_under("exercise::")
+            .map(|(key, _)| key)
+            .collect();
+        assert_eq!(exercises, ["exercise::ownership1"]);
+        progress.record_run(&lesson, Some("one"));
+        assert!(!progress.is_complete("loops"));
+        progress.record_run(&lesson, Some("two"));
+        assert!(progress.is_complete("loops"));
+        progress.start_run("all");
+        progress.save().unwrap();
+
+        let mut loaded = Progress::with_store(store());
+        assert!(loaded.is_complete("ownership"));
+        assert!(loaded.is_section_complete("loops", "two"));
+        assert_eq!(loaded.completed_count(), 2);
+        assert_eq!(loaded.interrupted_run().unwrap().0, "all");
+        assert_eq!(loaded.completed_version("loops"), Some(1));
+        assert_eq!(loaded.completed_version("ownership"), Some(1));
+        loaded.mark_for_review("loops");
+        loaded.record_run(&lesson, Some("one"));
+        assert!(loaded.is_marked_for_review("loops"));
+        loaded.record_run(&lesson, None);
+        assert!(!loaded.is_marked_for_review("loops"));
+        assert!(!loaded.toggle("loops"));
+        assert!(!loaded.is_section_complete("loops", "one"));
+        fs::remove_dir_all(dir).unwrap();
+
+        let time = parse_timestamp("2026-10-16T14:03:27Z").unwrap();
+        assert_eq!(format_timestamp(time), "2026-10-16T14:03:27Z");
+        assert_eq!(format_timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(
+            parse_timestamp("2024-02-29T00:00:00Z"),
+            Some(UNIX_EPOCH + Duration::from_secs(1_709_164_800))
+        );
+    }
+}
@@ -0,0 +1,350 @@
+//! Question Banks from Files
+//!
+//! Quiz questions written outside the code, so a teacher can add their own
+//! without rebuilding: rust-learn ships a few (`registry::BUNDLED_QUESTIONS`
+//! in rust-learn-content), and `quiz --bank <path>` or `banks` under `[quiz]`
+//! in config.toml adds more. A bank is a `.toml` file of `[[question]]`
+//! tables, in the subset of TOML config.toml is read with (`settings`):
+//!
+//! ```toml
+//! [[question]]
+//! lesson = "ownership"
+//! section = "slices"
+//! prompt = "What does `&s[0..2]` borrow?\n\n    let s = String::from(\"hello\");"
+//! choices = ["The first two bytes of s", "A copy of s", "All of s"]
+//! explanation = "A slice borrows part of the string; nothing is copied."
+//! ```
+//!
+//! or a `.json` file holding an array of objects with the same keys. A
+//! directory is every bank in it. Each question is checked like the built-in
+//! ones (`quiz::question_problems`): its lesson and section must exist, and
+//! there must be two choices at least, none repeated. Keys that are not part
+//! of a question are an error too, so a typo does not go unnoticed.
+//!
+//! Loaded questions are merged into their lessons' banks (`merge`). Their
+//! text lives as long as the process, like the built-in questions'.
+
+use crate::json;
+use crate::lesson::{Lesson, Question};
+use crate::quiz;
+use crate::settings::{Settings, Table};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The keys a question has, all of them required
+const KEYS: [&str; 5] = ["lesson", "section", "prompt", "choices", "explanation"];
+
+/// A question from a file, for the lesson it names
+pub struct Loaded {
+    pub lesson: &'static str,
+    pub question: Question,
+}
+
+/// A question as written, before it is checked
+struct Fields {
+    lesson: String,
+    section: String,
+    prompt: String,
+    choices: Vec<String>,
+    explanation: String,
+}
+
+/// The banks at `path`: a file, or every `.toml` and `.json` file in a
+/// directory. Errors name the file and the question
+pub fn load(path: &Path, lessons: &[Lesson]) -> Result<Vec<Loaded>, Vec<String>> {
+    let files = if path.is_dir() {
+        let entries = fs::read_dir(path).map_err(|error| vec![read_error(path, error)])?;
+        let mut files: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| {
+                path.extension()
+                    .is_some_and(|extension| extension == "toml" || extension == "json")
+            })
+            .collect();
+        files.sort();
+        files
+    } else {
+        vec![path.to_path_buf()]
+    };
+
+    let mut loaded = Vec::new();
+    let mut errors = Vec::new();
+    for file in files {
+        let text = match fs::read_to_string(&file) {
+            Ok(text) => text,
+            Err(error) => {
+                errors.push(read_error(&file, error));
+                continue;
+            }
+        };
+        match parse(&file.display().to_string(), &text, lessons) {
+            Ok(questions) => loaded.extend(questions),
+            Err(problems) => errors.extend(problems),
+        }
+    }
+    if errors.is_empty() {
+        Ok(loaded)
+    } else {
+        Err(errors)
+    }
+}
+
+fn read_error(path: &Path, error: std::io::Error) -> String {
+    format!("{}: cannot read it ({})", path.display(), error)
+}
+
+/// The questions in one bank called `name`: JSON when it ends in `.json`,
+/// TOML otherwise
+pub fn parse(name: &str, text: &str, lessons: &[Lesson]) -> Result<Vec<Loaded>, Vec<String>> {
+    let written = if name.ends_with(".json") {
+        from_json(text)
+    } else {
+        from_toml(text)
+    }
+    .map_err(|error| vec![format!("{}: {}", name, error)])?;
+
+    let mut loaded = Vec::new();
+    let mut errors = Vec::new();
+    for (index, fields) in written.into_iter().enumerate() {
+        let about = format!("{}: question {}", name, index + 1);
+        match check(fields, lessons) {
+            Ok(question) => loaded.push(question),
+            Err(problems) => {
+                errors.extend(
+                    problems
+                        .into_iter()
+                        .map(|problem| format!("{} {}", about, problem)),
+                );
+            }
+        }
+    }
+    if errors.is_empty() {
+        Ok(loaded)
+    } else {
+        Err(errors)
+    }
+}
+
+fn from_toml(text: &str) -> Result<Vec<Fields>, String> {
+    let settings = Settings::parse(text)?;
+    if let Some(key) = settings.root().keys().next() {
+        return Err(format!(
+            "'{}' is outside a [[question]]; every key belongs to one",
+            key
+        ));
+    }
+    settings
+        .all_tables()
+        .iter()
+        .enumerate()
+        .map(|(index, table)| {
+            if table.name != "question" {
+                return Err(format!(
+                    "[{}] is not a bank table; questions are [[question]]",
+                    table.name
+                ));
+            }
+            toml_fields(table).map_err(|error| format!("question {} {}", index + 1, error))
+        })
+        .collect()
+}
+
+fn toml_fields(table: &Table) -> Result<Fields, String> {
+    if let Some(key) = table.keys().find(|key| !KEYS.contains(key)) {
+        return Err(format!("has '{}', which is not a question key", key));
+    }
+    let text = |key: &str| {
+        table
+            .str(key)
+            .map(String::from)
+            .ok_or_else(|| format!("needs {} = \"...\"", key))
+    };
+    Ok(Fields {
+        lesson: text("lesson")?,
+        section: text("section")?,
+        prompt: text("prompt")?,
+        choices: table
+            .strings("choices")
+            .ok_or("needs choices = [\"right\", \"wrong\", ...]")?
+            .into_iter()
+            .map(String::from)
+            .collect(),
+        explanation: text("explanation")?,
+    })
+}
+
+fn from_json(text: &str) -> Result<Vec<Fields>, String> {
+    let value = json::Value::parse(text)?;
+    let questions = value.as_array().ok_or("expected an array of questions")?;
+    questions
+        .iter()
+        .enumerate()
+        .map(|(index, question)| {
+            json_fields(question).map_err(|error| format!("question {} {}", index + 1, error))
+        })
+        .collect()
+}
+
+fn json_fields(question: &json::Value) -> Result<Fields, String> {
+    let entries = question.as_object().ok_or("is not an object")?;
+    if let Some((key, _)) = entries
+        .iter()
+        .find(|(key, _)| !KEYS.contains(&key.as_str()))
+    {
+        return Err(format!("has '{}', which is not a question key", key));
+    }
+    let text = |key: &str| {
+        question
+            .get(key)
+            .and_then(json::Value::as_str)
+            .map(String::from)
+            .ok_or_else(|| format!("needs \"{}\" as a string", key))
+    };
+    let choices = question
+        .get("choices")
+        .and_then(json::Value::as_array)
+        .and_then(|choices| {
+            choices
+                .iter()
+                .map(|choice| choice.as_str().map(String::from))
+                .collect::<Option<Vec<_>>>()
+        })
+        .ok_or("needs \"choices\" as an array of strings")?;
+    Ok(Fields {
+        lesson: text("lesson")?,
+        section: text("section")?,
+        prompt: text("prompt")?,
+        choices,
+        explanation: text("explanation")?,
+    })
+}
+
+/// The question, if its lesson exists and `quiz::question_problems` finds
+/// nothing wrong with it
+fn check(fields: Fields, lessons: &[Lesson]) -> Result<Loaded, Vec<String>> {
+    let Some(lesson) = lessons.iter().find(|lesson| lesson.name == fields.lesson) else {
+        return Err(vec![format!(
+            "is about lesson '{}', which does not exist",
+            fields.lesson
+        )]);
+    };
+    if fields.prompt.trim().is_empty() {
+        return Err(vec!["has an empty prompt".to_string()]);
+    }
+    let question = Question {
+        section: leak(fields.section),
+        prompt: leak(fields.prompt),
+        choices: Box::leak(
+            fields
+                .choices
+                .into_iter()
+                .map(leak)
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        ),
+        explanation: leak(fields.explanation),
+    };
+    let problems = quiz::question_problems(lesson, &question);
+    if !problems.is_empty() {
+        return Err(problems);
+    }
+    Ok(Loaded {
+        lesson: lesson.name,
+        question,
+    })
+}
+
+fn leak(text: String) -> &'static str {
+    Box::leak(text.into_boxed_str())
+}
+
+/// `lessons` with the `loaded` questions added to their banks, after the
+/// built-in ones
+pub fn merge(lessons: &[Lesson], loaded: Vec<Loaded>) -> Vec<Lesson> {
+    if loaded.is_empty() {
+        return lessons.to_vec();
+    }
+    lessons
+        .iter()
+        .map(|lesson| {
+            let added: Vec<&Loaded> = loaded
+                .iter()
+                .filter(|question| question.lesson == lesson.name)
+                .collect();
+            if added.is_empty() {
+                return *lesson;
+            }
+            let questions: Vec<Question> = lesson
+                .questions
+                .iter()
+                .chain(added.into_iter().map(|loaded| &loaded.question))
+                .map(|question| Question { ..*question })
+                .collect();
+            Lesson {
+                questions: Box::leak(questions.into_boxed_slice()),
+                ..*lesson
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::Section;
+
+    fn lessons() -> Vec<Lesson> {
+        const SECTIONS: &[Section] = &[Section::new("slices", "Slices", || {})];
+        vec![Lesson {
+            name: "ownership",
+            title: "Ownership",
+            run: || {},
+            sections: SECTIONS,
+            source: "",
+            chapter: 1,
+            requires: &[],
+            difficulty: crate::lesson::Difficulty::Beginner,
+            tags: &[],
+            changelog: &[],
+            questions: &[],
+            snippets: &[],
+        }]
+    }
+
+    #[test]
+    fn test_parse_toml_and_json() {
+        let lessons = lessons();
+        let toml = "[[question]]\n\
+                    lesson = \"ownership\"\n\
+                    section = \"slices\"\n\
+                    prompt = \"Is a slice a copy?\\n\\n    &s[0..2]\"\n\
+                    choices = [\"No, a borrow\", \"Yes\"]\n\
+                    explanation = \"It borrows.\"\n";
+        let Ok(loaded) = parse("mine.toml", toml, &lessons) else {
+            panic!("mine.toml does not parse");
+        };
+        assert_eq!(
+            loaded[0].question.prompt,
+            "Is a slice a copy?\n\n    &s[0..2]"
+        );
+        let merged = merge(&lessons, loaded);
+        assert_eq!(merged[0].questions.len(), 1);
+
+        let json = r#"[{"lesson": "ownership", "section": "moves", "prompt": "?",
+                        "choices": ["a", "a"], "explanation": "", "hint": ""}]"#;
+        assert_eq!(
+            parse("mine.json", json, &lessons).err(),
+            Some(vec![
+                "mine.json: question 1 has 'hint', which is not a question key".to_string()
+            ])
+        );
+        let json = json.replace(r#", "hint": """#, "");
+        assert_eq!(
+            parse("mine.json", &json, &lessons).err(),
+            Some(vec![
+                "mine.json: question 1 offers \"a\" twice".to_string(),
+                "mine.json: question 1 is about 'moves', which is not a section".to_string()
+            ])
+        );
+    }
+}
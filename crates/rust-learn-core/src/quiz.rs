@@ -0,0 +1,399 @@
+//! Lesson Quiz
+//!
+//! Every lesson brings a bank of multiple-choice questions about itself
+//! (`Lesson::questions`), each explained once it is answered. More questions
+//! are built from the lessons themselves: pick a random section and ask which
+//! lesson it belongs to, so every new lesson adds questions for free.
+//!
+//! With a time limit (`quiz --timed 60`) there is no question count: the quiz
+//! asks as many as it can until the time is up. The clock runs in the answer
+//! prompt (`input::choice_until`), so it ends the quiz mid-question.
+
+use crate::input::{self, MenuChoice, TimedOut};
+use crate::lesson::{Lesson, Section};
+use crate::output::{self, Glyph};
+use crate::random::Rng;
+use std::time::{Duration, Instant};
+
+/// How many wrong answers to offer next to the right one
+const DISTRACTORS: usize = 3;
+
+/// One question in a lesson's bank
+pub struct Question {
+    /// The section it is about, by name
+    pub section: &'static str,
+    /// What to ask; code in it is indented four spaces
+    pub prompt: &'static str,
+    /// The right answer first, then the wrong ones; they are shuffled when
+    /// asked
+    pub choices: &'static [&'static str],
+    /// Why the right answer is right, shown after any answer
+    pub explanation: &'static str,
+}
+
+pub struct QuizScore {
+    pub asked: usize,
+    pub correct: usize,
+    /// Each question's `lesson::section`, and whether it was answered right
+    pub answers: Vec<(String, bool)>,
+}
+
+/// A question to ask, about a lesson
+enum Ask<'a> {
+    Bank(&'a Lesson, &'a Question),
+    /// Which lesson has this section?
+    Section(&'a Lesson, &'a Section),
+}
+
+/// Ask up to `questions` questions, stopping early if the user quits, or with
+/// a `limit`, as many as there is time for. With `about`, every question
+/// comes from that lesson's bank, or is about one of its sections when it has
+/// no bank
+pub fn run_quiz(
+    lessons: &[Lesson],
+    about: Option<&str>,
+    questions: usize,
+    limit: Option<Duration>,
+    rng: &mut Rng,
+) -> QuizScore {
+    let chosen = || {
+        lessons
+            .iter()
+            .filter(|lesson| about.is_none_or(|name| lesson.name == name))
+    };
+    let mut pool: Vec<Ask> = chosen()
+        .flat_map(|lesson| {
+            lesson
+                .questions
+                .iter()
+                .map(move |question| Ask::Bank(lesson, question))
+        })
+        .collect();
+    if about.is_none() || pool.is_empty() {
+        pool.extend(chosen().flat_map(|lesson| {
+            lesson
+                .sections
+                .iter()
+                .map(move |section| Ask::Section(lesson, section))
+        }));
+    }
+    rng.shuffle(&mut pool);
+
+    let total = match limit {
+        Some(_) => pool.len(),
+        None => questions.min(pool.len()),
+    };
+    let mut score = QuizScore {
+        asked: 0,
+        correct: 0,
+        answers: Vec::new(),
+    };
+
+    let start = Instant::now();
+    let deadline = limit.map(|limit| start + limit);
+    match limit {
+        Some(limit) => println!(
+            "=== Timed Quiz: {}s ===\n\nAs many as you can before the time is up.\n",
+            limit.as_secs()
+        ),
+        None => println!("=== Lesson Quiz ===\n"),
+    }
+
+    for (number, ask) in pool.into_iter().take(total).enumerate() {
+        match deadline {
+            Some(deadline) => {
+                let left = deadline.saturating_duration_since(Instant::now());
+                print!("Question {} ({}s left): ", number + 1, left.as_secs());
+            }
+            None => print!("Question {}/{}: ", number + 1, total),
+        }
+        let (lesson, section, answer) = match ask {
+            Ask::Bank(lesson, question) => {
+                (lesson, question.section, ask_bank(question, deadline, rng))
+            }
+            Ask::Section(lesson, section) => (
+                lesson,
+                section.name,
+                ask_section(lessons, lesson, section, deadline, rng),
+            ),
+        };
+        let correct = match answer {
+            Ok(Some(correct)) => correct,
+            Ok(None) => break,
+            Err(TimedOut) => {
+                println!("{}", output::bold("Time's up!"));
+                break;
+            }
+        };
+
+        score.asked += 1;
+        score
+            .answers
+            .push((format!("{}::{}", lesson.name, section), correct));
+        if correct {
+            score.correct += 1;
+        } else {
+            let review = format!("(rust-learn run {} {})", lesson.name, section);
+            println!("{}", output::status(Glyph::Hint, &review, output::dim, 0));
+        }
+        println!();
+    }
+
+    match limit {
+        Some(limit) => {
+            // The time taken, which is less than the limit after quitting
+            // early; at least a second, so the rate stays finite
+            let took = start.elapsed().min(limit);
+            let minutes = took.as_secs_f64().max(1.0) / 60.0;
+            println!(
+                "Score: {}/{} in {}s ({:.1} right a minute)",
+                score.correct,
+                score.asked,
+                took.as_secs(),
+                score.correct as f64 / minutes
+            );
+            if score.asked == total && took < limit {
+                println!(
+                    "{}",
+                    output::dim("Every question asked, with time to spare")
+                );
+            }
+        }
+        None => println!("Score: {}/{}", score.correct, score.asked),
+    }
+    score
+}
+
+/// Ask the user to pick one of `count` answers, until `deadline` if there is
+/// one
+fn choose(prompt: &str, count: usize, deadline: Option<Instant>) -> Result<MenuChoice, TimedOut> {
+    match deadline {
+        Some(deadline) => input::choice_until(prompt, count, deadline),
+        None => Ok(input::validated_choice(prompt, count)),
+    }
+}
+
+/// Ask a question from a bank; `None` when the user quits
+fn ask_bank(
+    question: &Question,
+    deadline: Option<Instant>,
+    rng: &mut Rng,
+) -> Result<Option<bool>, TimedOut> {
+    println!("{}", question.prompt);
+    let mut order: Vec<usize> = (0..question.choices.len()).collect();
+    rng.shuffle(&mut order);
+    for (index, &choice) in order.iter().enumerate() {
+        println!("  {}. {}", index + 1, question.choices[choice]);
+    }
+
+    let prompt = format!("Answer (1-{}) or 'quit': ", order.len());
+    let MenuChoice::Item(index) = choose(&prompt, order.len(), deadline)? else {
+        return Ok(None);
+    };
+    let correct = order[index] == 0;
+    if correct {
+        println!(
+            "{}",
+            output::status(Glyph::Ok, "Correct!", output::green, 0)
+        );
+    } else {
+        let right = order.iter().position(|&choice| choice == 0).unwrap_or(0);
+        let wrong = output::status(Glyph::Fail, "Not quite.", output::red, 0);
+        println!("{} The answer is {}.", wrong, right + 1);
+    }
+    println!("{}", question.explanation);
+    Ok(Some(correct))
+}
+
+/// Ask which lesson has `section`; `None` when the user quits
+fn ask_section(
+    lessons: &[Lesson],
+    lesson: &Lesson,
+    section: &Section,
+    deadline: Option<Instant>,
+    rng: &mut Rng,
+) -> Result<Option<bool>, TimedOut> {
+    let choices = choices_for(lessons, lesson, rng);
+    println!("Which lesson has the section \"{}\"?", section.title);
+    for (index, choice) in choices.iter().enumerate() {
+        println!("  {}. {}", index + 1, choice.name);
+    }
+
+    let prompt = format!("Answer (1-{}) or 'quit': ", choices.len());
+    let MenuChoice::Item(index) = choose(&prompt, choices.len(), deadline)? else {
+        return Ok(None);
+    };
+    let correct = choices[index].name == lesson.name;
+    if correct {
+        println!(
+            "{}",
+            output::status(Glyph::Ok, "Correct!", output::green, 0)
+        );
+    } else {
+        let wrong = output::status(Glyph::Fail, "Not quite.", output::red, 0);
+        println!("{} It is in {}.", wrong, lesson.name);
+    }
+    Ok(Some(correct))
+}
+
+/// What is wrong with `question`, in `lesson`'s bank: too few choices, a
+/// choice twice, a section the lesson does not have
+pub fn question_problems(lesson: &Lesson, question: &Question) -> Vec<String> {
+    let mut problems = Vec::new();
+    if question.choices.len() < 2 {
+        problems.push("needs two choices at least".to_string());
+    }
+    for (index, choice) in question.choices.iter().enumerate() {
+        if question.choices[..index].contains(choice) {
+            problems.push(format!("offers \"{}\" twice", choice));
+        }
+    }
+    if lesson.section(question.section).is_none() {
+        problems.push(format!(
+            "is about '{}', which is not a section",
+            question.section
+        ));
+    }
+    problems
+}
+
+/// Why some questions could not be asked fairly: too few lessons to choose
+/// from, a lesson without sections, a section title two lessons share, or a
+/// bank question with one choice, a repeated choice or an unknown section,
+/// or a snippet whose function is not in the lesson's source
+pub fn problems(lessons: &[Lesson]) -> Vec<String> {
+    let mut problems = Vec::new();
+    if lessons.len() <= DISTRACTORS {
+        problems.push(format!(
+            "{} lessons, but every question offers {} choices",
+            lessons.len(),
+            DISTRACTORS + 1
+        ));
+    }
+    for (index, lesson) in lessons.iter().enumerate() {
+        if lesson.sections.is_empty() {
+            problems.push(format!("{} has no sections to ask about", lesson.name));
+        }
+        for question in lesson.questions {
+            let first_line = question.prompt.lines().next().unwrap_or_default();
+            let about = format!("{} question \"{}\"", lesson.name, first_line);
+            problems.extend(
+                question_problems(lesson, question)
+                    .into_iter()
+                    .map(|problem| format!("{} {}", about, problem)),
+            );
+        }
+        for snippet in lesson.snippets {
+            if lesson.function_source(snippet.name).is_none() {
+                problems.push(format!(
+                    "{} snippet {} has no `fn {}` in the source to show",
+                    lesson.name, snippet.name, snippet.name
+                ));
+            }
+        }
+        for section in lesson.sections {
+            let earlier = lessons[..index].iter().find(|other| {
+                other
+                    .sections
+                    .iter()
+                    .any(|other| other.title.eq_ignore_ascii_case(section.title))
+            });
+            if let Some(other) = earlier {
+                problems.push(format!(
+                    "\"{}\" is a section of both {} and {}",
+                    section.title, other.name, lesson.name
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// The right lesson plus up to `DISTRACTORS` other lessons, shuffled
+fn choices_for<'a>(lessons: &'a [Lesson], answer: &'a Lesson, rng: &mut Rng) -> Vec<&'a Lesson> {
+    let mut others: Vec<&Lesson> = lessons
+        .iter()
+        .filter(|lesson| lesson.name != answer.name)
+        .collect();
+    rng.shuffle(&mut others);
+
+    let mut choices: Vec<&Lesson> = others.into_iter().take(DISTRACTORS).collect();
+    choices.push(answer);
+    rng.shuffle(&mut choices);
+    choices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson::Difficulty;
+
+    fn noop() {}
+
+    const SECTIONS: &[Section] = &[Section::new("only", "Only", noop)];
+
+    fn lesson(name: &'static str) -> Lesson {
+        Lesson {
+            name,
+            title: name,
+            run: noop,
+            sections: SECTIONS,
+            source: "",
+            chapter: 1,
+            requires: &[],
+            difficulty: Difficulty::Beginner,
+            tags: &[],
+            changelog: &[],
+            questions: &[],
+            snippets: &[],
+        }
+    }
+
+    #[test]
+    fn test_choices_include_answer_once() {
+        let lessons: Vec<Lesson> = ["a", "b", "c", "d", "e", "f"].map(lesson).into();
+        let mut rng = Rng::with_seed(7);
+
+        for answer in &lessons {
+            let choices = choices_for(&lessons, answer, &mut rng);
+            assert_eq!(choices.len(), DISTRACTORS + 1);
+            let hits = choices.iter().filter(|c| c.name == answer.name).count();
+            assert_eq!(hits, 1);
+        }
+    }
+
+    #[test]
+    fn test_problems_finds_shared_section_titles() {
+        let lessons: Vec<Lesson> = ["a", "b", "c", "d"].map(lesson).into();
+        assert_eq!(
+            problems(&lessons),
+            [
+                "\"Only\" is a section of both a and b",
+                "\"Only\" is a section of both a and c",
+                "\"Only\" is a section of both a and d",
+            ]
+        );
+        assert_eq!(
+            problems(&lessons[..1]),
+            ["1 lessons, but every question offers 4 choices"]
+        );
+
+        const QUESTIONS: &[Question] = &[Question {
+            section: "missing",
+            prompt: "Which one?\n\n    let x = 5;",
+            choices: &["this", "this"],
+            explanation: "",
+        }];
+        let banked = Lesson {
+            questions: QUESTIONS,
+            ..lesson("a")
+        };
+        assert_eq!(
+            problems(&[banked])[1..],
+            [
+                "a question \"Which one?\" offers \"this\" twice",
+                "a question \"Which one?\" is about 'missing', which is not a section",
+            ]
+        );
+    }
+}
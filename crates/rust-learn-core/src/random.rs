@@ -0,0 +1,109 @@
+//! A Small Random Number Generator
+//!
+//! xorshift64* - plenty for picking quiz questions, and it keeps the core
+//! crate free of dependencies. Not for anything secret.
+//!
+//! Everything random in a session (the quiz's questions and their order,
+//! `quiz --predict`'s snippets, `run --random`) draws from `session`, which
+//! starts from one seed per process: `--seed`, or `seed` under `[random]` in
+//! config.toml, or else the clock. Giving the same seed again repeats the
+//! session exactly, say in front of a class.
+
+use crate::settings;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+static SEED: OnceLock<Seed> = OnceLock::new();
+
+/// The session's seed, and whether someone chose it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Seed {
+    pub value: u64,
+    /// `false` when it came from the clock
+    pub chosen: bool,
+}
+
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// The same seed always gives the same sequence
+    pub fn with_seed(seed: u64) -> Self {
+        // xorshift gets stuck at zero, so never start there
+        Rng { state: seed.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A number in `0..bound` (`bound` must not be zero)
+    pub fn below(&mut self, bound: usize) -> usize {
+        (self.next_u64() % bound as u64) as usize
+    }
+
+    /// Shuffle in place (Fisher-Yates)
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for index in (1..items.len()).rev() {
+            let other = self.below(index + 1);
+            items.swap(index, other);
+        }
+    }
+}
+
+fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+/// Fix the session's seed from `--seed`; without one, `seed()` looks in
+/// config.toml and then at the clock. Only the first call counts
+pub fn init(seed: Option<u64>) {
+    if let Some(value) = seed {
+        let _ = SEED.set(Seed {
+            value,
+            chosen: true,
+        });
+    }
+}
+
+/// The seed every `session` generator starts from
+pub fn seed() -> Seed {
+    *SEED.get_or_init(|| {
+        let configured = settings::get()
+            .table("random")
+            .and_then(|table| table.integer("seed"));
+        match configured {
+            Some(value) => Seed {
+                value: value as u64,
+                chosen: true,
+            },
+            // Short enough to read out and type back in
+            None => Seed {
+                value: clock_seed() % 1_000_000,
+                chosen: false,
+            },
+        }
+    })
+}
+
+/// A generator for one use of randomness, like `"quiz"`: the seed and the
+/// name decide its sequence, so one use drawing more numbers does not change
+/// what the others get
+pub fn session(name: &str) -> Rng {
+    Rng::with_seed(seed().value ^ fnv1a(name.as_bytes()))
+}
+
+/// 64-bit FNV-1a: a hash that is small, and the same on every machine and
+/// Rust version, for anything that must pick the same way next time
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
@@ -0,0 +1,132 @@
+//! Recently Practiced
+//!
+//! `rust-learn run --random` picks a lesson (or section) the learner has not
+//! seen lately. What was shown is kept in `recent.txt` next to the progress
+//! store (`store::dir`), oldest first, one `lesson` or `lesson::section` per
+//! line.
+
+use crate::random::Rng;
+use crate::store;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+const FILE_NAME: &str = "recent.txt";
+
+/// Enough history for every lesson or every section of a big one
+const MAX_ENTRIES: usize = 100;
+
+pub struct Recent {
+    /// Where `save` writes; `None` when no data directory could be found
+    path: Option<PathBuf>,
+    /// Oldest first, each entry at most once
+    shown: VecDeque<String>,
+}
+
+impl Recent {
+    /// The saved history, or an empty one if there is no file yet
+    pub fn load() -> Recent {
+        match store::dir() {
+            Some(dir) => Recent::load_from(&dir.join(FILE_NAME)),
+            None => Recent {
+                path: None,
+                shown: VecDeque::new(),
+            },
+        }
+    }
+
+    /// The history saved in `path`; a missing or unreadable file is empty
+    pub fn load_from(path: &Path) -> Recent {
+        let shown = fs::read_to_string(path)
+            .unwrap_or_default()
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(String::from)
+            .collect::<VecDeque<_>>();
+        debug!(path = %path.display(), entries = shown.len(), "loaded recent picks");
+        Recent {
+            path: Some(path.to_path_buf()),
+            shown,
+        }
+    }
+
+    /// A random candidate, leaving out the most recently shown half of them
+    pub fn pick<'a>(&self, candidates: &[&'a str], rng: &mut Rng) -> Option<&'a str> {
+        let avoid: Vec<&str> = self
+            .shown
+            .iter()
+            .rev()
+            .map(String::as_str)
+            .filter(|key| candidates.contains(key))
+            .take(candidates.len() / 2)
+            .collect();
+        let fresh: Vec<&'a str> = candidates
+            .iter()
+            .copied()
+            .filter(|key| !avoid.contains(key))
+            .collect();
+        if fresh.is_empty() {
+            return None;
+        }
+        let pick = fresh[rng.below(fresh.len())];
+        debug!(
+            candidates = candidates.len(),
+            avoided = avoid.len(),
+            pick,
+            "picked"
+        );
+        Some(pick)
+    }
+
+    /// Note that `key` was just shown
+    pub fn record(&mut self, key: &str) {
+        self.shown.retain(|shown| shown != key);
+        self.shown.push_back(key.to_string());
+        while self.shown.len() > MAX_ENTRIES {
+            self.shown.pop_front();
+        }
+    }
+
+    /// Write the file, creating the data directory if needed
+    pub fn save(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "no data directory (set RUST_LEARN_DATA_DIR or HOME)",
+            ));
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut text = String::new();
+        for key in &self.shown {
+            text.push_str(key);
+            text.push('\n');
+        }
+        fs::write(path, text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_skips_recently_shown() {
+        let mut recent = Recent::load_from(Path::new("/nonexistent/recent.txt"));
+        let candidates = ["loops", "match", "enums", "arrays"];
+        recent.record("enums");
+        recent.record("loops");
+        recent.record("enums");
+
+        for seed in 1..50 {
+            let pick = recent.pick(&candidates, &mut Rng::with_seed(seed)).unwrap();
+            assert!(pick == "match" || pick == "arrays", "picked {}", pick);
+        }
+        assert_eq!(recent.pick(&["only"], &mut Rng::with_seed(7)), Some("only"));
+        assert_eq!(recent.pick(&[], &mut Rng::with_seed(7)), None);
+    }
+}
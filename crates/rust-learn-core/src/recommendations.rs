@@ -0,0 +1,180 @@
+//! Crate Recommendations
+//!
+//! "Which crate should I use for ...?" answered offline: `rust-learn crates
+//! http` prints a short, curated list of crates for a topic, each with one
+//! line on why and, when there is one, the lesson that shows it in use.
+//! Each topic is a TOML file read like the reference topics (`reference`),
+//! shipped in rust-learn-content (`registry::RECOMMENDATIONS`):
+//!
+//! ```toml
+//! name = "async"
+//! title = "Async Runtimes"
+//! aliases = ["await", "futures"]
+//! intro = "std defines futures but runs none."
+//!
+//! [[crate]]
+//! name = "tokio"
+//! why = "The most used runtime: tasks, timers, async I/O and channels."
+//! lesson = "async_await"      # optional: a lesson, or `lesson::section`
+//! ```
+
+use crate::fuzzy;
+use crate::settings::{Settings, Table};
+
+/// The crates for one topic
+#[derive(Debug)]
+pub struct Topic {
+    pub name: String,
+    pub title: String,
+    /// Other words for it, looked up like its name
+    pub aliases: Vec<String>,
+    pub intro: String,
+    /// Best first
+    pub crates: Vec<Recommendation>,
+}
+
+#[derive(Debug)]
+pub struct Recommendation {
+    pub name: String,
+    /// One line on what it is for
+    pub why: String,
+    /// The lesson, or `lesson::section`, that shows it in use
+    pub lesson: Option<String>,
+}
+
+impl Topic {
+    /// Parse a topic file; the error says what is missing or wrong
+    pub fn parse(text: &str) -> Result<Topic, String> {
+        let settings = Settings::parse(text)?;
+        let root = settings.root();
+        let required = |table: &Table, key: &str| {
+            table
+                .str(key)
+                .map(String::from)
+                .ok_or_else(|| format!("`{}` is missing", key))
+        };
+        if let Some(table) = settings
+            .all_tables()
+            .iter()
+            .find(|table| table.name != "crate")
+        {
+            return Err(format!("unknown table [{}]", table.name));
+        }
+        let crates = settings
+            .tables("crate")
+            .enumerate()
+            .map(|(index, table)| {
+                let field = |key| {
+                    required(table, key).map_err(|error| format!("crate {}: {}", index + 1, error))
+                };
+                Ok(Recommendation {
+                    name: field("name")?,
+                    why: field("why")?,
+                    lesson: table.str("lesson").map(String::from),
+                })
+            })
+            .collect::<Result<Vec<Recommendation>, String>>()?;
+        if crates.is_empty() {
+            return Err("there is no [[crate]]".to_string());
+        }
+        Ok(Topic {
+            name: required(root, "name")?,
+            title: required(root, "title")?,
+            aliases: root
+                .strings("aliases")
+                .unwrap_or_default()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            intro: required(root, "intro")?,
+            crates,
+        })
+    }
+}
+
+/// Every topic in `files` (name and text), in order; a file that does not
+/// parse is an error naming it
+pub fn parse_all(files: &[(&str, &str)]) -> Result<Vec<Topic>, String> {
+    files
+        .iter()
+        .map(|(file, text)| Topic::parse(text).map_err(|error| format!("{}: {}", file, error)))
+        .collect()
+}
+
+/// What `find` made of a query
+pub enum Found<'a> {
+    Topic(&'a Topic),
+    /// Only close: the topic, and whether it is close enough to use
+    Guess(&'a Topic, bool),
+    /// The topics that recommend a crate of that name
+    Crate(Vec<&'a Topic>),
+    Nothing,
+}
+
+/// The topic `query` names or has as an alias (ignoring case), else the
+/// topics recommending a crate of that name, else the closest name
+pub fn find<'a>(topics: &'a [Topic], query: &str) -> Found<'a> {
+    let query = query.trim().to_lowercase();
+    let exact = topics.iter().find(|topic| {
+        topic.name == query
+            || topic
+                .aliases
+                .iter()
+                .any(|alias| alias.to_lowercase() == query)
+    });
+    if let Some(topic) = exact {
+        return Found::Topic(topic);
+    }
+    let recommending: Vec<&Topic> = topics
+        .iter()
+        .filter(|topic| topic.crates.iter().any(|krate| krate.name == query))
+        .collect();
+    if !recommending.is_empty() {
+        return Found::Crate(recommending);
+    }
+    // Each topic under its name and its aliases
+    let names: Vec<(&str, &Topic)> = topics
+        .iter()
+        .flat_map(|topic| {
+            std::iter::once(topic.name.as_str())
+                .chain(topic.aliases.iter().map(String::as_str))
+                .map(move |name| (name, topic))
+        })
+        .collect();
+    let candidates: Vec<&str> = names.iter().map(|(name, _)| *name).collect();
+    match fuzzy::closest(&query, &candidates) {
+        Some(guess) => {
+            let (_, topic) = names
+                .iter()
+                .find(|(name, _)| *name == guess.name)
+                .expect("closest returns one of the names");
+            Found::Guess(topic, guess.confident)
+        }
+        None => Found::Nothing,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_find() {
+        let topics = parse_all(&[(
+            "cli.toml",
+            "name = \"cli\"\ntitle = \"CLI\"\naliases = [\"args\"]\nintro = \"Flags.\"\n\n\
+             [[crate]]\nname = \"clap\"\nwhy = \"Derive a parser.\"\n\n\
+             [[crate]]\nname = \"lexopt\"\nwhy = \"A lexer.\"\nlesson = \"loops\"\n",
+        )])
+        .unwrap();
+        assert_eq!(topics[0].crates[1].lesson.as_deref(), Some("loops"));
+        assert!(matches!(find(&topics, "cli"), Found::Topic(_)));
+        assert!(matches!(find(&topics, "ARGS"), Found::Topic(_)));
+        assert!(matches!(find(&topics, "clap"), Found::Crate(found) if found.len() == 1));
+        assert!(matches!(find(&topics, "arg"), Found::Guess(_, true)));
+        assert!(matches!(find(&topics, "graphics"), Found::Nothing));
+
+        let error = parse_all(&[("x.toml", "name = \"x\"\n[[crate]]\nname = \"a\"\n")]);
+        assert_eq!(error.unwrap_err(), "x.toml: crate 1: `why` is missing");
+    }
+}
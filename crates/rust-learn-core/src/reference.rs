@@ -0,0 +1,139 @@
+//! Reference Excerpts
+//!
+//! Short excerpts of the Rust reference material a lesson leans on (the
+//! borrowing rules, lifetime elision, which trait each operator calls,
+//! integer ranges), bundled so `rust-learn ref borrowing` works without a
+//! network. Each topic is a TOML file in the subset config.toml is read with
+//! (`settings`), shipped in rust-learn-content (`registry::REFERENCE`):
+//!
+//! ```toml
+//! name = "borrowing"
+//! title = "The Borrowing Rules"
+//! lessons = ["ownership", "borrowing"]
+//! see = "The Rust Programming Language, 4.2 References and Borrowing"
+//! intro = "A reference borrows a value without taking ownership of it."
+//!
+//! [[entry]]
+//! term = "One writer or many readers"
+//! text = "At any given time a value can have either one `&mut T` or any number of `&T`."
+//! code = "let r = &s;"
+//! ```
+//!
+//! `lessons` are the lessons that link to the topic when they finish. An
+//! entry's `term` and `code` are optional; when every entry has a `term` and
+//! no `code`, the topic is a table (term, then text), like the operator one.
+
+use crate::settings::{Settings, Table};
+
+/// One bundled topic
+#[derive(Debug)]
+pub struct Topic {
+    pub name: String,
+    pub title: String,
+    /// Lessons that point to it
+    pub lessons: Vec<String>,
+    /// Where the full text is, for when there is a network
+    pub see: Option<String>,
+    pub intro: String,
+    pub entries: Vec<Entry>,
+}
+
+/// A rule, or a row of a table
+#[derive(Debug)]
+pub struct Entry {
+    pub term: Option<String>,
+    pub text: String,
+    pub code: Option<String>,
+}
+
+impl Topic {
+    /// Parse a topic file; the error says what is missing or wrong
+    pub fn parse(text: &str) -> Result<Topic, String> {
+        let settings = Settings::parse(text)?;
+        let root = settings.root();
+        let required = |table: &Table, key: &str| {
+            table
+                .str(key)
+                .map(String::from)
+                .ok_or_else(|| format!("`{}` is missing", key))
+        };
+        if let Some(table) = settings
+            .all_tables()
+            .iter()
+            .find(|table| table.name != "entry")
+        {
+            return Err(format!("unknown table [{}]", table.name));
+        }
+        let entries = settings
+            .tables("entry")
+            .enumerate()
+            .map(|(index, table)| {
+                Ok(Entry {
+                    term: table.str("term").map(String::from),
+                    text: required(table, "text")
+                        .map_err(|error| format!("entry {}: {}", index + 1, error))?,
+                    code: table.str("code").map(String::from),
+                })
+            })
+            .collect::<Result<Vec<Entry>, String>>()?;
+        if entries.is_empty() {
+            return Err("there is no [[entry]]".to_string());
+        }
+        Ok(Topic {
+            name: required(root, "name")?,
+            title: required(root, "title")?,
+            lessons: root
+                .strings("lessons")
+                .unwrap_or_default()
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            see: root.str("see").map(String::from),
+            intro: required(root, "intro")?,
+            entries,
+        })
+    }
+
+    /// Whether the entries read best as a two-column table
+    pub fn is_table(&self) -> bool {
+        self.entries
+            .iter()
+            .all(|entry| entry.term.is_some() && entry.code.is_none())
+    }
+}
+
+/// Every topic in `files` (name and text), in order; a file that does not
+/// parse is an error naming it
+pub fn parse_all(files: &[(&str, &str)]) -> Result<Vec<Topic>, String> {
+    files
+        .iter()
+        .map(|(file, text)| Topic::parse(text).map_err(|error| format!("{}: {}", file, error)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_topic() {
+        let topic = Topic::parse(
+            "name = \"integers\"\ntitle = \"Integers\"\nlessons = [\"variables\"]\n\
+             intro = \"Literals are i32.\"\n\n\
+             [[entry]]\nterm = \"u8\"\ntext = \"0 to 255\"\n\n\
+             [[entry]]\nterm = \"i8\"\ntext = \"-128 to 127\"\n",
+        )
+        .unwrap();
+        assert_eq!(topic.name, "integers");
+        assert_eq!(topic.lessons, ["variables"]);
+        assert_eq!(topic.see, None);
+        assert_eq!(topic.entries[1].text, "-128 to 127");
+        assert!(topic.is_table());
+
+        let missing =
+            Topic::parse("name = \"x\"\ntitle = \"X\"\nintro = \"\"\n[[entry]]\nterm = \"a\"\n");
+        assert_eq!(missing.unwrap_err(), "entry 1: `text` is missing");
+        let unknown = Topic::parse("name = \"x\"\n[rule]\ntext = \"a\"\n");
+        assert_eq!(unknown.unwrap_err(), "unknown table [rule]");
+    }
+}
@@ -0,0 +1,280 @@
+//! Remote Builds
+//!
+//! On school computers where a toolchain cannot be installed, exercises and
+//! compile-error demos can be built by a service on the network instead.
+//! Set its URL, and `Remote` takes the place of the local `CommandRunner`
+//! (`toolchain::set_runner`) for the whole run:
+//!
+//! ```toml
+//! [remote]
+//! url = "http://build.school.local:8080/run"
+//! timeout = 60          # seconds, per read
+//! ```
+//!
+//! `RUST_LEARN_REMOTE` sets the URL too, ahead of config.toml. Without either,
+//! everything is built locally as usual.
+//!
+//! Only `rustc` compiling a file goes to the service, as one POST:
+//!
+//! ```json
+//! {"file": "ownership1.rs", "source": "fn main() {...}", "edition": "2024", "mode": "run"}
+//! ```
+//!
+//! `mode` is `run` (build the program and run it), `test` (build with
+//! `--test` and run the tests) or `check` (type-check only). The service
+//! answers with one JSON object a line, as things happen: `{"stream":
+//! "compile", "text": ...}` for rustc's messages, `{"stream": "stdout" |
+//! "stderr", "text": ...}` for what the program printed, and last
+//! `{"compiled": true, "success": false}`. The program's run is kept for the
+//! binary path the build asked for, and handed back when that binary is run;
+//! anything else (git, cargo, `rustc --version`) still runs here.
+
+use crate::json::Value;
+use crate::settings;
+use crate::toolchain::{CommandOutput, CommandRunner, Invocation, System};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::debug;
+
+const URL_VAR: &str = "RUST_LEARN_REMOTE";
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// A `CommandRunner` that sends builds to a remote service
+pub struct Remote {
+    url: String,
+    timeout: Duration,
+    /// What running each binary built remotely printed, by its path
+    runs: Mutex<HashMap<PathBuf, CommandOutput>>,
+}
+
+/// Send builds to the configured service from now on, if there is one; its
+/// URL when there is
+pub fn install() -> Option<String> {
+    let config = settings::get().table("remote");
+    let url = std::env::var(URL_VAR)
+        .ok()
+        .or_else(|| {
+            config
+                .and_then(|config| config.str("url"))
+                .map(String::from)
+        })
+        .filter(|url| !url.trim().is_empty())?;
+    let timeout = config
+        .and_then(|config| config.integer("timeout"))
+        .and_then(|seconds| u64::try_from(seconds).ok())
+        .map_or(DEFAULT_TIMEOUT, Duration::from_secs);
+    crate::toolchain::set_runner(Box::new(Remote::new(&url, timeout)));
+    Some(url)
+}
+
+/// A rustc invocation that compiles a file, as the service takes it
+#[derive(Debug, PartialEq)]
+struct Build {
+    file: PathBuf,
+    edition: String,
+    mode: &'static str,
+    /// Where the binary goes (`-o`), to answer its run from
+    output: Option<PathBuf>,
+}
+
+impl Remote {
+    pub fn new(url: &str, timeout: Duration) -> Remote {
+        Remote {
+            url: url.to_string(),
+            timeout,
+            runs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn build(&self, build: &Build, dir: &Path) -> io::Result<CommandOutput> {
+        let source = fs::read_to_string(dir.join(&build.file))?;
+        let name = build.file.file_name().unwrap_or_default().to_string_lossy();
+        let request = Value::Object(vec![
+            ("file".to_string(), Value::from(name.as_ref())),
+            ("source".to_string(), Value::from(source.as_str())),
+            ("edition".to_string(), Value::from(build.edition.as_str())),
+            ("mode".to_string(), Value::from(build.mode)),
+        ]);
+        let (mut compile, mut run) = (String::new(), CommandOutput::default());
+        let mut outcome = None;
+        let mut on_line = |line: &str| {
+            let Ok(message) = Value::parse(line) else {
+                debug!(line, "not JSON, ignored");
+                return;
+            };
+            let text = message.get("text").and_then(Value::as_str).unwrap_or("");
+            match message.get("stream").and_then(Value::as_str) {
+                Some("compile") => compile.push_str(text),
+                Some("stdout") => run.stdout.push_str(text),
+                Some("stderr") => run.stderr.push_str(text),
+                _ => {
+                    let flag = |key| message.get(key) == Some(&Value::Bool(true));
+                    if message.get("compiled").is_some() {
+                        outcome = Some((flag("compiled"), flag("success")));
+                    }
+                }
+            }
+        };
+        crate::http::post_lines(&self.url, &request.to_string(), self.timeout, &mut on_line)?;
+        let (compiled, success) = outcome.ok_or_else(|| {
+            io::Error::other("the build service stopped before saying how the build went")
+        })?;
+        debug!(compiled, success, mode = build.mode, "remote build");
+        if let (true, Some(output)) = (compiled, &build.output) {
+            run.success = success;
+            self.runs
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .insert(absolute(dir, output), run);
+        }
+        Ok(CommandOutput {
+            success: compiled,
+            stdout: String::new(),
+            stderr: compile,
+            timed_out: false,
+        })
+    }
+}
+
+impl CommandRunner for Remote {
+    fn run(&self, invocation: &Invocation) -> io::Result<CommandOutput> {
+        let program = Path::new(&invocation.program);
+        if program.file_stem().is_some_and(|stem| stem == "rustc")
+            && let Some(build) = parse_build(&invocation.args)
+        {
+            return self.build(&build, &invocation.dir);
+        }
+        let ran = self
+            .runs
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(&absolute(&invocation.dir, program));
+        match ran {
+            Some(output) => Ok(output),
+            None => System.run(invocation),
+        }
+    }
+}
+
+/// The build in rustc's `args`, when they compile a `.rs` file
+fn parse_build(args: &[String]) -> Option<Build> {
+    let mut build = Build {
+        file: PathBuf::new(),
+        edition: "2021".to_string(),
+        mode: "run",
+        output: None,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--edition" => build.edition = args.next()?.clone(),
+            "-o" => build.output = Some(PathBuf::from(args.next()?)),
+            "--test" => build.mode = "test",
+            "--emit=metadata" => build.mode = "check",
+            arg if arg.ends_with(".rs") => build.file = PathBuf::from(arg),
+            _ => {}
+        }
+    }
+    (!build.file.as_os_str().is_empty()).then_some(build)
+}
+
+fn absolute(dir: &Path, path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        dir.join(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn test_remote_build_and_run() {
+        let args = |args: &[&str]| args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        assert_eq!(parse_build(&args(&["--version"])), None);
+        assert_eq!(
+            parse_build(&args(&[
+                "--edition",
+                "2024",
+                "-o",
+                "bin/a",
+                "a.rs",
+                "--test"
+            ])),
+            Some(Build {
+                file: PathBuf::from("a.rs"),
+                edition: "2024".to_string(),
+                mode: "test",
+                output: Some(PathBuf::from("bin/a")),
+            })
+        );
+
+        // A service that streams its answer in chunks split mid-line
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/run", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut length = 0;
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap() > 2 {
+                if let Some(value) = line.strip_prefix("Content-Length: ") {
+                    length = value.trim().parse().unwrap();
+                }
+                line.clear();
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            let mut stream = stream;
+            let reply = "{\"stream\": \"stdout\", \"text\": \"hi\\n\"}\n\
+                         {\"compiled\": true, \"success\": true}\n";
+            let (first, second) = reply.split_at(20);
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n\
+                 {:x}\r\n{}\r\n{:x}\r\n{}\r\n0\r\n\r\n",
+                first.len(),
+                first,
+                second.len(),
+                second
+            )
+            .unwrap();
+            String::from_utf8(body).unwrap()
+        });
+
+        let dir = std::env::temp_dir().join(format!("rust-learn-remote-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.rs"), "fn main() {}").unwrap();
+        let remote = Remote::new(&url, Duration::from_secs(5));
+        let invocation = |program: &str, args: &[&str]| Invocation {
+            program: program.into(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+            dir: dir.clone(),
+            env: Vec::new(),
+            timeout: None,
+        };
+        let built = remote
+            .run(&invocation(
+                "rustc",
+                &["--edition", "2024", "-o", "a", "a.rs"],
+            ))
+            .unwrap();
+        assert!(built.success);
+        let request = Value::parse(&server.join().unwrap()).unwrap();
+        assert_eq!(request.get("file").and_then(Value::as_str), Some("a.rs"));
+        assert_eq!(request.get("mode").and_then(Value::as_str), Some("run"));
+
+        let ran = remote.run(&invocation("a", &[])).unwrap();
+        assert!(ran.success);
+        assert_eq!(ran.stdout, "hi\n");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
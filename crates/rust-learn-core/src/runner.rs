@@ -0,0 +1,298 @@
+//! Running Lessons
+//!
+//! `run_all` runs a list of lessons back to back (`rust-learn run --all`), with a
+//! banner before each one. `print_summary` then shows, for each lesson, the
+//! sections that ran, how long it took, the most memory it needed (see
+//! `memory`) and which sections were skipped;
+//! `report` is the same as JSON for scripts (`--report json`). A lesson that
+//! panics is reported in the summary instead of stopping the whole run; one
+//! that hangs past the watchdog's limit ends it (see `watchdog`).
+
+use crate::crash;
+use crate::events;
+use crate::history;
+use crate::input;
+use crate::json::Value;
+use crate::lesson::{self, Lesson};
+use crate::memory::{self, Peak, Source};
+use crate::output::{self, Glyph};
+use crate::watchdog;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+use tracing::{debug, debug_span, warn};
+
+/// How one lesson went during a full run
+pub struct LessonOutcome {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    /// `None` when it could not be measured on this platform
+    pub peak_memory: Option<Peak>,
+    /// `false` when the lesson panicked
+    pub completed: bool,
+    /// The sections that started, in order
+    pub sections_run: Vec<&'static str>,
+    /// The sections that never started, after a panic
+    pub sections_not_run: Vec<&'static str>,
+    /// Interactive sections that ran on sample input instead of the
+    /// learner's, because the run was not interactive
+    pub sample_input: Vec<&'static str>,
+}
+
+impl LessonOutcome {
+    /// What the learner did not see: the sections that did not run, then
+    /// the ones that used sample input
+    pub fn skipped(&self) -> Vec<String> {
+        self.sections_not_run
+            .iter()
+            .map(|name| name.to_string())
+            .chain(
+                self.sample_input
+                    .iter()
+                    .map(|name| format!("{} (sample input)", name)),
+            )
+            .collect()
+    }
+}
+
+/// Run one lesson, timing it and catching a panic
+pub fn run_timed(lesson: &Lesson) -> LessonOutcome {
+    let _span = debug_span!("lesson", name = lesson.name).entered();
+    crash::set_active(Some(lesson.name.to_string()));
+    input::take_sample_input_headings();
+    // The CLI takes the transcript (`history::finish`) once the lesson is done
+    history::start();
+    // Also starts a new list of the headings printed, one per section
+    let watch = watchdog::watch(lesson, None);
+    output::set_running(Some(lesson));
+    let start = Instant::now();
+    let (completed, peak_memory) =
+        memory::measure(|| panic::catch_unwind(AssertUnwindSafe(lesson.run)).is_ok());
+    output::set_running(None);
+    drop(watch);
+    crash::set_active(None);
+    if completed {
+        debug!(elapsed = ?start.elapsed(), "lesson finished");
+    } else {
+        warn!(elapsed = ?start.elapsed(), "lesson panicked");
+    }
+
+    let sections = |headings: Vec<String>| -> Vec<&'static str> {
+        headings
+            .iter()
+            .filter_map(|heading| lesson::section_by_heading(lesson.sections, heading))
+            .map(|section| section.name)
+            .collect()
+    };
+    let sections_run = sections(output::headings());
+    let sections_not_run = lesson
+        .sections
+        .iter()
+        .map(|section| section.name)
+        .filter(|name| !sections_run.contains(name))
+        .collect();
+
+    LessonOutcome {
+        name: lesson.name,
+        elapsed: start.elapsed(),
+        peak_memory,
+        completed,
+        sections_run,
+        sections_not_run,
+        sample_input: sections(input::take_sample_input_headings()),
+    }
+}
+
+/// Run every lesson in order. `finished` sees each outcome as soon as it is
+/// known, so a run stopped halfway still counts
+pub fn run_all(
+    lessons: &[Lesson],
+    mut finished: impl FnMut(&Lesson, &LessonOutcome),
+) -> Vec<LessonOutcome> {
+    let _span = debug_span!("run_all", lessons = lessons.len()).entered();
+    let mut outcomes = Vec::new();
+
+    for (index, lesson) in lessons.iter().enumerate() {
+        print_banner(index + 1, lessons.len(), lesson);
+        let outcome = run_timed(lesson);
+        finished(lesson, &outcome);
+        outcomes.push(outcome);
+    }
+    outcomes
+}
+
+fn print_banner(number: usize, count: usize, lesson: &Lesson) {
+    if !output::settings().lesson_output() || events::enabled() {
+        return;
+    }
+    let line = "#".repeat(72);
+    println!("{}", line);
+    println!("# Lesson {}/{}: {}", number, count, lesson.name);
+    println!("# {}", lesson.title);
+    println!("{}\n", line);
+}
+
+/// A table of the lessons run: sections, time, peak memory, whether each
+/// finished and what was skipped. The lesson column is `name_width` wide, or
+/// wider for a longer name
+pub fn print_summary(outcomes: &[LessonOutcome], name_width: usize) {
+    println!("\n=== Summary ===\n");
+
+    let width = outcomes
+        .iter()
+        .map(|outcome| outcome.name.len())
+        .chain([name_width, "LESSON".len()])
+        .max()
+        .unwrap_or(0);
+    println!(
+        "{:<width$} {:>8} {:>10} {:>11}  {:<12}  SKIPPED",
+        "LESSON", "SECTIONS", "TIME", "PEAK", "STATUS"
+    );
+    for outcome in outcomes {
+        let status = if outcome.completed {
+            output::status(Glyph::Ok, "ok", output::green, 12)
+        } else {
+            output::status(Glyph::Fail, "PANICKED", output::red, 12)
+        };
+        let sections = format!(
+            "{}/{}",
+            outcome.sections_run.len(),
+            outcome.sections_run.len() + outcome.sections_not_run.len()
+        );
+        let peak = outcome
+            .peak_memory
+            .map_or_else(|| "-".to_string(), |peak| peak.to_string());
+        let line = format!(
+            "{:<width$} {:>8} {:>10} {:>11}  {}  {}",
+            outcome.name,
+            sections,
+            format_duration(outcome.elapsed),
+            peak,
+            status,
+            outcome.skipped().join(", ")
+        );
+        println!("{}", line.trim_end());
+    }
+
+    let total: Duration = outcomes.iter().map(|outcome| outcome.elapsed).sum();
+    let failed = outcomes.iter().filter(|outcome| !outcome.completed).count();
+    let most = outcomes
+        .iter()
+        .filter_map(|outcome| outcome.peak_memory)
+        .max_by_key(|peak| peak.bytes)
+        .map_or_else(String::new, |peak| peak.to_string());
+    println!("{}", "-".repeat(width + 32));
+    println!(
+        "{:<width$} {:>8} {:>10} {:>11}",
+        "total",
+        "",
+        format_duration(total),
+        most
+    );
+    println!(
+        "\n{} lessons, {} completed, {} panicked",
+        outcomes.len(),
+        outcomes.len() - failed,
+        failed
+    );
+    let heap_only = outcomes.iter().any(|outcome| {
+        outcome
+            .peak_memory
+            .is_some_and(|peak| peak.source == Source::Heap)
+    });
+    if heap_only {
+        println!("* heap in use at most, where the resident growth could not be measured");
+    }
+}
+
+/// The summary as JSON, printed on one line:
+///
+/// ```json
+/// {"lessons":[{"name":"vectors","completed":true,"seconds":0.0031,
+///   "peak_memory":{"bytes":4739072,"source":"resident"},"sections_run":["creating_vectors",...],"sections_not_run":[],
+///   "sample_input":["reading_numbers"]}],"total_seconds":1.31,"completed":23,"panicked":0}
+/// ```
+pub fn report(outcomes: &[LessonOutcome]) -> Value {
+    let names = |names: &[&str]| Value::Array(names.iter().map(|&name| name.into()).collect());
+    let lessons = outcomes
+        .iter()
+        .map(|outcome| {
+            Value::Object(vec![
+                ("name".to_string(), outcome.name.into()),
+                ("completed".to_string(), Value::Bool(outcome.completed)),
+                (
+                    "seconds".to_string(),
+                    Value::Number(outcome.elapsed.as_secs_f64()),
+                ),
+                (
+                    "peak_memory".to_string(),
+                    outcome.peak_memory.map_or(Value::Null, |peak| {
+                        Value::Object(vec![
+                            ("bytes".to_string(), Value::Number(peak.bytes as f64)),
+                            ("source".to_string(), peak.source.name().into()),
+                        ])
+                    }),
+                ),
+                ("sections_run".to_string(), names(&outcome.sections_run)),
+                (
+                    "sections_not_run".to_string(),
+                    names(&outcome.sections_not_run),
+                ),
+                ("sample_input".to_string(), names(&outcome.sample_input)),
+            ])
+        })
+        .collect();
+    let total: Duration = outcomes.iter().map(|outcome| outcome.elapsed).sum();
+    let completed = outcomes.iter().filter(|outcome| outcome.completed).count();
+    Value::Object(vec![
+        ("lessons".to_string(), Value::Array(lessons)),
+        (
+            "total_seconds".to_string(),
+            Value::Number(total.as_secs_f64()),
+        ),
+        ("completed".to_string(), Value::Number(completed as f64)),
+        (
+            "panicked".to_string(),
+            Value::Number((outcomes.len() - completed) as f64),
+        ),
+    ])
+}
+
+/// `850.3ms` below a second, `2.41s` above
+pub fn format_duration(duration: Duration) -> String {
+    if duration < Duration::from_secs(1) {
+        format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_report() {
+        let outcomes = [LessonOutcome {
+            name: "vectors",
+            elapsed: Duration::from_millis(250),
+            peak_memory: Some(Peak {
+                bytes: 2048,
+                source: Source::Heap,
+            }),
+            completed: false,
+            sections_run: vec!["creating"],
+            sections_not_run: vec!["slices"],
+            sample_input: vec!["reading_numbers"],
+        }];
+        assert_eq!(
+            outcomes[0].skipped(),
+            ["slices", "reading_numbers (sample input)"]
+        );
+        assert_eq!(
+            report(&outcomes).to_string(),
+            "{\"lessons\":[{\"name\":\"vectors\",\"completed\":false,\"seconds\":0.25,\
+             \"peak_memory\":{\"bytes\":2048,\"source\":\"heap\"},\"sections_run\":[\"creating\"],\"sections_not_run\":[\"slices\"],\
+             \"sample_input\":[\"reading_numbers\"]}],\"total_seconds\":0.25,\"completed\":0,\"panicked\":1}"
+        );
+    }
+}
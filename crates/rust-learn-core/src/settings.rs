@@ -0,0 +1,331 @@
+//! The Settings File
+//!
+//! The learner's own settings, from `config.toml` in the config directory
+//! (`dirs::config_dir`). The file is optional; without it every setting has
+//! its default. It is a small subset of TOML, read by this module itself:
+//! `[tables]`, `[[arrays of tables]]`, comments, and `key = value` lines whose
+//! value is a string (with `\n`-style escapes), an integer, a boolean or an
+//! array of strings, all on one line.
+//!
+//! ```toml
+//! # Keep progress on the class drive
+//! [store]
+//! backend = "json"
+//! path = "/mnt/class/alice/progress.json"
+//! ```
+
+use crate::dirs;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tracing::debug;
+
+const FILE_NAME: &str = "config.toml";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Bool(bool),
+    Array(Vec<String>),
+}
+
+/// One `[table]` or one entry of a `[[table]]` array
+#[derive(Debug, Default)]
+pub struct Table {
+    pub name: String,
+    entries: Vec<(String, Value)>,
+}
+
+impl Table {
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        self.entries
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+
+    pub fn str(&self, key: &str) -> Option<&str> {
+        match self.get(key)? {
+            Value::String(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    pub fn integer(&self, key: &str) -> Option<i64> {
+        match self.get(key)? {
+            Value::Integer(number) => Some(*number),
+            _ => None,
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Option<bool> {
+        match self.get(key)? {
+            Value::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The keys, in the order they were written
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(key, _)| key.as_str())
+    }
+
+    /// An array of strings, or a single string as an array of one
+    pub fn strings(&self, key: &str) -> Option<Vec<&str>> {
+        match self.get(key)? {
+            Value::Array(items) => Some(items.iter().map(String::as_str).collect()),
+            Value::String(text) => Some(vec![text]),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Settings {
+    /// Keys before the first table header
+    root: Table,
+    tables: Vec<Table>,
+}
+
+impl Settings {
+    /// Parse the file's text; the error names the line that is wrong
+    pub fn parse(text: &str) -> Result<Settings, String> {
+        let mut settings = Settings::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let error = |message: &str| format!("line {}: {}", index + 1, message);
+
+            let header = line
+                .strip_prefix("[[")
+                .and_then(|rest| rest.strip_suffix("]]"))
+                .or_else(|| {
+                    line.strip_prefix('[')
+                        .and_then(|rest| rest.strip_suffix(']'))
+                });
+            if let Some(name) = header {
+                settings.tables.push(Table {
+                    name: name.trim().to_string(),
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error("expected `key = value` or a [table] header"));
+            };
+            let key = key.trim().trim_matches('"').to_string();
+            let value = parse_value(value.trim()).ok_or_else(|| {
+                error("expected a \"string\", a number, true or false, or [\"an\", \"array\"]")
+            })?;
+            let table = settings.tables.last_mut().unwrap_or(&mut settings.root);
+            table.entries.push((key, value));
+        }
+        Ok(settings)
+    }
+
+    /// The `[name]` table
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+
+    /// Every `[[name]]` entry, in order
+    pub fn tables<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Table> + 'a {
+        self.tables.iter().filter(move |table| table.name == name)
+    }
+
+    /// A key outside any table
+    pub fn root(&self) -> &Table {
+        &self.root
+    }
+
+    /// Every table and `[[table]]` entry, in order
+    pub fn all_tables(&self) -> &[Table] {
+        &self.tables
+    }
+}
+
+/// Where the config file is (or would be)
+pub fn path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?.join(FILE_NAME))
+}
+
+/// The config file, read once. A missing file is an empty config; one that
+/// does not parse is reported on stderr and ignored
+pub fn get() -> &'static Settings {
+    static CONFIG: OnceLock<Settings> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let Some(path) = path() else {
+            return Settings::default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            debug!(path = %path.display(), "no config file");
+            return Settings::default();
+        };
+        match Settings::parse(&text) {
+            Ok(settings) => {
+                debug!(path = %path.display(), tables = settings.tables.len(), "read config");
+                settings
+            }
+            Err(error) => {
+                eprintln!(
+                    "Ignoring {}: {} (defaults are used instead)",
+                    path.display(),
+                    error
+                );
+                Settings::default()
+            }
+        }
+    })
+}
+
+/// The learner's name, for announcements and submissions: `[profile] name`,
+/// or else the login name
+pub fn learner() -> String {
+    let configured = get()
+        .table("profile")
+        .and_then(|profile| profile.str("name"))
+        .map(String::from);
+    configured
+        .or_else(|| std::env::var("USER").ok())
+        .or_else(|| std::env::var("USERNAME").ok())
+        .unwrap_or_else(|| "Someone".to_string())
+}
+
+fn parse_value(text: &str) -> Option<Value> {
+    if let Some(items) = text
+        .strip_prefix('[')
+        .and_then(|rest| rest.strip_suffix(']'))
+    {
+        return split_items(items)
+            .into_iter()
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(string)
+            .collect::<Option<Vec<_>>>()
+            .map(Value::Array);
+    }
+    match text {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+    if let Some(text) = string(text) {
+        return Some(Value::String(text));
+    }
+    text.replace('_', "").parse().ok().map(Value::Integer)
+}
+
+/// A `"string"`, with its `\"`, `\\`, `\n` and `\t` escapes, or a
+/// `'literal string'`, which has none
+fn string(text: &str) -> Option<String> {
+    if let Some(literal) = text
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+    {
+        return Some(literal.to_string());
+    }
+    let inside = text.strip_prefix('"')?.strip_suffix('"')?;
+    let mut unescaped = String::new();
+    let mut chars = inside.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => unescaped.push('\n'),
+            't' => unescaped.push('\t'),
+            c @ ('"' | '\\') => unescaped.push(c),
+            // Not an escape, like the `\U` of `C:\Users`: kept as written
+            c => {
+                unescaped.push('\\');
+                unescaped.push(c);
+            }
+        }
+    }
+    Some(unescaped)
+}
+
+/// The items of an array, split at the commas outside quotes
+fn split_items(text: &str) -> Vec<&str> {
+    let mut items = Vec::new();
+    let mut quote = None;
+    let mut escaped = false;
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        match (c, quote) {
+            _ if escaped => escaped = false,
+            ('\\', Some('"')) => escaped = true,
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            (',', None) => {
+                items.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&text[start..]);
+    items
+}
+
+/// The line without a `# comment`, leaving a `#` inside quotes alone
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match (c, quote) {
+            _ if escaped => escaped = false,
+            ('\\', Some('"')) => escaped = true,
+            ('"' | '\'', None) => quote = Some(c),
+            (c, Some(open)) if c == open => quote = None,
+            ('#', None) => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let text = "verbose = true\n\
+                    # Keep progress on the class drive\n\
+                    [store]\n\
+                    backend = \"json\"   # or toml\n\
+                    path = '/mnt/class #3/progress.json'\n\
+                    \n\
+                    [[hooks]]\n\
+                    on = [\"lesson\", \"quiz\"]\n\
+                    timeout = 10\n\
+                    [[hooks]]\n\
+                    on = \"lesson\"\n\
+                    choices = [\"a, b\", 'c \\n', \"say \\\"hi\\\"\\n\"] # 3\n";
+        let settings = Settings::parse(text).unwrap();
+        assert_eq!(settings.root().bool("verbose"), Some(true));
+        let store = settings.table("store").unwrap();
+        assert_eq!(store.str("backend"), Some("json"));
+        assert_eq!(store.str("path"), Some("/mnt/class #3/progress.json"));
+        let hooks: Vec<&Table> = settings.tables("hooks").collect();
+        assert_eq!(hooks.len(), 2);
+        assert_eq!(hooks[0].strings("on"), Some(vec!["lesson", "quiz"]));
+        assert_eq!(hooks[0].integer("timeout"), Some(10));
+        assert_eq!(hooks[1].strings("on"), Some(vec!["lesson"]));
+        assert_eq!(
+            hooks[1].strings("choices"),
+            Some(vec!["a, b", "c \\n", "say \"hi\"\n"])
+        );
+
+        assert_eq!(
+            Settings::parse("[store]\nbackend json").unwrap_err(),
+            "line 2: expected `key = value` or a [table] header"
+        );
+    }
+}
@@ -0,0 +1,225 @@
+//! SQLite Databases
+//!
+//! Just enough of SQLite's C API, through libsqlite3-sys, for the `Sqlite`
+//! progress store: open a file, run statements, bind text and numbers, and
+//! read rows back. SQLite is compiled in (the `bundled` build), so nothing
+//! has to be installed. Built only with the `sqlite` feature.
+
+use libsqlite3_sys as ffi;
+use std::ffi::{CStr, CString, c_char, c_int};
+use std::io;
+use std::path::Path;
+use std::ptr;
+
+/// An open database, closed when dropped
+pub struct Connection {
+    raw: *mut ffi::sqlite3,
+}
+
+impl Connection {
+    /// Open the database at `path`, creating the file if there is none
+    pub fn open(path: &Path) -> io::Result<Connection> {
+        let path = path
+            .to_str()
+            .and_then(|path| CString::new(path).ok())
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("SQLite cannot open {}", path.display()),
+                )
+            })?;
+        let mut raw = ptr::null_mut();
+        let flags = ffi::SQLITE_OPEN_READWRITE | ffi::SQLITE_OPEN_CREATE;
+        // SAFETY: `path` is NUL-terminated and outlives the call
+        let code = unsafe { ffi::sqlite3_open_v2(path.as_ptr(), &mut raw, flags, ptr::null()) };
+        // Even a failed open allocates a handle, for its error message
+        let connection = Connection { raw };
+        if code != ffi::SQLITE_OK {
+            return Err(connection.error());
+        }
+        Ok(connection)
+    }
+
+    /// Run `sql`, one or more statements that return no rows
+    pub fn execute(&self, sql: &str) -> io::Result<()> {
+        let sql = CString::new(sql).map_err(|error| io::Error::other(error.to_string()))?;
+        // SAFETY: `raw` is open and `sql` is NUL-terminated
+        let code = unsafe {
+            ffi::sqlite3_exec(
+                self.raw,
+                sql.as_ptr(),
+                None,
+                ptr::null_mut(),
+                ptr::null_mut(),
+            )
+        };
+        self.check(code)
+    }
+
+    /// Compile one statement, to bind values to and step through
+    pub fn prepare(&self, sql: &str) -> io::Result<Statement<'_>> {
+        let mut raw = ptr::null_mut();
+        // SAFETY: the length is `sql`'s, so it needs no terminating NUL
+        let code = unsafe {
+            ffi::sqlite3_prepare_v2(
+                self.raw,
+                sql.as_ptr().cast::<c_char>(),
+                length(sql.len())?,
+                &mut raw,
+                ptr::null_mut(),
+            )
+        };
+        self.check(code)?;
+        Ok(Statement {
+            raw,
+            connection: self,
+            row: false,
+        })
+    }
+
+    fn check(&self, code: c_int) -> io::Result<()> {
+        match code {
+            ffi::SQLITE_OK | ffi::SQLITE_ROW | ffi::SQLITE_DONE => Ok(()),
+            _ => Err(self.error()),
+        }
+    }
+
+    /// The last error, in SQLite's words
+    fn error(&self) -> io::Error {
+        // SAFETY: SQLite returns a NUL-terminated message it owns, valid
+        // until the next call on this connection; it is copied at once
+        let message = unsafe { CStr::from_ptr(ffi::sqlite3_errmsg(self.raw)) };
+        io::Error::other(format!("SQLite: {}", message.to_string_lossy()))
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // SAFETY: every `Statement` borrows the connection, so all of them
+        // have been finalized by now
+        unsafe { ffi::sqlite3_close(self.raw) };
+    }
+}
+
+/// A compiled statement; values are bound by 1-based index, columns are read
+/// by 0-based index
+pub struct Statement<'a> {
+    raw: *mut ffi::sqlite3_stmt,
+    connection: &'a Connection,
+    /// Whether the last `step` found a row, which is all the columns can be
+    /// read from
+    row: bool,
+}
+
+impl Statement<'_> {
+    pub fn bind_text(&mut self, index: usize, text: &str) -> io::Result<()> {
+        // SAFETY: SQLITE_TRANSIENT makes SQLite copy the text before
+        // returning, so it need not outlive the call
+        let code = unsafe {
+            ffi::sqlite3_bind_text(
+                self.raw,
+                length(index)?,
+                text.as_ptr().cast::<c_char>(),
+                length(text.len())?,
+                ffi::SQLITE_TRANSIENT(),
+            )
+        };
+        self.connection.check(code)
+    }
+
+    pub fn bind_int(&mut self, index: usize, value: i64) -> io::Result<()> {
+        // SAFETY: `raw` is a live statement
+        let code = unsafe { ffi::sqlite3_bind_int64(self.raw, length(index)?, value) };
+        self.connection.check(code)
+    }
+
+    /// Run the statement to its next row: `true` when there is one to read
+    pub fn step(&mut self) -> io::Result<bool> {
+        // SAFETY: `raw` is a live statement
+        let code = unsafe { ffi::sqlite3_step(self.raw) };
+        self.row = code == ffi::SQLITE_ROW;
+        self.connection.check(code)?;
+        Ok(self.row)
+    }
+
+    /// Run the statement again from the start, with new values bound
+    pub fn reset(&mut self) -> io::Result<()> {
+        // SAFETY: `raw` is a live statement
+        let code = unsafe { ffi::sqlite3_reset(self.raw) };
+        self.row = false;
+        self.connection.check(code)
+    }
+
+    /// Column `index` of the current row as text (empty for NULL); an error
+    /// when there is no such column or no current row
+    pub fn text(&self, index: usize) -> io::Result<String> {
+        let index = self.column(index)?;
+        // SAFETY: `column` found a row with this column; the text is valid
+        // until the next step, and copied before then. `column_bytes` comes
+        // after `column_text`, as SQLite asks
+        unsafe {
+            let text = ffi::sqlite3_column_text(self.raw, index);
+            if text.is_null() {
+                return Ok(String::new());
+            }
+            let len = ffi::sqlite3_column_bytes(self.raw, index) as usize;
+            Ok(String::from_utf8_lossy(std::slice::from_raw_parts(text, len)).into_owned())
+        }
+    }
+
+    /// Column `index` of the current row as a number (0 for NULL); an error
+    /// when there is no such column or no current row
+    pub fn int(&self, index: usize) -> io::Result<i64> {
+        let index = self.column(index)?;
+        // SAFETY: `column` found a row with this column
+        Ok(unsafe { ffi::sqlite3_column_int64(self.raw, index) })
+    }
+
+    /// `index` as SQLite's, once it is known to name a column of a current
+    /// row: reading one otherwise is undefined behaviour
+    fn column(&self, index: usize) -> io::Result<c_int> {
+        if !self.row {
+            return Err(io::Error::other("SQLite: no row to read"));
+        }
+        // SAFETY: `raw` is a live statement
+        let count = unsafe { ffi::sqlite3_column_count(self.raw) };
+        match c_int::try_from(index) {
+            Ok(index) if index < count => Ok(index),
+            _ => Err(io::Error::other(format!("SQLite: no column {}", index))),
+        }
+    }
+}
+
+impl Drop for Statement<'_> {
+    fn drop(&mut self) {
+        // SAFETY: `raw` came from `sqlite3_prepare_v2` and is finalized once
+        unsafe { ffi::sqlite3_finalize(self.raw) };
+    }
+}
+
+/// `len` as the `int` SQLite's API takes
+fn length(len: usize) -> io::Result<c_int> {
+    c_int::try_from(len).map_err(|_| io::Error::other("too long for SQLite"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_only_with_a_row() {
+        let db = Connection::open(Path::new(":memory:")).unwrap();
+        db.execute("CREATE TABLE t (name TEXT, n INTEGER); INSERT INTO t VALUES ('a', 7);")
+            .unwrap();
+        let mut rows = db.prepare("SELECT name, n FROM t").unwrap();
+        assert!(rows.text(0).is_err(), "before the first step");
+        assert!(rows.step().unwrap());
+        assert_eq!(rows.text(0).unwrap(), "a");
+        assert_eq!(rows.int(1).unwrap(), 7);
+        assert!(rows.int(2).is_err());
+        assert!(!rows.step().unwrap());
+        assert!(rows.text(0).is_err(), "after the last row");
+        rows.reset().unwrap();
+        assert!(rows.int(1).is_err(), "after a reset");
+    }
+}
@@ -0,0 +1,522 @@
+//! Where Progress Is Saved
+//!
+//! `Progress` reads and writes through a `Store`. The `[store]` table of the
+//! config file picks one; without it progress goes to progress.toml in the
+//! data directory:
+//!
+//! ```toml
+//! [store]
+//! backend = "json"                        # or "toml", the default, or "sqlite"
+//! path = "/mnt/class/alice/progress.json" # instead of the data directory
+//! ```
+//!
+//! Both file stores write a temporary file next to the real one and rename it
+//! over, so a crash or a dropped network drive leaves the old file whole
+//! rather than half a new one. Built with the `sqlite` feature, `Sqlite`
+//! keeps the same data in tables of a SQLite database and saves in one
+//! transaction instead.
+//!
+//! The learner's other records, the activity log `stats` reads,
+//! calibration.toml and recent.txt, go in the same directory as the store
+//! (`dir`), so moving it to a shared drive moves them along.
+
+use crate::dirs;
+use crate::json::Value;
+use crate::progress::{format_timestamp, parse_timestamp};
+use crate::settings::{self, Settings};
+#[cfg(feature = "sqlite")]
+use crate::sqlite;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use tracing::debug;
+
+/// Everything a store keeps
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Snapshot {
+    /// `lesson` or `lesson::section`, and when it was completed
+    pub completed: BTreeMap<String, SystemTime>,
+    /// The run in progress: `all`, a lesson or `lesson::section`, and when it
+    /// started
+    pub run: Option<(String, SystemTime)>,
+    /// The content version of each lesson when it was completed
+    pub versions: BTreeMap<String, u32>,
+    /// Lessons the learner chose to go through again
+    pub review: BTreeSet<String>,
+}
+
+pub trait Store {
+    /// Where the data is, for messages: a path, say
+    fn location(&self) -> String;
+
+    /// What was saved, or `None` when nothing has been saved yet
+    fn load(&self) -> io::Result<Option<Snapshot>>;
+
+    fn save(&self, snapshot: &Snapshot) -> io::Result<()>;
+}
+
+/// The backends `[store] backend` accepts
+#[cfg(not(feature = "sqlite"))]
+pub const BACKENDS: &[&str] = &["toml", "json"];
+/// The backends `[store] backend` accepts
+#[cfg(feature = "sqlite")]
+pub const BACKENDS: &[&str] = &["toml", "json", "sqlite"];
+
+/// The store `settings` asks for; `Ok(None)` when it names no path and there is
+/// no data directory either
+pub fn from_settings(settings: &Settings) -> Result<Option<Box<dyn Store>>, String> {
+    let table = settings.table("store");
+    let backend = table
+        .and_then(|table| table.str("backend"))
+        .unwrap_or("toml");
+    let path = table.and_then(|table| table.str("path")).map(PathBuf::from);
+    let path = |file_name: &str| {
+        path.clone()
+            .or_else(|| Some(dirs::data_dir()?.join(file_name)))
+    };
+
+    let store: Option<Box<dyn Store>> = match backend {
+        "toml" => path(TomlFile::FILE_NAME).map(|path| Box::new(TomlFile::new(path)) as _),
+        "json" => path(JsonFile::FILE_NAME).map(|path| Box::new(JsonFile::new(path)) as _),
+        #[cfg(feature = "sqlite")]
+        "sqlite" => path(Sqlite::FILE_NAME).map(|path| Box::new(Sqlite::new(path)) as _),
+        #[cfg(not(feature = "sqlite"))]
+        "sqlite" => {
+            return Err(
+                "the sqlite backend needs a rust-learn built with `--features sqlite`".into(),
+            );
+        }
+        _ => {
+            return Err(format!(
+                "unknown [store] backend '{}' (try {})",
+                backend,
+                BACKENDS.join(", ")
+            ));
+        }
+    };
+    Ok(store)
+}
+
+/// Where the learner's records besides progress are kept: the directory of
+/// `[store] path`, or else the data directory
+pub fn dir() -> Option<PathBuf> {
+    dir_in(settings::get())
+}
+
+fn dir_in(settings: &Settings) -> Option<PathBuf> {
+    match settings.table("store").and_then(|table| table.str("path")) {
+        Some(path) => Path::new(path).parent().map(Path::to_path_buf),
+        None => dirs::data_dir(),
+    }
+}
+
+/// progress.toml:
+///
+/// ```toml
+/// [completed]
+/// variables = 2026-10-16T14:03:27Z
+/// "ownership::slices" = 2026-10-16T14:05:12Z
+///
+/// [run]
+/// target = "all"
+/// started = 2026-10-16T14:06:00Z
+///
+/// [versions]
+/// ownership = 3
+///
+/// [review]
+/// lessons = ["ownership"]
+/// ```
+pub struct TomlFile {
+    path: PathBuf,
+}
+
+impl TomlFile {
+    pub const FILE_NAME: &str = "progress.toml";
+
+    pub fn new(path: PathBuf) -> TomlFile {
+        TomlFile { path }
+    }
+}
+
+impl Store for TomlFile {
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    /// Lines that do not parse are skipped
+    fn load(&self) -> io::Result<Option<Snapshot>> {
+        let Some(text) = read_if_exists(&self.path)? else {
+            return Ok(None);
+        };
+        let mut snapshot = Snapshot::default();
+        let (mut target, mut started) = (None, None);
+        let mut table = "";
+        for line in text.lines().map(str::trim) {
+            if line.starts_with('[') {
+                table = line;
+                continue;
+            }
+            if line.starts_with('#') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim().trim_matches('"'), value.trim());
+            match (table, key) {
+                ("[completed]", _) => {
+                    if let Some(time) = parse_timestamp(value) {
+                        snapshot.completed.insert(key.to_string(), time);
+                    }
+                }
+                ("[run]", "target") => target = Some(value.trim_matches('"').to_string()),
+                ("[run]", "started") => started = parse_timestamp(value),
+                ("[versions]", _) => {
+                    if let Ok(version) = value.parse() {
+                        snapshot.versions.insert(key.to_string(), version);
+                    }
+                }
+                ("[review]", "lessons") => {
+                    let names = value.trim_start_matches('[').trim_end_matches(']');
+                    snapshot.review = names
+                        .split(',')
+                        .map(|name| name.trim().trim_matches('"'))
+                        .filter(|name| !name.is_empty())
+                        .map(String::from)
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+        snapshot.run = target.zip(started);
+        Ok(Some(snapshot))
+    }
+
+    fn save(&self, snapshot: &Snapshot) -> io::Result<()> {
+        let mut text = String::from("# When each lesson and section was completed\n[completed]\n");
+        for (key, time) in &snapshot.completed {
+            if key.contains("::") {
+                text.push_str(&format!("\"{}\" = {}\n", key, format_timestamp(*time)));
+            } else {
+                text.push_str(&format!("{} = {}\n", key, format_timestamp(*time)));
+            }
+        }
+        if let Some((target, started)) = &snapshot.run {
+            text.push_str(&format!(
+                "\n[run]\ntarget = \"{}\"\nstarted = {}\n",
+                target,
+                format_timestamp(*started)
+            ));
+        }
+        if !snapshot.versions.is_empty() {
+            text.push_str("\n[versions]\n");
+            for (lesson, version) in &snapshot.versions {
+                text.push_str(&format!("{} = {}\n", lesson, version));
+            }
+        }
+        if !snapshot.review.is_empty() {
+            let names: Vec<String> = snapshot
+                .review
+                .iter()
+                .map(|name| format!("\"{}\"", name))
+                .collect();
+            text.push_str(&format!("\n[review]\nlessons = [{}]\n", names.join(", ")));
+        }
+        write_atomically(&self.path, &text)
+    }
+}
+
+/// progress.json, the same data as `TomlFile` for tools that read JSON:
+///
+/// ```json
+/// {"completed":{"variables":"2026-10-16T14:03:27Z"},
+///  "run":{"target":"all","started":"2026-10-16T14:06:00Z"},
+///  "versions":{"variables":1},"review":["variables"]}
+/// ```
+pub struct JsonFile {
+    path: PathBuf,
+}
+
+impl JsonFile {
+    pub const FILE_NAME: &str = "progress.json";
+
+    pub fn new(path: PathBuf) -> JsonFile {
+        JsonFile { path }
+    }
+}
+
+impl Store for JsonFile {
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    /// Entries that do not parse are skipped; a file that is not JSON at all
+    /// is an error, so it is not overwritten with nothing
+    fn load(&self) -> io::Result<Option<Snapshot>> {
+        let Some(text) = read_if_exists(&self.path)? else {
+            return Ok(None);
+        };
+        let value = Value::parse(&text).map_err(|error| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {}", self.path.display(), error),
+            )
+        })?;
+
+        let mut snapshot = Snapshot::default();
+        let completed = value.get("completed").and_then(Value::as_object);
+        for (key, time) in completed.unwrap_or_default() {
+            if let Some(time) = time.as_str().and_then(parse_timestamp) {
+                snapshot.completed.insert(key.clone(), time);
+            }
+        }
+        snapshot.run = value.get("run").and_then(|run| {
+            let target = run.get("target")?.as_str()?;
+            let started = parse_timestamp(run.get("started")?.as_str()?)?;
+            Some((target.to_string(), started))
+        });
+        let versions = value.get("versions").and_then(Value::as_object);
+        for (lesson, version) in versions.unwrap_or_default() {
+            if let Some(version) = version.as_f64() {
+                snapshot.versions.insert(lesson.clone(), version as u32);
+            }
+        }
+        let review = value.get("review").and_then(Value::as_array);
+        snapshot.review = review
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| Some(name.as_str()?.to_string()))
+            .collect();
+        Ok(Some(snapshot))
+    }
+
+    fn save(&self, snapshot: &Snapshot) -> io::Result<()> {
+        let completed = snapshot
+            .completed
+            .iter()
+            .map(|(key, time)| (key.clone(), format_timestamp(*time).as_str().into()))
+            .collect();
+        let run = match &snapshot.run {
+            Some((target, started)) => Value::Object(vec![
+                ("target".to_string(), target.as_str().into()),
+                (
+                    "started".to_string(),
+                    format_timestamp(*started).as_str().into(),
+                ),
+            ]),
+            None => Value::Null,
+        };
+        let versions = snapshot
+            .versions
+            .iter()
+            .map(|(lesson, version)| (lesson.clone(), Value::Number(f64::from(*version))))
+            .collect();
+        let review = snapshot
+            .review
+            .iter()
+            .map(|name| name.as_str().into())
+            .collect();
+        let value = Value::Object(vec![
+            ("completed".to_string(), Value::Object(completed)),
+            ("run".to_string(), run),
+            ("versions".to_string(), Value::Object(versions)),
+            ("review".to_string(), Value::Array(review)),
+        ]);
+        write_atomically(&self.path, &format!("{}\n", value))
+    }
+}
+
+/// progress.db, the same data in a SQLite database, one table each (times
+/// as text, like the file stores). A save replaces every row in one
+/// transaction, so a crash leaves the last save whole
+#[cfg(feature = "sqlite")]
+pub struct Sqlite {
+    path: PathBuf,
+}
+
+#[cfg(feature = "sqlite")]
+impl Sqlite {
+    pub const FILE_NAME: &str = "progress.db";
+
+    const SCHEMA: &str = "
+        CREATE TABLE IF NOT EXISTS completed (key TEXT PRIMARY KEY, time TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS run (target TEXT NOT NULL, started TEXT NOT NULL);
+        CREATE TABLE IF NOT EXISTS versions (lesson TEXT PRIMARY KEY, version INTEGER NOT NULL);
+        CREATE TABLE IF NOT EXISTS review (lesson TEXT PRIMARY KEY);
+    ";
+
+    pub fn new(path: PathBuf) -> Sqlite {
+        Sqlite { path }
+    }
+
+    fn open(&self) -> io::Result<sqlite::Connection> {
+        let db = sqlite::Connection::open(&self.path)?;
+        db.execute(Self::SCHEMA)?;
+        Ok(db)
+    }
+
+    fn insert_all(db: &sqlite::Connection, snapshot: &Snapshot) -> io::Result<()> {
+        db.execute(
+            "DELETE FROM completed; DELETE FROM run; DELETE FROM versions; DELETE FROM review",
+        )?;
+        let mut insert = db.prepare("INSERT INTO completed (key, time) VALUES (?1, ?2)")?;
+        for (key, time) in &snapshot.completed {
+            insert.bind_text(1, key)?;
+            insert.bind_text(2, &format_timestamp(*time))?;
+            insert.step()?;
+            insert.reset()?;
+        }
+        if let Some((target, started)) = &snapshot.run {
+            let mut insert = db.prepare("INSERT INTO run (target, started) VALUES (?1, ?2)")?;
+            insert.bind_text(1, target)?;
+            insert.bind_text(2, &format_timestamp(*started))?;
+            insert.step()?;
+        }
+        let mut insert = db.prepare("INSERT INTO versions (lesson, version) VALUES (?1, ?2)")?;
+        for (lesson, version) in &snapshot.versions {
+            insert.bind_text(1, lesson)?;
+            insert.bind_int(2, i64::from(*version))?;
+            insert.step()?;
+            insert.reset()?;
+        }
+        let mut insert = db.prepare("INSERT INTO review (lesson) VALUES (?1)")?;
+        for lesson in &snapshot.review {
+            insert.bind_text(1, lesson)?;
+            insert.step()?;
+            insert.reset()?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Store for Sqlite {
+    fn location(&self) -> String {
+        self.path.display().to_string()
+    }
+
+    /// Rows whose time does not parse are skipped
+    fn load(&self) -> io::Result<Option<Snapshot>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+        let db = self.open()?;
+        let mut snapshot = Snapshot::default();
+        let mut rows = db.prepare("SELECT key, time FROM completed")?;
+        while rows.step()? {
+            if let Some(time) = parse_timestamp(&rows.text(1)?) {
+                snapshot.completed.insert(rows.text(0)?, time);
+            }
+        }
+        let mut rows = db.prepare("SELECT target, started FROM run LIMIT 1")?;
+        if rows.step()? {
+            let target = rows.text(0)?;
+            snapshot.run = parse_timestamp(&rows.text(1)?).map(|started| (target, started));
+        }
+        let mut rows = db.prepare("SELECT lesson, version FROM versions")?;
+        while rows.step()? {
+            if let Ok(version) = u32::try_from(rows.int(1)?) {
+                snapshot.versions.insert(rows.text(0)?, version);
+            }
+        }
+        let mut rows = db.prepare("SELECT lesson FROM review")?;
+        while rows.step()? {
+            snapshot.review.insert(rows.text(0)?);
+        }
+        Ok(Some(snapshot))
+    }
+
+    fn save(&self, snapshot: &Snapshot) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let db = self.open()?;
+        db.execute("BEGIN")?;
+        match Self::insert_all(&db, snapshot) {
+            Ok(()) => {
+                debug!(path = %self.path.display(), "saving");
+                db.execute("COMMIT")
+            }
+            Err(error) => {
+                let _ = db.execute("ROLLBACK");
+                Err(error)
+            }
+        }
+    }
+}
+
+/// The file's text, or `None` when there is no file
+fn read_if_exists(path: &Path) -> io::Result<Option<String>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error),
+    }
+}
+
+/// Write `text` to a temporary file beside `path`, then rename it over
+/// `path`, creating the directory if needed
+fn write_atomically(path: &Path, text: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, text)?;
+    debug!(path = %path.display(), bytes = text.len(), "saving");
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_file_stores_round_trip() {
+        let dir = std::env::temp_dir().join(format!("rust-learn-store-{}", std::process::id()));
+        let time = UNIX_EPOCH + Duration::from_secs(1_792_159_407);
+        let snapshot = Snapshot {
+            completed: BTreeMap::from([
+                ("loops".to_string(), time),
+                ("ownership::slices".to_string(), time),
+            ]),
+            run: Some(("all".to_string(), time)),
+            versions: BTreeMap::from([("loops".to_string(), 2)]),
+            review: BTreeSet::from(["loops".to_string(), "ownership".to_string()]),
+        };
+
+        let stores: Vec<Box<dyn Store>> = vec![
+            Box::new(TomlFile::new(dir.join(TomlFile::FILE_NAME))),
+            Box::new(JsonFile::new(dir.join(JsonFile::FILE_NAME))),
+            #[cfg(feature = "sqlite")]
+            Box::new(Sqlite::new(dir.join(Sqlite::FILE_NAME))),
+        ];
+        for store in stores {
+            assert_eq!(store.load().unwrap(), None);
+            store.save(&snapshot).unwrap();
+            assert_eq!(store.load().unwrap(), Some(snapshot.clone()));
+        }
+        assert_eq!(
+            fs::read_to_string(dir.join(JsonFile::FILE_NAME)).unwrap(),
+            "{\"completed\":{\"loops\":\"2026-10-16T14:03:27Z\",\
+             \"ownership::slices\":\"2026-10-16T14:03:27Z\"},\
+             \"run\":{\"target\":\"all\",\"started\":\"2026-10-16T14:03:27Z\"},\
+             \"versions\":{\"loops\":2},\"review\":[\"loops\",\"ownership\"]}\n"
+        );
+        fs::remove_dir_all(dir).unwrap();
+
+        let settings = Settings::parse("[store]\nbackend = \"postgres\"").unwrap();
+        assert!(from_settings(&settings).is_err());
+        let settings = Settings::parse("[store]\nbackend = \"sqlite\"").unwrap();
+        assert_eq!(from_settings(&settings).is_ok(), cfg!(feature = "sqlite"));
+
+        let settings =
+            Settings::parse("[store]\npath = \"/mnt/class/alice/progress.json\"").unwrap();
+        assert_eq!(dir_in(&settings), Some(PathBuf::from("/mnt/class/alice")));
+        assert_eq!(dir_in(&Settings::default()), dirs::data_dir());
+    }
+}
@@ -0,0 +1,167 @@
+//! Themes
+//!
+//! The named sets of colors everything rust-learn prints is styled with:
+//! lesson text (`output::lesson_line`), the status words of quiz feedback,
+//! diffs, tables and self-test (`output::green`, `red`, `dim`, `bold`), the
+//! TUI, and highlighted code (`highlight`). `--theme` picks one, or else
+//! `theme` under `[output]` in config.toml:
+//!
+//! ```toml
+//! [output]
+//! theme = "high-contrast"
+//! ```
+
+use crate::output::LineKind;
+use crate::settings;
+use std::str::FromStr;
+
+/// A set of colors for everything rust-learn prints
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Theme {
+    /// Bright colors for dark terminal backgrounds
+    #[default]
+    Dark,
+    /// Darker colors that stay readable on a white background
+    Light,
+    /// Bold, saturated colors and nothing dimmed, readable on any background
+    HighContrast,
+    /// Bold and underline only, for terminals without (or users without) color
+    Mono,
+}
+
+impl Theme {
+    pub const NAMES: [&'static str; 4] = ["dark", "light", "high-contrast", "mono"];
+
+    /// The theme in config.toml, if it names one; a name that does not is
+    /// reported on stderr
+    pub fn configured() -> Option<Theme> {
+        let name = settings::get().table("output")?.str("theme")?;
+        name.parse()
+            .inspect_err(|error| eprintln!("Ignoring [output] theme in config.toml: {}", error))
+            .ok()
+    }
+
+    /// The ANSI codes this theme uses for each part of the output
+    pub fn palette(self) -> Palette {
+        match self {
+            Theme::Dark => Palette {
+                heading: "1;36",
+                subheading: "1;33",
+                rule: "2",
+                code: "32",
+                comment: "2;3",
+                prose: "2",
+                success: "32",
+                error: "31",
+                muted: "2",
+                emphasis: "1",
+            },
+            Theme::Light => Palette {
+                heading: "1;34",
+                subheading: "1;35",
+                rule: "2",
+                code: "31",
+                comment: "3",
+                prose: "2",
+                success: "32",
+                error: "31",
+                muted: "2",
+                emphasis: "1",
+            },
+            Theme::HighContrast => Palette {
+                heading: "1;4",
+                subheading: "1",
+                rule: "1",
+                code: "1;34",
+                comment: "3",
+                prose: "",
+                success: "1;32",
+                error: "1;31",
+                muted: "",
+                emphasis: "1",
+            },
+            Theme::Mono => Palette {
+                heading: "1;4",
+                subheading: "1",
+                rule: "2",
+                code: "4",
+                comment: "2",
+                prose: "",
+                success: "1",
+                error: "1;4",
+                muted: "2",
+                emphasis: "1",
+            },
+        }
+    }
+}
+
+impl FromStr for Theme {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        match name {
+            "dark" => Ok(Theme::Dark),
+            "light" => Ok(Theme::Light),
+            "high-contrast" => Ok(Theme::HighContrast),
+            "mono" | "monochrome" => Ok(Theme::Mono),
+            _ => Err(format!(
+                "unknown theme '{}' (try {})",
+                name,
+                Theme::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+/// ANSI codes for each part of the output, e.g. `"1;36"` for bold cyan
+pub struct Palette {
+    /// `=== Banner ===` and `3. Numbered Section:`
+    pub heading: &'static str,
+    /// `KEY POINTS:`
+    pub subheading: &'static str,
+    /// The `=====` under a heading
+    pub rule: &'static str,
+    /// `backticked` names inside a line
+    pub code: &'static str,
+    /// Lines starting with `//`
+    pub comment: &'static str,
+    /// `- ` bullet points explaining the output
+    pub prose: &'static str,
+    /// A right answer, a check that passed, a printed line in a diff
+    pub success: &'static str,
+    /// A wrong answer, a failure, a line that should not be there
+    pub error: &'static str,
+    /// Notes and hints around the output
+    pub muted: &'static str,
+    /// Labels and totals
+    pub emphasis: &'static str,
+}
+
+impl Palette {
+    /// The codes for a whole line of this kind; `code` applies within lines
+    pub fn line(&self, kind: LineKind) -> &'static str {
+        match kind {
+            LineKind::Rule => self.rule,
+            LineKind::Heading => self.heading,
+            LineKind::Subheading => self.subheading,
+            LineKind::Comment => self.comment,
+            LineKind::Bullet => self.prose,
+            LineKind::Text => "",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names_parse() {
+        for name in Theme::NAMES {
+            assert!(name.parse::<Theme>().is_ok(), "{}", name);
+        }
+        assert_eq!("monochrome".parse(), Ok(Theme::Mono));
+        assert!("solarized".parse::<Theme>().is_err());
+    }
+}
@@ -0,0 +1,355 @@
+/// Toolchain - Running cargo, the C Compiler and Other Tools From Lessons
+///
+/// Lessons that need the real compiler (like the semver simulation) go through
+/// this module instead of building `Command`s themselves, so finding cargo, cc
+/// or a WebAssembly runtime, capturing their output, and reporting "not
+/// installed" happen in one place.
+///
+/// The commands themselves are started by a `CommandRunner`: `System` runs
+/// the real programs, a test can swap in a `Fake` that only records what it
+/// was asked to run (`with_runner`), and another backend, one that builds
+/// somewhere else say, can take over for the whole process (`set_runner`).
+use crate::watchdog;
+use std::cell::RefCell;
+use std::ffi::{OsStr, OsString};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, debug_span, trace};
+
+/// Whether a finished command succeeded, and what it printed
+#[derive(Debug, Clone, Default)]
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+    /// Stopped for running past its `Invocation::timeout` (and so not a success)
+    pub timed_out: bool,
+}
+
+/// The cargo binary to run: the one that launched us (`cargo run` sets
+/// `$CARGO`), otherwise whatever `cargo` is on the PATH
+fn cargo_program() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
+
+/// `cargo --version`, or `None` when cargo cannot be run at all
+pub fn cargo_version() -> Option<String> {
+    version(cargo_program())
+}
+
+/// Run `cargo <args>` inside `dir` and capture its output
+pub fn cargo(dir: &Path, args: &[&str]) -> io::Result<CommandOutput> {
+    run(cargo_program(), dir, args)
+}
+
+/// The Rust compiler to run: `$RUSTC` when set, otherwise `rustc`
+fn rustc_program() -> String {
+    std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string())
+}
+
+/// `rustc --version`, or `None` when there is no compiler
+pub fn rustc_version() -> Option<String> {
+    version(rustc_program())
+}
+
+/// Run `rustc <args>` inside `dir` and capture its output
+pub fn rustc(dir: &Path, args: &[&str]) -> io::Result<CommandOutput> {
+    run(rustc_program(), dir, args)
+}
+
+/// The C compiler to run: `$CC` when set, otherwise `cc`
+fn cc_program() -> String {
+    std::env::var("CC").unwrap_or_else(|_| "cc".to_string())
+}
+
+/// `cc --version` (first line), or `None` when there is no C compiler
+pub fn cc_version() -> Option<String> {
+    version(cc_program())
+}
+
+/// Run the C compiler with `args` inside `dir` and capture its output
+pub fn cc(dir: &Path, args: &[&str]) -> io::Result<CommandOutput> {
+    run(cc_program(), dir, args)
+}
+
+/// Run `git <args>` inside `dir` and capture its output
+pub fn git(dir: &Path, args: &[&str]) -> io::Result<CommandOutput> {
+    run("git", dir, args)
+}
+
+/// The first line of `<program> --version`, or `None` when it is not installed
+pub fn version(program: impl AsRef<OsStr>) -> Option<String> {
+    let program = program.as_ref();
+    let version = run(program, Path::new("."), &["--version"])
+        .ok()
+        .filter(|output| output.success)
+        .and_then(|output| {
+            output
+                .stdout
+                .lines()
+                .next()
+                .map(|line| line.trim().to_string())
+        });
+    trace!(program = %program.to_string_lossy(), ?version, "version");
+    version
+}
+
+/// Compilation targets rustup has installed (`rustup target list --installed`),
+/// or `None` without rustup
+pub fn installed_targets() -> Option<Vec<String>> {
+    output_lines("rustup", &["target", "list", "--installed"])
+}
+
+/// Every target rustc knows (`rustc --print target-list`), installed or not
+pub fn known_targets() -> Option<Vec<String>> {
+    output_lines("rustc", &["--print", "target-list"])
+}
+
+/// The trimmed lines a successful command printed
+fn output_lines(program: &str, args: &[&str]) -> Option<Vec<String>> {
+    let output = run(program, Path::new("."), args).ok()?;
+    if !output.success {
+        return None;
+    }
+    Some(
+        output
+            .stdout
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect(),
+    )
+}
+
+/// Run any program inside `dir` and capture its output
+pub fn run(program: impl AsRef<OsStr>, dir: &Path, args: &[&str]) -> io::Result<CommandOutput> {
+    run_with_env(program, dir, args, &[])
+}
+
+/// `run`, with extra environment variables set for the program
+pub fn run_with_env(
+    program: impl AsRef<OsStr>,
+    dir: &Path,
+    args: &[&str],
+    env: &[(&str, &OsStr)],
+) -> io::Result<CommandOutput> {
+    start(program.as_ref(), dir, args, env, None)
+}
+
+/// `run_with_env`, but the program is killed once it has run for `limit`;
+/// the output then has `timed_out` set
+pub fn run_limited(
+    program: impl AsRef<OsStr>,
+    dir: &Path,
+    args: &[&str],
+    env: &[(&str, &OsStr)],
+    limit: Duration,
+) -> io::Result<CommandOutput> {
+    start(program.as_ref(), dir, args, env, Some(limit))
+}
+
+fn start(
+    program: &OsStr,
+    dir: &Path,
+    args: &[&str],
+    env: &[(&str, &OsStr)],
+    timeout: Option<Duration>,
+) -> io::Result<CommandOutput> {
+    let invocation = Invocation {
+        program: program.to_os_string(),
+        args: args.iter().map(|arg| arg.to_string()).collect(),
+        dir: dir.to_path_buf(),
+        env: env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_os_string()))
+            .collect(),
+        timeout,
+    };
+    let _span = debug_span!("command", program = %invocation.program.to_string_lossy()).entered();
+    debug!(?args, dir = %dir.display(), ?env, "starting");
+    let start = Instant::now();
+    // A slow build is not the lesson hanging
+    let paused = watchdog::pause();
+    let output = match OVERRIDE.with(|runner| runner.borrow().clone()) {
+        Some(runner) => runner.run(&invocation),
+        None => RUNNER.get_or_init(|| Box::new(System)).run(&invocation),
+    }
+    .inspect_err(|error| debug!(%error, "could not start"))?;
+    drop(paused);
+    debug!(
+        success = output.success,
+        timed_out = output.timed_out,
+        elapsed = ?start.elapsed(),
+        stdout_bytes = output.stdout.len(),
+        stderr_bytes = output.stderr.len(),
+        "finished"
+    );
+    Ok(output)
+}
+
+/// A command to run: the program, its arguments, the directory to run it
+/// in, environment variables to set on top of ours, and how long it may run
+#[derive(Debug, Clone, PartialEq)]
+pub struct Invocation {
+    pub program: OsString,
+    pub args: Vec<String>,
+    pub dir: PathBuf,
+    pub env: Vec<(String, OsString)>,
+    pub timeout: Option<Duration>,
+}
+
+/// What starts the commands this module runs
+pub trait CommandRunner: Send + Sync {
+    /// Run `invocation` to the end; an error means it could not be started
+    fn run(&self, invocation: &Invocation) -> io::Result<CommandOutput>;
+}
+
+/// Starts the real programs
+pub struct System;
+
+impl CommandRunner for System {
+    fn run(&self, invocation: &Invocation) -> io::Result<CommandOutput> {
+        let mut command = Command::new(&invocation.program);
+        command
+            .args(&invocation.args)
+            .envs(invocation.env.iter().map(|(name, value)| (name, value)))
+            .current_dir(&invocation.dir);
+        let Some(limit) = invocation.timeout else {
+            let output = command.output()?;
+            return Ok(CommandOutput {
+                success: output.status.success(),
+                stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+                stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+                timed_out: false,
+            });
+        };
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // Read both pipes while waiting, so a chatty program cannot fill one
+        // and block before the deadline
+        let drain = |pipe: Option<Box<dyn Read + Send>>| {
+            thread::spawn(move || {
+                let mut text = Vec::new();
+                if let Some(mut pipe) = pipe {
+                    let _ = pipe.read_to_end(&mut text);
+                }
+                String::from_utf8_lossy(&text).into_owned()
+            })
+        };
+        let stdout = drain(child.stdout.take().map(|pipe| Box::new(pipe) as _));
+        let stderr = drain(child.stderr.take().map(|pipe| Box::new(pipe) as _));
+        let deadline = Instant::now() + limit;
+        let (status, timed_out) = loop {
+            if let Some(status) = child.try_wait()? {
+                break (Some(status), false);
+            }
+            if Instant::now() >= deadline {
+                let _ = child.kill();
+                let _ = child.wait();
+                break (None, true);
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        Ok(CommandOutput {
+            success: status.is_some_and(|status| status.success()),
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+            timed_out,
+        })
+    }
+}
+
+/// Starts nothing: records each invocation and answers with what `respond`
+/// makes of it
+pub struct Fake {
+    respond: Box<dyn Fn(&Invocation) -> CommandOutput + Send + Sync>,
+    invocations: Mutex<Vec<Invocation>>,
+}
+
+impl Fake {
+    pub fn new(respond: impl Fn(&Invocation) -> CommandOutput + Send + Sync + 'static) -> Fake {
+        Fake {
+            respond: Box::new(respond),
+            invocations: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// A fake every command succeeds with, printing nothing
+    pub fn succeeding() -> Fake {
+        Fake::new(|_| CommandOutput {
+            success: true,
+            ..CommandOutput::default()
+        })
+    }
+
+    /// What it has been asked to run, in order
+    pub fn invocations(&self) -> Vec<Invocation> {
+        self.invocations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+}
+
+impl CommandRunner for Fake {
+    fn run(&self, invocation: &Invocation) -> io::Result<CommandOutput> {
+        self.invocations
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(invocation.clone());
+        Ok((self.respond)(invocation))
+    }
+}
+
+/// The runner for the whole process, `System` unless `set_runner` came first
+static RUNNER: OnceLock<Box<dyn CommandRunner>> = OnceLock::new();
+
+thread_local! {
+    /// `with_runner`'s, which wins on its thread
+    static OVERRIDE: RefCell<Option<Arc<dyn CommandRunner>>> = const { RefCell::new(None) };
+}
+
+/// Run every command with `runner` from now on. It has to come before the
+/// first command; false when one has run already
+pub fn set_runner(runner: Box<dyn CommandRunner>) -> bool {
+    RUNNER.set(runner).is_ok()
+}
+
+/// Call `f` with commands on this thread going to `runner`, like a `Fake`
+/// in a test
+pub fn with_runner<T>(runner: Arc<dyn CommandRunner>, f: impl FnOnce() -> T) -> T {
+    let previous = OVERRIDE.with(|current| current.replace(Some(runner)));
+    let result = f();
+    OVERRIDE.with(|current| *current.borrow_mut() = previous);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fake_runner() {
+        let fake = Arc::new(Fake::new(|invocation| CommandOutput {
+            success: invocation.args != ["--version"],
+            ..CommandOutput::default()
+        }));
+        let (version, build) = with_runner(fake.clone(), || {
+            (cc_version(), git(Path::new("/work"), &["status"]).unwrap())
+        });
+        assert_eq!(version, None);
+        assert!(build.success);
+        let invocations = fake.invocations();
+        assert_eq!(invocations.len(), 2);
+        assert_eq!(invocations[1].args, ["status"]);
+        assert_eq!(invocations[1].dir, Path::new("/work"));
+        assert_eq!(invocations[1].program, "git");
+    }
+}
@@ -0,0 +1,180 @@
+//! Planned Changes
+//!
+//! Commands that remove or overwrite the learner's files (`cache gc`, `pack
+//! remove`, `exercise reset`, `dev vscode`) first collect what they would do
+//! in a `Transaction`, and only then make the changes. With `--dry-run` they print
+//! the plan (`describe`) instead, which is then exactly what a real run
+//! does, because it is the same list.
+//!
+//! Files are written like the progress store writes (`store`): to a
+//! temporary file next to the real one, renamed over it.
+
+use crate::memory::format_bytes;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// One change to the file system
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// Write `contents` to `path`, creating its directory and replacing what
+    /// is there
+    Write { path: PathBuf, contents: Vec<u8> },
+    /// Remove a file, or a directory with everything in it, of `bytes` in all
+    Remove { path: PathBuf, bytes: u64 },
+}
+
+/// Changes to make together, in order
+#[derive(Debug, Default)]
+pub struct Transaction {
+    changes: Vec<Change>,
+}
+
+impl Transaction {
+    pub fn new() -> Transaction {
+        Transaction::default()
+    }
+
+    /// Plan to write `contents` to `path`
+    pub fn write(&mut self, path: impl Into<PathBuf>, contents: impl Into<Vec<u8>>) {
+        self.changes.push(Change::Write {
+            path: path.into(),
+            contents: contents.into(),
+        });
+    }
+
+    /// Plan to remove `path`, measuring it now
+    pub fn remove(&mut self, path: impl Into<PathBuf>) -> io::Result<()> {
+        let path = path.into();
+        let bytes = size(&path)?;
+        self.remove_sized(path, bytes);
+        Ok(())
+    }
+
+    /// Plan to remove `path`, already measured at `bytes`
+    pub fn remove_sized(&mut self, path: impl Into<PathBuf>, bytes: u64) {
+        self.changes.push(Change::Remove {
+            path: path.into(),
+            bytes,
+        });
+    }
+
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// What the removals free
+    pub fn freed(&self) -> u64 {
+        self.changes
+            .iter()
+            .map(|change| match change {
+                Change::Remove { bytes, .. } => *bytes,
+                Change::Write { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// One line per change, as it stands now: `remove`, `create` or
+    /// `replace`, the size, and the path
+    pub fn describe(&self) -> Vec<String> {
+        self.changes
+            .iter()
+            .map(|change| match change {
+                Change::Remove { path, bytes } => {
+                    format!("remove  {:>10}  {}", format_bytes(*bytes), path.display())
+                }
+                Change::Write { path, contents } => {
+                    let verb = if path.exists() { "replace" } else { "create " };
+                    let size = format_bytes(contents.len() as u64);
+                    format!("{} {:>10}  {}", verb, size, path.display())
+                }
+            })
+            .collect()
+    }
+
+    /// Make the changes in order, stopping at the first that fails. A path
+    /// that is already gone counts as removed
+    pub fn apply(self) -> io::Result<()> {
+        for change in self.changes {
+            match change {
+                Change::Write { path, contents } => write(&path, &contents)?,
+                Change::Remove { path, .. } => match remove(&path) {
+                    Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+                    other => other?,
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The size of `path` and everything in it
+pub fn size(path: &Path) -> io::Result<u64> {
+    let meta = fs::symlink_metadata(path)?;
+    if !meta.is_dir() {
+        return Ok(meta.len());
+    }
+    let mut bytes = 0;
+    for entry in fs::read_dir(path)? {
+        bytes += size(&entry?.path())?;
+    }
+    Ok(bytes)
+}
+
+fn write(path: &Path, contents: &[u8]) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+fn remove(path: &Path) -> io::Result<()> {
+    if fs::symlink_metadata(path)?.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_then_apply() {
+        let dir =
+            std::env::temp_dir().join(format!("rust-learn-transaction-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("old/inner")).unwrap();
+        fs::write(dir.join("old/inner/a.txt"), "12345").unwrap();
+        fs::write(dir.join("index.txt"), "before").unwrap();
+
+        let mut transaction = Transaction::new();
+        transaction.remove(dir.join("old")).unwrap();
+        transaction.remove_sized(dir.join("gone.txt"), 0);
+        transaction.write(dir.join("index.txt"), "after");
+        transaction.write(dir.join("new/file.txt"), "x");
+        assert_eq!(transaction.freed(), 5);
+        let lines = transaction.describe();
+        assert!(lines[0].starts_with("remove ") && lines[0].ends_with("old"));
+        assert!(lines[2].starts_with("replace "), "{}", lines[2]);
+        assert!(lines[3].starts_with("create "), "{}", lines[3]);
+        // Planning changed nothing
+        assert!(dir.join("old/inner/a.txt").exists());
+        assert_eq!(fs::read_to_string(dir.join("index.txt")).unwrap(), "before");
+
+        transaction.apply().unwrap();
+        assert!(!dir.join("old").exists());
+        assert_eq!(fs::read_to_string(dir.join("index.txt")).unwrap(), "after");
+        assert_eq!(fs::read_to_string(dir.join("new/file.txt")).unwrap(), "x");
+        assert!(!dir.join("index.txt.tmp").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+//! Explanation Variants
+//!
+//! A section can explain the same idea more than one way, so content authors
+//! can find out which way works better. The section lists its variants
+//! (`Section::with_variants`) and asks which one to show:
+//!
+//! ```ignore
+//! const LENDING: &[&str] = &["plain", "analogy"];
+//!
+//! fn references_and_borrowing() {
+//!     match variants::pick("ownership::references_and_borrowing", LENDING) {
+//!         "analogy" => explain!("Like lending a book: ..."),
+//!         _ => explain!("s1 is still valid after borrowing!"),
+//!     }
+//! }
+//! ```
+//!
+//! The pick hashes the section with the learner's seed, so one learner always
+//! sees the same variant and different learners see different ones. The seed
+//! is `[variants] seed` in config.toml, or else a hash of the learner's name.
+//! The CLI writes what was shown to the activity log, and the variant goes
+//! with every quiz answer about the section after that, for
+//! `rust-learn stats variants` to compare.
+
+use crate::random::{Rng, fnv1a};
+use crate::settings;
+use std::sync::{Mutex, OnceLock};
+
+/// `lesson::section` and the variant shown, since the last `take_shown`
+static SHOWN: Mutex<Vec<(String, &'static str)>> = Mutex::new(Vec::new());
+
+/// The learner's seed: `[variants] seed`, or a hash of `settings::learner()`
+pub fn seed() -> u64 {
+    static SEED: OnceLock<u64> = OnceLock::new();
+    *SEED.get_or_init(|| {
+        let configured = settings::get()
+            .table("variants")
+            .and_then(|table| table.integer("seed"));
+        match configured {
+            Some(seed) => seed as u64,
+            None => fnv1a(settings::learner().as_bytes()),
+        }
+    })
+}
+
+/// Which of `variants` (there must be at least one) the learner with `seed`
+/// sees in section `key`
+pub fn choose(seed: u64, key: &str, variants: &[&'static str]) -> &'static str {
+    let mut rng = Rng::with_seed(seed ^ fnv1a(key.as_bytes()));
+    variants[rng.below(variants.len())]
+}
+
+/// The variant to show this learner in section `key`, remembered for
+/// `take_shown`
+pub fn pick(key: &str, variants: &[&'static str]) -> &'static str {
+    let variant = choose(seed(), key, variants);
+    SHOWN
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push((key.to_string(), variant));
+    variant
+}
+
+/// Every variant picked since the last call, in order
+pub fn take_shown() -> Vec<(String, &'static str)> {
+    std::mem::take(
+        &mut *SHOWN
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_choose() {
+        const VARIANTS: &[&str] = &["plain", "analogy"];
+        let key = "ownership::references_and_borrowing";
+        assert_eq!(choose(7, key, VARIANTS), choose(7, key, VARIANTS));
+        let seen: Vec<&str> = (0..32).map(|seed| choose(seed, key, VARIANTS)).collect();
+        assert!(seen.contains(&"plain") && seen.contains(&"analogy"));
+        assert_eq!(choose(7, key, &["only"]), "only");
+
+        pick(key, VARIANTS);
+        assert_eq!(take_shown().len(), 1);
+        assert!(take_shown().is_empty());
+    }
+}
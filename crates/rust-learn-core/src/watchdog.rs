@@ -0,0 +1,192 @@
+//! Hang Detection
+//!
+//! A lesson stuck in an endless loop (loop.rs keeps one from ever running)
+//! would hold up `run --all`, the self-test or a grading script forever. Once
+//! `init` is given a limit, every lesson run inside `watch` has that long; a
+//! monitor thread checks the deadline, and when it passes it names the lesson
+//! and the section that was running and ends the process with exit code 124,
+//! the one `timeout(1)` uses. A thread cannot be stopped from outside, so
+//! ending the process is the only clean way out of the loop.
+//!
+//! Time spent waiting on something else, like a cargo build the lesson started
+//! or a line the learner has not typed yet, does not count (`pause`).
+
+use crate::lesson::{self, Lesson, Section};
+use crate::output;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{debug, error};
+
+/// The exit code of a run stopped by the watchdog
+pub const EXIT_CODE: i32 = 124;
+
+/// How often the monitor thread looks at the deadline
+const CHECK_EVERY: Duration = Duration::from_millis(200);
+
+static LIMIT: OnceLock<Option<Duration>> = OnceLock::new();
+
+/// The lesson being watched, if any
+static WATCHED: Mutex<Option<Watched>> = Mutex::new(None);
+
+struct Watched {
+    lesson: &'static str,
+    sections: &'static [Section],
+    /// Set when a single section runs on its own
+    section: Option<&'static str>,
+    started: Instant,
+    /// Time spent paused so far, not counting a pause still going on
+    paused: Duration,
+    paused_since: Option<Instant>,
+}
+
+impl Watched {
+    /// How long the lesson itself has been running
+    fn running_for(&self, now: Instant) -> Duration {
+        let paused_now = self
+            .paused_since
+            .map(|since| now - since)
+            .unwrap_or_default();
+        (now - self.started).saturating_sub(self.paused + paused_now)
+    }
+
+    /// `lesson::section` when the section is known, else the lesson's name
+    fn key(&self) -> String {
+        let section = self.section.or_else(|| {
+            let heading = output::last_heading()?;
+            lesson::section_by_heading(self.sections, &heading).map(|section| section.name)
+        });
+        match section {
+            Some(section) => format!("{}::{}", self.lesson, section),
+            None => self.lesson.to_string(),
+        }
+    }
+}
+
+/// Give every watched lesson `limit` (`None` turns the watchdog off) and start
+/// the monitor thread; later calls are ignored
+pub fn init(limit: Option<Duration>) {
+    if LIMIT.set(limit).is_err() {
+        return;
+    }
+    let Some(limit) = limit else {
+        return;
+    };
+    debug!(?limit, "watchdog started");
+    thread::Builder::new()
+        .name("watchdog".to_string())
+        .spawn(move || monitor(limit))
+        .expect("Failed to start the watchdog thread");
+}
+
+/// Watches a lesson until it is dropped
+pub struct Watch(());
+
+/// Start the clock on `lesson`, or on just `section` of it
+pub fn watch(lesson: &Lesson, section: Option<&Section>) -> Watch {
+    output::clear_headings();
+    if let Ok(mut watched) = WATCHED.lock() {
+        *watched = Some(Watched {
+            lesson: lesson.name,
+            sections: lesson.sections,
+            section: section.map(|section| section.name),
+            started: Instant::now(),
+            paused: Duration::ZERO,
+            paused_since: None,
+        });
+    }
+    Watch(())
+}
+
+impl Drop for Watch {
+    fn drop(&mut self) {
+        if let Ok(mut watched) = WATCHED.lock() {
+            *watched = None;
+        }
+    }
+}
+
+/// Stops the clock until it is dropped
+pub struct Pause(());
+
+/// Stop counting time against the watched lesson, while it waits on
+/// something other than its own code
+pub fn pause() -> Pause {
+    if let Ok(mut watched) = WATCHED.lock()
+        && let Some(watched) = watched.as_mut()
+    {
+        watched.paused_since.get_or_insert_with(Instant::now);
+    }
+    Pause(())
+}
+
+impl Drop for Pause {
+    fn drop(&mut self) {
+        if let Ok(mut watched) = WATCHED.lock()
+            && let Some(watched) = watched.as_mut()
+            && let Some(since) = watched.paused_since.take()
+        {
+            watched.paused += since.elapsed();
+        }
+    }
+}
+
+fn monitor(limit: Duration) {
+    loop {
+        thread::sleep(CHECK_EVERY);
+        let Ok(watched) = WATCHED.lock() else {
+            return;
+        };
+        let Some(watched) = watched.as_ref() else {
+            continue;
+        };
+        if watched.running_for(Instant::now()) <= limit {
+            continue;
+        }
+
+        let key = watched.key();
+        error!(running = %key, ?limit, "lesson timed out");
+        eprintln!(
+            "\n{}",
+            output::red(&format!(
+                "{} ran for more than {}s without finishing, so it looks stuck \
+                 (an endless loop?). Stopping.",
+                key,
+                limit.as_secs()
+            ))
+        );
+        eprintln!("--timeout <SECONDS> changes the limit; --timeout 0 turns it off.");
+        std::process::exit(EXIT_CODE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop() {}
+
+    const SECTIONS: &[Section] = &[
+        Section::new("ranges", "Ranges", noop),
+        Section::new("labels", "Loop Labels", noop),
+    ];
+
+    #[test]
+    fn test_paused_time_does_not_count() {
+        let start = Instant::now();
+        let watched = Watched {
+            lesson: "loops",
+            sections: SECTIONS,
+            section: None,
+            started: start,
+            paused: Duration::from_secs(30),
+            paused_since: Some(start + Duration::from_secs(100)),
+        };
+        let now = start + Duration::from_secs(150);
+        assert_eq!(watched.running_for(now), Duration::from_secs(70));
+
+        let section = lesson::section_by_heading(SECTIONS, "2. Loop Labels:");
+        assert_eq!(section.map(|section| section.name), Some("labels"));
+        assert!(lesson::section_by_heading(SECTIONS, "=== Loops ===").is_none());
+    }
+}
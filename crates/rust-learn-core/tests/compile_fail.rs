@@ -0,0 +1,11 @@
+/// Compile-fail fixtures for the API design lesson
+///
+/// Each file in tests/compile_fail is a caller that breaks when an API changes in
+/// a way the lesson calls "major". The expected rustc output sits next to it in a
+/// `.stderr` file; regenerate with `TRYBUILD=overwrite cargo test --test compile_fail`.
+
+#[test]
+fn breaking_api_changes_fail_to_compile() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+}
@@ -0,0 +1,8 @@
+// Deprecation is only a warning, unless the caller denies warnings.
+#![deny(deprecated)]
+
+use rust_learn_core::config_loader::load_strict;
+
+fn main() {
+    let _ = load_strict("app.conf");
+}
@@ -0,0 +1,9 @@
+// 0.1's `Config` only had `name`. Adding the public `entries` field broke every
+// caller that built a Config with a struct literal - it is a major change.
+use rust_learn_core::config_loader::Config;
+
+fn main() {
+    let _config = Config {
+        name: String::from("app"),
+    };
+}
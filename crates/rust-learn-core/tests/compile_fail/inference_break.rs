@@ -0,0 +1,9 @@
+// 0.1 declared `load(path: &str)`, so `name.as_ref()` was inferred as `&str`.
+// Since 0.2 the parameter is `impl AsRef<Path>` and the target type of
+// `as_ref()` is ambiguous: String implements AsRef<str>, AsRef<Path>, ...
+use rust_learn_core::config_loader::load;
+
+fn main() {
+    let name = String::from("app.conf");
+    let _ = load(name.as_ref());
+}
@@ -0,0 +1,12 @@
+// `LoadError` is #[non_exhaustive]: listing every current variant is not
+// enough, a `_` arm is required so new variants are not a breaking change.
+use rust_learn_core::config_loader::{load, LoadError};
+
+fn main() {
+    match load("app.conf") {
+        Ok(_) => {}
+        Err(LoadError::Io(_)) => {}
+        Err(LoadError::TooLarge { .. }) => {}
+        Err(LoadError::Syntax { .. }) => {}
+    }
+}
@@ -0,0 +1,10 @@
+// `LoadOptions` is #[non_exhaustive]: other crates cannot build it with a
+// struct literal, because a later release may add fields.
+use rust_learn_core::config_loader::LoadOptions;
+
+fn main() {
+    let _options = LoadOptions {
+        strict: true,
+        max_size: 1024,
+    };
+}
@@ -0,0 +1,338 @@
+//! `#[derive(Error)]`, in the style of the thiserror crate: `Display` from an
+//! `#[error("...")]` attribute on each variant (or on the struct), and
+//! `std::error::Error` whose `source()` is the field marked `#[source]` or
+//! `#[from]`, or else a field named `source`. A `#[from]` field, alone in its
+//! variant, also gets a `From` impl, so `?` converts into the variant.
+//!
+//! The message is a format string whose `{name}` and `{0}` are the variant's
+//! fields; the generated `fmt` binds every field by name (`_0`, `_1` for tuple
+//! fields) and hands the string to `write!`.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+struct Field {
+    /// What the generated code binds it as: its name, or `_0`, `_1`, ...
+    binding: String,
+    /// The name as written, for named fields
+    name: Option<String>,
+    ty: String,
+    source: bool,
+    from: bool,
+}
+
+enum Shape {
+    Unit,
+    Named,
+    Tuple,
+}
+
+struct Variant {
+    /// `None` for a struct
+    name: Option<String>,
+    message: Option<String>,
+    shape: Shape,
+    fields: Vec<Field>,
+}
+
+impl Variant {
+    /// `Type::Variant { a, b }`, `Type::Variant(_0, _1)` or `Type`
+    fn pattern(&self, type_name: &str) -> String {
+        let path = match &self.name {
+            Some(name) => format!("{}::{}", type_name, name),
+            None => type_name.to_string(),
+        };
+        let bindings: Vec<&str> = self.fields.iter().map(|f| f.binding.as_str()).collect();
+        match self.shape {
+            Shape::Unit => path,
+            Shape::Named => format!("{} {{ {} }}", path, bindings.join(", ")),
+            Shape::Tuple => format!("{}({})", path, bindings.join(", ")),
+        }
+    }
+
+    fn source(&self) -> Option<&Field> {
+        let marked = self.fields.iter().find(|field| field.source || field.from);
+        marked.or_else(|| {
+            self.fields
+                .iter()
+                .find(|field| field.name.as_deref() == Some("source"))
+        })
+    }
+
+    fn label(&self, type_name: &str) -> String {
+        self.name.clone().unwrap_or_else(|| type_name.to_string())
+    }
+}
+
+/// The impls for `input`, as source text, or why they cannot be written
+pub fn derive(input: TokenStream) -> Result<String, String> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let (attrs, mut rest) = split_attrs(&tokens);
+    rest = skip_visibility(rest);
+
+    let keyword = match rest.first() {
+        Some(TokenTree::Ident(ident)) => ident.to_string(),
+        _ => return Err("Error can only be derived for structs and enums".into()),
+    };
+    let Some(TokenTree::Ident(name)) = rest.get(1) else {
+        return Err("Error can only be derived for structs and enums".into());
+    };
+    let type_name = name.to_string();
+    if matches!(rest.get(2), Some(TokenTree::Punct(punct)) if punct.as_char() == '<') {
+        return Err("Error does not support generic types".into());
+    }
+
+    let variants = match (keyword.as_str(), rest.get(2)) {
+        ("enum", Some(TokenTree::Group(body))) => {
+            let tokens: Vec<TokenTree> = body.stream().into_iter().collect();
+            split_commas(&tokens)
+                .iter()
+                .map(|chunk| variant(chunk))
+                .collect::<Result<Vec<_>, _>>()?
+        }
+        ("struct", body) => {
+            let (shape, fields) = match body {
+                Some(TokenTree::Group(group)) => fields(group)?,
+                _ => (Shape::Unit, Vec::new()),
+            };
+            vec![Variant {
+                name: None,
+                message: message(&attrs),
+                shape,
+                fields,
+            }]
+        }
+        _ => return Err("Error can only be derived for structs and enums".into()),
+    };
+
+    let mut code = String::new();
+    code.push_str(&display(&type_name, &variants)?);
+    code.push_str(&error(&type_name, &variants));
+    for variant in &variants {
+        code.push_str(&from(&type_name, variant)?);
+    }
+    Ok(code)
+}
+
+fn display(type_name: &str, variants: &[Variant]) -> Result<String, String> {
+    let mut arms = String::new();
+    for variant in variants {
+        let Some(message) = &variant.message else {
+            return Err(format!(
+                "{} needs an #[error(\"...\")] attribute with its message",
+                variant.label(type_name)
+            ));
+        };
+        arms.push_str(&format!(
+            "{} => ::core::write!(f, {}),\n",
+            variant.pattern(type_name),
+            numbered_to_bindings(message)
+        ));
+    }
+    if variants.is_empty() {
+        arms.push_str("_ => ::core::result::Result::Ok(()),\n");
+    }
+    Ok(format!(
+        "impl ::core::fmt::Display for {name} {{
+            #[allow(unused_variables)]
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {{
+                match self {{ {arms} }}
+            }}
+        }}\n",
+        name = type_name,
+        arms = arms
+    ))
+}
+
+fn error(type_name: &str, variants: &[Variant]) -> String {
+    let mut arms = String::new();
+    for variant in variants {
+        if let Some(field) = variant.source() {
+            arms.push_str(&format!(
+                "{} => ::core::option::Option::Some({}),\n",
+                variant.pattern(type_name),
+                field.binding
+            ));
+        }
+    }
+    arms.push_str("#[allow(unreachable_patterns)] _ => ::core::option::Option::None,\n");
+    format!(
+        "impl ::std::error::Error for {name} {{
+            #[allow(unused_variables)]
+            fn source(&self) -> ::core::option::Option<&(dyn ::std::error::Error + 'static)> {{
+                match self {{ {arms} }}
+            }}
+        }}\n",
+        name = type_name,
+        arms = arms
+    )
+}
+
+fn from(type_name: &str, variant: &Variant) -> Result<String, String> {
+    let Some(field) = variant.fields.iter().find(|field| field.from) else {
+        return Ok(String::new());
+    };
+    if variant.fields.len() > 1 {
+        return Err(format!(
+            "#[from] in {} needs it to be the only field: From has nothing to fill the others with",
+            variant.label(type_name)
+        ));
+    }
+    let value = match (&variant.shape, &field.name) {
+        (Shape::Named, Some(name)) => format!("{{ {}: source }}", name),
+        _ => "(source)".to_string(),
+    };
+    let path = match &variant.name {
+        Some(name) => format!("{}::{}", type_name, name),
+        None => type_name.to_string(),
+    };
+    Ok(format!(
+        "impl ::core::convert::From<{ty}> for {name} {{
+            fn from(source: {ty}) -> Self {{ {path}{value} }}
+        }}\n",
+        ty = field.ty,
+        name = type_name,
+        path = path,
+        value = value
+    ))
+}
+
+/// One enum variant: attributes, name, fields, maybe `= discriminant`
+fn variant(tokens: &[TokenTree]) -> Result<Variant, String> {
+    let (attrs, rest) = split_attrs(tokens);
+    let Some(TokenTree::Ident(name)) = rest.first() else {
+        return Err("expected a variant name".into());
+    };
+    let (shape, fields) = match rest.get(1) {
+        Some(TokenTree::Group(group)) => fields(group)?,
+        _ => (Shape::Unit, Vec::new()),
+    };
+    Ok(Variant {
+        name: Some(name.to_string()),
+        message: message(&attrs),
+        shape,
+        fields,
+    })
+}
+
+/// The fields in `{ ... }` or `( ... )`
+fn fields(group: &proc_macro::Group) -> Result<(Shape, Vec<Field>), String> {
+    let shape = match group.delimiter() {
+        Delimiter::Brace => Shape::Named,
+        Delimiter::Parenthesis => Shape::Tuple,
+        _ => return Ok((Shape::Unit, Vec::new())),
+    };
+    let tokens: Vec<TokenTree> = group.stream().into_iter().collect();
+    let mut fields = Vec::new();
+    for (index, chunk) in split_commas(&tokens).iter().enumerate() {
+        let (attrs, rest) = split_attrs(chunk);
+        let rest = skip_visibility(rest);
+        let (name, ty) = match shape {
+            Shape::Named => match rest {
+                [TokenTree::Ident(name), TokenTree::Punct(colon), ty @ ..]
+                    if colon.as_char() == ':' =>
+                {
+                    (Some(name.to_string()), ty)
+                }
+                _ => return Err("expected `name: Type`".into()),
+            },
+            _ => (None, rest),
+        };
+        let ty: TokenStream = ty.iter().cloned().collect();
+        fields.push(Field {
+            binding: name.clone().unwrap_or_else(|| format!("_{}", index)),
+            name,
+            ty: ty.to_string(),
+            source: attrs.iter().any(|attr| is_marker(attr, "source")),
+            from: attrs.iter().any(|attr| is_marker(attr, "from")),
+        });
+    }
+    Ok((shape, fields))
+}
+
+/// Leading `#[...]` attributes (the bracket groups) and what follows them
+fn split_attrs(tokens: &[TokenTree]) -> (Vec<&proc_macro::Group>, &[TokenTree]) {
+    let mut attrs = Vec::new();
+    let mut rest = tokens;
+    while let [TokenTree::Punct(hash), TokenTree::Group(group), after @ ..] = rest {
+        if hash.as_char() != '#' || group.delimiter() != Delimiter::Bracket {
+            break;
+        }
+        attrs.push(group);
+        rest = after;
+    }
+    (attrs, rest)
+}
+
+/// Past `pub` or `pub(crate)`
+fn skip_visibility(tokens: &[TokenTree]) -> &[TokenTree] {
+    match tokens {
+        [TokenTree::Ident(ident), TokenTree::Group(group), rest @ ..]
+            if ident.to_string() == "pub" && group.delimiter() == Delimiter::Parenthesis =>
+        {
+            rest
+        }
+        [TokenTree::Ident(ident), rest @ ..] if ident.to_string() == "pub" => rest,
+        _ => tokens,
+    }
+}
+
+/// The string literal in `#[error("...")]`, as written
+fn message(attrs: &[&proc_macro::Group]) -> Option<String> {
+    attrs.iter().find_map(|attr| {
+        let tokens: Vec<TokenTree> = attr.stream().into_iter().collect();
+        match tokens.as_slice() {
+            [TokenTree::Ident(ident), TokenTree::Group(args)] if ident.to_string() == "error" => {
+                Some(args.stream().to_string())
+            }
+            _ => None,
+        }
+    })
+}
+
+/// `#[source]` or `#[from]`
+fn is_marker(attr: &proc_macro::Group, name: &str) -> bool {
+    let tokens: Vec<TokenTree> = attr.stream().into_iter().collect();
+    matches!(tokens.as_slice(), [TokenTree::Ident(ident)] if ident.to_string() == name)
+}
+
+/// `tokens` split at the commas that are not inside `<...>`
+fn split_commas(tokens: &[TokenTree]) -> Vec<&[TokenTree]> {
+    let mut chunks = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (index, token) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(punct) = token {
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' if depth > 0 => depth -= 1,
+                ',' if depth == 0 => {
+                    chunks.push(&tokens[start..index]);
+                    start = index + 1;
+                }
+                _ => {}
+            }
+        }
+    }
+    if start < tokens.len() {
+        chunks.push(&tokens[start..]);
+    }
+    chunks
+}
+
+/// `{0}` and `{1:?}` in a format string become `{_0}` and `{_1:?}`, the
+/// names the tuple fields are bound to; `{{` stays as it is
+fn numbered_to_bindings(message: &str) -> String {
+    let mut result = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+    while let Some(c) = chars.next() {
+        result.push(c);
+        if c == '{' {
+            match chars.peek() {
+                Some('{') => result.push(chars.next().expect("peeked")),
+                Some(next) if next.is_ascii_digit() => result.push('_'),
+                _ => {}
+            }
+        }
+    }
+    result
+}
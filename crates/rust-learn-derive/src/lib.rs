@@ -0,0 +1,90 @@
+//! rust-learn derive macros
+//!
+//! Procedural macros have to live in their own `proc-macro = true` crate: the
+//! compiler builds them for the host and loads them while compiling the crates
+//! that use them. The traits they implement live in rust-learn-core (or in
+//! std, for `Error`), which re-exports the macros, so users only ever depend
+//! on rust-learn-core.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+mod error;
+
+/// `#[derive(Explain)]` - implement `rust_learn_core::Explain` by returning the
+/// type's definition exactly as it is written in the source (without attributes)
+#[proc_macro_derive(Explain)]
+pub fn derive_explain(input: TokenStream) -> TokenStream {
+    let mut tokens = input.into_iter();
+    let mut parts: Vec<String> = Vec::new();
+    let mut name = None;
+
+    while let Some(token) = tokens.next() {
+        // Skip `#[...]` attributes (including doc comments)
+        if let TokenTree::Punct(punct) = &token
+            && punct.as_char() == '#'
+            && parts.is_empty()
+        {
+            tokens.next();
+            continue;
+        }
+
+        if let TokenTree::Punct(punct) = &token
+            && punct.as_char() == '<'
+        {
+            return compile_error("Explain does not support generic types");
+        }
+
+        if let TokenTree::Ident(ident) = &token
+            && name.is_none()
+            && parts
+                .last()
+                .is_some_and(|last| matches!(last.as_str(), "struct" | "enum" | "union"))
+        {
+            name = Some(ident.to_string());
+        }
+
+        // A group's source text keeps the original formatting and comments
+        let text = match &token {
+            TokenTree::Group(group) => group
+                .span()
+                .source_text()
+                .unwrap_or_else(|| group.to_string()),
+            _ => token.to_string(),
+        };
+        let glued = matches!(&token, TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis)
+            || text == ";";
+        match parts.last_mut() {
+            Some(last) if glued => last.push_str(&text),
+            _ => parts.push(text),
+        }
+    }
+
+    let Some(name) = name else {
+        return compile_error("Explain can only be derived for structs, enums and unions");
+    };
+
+    format!(
+        "impl ::rust_learn_core::Explain for {} {{ fn definition() -> &'static str {{ {:?} }} }}",
+        name,
+        parts.join(" ")
+    )
+    .parse()
+    .expect("generated impl is valid Rust")
+}
+
+/// `#[derive(Error)]` - implement `Display` from `#[error("...")]` messages
+/// and `std::error::Error` with `source()`, like the thiserror crate (see
+/// `error.rs` for the attributes)
+#[proc_macro_derive(Error, attributes(error, source, from))]
+pub fn derive_error(input: TokenStream) -> TokenStream {
+    match error::derive(input) {
+        Ok(code) => code.parse().expect("generated impls are valid Rust"),
+        Err(message) => compile_error(&message),
+    }
+}
+
+fn compile_error(message: &str) -> TokenStream {
+    format!("compile_error!({:?});", message)
+        .parse()
+        .expect("compile_error! invocation is valid Rust")
+}
@@ -0,0 +1,28 @@
+//! Build script for rust-learn-ffi
+//!
+//! Runs cbindgen over `src/lib.rs` and writes the C header for the exported
+//! functions to `$OUT_DIR/rust_learn_lru.h`. The crate embeds it as `HEADER`,
+//! so the header always matches the library it was built with.
+
+use std::env;
+use std::path::PathBuf;
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // with_src parses one file; with_crate would run `cargo metadata` first
+    cbindgen::Builder::new()
+        .with_src(manifest_dir.join("src/lib.rs"))
+        .with_language(cbindgen::Language::C)
+        .with_include_guard("RUST_LEARN_LRU_H")
+        .with_header(
+            "/* Generated by cbindgen from crates/rust-learn-ffi/src/lib.rs - do not edit */",
+        )
+        .generate()
+        .expect("cbindgen could not generate the header")
+        .write_to_file(out_dir.join("rust_learn_lru.h"));
+}
@@ -0,0 +1,31 @@
+/* Exercises the rust-learn-ffi C API. The cbindgen lesson compiles this
+ * against the generated header and librust_learn_ffi, then runs it. */
+
+#include <stdio.h>
+#include "rust_learn_lru.h"
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    printf("%s %s\n", ok ? "ok  " : "FAIL", what);
+    if (!ok) {
+        failures++;
+    }
+}
+
+int main(void) {
+    RlLru *cache = rl_lru_new(2);
+    int64_t value = 0;
+
+    check(!rl_lru_put(cache, "apple", 1), "put apple = 1");
+    check(!rl_lru_put(cache, "banana", 2), "put banana = 2");
+    check(rl_lru_get(cache, "apple", &value) && value == 1, "get apple == 1");
+    check(rl_lru_put(cache, "cherry", 3), "put cherry = 3 evicts an entry");
+    check(!rl_lru_get(cache, "banana", &value), "banana was the one evicted");
+    check(rl_lru_len(cache) == 2, "len == 2");
+    check(!rl_lru_put(NULL, "apple", 1), "NULL cache is rejected");
+
+    rl_lru_free(cache);
+    printf("%d failure(s)\n", failures);
+    return failures == 0 ? 0 : 1;
+}
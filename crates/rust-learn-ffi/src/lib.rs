@@ -0,0 +1,146 @@
+//! rust-learn ffi
+//!
+//! The LRU cache from rust-learn-utils behind a C API. Built as a `cdylib`, it
+//! is a shared library any C program can link against; `build.rs` runs cbindgen
+//! over this file to write the matching header, `rust_learn_lru.h`.
+//!
+//! C only sees an opaque `RlLru` pointer. Keys are NUL-terminated strings,
+//! copied into the cache; values are 64-bit integers.
+
+use rust_learn_utils::lru::LruCache;
+use std::ffi::{CStr, c_char};
+
+/// The generated C header, as cbindgen wrote it for this build
+pub const HEADER: &str = include_str!(concat!(env!("OUT_DIR"), "/rust_learn_lru.h"));
+
+/// File name of the generated header
+pub const HEADER_NAME: &str = "rust_learn_lru.h";
+
+/// A C program exercising the API; the cbindgen lesson compiles and runs it
+pub const C_TEST: &str = include_str!("../c/lru_test.c");
+
+/// An LRU cache from string keys to 64-bit values (opaque to C)
+pub struct RlLru {
+    cache: LruCache<Vec<u8>, i64>,
+}
+
+/// Create a cache holding at most `capacity` entries. Free it with `rl_lru_free`.
+///
+/// Memory is taken as entries are added, not reserved for `capacity`, so a
+/// huge capacity cannot fail here: `SIZE_MAX` makes a cache that never
+/// evicts. 0 is taken as 1.
+#[unsafe(no_mangle)]
+pub extern "C" fn rl_lru_new(capacity: usize) -> *mut RlLru {
+    let lru = RlLru {
+        cache: LruCache::new(capacity),
+    };
+    // Ownership moves to C until rl_lru_free hands it back
+    Box::into_raw(Box::new(lru))
+}
+
+/// Free a cache. Passing NULL does nothing.
+///
+/// # Safety
+///
+/// `lru` must be NULL or a pointer from `rl_lru_new` that was not freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rl_lru_free(lru: *mut RlLru) {
+    if !lru.is_null() {
+        // SAFETY: the caller promises `lru` came from Box::into_raw in rl_lru_new
+        drop(unsafe { Box::from_raw(lru) });
+    }
+}
+
+/// Insert or replace `key`. Returns true when another entry was evicted to
+/// make room, false otherwise (including when `lru` or `key` is NULL).
+///
+/// # Safety
+///
+/// `lru` must be NULL or a live cache; `key` must be NULL or a NUL-terminated
+/// string. The key is copied, so the caller keeps ownership of it.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rl_lru_put(lru: *mut RlLru, key: *const c_char, value: i64) -> bool {
+    // SAFETY: upheld by the caller, see above
+    let (Some(lru), Some(key)) = (unsafe { lru.as_mut() }, unsafe { key_bytes(key) }) else {
+        return false;
+    };
+    lru.cache.put(key.to_vec(), value).is_some()
+}
+
+/// Look up `key`, marking it as recently used. On a hit, stores the value in
+/// `*out` and returns true; on a miss returns false and leaves `*out` alone.
+///
+/// # Safety
+///
+/// As for `rl_lru_put`; `out` must be NULL or point to writable memory for
+/// one `int64_t`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rl_lru_get(lru: *mut RlLru, key: *const c_char, out: *mut i64) -> bool {
+    // SAFETY: upheld by the caller, see above
+    let (Some(lru), Some(key)) = (unsafe { lru.as_mut() }, unsafe { key_bytes(key) }) else {
+        return false;
+    };
+    match lru.cache.get(&key.to_vec()) {
+        Some(&value) => {
+            if !out.is_null() {
+                // SAFETY: non-null, and the caller promises it is writable
+                unsafe { out.write(value) };
+            }
+            true
+        }
+        None => false,
+    }
+}
+
+/// Number of entries in the cache (0 for NULL)
+///
+/// # Safety
+///
+/// `lru` must be NULL or a live cache.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn rl_lru_len(lru: *const RlLru) -> usize {
+    // SAFETY: upheld by the caller
+    unsafe { lru.as_ref() }.map_or(0, |lru| lru.cache.len())
+}
+
+/// The bytes of a NUL-terminated string, without the NUL
+///
+/// # Safety
+///
+/// `key` must be NULL or point to a NUL-terminated string that outlives `'a`.
+unsafe fn key_bytes<'a>(key: *const c_char) -> Option<&'a [u8]> {
+    if key.is_null() {
+        return None;
+    }
+    // SAFETY: non-null and NUL-terminated, as the caller promises
+    Some(unsafe { CStr::from_ptr(key) }.to_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_c_api_from_rust() {
+        let lru = rl_lru_new(2);
+        let mut value = 0;
+        // SAFETY: `lru` is live until rl_lru_free, and the keys are C string literals
+        unsafe {
+            assert!(!rl_lru_put(lru, c"a".as_ptr(), 1));
+            assert!(!rl_lru_put(lru, c"b".as_ptr(), 2));
+            assert!(rl_lru_get(lru, c"a".as_ptr(), &mut value));
+            assert_eq!(value, 1);
+            assert!(rl_lru_put(lru, c"c".as_ptr(), 3));
+            assert!(!rl_lru_get(lru, c"b".as_ptr(), &mut value));
+            assert!(!rl_lru_put(lru, std::ptr::null(), 4));
+            assert_eq!(rl_lru_len(lru), 2);
+            rl_lru_free(lru);
+
+            let unbounded = rl_lru_new(usize::MAX);
+            assert!(!rl_lru_put(unbounded, c"a".as_ptr(), 1));
+            assert_eq!(rl_lru_len(unbounded), 1);
+            rl_lru_free(unbounded);
+        }
+        assert!(HEADER.contains("rl_lru_new"));
+    }
+}
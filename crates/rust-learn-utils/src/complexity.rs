@@ -0,0 +1,274 @@
+//! Growth Rate Estimates
+//!
+//! Big-O says how an operation's time grows with its input, not how long it
+//! takes, so it can be measured: `Harness` times a closure on inputs that grow
+//! by a fixed factor, and `Estimate::fit` compares the times with each
+//! `Class` and picks the one they follow most closely. Timing reads the
+//! clock, so this is only built with the `std` feature.
+
+use core::fmt;
+use core::time::Duration;
+use std::hint::black_box;
+use std::time::Instant;
+use std::vec::Vec;
+
+/// The growth rates an estimate chooses between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+}
+
+impl Class {
+    pub const ALL: [Class; 6] = [
+        Class::Constant,
+        Class::Logarithmic,
+        Class::Linear,
+        Class::Linearithmic,
+        Class::Quadratic,
+        Class::Cubic,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Class::Constant => "O(1)",
+            Class::Logarithmic => "O(log n)",
+            Class::Linear => "O(n)",
+            Class::Linearithmic => "O(n log n)",
+            Class::Quadratic => "O(n^2)",
+            Class::Cubic => "O(n^3)",
+        }
+    }
+
+    /// The natural log of the class's function at `n`; only differences
+    /// between sizes matter, so constant factors are left out
+    fn ln_at(self, n: f64) -> f64 {
+        let ln = n.ln();
+        match self {
+            Class::Constant => 0.0,
+            Class::Logarithmic => ln.ln(),
+            Class::Linear => ln,
+            Class::Linearithmic => ln + ln.ln(),
+            Class::Quadratic => 2.0 * ln,
+            Class::Cubic => 3.0 * ln,
+        }
+    }
+}
+
+/// How long one input size took
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sample {
+    pub size: usize,
+    pub time: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Estimate {
+    pub samples: Vec<Sample>,
+    /// The class the times follow most closely
+    pub class: Class,
+    /// The slope of log(time) against log(size): 1.0 for linear, 2.0 for
+    /// quadratic, in between for n log n
+    pub exponent: f64,
+}
+
+impl Estimate {
+    /// Fit `samples` (at least two sizes, all above 1) against every class
+    pub fn fit(samples: Vec<Sample>) -> Estimate {
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|sample| {
+                let nanos = sample.time.as_nanos().max(1) as f64;
+                (sample.size.max(2) as f64, nanos.ln())
+            })
+            .collect();
+
+        // time = c * f(n) is ln(time) - ln(f(n)) = ln(c) for every sample:
+        // the class whose differences vary least fits best
+        let spread = |class: Class| {
+            let residuals: Vec<f64> = points
+                .iter()
+                .map(|&(n, ln_time)| ln_time - class.ln_at(n))
+                .collect();
+            let mean = residuals.iter().sum::<f64>() / residuals.len() as f64;
+            residuals
+                .iter()
+                .map(|r| (r - mean) * (r - mean))
+                .sum::<f64>()
+        };
+        let class = Class::ALL
+            .into_iter()
+            .min_by(|a, b| spread(*a).total_cmp(&spread(*b)))
+            .unwrap_or(Class::Constant);
+
+        let count = points.len() as f64;
+        let mean_x = points.iter().map(|&(n, _)| n.ln()).sum::<f64>() / count;
+        let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / count;
+        let (mut covariance, mut variance) = (0.0, 0.0);
+        for &(n, y) in &points {
+            covariance += (n.ln() - mean_x) * (y - mean_y);
+            variance += (n.ln() - mean_x) * (n.ln() - mean_x);
+        }
+        let exponent = if variance > 0.0 {
+            covariance / variance
+        } else {
+            0.0
+        };
+
+        Estimate {
+            samples,
+            class,
+            exponent,
+        }
+    }
+}
+
+/// `12.3µs`, with three significant digits or so
+struct Time(Duration);
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let nanos = self.0.as_nanos() as f64;
+        let (value, unit) = if nanos < 1e3 {
+            (nanos, "ns")
+        } else if nanos < 1e6 {
+            (nanos / 1e3, "µs")
+        } else if nanos < 1e9 {
+            (nanos / 1e6, "ms")
+        } else {
+            (nanos / 1e9, "s")
+        };
+        let text = if value < 10.0 {
+            std::format!("{:.2}{}", value, unit)
+        } else if value < 100.0 {
+            std::format!("{:.1}{}", value, unit)
+        } else {
+            std::format!("{:.0}{}", value, unit)
+        };
+        f.pad(&text)
+    }
+}
+
+/// One line per size with its time and how much it grew, then the class
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:>10}  {:>9}  {:>7}", "n", "time", "growth")?;
+        let mut previous: Option<Duration> = None;
+        for sample in &self.samples {
+            write!(f, "{:>10}  {:>9}", sample.size, Time(sample.time))?;
+            if let Some(previous) = previous {
+                let ratio = sample.time.as_secs_f64() / previous.as_secs_f64().max(1e-9);
+                write!(f, "  {:>6.2}x", ratio)?;
+            }
+            writeln!(f)?;
+            previous = Some(sample.time);
+        }
+        write!(
+            f,
+            "grows like {}: time ~ n^{:.2}",
+            self.class.name(),
+            self.exponent
+        )
+    }
+}
+
+/// Which sizes to time, and how carefully
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Harness {
+    /// The first input size
+    pub start: usize,
+    /// Each size is the previous one times this
+    pub factor: usize,
+    /// How many sizes
+    pub steps: usize,
+    /// Runs per size; the fastest counts, as the others were interrupted
+    pub repeats: usize,
+}
+
+impl Harness {
+    /// 1,000 doubling up to 32,000, fastest of five runs
+    pub const DEFAULT: Harness = Harness {
+        start: 1_000,
+        factor: 2,
+        steps: 6,
+        repeats: 5,
+    };
+
+    pub fn sizes(&self) -> impl Iterator<Item = usize> + use<> {
+        let (start, factor) = (self.start.max(2), self.factor.max(2));
+        (0..self.steps as u32).map(move |step| start.saturating_mul(factor.saturating_pow(step)))
+    }
+
+    /// Time `run` on an input of each size made by `setup`; only `run` is
+    /// timed, and each run gets a fresh input
+    pub fn measure<T>(
+        &self,
+        mut setup: impl FnMut(usize) -> T,
+        mut run: impl FnMut(&mut T),
+    ) -> Estimate {
+        let samples = self
+            .sizes()
+            .map(|size| {
+                let time = (0..self.repeats.max(1))
+                    .map(|_| {
+                        let mut input = setup(size);
+                        let start = Instant::now();
+                        run(&mut input);
+                        let time = start.elapsed();
+                        black_box(input);
+                        time
+                    })
+                    .min()
+                    .unwrap_or_default();
+                Sample { size, time }
+            })
+            .collect();
+        Estimate::fit(samples)
+    }
+}
+
+impl Default for Harness {
+    fn default() -> Self {
+        Harness::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples(time: impl Fn(f64) -> f64) -> Vec<Sample> {
+        Harness::DEFAULT
+            .sizes()
+            .map(|size| Sample {
+                size,
+                time: Duration::from_nanos(time(size as f64) as u64),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_fit_picks_the_class_the_times_follow() {
+        assert_eq!(Estimate::fit(samples(|_| 500.0)).class, Class::Constant);
+        assert_eq!(
+            Estimate::fit(samples(|n| 40.0 * n.ln())).class,
+            Class::Logarithmic
+        );
+        assert_eq!(Estimate::fit(samples(|n| 3.0 * n)).class, Class::Linear);
+        assert_eq!(
+            Estimate::fit(samples(|n| 3.0 * n * n.ln())).class,
+            Class::Linearithmic
+        );
+        let quadratic = Estimate::fit(samples(|n| n * n));
+        assert_eq!(quadratic.class, Class::Quadratic);
+        assert!((quadratic.exponent - 2.0).abs() < 0.01);
+        assert_eq!(
+            Harness::DEFAULT.sizes().collect::<Vec<_>>(),
+            [1_000, 2_000, 4_000, 8_000, 16_000, 32_000]
+        );
+    }
+}
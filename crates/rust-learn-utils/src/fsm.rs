@@ -0,0 +1,70 @@
+//! Connection State Machine
+//!
+//! The `ConnectionState` example from the enums lesson, made strict: every
+//! change goes through `State::next`, and an event that makes no sense in the
+//! current state is an error instead of being silently ignored.
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Disconnected,
+    /// `attempt` counts from 1
+    Connecting {
+        attempt: u32,
+    },
+    Connected {
+        id: u32,
+    },
+    /// Gave up after too many attempts
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Connect,
+    Established {
+        id: u32,
+    },
+    /// A connection attempt failed, or an open connection was lost
+    Dropped,
+    Disconnect,
+}
+
+/// An event that is not allowed in the state it arrived in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidTransition {
+    pub from: State,
+    pub event: Event,
+}
+
+// core::fmt works without std, so errors can still describe themselves
+impl fmt::Display for InvalidTransition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not allowed while {:?}", self.event, self.from)
+    }
+}
+
+impl core::error::Error for InvalidTransition {}
+
+impl State {
+    /// The state after `event`; a failed attempt retries until `max_attempts`
+    pub fn next(self, event: Event, max_attempts: u32) -> Result<State, InvalidTransition> {
+        let next = match (self, event) {
+            (State::Disconnected | State::Failed, Event::Connect) => {
+                State::Connecting { attempt: 1 }
+            }
+            (State::Connecting { .. }, Event::Established { id }) => State::Connected { id },
+            (State::Connecting { attempt }, Event::Dropped) if attempt < max_attempts => {
+                State::Connecting {
+                    attempt: attempt + 1,
+                }
+            }
+            (State::Connecting { .. }, Event::Dropped) => State::Failed,
+            (State::Connected { .. }, Event::Dropped) => State::Connecting { attempt: 1 },
+            (_, Event::Disconnect) => State::Disconnected,
+            (from, event) => return Err(InvalidTransition { from, event }),
+        };
+        Ok(next)
+    }
+}
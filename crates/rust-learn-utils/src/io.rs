@@ -0,0 +1,152 @@
+//! Fast Input
+//!
+//! Reading "n, then n numbers" the obvious way, `read_line` into a `String`
+//! then `split_whitespace` and `parse`, checks every line for UTF-8 and walks
+//! each number twice. `Scanner` takes whitespace-separated tokens straight
+//! out of a `BufRead`'s buffer, and `int` parses them from the bytes, which
+//! matters once the input is millions of numbers. It reads from `std::io`,
+//! so it is only built with the `std` feature.
+
+use std::format;
+use std::io::{self, BufRead};
+use std::str::{self, FromStr};
+use std::string::String;
+use std::vec::Vec;
+
+pub struct Scanner<R> {
+    reader: R,
+    /// The current token, kept to reuse its allocation
+    token: Vec<u8>,
+}
+
+impl<R: BufRead> Scanner<R> {
+    /// Wrap a buffered reader: `io::stdin().lock()`, a `BufReader` or a
+    /// `&[u8]`
+    pub fn new(reader: R) -> Self {
+        Scanner {
+            reader,
+            token: Vec::new(),
+        }
+    }
+
+    /// The next whitespace-separated token, or `None` at the end of the input
+    pub fn token(&mut self) -> io::Result<Option<&[u8]>> {
+        self.token.clear();
+        loop {
+            let buffer = self.reader.fill_buf()?;
+            if buffer.is_empty() {
+                break;
+            }
+            // Whitespace before the token; a token cut by the end of the
+            // buffer continues at the start of the next one
+            let skipped = if self.token.is_empty() {
+                buffer
+                    .iter()
+                    .take_while(|byte| byte.is_ascii_whitespace())
+                    .count()
+            } else {
+                0
+            };
+            let rest = &buffer[skipped..];
+            let length = rest
+                .iter()
+                .position(|byte| byte.is_ascii_whitespace())
+                .unwrap_or(rest.len());
+            self.token.extend_from_slice(&rest[..length]);
+            let ended = length < rest.len();
+            self.reader.consume(skipped + length);
+            if ended && !self.token.is_empty() {
+                break;
+            }
+        }
+        Ok((!self.token.is_empty()).then_some(self.token.as_slice()))
+    }
+
+    /// The next token as a `T`, for anything with `FromStr`
+    pub fn parse<T: FromStr>(&mut self) -> io::Result<T> {
+        let token = self.token()?.ok_or_else(end_of_input)?;
+        str::from_utf8(token)
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| not_a(token, core::any::type_name::<T>()))
+    }
+
+    /// The next token as an integer, parsed from its bytes without going
+    /// through `&str`
+    pub fn int(&mut self) -> io::Result<i64> {
+        let token = self.token()?.ok_or_else(end_of_input)?;
+        parse_int(token).ok_or_else(|| not_a(token, "i64"))
+    }
+
+    /// The next `count` tokens as integers
+    pub fn ints(&mut self, count: usize) -> io::Result<Vec<i64>> {
+        (0..count).map(|_| self.int()).collect()
+    }
+}
+
+/// `-12` or `+7` or `42`, or `None` when it is not an i64
+fn parse_int(bytes: &[u8]) -> Option<i64> {
+    let (negative, digits) = match bytes {
+        [b'-', rest @ ..] => (true, rest),
+        [b'+', rest @ ..] => (false, rest),
+        _ => (false, bytes),
+    };
+    if digits.is_empty() {
+        return None;
+    }
+    // Counted downwards, so i64::MIN, which has no positive twin, fits
+    let mut value: i64 = 0;
+    for &byte in digits {
+        if !byte.is_ascii_digit() {
+            return None;
+        }
+        value = value.checked_mul(10)?.checked_sub(i64::from(byte - b'0'))?;
+    }
+    if negative {
+        Some(value)
+    } else {
+        value.checked_neg()
+    }
+}
+
+fn end_of_input() -> io::Error {
+    io::Error::new(io::ErrorKind::UnexpectedEof, "the input ended early")
+}
+
+fn not_a(token: &[u8], type_name: &str) -> io::Error {
+    let message = format!(
+        "`{}` is not a valid {}",
+        String::from_utf8_lossy(token),
+        type_name
+    );
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    #[test]
+    fn test_tokens_across_buffer_boundaries() {
+        // A three-byte buffer splits most tokens between two fills
+        let input = "3\n  -120 45\t\n9223372036854775807 -9223372036854775808\nword 2.5\n";
+        let mut scanner = Scanner::new(BufReader::with_capacity(3, input.as_bytes()));
+
+        let count = scanner.int().unwrap() as usize;
+        assert_eq!(scanner.ints(count).unwrap(), [-120, 45, i64::MAX]);
+        assert_eq!(scanner.int().unwrap(), i64::MIN);
+        assert_eq!(scanner.parse::<String>().unwrap(), "word");
+        assert_eq!(scanner.parse::<f64>().unwrap(), 2.5);
+        assert_eq!(scanner.token().unwrap(), None);
+        assert_eq!(
+            scanner.int().unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+
+        let mut bad = Scanner::new("12x 99999999999999999999 -".as_bytes());
+        for _ in 0..3 {
+            assert_eq!(bad.int().unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+    }
+}
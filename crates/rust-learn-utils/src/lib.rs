@@ -0,0 +1,28 @@
+//! rust-learn utils
+//!
+//! Small, pure utilities that only need `core`: a connection state machine and
+//! retry backoff math. With the `alloc` feature there is also an LRU cache,
+//! and with `std` a fast token scanner for reading large inputs, a harness
+//! that measures how an operation's time grows with its input, and one that
+//! runs an operation on many threads at once to shake out races.
+//! Without `std`, nothing here reads the clock, prints, or allocates behind
+//! the caller's back, so the crate builds for targets without an operating
+//! system.
+
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+#[cfg(feature = "std")]
+pub mod complexity;
+pub mod fsm;
+#[cfg(feature = "std")]
+pub mod io;
+#[cfg(feature = "alloc")]
+pub mod lru;
+pub mod retry;
+#[cfg(feature = "std")]
+pub mod stress;
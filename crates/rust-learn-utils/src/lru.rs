@@ -0,0 +1,85 @@
+//! LRU Cache
+//!
+//! A least-recently-used cache with a fixed capacity. It needs a heap, so it
+//! is only built with the `alloc` feature, and it uses `alloc::vec::Vec`
+//! rather than `std::vec::Vec` (they are the same type; std re-exports it).
+//!
+//! Entries are kept in a `Vec`, most recently used last. Lookups are linear,
+//! which is fast for the small capacities this is meant for.
+
+use alloc::vec::Vec;
+
+pub struct LruCache<K, V> {
+    entries: Vec<(K, V)>,
+    capacity: usize,
+}
+
+impl<K: PartialEq, V> LruCache<K, V> {
+    /// A cache holding at most `capacity` entries (at least one). Nothing is
+    /// reserved up front, so any capacity is fine: `usize::MAX` is a cache
+    /// that never evicts
+    pub fn new(capacity: usize) -> Self {
+        LruCache {
+            entries: Vec::new(),
+            capacity: capacity.max(1),
+        }
+    }
+
+    /// Look up `key`, marking it as the most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let index = self.entries.iter().position(|(k, _)| k == key)?;
+        let entry = self.entries.remove(index);
+        self.entries.push(entry);
+        self.entries.last().map(|(_, value)| value)
+    }
+
+    /// Insert or replace `key`, returning the entry evicted to make room
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(index) = self.entries.iter().position(|(k, _)| *k == key) {
+            self.entries.remove(index);
+        }
+
+        let evicted = if self.entries.len() == self.capacity {
+            Some(self.entries.remove(0))
+        } else {
+            None
+        };
+        self.entries.push((key, value));
+        evicted
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Keys from least to most recently used
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.entries.iter().map(|(key, _)| key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_least_recently_used_is_evicted() {
+        let mut cache = LruCache::new(2);
+        cache.put("a", 1);
+        cache.put("b", 2);
+        assert_eq!(cache.get(&"a"), Some(&1));
+
+        // "b" is now the least recently used
+        assert_eq!(cache.put("c", 3), Some(("b", 2)));
+        assert_eq!(cache.get(&"b"), None);
+        assert_eq!(cache.keys().copied().collect::<Vec<_>>(), ["a", "c"]);
+
+        let mut unbounded = LruCache::new(usize::MAX);
+        assert_eq!(unbounded.put("a", 1), None);
+        assert_eq!(unbounded.len(), 1);
+    }
+}
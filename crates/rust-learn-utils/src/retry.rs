@@ -0,0 +1,78 @@
+//! Retry Policy Math
+//!
+//! Exponential backoff with a cap and optional jitter. The policy only
+//! computes delays: the caller sleeps (a thread, a tokio timer, or a hardware
+//! timer) and supplies the random number, so nothing here needs an OS.
+
+use core::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Delay before the first retry
+    pub base: Duration,
+    /// No delay is ever longer than this
+    pub max_delay: Duration,
+    /// Attempts in total, the first one included
+    pub max_attempts: u32,
+}
+
+impl RetryPolicy {
+    /// 100ms, doubling up to 10s, five attempts
+    pub const DEFAULT: RetryPolicy = RetryPolicy {
+        base: Duration::from_millis(100),
+        max_delay: Duration::from_secs(10),
+        max_attempts: 5,
+    };
+
+    /// How long to wait after failed attempt number `attempt` (counting from 1),
+    /// or `None` when no attempts are left
+    pub fn delay(&self, attempt: u32) -> Option<Duration> {
+        if attempt == 0 || attempt >= self.max_attempts {
+            return None;
+        }
+        // base * 2^(attempt - 1), without overflowing for large attempts
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        Some(self.base.saturating_mul(factor).min(self.max_delay))
+    }
+
+    /// `delay` scaled into its upper half by `random` (any `u64`), so that
+    /// many clients failing together do not all retry at the same moment
+    pub fn delay_with_jitter(&self, attempt: u32, random: u64) -> Option<Duration> {
+        let delay = self.delay(attempt)?;
+        let half = delay / 2;
+        let nanos = half.as_nanos() as u64;
+        let extra = if nanos == 0 { 0 } else { random % (nanos + 1) };
+        Some(half + Duration::from_nanos(extra))
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::DEFAULT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_doubles_up_to_the_cap() {
+        let policy = RetryPolicy {
+            base: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+            max_attempts: 6,
+        };
+
+        let delays = [1, 2, 3, 4, 5].map(|attempt| policy.delay(attempt).unwrap());
+        assert_eq!(
+            delays.map(|delay| delay.as_millis()),
+            [100, 200, 400, 500, 500]
+        );
+        assert_eq!(policy.delay(6), None);
+        assert_eq!(policy.delay(0), None);
+
+        let jittered = policy.delay_with_jitter(3, u64::MAX).unwrap();
+        assert!(jittered >= Duration::from_millis(200) && jittered <= Duration::from_millis(400));
+    }
+}
@@ -0,0 +1,174 @@
+//! Concurrency Stress Tests
+//!
+//! The compiler rules out data races on memory, but not races in behavior:
+//! a `load` then a `store` on an atomic, or a transfer that locks one account
+//! at a time, compiles fine and still loses updates when two threads
+//! interleave. `run_concurrently` makes those interleavings likely: it starts
+//! every thread, holds them at a `Barrier` so they begin the operation at the
+//! same moment, and runs it many times on each. `check` repeats that in
+//! fresh rounds and tests an invariant after each, since a race that shows
+//! up once in a thousand runs usually hides in any single one.
+//!
+//! The threads are scoped, so the operation can borrow the state it shares.
+//! A panic in the operation is passed on once every thread has finished.
+//! Only built with the `std` feature: it needs threads.
+
+use core::fmt;
+use core::time::Duration;
+use std::string::String;
+use std::sync::{Barrier, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+/// What one `run_concurrently` did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Run {
+    pub threads: usize,
+    /// Runs of the operation per thread
+    pub iterations: usize,
+    /// From the barrier opening to the last thread finishing
+    pub elapsed: Duration,
+}
+
+impl Run {
+    /// Runs of the operation on all threads together
+    pub fn operations(&self) -> usize {
+        self.threads * self.iterations
+    }
+}
+
+/// Call `op(thread, iteration)` `iterations` times on each of `n_threads`
+/// threads, which all wait at a barrier first so they start together
+pub fn run_concurrently<F>(n_threads: usize, iterations: usize, op: F) -> Run
+where
+    F: Fn(usize, usize) + Sync,
+{
+    let threads = n_threads.max(1);
+    // One more party than threads, so none starts before all exist. Whichever
+    // arrives last opens the barrier and starts the clock; this thread may
+    // only be woken after the others have finished
+    let barrier = Barrier::new(threads + 1);
+    let start = OnceLock::new();
+    let wait = || {
+        if barrier.wait().is_leader() {
+            let _ = start.set(Instant::now());
+        }
+    };
+    let (op, wait) = (&op, &wait);
+    thread::scope(|scope| {
+        for thread in 0..threads {
+            scope.spawn(move || {
+                wait();
+                for iteration in 0..iterations {
+                    op(thread, iteration);
+                }
+            });
+        }
+        wait();
+    });
+    // The scope has joined every thread: this is after the last one finished
+    let elapsed = start.get().map(Instant::elapsed).unwrap_or_default();
+    Run {
+        threads,
+        iterations,
+        elapsed,
+    }
+}
+
+/// An invariant that did not hold after a round
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Which round, from 0; the rounds before it passed
+    pub round: usize,
+    /// What the invariant said was wrong
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "round {}: {}", self.round, self.message)
+    }
+}
+
+impl std::error::Error for Violation {}
+
+/// Run `rounds` rounds: each makes fresh state with `setup`, stresses it
+/// with `run_concurrently` (`op` gets the state, the thread and the
+/// iteration), then tests it with `invariant`. The first round whose
+/// invariant fails stops the rest; otherwise the last round's `Run` is
+/// returned
+pub fn check<S, F, I>(
+    rounds: usize,
+    n_threads: usize,
+    iterations: usize,
+    mut setup: impl FnMut() -> S,
+    op: F,
+    invariant: I,
+) -> Result<Run, Violation>
+where
+    S: Sync,
+    F: Fn(&S, usize, usize) + Sync,
+    I: Fn(&S) -> Result<(), String>,
+{
+    let mut last = Run {
+        threads: n_threads.max(1),
+        iterations,
+        elapsed: Duration::ZERO,
+    };
+    for round in 0..rounds.max(1) {
+        let state = setup();
+        last = run_concurrently(n_threads, iterations, |thread, iteration| {
+            op(&state, thread, iteration)
+        });
+        invariant(&state).map_err(|message| Violation { round, message })?;
+    }
+    Ok(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::format;
+    use std::string::ToString;
+    use std::sync::Mutex;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::vec;
+
+    #[test]
+    fn test_every_thread_runs_every_iteration() {
+        let count = AtomicUsize::new(0);
+        let seen = Mutex::new(vec![0; 4]);
+        let run = run_concurrently(4, 250, |thread, _| {
+            count.fetch_add(1, Ordering::Relaxed);
+            seen.lock().unwrap()[thread] += 1;
+        });
+        assert_eq!(run.operations(), 1_000);
+        assert_eq!(count.into_inner(), 1_000);
+        assert_eq!(seen.into_inner().unwrap(), [250; 4]);
+        assert_eq!(run_concurrently(0, 3, |_, _| {}).threads, 1);
+    }
+
+    #[test]
+    fn test_check_reports_the_first_round_that_breaks() {
+        let counted = |expected: usize| {
+            move |count: &AtomicUsize| {
+                let count = count.load(Ordering::Relaxed);
+                if count == expected {
+                    Ok(())
+                } else {
+                    Err(format!("{} increments, expected {}", count, expected))
+                }
+            }
+        };
+        let increment = |count: &AtomicUsize, _: usize, _: usize| {
+            count.fetch_add(1, Ordering::Relaxed);
+        };
+        let run = check(3, 4, 100, || AtomicUsize::new(0), increment, counted(400));
+        assert_eq!(run.unwrap().operations(), 400);
+
+        let violation =
+            check(3, 4, 100, || AtomicUsize::new(0), increment, counted(1)).unwrap_err();
+        assert_eq!(violation.round, 0);
+        assert_eq!(violation.to_string(), "round 0: 400 increments, expected 1");
+    }
+}
@@ -0,0 +1,40 @@
+// options_type1.rs
+//
+// The ice cream shop has five scoops left until 22:00, then none. Outside
+// 0..=23 there is no answer at all: return None.
+//
+// The options lesson covers this: rust-learn run options_type
+//
+// Delete the `// I AM NOT DONE` line when the tests pass.
+
+// I AM NOT DONE
+
+fn scoops_left(hour: u16) -> Option<u16> {
+    todo!("Some(5), Some(0) or None, depending on `hour`")
+}
+
+fn main() {
+    println!("{:?}", scoops_left(12));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_ten() {
+        assert_eq!(scoops_left(0), Some(5));
+        assert_eq!(scoops_left(21), Some(5));
+    }
+
+    #[test]
+    fn after_ten() {
+        assert_eq!(scoops_left(22), Some(0));
+        assert_eq!(scoops_left(23), Some(0));
+    }
+
+    #[test]
+    fn not_an_hour() {
+        assert_eq!(scoops_left(24), None);
+    }
+}
@@ -0,0 +1,36 @@
+// options_type2.rs
+//
+// `if let` and `while let` only run their block when the pattern matches.
+// Fix the two patterns so the tests pass.
+//
+// The options lesson covers this: rust-learn run options_type
+//
+// Delete the `// I AM NOT DONE` line when the tests pass.
+
+// I AM NOT DONE
+
+fn main() {}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn simple_option() {
+        let target = "rustacean";
+        let optional_target = Some(target);
+
+        if let word = optional_target {
+            assert_eq!(word, target);
+        }
+    }
+
+    #[test]
+    fn pop_until_empty() {
+        let mut stack = vec![1, 2, 3];
+        let mut popped = Vec::new();
+
+        while let number = stack.pop() {
+            popped.push(number);
+        }
+        assert_eq!(popped, [3, 2, 1]);
+    }
+}
@@ -0,0 +1,39 @@
+// Check the hours from the top, as you would say it: before 22, between 22
+// and 23, anything else. Each branch returns, so the order of the checks
+// carries the meaning.
+
+fn scoops_left(hour: u16) -> Option<u16> {
+    if hour < 22 {
+        Some(5)
+    } else if hour < 24 {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+fn main() {
+    println!("{:?}", scoops_left(12));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_ten() {
+        assert_eq!(scoops_left(0), Some(5));
+        assert_eq!(scoops_left(21), Some(5));
+    }
+
+    #[test]
+    fn after_ten() {
+        assert_eq!(scoops_left(22), Some(0));
+        assert_eq!(scoops_left(23), Some(0));
+    }
+
+    #[test]
+    fn not_an_hour() {
+        assert_eq!(scoops_left(24), None);
+    }
+}
@@ -0,0 +1,33 @@
+// `bool::then` turns the "is it an hour at all?" question into the Option,
+// leaving one plain if for the number. Compact, though the two conditions
+// now live in different places.
+
+fn scoops_left(hour: u16) -> Option<u16> {
+    (hour < 24).then(|| if hour < 22 { 5 } else { 0 })
+}
+
+fn main() {
+    println!("{:?}", scoops_left(12));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_ten() {
+        assert_eq!(scoops_left(0), Some(5));
+        assert_eq!(scoops_left(21), Some(5));
+    }
+
+    #[test]
+    fn after_ten() {
+        assert_eq!(scoops_left(22), Some(0));
+        assert_eq!(scoops_left(23), Some(0));
+    }
+
+    #[test]
+    fn not_an_hour() {
+        assert_eq!(scoops_left(24), None);
+    }
+}
@@ -0,0 +1,37 @@
+// A match on ranges names every interval outright, so the arms read like
+// the opening hours and can go in any order. The compiler checks that the
+// arms, with the `_` for the rest, cover every u16.
+
+fn scoops_left(hour: u16) -> Option<u16> {
+    match hour {
+        0..=21 => Some(5),
+        22..=23 => Some(0),
+        _ => None,
+    }
+}
+
+fn main() {
+    println!("{:?}", scoops_left(12));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn before_ten() {
+        assert_eq!(scoops_left(0), Some(5));
+        assert_eq!(scoops_left(21), Some(5));
+    }
+
+    #[test]
+    fn after_ten() {
+        assert_eq!(scoops_left(22), Some(0));
+        assert_eq!(scoops_left(23), Some(0));
+    }
+
+    #[test]
+    fn not_an_hour() {
+        assert_eq!(scoops_left(24), None);
+    }
+}
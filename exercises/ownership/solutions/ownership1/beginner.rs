@@ -0,0 +1,10 @@
+// Clone the string, so `copy` owns a second String and `greeting` keeps its
+// own. It is the fix most people find first and it always works, at the
+// cost of a second allocation and a copy of the text.
+
+fn main() {
+    let greeting = String::from("hello");
+    let copy = greeting.clone();
+    println!("{} from greeting", greeting);
+    println!("{} from copy", copy);
+}
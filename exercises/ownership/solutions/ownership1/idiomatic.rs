@@ -0,0 +1,10 @@
+// Nothing here needs a second owner: a reference lets both names read the
+// one String, with no copy at all. Reach for `clone` when the two really
+// must change or outlive each other separately.
+
+fn main() {
+    let greeting = String::from("hello");
+    let copy = &greeting;
+    println!("{} from greeting", greeting);
+    println!("{} from copy", copy);
+}
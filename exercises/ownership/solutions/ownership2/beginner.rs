@@ -0,0 +1,25 @@
+// Move the vector into a new, mutable binding, then change that one. The
+// vector itself is never copied: only who may change it changes.
+
+fn fill_vec(vec: Vec<i32>) -> Vec<i32> {
+    let mut vec = vec;
+    vec.push(88);
+    vec
+}
+
+fn main() {
+    let numbers = fill_vec(vec![22, 44, 66]);
+    println!("{:?}", numbers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_the_vector() {
+        let original = vec![22, 44, 66];
+        let filled = fill_vec(original);
+        assert_eq!(filled, [22, 44, 66, 88]);
+    }
+}
@@ -0,0 +1,25 @@
+// `mut` can go on the parameter itself: the function owns the vector, so
+// it decides whether its own binding is mutable. Callers see the same
+// signature, `Vec<i32>` in and out.
+
+fn fill_vec(mut vec: Vec<i32>) -> Vec<i32> {
+    vec.push(88);
+    vec
+}
+
+fn main() {
+    let numbers = fill_vec(vec![22, 44, 66]);
+    println!("{:?}", numbers);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_the_vector() {
+        let original = vec![22, 44, 66];
+        let filled = fill_vec(original);
+        assert_eq!(filled, [22, 44, 66, 88]);
+    }
+}
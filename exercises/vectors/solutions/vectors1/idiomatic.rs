@@ -0,0 +1,25 @@
+// Build the vector from the array, so there is one list of numbers.
+// `to_vec` copies the elements into a new Vec and leaves the array usable;
+// `Vec::from(array)` does the same.
+
+fn array_and_vec() -> ([i32; 4], Vec<i32>) {
+    let array = [10, 20, 30, 40];
+    let vector = array.to_vec();
+
+    (array, vector)
+}
+
+fn main() {
+    println!("{:?}", array_and_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_numbers() {
+        let (array, vector) = array_and_vec();
+        assert_eq!(array, vector[..]);
+    }
+}
@@ -0,0 +1,26 @@
+// Fill in the loop: `number` is a &i32, and `*` on it works through the
+// reference. Clear and easy to step through; the vector grows as it goes,
+// reallocating a few times on the way.
+
+fn doubled(numbers: &[i32]) -> Vec<i32> {
+    let mut output = Vec::new();
+    for number in numbers {
+        output.push(number * 2);
+    }
+    output
+}
+
+fn main() {
+    println!("{:?}", doubled(&[1, 2, 3]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_each_number() {
+        assert_eq!(doubled(&[2, 4, 6, 8, 10]), [4, 8, 12, 16, 20]);
+        assert_eq!(doubled(&[]), []);
+    }
+}
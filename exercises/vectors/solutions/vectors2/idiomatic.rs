@@ -0,0 +1,22 @@
+// Say what the result is rather than how to build it: each number, times 2,
+// collected. `collect` knows how many items a slice's `map` yields, so it
+// allocates once, the right size.
+
+fn doubled(numbers: &[i32]) -> Vec<i32> {
+    numbers.iter().map(|number| number * 2).collect()
+}
+
+fn main() {
+    println!("{:?}", doubled(&[1, 2, 3]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_each_number() {
+        assert_eq!(doubled(&[2, 4, 6, 8, 10]), [4, 8, 12, 16, 20]);
+        assert_eq!(doubled(&[]), []);
+    }
+}
@@ -0,0 +1,26 @@
+// Keep the loop but allocate once, up front, with the exact capacity: no
+// reallocation however long the input. This is what `collect` does here on
+// its own; write it out when the loop does more than one push per item.
+
+fn doubled(numbers: &[i32]) -> Vec<i32> {
+    let mut output = Vec::with_capacity(numbers.len());
+    for number in numbers {
+        output.push(number * 2);
+    }
+    output
+}
+
+fn main() {
+    println!("{:?}", doubled(&[1, 2, 3]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_each_number() {
+        assert_eq!(doubled(&[2, 4, 6, 8, 10]), [4, 8, 12, 16, 20]);
+        assert_eq!(doubled(&[]), []);
+    }
+}
@@ -0,0 +1,31 @@
+// vectors1.rs
+//
+// Build a vector holding the same numbers as the array, so the test passes.
+//
+// The vectors lesson covers this: rust-learn run vectors
+//
+// Delete the `// I AM NOT DONE` line when the test passes.
+
+// I AM NOT DONE
+
+fn array_and_vec() -> ([i32; 4], Vec<i32>) {
+    let array = [10, 20, 30, 40];
+    let vector = // TODO: the same numbers, in a Vec
+
+    (array, vector)
+}
+
+fn main() {
+    println!("{:?}", array_and_vec());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_numbers() {
+        let (array, vector) = array_and_vec();
+        assert_eq!(array, vector[..]);
+    }
+}
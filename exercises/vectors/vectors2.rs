@@ -0,0 +1,33 @@
+// vectors2.rs
+//
+// `doubled` should return every number twice as big, in order. Write the
+// loop body, then try it again with `iter().map(..).collect()`.
+//
+// The vectors lesson covers this: rust-learn run vectors
+//
+// Delete the `// I AM NOT DONE` line when the test passes.
+
+// I AM NOT DONE
+
+fn doubled(numbers: &[i32]) -> Vec<i32> {
+    let mut output = Vec::new();
+    for number in numbers {
+        // TODO: push `number` times 2 onto `output`
+    }
+    output
+}
+
+fn main() {
+    println!("{:?}", doubled(&[1, 2, 3]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubles_each_number() {
+        assert_eq!(doubled(&[2, 4, 6, 8, 10]), [4, 8, 12, 16, 20]);
+        assert_eq!(doubled(&[]), []);
+    }
+}